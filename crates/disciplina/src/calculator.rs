@@ -4,10 +4,22 @@
 //! sizing formula with mathematical precision using decimal arithmetic.
 
 use crate::errors::PositionSizingError;
-use crate::types::{AccountEquity, RiskPercentage, PricePoint, PositionSize};
+use crate::types::{AccountEquity, ConvictionMultiplier, FeeAdjustedPositionSize, RiskPercentage, PricePoint, PositionSize, PositionSide};
 use rust_decimal::Decimal;
 use tracing::{debug, instrument, warn};
 
+/// Highest fee rate accepted on either side of a trade (10%)
+const MAX_FEE_RATE: Decimal = Decimal::from_parts(1, 0, 0, false, 1);
+
+/// Largest difference between primary and independent results treated as equal
+const VERIFICATION_EPSILON: Decimal = Decimal::from_parts(1, 0, 0, false, 20);
+
+/// Coarsest configurable verification tolerance (1e-8 units)
+///
+/// Finer than any exchange lot step, so a difference within it can never
+/// change the quantity actually ordered.
+pub const MAX_VERIFICATION_TOLERANCE: Decimal = Decimal::from_parts(1, 0, 0, false, 8);
+
 /// Core position sizing calculator implementing Van Tharp methodology
 ///
 /// The calculator uses the formula:
@@ -17,6 +29,10 @@ use tracing::{debug, instrument, warn};
 /// 
 /// All calculations use decimal arithmetic to prevent floating-point precision errors
 /// that could result in incorrect position sizes.
+///
+/// Sizing is pure, synchronous computation with no I/O, so it can be called
+/// directly from synchronous code such as rule assessment. The calculator is
+/// `Copy`; hold it by value rather than behind an `Arc`.
 /// 
 /// # Examples
 /// 
@@ -42,10 +58,14 @@ use tracing::{debug, instrument, warn};
 /// assert_eq!(position_size.value(), Decimal::from(40));
 /// # Ok::<(), Box<dyn std::error::Error>>(())
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy)]
 pub struct PositionSizingCalculator {
     /// Optional precision override for calculations (defaults to 28 decimal places)
     precision: Option<u32>,
+    /// Re-derive every Van Tharp result independently and fail on a mismatch
+    self_verify: bool,
+    /// Largest discrepancy verification accepts (defaults to 1e-20)
+    verification_tolerance: Decimal,
 }
 
 impl PositionSizingCalculator {
@@ -63,6 +83,8 @@ impl PositionSizingCalculator {
     pub fn new() -> Self {
         Self {
             precision: None,
+            self_verify: false,
+            verification_tolerance: VERIFICATION_EPSILON,
         }
     }
 
@@ -80,9 +102,61 @@ impl PositionSizingCalculator {
     pub fn with_precision(precision: u32) -> Self {
         Self {
             precision: Some(precision),
+            self_verify: false,
+            verification_tolerance: VERIFICATION_EPSILON,
         }
     }
 
+    /// Enables or disables self-verification of every position size calculation
+    ///
+    /// When enabled, `calculate_position_size` checks its result with
+    /// [`verify_calculation`](Self::verify_calculation) and returns
+    /// `VerificationFailure` rather than a size the two methods disagree on.
+    ///
+    /// # Examples
+    /// ```
+    /// use disciplina::PositionSizingCalculator;
+    ///
+    /// let calculator = PositionSizingCalculator::new().with_self_verification(true);
+    /// ```
+    pub fn with_self_verification(mut self, enabled: bool) -> Self {
+        self.self_verify = enabled;
+        self
+    }
+
+    /// Sets the largest discrepancy verification treats as agreement
+    ///
+    /// Lets callers absorb rounding noise between the two sizing methods.
+    /// The tolerance is capped at [`MAX_VERIFICATION_TOLERANCE`], so any
+    /// difference large enough to change an order still fails.
+    ///
+    /// # Arguments
+    /// * `epsilon` - Accepted absolute difference in position units, in [0, 1e-8]
+    ///
+    /// # Returns
+    /// * `Ok(PositionSizingCalculator)` - The calculator with the new tolerance
+    /// * `Err(PositionSizingError)` - If the tolerance is negative or above the cap
+    ///
+    /// # Examples
+    /// ```
+    /// use disciplina::PositionSizingCalculator;
+    /// use rust_decimal::Decimal;
+    ///
+    /// let calculator = PositionSizingCalculator::new().with_verification_tolerance(Decimal::new(1, 12))?;
+    /// assert!(PositionSizingCalculator::new().with_verification_tolerance(Decimal::new(1, 2)).is_err());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn with_verification_tolerance(mut self, epsilon: Decimal) -> Result<Self, PositionSizingError> {
+        if epsilon < Decimal::ZERO || epsilon > MAX_VERIFICATION_TOLERANCE {
+            return Err(PositionSizingError::invalid_verification_tolerance(
+                epsilon,
+                MAX_VERIFICATION_TOLERANCE,
+            ));
+        }
+        self.verification_tolerance = epsilon;
+        Ok(self)
+    }
+
     /// Calculates position size using Van Tharp methodology
     /// 
     /// This is the core method that implements the Van Tharp position sizing formula:
@@ -161,6 +235,492 @@ impl PositionSizingCalculator {
             ));
         }
 
+        let position_size =
+            self.size_for_stop_distance(account_equity, risk_percentage, entry_price, stop_distance)?;
+
+        if self.self_verify
+            && !self.verify_calculation(account_equity, risk_percentage, entry_price, stop_loss, position_size)?
+        {
+            let independent = self.independent_position_size(account_equity, risk_percentage, entry_price, stop_loss)?;
+            warn!(
+                primary = %position_size.value(),
+                independent = %independent,
+                "Position size failed independent verification"
+            );
+            return Err(PositionSizingError::verification_failure(position_size.value(), independent));
+        }
+
+        Ok(position_size)
+    }
+
+    /// Cross-checks a position size against an independent recalculation
+    ///
+    /// The size is re-derived from the raw inputs, risk amount first
+    /// (`risk_amount = equity × risk %`, then `size = risk_amount ÷ stop_distance`),
+    /// without the primary path's shared helpers or precision handling. The
+    /// results agree when their [discrepancy](Self::verification_discrepancy)
+    /// is within the configured tolerance, or within half a unit in the last
+    /// place when a precision is configured.
+    ///
+    /// # Returns
+    /// * `Ok(true)` - The two methods agree
+    /// * `Ok(false)` - The results differ
+    /// * `Err(PositionSizingError)` - If the inputs cannot be sized at all
+    ///
+    /// # Examples
+    /// ```
+    /// use disciplina::{PositionSizingCalculator, AccountEquity, RiskPercentage, PricePoint, PositionSize};
+    /// use rust_decimal::Decimal;
+    ///
+    /// let calculator = PositionSizingCalculator::new();
+    /// let (equity, risk) = (AccountEquity::new(Decimal::from(10000))?, RiskPercentage::new(Decimal::new(2, 2))?);
+    /// let (entry, stop) = (PricePoint::new(Decimal::from(100))?, PricePoint::new(Decimal::from(95))?);
+    ///
+    /// let size = calculator.calculate_position_size(equity, risk, entry, stop)?;
+    /// assert!(calculator.verify_calculation(equity, risk, entry, stop, size)?);
+    /// assert!(!calculator.verify_calculation(equity, risk, entry, stop, PositionSize::new(Decimal::from(41))?)?);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn verify_calculation(
+        &self,
+        account_equity: AccountEquity,
+        risk_percentage: RiskPercentage,
+        entry_price: PricePoint,
+        stop_loss: PricePoint,
+        position_size: PositionSize,
+    ) -> Result<bool, PositionSizingError> {
+        let discrepancy =
+            self.verification_discrepancy(account_equity, risk_percentage, entry_price, stop_loss, position_size)?;
+        let tolerance = match self.precision {
+            Some(precision) => self.verification_tolerance.max(Decimal::new(5, precision + 1)),
+            None => self.verification_tolerance,
+        };
+
+        Ok(discrepancy <= tolerance)
+    }
+
+    /// Absolute difference between a position size and its independent recalculation
+    ///
+    /// The measure behind [`verify_calculation`](Self::verify_calculation),
+    /// for callers that want to log or judge the difference themselves.
+    ///
+    /// # Returns
+    /// * `Ok(Decimal)` - The discrepancy in position units; zero when the methods agree exactly
+    /// * `Err(PositionSizingError)` - If the inputs cannot be sized at all
+    ///
+    /// # Examples
+    /// ```
+    /// use disciplina::{PositionSizingCalculator, AccountEquity, RiskPercentage, PricePoint, PositionSize};
+    /// use rust_decimal::Decimal;
+    ///
+    /// let calculator = PositionSizingCalculator::new();
+    /// let (equity, risk) = (AccountEquity::new(Decimal::from(10000))?, RiskPercentage::new(Decimal::new(2, 2))?);
+    /// let (entry, stop) = (PricePoint::new(Decimal::from(100))?, PricePoint::new(Decimal::from(95))?);
+    ///
+    /// let discrepancy = calculator.verification_discrepancy(equity, risk, entry, stop, PositionSize::new(Decimal::from(41))?)?;
+    /// assert_eq!(discrepancy, Decimal::ONE);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn verification_discrepancy(
+        &self,
+        account_equity: AccountEquity,
+        risk_percentage: RiskPercentage,
+        entry_price: PricePoint,
+        stop_loss: PricePoint,
+        position_size: PositionSize,
+    ) -> Result<Decimal, PositionSizingError> {
+        let independent = self.independent_position_size(account_equity, risk_percentage, entry_price, stop_loss)?;
+        Ok((position_size.value() - independent).abs())
+    }
+
+    /// Van Tharp size at full precision, computed directly from the inputs
+    fn independent_position_size(
+        &self,
+        account_equity: AccountEquity,
+        risk_percentage: RiskPercentage,
+        entry_price: PricePoint,
+        stop_loss: PricePoint,
+    ) -> Result<Decimal, PositionSizingError> {
+        let stop_distance = entry_price.value() - stop_loss.value();
+        if stop_distance <= Decimal::ZERO {
+            return Err(PositionSizingError::invalid_stop_distance(entry_price.value(), stop_loss.value()));
+        }
+
+        account_equity
+            .value()
+            .checked_mul(risk_percentage.value())
+            .and_then(|risk_amount| risk_amount.checked_div(stop_distance))
+            .ok_or(PositionSizingError::CalculationOverflow)
+    }
+
+    /// Calculates position size with trading fees counted as part of the risk
+    ///
+    /// A stopped-out trade loses the stop distance plus the fee paid on entry
+    /// and the fee paid on the stop exit. Dividing the risk budget by that
+    /// full per-unit loss keeps the true dollar risk, fees included, at or
+    /// under the risk percentage:
+    /// Net Size = Risk Amount ÷ (Stop Distance + Entry × Entry Fee + Stop × Exit Fee)
+    ///
+    /// # Arguments
+    /// * `account_equity` - Total account balance available for trading
+    /// * `risk_percentage` - Risk per trade as decimal (e.g., 0.02 for 2%)
+    /// * `entry_price` - Planned entry price for the position
+    /// * `stop_loss` - Stop loss price (must be below entry for long positions)
+    /// * `entry_fee_rate` - Fee on the entry's notional (e.g., 0.001 for 0.1%), in [0, 0.1]
+    /// * `exit_fee_rate` - Fee on the stop exit's notional, in [0, 0.1]
+    ///
+    /// # Returns
+    /// * `Ok(FeeAdjustedPositionSize)` - Gross and fee-adjusted sizes and the estimated fees
+    /// * `Err(PositionSizingError)` - If inputs are invalid or calculation fails
+    ///
+    /// # Examples
+    /// ```
+    /// use disciplina::{PositionSizingCalculator, AccountEquity, RiskPercentage, PricePoint};
+    /// use rust_decimal::Decimal;
+    /// use std::str::FromStr;
+    ///
+    /// let calculator = PositionSizingCalculator::new();
+    /// let sizing = calculator.calculate_position_size_with_fees(
+    ///     AccountEquity::new(Decimal::from(10000))?,
+    ///     RiskPercentage::new(Decimal::from_str("0.02")?)?, // 2%
+    ///     PricePoint::new(Decimal::from(100))?,
+    ///     PricePoint::new(Decimal::from(95))?,
+    ///     Decimal::from_str("0.001")?, // 0.1% taker fee
+    ///     Decimal::from_str("0.001")?,
+    /// )?;
+    ///
+    /// // Expected: 200 / (5 + 0.1 + 0.095) = 38.50 instead of 200 / 5 = 40
+    /// assert_eq!(sizing.gross_size.value(), Decimal::from(40));
+    /// assert_eq!(sizing.net_size.rounded(2), Decimal::from_str("38.50")?);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn calculate_position_size_with_fees(
+        &self,
+        account_equity: AccountEquity,
+        risk_percentage: RiskPercentage,
+        entry_price: PricePoint,
+        stop_loss: PricePoint,
+        entry_fee_rate: Decimal,
+        exit_fee_rate: Decimal,
+    ) -> Result<FeeAdjustedPositionSize, PositionSizingError> {
+        for fee_rate in [entry_fee_rate, exit_fee_rate] {
+            if fee_rate < Decimal::ZERO || fee_rate > MAX_FEE_RATE {
+                return Err(PositionSizingError::invalid_fee_rate(fee_rate));
+            }
+        }
+
+        let gross_size =
+            self.calculate_position_size(account_equity, risk_percentage, entry_price, stop_loss)?;
+
+        let fees_per_unit = entry_price
+            .value()
+            .checked_mul(entry_fee_rate)
+            .zip(stop_loss.value().checked_mul(exit_fee_rate))
+            .and_then(|(entry_fee, exit_fee)| entry_fee.checked_add(exit_fee))
+            .ok_or(PositionSizingError::CalculationOverflow)?;
+        let loss_per_unit = (entry_price.value() - stop_loss.value())
+            .checked_add(fees_per_unit)
+            .ok_or(PositionSizingError::CalculationOverflow)?;
+
+        let net_size =
+            self.size_for_stop_distance(account_equity, risk_percentage, entry_price, loss_per_unit)?;
+
+        Ok(FeeAdjustedPositionSize {
+            gross_size,
+            net_size,
+            estimated_fees: net_size.value() * fees_per_unit,
+        })
+    }
+
+    /// Calculates position size rounded down to an exchange lot step
+    ///
+    /// The Van Tharp size is computed at full precision, ignoring any
+    /// configured precision (which rounds to nearest and could round up), and
+    /// then floored to a multiple of `step_size`. The rounded position
+    /// therefore never risks more than the risk budget.
+    ///
+    /// # Arguments
+    /// * `account_equity` - Total account balance available for trading
+    /// * `risk_percentage` - Risk per trade as decimal (e.g., 0.02 for 2%)
+    /// * `entry_price` - Planned entry price for the position
+    /// * `stop_loss` - Stop loss price (must be below entry for long positions)
+    /// * `step_size` - Smallest quantity increment the exchange accepts
+    ///
+    /// # Returns
+    /// * `Ok(PositionSize)` - Position size as a whole number of steps
+    /// * `Err(PositionSizingError)` - If inputs are invalid or the size is below one step
+    ///
+    /// # Examples
+    /// ```
+    /// use disciplina::{PositionSizingCalculator, AccountEquity, RiskPercentage, PricePoint};
+    /// use rust_decimal::Decimal;
+    /// use std::str::FromStr;
+    ///
+    /// let calculator = PositionSizingCalculator::new();
+    /// let result = calculator.calculate_position_size_rounded(
+    ///     AccountEquity::new(Decimal::from(10000))?,
+    ///     RiskPercentage::new(Decimal::from_str("0.02")?)?, // 2%
+    ///     PricePoint::new(Decimal::from(50000))?,
+    ///     PricePoint::new(Decimal::from(48500))?, // $1500 stop distance
+    ///     Decimal::from_str("0.001")?, // BTC lot step
+    /// )?;
+    ///
+    /// // Expected: 200 / 1500 = 0.1333..., floored to 0.133
+    /// assert_eq!(result.value(), Decimal::from_str("0.133")?);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn calculate_position_size_rounded(
+        &self,
+        account_equity: AccountEquity,
+        risk_percentage: RiskPercentage,
+        entry_price: PricePoint,
+        stop_loss: PricePoint,
+        step_size: Decimal,
+    ) -> Result<PositionSize, PositionSizingError> {
+        if step_size <= Decimal::ZERO {
+            return Err(PositionSizingError::invalid_step_size(step_size));
+        }
+
+        let full_precision = Self { precision: None, ..*self };
+        full_precision
+            .calculate_position_size(account_equity, risk_percentage, entry_price, stop_loss)?
+            .round_to_step(step_size)
+    }
+
+    /// Calculates position size risking a fraction of the base risk percentage
+    ///
+    /// The Van Tharp size is scaled by the conviction multiplier, so a 0.5×
+    /// trade risks half of `risk_percentage`. The multiplier is capped at 1×,
+    /// so the trade never risks more than the validated base risk.
+    ///
+    /// # Arguments
+    /// * `account_equity` - Total account balance available for trading
+    /// * `risk_percentage` - Base risk per trade as decimal (e.g., 0.02 for 2%)
+    /// * `entry_price` - Planned entry price for the position
+    /// * `stop_loss` - Stop loss price (must be below entry for long positions)
+    /// * `conviction` - Fraction of the base risk to commit (0.25× to 1×)
+    ///
+    /// # Returns
+    /// * `Ok(PositionSize)` - Position size risking `risk_percentage × conviction`
+    /// * `Err(PositionSizingError)` - If inputs are invalid or calculation fails
+    ///
+    /// # Examples
+    /// ```
+    /// use disciplina::{PositionSizingCalculator, AccountEquity, RiskPercentage, PricePoint, ConvictionMultiplier};
+    /// use rust_decimal::Decimal;
+    /// use std::str::FromStr;
+    ///
+    /// let calculator = PositionSizingCalculator::new();
+    /// let result = calculator.calculate_position_size_with_conviction(
+    ///     AccountEquity::new(Decimal::from(10000))?,
+    ///     RiskPercentage::new(Decimal::from_str("0.02")?)?, // 2%
+    ///     PricePoint::new(Decimal::from(100))?,
+    ///     PricePoint::new(Decimal::from(95))?,
+    ///     ConvictionMultiplier::new(Decimal::from_str("0.5")?)?, // half conviction
+    /// )?;
+    ///
+    /// // Expected: (10000 * 0.02 * 0.5) / 5 = 20 shares
+    /// assert_eq!(result.value(), Decimal::from(20));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn calculate_position_size_with_conviction(
+        &self,
+        account_equity: AccountEquity,
+        risk_percentage: RiskPercentage,
+        entry_price: PricePoint,
+        stop_loss: PricePoint,
+        conviction: ConvictionMultiplier,
+    ) -> Result<PositionSize, PositionSizingError> {
+        let full_precision = Self { precision: None, ..*self };
+        let base_size =
+            full_precision.calculate_position_size(account_equity, risk_percentage, entry_price, stop_loss)?;
+
+        let scaled_size = base_size.value() * conviction.value();
+        let final_position_size = match self.precision {
+            Some(precision) => scaled_size.round_dp(precision),
+            None => scaled_size,
+        };
+
+        debug!(
+            base_position_size = %base_size.value(),
+            conviction = %conviction,
+            calculated_position_size = %final_position_size,
+            "Conviction-scaled position size calculation completed"
+        );
+
+        PositionSize::new(final_position_size)
+    }
+
+    /// Calculates position size with the stop placed a multiple of ATR from entry
+    ///
+    /// The stop is `entry - atr × atr_multiplier` for longs and
+    /// `entry + atr × atr_multiplier` for shorts; the size then follows the
+    /// usual Van Tharp formula over that distance.
+    ///
+    /// # Arguments
+    /// * `account_equity` - Total account balance available for trading
+    /// * `risk_percentage` - Risk per trade as decimal (e.g., 0.02 for 2%)
+    /// * `entry_price` - Planned entry price for the position
+    /// * `atr` - Average true range of the instrument, in price units
+    /// * `atr_multiplier` - Number of ATRs between entry and stop (must be positive)
+    /// * `side` - Direction of the position
+    ///
+    /// # Returns
+    /// * `Ok((PositionSize, PricePoint))` - Calculated position size and the stop to place
+    /// * `Err(PositionSizingError)` - If inputs are invalid or calculation fails
+    ///
+    /// # Examples
+    /// ```
+    /// use disciplina::{PositionSizingCalculator, AccountEquity, RiskPercentage, PricePoint, PositionSide};
+    /// use rust_decimal::Decimal;
+    /// use std::str::FromStr;
+    ///
+    /// let calculator = PositionSizingCalculator::new();
+    /// let (position_size, stop_loss) = calculator.calculate_position_size_from_atr(
+    ///     AccountEquity::new(Decimal::from(10000))?,
+    ///     RiskPercentage::new(Decimal::from_str("0.02")?)?, // 2%
+    ///     PricePoint::new(Decimal::from(100))?,
+    ///     Decimal::from(2), // ATR of $2
+    ///     Decimal::from_str("2.5")?, // stop 2.5 ATR below entry
+    ///     PositionSide::Long,
+    /// )?;
+    ///
+    /// // Expected: stop at 100 - 2 * 2.5 = 95, size (10000 * 0.02) / 5 = 40
+    /// assert_eq!(stop_loss.value(), Decimal::from(95));
+    /// assert_eq!(position_size.value(), Decimal::from(40));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[instrument(level = "debug", skip(self),
+        fields(
+            account_equity = %account_equity.value(),
+            risk_percentage = %risk_percentage.value(),
+            entry_price = %entry_price.value(),
+            atr = %atr,
+            atr_multiplier = %atr_multiplier
+        ))]
+    pub fn calculate_position_size_from_atr(
+        &self,
+        account_equity: AccountEquity,
+        risk_percentage: RiskPercentage,
+        entry_price: PricePoint,
+        atr: Decimal,
+        atr_multiplier: Decimal,
+        side: PositionSide,
+    ) -> Result<(PositionSize, PricePoint), PositionSizingError> {
+        if atr_multiplier <= Decimal::ZERO {
+            return Err(PositionSizingError::invalid_atr_multiplier(atr_multiplier));
+        }
+
+        let stop_distance = atr
+            .checked_mul(atr_multiplier)
+            .ok_or(PositionSizingError::CalculationOverflow)?;
+        let stop_value = match side {
+            PositionSide::Long => entry_price.value() - stop_distance,
+            PositionSide::Short => entry_price.value() + stop_distance,
+        };
+        if stop_distance <= Decimal::ZERO {
+            return Err(PositionSizingError::invalid_stop_distance(entry_price.value(), stop_value));
+        }
+        let stop_loss = PricePoint::new(stop_value)?;
+
+        let position_size =
+            self.size_for_stop_distance(account_equity, risk_percentage, entry_price, stop_distance)?;
+        Ok((position_size, stop_loss))
+    }
+
+    /// Sizes a position scaled into across several entries sharing one risk budget
+    ///
+    /// The risk amount is split equally between the entries, and each unit is
+    /// sized over its own distance to the shared stop. Once every unit has
+    /// filled, the aggregate loss at the stop equals the full risk percentage.
+    ///
+    /// # Arguments
+    /// * `account_equity` - Total account balance available for trading
+    /// * `risk_percentage` - Risk for the whole position as decimal (e.g., 0.02 for 2%)
+    /// * `entries` - Entry price of each unit
+    /// * `stop_loss` - Stop loss shared by all units
+    /// * `side` - Direction of the position
+    ///
+    /// # Returns
+    /// * `Ok(Vec<(PricePoint, PositionSize)>)` - Each entry with its unit size, in order
+    /// * `Err(PositionSizingError)` - If there are no entries, an entry is on the wrong
+    ///   side of the stop, or the filled position would exceed the account balance
+    ///
+    /// # Examples
+    /// ```
+    /// use disciplina::{PositionSizingCalculator, AccountEquity, RiskPercentage, PricePoint, PositionSide};
+    /// use rust_decimal::Decimal;
+    /// use std::str::FromStr;
+    ///
+    /// let calculator = PositionSizingCalculator::new();
+    /// let units = calculator.calculate_scaled_entries(
+    ///     AccountEquity::new(Decimal::from(10000))?,
+    ///     RiskPercentage::new(Decimal::from_str("0.02")?)?, // 2% across both units
+    ///     &[PricePoint::new(Decimal::from(100))?, PricePoint::new(Decimal::from(98))?],
+    ///     PricePoint::new(Decimal::from(95))?,
+    ///     PositionSide::Long,
+    /// )?;
+    ///
+    /// // Expected: $100 per unit, so 100 / 5 = 20 and 100 / 3 = 33.33...
+    /// assert_eq!(units[0].1.value(), Decimal::from(20));
+    /// assert_eq!(units[1].1.rounded(2), Decimal::from_str("33.33")?);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn calculate_scaled_entries(
+        &self,
+        account_equity: AccountEquity,
+        risk_percentage: RiskPercentage,
+        entries: &[PricePoint],
+        stop_loss: PricePoint,
+        side: PositionSide,
+    ) -> Result<Vec<(PricePoint, PositionSize)>, PositionSizingError> {
+        if entries.is_empty() {
+            return Err(PositionSizingError::calculation_failed("scaled entries require at least one entry price"));
+        }
+
+        let risk_amount = account_equity
+            .value()
+            .checked_mul(risk_percentage.value())
+            .ok_or(PositionSizingError::CalculationOverflow)?;
+        let unit_risk = risk_amount / Decimal::from(entries.len());
+
+        let mut units = Vec::with_capacity(entries.len());
+        let mut position_value = Decimal::ZERO;
+        for &entry_price in entries {
+            let stop_distance = match side {
+                PositionSide::Long => entry_price.value() - stop_loss.value(),
+                PositionSide::Short => stop_loss.value() - entry_price.value(),
+            };
+            if stop_distance <= Decimal::ZERO {
+                return Err(PositionSizingError::invalid_stop_distance(entry_price.value(), stop_loss.value()));
+            }
+
+            let unit_size = unit_risk
+                .checked_div(stop_distance)
+                .ok_or(PositionSizingError::CalculationOverflow)?;
+            let unit_size = PositionSize::new(match self.precision {
+                Some(precision) => unit_size.round_dp(precision),
+                None => unit_size,
+            })?;
+            position_value += unit_size.total_value(entry_price);
+            units.push((entry_price, unit_size));
+        }
+
+        if position_value > account_equity.value() {
+            return Err(PositionSizingError::exceeds_account_balance(position_value, account_equity.value()));
+        }
+
+        Ok(units)
+    }
+
+    /// Van Tharp sizing over an already validated, positive stop distance
+    fn size_for_stop_distance(
+        &self,
+        account_equity: AccountEquity,
+        risk_percentage: RiskPercentage,
+        entry_price: PricePoint,
+        stop_distance: Decimal,
+    ) -> Result<PositionSize, PositionSizingError> {
         // Calculate risk amount (total dollar amount at risk)
         let risk_amount = match account_equity.value().checked_mul(risk_percentage.value()) {
             Some(amount) => amount,
@@ -360,6 +920,7 @@ impl Default for PositionSizingCalculator {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::MAX_RISK_PERCENTAGE;
     use std::str::FromStr;
 
     #[test]
@@ -508,4 +1069,253 @@ mod tests {
             _ => panic!("Expected ExceedsAccountBalance error"),
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_atr_stop_for_long_and_short() {
+        let calculator = PositionSizingCalculator::new();
+        let equity = AccountEquity::new(Decimal::from(10000)).unwrap();
+        let risk = RiskPercentage::new(Decimal::from_str("0.02").unwrap()).unwrap();
+        let entry = PricePoint::new(Decimal::from(100)).unwrap();
+        let atr = Decimal::from_str("1.6").unwrap();
+        let multiplier = Decimal::from_str("2.5").unwrap();
+
+        // 1.6 ATR × 2.5 = 4 below entry for a long: 200 / 4 = 50
+        let (size, stop) = calculator
+            .calculate_position_size_from_atr(equity, risk, entry, atr, multiplier, PositionSide::Long)
+            .unwrap();
+        assert_eq!(stop.value(), Decimal::from(96));
+        assert_eq!(size.value(), Decimal::from(50));
+        assert_eq!(
+            calculator.calculate_position_size(equity, risk, entry, stop).unwrap(),
+            size
+        );
+
+        // The same distance above entry for a short
+        let (short_size, short_stop) = calculator
+            .calculate_position_size_from_atr(equity, risk, entry, atr, multiplier, PositionSide::Short)
+            .unwrap();
+        assert_eq!(short_stop.value(), Decimal::from(104));
+        assert_eq!(short_size, size);
+    }
+
+    #[test]
+    fn test_atr_multiplier_must_be_positive() {
+        let calculator = PositionSizingCalculator::new();
+        let result = calculator.calculate_position_size_from_atr(
+            AccountEquity::new(Decimal::from(10000)).unwrap(),
+            RiskPercentage::new(Decimal::from_str("0.02").unwrap()).unwrap(),
+            PricePoint::new(Decimal::from(100)).unwrap(),
+            Decimal::from(2),
+            Decimal::ZERO,
+            PositionSide::Long,
+        );
+
+        assert_eq!(result, Err(PositionSizingError::InvalidAtrMultiplier { value: Decimal::ZERO }));
+
+        // An ATR wide enough to put a long stop below zero is rejected as a price
+        let result = calculator.calculate_position_size_from_atr(
+            AccountEquity::new(Decimal::from(10000)).unwrap(),
+            RiskPercentage::new(Decimal::from_str("0.02").unwrap()).unwrap(),
+            PricePoint::new(Decimal::from(100)).unwrap(),
+            Decimal::from(60),
+            Decimal::from(2),
+            PositionSide::Long,
+        );
+        assert!(matches!(result, Err(PositionSizingError::InvalidPricePoint { .. })));
+    }
+
+    #[test]
+    fn test_rounded_size_never_exceeds_risk_budget() {
+        // 200 / 3 = 66.666...; two decimal places of precision rounds it up to 66.67
+        let calculator = PositionSizingCalculator::with_precision(2);
+        let equity = AccountEquity::new(Decimal::from(10000)).unwrap();
+        let risk = RiskPercentage::new(Decimal::from_str("0.02").unwrap()).unwrap();
+        let entry = PricePoint::new(Decimal::from(100)).unwrap();
+        let stop = PricePoint::new(Decimal::from(97)).unwrap();
+
+        let step = Decimal::from_str("0.001").unwrap();
+        let size = calculator
+            .calculate_position_size_rounded(equity, risk, entry, stop, step)
+            .unwrap();
+        assert_eq!(size.value(), Decimal::from_str("66.666").unwrap());
+        assert!(size.value() * Decimal::from(3) <= Decimal::from(200));
+
+        // A step larger than the whole position is refused
+        let result = calculator.calculate_position_size_rounded(equity, risk, entry, stop, Decimal::from(100));
+        assert!(matches!(result, Err(PositionSizingError::BelowStepSize { .. })));
+    }
+
+    #[test]
+    fn test_fees_come_out_of_the_risk_budget() {
+        let calculator = PositionSizingCalculator::new();
+        let equity = AccountEquity::new(Decimal::from(10000)).unwrap();
+        let risk = RiskPercentage::new(Decimal::from_str("0.02").unwrap()).unwrap();
+        let entry = PricePoint::new(Decimal::from(100)).unwrap();
+        let stop = PricePoint::new(Decimal::from(95)).unwrap();
+        let fee = Decimal::from_str("0.001").unwrap();
+
+        let sizing = calculator
+            .calculate_position_size_with_fees(equity, risk, entry, stop, fee, fee)
+            .unwrap();
+        assert_eq!(sizing.gross_size.value(), Decimal::from(40));
+        assert!(sizing.net_size < sizing.gross_size);
+
+        // Stop-out loss plus fees on the net size spends exactly the $200 budget
+        let stop_loss_amount = sizing.net_size.value() * Decimal::from(5);
+        assert_eq!((stop_loss_amount + sizing.estimated_fees).round_dp(20), Decimal::from(200));
+
+        // Without fees both sizes agree
+        let no_fees = calculator
+            .calculate_position_size_with_fees(equity, risk, entry, stop, Decimal::ZERO, Decimal::ZERO)
+            .unwrap();
+        assert_eq!(no_fees.net_size, no_fees.gross_size);
+        assert_eq!(no_fees.estimated_fees, Decimal::ZERO);
+
+        let too_high = Decimal::from_str("0.2").unwrap();
+        assert_eq!(
+            calculator.calculate_position_size_with_fees(equity, risk, entry, stop, fee, too_high),
+            Err(PositionSizingError::InvalidFeeRate { value: too_high })
+        );
+    }
+
+    #[test]
+    fn test_scaled_entries_share_one_risk_budget() {
+        let calculator = PositionSizingCalculator::new();
+        let equity = AccountEquity::new(Decimal::from(10000)).unwrap();
+        let risk = RiskPercentage::new(Decimal::from_str("0.03").unwrap()).unwrap();
+        let entries = [
+            PricePoint::new(Decimal::from(100)).unwrap(),
+            PricePoint::new(Decimal::from(102)).unwrap(),
+            PricePoint::new(Decimal::from(104)).unwrap(),
+        ];
+        let stop = PricePoint::new(Decimal::from(110)).unwrap();
+
+        let units = calculator
+            .calculate_scaled_entries(equity, risk, &entries, stop, PositionSide::Short)
+            .unwrap();
+        assert_eq!(units.len(), 3);
+        assert_eq!(units[0], (entries[0], PositionSize::new(Decimal::from(10)).unwrap()));
+
+        // All units stopped out together lose exactly the $300 budget
+        let total_risk: Decimal = units
+            .iter()
+            .map(|(entry, size)| size.value() * (stop.value() - entry.value()))
+            .sum();
+        assert_eq!(total_risk.round_dp(20), Decimal::from(300));
+
+        // An entry beyond the stop cannot be part of the ladder
+        let beyond_stop = [entries[0], PricePoint::new(Decimal::from(111)).unwrap()];
+        assert_eq!(
+            calculator.calculate_scaled_entries(equity, risk, &beyond_stop, stop, PositionSide::Short),
+            Err(PositionSizingError::InvalidStopDistance {
+                entry: Decimal::from(111),
+                stop: Decimal::from(110),
+            })
+        );
+        assert!(calculator
+            .calculate_scaled_entries(equity, risk, &[], stop, PositionSide::Short)
+            .is_err());
+    }
+
+    #[test]
+    fn test_self_verification_cross_checks_results() {
+        let equity = AccountEquity::new(Decimal::from(10000)).unwrap();
+        let risk = RiskPercentage::new(Decimal::from_str("0.02").unwrap()).unwrap();
+        let entry = PricePoint::new(Decimal::from(100)).unwrap();
+        let stop = PricePoint::new(Decimal::from(97)).unwrap();
+
+        // Rounded to 4 places, 200 / 3 still agrees with the full-precision recalculation
+        for calculator in [
+            PositionSizingCalculator::new().with_self_verification(true),
+            PositionSizingCalculator::with_precision(4).with_self_verification(true),
+        ] {
+            let size = calculator.calculate_position_size(equity, risk, entry, stop).unwrap();
+            assert!(calculator.verify_calculation(equity, risk, entry, stop, size).unwrap());
+        }
+
+        let calculator = PositionSizingCalculator::new();
+        let off_by_a_cent = PositionSize::new(Decimal::from_str("66.67").unwrap()).unwrap();
+        assert!(!calculator.verify_calculation(equity, risk, entry, stop, off_by_a_cent).unwrap());
+        assert_eq!(
+            calculator.verify_calculation(equity, risk, stop, entry, off_by_a_cent),
+            Err(PositionSizingError::InvalidStopDistance {
+                entry: Decimal::from(97),
+                stop: Decimal::from(100),
+            })
+        );
+    }
+
+    #[test]
+    fn test_verification_tolerance_absorbs_only_rounding_noise() {
+        let equity = AccountEquity::new(Decimal::from(10000)).unwrap();
+        let risk = RiskPercentage::new(Decimal::from_str("0.02").unwrap()).unwrap();
+        let entry = PricePoint::new(Decimal::from(100)).unwrap();
+        let stop = PricePoint::new(Decimal::from(95)).unwrap();
+        let calculator = PositionSizingCalculator::new()
+            .with_verification_tolerance(Decimal::from_str("0.000000001").unwrap())
+            .unwrap();
+
+        // 40 is exact; a size off by 5e-10 is within the 1e-9 tolerance
+        let noisy = PositionSize::new(Decimal::from_str("40.0000000005").unwrap()).unwrap();
+        assert!(calculator.verify_calculation(equity, risk, entry, stop, noisy).unwrap());
+        assert!(!PositionSizingCalculator::new().verify_calculation(equity, risk, entry, stop, noisy).unwrap());
+
+        // 2e-9 off is beyond it, and the discrepancy is reported
+        let drifted = PositionSize::new(Decimal::from_str("40.000000002").unwrap()).unwrap();
+        assert!(!calculator.verify_calculation(equity, risk, entry, stop, drifted).unwrap());
+        assert_eq!(
+            calculator.verification_discrepancy(equity, risk, entry, stop, drifted).unwrap(),
+            Decimal::from_str("0.000000002").unwrap()
+        );
+
+        // Tolerances coarse enough to hide a material difference are refused
+        for epsilon in ["-0.000000001", "0.0001"] {
+            let epsilon = Decimal::from_str(epsilon).unwrap();
+            assert_eq!(
+                PositionSizingCalculator::new().with_verification_tolerance(epsilon).unwrap_err(),
+                PositionSizingError::InvalidVerificationTolerance {
+                    value: epsilon,
+                    max: MAX_VERIFICATION_TOLERANCE,
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn test_conviction_multiplier_scales_risk_without_exceeding_limit() {
+        let calculator = PositionSizingCalculator::new();
+        let equity = AccountEquity::new(Decimal::from(10000)).unwrap();
+        let entry = PricePoint::new(Decimal::from(100)).unwrap();
+        let stop = PricePoint::new(Decimal::from(80)).unwrap();
+        let size_at = |risk: &str, conviction: &str| {
+            calculator
+                .calculate_position_size_with_conviction(
+                    equity,
+                    RiskPercentage::from_str(risk).unwrap(),
+                    entry,
+                    stop,
+                    ConvictionMultiplier::new(Decimal::from_str(conviction).unwrap()).unwrap(),
+                )
+                .unwrap()
+        };
+
+        // A 0.5x trade is half the size of a 1x trade on the same setup
+        let full = size_at("0.02", "1");
+        assert_eq!(full.value(), Decimal::from(10));
+        assert_eq!(size_at("0.02", "0.5").value(), full.value() / Decimal::from(2));
+
+        // At the protocol limit, full conviction risks exactly the limit and no more
+        let at_limit = size_at(MAX_RISK_PERCENTAGE, "1");
+        let max_risk_amount = equity.value() * Decimal::from_str(MAX_RISK_PERCENTAGE).unwrap();
+        assert_eq!(at_limit.value() * (entry.value() - stop.value()), max_risk_amount);
+
+        // Multipliers that would raise risk above the base are refused
+        for multiplier in ["1.5", "1.01", "0.1"] {
+            let multiplier = Decimal::from_str(multiplier).unwrap();
+            assert_eq!(
+                ConvictionMultiplier::new(multiplier),
+                Err(PositionSizingError::InvalidConvictionMultiplier { value: multiplier })
+            );
+        }
+    }
+}
@@ -17,6 +17,10 @@ pub enum PositionSizingError {
     #[error("Invalid risk percentage: {value}. Risk must be between 0.5% (0.005) and 6% (0.06)")]
     InvalidRiskPercentage { value: Decimal },
 
+    /// Conviction multiplier is outside acceptable bounds
+    #[error("Invalid conviction multiplier: {value}. Multiplier must be between 0.25 and 1")]
+    InvalidConvictionMultiplier { value: Decimal },
+
     /// Price point is invalid (zero, negative, or missing)
     #[error("Invalid price point: {value}. Price must be positive (> 0)")]
     InvalidPricePoint { value: Decimal },
@@ -25,6 +29,33 @@ pub enum PositionSizingError {
     #[error("Invalid stop distance: entry_price={entry}, stop_loss={stop}. Stop loss must be below entry price for long positions")]
     InvalidStopDistance { entry: Decimal, stop: Decimal },
 
+    /// ATR multiplier used to place the stop is zero or negative
+    #[error("Invalid ATR multiplier: {value}. The multiplier must be positive (> 0)")]
+    InvalidAtrMultiplier { value: Decimal },
+
+    /// Trading fee rate is outside acceptable bounds
+    #[error("Invalid fee rate: {value}. Fee rate must be between 0 and 10% (0.1)")]
+    InvalidFeeRate { value: Decimal },
+
+    /// Exchange lot step size is zero or negative
+    #[error("Invalid step size: {value}. Step size must be positive (> 0)")]
+    InvalidStepSize { value: Decimal },
+
+    /// Position size is smaller than a single exchange lot step
+    #[error("Position size {position_size} is below the minimum step size {step_size}")]
+    BelowStepSize {
+        position_size: Decimal,
+        step_size: Decimal,
+    },
+
+    /// Independent recalculation disagreed with the primary result
+    #[error("Verification failed: primary position size {primary} differs from independent result {independent}")]
+    VerificationFailure { primary: Decimal, independent: Decimal },
+
+    /// Verification tolerance is negative or coarse enough to hide a material difference
+    #[error("Invalid verification tolerance: {value}. Tolerance must be between 0 and {max}")]
+    InvalidVerificationTolerance { value: Decimal, max: Decimal },
+
     /// Calculation would result in arithmetic overflow
     #[error("Calculation overflow: position size calculation exceeded maximum decimal precision")]
     CalculationOverflow,
@@ -60,6 +91,11 @@ impl PositionSizingError {
         Self::InvalidRiskPercentage { value }
     }
 
+    /// Creates an InvalidConvictionMultiplier error
+    pub fn invalid_conviction_multiplier(value: Decimal) -> Self {
+        Self::InvalidConvictionMultiplier { value }
+    }
+
     /// Creates an InvalidPricePoint error
     pub fn invalid_price_point(value: Decimal) -> Self {
         Self::InvalidPricePoint { value }
@@ -70,6 +106,39 @@ impl PositionSizingError {
         Self::InvalidStopDistance { entry, stop }
     }
 
+    /// Creates an InvalidAtrMultiplier error
+    pub fn invalid_atr_multiplier(value: Decimal) -> Self {
+        Self::InvalidAtrMultiplier { value }
+    }
+
+    /// Creates an InvalidFeeRate error
+    pub fn invalid_fee_rate(value: Decimal) -> Self {
+        Self::InvalidFeeRate { value }
+    }
+
+    /// Creates an InvalidStepSize error
+    pub fn invalid_step_size(value: Decimal) -> Self {
+        Self::InvalidStepSize { value }
+    }
+
+    /// Creates a BelowStepSize error
+    pub fn below_step_size(position_size: Decimal, step_size: Decimal) -> Self {
+        Self::BelowStepSize {
+            position_size,
+            step_size,
+        }
+    }
+
+    /// Creates a VerificationFailure error
+    pub fn verification_failure(primary: Decimal, independent: Decimal) -> Self {
+        Self::VerificationFailure { primary, independent }
+    }
+
+    /// Creates an InvalidVerificationTolerance error
+    pub fn invalid_verification_tolerance(value: Decimal, max: Decimal) -> Self {
+        Self::InvalidVerificationTolerance { value, max }
+    }
+
     /// Creates a DivisionByZero error
     pub fn division_by_zero(entry: Decimal, stop: Decimal) -> Self {
         Self::DivisionByZero { entry, stop }
@@ -39,7 +39,7 @@ pub mod errors;
 pub mod calculator;
 
 // Re-export main types for convenience
-pub use types::{AccountEquity, RiskPercentage, PricePoint, PositionSize};
+pub use types::{AccountEquity, RiskPercentage, ConvictionMultiplier, PricePoint, PositionSize, PositionSide, FeeAdjustedPositionSize};
 pub use errors::PositionSizingError;
 pub use calculator::PositionSizingCalculator;
 
@@ -15,6 +15,12 @@ pub const MIN_RISK_PERCENTAGE: &str = "0.005";
 /// Maximum allowable risk percentage (6% - Testudo Protocol limit)
 pub const MAX_RISK_PERCENTAGE: &str = "0.06";
 
+/// Minimum conviction multiplier (a quarter of the base risk)
+pub const MIN_CONVICTION_MULTIPLIER: &str = "0.25";
+
+/// Maximum conviction multiplier (the full base risk, never more)
+pub const MAX_CONVICTION_MULTIPLIER: &str = "1";
+
 /// Represents account equity with validation
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct AccountEquity(Decimal);
@@ -154,6 +160,66 @@ impl fmt::Display for RiskPercentage {
     }
 }
 
+/// Fraction of the base risk percentage to commit to a trade
+///
+/// Lower-conviction setups risk less than the configured base risk. The
+/// multiplier can only scale risk down, so a trade sized with it never risks
+/// more than the protocol limit the base risk was validated against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ConvictionMultiplier(Decimal);
+
+impl ConvictionMultiplier {
+    /// Full conviction: the trade risks the whole base risk percentage
+    pub const FULL: Self = Self(Decimal::ONE);
+
+    /// Creates a new ConvictionMultiplier instance
+    ///
+    /// # Arguments
+    /// * `value` - Multiplier applied to the base risk (e.g., 0.5 for half risk)
+    ///
+    /// # Returns
+    /// * `Ok(ConvictionMultiplier)` if value is between 0.25 and 1
+    /// * `Err(PositionSizingError)` if value is outside valid range
+    ///
+    /// # Examples
+    /// ```
+    /// use disciplina::ConvictionMultiplier;
+    /// use rust_decimal::Decimal;
+    /// use std::str::FromStr;
+    ///
+    /// let conviction = ConvictionMultiplier::new(Decimal::from_str("0.5")?)?;
+    /// assert_eq!(conviction.value(), Decimal::from_str("0.5")?);
+    /// assert!(ConvictionMultiplier::new(Decimal::from_str("1.5")?).is_err());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn new(value: Decimal) -> Result<Self, PositionSizingError> {
+        let min_multiplier = Decimal::from_str(MIN_CONVICTION_MULTIPLIER).unwrap();
+        let max_multiplier = Decimal::from_str(MAX_CONVICTION_MULTIPLIER).unwrap();
+
+        if value < min_multiplier || value > max_multiplier {
+            return Err(PositionSizingError::invalid_conviction_multiplier(value));
+        }
+        Ok(Self(value))
+    }
+
+    /// Returns the underlying multiplier value
+    pub fn value(self) -> Decimal {
+        self.0
+    }
+}
+
+impl Default for ConvictionMultiplier {
+    fn default() -> Self {
+        Self::FULL
+    }
+}
+
+impl fmt::Display for ConvictionMultiplier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}x", self.0)
+    }
+}
+
 /// Represents a price point with validation
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct PricePoint(Decimal);
@@ -209,6 +275,15 @@ impl fmt::Display for PricePoint {
     }
 }
 
+/// Direction of a position, which decides the side of entry its stop sits on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PositionSide {
+    /// Bought; the stop sits below entry
+    Long,
+    /// Sold short; the stop sits above entry
+    Short,
+}
+
 /// Represents a calculated position size
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct PositionSize(Decimal);
@@ -253,6 +328,47 @@ impl PositionSize {
         self.0.round_dp(decimal_places)
     }
 
+    /// Floors the position size to a whole multiple of an exchange lot step
+    ///
+    /// Exchanges reject quantities that are not a multiple of the symbol's
+    /// step size. Rounding is always down, so the rounded position never
+    /// risks more than the unrounded one.
+    ///
+    /// # Arguments
+    /// * `step_size` - Smallest quantity increment the exchange accepts
+    ///
+    /// # Returns
+    /// * `Ok(PositionSize)` rounded down to a multiple of `step_size`
+    /// * `Err(PositionSizingError)` if `step_size` is not positive or the size is below one step
+    ///
+    /// # Examples
+    /// ```
+    /// use disciplina::PositionSize;
+    /// use rust_decimal::Decimal;
+    /// use std::str::FromStr;
+    ///
+    /// let size = PositionSize::new(Decimal::from_str("0.123456")?)?;
+    /// let rounded = size.round_to_step(Decimal::from_str("0.001")?)?;
+    /// assert_eq!(rounded.value(), Decimal::from_str("0.123")?);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn round_to_step(self, step_size: Decimal) -> Result<Self, PositionSizingError> {
+        if step_size <= Decimal::ZERO {
+            return Err(PositionSizingError::invalid_step_size(step_size));
+        }
+
+        // The remainder is exact in decimal, so subtracting it floors without drift
+        let remainder = self
+            .0
+            .checked_rem(step_size)
+            .ok_or(PositionSizingError::CalculationOverflow)?;
+        let rounded = self.0 - remainder;
+        if rounded <= Decimal::ZERO {
+            return Err(PositionSizingError::below_step_size(self.0, step_size));
+        }
+        Ok(Self(rounded))
+    }
+
     /// Calculates the total value of this position at a given price
     /// 
     /// # Arguments
@@ -283,6 +399,17 @@ impl fmt::Display for PositionSize {
     }
 }
 
+/// Position size with round-trip trading fees counted against the risk budget
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeAdjustedPositionSize {
+    /// Van Tharp size ignoring fees
+    pub gross_size: PositionSize,
+    /// Size whose stop-out loss plus fees stays within the risk budget
+    pub net_size: PositionSize,
+    /// Entry and stop-exit fees on `net_size`, in account currency
+    pub estimated_fees: Decimal,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -370,6 +497,32 @@ mod tests {
         assert_eq!(size.total_value(price), expected_total);
     }
 
+    #[test]
+    fn test_round_to_step_floors_to_lot_size() {
+        let size = PositionSize::new(Decimal::from_str("1.23999").unwrap()).unwrap();
+        let step = Decimal::from_str("0.01").unwrap();
+        assert_eq!(size.round_to_step(step).unwrap().value(), Decimal::from_str("1.23").unwrap());
+
+        // Already a multiple of the step
+        let exact = PositionSize::new(Decimal::from_str("0.75").unwrap()).unwrap();
+        let step = Decimal::from_str("0.25").unwrap();
+        assert_eq!(exact.round_to_step(step).unwrap(), exact);
+
+        // Less than one step is an error rather than a zero-sized order
+        let tiny = PositionSize::new(Decimal::from_str("0.0004").unwrap()).unwrap();
+        assert_eq!(
+            tiny.round_to_step(Decimal::from_str("0.001").unwrap()),
+            Err(PositionSizingError::BelowStepSize {
+                position_size: Decimal::from_str("0.0004").unwrap(),
+                step_size: Decimal::from_str("0.001").unwrap(),
+            })
+        );
+        assert!(matches!(
+            exact.round_to_step(Decimal::ZERO),
+            Err(PositionSizingError::InvalidStepSize { .. })
+        ));
+    }
+
     #[test]
     fn test_display_formatting() {
         let equity = AccountEquity::new(Decimal::from(10000)).unwrap();
@@ -10,7 +10,7 @@ use proptest::prelude::*;
 // Import the types we're going to test (these don't exist yet - TDD approach)
 use disciplina::{
     PositionSizingCalculator, PositionSizingError, AccountEquity, 
-    RiskPercentage, PricePoint
+    RiskPercentage, PricePoint, PositionSide
 };
 
 /// Property-based tests for position sizing calculation
@@ -170,6 +170,73 @@ mod property_tests {
                 prop_assert!((actual_risk - expected_risk).abs() <= tolerance);
             }
         }
+
+        /// Property 6: ATR-derived stops keep the inverse relationship
+        /// A smaller ATR multiplier puts the stop closer and yields a larger position, on either side
+        #[test]
+        fn atr_position_size_inverse_to_multiplier(
+            equity in 10000.0..1_000_000.0f64,
+            risk_pct in 0.005..0.02f64,
+            entry in 100.0..1000.0f64,
+            atr in 0.5..5.0f64,
+            multiplier in 1.0..4.0f64,
+            short in any::<bool>(),
+        ) {
+            let account_equity = AccountEquity::new(Decimal::try_from(equity).unwrap()).unwrap();
+            let risk_percentage = RiskPercentage::new(Decimal::try_from(risk_pct).unwrap()).unwrap();
+            let entry_price = PricePoint::new(Decimal::try_from(entry).unwrap()).unwrap();
+            let atr = Decimal::try_from(atr).unwrap();
+            let close = Decimal::try_from(multiplier).unwrap();
+            let far = close * Decimal::from(2);
+            let side = if short { PositionSide::Short } else { PositionSide::Long };
+
+            let calculator = PositionSizingCalculator::new();
+
+            if let (Ok((size_close, stop_close)), Ok((size_far, stop_far))) = (
+                calculator.calculate_position_size_from_atr(account_equity, risk_percentage, entry_price, atr, close, side),
+                calculator.calculate_position_size_from_atr(account_equity, risk_percentage, entry_price, atr, far, side)
+            ) {
+                prop_assert!(size_close.value() > size_far.value());
+                // The stop sits on the losing side of entry
+                match side {
+                    PositionSide::Long => prop_assert!(stop_far.value() < stop_close.value() && stop_close.value() < entry_price.value()),
+                    PositionSide::Short => prop_assert!(stop_far.value() > stop_close.value() && stop_close.value() > entry_price.value()),
+                }
+            }
+        }
+
+        /// Property 7: Scaled entries spend the whole risk budget once filled
+        /// However the ladder is spaced, the units' combined loss at the stop equals equity × risk %
+        #[test]
+        fn scaled_entries_aggregate_to_risk_budget(
+            equity in 10000.0..1_000_000.0f64,
+            risk_pct in 0.005..0.02f64,
+            stop in 50.0..500.0f64,
+            offsets in prop::collection::vec(5.0..50.0f64, 1..5),
+        ) {
+            let account_equity = AccountEquity::new(Decimal::try_from(equity).unwrap()).unwrap();
+            let risk_percentage = RiskPercentage::new(Decimal::try_from(risk_pct).unwrap()).unwrap();
+            let stop_loss = PricePoint::new(Decimal::try_from(stop).unwrap()).unwrap();
+            let entries: Vec<PricePoint> = offsets
+                .iter()
+                .map(|offset| PricePoint::new(Decimal::try_from(stop + offset).unwrap()).unwrap())
+                .collect();
+
+            let calculator = PositionSizingCalculator::new();
+
+            if let Ok(units) = calculator.calculate_scaled_entries(
+                account_equity, risk_percentage, &entries, stop_loss, PositionSide::Long
+            ) {
+                let total_risk: Decimal = units
+                    .iter()
+                    .map(|(entry, size)| size.value() * (entry.value() - stop_loss.value()))
+                    .sum();
+                let expected_risk = account_equity.value() * risk_percentage.value();
+
+                let tolerance = Decimal::from_str("0.01").unwrap(); // 1 cent tolerance
+                prop_assert!((total_risk - expected_risk).abs() <= tolerance);
+            }
+        }
     }
 }
 
@@ -0,0 +1,303 @@
+//! Automatic breakeven stop moves driven by the price stream
+//!
+//! Once a position has gained a set multiple of its initial risk (1R by
+//! default), its stop is moved to the entry price. The bracket is modified on
+//! the exchange and the position's tracked risk is released from the
+//! protocol, so the freed budget is available to new trades.
+
+use crate::executor::Executor;
+use crate::ooda::SharedProtocol;
+use crate::types::TradeSetup;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::sync::Arc;
+use testudo_types::OrderSide;
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Default profit multiple, in R, at which the stop moves to breakeven
+pub const DEFAULT_BREAKEVEN_TRIGGER: Decimal = Decimal::ONE;
+
+/// An open bracket order watched for the breakeven move
+#[derive(Debug, Clone)]
+pub struct BreakevenWatch {
+    pub order_id: String,
+    pub setup: TradeSetup,
+    /// Equity the position's tracked risk is a fraction of
+    pub account_equity: Decimal,
+    /// Unrealized gain, in multiples of the initial risk, that triggers the move
+    pub trigger_multiple: Decimal,
+    /// Protocol position the bracket protects; its tracked risk is replaced on
+    /// the move, otherwise the symbol's exposure is adjusted
+    pub position_id: Option<Uuid>,
+    /// Exchange holding the bracket, when not the executor's default
+    pub venue: Option<String>,
+}
+
+impl BreakevenWatch {
+    pub fn new(order_id: &str, setup: TradeSetup, account_equity: Decimal) -> Self {
+        Self {
+            order_id: order_id.to_string(),
+            setup,
+            account_equity,
+            trigger_multiple: DEFAULT_BREAKEVEN_TRIGGER,
+            position_id: None,
+            venue: None,
+        }
+    }
+
+    pub fn with_trigger_multiple(mut self, trigger_multiple: Decimal) -> Self {
+        self.trigger_multiple = trigger_multiple;
+        self
+    }
+
+    pub fn with_position_id(mut self, position_id: Uuid) -> Self {
+        self.position_id = Some(position_id);
+        self
+    }
+
+    pub fn with_venue(mut self, venue: &str) -> Self {
+        self.venue = Some(venue.to_string());
+        self
+    }
+
+    /// Unrealized gain at `price` in multiples of the initial risk
+    ///
+    /// `None` when the stop already sits at or beyond the entry.
+    pub fn r_multiple_at(&self, price: Decimal) -> Option<Decimal> {
+        let setup = &self.setup;
+        let (risk, gain) = match setup.side {
+            OrderSide::Buy => (setup.entry_price - setup.stop_loss, price - setup.entry_price),
+            OrderSide::Sell => (setup.stop_loss - setup.entry_price, setup.entry_price - price),
+        };
+        (risk > Decimal::ZERO).then(|| gain / risk)
+    }
+}
+
+/// A stop moved to breakeven
+#[derive(Debug, Clone, PartialEq)]
+pub struct BreakevenMoved {
+    pub order_id: String,
+    pub position_id: Option<Uuid>,
+    pub symbol: String,
+    pub previous_stop: Decimal,
+    pub new_stop: Decimal,
+    /// Tracked risk before the move, as a fraction of account equity
+    pub previous_risk: Decimal,
+    /// Tracked risk after the move, as a fraction of account equity
+    pub new_risk: Decimal,
+}
+
+/// Moves watched positions' stops to breakeven as prices arrive
+pub struct BreakevenAutomation {
+    executor: Arc<Executor>,
+    watches: Mutex<HashMap<String, BreakevenWatch>>,
+}
+
+impl BreakevenAutomation {
+    pub fn new(executor: Arc<Executor>) -> Self {
+        Self {
+            executor,
+            watches: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Start watching a position, replacing any watch on the same order
+    pub async fn watch(&self, watch: BreakevenWatch) {
+        self.watches.lock().await.insert(watch.order_id.clone(), watch);
+    }
+
+    /// Stop watching a position, e.g. once it has closed
+    pub async fn unwatch(&self, order_id: &str) -> Option<BreakevenWatch> {
+        self.watches.lock().await.remove(order_id)
+    }
+
+    pub async fn watched_count(&self) -> usize {
+        self.watches.lock().await.len()
+    }
+
+    /// Apply a price tick for `symbol`
+    ///
+    /// Every watched position that has reached its trigger has its stop moved
+    /// to entry and its tracked risk updated in `protocol`; it is then no
+    /// longer watched. The exchange is contacted without holding the watch
+    /// list. A failed modification is logged and leaves that position watched
+    /// so a later tick retries it; the other positions are still moved.
+    pub async fn on_price(&self, symbol: &str, price: Decimal, protocol: &SharedProtocol) -> Vec<BreakevenMoved> {
+        let triggered: Vec<BreakevenWatch> = self
+            .watches
+            .lock()
+            .await
+            .values()
+            .filter(|watch| watch.setup.symbol == symbol)
+            .filter(|watch| {
+                watch
+                    .r_multiple_at(price)
+                    .is_some_and(|r| r >= watch.trigger_multiple)
+            })
+            .cloned()
+            .collect();
+
+        let mut moved = Vec::new();
+        for watch in triggered {
+            let modification = match self
+                .executor
+                .modify_bracket(
+                    &watch.order_id,
+                    &watch.setup,
+                    watch.venue.as_deref(),
+                    Some(watch.setup.entry_price),
+                    None,
+                )
+                .await
+            {
+                Ok(modification) => modification,
+                Err(e) => {
+                    warn!("Failed to move stop for {} ({}) to breakeven: {}", symbol, watch.order_id, e);
+                    continue;
+                }
+            };
+            // A position unwatched meanwhile (e.g. closed) has had its risk released already
+            if self.watches.lock().await.remove(&watch.order_id).is_none() {
+                continue;
+            }
+
+            let previous_risk = modification.previous_risk / watch.account_equity;
+            let new_risk = modification.new_risk / watch.account_equity;
+            {
+                let mut protocol = protocol.lock().await;
+                let tracked = watch
+                    .position_id
+                    .is_some_and(|position_id| protocol.set_tracked_risk(position_id, new_risk));
+                if !tracked {
+                    protocol.update_position_risk(symbol, previous_risk, new_risk);
+                }
+            }
+            info!(
+                "Moved stop for {} ({}) to breakeven at {} from {}",
+                symbol, watch.order_id, watch.setup.entry_price, watch.setup.stop_loss
+            );
+
+            moved.push(BreakevenMoved {
+                order_id: watch.order_id,
+                position_id: watch.position_id,
+                symbol: symbol.to_string(),
+                previous_stop: watch.setup.stop_loss,
+                new_stop: modification.setup.stop_loss,
+                previous_risk,
+                new_risk,
+            });
+        }
+
+        moved
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prudentia::exchange::MockExchange;
+    use rust_decimal_macros::dec;
+    use testudo_types::{ExchangeAdapterTrait, OrderType, TradeOrder};
+
+    #[tokio::test]
+    async fn test_stop_moves_to_breakeven_at_one_r() {
+        let exchange = Arc::new(MockExchange::new());
+        let automation = BreakevenAutomation::new(Arc::new(Executor::new(exchange.clone())));
+
+        // Long 0.1 BTC at 48,000 with a 46,000 stop: 1R is 2,000 per BTC
+        let setup = TradeSetup {
+            symbol: "BTC/USDT".to_string(),
+            entry_price: dec!(48000),
+            stop_loss: dec!(46000),
+            take_profit: Some(dec!(54000)),
+            position_size: dec!(0.1),
+            side: OrderSide::Buy,
+        };
+        let order_id = exchange
+            .place_order(&TradeOrder {
+                client_order_id: "bracket-1".to_string(),
+                symbol: setup.symbol.clone(),
+                side: setup.side,
+                order_type: OrderType::Limit,
+                quantity: setup.position_size,
+                price: Some(setup.entry_price),
+                stop_price: Some(setup.stop_loss),
+                quote_quantity: None,
+            })
+            .await
+            .unwrap()
+            .order_id;
+
+        let equity = dec!(10000);
+        let protocol: SharedProtocol = Default::default();
+        protocol
+            .lock()
+            .await
+            .update_position_risk(&setup.symbol, Decimal::ZERO, setup.risk_amount() / equity);
+        automation.watch(BreakevenWatch::new(&order_id, setup, equity)).await;
+
+        // +0.5R is not enough
+        let moved = automation.on_price("BTC/USDT", dec!(49000), &protocol).await;
+        assert!(moved.is_empty());
+        assert!(exchange.get_oco_modifications().await.is_empty());
+
+        // The mock market sits at 50,000, exactly +1R
+        let moved = automation.on_price("BTC/USDT", dec!(50000), &protocol).await;
+        assert_eq!(moved.len(), 1);
+        assert_eq!(moved[0].previous_stop, dec!(46000));
+        assert_eq!(moved[0].new_stop, dec!(48000));
+        assert_eq!(moved[0].previous_risk, dec!(0.02));
+        assert_eq!(moved[0].new_risk, Decimal::ZERO);
+
+        let modifications = exchange.get_oco_modifications().await;
+        assert_eq!(modifications.len(), 1);
+        assert_eq!(modifications[0].stop_price, Some(dec!(48000)));
+        let exposure = protocol.lock().await.get_status().portfolio_exposure;
+        assert_eq!(exposure.get("BTC/USDT").copied().unwrap_or_default(), Decimal::ZERO);
+        assert_eq!(automation.watched_count().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_failed_move_does_not_hold_up_other_positions() {
+        let exchange = Arc::new(MockExchange::new());
+        let automation = BreakevenAutomation::new(Arc::new(Executor::new(exchange.clone())));
+        let setup = TradeSetup {
+            symbol: "BTC/USDT".to_string(),
+            entry_price: dec!(48000),
+            stop_loss: dec!(46000),
+            take_profit: None,
+            position_size: dec!(0.1),
+            side: OrderSide::Buy,
+        };
+        let order_id = exchange
+            .place_order(&TradeOrder {
+                client_order_id: "bracket-1".to_string(),
+                symbol: setup.symbol.clone(),
+                side: setup.side,
+                order_type: OrderType::Limit,
+                quantity: setup.position_size,
+                price: Some(setup.entry_price),
+                stop_price: Some(setup.stop_loss),
+                quote_quantity: None,
+            })
+            .await
+            .unwrap()
+            .order_id;
+        let protocol: SharedProtocol = Default::default();
+
+        // The exchange does not know the first bracket, so modifying it fails
+        automation.watch(BreakevenWatch::new("unknown-bracket", setup.clone(), dec!(10000))).await;
+        automation.watch(BreakevenWatch::new(&order_id, setup, dec!(10000))).await;
+
+        let moved = automation.on_price("BTC/USDT", dec!(50000), &protocol).await;
+        assert_eq!(moved.len(), 1);
+        assert_eq!(moved[0].order_id, order_id);
+        assert_eq!(exchange.get_oco_modifications().await.len(), 1);
+
+        // The failed move stays watched for the next tick
+        assert_eq!(automation.watched_count().await, 1);
+        assert!(automation.unwatch("unknown-bracket").await.is_some());
+    }
+}
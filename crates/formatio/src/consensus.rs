@@ -0,0 +1,196 @@
+//! Multi-source price consensus for the Observe phase
+//!
+//! A single bad feed (a glitch or a manipulated print) would otherwise flow
+//! straight into position sizing. In consensus mode the OODA loop queries
+//! several price sources and only proceeds when they agree within a
+//! configured tolerance; divergent prices reject the observation.
+
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use std::sync::Arc;
+use testudo_types::{ExchangeAdapterTrait, ExchangeError};
+use thiserror::Error;
+
+/// A source of last-traded prices
+#[async_trait]
+pub trait PriceSource: Send + Sync {
+    /// Name of the source, used in divergence reports
+    fn source_name(&self) -> &str;
+
+    /// Latest price for a symbol
+    async fn latest_price(&self, symbol: &str) -> Result<Decimal, ExchangeError>;
+}
+
+#[async_trait]
+impl<T> PriceSource for T
+where
+    T: ExchangeAdapterTrait + Send + Sync + ?Sized,
+{
+    fn source_name(&self) -> &str {
+        self.exchange_name()
+    }
+
+    async fn latest_price(&self, symbol: &str) -> Result<Decimal, ExchangeError> {
+        Ok(self.get_market_data(symbol).await?.last_price)
+    }
+}
+
+/// Errors from a consensus price query
+#[derive(Debug, Error)]
+pub enum ConsensusError {
+    #[error("No price sources configured for consensus")]
+    NoSources,
+    #[error("Price source {source_name} unavailable: {message}")]
+    SourceUnavailable { source_name: String, message: String },
+    #[error("Price sources diverge for {symbol}: {min_price}..{max_price} spread {spread} exceeds tolerance {tolerance}")]
+    Divergence {
+        symbol: String,
+        min_price: Decimal,
+        max_price: Decimal,
+        spread: Decimal,
+        tolerance: Decimal,
+    },
+}
+
+/// Requires several price sources to agree before an observation is accepted
+pub struct PriceConsensus {
+    sources: Vec<Arc<dyn PriceSource>>,
+    tolerance: Decimal,
+}
+
+impl PriceConsensus {
+    /// Create a consensus over `sources`
+    ///
+    /// `tolerance` is the largest accepted spread between the highest and
+    /// lowest price, as a fraction of the lowest (0.001 = 0.1%).
+    pub fn new(sources: Vec<Arc<dyn PriceSource>>, tolerance: Decimal) -> Self {
+        Self { sources, tolerance }
+    }
+
+    pub fn tolerance(&self) -> Decimal {
+        self.tolerance
+    }
+
+    /// Query every source and return the median price if they agree
+    ///
+    /// Any failing source fails the query: a missing feed cannot vouch for
+    /// the others.
+    pub async fn consensus_price(&self, symbol: &str) -> Result<Decimal, ConsensusError> {
+        if self.sources.is_empty() {
+            return Err(ConsensusError::NoSources);
+        }
+
+        let mut prices = Vec::with_capacity(self.sources.len());
+        for source in &self.sources {
+            let price = source.latest_price(symbol).await.map_err(|e| {
+                ConsensusError::SourceUnavailable {
+                    source_name: source.source_name().to_string(),
+                    message: format!("{:?}", e),
+                }
+            })?;
+            prices.push(price);
+        }
+        prices.sort();
+
+        let min_price = prices[0];
+        let max_price = prices[prices.len() - 1];
+        if min_price <= Decimal::ZERO {
+            return Err(ConsensusError::SourceUnavailable {
+                source_name: "consensus".to_string(),
+                message: format!("non-positive price {} for {}", min_price, symbol),
+            });
+        }
+
+        let spread = (max_price - min_price) / min_price;
+        if spread > self.tolerance {
+            return Err(ConsensusError::Divergence {
+                symbol: symbol.to_string(),
+                min_price,
+                max_price,
+                spread,
+                tolerance: self.tolerance,
+            });
+        }
+
+        let mid = prices.len() / 2;
+        Ok(if prices.len() % 2 == 0 {
+            (prices[mid - 1] + prices[mid]) / Decimal::TWO
+        } else {
+            prices[mid]
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decider::RiskDecider;
+    use crate::ooda::{OodaLoop, OodaLoopError, OodaState};
+    use crate::types::{TradeDirection, TradeIntent};
+    use prudentia::exchange::MockExchange;
+    use prudentia::risk::{MaxTradeRiskRule, RiskManagementProtocol};
+    use rust_decimal_macros::dec;
+    use std::time::SystemTime;
+    use testudo_types::MarketData;
+
+    async fn feed(name: &str, price: Decimal) -> Arc<MockExchange> {
+        let exchange = Arc::new(MockExchange::with_name(name.to_string()));
+        exchange
+            .set_market_data(
+                "BTC/USDT".to_string(),
+                MarketData {
+                    symbol: "BTC/USDT".to_string(),
+                    bid_price: price - dec!(5),
+                    ask_price: price + dec!(5),
+                    last_price: price,
+                    volume_24h: dec!(1000),
+                    timestamp: SystemTime::now(),
+                },
+            )
+            .await;
+        exchange
+    }
+
+    async fn consensus_loop(secondary_price: Decimal) -> OodaLoop {
+        let primary = feed("primary", dec!(50000)).await;
+        let secondary = feed("secondary", secondary_price).await;
+        let consensus = PriceConsensus::new(vec![primary.clone(), secondary], dec!(0.001));
+
+        let protocol = Arc::new(RiskManagementProtocol::new().add_rule(MaxTradeRiskRule::new()));
+        OodaLoop::with_all_components(primary, Arc::new(RiskDecider::new(protocol)))
+            .with_price_consensus(consensus)
+    }
+
+    fn intent() -> TradeIntent {
+        TradeIntent {
+            symbol: "BTC/USDT".to_string(),
+            direction: TradeDirection::Long,
+            account_equity: dec!(10000),
+            risk_percentage: dec!(0.01),
+            preferred_exchange: None,
+            quote_amount: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_agreeing_sources_proceed_at_consensus_price() {
+        let ooda_loop = consensus_loop(dec!(50010)).await;
+
+        let plan = ooda_loop.execute_cycle(intent()).await.unwrap();
+        assert!(plan.approved, "{}", plan.risk_assessment);
+        assert_eq!(plan.setup.entry_price, dec!(50005));
+    }
+
+    #[tokio::test]
+    async fn test_divergent_sources_reject_observation() {
+        let ooda_loop = consensus_loop(dec!(51000)).await;
+
+        let result = ooda_loop.execute_cycle(intent()).await;
+        assert!(
+            matches!(result, Err(OodaLoopError::InvalidObservation { .. })),
+            "{:?}",
+            result
+        );
+        assert!(matches!(ooda_loop.get_state().await, OodaState::Failed(_)));
+    }
+}
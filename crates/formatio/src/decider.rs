@@ -1,8 +1,11 @@
 //! Risk decider for OODA loop - Phase 3 (Decide)
 
-use crate::types::DecisionError;
-use prudentia::risk::RiskManagementProtocol;
+use crate::types::{DecisionError, SizeReduction};
+use prudentia::risk::{ProtocolAssessmentResult, ProtocolDecision, RiskManagementProtocol, RuleFailure, RuleOutcomeCounts};
+use disciplina::RiskPercentage;
+use prudentia::types::{ProtocolViolation, SuggestedAction, TradeProposal, ViolationSeverity};
 use rust_decimal::Decimal;
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::time::{timeout, Duration};
 
@@ -12,10 +15,13 @@ pub enum RiskDecision {
     Execute {
         approved_position_size: Decimal,
         execution_priority: ExecutionPriority,
+        /// Set when the requested size was shrunk to fit the portfolio budget
+        size_reduction: Option<SizeReduction>,
     },
     Reject {
         rejection_reason: String,
         violation_count: u32,
+        violations: Vec<ProtocolViolation>,
     },
     AssessmentFailed {
         error_details: String,
@@ -28,6 +34,10 @@ pub struct DecisionResult {
     pub decision: RiskDecision,
     pub decision_latency_ms: u64,
     pub audit_trail: Vec<String>,
+    /// Rules that failed to run, kept apart from rules that rejected the trade
+    pub failed_rules: Vec<RuleFailure>,
+    /// Rules that ran and rejected the trade
+    pub rejecting_rules: Vec<String>,
 }
 
 /// Priority level for trade execution.
@@ -41,6 +51,7 @@ pub enum ExecutionPriority {
 pub struct RiskDecider {
     protocol: Arc<RiskManagementProtocol>,
     decision_timeout: Duration,
+    auto_reduce_min_notional: Option<Decimal>,
 }
 
 impl RiskDecider {
@@ -48,41 +59,67 @@ impl RiskDecider {
         Self {
             protocol,
             decision_timeout: Duration::from_millis(25),
+            auto_reduce_min_notional: None,
         }
     }
 
+    /// Shrink trades that would breach the portfolio risk limit to the
+    /// remaining budget instead of rejecting them, as long as the reduced
+    /// position is still worth at least `min_notional` in quote currency
+    pub fn with_auto_reduce(mut self, min_notional: Decimal) -> Self {
+        self.auto_reduce_min_notional = Some(min_notional);
+        self
+    }
+
+    /// Pass, rejection and failure counts of each rule since startup
+    pub fn rule_outcome_counts(&self) -> HashMap<String, RuleOutcomeCounts> {
+        self.protocol.rule_outcome_counts()
+    }
+
     pub async fn decide_trade(
         &self,
         proposal: prudentia::types::TradeProposal,
     ) -> Result<DecisionResult, DecisionError> {
         let start_time = std::time::Instant::now();
 
-        // Corrected method call from .assess to .assess_trade (now synchronous)
-        match timeout(self.decision_timeout, async { self.protocol.assess_trade(&proposal) }).await {
-            Ok(Ok(assessment)) => {
+        // Async rules are awaited inside the decision timeout
+        let assess = async {
+            let assessment = self.protocol.assess_trade_async(&proposal).await?;
+            let reduction = self.reduce_to_budget(&proposal, &assessment).await;
+            Ok::<_, prudentia::risk::ProtocolError>(match reduction {
+                Some((reduced, size_reduction)) => (reduced, Some(size_reduction)),
+                None => (assessment, None),
+            })
+        };
+        match timeout(self.decision_timeout, assess).await {
+            Ok(Ok((assessment, size_reduction))) => {
                 let decision = if matches!(assessment.protocol_decision, 
-                    prudentia::risk::ProtocolDecision::Approved | 
-                    prudentia::risk::ProtocolDecision::ApprovedWithWarnings) {
+                    ProtocolDecision::Approved | 
+                    ProtocolDecision::ApprovedWithWarnings) {
                     // The proposal from prudentia doesn't have a separate `approved_position_size`
                     // it either approves the size in the proposal or rejects.
                     RiskDecision::Execute {
                         approved_position_size: assessment.assessment.position_size.value(),
                         execution_priority: ExecutionPriority::Standard,
+                        size_reduction,
                     }
                 } else {
                     RiskDecision::Reject {
                         rejection_reason: assessment.decision_reasoning.clone(),
                         violation_count: assessment.assessment.violations.len() as u32,
+                        violations: assessment.assessment.violations.clone(),
                     }
                 };
                 Ok(DecisionResult {
                     decision,
                     decision_latency_ms: start_time.elapsed().as_millis() as u64,
                     audit_trail: vec![
-                        format!("Rules executed: {}", assessment.rule_results.len()),
+                        format!("Rules executed: {}", assessment.executed_rule_count()),
                         format!("Decision: {:?}", assessment.protocol_decision),
                         format!("Reasoning: {}", assessment.decision_reasoning),
                     ],
+                    failed_rules: assessment.failed_rules,
+                    rejecting_rules: assessment.rejecting_rules,
                 })
             }
             Ok(Err(e)) => Ok(DecisionResult {
@@ -91,6 +128,8 @@ impl RiskDecider {
                 },
                 decision_latency_ms: start_time.elapsed().as_millis() as u64,
                 audit_trail: vec![format!("Risk assessment failed: {}", e)],
+                failed_rules: Vec::new(),
+                rejecting_rules: Vec::new(),
             }),
             Err(_) => Err(DecisionError::AssessmentTimeout(format!(
                 "Timeout after {}ms",
@@ -98,4 +137,146 @@ impl RiskDecider {
             ))),
         }
     }
-}
\ No newline at end of file
+
+    /// Re-assess a rejected trade at the risk still available in the portfolio
+    ///
+    /// Only applies when auto-reduction is enabled and the portfolio limit is
+    /// the sole reason for the rejection; returns the approved assessment of
+    /// the reduced trade and a notice describing the change.
+    async fn reduce_to_budget(
+        &self,
+        proposal: &TradeProposal,
+        assessment: &ProtocolAssessmentResult,
+    ) -> Option<(ProtocolAssessmentResult, SizeReduction)> {
+        let min_notional = self.auto_reduce_min_notional?;
+        if assessment.protocol_decision != ProtocolDecision::Rejected {
+            return None;
+        }
+
+        let mut rejecting = assessment
+            .assessment
+            .violations
+            .iter()
+            .filter(|violation| violation.severity >= ViolationSeverity::Critical);
+        let portfolio = rejecting.next()?;
+        let resizable = portfolio.rule_name == "MaxPortfolioRisk"
+            && matches!(portfolio.hint, Some(SuggestedAction::ReducePositionSize { .. }));
+        if !resizable || rejecting.next().is_some() {
+            return None;
+        }
+
+        // The violation reports projected risk, i.e. open risk plus this trade
+        let open_risk = portfolio.current_value - proposal.risk_percentage.value();
+        let reduced_risk = portfolio.limit_value - open_risk;
+        let mut reduced = proposal.clone();
+        reduced.risk_percentage = RiskPercentage::new(reduced_risk).ok()?;
+
+        let reassessment = self.protocol.assess_trade_async(&reduced).await.ok()?;
+        if !matches!(
+            reassessment.protocol_decision,
+            ProtocolDecision::Approved | ProtocolDecision::ApprovedWithWarnings
+        ) {
+            return None;
+        }
+        let requested_size = assessment.assessment.position_size.value();
+        let reduced_size = reassessment.assessment.position_size.value();
+        if reduced_size * proposal.entry_price.value() < min_notional {
+            return None;
+        }
+
+        let notice = format!(
+            "Position reduced from {} to {} to fit the remaining portfolio risk budget of {:.2}%",
+            requested_size.normalize(),
+            reduced_size.normalize(),
+            reduced_risk * Decimal::ONE_HUNDRED
+        );
+        Some((
+            reassessment,
+            SizeReduction {
+                requested_size,
+                reduced_size,
+                reduced_risk,
+                notice,
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prudentia::risk::{MaxPortfolioRiskRule, OpenPosition};
+    use disciplina::{AccountEquity, PricePoint};
+    use prudentia::types::TradeSide;
+    use rust_decimal_macros::dec;
+    use std::time::SystemTime;
+    use uuid::Uuid;
+
+    fn proposal(risk_percentage: Decimal) -> TradeProposal {
+        TradeProposal {
+            id: Uuid::new_v4(),
+            symbol: "BTC/USDT".to_string(),
+            side: TradeSide::Long,
+            entry_price: PricePoint::new(dec!(50000)).unwrap(),
+            stop_loss: PricePoint::new(dec!(48000)).unwrap(),
+            take_profit: None,
+            account_equity: AccountEquity::new(dec!(10000)).unwrap(),
+            risk_percentage: RiskPercentage::new(risk_percentage).unwrap(),
+            timestamp: SystemTime::now(),
+            metadata: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_trade_over_portfolio_limit_is_reduced_to_remaining_budget() {
+        // 8% already open against the default 10% limit leaves 2%
+        let rule = MaxPortfolioRiskRule::new();
+        rule.add_open_position(OpenPosition {
+            id: "existing".to_string(),
+            symbol: "ETH/USDT".to_string(),
+            risk_amount: dec!(800),
+            risk_percentage: dec!(0.08),
+            opened_at: SystemTime::now(),
+            unrealized_pnl: dec!(0),
+            max_adverse_excursion: dec!(0),
+        });
+        let protocol = Arc::new(RiskManagementProtocol::new().add_rule(rule));
+
+        // Without auto-reduction the trade is rejected outright
+        let strict = RiskDecider::new(protocol.clone());
+        let result = strict.decide_trade(proposal(dec!(0.04))).await.unwrap();
+        assert!(matches!(result.decision, RiskDecision::Reject { .. }));
+
+        // 4% over a $2,000 stop sizes at 0.2 BTC; 2% of budget fits 0.1 BTC
+        let decider = RiskDecider::new(protocol).with_auto_reduce(dec!(100));
+        let result = decider.decide_trade(proposal(dec!(0.04))).await.unwrap();
+        match result.decision {
+            RiskDecision::Execute { approved_position_size, size_reduction, .. } => {
+                assert_eq!(approved_position_size, dec!(0.1));
+                let reduction = size_reduction.expect("reduction should be flagged");
+                assert_eq!(reduction.requested_size, dec!(0.2));
+                assert_eq!(reduction.reduced_size, dec!(0.1));
+                assert_eq!(reduction.reduced_risk, dec!(0.02));
+                assert!(reduction.notice.contains("0.2 to 0.1"));
+            }
+            other => panic!("expected a reduced execution, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_audit_trail_counts_only_rules_that_ran() {
+        use prudentia::risk::MaxTradeRiskRule;
+
+        // 3% breaks the conservative 2% cap, so the aggressive rule never runs
+        let protocol = RiskManagementProtocol::new()
+            .add_rule(MaxTradeRiskRule::conservative())
+            .add_rule(MaxTradeRiskRule::aggressive())
+            .with_fail_on_critical(true);
+        let decider = RiskDecider::new(Arc::new(protocol));
+        let result = decider.decide_trade(proposal(dec!(0.03))).await.unwrap();
+
+        assert!(matches!(result.decision, RiskDecision::Reject { .. }));
+        assert_eq!(result.audit_trail[0], "Rules executed: 1");
+        assert_eq!(result.rejecting_rules, vec!["MaxTradeRisk"]);
+    }
+}
@@ -2,12 +2,20 @@
 
 use crate::types::{ExecutionPlan, TradeSetup};
 use chrono::Utc;
+use disciplina::PositionSize;
+use rust_decimal::{Decimal, RoundingStrategy};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 use testudo_types::{
-    ExchangeAdapterTrait, OrderResult, OrderStatus, OrderType, TradeOrder,
+    ExchangeAdapterTrait, OcoModification, OrderResult, OrderSide, OrderStatus, OrderType,
+    TradeOrder,
 };
 use thiserror::Error;
 use uuid::Uuid;
 
+/// Decimal places TWAP child order sizes are rounded down to
+const TWAP_SLICE_SCALE: u32 = 8;
+
 /// Errors that can occur during trade execution.
 #[derive(Debug, Error)]
 pub enum ExecutorError {
@@ -17,25 +25,156 @@ pub enum ExecutorError {
     Timeout(std::time::Duration),
     #[error("Pre-flight check failed: {0}")]
     PreFlightCheckFailed(String),
+    #[error("Invalid bracket modification: {0}")]
+    InvalidModification(String),
+    #[error("Order failed sanity check: {0}")]
+    SanityCheckFailed(String),
+    /// The entry filled but its stop was refused, so the position was closed
+    #[error("Protective stop was refused ({reason}); the entry was flattened")]
+    EntryFlattened { reason: String },
+    /// The entry filled, its stop was refused and closing it failed too
+    #[error("Position is unprotected: stop refused ({reason}), flatten failed ({flatten_error})")]
+    UnprotectedPosition { reason: String, flatten_error: String },
+    /// A staggered entry stopped early; the filled part is open and protected
+    #[error("Entry stopped after filling {filled_quantity}: {reason}")]
+    PartialEntry {
+        filled_quantity: Decimal,
+        protective_order_id: String,
+        reason: String,
+    },
+}
+
+/// Attempts at placing a protective stop before the entry is flattened
+const STOP_PLACEMENT_ATTEMPTS: u32 = 2;
+
+/// Last-line-of-defense bounds checked on every plan before it is submitted.
+///
+/// These duplicate upstream risk checks on purpose: a plan that violates them
+/// indicates a bug earlier in the loop and is refused outright.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExecutionSafetyLimits {
+    /// Maximum order notional as a multiple of account equity
+    pub max_leverage: Decimal,
+    /// Maximum loss at the stop as a fraction of account equity
+    pub max_trade_risk: Decimal,
+}
+
+impl Default for ExecutionSafetyLimits {
+    fn default() -> Self {
+        Self {
+            max_leverage: Decimal::ONE,
+            max_trade_risk: prudentia::ProtocolLimits::default_limits().max_individual_trade_risk,
+        }
+    }
+}
+
+/// How an approved entry is worked on the exchange.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExecutionMode {
+    /// A single market order for the full size
+    #[default]
+    Immediate,
+    /// `slices` market orders summing to the full size, spaced evenly over
+    /// `duration` to reduce the market impact of large entries; the
+    /// protective stop is placed once the last slice has filled, or on the
+    /// filled part if the ladder stops early
+    Twap { slices: u32, duration: Duration },
+}
+
+/// Entry orders placed so far, and why the entry stopped short of its size
+struct EntryFills {
+    fills: Vec<OrderResult>,
+    interrupted: Option<String>,
+}
+
+/// Tells a detached entry that its caller has gone away
+struct CancelOnDrop(std::sync::Arc<AtomicBool>);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
 }
 
 /// The result of a trade execution.
 #[derive(Debug, Clone)]
 pub struct ExecutionResult {
+    /// The first (or only) entry order
     pub order_id: String,
+    /// Every entry order placed, in order; one unless the entry was staggered
+    pub child_order_ids: Vec<String>,
     pub status: OrderStatus,
+    /// Cumulative quantity filled across the entry orders
+    pub filled_quantity: Decimal,
+    /// Fill-weighted average entry price across the entry orders
+    pub average_entry_price: Decimal,
     pub executed_at: chrono::DateTime<Utc>,
     pub execution_time_ms: u64,
+    /// Stop-limit order protecting the position, when slippage tolerance is set
+    pub protective_order_id: Option<String>,
+    /// Exchange the orders were placed on; later changes to them go there too
+    pub venue: String,
+}
+
+/// The outcome of modifying the legs of an open bracket order.
+#[derive(Debug, Clone)]
+pub struct BracketModification {
+    pub order: OrderResult,
+    /// The setup with the new stop and take-profit applied
+    pub setup: TradeSetup,
+    /// Risk amount before the modification, in quote currency
+    pub previous_risk: Decimal,
+    /// Risk amount after the modification, in quote currency
+    pub new_risk: Decimal,
+}
+
+/// A standalone stop protecting a position opened outside the loop.
+#[derive(Debug, Clone)]
+pub struct ProtectiveStop {
+    pub order: OrderResult,
+    /// The position as seen from the current market price
+    pub setup: TradeSetup,
+    /// Loss if the stop is hit from the current price, in quote currency
+    pub risk_amount: Decimal,
 }
 
 /// The Executor component for the OODA loop's Act phase.
+#[derive(Clone)]
 pub struct Executor {
     exchange: std::sync::Arc<dyn ExchangeAdapterTrait + Send + Sync>,
+    /// Routes orders to a plan's preferred exchange, failing over when unhealthy
+    router: Option<std::sync::Arc<prudentia::ExchangeManager>>,
+    safety_limits: ExecutionSafetyLimits,
+    execution_mode: ExecutionMode,
 }
 
 impl Executor {
     pub fn new(exchange: std::sync::Arc<dyn ExchangeAdapterTrait + Send + Sync>) -> Self {
-        Self { exchange }
+        Self {
+            exchange,
+            router: None,
+            safety_limits: ExecutionSafetyLimits::default(),
+            execution_mode: ExecutionMode::default(),
+        }
+    }
+
+    /// Execute on each plan's preferred exchange through `router`
+    ///
+    /// Plans without a preference, or whose preferred exchange is unhealthy,
+    /// go to the router's failover primary.
+    pub fn with_router(mut self, router: std::sync::Arc<prudentia::ExchangeManager>) -> Self {
+        self.router = Some(router);
+        self
+    }
+
+    pub fn with_safety_limits(mut self, safety_limits: ExecutionSafetyLimits) -> Self {
+        self.safety_limits = safety_limits;
+        self
+    }
+
+    pub fn with_execution_mode(mut self, execution_mode: ExecutionMode) -> Self {
+        self.execution_mode = execution_mode;
+        self
     }
 
     pub async fn execute_trade(
@@ -44,31 +183,521 @@ impl Executor {
     ) -> Result<ExecutionResult, ExecutorError> {
         let start_time = std::time::Instant::now();
 
-        self.run_pre_flight_checks(&plan.setup).await?;
+        self.check_plan_sanity(&plan)?;
+        let exchange = self.venue_for(&plan).await;
+        Self::run_pre_flight_checks(exchange.as_ref(), &plan.setup).await?;
 
-        let trade_order = self.create_trade_order(&plan.setup)?;
+        self.run_entry(exchange, plan, start_time).await
+    }
+
+    /// Spend `quote_amount` of quote currency on the plan's symbol.
+    ///
+    /// The plan's position size is replaced by the base quantity the amount
+    /// buys at the current market price, and the plan is re-checked against
+    /// that position before anything is submitted, so a quote-sized entry is
+    /// held to the same caps as one sized in base units. Venues advertising
+    /// `quote_orders` receive the amount natively as a single market order;
+    /// elsewhere the quantity is floored to `lot_step` and placed through the
+    /// configured execution mode.
+    pub async fn execute_quote_trade(
+        &self,
+        mut plan: ExecutionPlan,
+        quote_amount: Decimal,
+        lot_step: Decimal,
+    ) -> Result<ExecutionResult, ExecutorError> {
+        let start_time = std::time::Instant::now();
+
+        if quote_amount <= Decimal::ZERO {
+            return Err(ExecutorError::SanityCheckFailed(format!(
+                "Quote amount {} is not positive",
+                quote_amount
+            )));
+        }
+
+        let exchange = self.venue_for(&plan).await;
+        let market = exchange
+            .get_market_data(&plan.setup.symbol)
+            .await
+            .map_err(|e| ExecutorError::ExchangeError(e.to_string()))?;
+        if market.last_price <= Decimal::ZERO {
+            return Err(ExecutorError::SanityCheckFailed(format!(
+                "Market price {} for {} is not positive",
+                market.last_price, plan.setup.symbol
+            )));
+        }
+        let native = exchange.capabilities().quote_orders;
+
+        let quantity = PositionSize::new(quote_amount / market.last_price)
+            .and_then(|size| if native { Ok(size) } else { size.round_to_step(lot_step) })
+            .map_err(|e| ExecutorError::SanityCheckFailed(e.to_string()))?;
+        plan.setup.entry_price = market.last_price;
+        plan.setup.position_size = quantity.value();
 
-        let order_result = self
-            .exchange
-            .place_order(&trade_order)
+        self.check_plan_sanity(&plan)?;
+        Self::run_pre_flight_checks(exchange.as_ref(), &plan.setup).await?;
+
+        if !native {
+            return self.run_entry(exchange, plan, start_time).await;
+        }
+
+        let mut order = create_trade_order(&plan.setup, plan.setup.position_size);
+        order.quote_quantity = Some(quote_amount);
+        let fill = exchange
+            .place_order(&order)
             .await
             .map_err(|e| ExecutorError::ExchangeError(e.to_string()))?;
+        // The venue settles the base quantity; protect what actually filled
+        plan.setup.position_size = fill.executed_quantity;
+        let entry = EntryFills { fills: vec![fill], interrupted: None };
+        Self::complete_execution(exchange.as_ref(), plan, entry, start_time).await
+    }
+
+    /// Work the entry and its stop on a detached task.
+    ///
+    /// A caller that stops waiting (an API timeout, say) must not leave a
+    /// half-placed ladder unprotected: dropping this future flags the task,
+    /// which places no further slices and puts the stop on what filled.
+    async fn run_entry(
+        &self,
+        exchange: std::sync::Arc<dyn ExchangeAdapterTrait + Send + Sync>,
+        plan: ExecutionPlan,
+        start_time: std::time::Instant,
+    ) -> Result<ExecutionResult, ExecutorError> {
+        let cancelled = std::sync::Arc::new(AtomicBool::new(false));
+        let _cancel_on_drop = CancelOnDrop(cancelled.clone());
+        let execution_mode = self.execution_mode;
+
+        let task = tokio::spawn(async move {
+            let entry =
+                Self::place_entry(exchange.as_ref(), &plan.setup, execution_mode, &cancelled)
+                    .await?;
+            let symbol = plan.setup.symbol.clone();
+            let result = Self::complete_execution(exchange.as_ref(), plan, entry, start_time).await;
+            if cancelled.load(Ordering::SeqCst) {
+                match &result {
+                    Ok(execution) => tracing::warn!(
+                        symbol = %symbol,
+                        filled = %execution.filled_quantity,
+                        "Entry completed after its caller stopped waiting"
+                    ),
+                    Err(e) => tracing::warn!(
+                        symbol = %symbol,
+                        error = %e,
+                        "Entry interrupted after its caller stopped waiting"
+                    ),
+                }
+            }
+            result
+        });
+
+        task.await
+            .map_err(|e| ExecutorError::ExchangeError(format!("Entry task failed: {}", e)))?
+    }
+
+    /// Place the protective stop, if any, and summarize the entry fills.
+    ///
+    /// An entry that stopped short is always protected, with a plain stop
+    /// when the plan has no slippage tolerance, and reported as a
+    /// `PartialEntry`.
+    async fn complete_execution(
+        exchange: &(dyn ExchangeAdapterTrait + Send + Sync),
+        plan: ExecutionPlan,
+        entry: EntryFills,
+        start_time: std::time::Instant,
+    ) -> Result<ExecutionResult, ExecutorError> {
+        let EntryFills { fills, interrupted } = entry;
+        let filled_quantity: Decimal = fills.iter().map(|fill| fill.executed_quantity).sum();
+
+        if let Some(reason) = interrupted {
+            let stop_order = match plan.stop_slippage_tolerance {
+                Some(tolerance) => stop_limit_order(&plan.setup, filled_quantity, tolerance),
+                None => stop_loss_order(&plan.setup, filled_quantity),
+            };
+            let protective_order_id =
+                Self::protect_or_flatten(exchange, &plan.setup, stop_order).await?;
+            return Err(ExecutorError::PartialEntry {
+                filled_quantity,
+                protective_order_id,
+                reason,
+            });
+        }
+
+        let protective_order_id = match plan.stop_slippage_tolerance {
+            Some(tolerance) => {
+                let stop_order = stop_limit_order(&plan.setup, filled_quantity, tolerance);
+                Some(Self::protect_or_flatten(exchange, &plan.setup, stop_order).await?)
+            }
+            None => None,
+        };
+
+        let average_entry_price = if filled_quantity > Decimal::ZERO {
+            fills
+                .iter()
+                .map(|fill| fill.executed_price * fill.executed_quantity)
+                .sum::<Decimal>()
+                / filled_quantity
+        } else {
+            Decimal::ZERO
+        };
 
         Ok(ExecutionResult {
-            order_id: order_result.order_id,
-            status: order_result.status,
+            order_id: fills[0].order_id.clone(),
+            child_order_ids: fills.iter().map(|fill| fill.order_id.clone()).collect(),
+            status: fills[fills.len() - 1].status,
+            filled_quantity,
+            average_entry_price,
             executed_at: Utc::now(),
             execution_time_ms: start_time.elapsed().as_millis() as u64,
+            protective_order_id,
+            venue: exchange.exchange_name().to_string(),
         })
     }
 
-    async fn run_pre_flight_checks(&self, setup: &TradeSetup) -> Result<(), ExecutorError> {
-        if !self.exchange.health_check().await.unwrap_or(false) {
+    /// Place `stop_order` for a filled entry, retrying once before closing the
+    /// position at market: a filled entry is never left without its stop.
+    async fn protect_or_flatten(
+        exchange: &(dyn ExchangeAdapterTrait + Send + Sync),
+        setup: &TradeSetup,
+        stop_order: TradeOrder,
+    ) -> Result<String, ExecutorError> {
+        let mut reason = String::new();
+        for attempt in 1..=STOP_PLACEMENT_ATTEMPTS {
+            let order = TradeOrder {
+                client_order_id: Uuid::new_v4().to_string(),
+                ..stop_order.clone()
+            };
+            match exchange.place_order(&order).await {
+                Ok(result) => return Ok(result.order_id),
+                Err(e) => {
+                    tracing::warn!(
+                        symbol = %setup.symbol,
+                        attempt,
+                        error = %e,
+                        "Protective stop was refused"
+                    );
+                    reason = e.to_string();
+                }
+            }
+        }
+
+        let flatten = TradeOrder {
+            client_order_id: Uuid::new_v4().to_string(),
+            symbol: setup.symbol.clone(),
+            side: stop_order.side,
+            order_type: OrderType::Market,
+            quantity: stop_order.quantity,
+            price: None,
+            stop_price: None,
+            quote_quantity: None,
+        };
+        match exchange.place_order(&flatten).await {
+            Ok(_) => Err(ExecutorError::EntryFlattened { reason }),
+            Err(e) => {
+                tracing::error!(
+                    symbol = %setup.symbol,
+                    quantity = %stop_order.quantity,
+                    error = %e,
+                    "Failed to flatten an entry left without a protective stop"
+                );
+                Err(ExecutorError::UnprotectedPosition {
+                    reason,
+                    flatten_error: e.to_string(),
+                })
+            }
+        }
+    }
+
+    /// Place the entry according to the execution mode, returning every fill.
+    ///
+    /// Only a failure of the first order is an error; once something has
+    /// filled, a failed child or a cancellation ends the ladder and is
+    /// reported alongside the fills so the caller can protect them.
+    async fn place_entry(
+        exchange: &(dyn ExchangeAdapterTrait + Send + Sync),
+        setup: &TradeSetup,
+        execution_mode: ExecutionMode,
+        cancelled: &AtomicBool,
+    ) -> Result<EntryFills, ExecutorError> {
+        let (slices, duration) = match execution_mode {
+            ExecutionMode::Immediate => (1, Duration::ZERO),
+            ExecutionMode::Twap { slices: 0, .. } => {
+                return Err(ExecutorError::SanityCheckFailed(
+                    "TWAP execution needs at least one slice".to_string(),
+                ));
+            }
+            ExecutionMode::Twap { slices, duration } => (slices, duration),
+        };
+
+        // Equal slices rounded down; the last takes the remainder so the
+        // children sum to exactly the planned size
+        let slice_size = (setup.position_size / Decimal::from(slices))
+            .round_dp_with_strategy(TWAP_SLICE_SCALE, RoundingStrategy::ToZero);
+        if slices > 1 && slice_size.is_zero() {
+            return Err(ExecutorError::SanityCheckFailed(format!(
+                "Position size {} is too small to split into {} slices",
+                setup.position_size, slices
+            )));
+        }
+        let interval = duration / slices;
+
+        let mut fills = Vec::with_capacity(slices as usize);
+        for slice in 0..slices {
+            if slice > 0 {
+                tokio::time::sleep(interval).await;
+                if cancelled.load(Ordering::SeqCst) {
+                    return Ok(EntryFills {
+                        fills,
+                        interrupted: Some(format!(
+                            "cancelled after {} of {} slices",
+                            slice, slices
+                        )),
+                    });
+                }
+            }
+            let quantity = if slice + 1 == slices {
+                setup.position_size - slice_size * Decimal::from(slices - 1)
+            } else {
+                slice_size
+            };
+            let order = create_trade_order(setup, quantity);
+            match exchange.place_order(&order).await {
+                Ok(fill) => fills.push(fill),
+                Err(e) if fills.is_empty() => {
+                    return Err(ExecutorError::ExchangeError(e.to_string()));
+                }
+                Err(e) => {
+                    return Ok(EntryFills {
+                        fills,
+                        interrupted: Some(format!(
+                            "slice {} of {} failed: {}",
+                            slice + 1,
+                            slices,
+                            e
+                        )),
+                    });
+                }
+            }
+        }
+
+        Ok(EntryFills { fills, interrupted: None })
+    }
+
+    /// Move the stop and/or take-profit of an existing bracket order.
+    ///
+    /// The new legs are validated against the current market price before the
+    /// exchange is contacted: a long's stop must stay below the market and its
+    /// target above it, and the reverse for a short. The returned risk amounts
+    /// let the caller update tracked portfolio risk. `venue` is the exchange
+    /// holding the order, as recorded in its [`ExecutionResult`]; `None` means
+    /// the default exchange.
+    pub async fn modify_bracket(
+        &self,
+        order_id: &str,
+        setup: &TradeSetup,
+        venue: Option<&str>,
+        new_stop: Option<Decimal>,
+        new_take_profit: Option<Decimal>,
+    ) -> Result<BracketModification, ExecutorError> {
+        if new_stop.is_none() && new_take_profit.is_none() {
+            return Err(ExecutorError::InvalidModification(
+                "No stop or take-profit change requested".to_string(),
+            ));
+        }
+
+        let exchange = self.venue_named(venue).await?;
+        let market = exchange
+            .get_market_data(&setup.symbol)
+            .await
+            .map_err(|e| ExecutorError::ExchangeError(e.to_string()))?;
+
+        let mut updated = setup.clone();
+        if let Some(stop) = new_stop {
+            let valid = match setup.side {
+                OrderSide::Buy => stop < market.last_price,
+                OrderSide::Sell => stop > market.last_price,
+            };
+            if !valid {
+                return Err(ExecutorError::InvalidModification(format!(
+                    "Stop {} is on the wrong side of market price {} for a {:?} position",
+                    stop, market.last_price, setup.side
+                )));
+            }
+            updated.stop_loss = stop;
+        }
+        if let Some(take_profit) = new_take_profit {
+            let valid = match setup.side {
+                OrderSide::Buy => take_profit > market.last_price,
+                OrderSide::Sell => take_profit < market.last_price,
+            };
+            if !valid {
+                return Err(ExecutorError::InvalidModification(format!(
+                    "Take-profit {} is on the wrong side of market price {} for a {:?} position",
+                    take_profit, market.last_price, setup.side
+                )));
+            }
+            updated.take_profit = Some(take_profit);
+        }
+
+        let modification = OcoModification {
+            order_id: order_id.to_string(),
+            symbol: setup.symbol.clone(),
+            stop_price: new_stop,
+            take_profit_price: new_take_profit,
+        };
+        let order = exchange
+            .modify_oco_order(&modification)
+            .await
+            .map_err(|e| ExecutorError::ExchangeError(e.to_string()))?;
+
+        Ok(BracketModification {
+            order,
+            previous_risk: setup.risk_amount(),
+            new_risk: updated.risk_amount(),
+            setup: updated,
+        })
+    }
+
+    /// Place a stop-loss order for an existing position, without an entry leg.
+    ///
+    /// For positions imported or opened manually: `side` is the side of the
+    /// position being protected. The stop must be on the losing side of the
+    /// current market price, and the returned risk is measured from that
+    /// price so the caller can register it with the protocol. The stop is
+    /// placed on `venue`, the exchange holding the position, or on the
+    /// default exchange when that is not known.
+    pub async fn attach_protective_stop(
+        &self,
+        symbol: &str,
+        venue: Option<&str>,
+        side: OrderSide,
+        quantity: Decimal,
+        stop_price: Decimal,
+    ) -> Result<ProtectiveStop, ExecutorError> {
+        let exchange = self.venue_named(venue).await?;
+        let market = exchange
+            .get_market_data(symbol)
+            .await
+            .map_err(|e| ExecutorError::ExchangeError(e.to_string()))?;
+
+        let valid = match side {
+            OrderSide::Buy => stop_price < market.last_price,
+            OrderSide::Sell => stop_price > market.last_price,
+        };
+        if !valid {
+            return Err(ExecutorError::InvalidModification(format!(
+                "Stop {} is on the wrong side of market price {} for a {:?} position",
+                stop_price, market.last_price, side
+            )));
+        }
+
+        let setup = TradeSetup {
+            symbol: symbol.to_string(),
+            entry_price: market.last_price,
+            stop_loss: stop_price,
+            take_profit: None,
+            position_size: quantity,
+            side,
+        };
+        let order = exchange
+            .place_order(&stop_loss_order(&setup, quantity))
+            .await
+            .map_err(|e| ExecutorError::ExchangeError(e.to_string()))?;
+
+        Ok(ProtectiveStop {
+            order,
+            risk_amount: setup.risk_amount(),
+            setup,
+        })
+    }
+
+    /// Refuse plans whose size is implausible for the account.
+    fn check_plan_sanity(&self, plan: &ExecutionPlan) -> Result<(), ExecutorError> {
+        let setup = &plan.setup;
+        if plan.account_equity <= Decimal::ZERO {
+            return Err(ExecutorError::SanityCheckFailed(format!(
+                "Account equity {} is not positive",
+                plan.account_equity
+            )));
+        }
+        if setup.position_size <= Decimal::ZERO {
+            return Err(ExecutorError::SanityCheckFailed(format!(
+                "Position size {} is not positive",
+                setup.position_size
+            )));
+        }
+
+        let notional = setup.position_size * setup.entry_price;
+        let max_notional = plan.account_equity * self.safety_limits.max_leverage;
+        if notional > max_notional {
+            return Err(ExecutorError::SanityCheckFailed(format!(
+                "Order notional {} exceeds {}x account equity ({})",
+                notional, self.safety_limits.max_leverage, max_notional
+            )));
+        }
+
+        if let Some(tolerance) = plan.stop_slippage_tolerance {
+            if tolerance <= Decimal::ZERO || tolerance >= Decimal::ONE {
+                return Err(ExecutorError::SanityCheckFailed(format!(
+                    "Stop slippage tolerance {} must be between 0 and 1",
+                    tolerance
+                )));
+            }
+        }
+
+        let trade_risk = setup.risk_amount() / plan.account_equity;
+        if trade_risk > self.safety_limits.max_trade_risk {
+            return Err(ExecutorError::SanityCheckFailed(format!(
+                "Trade risk {:.4} exceeds individual cap {}",
+                trade_risk, self.safety_limits.max_trade_risk
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Exchange a plan executes on
+    async fn venue_for(
+        &self,
+        plan: &ExecutionPlan,
+    ) -> std::sync::Arc<dyn ExchangeAdapterTrait + Send + Sync> {
+        let routed = match &self.router {
+            Some(router) => router.route_adapter(plan.preferred_exchange.as_deref()).await,
+            None => None,
+        };
+        routed.unwrap_or_else(|| self.exchange.clone())
+    }
+
+    /// Exchange already holding an order or position
+    ///
+    /// Unlike [`venue_for`](Self::venue_for) this never fails over: a change
+    /// sent anywhere but the named exchange would not reach the order.
+    async fn venue_named(
+        &self,
+        venue: Option<&str>,
+    ) -> Result<std::sync::Arc<dyn ExchangeAdapterTrait + Send + Sync>, ExecutorError> {
+        let Some(venue) = venue.filter(|venue| *venue != self.exchange.exchange_name()) else {
+            return Ok(self.exchange.clone());
+        };
+        if let Some(router) = &self.router {
+            for (_, adapter) in router.adapters().await {
+                if adapter.exchange_name() == venue {
+                    return Ok(adapter);
+                }
+            }
+        }
+        Err(ExecutorError::ExchangeError(format!("Exchange {} is not configured", venue)))
+    }
+
+    async fn run_pre_flight_checks(
+        exchange: &(dyn ExchangeAdapterTrait + Send + Sync),
+        setup: &TradeSetup,
+    ) -> Result<(), ExecutorError> {
+        if !exchange.health_check().await.unwrap_or(false) {
             return Err(ExecutorError::PreFlightCheckFailed(
                 "Exchange is not healthy".to_string(),
             ));
         }
-        if !self.exchange.is_symbol_supported(&setup.symbol).await.unwrap_or(false) {
+        if !exchange.is_symbol_supported(&setup.symbol).await.unwrap_or(false) {
             return Err(ExecutorError::PreFlightCheckFailed(format!(
                 "Symbol {} is not supported by the exchange",
                 setup.symbol
@@ -77,15 +706,592 @@ impl Executor {
         Ok(())
     }
 
-    fn create_trade_order(&self, setup: &TradeSetup) -> Result<TradeOrder, ExecutorError> {
-        Ok(TradeOrder {
-            client_order_id: Uuid::new_v4().to_string(),
+}
+
+fn create_trade_order(setup: &TradeSetup, quantity: Decimal) -> TradeOrder {
+    TradeOrder {
+        client_order_id: Uuid::new_v4().to_string(),
+        symbol: setup.symbol.clone(),
+        side: setup.side,
+        order_type: OrderType::Market,
+        quantity,
+        price: None, // Market order
+        stop_price: Some(setup.stop_loss),
+        quote_quantity: None,
+    }
+}
+
+fn exit_side(side: OrderSide) -> OrderSide {
+    match side {
+        OrderSide::Buy => OrderSide::Sell,
+        OrderSide::Sell => OrderSide::Buy,
+    }
+}
+
+/// Stop-market order closing `quantity` of the position at the stop
+fn stop_loss_order(setup: &TradeSetup, quantity: Decimal) -> TradeOrder {
+    TradeOrder {
+        client_order_id: Uuid::new_v4().to_string(),
+        symbol: setup.symbol.clone(),
+        side: exit_side(setup.side),
+        order_type: OrderType::StopLoss,
+        quantity,
+        price: None,
+        stop_price: Some(setup.stop_loss),
+        quote_quantity: None,
+    }
+}
+
+/// Stop-limit order closing `quantity` of the position at the stop
+fn stop_limit_order(setup: &TradeSetup, quantity: Decimal, slippage_tolerance: Decimal) -> TradeOrder {
+    TradeOrder {
+        client_order_id: Uuid::new_v4().to_string(),
+        symbol: setup.symbol.clone(),
+        side: exit_side(setup.side),
+        order_type: OrderType::StopLossLimit,
+        quantity,
+        price: Some(setup.stop_limit_price(slippage_tolerance)),
+        stop_price: Some(setup.stop_loss),
+        quote_quantity: None,
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prudentia::exchange::MockExchange;
+    use prudentia::TestudoProtocol;
+    use rust_decimal_macros::dec;
+    use std::sync::Arc;
+    use testudo_types::ExchangeCapabilities;
+
+    fn long_setup() -> TradeSetup {
+        TradeSetup {
+            symbol: "BTC/USDT".to_string(),
+            entry_price: dec!(48000),
+            stop_loss: dec!(46000),
+            take_profit: Some(dec!(54000)),
+            position_size: dec!(0.1),
+            side: OrderSide::Buy,
+        }
+    }
+
+    async fn open_bracket(exchange: &MockExchange, setup: &TradeSetup) -> String {
+        let order = TradeOrder {
+            client_order_id: "bracket-1".to_string(),
             symbol: setup.symbol.clone(),
             side: setup.side,
-            order_type: OrderType::Market,
+            order_type: OrderType::Limit,
             quantity: setup.position_size,
-            price: None, // Market order
+            price: Some(setup.entry_price),
             stop_price: Some(setup.stop_loss),
-        })
+            quote_quantity: None,
+        };
+        exchange.place_order(&order).await.unwrap().order_id
+    }
+
+    #[tokio::test]
+    async fn test_move_stop_to_breakeven_updates_portfolio_risk() {
+        let exchange = Arc::new(MockExchange::new());
+        let executor = Executor::new(exchange.clone());
+        let setup = long_setup();
+        let order_id = open_bracket(&exchange, &setup).await;
+
+        // 0.1 BTC risking $2,000 per BTC on $10,000 equity = 2% portfolio risk
+        let equity = dec!(10000);
+        let mut protocol = TestudoProtocol::new();
+        protocol.update_position_risk(&setup.symbol, Decimal::ZERO, setup.risk_amount() / equity);
+        assert_eq!(protocol.get_status().total_portfolio_risk, dec!(0.02));
+
+        // Market is at 50,000 so a stop at the 48,000 entry is below it
+        let result = executor
+            .modify_bracket(&order_id, &setup, None, Some(setup.entry_price), None)
+            .await
+            .unwrap();
+
+        let modifications = exchange.get_oco_modifications().await;
+        assert_eq!(modifications.len(), 1);
+        assert_eq!(modifications[0].order_id, order_id);
+        assert_eq!(modifications[0].stop_price, Some(dec!(48000)));
+        assert_eq!(modifications[0].take_profit_price, None);
+
+        assert_eq!(result.setup.stop_loss, dec!(48000));
+        assert_eq!(result.previous_risk, dec!(200));
+        assert_eq!(result.new_risk, Decimal::ZERO);
+
+        protocol.update_position_risk(
+            &setup.symbol,
+            result.previous_risk / equity,
+            result.new_risk / equity,
+        );
+        assert_eq!(protocol.get_status().total_portfolio_risk, Decimal::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_stop_on_external_position_registers_its_risk() {
+        let exchange = Arc::new(MockExchange::new());
+        let executor = Executor::new(exchange.clone());
+
+        // A position bought manually, outside the OODA loop
+        exchange
+            .place_order(&TradeOrder {
+                client_order_id: "manual-1".to_string(),
+                symbol: "BTC/USDT".to_string(),
+                side: OrderSide::Buy,
+                order_type: OrderType::Market,
+                quantity: dec!(0.1),
+                price: None,
+                stop_price: None,
+                quote_quantity: None,
+            })
+            .await
+            .unwrap();
+
+        // Market is at 50,000: a 48,000 stop on 0.1 BTC risks $200
+        let stop = executor
+            .attach_protective_stop("BTC/USDT", None, OrderSide::Buy, dec!(0.1), dec!(48000))
+            .await
+            .unwrap();
+        assert_eq!(stop.risk_amount, dec!(200));
+
+        let orders = exchange.get_placed_orders().await;
+        assert_eq!(orders.len(), 2);
+        assert!(orders.iter().any(|order| order.order_id == stop.order.order_id));
+
+        let equity = dec!(10000);
+        let position_id = Uuid::new_v4();
+        let mut protocol = TestudoProtocol::new();
+        protocol.track_external_position(position_id, "BTC/USDT", stop.risk_amount / equity);
+
+        let status = protocol.get_status();
+        assert_eq!(status.total_portfolio_risk, dec!(0.02));
+        assert_eq!(status.open_positions, 1);
+        assert_eq!(protocol.tracked_position(position_id).unwrap().risk, dec!(0.02));
+
+        // A stop above the market cannot protect a long
+        let error = executor
+            .attach_protective_stop("BTC/USDT", None, OrderSide::Buy, dec!(0.1), dec!(51000))
+            .await
+            .unwrap_err();
+        assert!(matches!(error, ExecutorError::InvalidModification(_)));
+    }
+
+    #[tokio::test]
+    async fn test_oversized_plan_is_refused_before_submission() {
+        let exchange = Arc::new(MockExchange::new());
+        let executor = Executor::new(exchange.clone());
+
+        // 2 BTC at 48,000 is a 96,000 notional on a 10,000 account
+        let mut setup = long_setup();
+        setup.position_size = dec!(2);
+        let plan = ExecutionPlan {
+            setup,
+            approved: true,
+            risk_assessment: "Trade approved by Testudo Protocol".to_string(),
+            account_equity: dec!(10000),
+            stop_slippage_tolerance: None,
+            violations: Vec::new(),
+            failed_rules: Vec::new(),
+            rejecting_rules: Vec::new(),
+            preferred_exchange: None,
+            size_reduction: None,
+            position_id: Uuid::new_v4(),
+            execution: None,
+        };
+
+        let result = executor.execute_trade(plan).await;
+
+        assert!(matches!(result, Err(ExecutorError::SanityCheckFailed(_))));
+        assert!(exchange.get_placed_orders().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_plan_exceeding_risk_cap_is_refused() {
+        let exchange = Arc::new(MockExchange::new());
+        let executor = Executor::new(exchange.clone()).with_safety_limits(ExecutionSafetyLimits {
+            max_leverage: dec!(3),
+            max_trade_risk: dec!(0.06),
+        });
+
+        // Within 3x leverage, but 0.5 BTC * 2,000 stop distance = 10% risk
+        let mut setup = long_setup();
+        setup.position_size = dec!(0.5);
+        let plan = ExecutionPlan {
+            setup,
+            approved: true,
+            risk_assessment: "Trade approved by Testudo Protocol".to_string(),
+            account_equity: dec!(10000),
+            stop_slippage_tolerance: None,
+            violations: Vec::new(),
+            failed_rules: Vec::new(),
+            rejecting_rules: Vec::new(),
+            preferred_exchange: None,
+            size_reduction: None,
+            position_id: Uuid::new_v4(),
+            execution: None,
+        };
+
+        let result = executor.execute_trade(plan).await;
+
+        assert!(matches!(result, Err(ExecutorError::SanityCheckFailed(_))));
+        assert!(exchange.get_placed_orders().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_bracket_changes_go_to_the_venue_that_placed_the_entry() {
+        let default = Arc::new(MockExchange::new());
+        let kraken = Arc::new(MockExchange::with_name("kraken".to_string()));
+        let router = Arc::new(prudentia::ExchangeManager::new(prudentia::ExchangeFailoverConfig {
+            primary_exchange: "kraken".to_string(),
+            backup_exchanges: Vec::new(),
+            health_check_interval_secs: 30,
+            health_check_jitter: false,
+        }));
+        router.add_adapter("kraken", kraken.clone()).await;
+        let executor = Executor::new(default.clone()).with_router(router);
+        let plan = ExecutionPlan {
+            setup: long_setup(),
+            approved: true,
+            risk_assessment: "Trade approved by Testudo Protocol".to_string(),
+            account_equity: dec!(10000),
+            stop_slippage_tolerance: None,
+            violations: Vec::new(),
+            failed_rules: Vec::new(),
+            rejecting_rules: Vec::new(),
+            preferred_exchange: Some("kraken".to_string()),
+            size_reduction: None,
+            position_id: Uuid::new_v4(),
+            execution: None,
+        };
+
+        let result = executor.execute_trade(plan.clone()).await.unwrap();
+        assert_eq!(result.venue, "kraken");
+
+        executor
+            .modify_bracket(&result.order_id, &plan.setup, Some(&result.venue), Some(dec!(47000)), None)
+            .await
+            .unwrap();
+        assert_eq!(kraken.get_oco_modifications().await.len(), 1);
+        assert!(default.get_oco_modifications().await.is_empty());
+
+        // A venue the executor cannot reach is refused rather than failed over
+        let error = executor
+            .modify_bracket(&result.order_id, &plan.setup, Some("binance"), Some(dec!(47000)), None)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, ExecutorError::ExchangeError(_)));
+        assert!(default.get_oco_modifications().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_stop_limit_price_follows_slippage_tolerance() {
+        let exchange = Arc::new(MockExchange::new());
+        let executor = Executor::new(exchange.clone());
+        let plan = ExecutionPlan {
+            setup: long_setup(),
+            approved: true,
+            risk_assessment: "Trade approved by Testudo Protocol".to_string(),
+            account_equity: dec!(10000),
+            stop_slippage_tolerance: Some(dec!(0.005)),
+            violations: Vec::new(),
+            failed_rules: Vec::new(),
+            rejecting_rules: Vec::new(),
+            preferred_exchange: None,
+            size_reduction: None,
+            position_id: Uuid::new_v4(),
+            execution: None,
+        };
+
+        // The long's stop sells at most 0.5% below the 46,000 trigger
+        let stop_order = stop_limit_order(&plan.setup, plan.setup.position_size, dec!(0.005));
+        assert_eq!(stop_order.order_type, OrderType::StopLossLimit);
+        assert_eq!(stop_order.side, OrderSide::Sell);
+        assert_eq!(stop_order.stop_price, Some(dec!(46000)));
+        assert_eq!(stop_order.price, Some(dec!(45770)));
+
+        // Execution submits the entry and then the stop-limit
+        let result = executor.execute_trade(plan).await.unwrap();
+        let orders = exchange.get_placed_orders().await;
+        assert_eq!(orders.len(), 2);
+        let protective_order_id = result.protective_order_id.unwrap();
+        assert_ne!(protective_order_id, result.order_id);
+        assert!(orders.iter().any(|order| order.order_id == protective_order_id));
+
+        let mut short_setup = long_setup();
+        short_setup.side = OrderSide::Sell;
+        short_setup.stop_loss = dec!(50000);
+        assert_eq!(short_setup.stop_limit_price(dec!(0.005)), dec!(50250));
+    }
+
+    #[tokio::test]
+    async fn test_refused_stop_flattens_the_entry() {
+        let exchange = Arc::new(MockExchange::new());
+        exchange.reject_order_type(OrderType::StopLossLimit).await;
+        let executor = Executor::new(exchange.clone());
+        let plan = ExecutionPlan {
+            setup: long_setup(),
+            approved: true,
+            risk_assessment: "Trade approved by Testudo Protocol".to_string(),
+            account_equity: dec!(10000),
+            stop_slippage_tolerance: Some(dec!(0.005)),
+            violations: Vec::new(),
+            failed_rules: Vec::new(),
+            rejecting_rules: Vec::new(),
+            preferred_exchange: None,
+            size_reduction: None,
+            position_id: Uuid::new_v4(),
+            execution: None,
+        };
+
+        let error = executor.execute_trade(plan).await.unwrap_err();
+        assert!(matches!(error, ExecutorError::EntryFlattened { .. }));
+
+        // Entry, then a market sell of the same size once the stop was
+        // refused twice
+        let orders = exchange.get_submitted_orders().await;
+        assert_eq!(orders.len(), 2);
+        assert_eq!(orders[0].side, OrderSide::Buy);
+        assert_eq!(orders[1].side, OrderSide::Sell);
+        assert_eq!(orders[1].order_type, OrderType::Market);
+        assert_eq!(orders[1].quantity, orders[0].quantity);
+
+        // A stop refused only once is retried and the position kept
+        let exchange = Arc::new(MockExchange::new());
+        exchange.reject_order_attempt(2).await;
+        let executor = Executor::new(exchange.clone());
+        let plan = ExecutionPlan {
+            setup: long_setup(),
+            approved: true,
+            risk_assessment: "Trade approved by Testudo Protocol".to_string(),
+            account_equity: dec!(10000),
+            stop_slippage_tolerance: Some(dec!(0.005)),
+            violations: Vec::new(),
+            failed_rules: Vec::new(),
+            rejecting_rules: Vec::new(),
+            preferred_exchange: None,
+            size_reduction: None,
+            position_id: Uuid::new_v4(),
+            execution: None,
+        };
+        let result = executor.execute_trade(plan).await.unwrap();
+        assert!(result.protective_order_id.is_some());
+        let orders = exchange.get_submitted_orders().await;
+        assert_eq!(orders.len(), 2);
+        assert_eq!(orders[1].order_type, OrderType::StopLossLimit);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_twap_entry_staggers_child_orders_and_blends_entry() {
+        let exchange = Arc::new(MockExchange::new());
+        let executor = Executor::new(exchange.clone()).with_execution_mode(ExecutionMode::Twap {
+            slices: 4,
+            duration: Duration::from_secs(60),
+        });
+        let plan = ExecutionPlan {
+            setup: long_setup(),
+            approved: true,
+            risk_assessment: "Trade approved by Testudo Protocol".to_string(),
+            account_equity: dec!(10000),
+            stop_slippage_tolerance: None,
+            violations: Vec::new(),
+            failed_rules: Vec::new(),
+            rejecting_rules: Vec::new(),
+            preferred_exchange: None,
+            size_reduction: None,
+            position_id: Uuid::new_v4(),
+            execution: None,
+        };
+
+        // The market ticks up by 100 after each child fills
+        let drift_market = async {
+            for (filled, price) in [(1, dec!(50100)), (2, dec!(50200)), (3, dec!(50300))] {
+                while exchange.get_placed_orders().await.len() < filled {
+                    tokio::time::sleep(Duration::from_millis(1)).await;
+                }
+                exchange
+                    .set_market_data(
+                        "BTC/USDT".to_string(),
+                        testudo_types::MarketData {
+                            symbol: "BTC/USDT".to_string(),
+                            bid_price: price - dec!(1),
+                            ask_price: price + dec!(1),
+                            last_price: price,
+                            volume_24h: dec!(1000),
+                            timestamp: std::time::SystemTime::now(),
+                        },
+                    )
+                    .await;
+            }
+        };
+        let started = tokio::time::Instant::now();
+        let (result, _) = tokio::join!(executor.execute_trade(plan), drift_market);
+        let result = result.unwrap();
+
+        // Four children 15s apart: the last goes out 45s after the first
+        assert_eq!(started.elapsed(), Duration::from_secs(45));
+        let orders = exchange.get_placed_orders().await;
+        assert_eq!(orders.len(), 4);
+        assert_eq!(result.child_order_ids.len(), 4);
+        assert!(orders.iter().all(|order| order.executed_quantity == dec!(0.025)));
+        assert_eq!(result.filled_quantity, dec!(0.1));
+        assert_eq!(result.average_entry_price, dec!(50150));
     }
-}
\ No newline at end of file
+
+    fn twap_plan(setup: TradeSetup) -> ExecutionPlan {
+        ExecutionPlan {
+            setup,
+            approved: true,
+            risk_assessment: "Trade approved by Testudo Protocol".to_string(),
+            account_equity: dec!(10000),
+            stop_slippage_tolerance: None,
+            violations: Vec::new(),
+            failed_rules: Vec::new(),
+            rejecting_rules: Vec::new(),
+            preferred_exchange: None,
+            size_reduction: None,
+            position_id: Uuid::new_v4(),
+            execution: None,
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_failed_twap_child_protects_the_filled_slices() {
+        let exchange = Arc::new(MockExchange::new());
+        exchange.reject_order_attempt(3).await;
+        let executor = Executor::new(exchange.clone()).with_execution_mode(ExecutionMode::Twap {
+            slices: 4,
+            duration: Duration::from_secs(60),
+        });
+
+        let error = executor.execute_trade(twap_plan(long_setup())).await.unwrap_err();
+        let ExecutorError::PartialEntry { filled_quantity, protective_order_id, .. } = error else {
+            panic!("expected a partial entry, got {:?}", error);
+        };
+        assert_eq!(filled_quantity, dec!(0.05));
+
+        // Two slices filled, the third was refused, the fourth never sent;
+        // a stop covers exactly what filled
+        let orders = exchange.get_submitted_orders().await;
+        assert_eq!(orders.len(), 3);
+        let stop = &orders[2];
+        assert_eq!(stop.order_type, OrderType::StopLoss);
+        assert_eq!(stop.side, OrderSide::Sell);
+        assert_eq!(stop.quantity, dec!(0.05));
+        assert_eq!(stop.stop_price, Some(dec!(46000)));
+        let placed = exchange.get_placed_orders().await;
+        assert!(placed.iter().any(|order| order.order_id == protective_order_id));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_cancelled_twap_entry_stops_and_protects_the_filled_slices() {
+        let exchange = Arc::new(MockExchange::new());
+        let executor = Executor::new(exchange.clone()).with_execution_mode(ExecutionMode::Twap {
+            slices: 4,
+            duration: Duration::from_secs(60),
+        });
+
+        // The caller gives up 20s in, after the slices at 0s and 15s
+        let timed_out = tokio::time::timeout(
+            Duration::from_secs(20),
+            executor.execute_trade(twap_plan(long_setup())),
+        )
+        .await;
+        assert!(timed_out.is_err());
+
+        tokio::time::sleep(Duration::from_secs(60)).await;
+        let orders = exchange.get_submitted_orders().await;
+        assert_eq!(orders.len(), 3);
+        assert!(orders[..2].iter().all(|order| order.side == OrderSide::Buy));
+        assert_eq!(orders[2].order_type, OrderType::StopLoss);
+        assert_eq!(orders[2].quantity, dec!(0.05));
+    }
+
+    #[tokio::test]
+    async fn test_twap_slices_rounding_to_zero_are_refused() {
+        let exchange = Arc::new(MockExchange::new());
+        let executor = Executor::new(exchange.clone()).with_execution_mode(ExecutionMode::Twap {
+            slices: 4,
+            duration: Duration::from_secs(60),
+        });
+
+        // 0.00000003 / 4 rounds down to zero at 8 decimal places
+        let mut setup = long_setup();
+        setup.position_size = dec!(0.00000003);
+        let error = executor.execute_trade(twap_plan(setup)).await.unwrap_err();
+
+        assert!(matches!(error, ExecutorError::SanityCheckFailed(_)));
+        assert!(exchange.get_submitted_orders().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_quote_order_converts_to_lot_rounded_base_quantity() {
+        let plan = |stop_loss| ExecutionPlan {
+            setup: TradeSetup {
+                symbol: "ETH/USDT".to_string(),
+                entry_price: dec!(3000),
+                stop_loss,
+                take_profit: None,
+                position_size: Decimal::ZERO,
+                side: OrderSide::Buy,
+            },
+            approved: true,
+            risk_assessment: "Trade approved by Testudo Protocol".to_string(),
+            account_equity: dec!(10000),
+            stop_slippage_tolerance: None,
+            violations: Vec::new(),
+            failed_rules: Vec::new(),
+            rejecting_rules: Vec::new(),
+            preferred_exchange: None,
+            size_reduction: None,
+            position_id: Uuid::new_v4(),
+            execution: None,
+        };
+
+        // $500 at 3,000 is 0.1666.. ETH, floored to the 0.001 lot step;
+        // 0.166 ETH with a 100 stop distance risks $16.60, well under the cap
+        let exchange = Arc::new(MockExchange::new());
+        let executor = Executor::new(exchange.clone());
+        let result = executor
+            .execute_quote_trade(plan(dec!(2900)), dec!(500), dec!(0.001))
+            .await
+            .unwrap();
+        assert_eq!(result.filled_quantity, dec!(0.166));
+        assert_eq!(result.average_entry_price, dec!(3000));
+
+        // Spending $5,000 against a 1,000 stop risks ~$1,667, so the
+        // converted position is refused before submission
+        let error = executor
+            .execute_quote_trade(plan(dec!(2000)), dec!(5000), dec!(0.001))
+            .await
+            .unwrap_err();
+        assert!(matches!(error, ExecutorError::SanityCheckFailed(_)));
+        assert_eq!(exchange.get_placed_orders().await.len(), 1);
+
+        // A venue with native quote orders is sent the amount itself
+        let native = Arc::new(MockExchange::new().with_capabilities(ExchangeCapabilities {
+            quote_orders: true,
+            ..ExchangeCapabilities::default()
+        }));
+        let result = Executor::new(native.clone())
+            .execute_quote_trade(plan(dec!(2900)), dec!(500), dec!(0.001))
+            .await
+            .unwrap();
+        let filled = dec!(500) / dec!(3000);
+        assert_eq!(result.filled_quantity, filled);
+        assert_eq!(native.get_placed_orders().await[0].executed_quantity, filled);
+    }
+
+    #[tokio::test]
+    async fn test_stop_above_market_is_rejected_for_long() {
+        let exchange = Arc::new(MockExchange::new());
+        let executor = Executor::new(exchange.clone());
+        let setup = long_setup();
+        let order_id = open_bracket(&exchange, &setup).await;
+
+        let result = executor
+            .modify_bracket(&order_id, &setup, None, Some(dec!(51000)), None)
+            .await;
+
+        assert!(matches!(result, Err(ExecutorError::InvalidModification(_))));
+        assert!(exchange.get_oco_modifications().await.is_empty());
+    }
+}
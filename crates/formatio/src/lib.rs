@@ -4,10 +4,14 @@ use thiserror::Error;
 use std::sync::Arc;
 
 // 1. Module Declarations
+pub mod breakeven;
+pub mod consensus;
 pub mod decider;
 pub mod executor;
+pub mod observation_cache;
 pub mod ooda;
 pub mod orientator;
+pub mod strategy;
 pub mod types;
 
 // 2. Consolidated Error Type for Imperium Integration
@@ -71,6 +75,37 @@ impl OodaController {
         self.ooda_loop.execute_cycle(intent).await
             .map_err(FormatioError::from)
     }
+
+    /// Execute a cycle held to a trader's stateful protocol
+    pub async fn execute_cycle_with_protocol(
+        &self,
+        intent: TradeIntent,
+        protocol: &SharedProtocol,
+    ) -> Result<ExecutionPlan, FormatioError> {
+        self.ooda_loop.execute_cycle_with_protocol(intent, protocol).await
+            .map_err(FormatioError::from)
+    }
+
+    /// Execute a protocol cycle that acts only if `commitment` lets it
+    pub async fn execute_cycle_with_commitment(
+        &self,
+        intent: TradeIntent,
+        protocol: &SharedProtocol,
+        commitment: &ActCommitment,
+    ) -> Result<ExecutionPlan, FormatioError> {
+        self.ooda_loop.execute_cycle_with_commitment(intent, protocol, commitment).await
+            .map_err(FormatioError::from)
+    }
+    
+    /// Latency and sizing counters of the OODA loop
+    pub async fn metrics(&self) -> LoopMetrics {
+        self.ooda_loop.metrics().await
+    }
+    
+    /// Outcome counts of each risk rule the loop's decider has run
+    pub fn rule_outcome_counts(&self) -> std::collections::HashMap<String, prudentia::risk::RuleOutcomeCounts> {
+        self.ooda_loop.rule_outcome_counts()
+    }
     
     /// Force transition to a specific state (for testing/recovery)
     pub async fn force_state_transition(&self, new_state: OodaState) -> Result<(), FormatioError> {
@@ -80,16 +115,35 @@ impl OodaController {
 }
 
 // 4. Public API Exports
+pub use breakeven::{BreakevenAutomation, BreakevenMoved, BreakevenWatch, DEFAULT_BREAKEVEN_TRIGGER};
+pub use consensus::{ConsensusError, PriceConsensus, PriceSource};
 pub use decider::{DecisionResult, RiskDecision, RiskDecider};
-pub use executor::{ExecutionResult, Executor, ExecutorError};
-pub use ooda::{OodaLoop, OodaLoopError, OodaState};
-pub use orientator::{OrientationError, PositionOrientator, TradeOrientation};
+pub use executor::{
+    BracketModification, ExecutionMode, ExecutionResult, ExecutionSafetyLimits, Executor, ExecutorError,
+    ProtectiveStop,
+};
+pub use observation_cache::{ObservationCache, DEFAULT_MAX_PRICE_MOVE};
+pub use ooda::{
+    ActCommitment, OodaLoop, OodaLoopError, OodaState, SharedProtocol, DEFAULT_MAX_CLOCK_SKEW,
+};
+pub use orientator::{
+    OrientationError, PositionOrientator, PositionSizer, TradeOrientation, DEFAULT_MIN_BOOK_LIQUIDITY,
+};
+pub use strategy::{
+    MovingAverageCrossover, PromotionCriteria, Strategy, StrategyContext, StrategyRegistry, StrategyRouter,
+    StrategyError, StrategySignal, TradingMode,
+};
 pub use types::{
+    BookDepth,
+    CorrelatedExposure,
     DecisionError,
     ExecutionPlan,
     LoopMetrics,
+    PhaseBudgets,
     MarketObservation,
     OodaPhase,
+    SizeReduction,
+    SymbolMetadata,
     TradeDirection,
     TradeIntent,
     TradeProposal,
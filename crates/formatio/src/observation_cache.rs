@@ -0,0 +1,138 @@
+//! Short-lived cache of market observations for the Observe phase
+//!
+//! Reusing a recent observation saves an exchange round-trip per cycle, but
+//! an entry that is fresh by age can be stale by movement if the market has
+//! just gapped. Callers feed the newest tick for each symbol into the cache;
+//! an entry whose price has drifted from that tick by more than the
+//! configured fraction is evicted, forcing a refetch before sizing.
+
+use crate::types::MarketObservation;
+use std::{
+    collections::HashMap,
+    sync::RwLock,
+    time::{Duration, Instant},
+};
+
+/// Default largest move from the cached price, as a fraction (0.5%)
+pub const DEFAULT_MAX_PRICE_MOVE: f64 = 0.005;
+
+struct CachedEntry {
+    observation: MarketObservation,
+    cached_at: Instant,
+}
+
+/// Caches observations per symbol until they expire or the price moves
+pub struct ObservationCache {
+    ttl: Duration,
+    max_price_move: f64,
+    entries: RwLock<HashMap<String, CachedEntry>>,
+    latest_ticks: RwLock<HashMap<String, f64>>,
+}
+
+impl ObservationCache {
+    /// Create a cache whose entries live for at most `ttl`
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            max_price_move: DEFAULT_MAX_PRICE_MOVE,
+            entries: RwLock::new(HashMap::new()),
+            latest_ticks: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Invalidate entries once the latest tick moves more than `fraction`
+    /// away from the cached price (0.005 = 0.5%)
+    pub fn with_max_price_move(mut self, fraction: f64) -> Self {
+        self.max_price_move = fraction;
+        self
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    pub fn max_price_move(&self) -> f64 {
+        self.max_price_move
+    }
+
+    /// Cache an observation, replacing any entry for its symbol
+    pub fn insert(&self, observation: MarketObservation) {
+        self.entries.write().unwrap().insert(
+            observation.symbol.clone(),
+            CachedEntry {
+                observation,
+                cached_at: Instant::now(),
+            },
+        );
+    }
+
+    /// Record the newest traded price seen for a symbol
+    pub fn record_tick(&self, symbol: &str, price: f64) {
+        self.latest_ticks
+            .write()
+            .unwrap()
+            .insert(symbol.to_string(), price);
+    }
+
+    /// Cached observation for a symbol, if it is still trustworthy
+    ///
+    /// Entries past their TTL, or whose price the latest tick has moved away
+    /// from by more than the threshold, are evicted and `None` is returned.
+    pub fn get(&self, symbol: &str) -> Option<MarketObservation> {
+        let latest_tick = self.latest_ticks.read().unwrap().get(symbol).copied();
+        let mut entries = self.entries.write().unwrap();
+        let entry = entries.get(symbol)?;
+
+        let expired = entry.cached_at.elapsed() > self.ttl;
+        let gapped = latest_tick.is_some_and(|tick| self.exceeds_move(entry.observation.price, tick));
+        if expired || gapped {
+            entries.remove(symbol);
+            return None;
+        }
+
+        Some(entry.observation.clone())
+    }
+
+    /// Drop the cached observation for a symbol
+    pub fn invalidate(&self, symbol: &str) {
+        self.entries.write().unwrap().remove(symbol);
+    }
+
+    fn exceeds_move(&self, cached_price: f64, tick: f64) -> bool {
+        if cached_price <= 0.0 {
+            return true;
+        }
+        ((tick - cached_price) / cached_price).abs() > self.max_price_move
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn observation(price: f64) -> MarketObservation {
+        MarketObservation {
+            symbol: "BTC/USDT".to_string(),
+            price,
+            volume: 1000.0,
+            timestamp: Instant::now(),
+            atr: None,
+            depth: None,
+        }
+    }
+
+    #[test]
+    fn test_price_gap_invalidates_entry_within_ttl() {
+        let cache = ObservationCache::new(Duration::from_secs(60)).with_max_price_move(0.01);
+        cache.insert(observation(50000.0));
+
+        // A move inside the threshold keeps the entry
+        cache.record_tick("BTC/USDT", 50200.0);
+        assert_eq!(cache.get("BTC/USDT").map(|obs| obs.price), Some(50000.0));
+
+        // A 4% gap evicts it even though the TTL has not elapsed
+        cache.record_tick("BTC/USDT", 48000.0);
+        assert!(cache.get("BTC/USDT").is_none());
+        assert!(cache.get("BTC/USDT").is_none());
+    }
+}
@@ -72,6 +72,8 @@ impl MarketObserver {
                     price: exchange_data.last_price.to_f64().unwrap_or(0.0),
                     volume: exchange_data.volume_24h.to_f64().unwrap_or(0.0),
                     timestamp: observation_start,
+                    atr: None,
+                    depth: None,
                 };
                 
                 // Validate data freshness
@@ -101,6 +103,8 @@ impl MarketObserver {
                         price: 0.0,
                         volume: 0.0,
                         timestamp: observation_start,
+                        atr: None,
+                        depth: None,
                     },
                     success: false,
                     error: Some(format!("Exchange error: {}", exchange_error)),
@@ -1,24 +1,91 @@
 //! OODA Loop core implementation - The heart of Testudo's systematic trading
 
+use crate::consensus::{ConsensusError, PriceConsensus};
 use crate::decider::{RiskDecision, RiskDecider};
 use crate::executor::{ExecutionResult, Executor, ExecutorError};
+use crate::observation_cache::ObservationCache;
 use crate::orientator::{OrientationError, PositionOrientator};
 use crate::types::{
-    ExecutionPlan, LoopMetrics, MarketObservation, OodaPhase, TradeDirection, TradeIntent,
-    TradeSetup,
+    ExecutionPlan, LoopMetrics, MarketObservation, OodaPhase, PhaseBudgets, TradeDirection,
+    TradeIntent, TradeSetup,
 };
+use disciplina::types::MIN_RISK_PERCENTAGE;
+use disciplina::{PositionSize, PositionSizingError, RiskPercentage};
+use prudentia::risk::RuleOutcomeCounts;
 use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
 use std::sync::Arc;
-use testudo_types::ExchangeAdapterTrait;
+use std::time::{Duration, Instant, SystemTime};
+use testudo_types::{min_valid_stop, stop_too_close, ExchangeAdapterTrait};
 use thiserror::Error;
-use tokio::sync::RwLock;
+use tokio::sync::{Mutex, RwLock};
+use tracing::warn;
+use uuid::Uuid;
+
+/// Default tolerance for market data timestamped ahead of server time
+pub const DEFAULT_MAX_CLOCK_SKEW: Duration = Duration::from_secs(1);
+
+/// Base-quantity increment assumed for symbols without a configured lot step
+pub const DEFAULT_LOT_STEP: Decimal = dec!(0.00000001);
+
+/// A trader's protocol state, shared by every cycle trading for them
+///
+/// Cycles run with [`OodaLoop::execute_cycle_with_protocol`] check approved
+/// plans against it and record the positions they open in it.
+pub type SharedProtocol = Arc<Mutex<prudentia::TestudoProtocol>>;
+
+const RUNNING: u8 = 0;
+const COMMITTED: u8 = 1;
+const ABANDONED: u8 = 2;
+
+/// Settles, once, whether a cycle goes on to act or is given up
+///
+/// A caller that may stop waiting for a cycle shares one with it. The cycle
+/// commits just before its Act phase and the caller abandons it when it stops
+/// waiting; whichever comes first wins. An abandoned cycle never places an
+/// order, and a committed one must be left to finish so its reservation is
+/// settled and its result recorded.
+#[derive(Debug, Clone, Default)]
+pub struct ActCommitment(Arc<AtomicU8>);
+
+impl ActCommitment {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Commit the cycle to acting; false if it was already abandoned
+    pub fn commit(&self) -> bool {
+        self.settle(COMMITTED)
+    }
+
+    /// Give up the cycle unless it already committed; false if it did
+    pub fn abandon(&self) -> bool {
+        self.settle(ABANDONED)
+    }
+
+    pub fn is_committed(&self) -> bool {
+        self.0.load(Ordering::Acquire) == COMMITTED
+    }
+
+    fn settle(&self, outcome: u8) -> bool {
+        match self.0.compare_exchange(RUNNING, outcome, Ordering::AcqRel, Ordering::Acquire) {
+            Ok(_) => true,
+            Err(current) => current == outcome,
+        }
+    }
+}
 
 /// Errors that can occur during OODA loop execution
 #[derive(Debug, Error)]
 pub enum OodaLoopError {
     #[error("OBSERVE phase failed: {message}")]
     ObserveFailed { message: String },
+    #[error("OBSERVE phase rejected observation: {message}")]
+    InvalidObservation { message: String },
     #[error("ORIENT phase failed: {source}")]
     OrientFailed {
         #[from]
@@ -39,6 +106,33 @@ pub enum OodaLoopError {
     NoOrientatorConfigured,
     #[error("Execution plan not approved by risk management")]
     ExecutionNotApproved,
+    #[error("Cycle abandoned by its caller before the Act phase")]
+    Abandoned,
+    #[error("Stop {stop_loss} for {symbol} is inside the exchange's minimum trigger distance {min_stop_distance}; closest valid stop is {suggested_stop}")]
+    StopTooClose {
+        symbol: String,
+        stop_loss: Decimal,
+        min_stop_distance: Decimal,
+        suggested_stop: Decimal,
+    },
+}
+
+impl OodaLoopError {
+    /// Whether a fresh cycle may succeed where this one failed
+    ///
+    /// Stale or divergent market data and unreachable exchanges are transient.
+    /// Risk and sizing failures would repeat, and a failed Act may already
+    /// have placed orders, so those are terminal.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            OodaLoopError::ObserveFailed { .. } | OodaLoopError::InvalidObservation { .. } => true,
+            OodaLoopError::OrientFailed { source } => matches!(
+                source,
+                OrientationError::InvalidObservation(_) | OrientationError::SizingBudgetExceeded { .. }
+            ),
+            _ => false,
+        }
+    }
 }
 
 /// State machine representing the current phase of the OODA loop
@@ -61,6 +155,14 @@ pub struct OodaLoop {
     orientator: Option<Arc<PositionOrientator>>,
     decider: Option<Arc<RiskDecider>>,
     exchange: Option<Arc<dyn ExchangeAdapterTrait + Send + Sync>>,
+    price_consensus: Option<Arc<PriceConsensus>>,
+    observation_cache: Option<Arc<ObservationCache>>,
+    stop_slippage_tolerance: Option<Decimal>,
+    max_cycle_retries: u32,
+    retry_backoff: Duration,
+    phase_budgets: PhaseBudgets,
+    max_clock_skew: Duration,
+    lot_steps: HashMap<String, Decimal>,
 }
 
 impl OodaLoop {
@@ -73,6 +175,14 @@ impl OodaLoop {
             orientator: None,
             decider: None,
             exchange: None,
+            price_consensus: None,
+            observation_cache: None,
+            stop_slippage_tolerance: None,
+            max_cycle_retries: 0,
+            retry_backoff: Duration::ZERO,
+            phase_budgets: PhaseBudgets::default(),
+            max_clock_skew: DEFAULT_MAX_CLOCK_SKEW,
+            lot_steps: HashMap::new(),
         }
     }
 
@@ -87,9 +197,146 @@ impl OodaLoop {
             orientator: Some(Arc::new(PositionOrientator::new())),
             decider: Some(decider),
             exchange: Some(exchange),
+            price_consensus: None,
+            observation_cache: None,
+            stop_slippage_tolerance: None,
+            max_cycle_retries: 0,
+            retry_backoff: Duration::ZERO,
+            phase_budgets: PhaseBudgets::default(),
+            max_clock_skew: DEFAULT_MAX_CLOCK_SKEW,
+            lot_steps: HashMap::new(),
         }
     }
 
+    /// Protect approved trades with stop-limit orders
+    ///
+    /// `tolerance` is the fraction of the stop price the fill may slip past
+    /// the trigger: too tight and fast markets gap through the limit, too
+    /// wide and the stop no longer bounds the loss.
+    pub fn with_stop_slippage_tolerance(mut self, tolerance: Decimal) -> Self {
+        self.stop_slippage_tolerance = Some(tolerance);
+        self
+    }
+
+    /// Retry a cycle from Observe after transient failures
+    ///
+    /// At most `max_retries` further attempts are made, waiting `backoff`
+    /// before the first and doubling the wait before each one after that.
+    /// Terminal failures (see [`OodaLoopError::is_retryable`]) fail at once.
+    pub fn with_cycle_retries(mut self, max_retries: u32, backoff: Duration) -> Self {
+        self.max_cycle_retries = max_retries;
+        self.retry_backoff = backoff;
+        self
+    }
+
+    /// Execute through `router`, honouring each intent's preferred exchange
+    ///
+    /// Intents without a preference execute on the router's failover primary.
+    /// The executor keeps its safety limits and execution mode. Fails when the
+    /// loop has no executor to route, since there is no exchange to act on.
+    pub fn with_exchange_router(mut self, router: Arc<prudentia::ExchangeManager>) -> Result<Self, OodaLoopError> {
+        let executor = self.executor.as_deref().ok_or(OodaLoopError::NoExecutorConfigured)?;
+        self.executor = Some(Arc::new(executor.clone().with_router(router)));
+        Ok(self)
+    }
+
+    /// Orient through `orientator`, e.g. one snapping prices to symbol ticks
+    pub fn with_orientator(mut self, orientator: PositionOrientator) -> Self {
+        self.orientator = Some(Arc::new(orientator));
+        self
+    }
+
+    /// Reject market data timestamped more than `skew` ahead of server time
+    ///
+    /// Future-dated ticks mean the feed's clock (or ours) is wrong, so their
+    /// age, and therefore their staleness, cannot be trusted.
+    pub fn with_max_clock_skew(mut self, skew: Duration) -> Self {
+        self.max_clock_skew = skew;
+        self
+    }
+
+    /// Floor quote-sized positions in `symbol` to multiples of `step`
+    pub fn with_lot_step(mut self, symbol: impl Into<String>, step: Decimal) -> Self {
+        self.lot_steps.insert(symbol.into(), step);
+        self
+    }
+
+    fn lot_step_for(&self, symbol: &str) -> Decimal {
+        self.lot_steps.get(symbol).copied().unwrap_or(DEFAULT_LOT_STEP)
+    }
+
+    /// Log a warning whenever a phase takes longer than its budget
+    pub fn with_phase_budgets(mut self, budgets: PhaseBudgets) -> Self {
+        self.phase_budgets = budgets;
+        self
+    }
+
+    /// Require the given price sources to agree before observing a symbol
+    ///
+    /// The consensus (median) price replaces the exchange's last price, so
+    /// include the primary exchange among the sources to cross-check it.
+    pub fn with_price_consensus(mut self, consensus: PriceConsensus) -> Self {
+        self.price_consensus = Some(Arc::new(consensus));
+        self
+    }
+
+    /// Reuse recent observations from `cache` instead of refetching
+    ///
+    /// Feed ticks into the cache with `ObservationCache::record_tick` so a
+    /// gapped market invalidates the cached price before it is sized on.
+    pub fn with_observation_cache(mut self, cache: Arc<ObservationCache>) -> Self {
+        self.observation_cache = Some(cache);
+        self
+    }
+
+    /// Snapshot of the loop's latency metrics
+    pub async fn metrics(&self) -> LoopMetrics {
+        self.metrics.read().await.clone()
+    }
+
+    /// Outcome counts of the decider's risk rules; empty without a decider
+    pub fn rule_outcome_counts(&self) -> HashMap<String, RuleOutcomeCounts> {
+        self.decider
+            .as_ref()
+            .map(|decider| decider.rule_outcome_counts())
+            .unwrap_or_default()
+    }
+
+    /// Record a phase's latency, warning when it exceeded its budget
+    async fn record_phase(&self, phase: OodaPhase, elapsed: Duration) {
+        let budget = self.phase_budgets.for_phase(&phase);
+        if elapsed > budget {
+            warn!(
+                "Slow OODA phase {:?} took {}ms (budget {}ms)",
+                phase,
+                elapsed.as_millis(),
+                budget.as_millis()
+            );
+        }
+
+        let mut metrics = self.metrics.write().await;
+        match phase {
+            OodaPhase::Observe => metrics.observe_latency = Some(elapsed),
+            OodaPhase::Orient => metrics.orient_latency = Some(elapsed),
+            OodaPhase::Decide => metrics.decide_latency = Some(elapsed),
+            OodaPhase::Act => metrics.act_duration = elapsed,
+        }
+        metrics.last_updated = Instant::now();
+    }
+
+    pub(crate) async fn record_sizing(&self, elapsed: Duration) {
+        let mut metrics = self.metrics.write().await;
+        metrics.sizing_calculations += 1;
+        metrics.sizing_latency_total += elapsed;
+        metrics.last_updated = Instant::now();
+    }
+
+    pub(crate) async fn record_sizing_budget_overrun(&self) {
+        let mut metrics = self.metrics.write().await;
+        metrics.sizing_budget_overruns += 1;
+        metrics.last_updated = std::time::Instant::now();
+    }
+
     pub async fn get_state(&self) -> OodaState {
         self.state.read().await.clone()
     }
@@ -123,17 +370,139 @@ impl OodaLoop {
         &self,
         intent: TradeIntent,
     ) -> Result<ExecutionPlan, OodaLoopError> {
+        self.execute_with_retries(intent, None, None).await
+    }
+
+    /// Execute a cycle held to a trader's stateful protocol
+    ///
+    /// A plan the risk decider approves is validated against `protocol` and
+    /// its risk reserved before anything is placed; the reservation becomes
+    /// a tracked open position once the entry fills, and is released if the
+    /// cycle fails before it does.
+    pub async fn execute_cycle_with_protocol(
+        &self,
+        intent: TradeIntent,
+        protocol: &SharedProtocol,
+    ) -> Result<ExecutionPlan, OodaLoopError> {
+        self.execute_with_retries(intent, Some(protocol), None).await
+    }
+
+    /// Execute a protocol cycle that acts only if `commitment` lets it
+    ///
+    /// The cycle commits before placing anything. If the caller abandoned it
+    /// first, its reservation is released and it fails with
+    /// [`OodaLoopError::Abandoned`]; once committed, the caller should let
+    /// the cycle run to completion rather than cancel it.
+    pub async fn execute_cycle_with_commitment(
+        &self,
+        intent: TradeIntent,
+        protocol: &SharedProtocol,
+        commitment: &ActCommitment,
+    ) -> Result<ExecutionPlan, OodaLoopError> {
+        self.execute_with_retries(intent, Some(protocol), Some(commitment)).await
+    }
+
+    async fn execute_with_retries(
+        &self,
+        intent: TradeIntent,
+        protocol: Option<&SharedProtocol>,
+        commitment: Option<&ActCommitment>,
+    ) -> Result<ExecutionPlan, OodaLoopError> {
+        let mut backoff = self.retry_backoff;
+        let mut retries = 0;
+        loop {
+            match self.run_cycle(&intent, protocol, commitment).await {
+                Err(e) if e.is_retryable() && retries < self.max_cycle_retries => {
+                    retries += 1;
+                    warn!(
+                        "OODA cycle for {} failed ({}); retry {}/{} in {}ms",
+                        intent.symbol,
+                        e,
+                        retries,
+                        self.max_cycle_retries,
+                        backoff.as_millis()
+                    );
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn run_cycle(
+        &self,
+        intent: &TradeIntent,
+        protocol: Option<&SharedProtocol>,
+        commitment: Option<&ActCommitment>,
+    ) -> Result<ExecutionPlan, OodaLoopError> {
+        // A finished cycle leaves the loop Completed or Failed, and neither may
+        // move to Observing, so a reused loop would otherwise run only once
+        if matches!(self.get_state().await, OodaState::Completed | OodaState::Failed(_)) {
+            self.transition_to(OodaState::Idle).await?;
+        }
+
         self.transition_to(OodaState::Observing).await?;
-        let observation = self.observe_market_for_symbol(&intent.symbol).await?;
+        let started = Instant::now();
+        let observed = self.observe_market_for_symbol(&intent.symbol).await;
+        self.record_phase(OodaPhase::Observe, started.elapsed()).await;
+        let observation = match observed {
+            Ok(observation) => observation,
+            Err(e) => {
+                self.transition_to(OodaState::Failed(e.to_string())).await?;
+                return Err(e);
+            }
+        };
 
+        // The orientator moves the loop on to Deciding once it has a proposal
         self.transition_to(OodaState::Orienting).await?;
-        let trade_setup = self.orient_situation(&observation, &intent).await?;
+        let started = Instant::now();
+        let oriented = match (self.orient_situation(&observation, intent).await, intent.quote_amount) {
+            (Ok(setup), Some(quote_amount)) => {
+                self.size_for_quote_amount(setup, quote_amount, intent.account_equity)
+            }
+            (oriented, _) => oriented,
+        };
+        self.record_phase(OodaPhase::Orient, started.elapsed()).await;
+        let trade_setup = match oriented {
+            Ok(trade_setup) => trade_setup,
+            Err(e) => {
+                self.transition_to(OodaState::Failed(e.to_string())).await?;
+                return Err(e);
+            }
+        };
+        if let Err(e) = self.prevalidate_stop(&trade_setup).await {
+            self.transition_to(OodaState::Failed(e.to_string())).await?;
+            return Err(e);
+        }
 
-        self.transition_to(OodaState::Deciding).await?;
-        let execution_plan = self.decide_action(trade_setup, &intent).await?;
+        let started = Instant::now();
+        let decided = self.decide_action(trade_setup, intent).await;
+        self.record_phase(OodaPhase::Decide, started.elapsed()).await;
+        let mut execution_plan = decided?;
+        if let (true, Some(protocol)) = (execution_plan.approved, protocol) {
+            if let Err(e) = Self::claim_protocol_risk(&mut execution_plan, protocol).await {
+                self.transition_to(OodaState::Failed(e.to_string())).await?;
+                return Err(e);
+            }
+        }
 
         if execution_plan.approved {
-            self.act(execution_plan.clone()).await?;
+            if commitment.is_some_and(|commitment| !commitment.commit()) {
+                if let Some(protocol) = protocol {
+                    protocol.lock().await.release_reservation(execution_plan.position_id);
+                }
+                let e = OodaLoopError::Abandoned;
+                self.transition_to(OodaState::Failed(e.to_string())).await?;
+                return Err(e);
+            }
+            let started = Instant::now();
+            let acted = self.act(execution_plan.clone(), intent).await;
+            self.record_phase(OodaPhase::Act, started.elapsed()).await;
+            if let Some(protocol) = protocol {
+                Self::settle_protocol_risk(&execution_plan, &acted, protocol).await;
+            }
+            execution_plan.execution = Some(acted?);
         } else {
             self.transition_to(OodaState::Completed).await?;
         }
@@ -145,6 +514,18 @@ impl OodaLoop {
         &self,
         symbol: &str,
     ) -> Result<MarketObservation, OodaLoopError> {
+        if let Some(cached) = self.observation_cache.as_ref().and_then(|cache| cache.get(symbol)) {
+            return Ok(cached);
+        }
+
+        let observation = self.fetch_observation(symbol).await?;
+        if let Some(cache) = &self.observation_cache {
+            cache.insert(observation.clone());
+        }
+        Ok(observation)
+    }
+
+    async fn fetch_observation(&self, symbol: &str) -> Result<MarketObservation, OodaLoopError> {
         let exchange = self.exchange.as_ref().ok_or_else(|| {
             OodaLoopError::ObserveFailed { message: "Exchange adapter not configured".to_string() }
         })?;
@@ -154,14 +535,87 @@ impl OodaLoop {
             .map_err(|e| OodaLoopError::ObserveFailed {
                 message: format!("Failed to get market data: {:?}", e),
             })?;
+
+        let price = match &self.price_consensus {
+            Some(consensus) => consensus.consensus_price(symbol).await.map_err(|e| match e {
+                ConsensusError::Divergence { .. } => {
+                    OodaLoopError::InvalidObservation { message: e.to_string() }
+                }
+                _ => OodaLoopError::ObserveFailed { message: e.to_string() },
+            })?,
+            None => market_data.last_price,
+        };
+
+        if let Ok(ahead) = market_data.timestamp.duration_since(SystemTime::now()) {
+            if ahead > self.max_clock_skew {
+                return Err(OodaLoopError::InvalidObservation {
+                    message: format!(
+                        "Market data for {} is timestamped {}ms ahead of server time (max skew {}ms)",
+                        symbol,
+                        ahead.as_millis(),
+                        self.max_clock_skew.as_millis()
+                    ),
+                });
+            }
+        }
+
+        // Age the observation by how old the exchange's data already is, so
+        // the orientator can reject stale quotes
+        let data_age = SystemTime::now()
+            .duration_since(market_data.timestamp)
+            .unwrap_or_default();
+        let now = Instant::now();
+
+        // Volatility is optional input to sizing, so a failed lookup only
+        // leaves the observation unscaled
+        let atr = match exchange.average_true_range(symbol).await {
+            Ok(atr) => atr.and_then(|atr| atr.to_f64()),
+            Err(e) => {
+                warn!("Failed to get the average true range of {}: {:?}", symbol, e);
+                None
+            }
+        };
+
         Ok(MarketObservation {
             symbol: market_data.symbol,
-            price: market_data.last_price.to_f64().unwrap_or(0.0),
+            price: price.to_f64().unwrap_or(0.0),
             volume: market_data.volume_24h.to_f64().unwrap_or(0.0),
-            timestamp: std::time::Instant::now(),
+            timestamp: now.checked_sub(data_age).unwrap_or(now),
+            atr,
+            depth: None,
         })
     }
 
+    /// Reject a stop the exchange would refuse for sitting too near the price
+    ///
+    /// Catching this before the Decide phase avoids an entry that fills while
+    /// its protective stop is rejected.
+    async fn prevalidate_stop(&self, setup: &TradeSetup) -> Result<(), OodaLoopError> {
+        let Some(exchange) = &self.exchange else {
+            return Ok(());
+        };
+        let min_distance = exchange
+            .min_stop_distance(&setup.symbol)
+            .await
+            .map_err(|e| OodaLoopError::ObserveFailed {
+                message: format!("Failed to get stop-trigger rules: {:?}", e),
+            })?;
+
+        match min_distance {
+            Some(min_distance)
+                if stop_too_close(setup.entry_price, setup.stop_loss, setup.side, min_distance) =>
+            {
+                Err(OodaLoopError::StopTooClose {
+                    symbol: setup.symbol.clone(),
+                    stop_loss: setup.stop_loss,
+                    min_stop_distance: min_distance,
+                    suggested_stop: min_valid_stop(setup.entry_price, setup.side, min_distance),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
     async fn orient_situation(
         &self,
         observation: &MarketObservation,
@@ -192,43 +646,53 @@ impl OodaLoop {
         })
     }
 
+    /// Resize an oriented setup to spend `quote_amount` at its entry price
+    ///
+    /// The quantity is floored to the symbol's lot step. A position whose
+    /// stop would lose more than the maximum risk percentage is refused, as
+    /// risk-based sizing would never produce one.
+    fn size_for_quote_amount(
+        &self,
+        mut setup: TradeSetup,
+        quote_amount: Decimal,
+        account_equity: Decimal,
+    ) -> Result<TradeSetup, OodaLoopError> {
+        let sizing_failed = |message: String| OodaLoopError::OrientFailed {
+            source: OrientationError::PositionSizingFailed(message),
+        };
+        if quote_amount <= Decimal::ZERO {
+            return Err(sizing_failed(format!("Quote amount {} is not positive", quote_amount)));
+        }
+
+        let quantity = PositionSize::new(quote_amount / setup.entry_price)
+            .and_then(|size| size.round_to_step(self.lot_step_for(&setup.symbol)))
+            .map_err(|e| sizing_failed(e.to_string()))?;
+        setup.position_size = quantity.value();
+        position_risk(&setup, account_equity).map_err(|e| {
+            sizing_failed(format!("{} of {} carries too much risk: {}", quote_amount, setup.symbol, e))
+        })?;
+        Ok(setup)
+    }
+
     async fn decide_action(&self, setup: TradeSetup, intent: &TradeIntent) -> Result<ExecutionPlan, OodaLoopError> {
         let decider = self.decider.as_ref().ok_or_else(|| {
             OodaLoopError::DecideFailed { message: "Risk decider not configured".to_string() }
         })?;
         
-        use disciplina::{AccountEquity, RiskPercentage, PricePoint};
-        use prudentia::types::TradeSide;
-        use testudo_types::OrderSide;
-        use std::time::SystemTime;
-        use uuid::Uuid;
-        
-        // Convert OrderSide to TradeSide
-        let trade_side = match setup.side {
-            OrderSide::Buy => TradeSide::Long,
-            OrderSide::Sell => TradeSide::Short,
-        };
-        
-        let trade_proposal = prudentia::types::TradeProposal {
-            id: Uuid::new_v4(),
-            symbol: setup.symbol.clone(),
-            side: trade_side,
-            entry_price: PricePoint::new(setup.entry_price).map_err(|e| 
-                OodaLoopError::DecideFailed { message: format!("Invalid entry price: {:?}", e) })?,
-            stop_loss: PricePoint::new(setup.stop_loss).map_err(|e| 
-                OodaLoopError::DecideFailed { message: format!("Invalid stop loss: {:?}", e) })?,
-            take_profit: match setup.take_profit {
-                Some(tp) => Some(PricePoint::new(tp).map_err(|e| 
-                    OodaLoopError::DecideFailed { message: format!("Invalid take profit: {:?}", e) })?),
-                None => None,
-            },
-            account_equity: AccountEquity::new(intent.account_equity).map_err(|e| 
-                OodaLoopError::DecideFailed { message: format!("Invalid account equity: {:?}", e) })?,
-            risk_percentage: RiskPercentage::new(intent.risk_percentage).map_err(|e| 
-                OodaLoopError::DecideFailed { message: format!("Invalid risk percentage: {:?}", e) })?,
-            timestamp: SystemTime::now(),
-            metadata: None,
+        // A quote-sized position is assessed on the risk it actually carries
+        let risk_percentage = match intent.quote_amount {
+            Some(_) => position_risk(&setup, intent.account_equity).map_err(|e| OodaLoopError::DecideFailed {
+                message: format!("Invalid risk percentage: {}", e),
+            })?,
+            None => intent.risk_percentage,
         };
+        let trade_proposal = trade_proposal(
+            &setup,
+            intent.account_equity,
+            risk_percentage,
+            Uuid::new_v4(),
+        )?;
+        let position_id = trade_proposal.id;
         let decision_result = decider
             .decide_trade(trade_proposal)
             .await
@@ -236,19 +700,46 @@ impl OodaLoop {
                 message: format!("Risk decision failed: {:?}", e),
             })?;
         match decision_result.decision {
-            RiskDecision::Execute { approved_position_size, .. } => {
+            RiskDecision::Execute { approved_position_size, size_reduction, .. } => {
                 let mut approved_setup = setup;
-                approved_setup.position_size = approved_position_size;
+                // The decider sizes from the risk percentage, which for a
+                // quote-sized trade may be the minimum rather than its own
+                approved_setup.position_size = match intent.quote_amount {
+                    Some(_) => approved_position_size.min(approved_setup.position_size),
+                    None => approved_position_size,
+                };
+                let risk_assessment = match &size_reduction {
+                    Some(reduction) => format!("Trade approved by Testudo Protocol. {}", reduction.notice),
+                    None => "Trade approved by Testudo Protocol".to_string(),
+                };
                 Ok(ExecutionPlan {
                     setup: approved_setup,
                     approved: true,
-                    risk_assessment: "Trade approved by Testudo Protocol".to_string(),
+                    risk_assessment,
+                    account_equity: intent.account_equity,
+                    stop_slippage_tolerance: self.stop_slippage_tolerance,
+                    violations: Vec::new(),
+                    failed_rules: decision_result.failed_rules,
+                    rejecting_rules: decision_result.rejecting_rules,
+                    preferred_exchange: intent.preferred_exchange.clone(),
+                    size_reduction,
+                    position_id,
+                    execution: None,
                 })
             }
-            RiskDecision::Reject { rejection_reason, .. } => Ok(ExecutionPlan {
+            RiskDecision::Reject { rejection_reason, violations, .. } => Ok(ExecutionPlan {
                 setup,
                 approved: false,
                 risk_assessment: format!("Trade rejected: {}", rejection_reason),
+                account_equity: intent.account_equity,
+                stop_slippage_tolerance: self.stop_slippage_tolerance,
+                violations,
+                failed_rules: decision_result.failed_rules,
+                rejecting_rules: decision_result.rejecting_rules,
+                preferred_exchange: intent.preferred_exchange.clone(),
+                size_reduction: None,
+                position_id,
+                execution: None,
             }),
             RiskDecision::AssessmentFailed { error_details } => {
                 Err(OodaLoopError::DecideFailed {
@@ -258,32 +749,173 @@ impl OodaLoop {
         }
     }
 
-    async fn act(&self, plan: ExecutionPlan) -> Result<ExecutionResult, OodaLoopError> {
+    /// Validate an approved plan against the trader's protocol and reserve its risk
+    ///
+    /// The check and the reservation happen under one lock, so two cycles for
+    /// the same trader cannot both spend the last of the budget. A plan the
+    /// protocol refuses becomes a rejection carrying its violations.
+    async fn claim_protocol_risk(plan: &mut ExecutionPlan, protocol: &SharedProtocol) -> Result<(), OodaLoopError> {
+        // Check the risk the sized position carries, not the requested one
+        let risk = position_risk(&plan.setup, plan.account_equity).map_err(|e| OodaLoopError::DecideFailed {
+            message: format!("Invalid risk percentage: {}", e),
+        })?;
+        let proposal = trade_proposal(&plan.setup, plan.account_equity, risk, plan.position_id)?;
+
+        let mut protocol = protocol.lock().await;
+        if let Err(violations) = protocol.validate_trade(&proposal) {
+            plan.approved = false;
+            plan.risk_assessment = format!(
+                "Trade rejected: {}",
+                violations
+                    .iter()
+                    .map(|violation| violation.description.as_str())
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            );
+            plan.rejecting_rules = violations.iter().map(|violation| violation.rule_name.clone()).collect();
+            plan.violations = violations;
+            return Ok(());
+        }
+        protocol
+            .reserve_risk(plan.position_id, proposal.risk_percentage.value(), plan.account_equity)
+            .map_err(|e| OodaLoopError::DecideFailed {
+                message: format!("Risk reservation failed: {}", e),
+            })?;
+        Ok(())
+    }
+
+    /// Settle a plan's reservation once its Act phase has finished
+    ///
+    /// Anything that left a position open is recorded as open, at the risk
+    /// of what actually filled; the reservation is released when nothing was
+    /// left open.
+    async fn settle_protocol_risk(
+        plan: &ExecutionPlan,
+        acted: &Result<ExecutionResult, OodaLoopError>,
+        protocol: &SharedProtocol,
+    ) {
+        let filled_risk = match acted {
+            Ok(result) => {
+                let entry_price = if result.average_entry_price > Decimal::ZERO {
+                    result.average_entry_price
+                } else {
+                    plan.setup.entry_price
+                };
+                Some((result.filled_quantity, entry_price))
+            }
+            Err(OodaLoopError::ActFailed {
+                source: ExecutorError::PartialEntry { filled_quantity, .. },
+            }) => Some((*filled_quantity, plan.setup.entry_price)),
+            Err(OodaLoopError::ActFailed {
+                source: ExecutorError::UnprotectedPosition { .. },
+            }) => Some((plan.setup.position_size, plan.setup.entry_price)),
+            Err(_) => None,
+        }
+        .map(|(position_size, entry_price)| {
+            TradeSetup { position_size, entry_price, ..plan.setup.clone() }.risk_amount() / plan.account_equity
+        });
+
+        let mut protocol = protocol.lock().await;
+        let Some(risk) = filled_risk else {
+            protocol.release_reservation(plan.position_id);
+            return;
+        };
+        // A reservation outlived by a long entry has expired; record the
+        // position directly
+        if !protocol.commit_reservation(plan.position_id, &plan.setup.symbol) {
+            protocol.track_external_position(plan.position_id, &plan.setup.symbol, risk);
+        }
+        protocol.set_tracked_risk(plan.position_id, risk);
+    }
+
+    async fn act(&self, plan: ExecutionPlan, intent: &TradeIntent) -> Result<ExecutionResult, OodaLoopError> {
         if !plan.approved {
             return Err(OodaLoopError::ExecutionNotApproved);
         }
         let executor = self.executor.as_ref().ok_or(OodaLoopError::NoExecutorConfigured)?;
         self.transition_to(OodaState::Acting).await?;
-        let execution_result = executor.execute_trade(plan).await?;
+        let execution_result = match intent.quote_amount {
+            Some(quote_amount) => {
+                // Spend no more than the decider approved
+                let quote_amount = match plan.size_reduction {
+                    Some(_) => plan.setup.position_size * plan.setup.entry_price,
+                    None => quote_amount,
+                };
+                let lot_step = self.lot_step_for(&plan.setup.symbol);
+                executor.execute_quote_trade(plan, quote_amount, lot_step).await?
+            }
+            None => executor.execute_trade(plan).await?,
+        };
         self.transition_to(OodaState::Completed).await?;
         Ok(execution_result)
     }
 }
 
+/// Risk a setup carries as a fraction of `account_equity`
+///
+/// Positions risking less than the smallest valid percentage are counted at
+/// that minimum, so a small position is never assessed as risk-free; one
+/// risking more than the maximum is an error.
+fn position_risk(setup: &TradeSetup, account_equity: Decimal) -> Result<Decimal, PositionSizingError> {
+    let min_risk = Decimal::from_str(MIN_RISK_PERCENTAGE).unwrap();
+    let risk = (setup.risk_amount() / account_equity).max(min_risk);
+    Ok(RiskPercentage::new(risk)?.value())
+}
+
+/// The protocol's view of a setup sized against `account_equity`
+fn trade_proposal(
+    setup: &TradeSetup,
+    account_equity: Decimal,
+    risk_percentage: Decimal,
+    id: Uuid,
+) -> Result<prudentia::types::TradeProposal, OodaLoopError> {
+    use disciplina::{AccountEquity, PricePoint};
+    use prudentia::types::TradeSide;
+    use testudo_types::OrderSide;
+
+    // Convert OrderSide to TradeSide
+    let trade_side = match setup.side {
+        OrderSide::Buy => TradeSide::Long,
+        OrderSide::Sell => TradeSide::Short,
+    };
+
+    Ok(prudentia::types::TradeProposal {
+        id,
+        symbol: setup.symbol.clone(),
+        side: trade_side,
+        entry_price: PricePoint::new(setup.entry_price).map_err(|e| 
+            OodaLoopError::DecideFailed { message: format!("Invalid entry price: {:?}", e) })?,
+        stop_loss: PricePoint::new(setup.stop_loss).map_err(|e| 
+            OodaLoopError::DecideFailed { message: format!("Invalid stop loss: {:?}", e) })?,
+        take_profit: match setup.take_profit {
+            Some(tp) => Some(PricePoint::new(tp).map_err(|e| 
+                OodaLoopError::DecideFailed { message: format!("Invalid take profit: {:?}", e) })?),
+            None => None,
+        },
+        account_equity: AccountEquity::new(account_equity).map_err(|e| 
+            OodaLoopError::DecideFailed { message: format!("Invalid account equity: {:?}", e) })?,
+        risk_percentage: RiskPercentage::new(risk_percentage).map_err(|e| 
+            OodaLoopError::DecideFailed { message: format!("Invalid risk percentage: {:?}", e) })?,
+        timestamp: SystemTime::now(),
+        metadata: None,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::TradeDirection;
+    use crate::types::{SymbolMetadata, TradeDirection};
     use prudentia::exchange::MockExchange;
-    use prudentia::risk::{rules::MaxTradeRiskRule, RiskManagementProtocol};
+    use prudentia::risk::{MaxTradeRiskRule, RiskManagementProtocol};
+    use prudentia::ProtocolLimits;
     use rust_decimal_macros::dec;
-    use testudo_types::AccountBalance;
+    use testudo_types::{AccountBalance, MarketData};
 
     #[tokio::test]
     async fn test_full_ooda_cycle_integration() {
         // 1. Setup
-        let mut mock_exchange = MockExchange::new();
-        mock_exchange.set_health(true);
+        let mock_exchange = MockExchange::new();
+        mock_exchange.set_health(true).await;
         mock_exchange.set_balance(
             "USDT".to_string(),
             AccountBalance {
@@ -292,13 +924,16 @@ mod tests {
                 locked: dec!(0.0),
                 total: dec!(10000.0),
             },
-        );
-        mock_exchange.set_market_data("BTC/USDT", dec!(50000.0), dec!(100.0));
+        ).await;
+        mock_exchange.set_market_data("BTC/USDT".to_string(), btc_market_data(SystemTime::now())).await;
         let exchange = Arc::new(mock_exchange);
 
         let protocol = Arc::new(
             RiskManagementProtocol::new()
-                .add_rule(MaxTradeRiskRule::new(dec!(0.06)))
+                .add_rule(MaxTradeRiskRule::with_limits(ProtocolLimits {
+                    max_individual_trade_risk: dec!(0.06),
+                    ..ProtocolLimits::default()
+                }))
         );
         let decider = Arc::new(RiskDecider::new(protocol));
 
@@ -310,6 +945,8 @@ mod tests {
             direction: TradeDirection::Long,
             account_equity: dec!(10000.0),
             risk_percentage: dec!(0.02),
+            preferred_exchange: None,
+            quote_amount: None,
         };
 
         // 3. Execute
@@ -323,4 +960,326 @@ mod tests {
         // Expected position size: (10000 * 0.02) / (50000 * 0.02) = 200 / 1000 = 0.2
         assert_eq!(plan.setup.position_size, dec!(0.2));
     }
+
+    fn btc_market_data(timestamp: SystemTime) -> MarketData {
+        MarketData {
+            symbol: "BTC/USDT".to_string(),
+            bid_price: dec!(49999.0),
+            ask_price: dec!(50001.0),
+            last_price: dec!(50000.0),
+            volume_24h: dec!(100.0),
+            timestamp,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cycle_proposes_prices_on_the_symbol_tick() {
+        let exchange = Arc::new(MockExchange::new());
+        exchange
+            .set_market_data("BTC/USDT".to_string(), MarketData {
+                last_price: dec!(50003.0),
+                ..btc_market_data(SystemTime::now())
+            })
+            .await;
+        let protocol = Arc::new(RiskManagementProtocol::new().add_rule(MaxTradeRiskRule::new()));
+        let loop_instance = OodaLoop::with_all_components(exchange, Arc::new(RiskDecider::new(protocol)))
+            .with_orientator(
+                PositionOrientator::new().with_symbol_metadata(SymbolMetadata::new("BTC/USDT", dec!(10))),
+            );
+        let intent = TradeIntent {
+            symbol: "BTC/USDT".to_string(),
+            direction: TradeDirection::Long,
+            account_equity: dec!(10000.0),
+            risk_percentage: dec!(0.01),
+            preferred_exchange: None,
+            quote_amount: None,
+        };
+
+        let plan = loop_instance.execute_cycle(intent).await.unwrap();
+
+        // The raw 49,002.94 stop and 52,003.12 target both floor to the 10 tick
+        assert_eq!(plan.setup.stop_loss, dec!(49000));
+        assert_eq!(plan.setup.take_profit, Some(dec!(52000)));
+    }
+
+    #[tokio::test]
+    async fn test_loop_runs_again_after_a_finished_cycle() {
+        let exchange = Arc::new(MockExchange::new());
+        let protocol = Arc::new(RiskManagementProtocol::new().add_rule(MaxTradeRiskRule::new()));
+        let loop_instance = OodaLoop::with_all_components(exchange.clone(), Arc::new(RiskDecider::new(protocol)));
+        let intent = TradeIntent {
+            symbol: "BTC/USDT".to_string(),
+            direction: TradeDirection::Long,
+            account_equity: dec!(10000.0),
+            risk_percentage: dec!(0.01),
+            preferred_exchange: None,
+            quote_amount: None,
+        };
+
+        // A stale quote fails the first cycle
+        let stale = SystemTime::now() - Duration::from_secs(30);
+        exchange.set_market_data("BTC/USDT".to_string(), btc_market_data(stale)).await;
+        assert!(loop_instance.execute_cycle(intent.clone()).await.is_err());
+        assert!(matches!(loop_instance.get_state().await, OodaState::Failed(_)));
+
+        // The same loop runs from Failed, then again from Completed
+        exchange.set_market_data("BTC/USDT".to_string(), btc_market_data(SystemTime::now())).await;
+        for _ in 0..2 {
+            assert!(loop_instance.execute_cycle(intent.clone()).await.unwrap().approved);
+            assert_eq!(loop_instance.get_state().await, OodaState::Completed);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cycle_retries_after_stale_observation() {
+        let exchange = Arc::new(MockExchange::new());
+        let stale = SystemTime::now() - Duration::from_secs(30);
+        exchange.set_market_data("BTC/USDT".to_string(), btc_market_data(stale)).await;
+
+        let protocol = Arc::new(RiskManagementProtocol::new().add_rule(MaxTradeRiskRule::new()));
+        let loop_instance = OodaLoop::with_all_components(exchange.clone(), Arc::new(RiskDecider::new(protocol)))
+            .with_cycle_retries(2, Duration::from_millis(200));
+        let intent = TradeIntent {
+            symbol: "BTC/USDT".to_string(),
+            direction: TradeDirection::Long,
+            account_equity: dec!(10000.0),
+            risk_percentage: dec!(0.01),
+            preferred_exchange: None,
+            quote_amount: None,
+        };
+
+        // Once the stale attempt fails, a fresh quote arrives during the backoff
+        let refresh_feed = async {
+            while !matches!(loop_instance.get_state().await, OodaState::Failed(_)) {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+            exchange.set_market_data("BTC/USDT".to_string(), btc_market_data(SystemTime::now())).await;
+        };
+        let (result, _) = tokio::join!(loop_instance.execute_cycle(intent), refresh_feed);
+
+        let plan = result.expect("retried cycle succeeds");
+        assert!(plan.approved);
+        assert_eq!(loop_instance.get_state().await, OodaState::Completed);
+        assert_eq!(exchange.get_placed_orders().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_future_dated_observation_is_rejected() {
+        let exchange = Arc::new(MockExchange::new());
+        let protocol = Arc::new(RiskManagementProtocol::new().add_rule(MaxTradeRiskRule::new()));
+        let loop_instance = OodaLoop::with_all_components(exchange.clone(), Arc::new(RiskDecider::new(protocol)))
+            .with_max_clock_skew(Duration::from_secs(2));
+        let intent = TradeIntent {
+            symbol: "BTC/USDT".to_string(),
+            direction: TradeDirection::Long,
+            account_equity: dec!(10000.0),
+            risk_percentage: dec!(0.01),
+            preferred_exchange: None,
+            quote_amount: None,
+        };
+
+        let ahead = SystemTime::now() + Duration::from_secs(30);
+        exchange.set_market_data("BTC/USDT".to_string(), btc_market_data(ahead)).await;
+        let error = loop_instance.execute_cycle(intent.clone()).await.unwrap_err();
+        assert!(matches!(error, OodaLoopError::InvalidObservation { .. }), "{}", error);
+        assert!(exchange.get_placed_orders().await.is_empty());
+
+        // Drift within the tolerated skew is accepted
+        let slightly_ahead = SystemTime::now() + Duration::from_millis(500);
+        exchange.set_market_data("BTC/USDT".to_string(), btc_market_data(slightly_ahead)).await;
+        assert!(loop_instance.execute_cycle(intent).await.unwrap().approved);
+    }
+
+    #[tokio::test]
+    async fn test_stop_inside_exchange_trigger_distance_is_flagged() {
+        let exchange = Arc::new(MockExchange::new());
+        exchange.set_market_data("BTC/USDT".to_string(), btc_market_data(SystemTime::now())).await;
+        // The orientator's 2% stop is tighter than the exchange's 3% minimum
+        exchange.set_min_stop_distance("BTC/USDT", dec!(0.03)).await;
+
+        let protocol = Arc::new(RiskManagementProtocol::new().add_rule(MaxTradeRiskRule::new()));
+        let loop_instance = OodaLoop::with_all_components(exchange.clone(), Arc::new(RiskDecider::new(protocol)));
+        let intent = TradeIntent {
+            symbol: "BTC/USDT".to_string(),
+            direction: TradeDirection::Long,
+            account_equity: dec!(10000.0),
+            risk_percentage: dec!(0.01),
+            preferred_exchange: None,
+            quote_amount: None,
+        };
+
+        match loop_instance.execute_cycle(intent).await.unwrap_err() {
+            OodaLoopError::StopTooClose { stop_loss, min_stop_distance, suggested_stop, .. } => {
+                assert_eq!(stop_loss, dec!(49000));
+                assert_eq!(min_stop_distance, dec!(0.03));
+                assert_eq!(suggested_stop, dec!(48500));
+            }
+            other => panic!("expected StopTooClose, got {}", other),
+        }
+        assert!(matches!(loop_instance.get_state().await, OodaState::Failed(_)));
+        assert!(exchange.get_placed_orders().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_protocol_held_cycle_records_and_enforces_open_risk() {
+        let exchange = Arc::new(MockExchange::new());
+        exchange.set_market_data("BTC/USDT".to_string(), btc_market_data(SystemTime::now())).await;
+        let protocol = Arc::new(RiskManagementProtocol::new().add_rule(MaxTradeRiskRule::new()));
+        let loop_instance = OodaLoop::with_all_components(exchange.clone(), Arc::new(RiskDecider::new(protocol)));
+        let shared: SharedProtocol = Arc::new(Mutex::new(prudentia::TestudoProtocol::new()));
+        let intent = TradeIntent {
+            symbol: "BTC/USDT".to_string(),
+            direction: TradeDirection::Long,
+            account_equity: dec!(10000.0),
+            risk_percentage: dec!(0.02),
+            preferred_exchange: None,
+            quote_amount: None,
+        };
+
+        let plan = loop_instance.execute_cycle_with_protocol(intent.clone(), &shared).await.unwrap();
+        assert!(plan.approved);
+        {
+            let protocol = shared.lock().await;
+            assert!(protocol.tracked_position(plan.position_id).is_some());
+            assert_eq!(protocol.get_status().total_portfolio_risk, dec!(0.02));
+            assert_eq!(protocol.reserved_risk(), Decimal::ZERO);
+        }
+
+        // Exposure held elsewhere leaves no room for another 2%
+        shared.lock().await.track_external_position(Uuid::new_v4(), "ETH/USDT", dec!(0.07));
+        let rejected = loop_instance.execute_cycle_with_protocol(intent, &shared).await.unwrap();
+        assert!(!rejected.approved);
+        assert!(!rejected.violations.is_empty());
+        assert_eq!(exchange.get_placed_orders().await.len(), 1);
+        assert_eq!(shared.lock().await.reserved_risk(), Decimal::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_abandoned_cycle_places_nothing_and_releases_its_reservation() {
+        let exchange = Arc::new(MockExchange::new());
+        exchange.set_market_data("BTC/USDT".to_string(), btc_market_data(SystemTime::now())).await;
+        let protocol = Arc::new(RiskManagementProtocol::new().add_rule(MaxTradeRiskRule::new()));
+        let loop_instance = OodaLoop::with_all_components(exchange.clone(), Arc::new(RiskDecider::new(protocol)));
+        let shared: SharedProtocol = Arc::new(Mutex::new(prudentia::TestudoProtocol::new()));
+        let intent = TradeIntent {
+            symbol: "BTC/USDT".to_string(),
+            direction: TradeDirection::Long,
+            account_equity: dec!(10000.0),
+            risk_percentage: dec!(0.02),
+            preferred_exchange: None,
+            quote_amount: None,
+        };
+
+        let abandoned = ActCommitment::new();
+        assert!(abandoned.abandon());
+        let error = loop_instance
+            .execute_cycle_with_commitment(intent.clone(), &shared, &abandoned)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, OodaLoopError::Abandoned), "{}", error);
+        assert!(exchange.get_placed_orders().await.is_empty());
+        assert_eq!(shared.lock().await.reserved_risk(), Decimal::ZERO);
+
+        // A committed cycle can no longer be abandoned
+        let commitment = ActCommitment::new();
+        let plan = loop_instance.execute_cycle_with_commitment(intent, &shared, &commitment).await.unwrap();
+        assert!(plan.execution.is_some());
+        assert!(commitment.is_committed());
+        assert!(!commitment.abandon());
+        assert!(shared.lock().await.tracked_position(plan.position_id).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_quote_sized_cycle_is_checked_and_recorded_at_its_actual_risk() {
+        let exchange = Arc::new(MockExchange::new());
+        exchange.set_market_data("BTC/USDT".to_string(), btc_market_data(SystemTime::now())).await;
+        let protocol = Arc::new(RiskManagementProtocol::new().add_rule(MaxTradeRiskRule::new()));
+        let loop_instance = OodaLoop::with_all_components(exchange.clone(), Arc::new(RiskDecider::new(protocol)))
+            .with_lot_step("BTC/USDT", dec!(0.001));
+        let shared: SharedProtocol = Arc::new(Mutex::new(prudentia::TestudoProtocol::new()));
+        let intent = |quote_amount| TradeIntent {
+            symbol: "BTC/USDT".to_string(),
+            direction: TradeDirection::Long,
+            account_equity: dec!(10000.0),
+            risk_percentage: dec!(0.02),
+            preferred_exchange: None,
+            quote_amount: Some(quote_amount),
+        };
+
+        // $500 at 50,000 buys 0.01 BTC; its 1,000 stop distance risks $10
+        let plan = loop_instance.execute_cycle_with_protocol(intent(dec!(500)), &shared).await.unwrap();
+        assert!(plan.approved);
+        assert_eq!(plan.setup.position_size, dec!(0.01));
+        assert_eq!(exchange.get_placed_orders().await[0].executed_quantity, dec!(0.01));
+        let tracked_risk = shared.lock().await.tracked_position(plan.position_id).unwrap().risk;
+        assert_eq!(tracked_risk, dec!(0.001));
+
+        // $40,000 buys 0.8 BTC, risking 8% of equity
+        let error = loop_instance
+            .execute_cycle_with_protocol(intent(dec!(40000)), &shared)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, OodaLoopError::OrientFailed { .. }), "{}", error);
+        assert_eq!(exchange.get_placed_orders().await.len(), 1);
+        assert_eq!(shared.lock().await.get_status().total_portfolio_risk, dec!(0.001));
+    }
+
+    #[test]
+    fn test_risk_and_execution_failures_are_terminal() {
+        let stale = OodaLoopError::OrientFailed {
+            source: OrientationError::InvalidObservation("Market data is stale".to_string()),
+        };
+        let sizing = OodaLoopError::OrientFailed {
+            source: OrientationError::PositionSizingFailed("Invalid risk percentage".to_string()),
+        };
+
+        assert!(stale.is_retryable());
+        assert!(OodaLoopError::ObserveFailed { message: "timeout".to_string() }.is_retryable());
+        assert!(!sizing.is_retryable());
+        assert!(!OodaLoopError::DecideFailed { message: "rejected".to_string() }.is_retryable());
+        assert!(!OodaLoopError::ExecutionNotApproved.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_observation_carries_the_exchange_atr() {
+        let exchange = Arc::new(MockExchange::new());
+        exchange.set_market_data(
+            "BTC/USDT".to_string(),
+            MarketData {
+                symbol: "BTC/USDT".to_string(),
+                bid_price: dec!(49999),
+                ask_price: dec!(50001),
+                last_price: dec!(50000),
+                volume_24h: dec!(100),
+                timestamp: SystemTime::now(),
+            },
+        ).await;
+        let decider = Arc::new(RiskDecider::new(Arc::new(RiskManagementProtocol::new())));
+        let ooda_loop = OodaLoop::with_all_components(exchange.clone(), decider);
+
+        assert_eq!(ooda_loop.fetch_observation("BTC/USDT").await.unwrap().atr, None);
+        exchange.set_average_true_range("BTC/USDT", dec!(1250)).await;
+        assert_eq!(ooda_loop.fetch_observation("BTC/USDT").await.unwrap().atr, Some(1250.0));
+    }
+
+    #[test]
+    fn test_exchange_router_requires_an_executor() {
+        let router = || {
+            Arc::new(prudentia::ExchangeManager::new(prudentia::ExchangeFailoverConfig {
+                primary_exchange: "binance".to_string(),
+                backup_exchanges: Vec::new(),
+                health_check_interval_secs: 30,
+                health_check_jitter: false,
+            }))
+        };
+
+        assert!(matches!(
+            OodaLoop::new().with_exchange_router(router()),
+            Err(OodaLoopError::NoExecutorConfigured)
+        ));
+        let decider = Arc::new(RiskDecider::new(Arc::new(RiskManagementProtocol::new())));
+        assert!(OodaLoop::with_all_components(Arc::new(MockExchange::new()), decider)
+            .with_exchange_router(router())
+            .is_ok());
+    }
 }
\ No newline at end of file
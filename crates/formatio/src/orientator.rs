@@ -1,17 +1,75 @@
 //! Position orientation module
 
 use crate::ooda::{OodaLoop, OodaState};
-use crate::types::{MarketObservation, TradeDirection, TradeProposal};
+use crate::types::{
+    CorrelatedExposure, MarketObservation, SymbolMetadata, TradeDirection, TradeProposal,
+};
 use disciplina::calculator::PositionSizingCalculator;
-use disciplina::types::{AccountEquity, PricePoint, RiskPercentage};
+use disciplina::types::{AccountEquity, ConvictionMultiplier, PositionSize, PricePoint, RiskPercentage};
+use disciplina::PositionSizingError;
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
 use testudo_types::OrderSide;
 
+/// Default cap on combined correlated risk, matching the default portfolio risk limit.
+pub const DEFAULT_MAX_CORRELATED_RISK: Decimal = dec!(0.10);
+
+/// Default latency budget for position sizing within the orient phase.
+pub const DEFAULT_SIZING_BUDGET: Duration = Duration::from_millis(50);
+
+/// Default top-of-book liquidity, in quote currency, below which confidence is reduced.
+pub const DEFAULT_MIN_BOOK_LIQUIDITY: f64 = 50_000.0;
+
+/// Van Tharp position sizing used by the orient phase
+pub trait PositionSizer: Send + Sync {
+    /// Size a position at `conviction` of the requested risk
+    fn size_position(
+        &self,
+        account_equity: AccountEquity,
+        risk_percentage: RiskPercentage,
+        entry_price: PricePoint,
+        stop_loss: PricePoint,
+        conviction: ConvictionMultiplier,
+    ) -> Result<PositionSize, PositionSizingError>;
+}
+
+impl PositionSizer for PositionSizingCalculator {
+    fn size_position(
+        &self,
+        account_equity: AccountEquity,
+        risk_percentage: RiskPercentage,
+        entry_price: PricePoint,
+        stop_loss: PricePoint,
+        conviction: ConvictionMultiplier,
+    ) -> Result<PositionSize, PositionSizingError> {
+        self.calculate_position_size_with_conviction(
+            account_equity,
+            risk_percentage,
+            entry_price,
+            stop_loss,
+            conviction,
+        )
+    }
+}
+
 /// Orientator component that analyzes market observations and creates trade proposals.
-#[derive(Default)]
 pub struct PositionOrientator {
-    calculator: PositionSizingCalculator,
+    calculator: Arc<dyn PositionSizer>,
+    symbol_metadata: HashMap<String, SymbolMetadata>,
+    conviction: HashMap<String, ConvictionMultiplier>,
+    max_correlated_risk: Decimal,
+    volatility_baseline: Option<Decimal>,
+    sizing_budget: Duration,
+    min_book_liquidity: f64,
+}
+
+impl Default for PositionOrientator {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 /// Result of the orientation process containing a trade proposal.
@@ -31,15 +89,80 @@ pub enum OrientationError {
     InvalidObservation(String),
     #[error("State transition failed: {0}")]
     StateTransitionFailed(String),
+    #[error("Position sizing took {elapsed_ms}ms, exceeding the {budget_ms}ms budget")]
+    SizingBudgetExceeded { elapsed_ms: u64, budget_ms: u64 },
 }
 
 impl PositionOrientator {
     pub fn new() -> Self {
         Self {
-            calculator: PositionSizingCalculator::new(),
+            calculator: Arc::new(PositionSizingCalculator::new()),
+            symbol_metadata: HashMap::new(),
+            conviction: HashMap::new(),
+            max_correlated_risk: DEFAULT_MAX_CORRELATED_RISK,
+            volatility_baseline: None,
+            sizing_budget: DEFAULT_SIZING_BUDGET,
+            min_book_liquidity: DEFAULT_MIN_BOOK_LIQUIDITY,
         }
     }
 
+    /// Size positions with `sizer` instead of the default Van Tharp calculator.
+    pub fn with_sizer(mut self, sizer: Arc<dyn PositionSizer>) -> Self {
+        self.calculator = sizer;
+        self
+    }
+
+    /// Scale risk down when recent volatility exceeds `baseline`.
+    ///
+    /// Volatility is the observation's ATR as a fraction of price. Above the
+    /// baseline, the position shrinks by `baseline / volatility`, so twice the
+    /// normal volatility risks half as much. Observations without an ATR are
+    /// sized normally.
+    pub fn with_volatility_scaling(mut self, baseline: Decimal) -> Self {
+        self.volatility_baseline = Some(baseline);
+        self
+    }
+
+    /// Abort orientation when sizing takes longer than `budget`.
+    ///
+    /// A size computed late is sized on a stale price, so the cycle fails
+    /// rather than acting on it.
+    pub fn with_sizing_budget(mut self, budget: Duration) -> Self {
+        self.sizing_budget = budget;
+        self
+    }
+
+    /// Lower confidence when the thinner side of the book is worth less than `liquidity`.
+    ///
+    /// Confidence scales with the shortfall, down to half at an empty book.
+    /// Observations without depth are scored on volume alone.
+    pub fn with_min_book_liquidity(mut self, liquidity: f64) -> Self {
+        self.min_book_liquidity = liquidity;
+        self
+    }
+
+    /// Cap the combined risk of a new trade and the correlated exposure it joins.
+    pub fn with_max_correlated_risk(mut self, max_correlated_risk: Decimal) -> Self {
+        self.max_correlated_risk = max_correlated_risk;
+        self
+    }
+
+    /// Register exchange price rules so computed stops/targets land on valid ticks.
+    pub fn with_symbol_metadata(mut self, metadata: SymbolMetadata) -> Self {
+        self.symbol_metadata.insert(metadata.symbol.clone(), metadata);
+        self
+    }
+
+    /// Risk only a fraction of the requested risk percentage on `symbol`.
+    ///
+    /// The multiplier is at most 1×, so a symbol is never sized above the
+    /// risk the trade asked for. Symbols without one are sized at full
+    /// conviction.
+    pub fn with_conviction(mut self, symbol: impl Into<String>, conviction: ConvictionMultiplier) -> Self {
+        self.conviction.insert(symbol.into(), conviction);
+        self
+    }
+
     pub async fn orient(
         &self,
         observation: &MarketObservation,
@@ -47,6 +170,27 @@ impl PositionOrientator {
         account_equity: Decimal,
         risk_percentage: Decimal,
         stop_loss_distance_percent: Decimal,
+    ) -> Result<TradeOrientation, OrientationError> {
+        self.orient_with_exposure(
+            observation,
+            ooda_loop,
+            account_equity,
+            risk_percentage,
+            stop_loss_distance_percent,
+            None,
+        )
+        .await
+    }
+
+    /// Orient a trade, shrinking it so correlated risk stays within the cap.
+    pub async fn orient_with_exposure(
+        &self,
+        observation: &MarketObservation,
+        ooda_loop: &OodaLoop,
+        account_equity: Decimal,
+        risk_percentage: Decimal,
+        stop_loss_distance_percent: Decimal,
+        correlated_exposure: Option<&CorrelatedExposure>,
     ) -> Result<TradeOrientation, OrientationError> {
         let start_time = std::time::Instant::now();
         self.validate_observation(observation)?;
@@ -54,12 +198,28 @@ impl PositionOrientator {
         let (entry_price, stop_loss, take_profit) =
             self.analyze_market_conditions(observation, stop_loss_distance_percent)?;
 
+        let sizing_start = std::time::Instant::now();
         let position_size = self.calculate_position_size(
+            &observation.symbol,
             account_equity,
             risk_percentage,
             entry_price,
             stop_loss,
         )?;
+        let position_size = match correlated_exposure {
+            Some(exposure) => position_size * self.correlation_scale(exposure, risk_percentage)?,
+            None => position_size,
+        };
+        let position_size = position_size * self.volatility_scale(observation, entry_price);
+        let sizing_elapsed = sizing_start.elapsed();
+        ooda_loop.record_sizing(sizing_elapsed).await;
+        if sizing_elapsed > self.sizing_budget {
+            ooda_loop.record_sizing_budget_overrun().await;
+            return Err(OrientationError::SizingBudgetExceeded {
+                elapsed_ms: sizing_elapsed.as_millis() as u64,
+                budget_ms: self.sizing_budget.as_millis() as u64,
+            });
+        }
 
         let proposal = TradeProposal {
             symbol: observation.symbol.clone(),
@@ -111,11 +271,41 @@ impl PositionOrientator {
         let stop_distance = entry_price * stop_loss_distance_percent;
         let stop_loss = entry_price - stop_distance;
         let take_profit = entry_price + (stop_distance * dec!(2.0)); // 2:1 R:R
+        let (stop_loss, take_profit) =
+            self.snap_to_ticks(&observation.symbol, &TradeDirection::Long, stop_loss, take_profit);
         Ok((entry_price, stop_loss, take_profit))
     }
 
+    /// Snap stop and target to the symbol's tick size in the risk-conservative direction.
+    ///
+    /// The stop is moved away from entry (risk widens slightly, so the position is
+    /// never sized on a tighter stop than the one placed) and the target is moved
+    /// toward entry (so it remains achievable). Unknown symbols are left unchanged.
+    fn snap_to_ticks(
+        &self,
+        symbol: &str,
+        direction: &TradeDirection,
+        stop_loss: Decimal,
+        take_profit: Decimal,
+    ) -> (Decimal, Decimal) {
+        match self.symbol_metadata.get(symbol) {
+            Some(metadata) => match direction {
+                TradeDirection::Long => (
+                    metadata.floor_to_tick(stop_loss),
+                    metadata.floor_to_tick(take_profit),
+                ),
+                TradeDirection::Short => (
+                    metadata.ceil_to_tick(stop_loss),
+                    metadata.ceil_to_tick(take_profit),
+                ),
+            },
+            None => (stop_loss, take_profit),
+        }
+    }
+
     fn calculate_position_size(
         &self,
+        symbol: &str,
         account_equity: Decimal,
         risk_percentage: Decimal,
         entry_price: Decimal,
@@ -130,17 +320,59 @@ impl PositionOrientator {
         let stop_loss_typed = PricePoint::new(stop_loss)
             .map_err(|e| OrientationError::PositionSizingFailed(format!("Invalid stop loss: {}", e)))?;
 
+        let conviction = self.conviction.get(symbol).copied().unwrap_or_default();
+
         self.calculator
-            .calculate_position_size(
+            .size_position(
                 account_equity_typed,
                 risk_percentage_typed,
                 entry_price_typed,
                 stop_loss_typed,
+                conviction,
             )
             .map(|ps| ps.value())
             .map_err(|e| OrientationError::PositionSizingFailed(format!("Position sizing failed: {}", e)))
     }
 
+    /// Fraction of the requested size that keeps correlated risk within the cap.
+    ///
+    /// The correlated part of the open exposure uses up the cap first; the new
+    /// trade may only risk what is left. Size scales linearly with risk, so the
+    /// fraction applies directly to the Van Tharp size.
+    fn correlation_scale(
+        &self,
+        exposure: &CorrelatedExposure,
+        risk_percentage: Decimal,
+    ) -> Result<Decimal, OrientationError> {
+        let remaining = (self.max_correlated_risk - exposure.effective_risk()).max(Decimal::ZERO);
+        if remaining.is_zero() {
+            return Err(OrientationError::PositionSizingFailed(format!(
+                "Correlated exposure of {} already uses the {} correlated risk cap",
+                exposure.effective_risk(),
+                self.max_correlated_risk
+            )));
+        }
+
+        Ok(remaining.min(risk_percentage) / risk_percentage)
+    }
+
+    /// Fraction of the size to keep given the observation's volatility.
+    fn volatility_scale(&self, observation: &MarketObservation, entry_price: Decimal) -> Decimal {
+        let (Some(baseline), Some(atr)) = (self.volatility_baseline, observation.atr) else {
+            return Decimal::ONE;
+        };
+        let Some(atr) = Decimal::from_f64_retain(atr).filter(|atr| *atr > Decimal::ZERO) else {
+            return Decimal::ONE;
+        };
+
+        let volatility = atr / entry_price;
+        if volatility > baseline {
+            baseline / volatility
+        } else {
+            Decimal::ONE
+        }
+    }
+
     fn calculate_confidence(
         &self,
         observation: &MarketObservation,
@@ -154,6 +386,12 @@ impl PositionOrientator {
         if observation.volume < 1000.0 {
             confidence *= 0.8;
         }
+        if let Some(depth) = observation.depth {
+            let liquidity = depth.liquidity(observation.price);
+            if liquidity < self.min_book_liquidity {
+                confidence *= (liquidity / self.min_book_liquidity).max(0.5);
+            }
+        }
         if (entry_price - stop_loss) / entry_price < dec!(0.005) {
             confidence *= 0.7;
         }
@@ -168,4 +406,239 @@ impl From<TradeDirection> for OrderSide {
             TradeDirection::Short => OrderSide::Sell,
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::BookDepth;
+
+    fn observation(symbol: &str, price: f64) -> MarketObservation {
+        MarketObservation {
+            symbol: symbol.to_string(),
+            price,
+            volume: 5000.0,
+            timestamp: std::time::Instant::now(),
+            atr: None,
+            depth: None,
+        }
+    }
+
+    /// Sizes with the real calculator after stalling for `delay`
+    struct SlowSizer {
+        delay: Duration,
+    }
+
+    impl PositionSizer for SlowSizer {
+        fn size_position(
+            &self,
+            account_equity: AccountEquity,
+            risk_percentage: RiskPercentage,
+            entry_price: PricePoint,
+            stop_loss: PricePoint,
+            conviction: ConvictionMultiplier,
+        ) -> Result<PositionSize, PositionSizingError> {
+            std::thread::sleep(self.delay);
+            PositionSizingCalculator::new().size_position(
+                account_equity,
+                risk_percentage,
+                entry_price,
+                stop_loss,
+                conviction,
+            )
+        }
+    }
+
+    async fn orienting_loop() -> OodaLoop {
+        let ooda_loop = OodaLoop::new();
+        ooda_loop.transition_to(OodaState::Observing).await.unwrap();
+        ooda_loop.transition_to(OodaState::Orienting).await.unwrap();
+        ooda_loop
+    }
+
+    #[tokio::test]
+    async fn test_stop_and_target_snapped_to_tick_size() {
+        let orientator = PositionOrientator::new()
+            .with_symbol_metadata(SymbolMetadata::new("BTC/USDT", dec!(0.5)));
+        let ooda_loop = orienting_loop().await;
+
+        // Raw 3% stop: 50000.3 - 1500.009 = 48500.291, raw target: 53000.318
+        let orientation = orientator
+            .orient(&observation("BTC/USDT", 50000.3), &ooda_loop, dec!(10000), dec!(0.02), dec!(0.03))
+            .await
+            .unwrap();
+
+        // Long: stop rounds down (wider risk), target rounds down (achievable)
+        assert_eq!(orientation.proposal.stop_loss, dec!(48500.0));
+        assert_eq!(orientation.proposal.take_profit, Some(dec!(53000.0)));
+    }
+
+    #[tokio::test]
+    async fn test_correlated_exposure_shrinks_position() {
+        let orientator = PositionOrientator::new();
+        let orient = |exposure: Option<CorrelatedExposure>| {
+            let orientator = &orientator;
+            async move {
+                let ooda_loop = orienting_loop().await;
+                orientator
+                    .orient_with_exposure(
+                        &observation("ETH/USDT", 3000.0),
+                        &ooda_loop,
+                        dec!(10000),
+                        dec!(0.02),
+                        dec!(0.02),
+                        exposure.as_ref(),
+                    )
+                    .await
+                    .map(|orientation| orientation.proposal.position_size)
+            }
+        };
+
+        let unexposed = orient(None).await.unwrap();
+        // 9% open in BTC, moving almost in lockstep with ETH
+        let correlated = orient(Some(CorrelatedExposure {
+            open_risk: dec!(0.09),
+            correlation: dec!(0.95),
+        }))
+        .await
+        .unwrap();
+        let uncorrelated = orient(Some(CorrelatedExposure {
+            open_risk: dec!(0.09),
+            correlation: Decimal::ZERO,
+        }))
+        .await
+        .unwrap();
+
+        assert!(correlated < unexposed);
+        // Only 10% - 8.55% = 1.45% of the requested 2% remains
+        assert_eq!(correlated, unexposed * dec!(0.725));
+        assert_eq!(uncorrelated, unexposed);
+
+        let exhausted = orient(Some(CorrelatedExposure {
+            open_risk: dec!(0.12),
+            correlation: Decimal::ONE,
+        }))
+        .await;
+        assert!(matches!(exhausted, Err(OrientationError::PositionSizingFailed(_))));
+    }
+
+    #[tokio::test]
+    async fn test_high_volatility_shrinks_position() {
+        let orientator = PositionOrientator::new().with_volatility_scaling(dec!(0.02));
+        let size_with_atr = |atr: f64| {
+            let orientator = &orientator;
+            async move {
+                let ooda_loop = orienting_loop().await;
+                let observation = MarketObservation {
+                    atr: Some(atr),
+                    ..observation("BTC/USDT", 50000.0)
+                };
+                orientator
+                    .orient(&observation, &ooda_loop, dec!(10000), dec!(0.02), dec!(0.02))
+                    .await
+                    .unwrap()
+                    .proposal
+                    .position_size
+            }
+        };
+
+        // 1% ATR is within the 2% baseline; 4% ATR is twice it
+        let calm = size_with_atr(500.0).await;
+        let volatile = size_with_atr(2000.0).await;
+
+        assert_eq!(calm, dec!(0.2));
+        assert!(volatile < calm);
+        assert_eq!(volatile, calm / dec!(2));
+    }
+
+    #[tokio::test]
+    async fn test_conviction_multiplier_scales_symbol_size() {
+        let half = ConvictionMultiplier::new(dec!(0.5)).unwrap();
+        let orientator = PositionOrientator::new().with_conviction("ETH/USDT", half);
+        let size_for = |symbol: &'static str| {
+            let orientator = &orientator;
+            async move {
+                let ooda_loop = orienting_loop().await;
+                orientator
+                    .orient(&observation(symbol, 2000.0), &ooda_loop, dec!(10000), dec!(0.02), dec!(0.02))
+                    .await
+                    .unwrap()
+                    .proposal
+                    .position_size
+            }
+        };
+
+        // $200 at risk over a $40 stop is 5 units; half conviction risks $100
+        assert_eq!(size_for("BTC/USDT").await, dec!(5));
+        assert_eq!(size_for("ETH/USDT").await, dec!(2.5));
+    }
+
+    #[tokio::test]
+    async fn test_thin_book_lowers_confidence() {
+        let orientator = PositionOrientator::new();
+        let confidence_with_depth = |size: f64| {
+            let orientator = &orientator;
+            async move {
+                let ooda_loop = orienting_loop().await;
+                let observation = MarketObservation {
+                    depth: Some(BookDepth { bid_size: size, ask_size: size }),
+                    ..observation("BTC/USDT", 50000.0)
+                };
+                orientator
+                    .orient(&observation, &ooda_loop, dec!(10000), dec!(0.02), dec!(0.02))
+                    .await
+                    .unwrap()
+                    .confidence
+            }
+        };
+
+        // 0.5 BTC a side is 25k of liquidity, 10 BTC is 500k
+        let thin = confidence_with_depth(0.5).await;
+        let deep = confidence_with_depth(10.0).await;
+
+        assert!(thin < deep);
+        assert_eq!(deep, 1.0);
+        assert!((thin - 0.5).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_slow_sizing_aborts_orientation() {
+        let orientator = PositionOrientator::new()
+            .with_sizing_budget(Duration::from_millis(5))
+            .with_sizer(Arc::new(SlowSizer { delay: Duration::from_millis(20) }));
+        let ooda_loop = orienting_loop().await;
+
+        let result = orientator
+            .orient(&observation("BTC/USDT", 50000.0), &ooda_loop, dec!(10000), dec!(0.02), dec!(0.02))
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(OrientationError::SizingBudgetExceeded { budget_ms: 5, .. })
+        ));
+        // The loop never reached Deciding, and the overrun was counted
+        assert_eq!(ooda_loop.get_state().await, OodaState::Orienting);
+        assert_eq!(ooda_loop.metrics().await.sizing_budget_overruns, 1);
+    }
+
+    #[test]
+    fn test_short_snapping_is_risk_conservative() {
+        let orientator = PositionOrientator::new()
+            .with_symbol_metadata(SymbolMetadata::new("ETH/USDT", dec!(0.01)));
+
+        let (stop, target) =
+            orientator.snap_to_ticks("ETH/USDT", &TradeDirection::Short, dec!(3060.123), dec!(2879.987));
+
+        assert_eq!(stop, dec!(3060.13));
+        assert_eq!(target, dec!(2879.99));
+    }
+
+    #[test]
+    fn test_unknown_symbol_is_not_snapped() {
+        let orientator = PositionOrientator::new();
+        let (stop, target) =
+            orientator.snap_to_ticks("SOL/USDT", &TradeDirection::Long, dec!(98.123), dec!(104.567));
+
+        assert_eq!(stop, dec!(98.123));
+        assert_eq!(target, dec!(104.567));
+    }
+}
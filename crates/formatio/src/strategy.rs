@@ -0,0 +1,469 @@
+//! Automated strategies that feed trade intents into the OODA loop
+//!
+//! A strategy watches market observations and emits `TradeIntent`s. Intents
+//! from strategies go through the same risk-gated OODA cycle as API requests,
+//! so the Testudo Protocol applies to automated and manual trades alike.
+//!
+//! With paper-until-proven enabled, a new strategy's intents are routed to a
+//! paper venue until its paper track record meets the promotion criteria;
+//! only then does it trade live.
+
+use crate::ooda::{OodaLoop, OodaLoopError};
+use crate::types::{ExecutionPlan, MarketObservation, TradeDirection, TradeIntent};
+use rust_decimal::Decimal;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use thiserror::Error;
+
+/// Default number of profitable paper trades that promote a strategy to live
+pub const DEFAULT_PROMOTION_PROFITABLE_TRADES: u32 = 10;
+
+/// Account parameters a strategy uses when emitting intents
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrategyContext {
+    pub account_equity: Decimal,
+    pub risk_percentage: Decimal,
+}
+
+/// Errors from configuring a strategy
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum StrategyError {
+    #[error("Short period {short_period} must be positive and shorter than the long period {long_period}")]
+    InvalidPeriods { short_period: usize, long_period: usize },
+}
+
+/// A source of trade intents driven by market observations
+pub trait Strategy: Send + Sync {
+    /// Unique name of the strategy, used to enable or disable it
+    fn name(&self) -> &str;
+
+    /// Process an observation, returning an intent when the strategy signals a trade
+    fn on_observation(
+        &mut self,
+        observation: &MarketObservation,
+        context: &StrategyContext,
+    ) -> Option<TradeIntent>;
+}
+
+/// Moving-average crossover on a single symbol
+///
+/// Emits a long intent when the short average crosses above the long average
+/// and a short intent when it crosses below.
+pub struct MovingAverageCrossover {
+    name: String,
+    symbol: String,
+    short_period: usize,
+    long_period: usize,
+    prices: VecDeque<f64>,
+    short_above: Option<bool>,
+}
+
+impl MovingAverageCrossover {
+    pub fn new(symbol: impl Into<String>, short_period: usize, long_period: usize) -> Result<Self, StrategyError> {
+        if short_period == 0 || short_period >= long_period {
+            return Err(StrategyError::InvalidPeriods { short_period, long_period });
+        }
+        let symbol = symbol.into();
+
+        Ok(Self {
+            name: format!("ma_crossover_{}_{}_{}", symbol, short_period, long_period),
+            symbol,
+            short_period,
+            long_period,
+            prices: VecDeque::with_capacity(long_period),
+            short_above: None,
+        })
+    }
+
+    fn average(&self, period: usize) -> f64 {
+        self.prices.iter().rev().take(period).sum::<f64>() / period as f64
+    }
+}
+
+impl Strategy for MovingAverageCrossover {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn on_observation(
+        &mut self,
+        observation: &MarketObservation,
+        context: &StrategyContext,
+    ) -> Option<TradeIntent> {
+        if observation.symbol != self.symbol {
+            return None;
+        }
+
+        if self.prices.len() == self.long_period {
+            self.prices.pop_front();
+        }
+        self.prices.push_back(observation.price);
+        if self.prices.len() < self.long_period {
+            return None;
+        }
+
+        let short_average = self.average(self.short_period);
+        let long_average = self.average(self.long_period);
+        if short_average == long_average {
+            return None;
+        }
+
+        let short_above = short_average > long_average;
+        let crossed = self.short_above.is_some_and(|previous| previous != short_above);
+        self.short_above = Some(short_above);
+
+        crossed.then(|| TradeIntent {
+            symbol: self.symbol.clone(),
+            direction: if short_above { TradeDirection::Long } else { TradeDirection::Short },
+            account_equity: context.account_equity,
+            risk_percentage: context.risk_percentage,
+            preferred_exchange: None,
+            quote_amount: None,
+        })
+    }
+}
+
+/// Where a strategy's intents are executed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TradingMode {
+    /// Simulated execution against a paper venue
+    Paper,
+    /// Real orders on the exchange
+    Live,
+}
+
+/// Paper track record a strategy needs before it may trade live
+///
+/// Either criterion promotes the strategy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PromotionCriteria {
+    /// Profitable paper trades required
+    pub min_profitable_trades: u32,
+    /// Number of most recent paper trades whose mean R multiple, once that
+    /// many have closed, must be positive; `None` disables the check
+    pub expectancy_window: Option<usize>,
+}
+
+impl Default for PromotionCriteria {
+    fn default() -> Self {
+        Self {
+            min_profitable_trades: DEFAULT_PROMOTION_PROFITABLE_TRADES,
+            expectancy_window: None,
+        }
+    }
+}
+
+impl PromotionCriteria {
+    /// Whether a track record of R multiples, oldest first, meets the bar
+    pub fn is_met(&self, paper_results: &[Decimal]) -> bool {
+        let profitable = paper_results.iter().filter(|r| **r > Decimal::ZERO).count();
+        if profitable >= self.min_profitable_trades as usize {
+            return true;
+        }
+
+        self.expectancy_window
+            .filter(|window| *window > 0 && paper_results.len() >= *window)
+            .is_some_and(|window| {
+                let recent = &paper_results[paper_results.len() - window..];
+                recent.iter().sum::<Decimal>() / Decimal::from(window) > Decimal::ZERO
+            })
+    }
+}
+
+/// An intent emitted by a strategy, with where it should execute
+#[derive(Debug, Clone)]
+pub struct StrategySignal {
+    pub user_id: String,
+    pub strategy: String,
+    pub mode: TradingMode,
+    pub intent: TradeIntent,
+}
+
+struct RegisteredStrategy {
+    strategy: Box<dyn Strategy>,
+    context: StrategyContext,
+    enabled: bool,
+    mode: TradingMode,
+    /// R multiples of closed paper trades, oldest first
+    paper_results: Vec<Decimal>,
+}
+
+/// Strategies registered per user
+///
+/// Newly registered strategies are disabled until explicitly enabled. With
+/// paper-until-proven criteria set they also start in paper mode.
+#[derive(Default)]
+pub struct StrategyRegistry {
+    strategies: HashMap<String, Vec<RegisteredStrategy>>,
+    promotion_criteria: Option<PromotionCriteria>,
+}
+
+impl StrategyRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep newly registered strategies in paper mode until their paper
+    /// track record meets `criteria`
+    pub fn with_paper_until_proven(mut self, criteria: PromotionCriteria) -> Self {
+        self.promotion_criteria = Some(criteria);
+        self
+    }
+
+    /// Current trading mode of a user's strategy
+    pub fn mode(&self, user_id: &str, strategy_name: &str) -> Option<TradingMode> {
+        self.find(user_id, strategy_name).map(|registered| registered.mode)
+    }
+
+    /// Record the R multiple of a closed paper trade
+    ///
+    /// Promotes the strategy to live once its track record meets the
+    /// criteria, and returns its mode afterwards. Results for strategies
+    /// already live are ignored.
+    pub fn record_paper_result(
+        &mut self,
+        user_id: &str,
+        strategy_name: &str,
+        r_multiple: Decimal,
+    ) -> Option<TradingMode> {
+        let criteria = self.promotion_criteria.clone();
+        let registered = self.find_mut(user_id, strategy_name)?;
+        if registered.mode == TradingMode::Paper {
+            registered.paper_results.push(r_multiple);
+            if criteria.is_none_or(|criteria| criteria.is_met(&registered.paper_results)) {
+                registered.mode = TradingMode::Live;
+            }
+        }
+        Some(registered.mode)
+    }
+
+    fn find(&self, user_id: &str, strategy_name: &str) -> Option<&RegisteredStrategy> {
+        self.strategies.get(user_id).and_then(|strategies| {
+            strategies
+                .iter()
+                .find(|registered| registered.strategy.name() == strategy_name)
+        })
+    }
+
+    fn find_mut(&mut self, user_id: &str, strategy_name: &str) -> Option<&mut RegisteredStrategy> {
+        self.strategies.get_mut(user_id).and_then(|strategies| {
+            strategies
+                .iter_mut()
+                .find(|registered| registered.strategy.name() == strategy_name)
+        })
+    }
+
+    /// Register a strategy for a user, disabled
+    pub fn register(
+        &mut self,
+        user_id: &str,
+        strategy: Box<dyn Strategy>,
+        context: StrategyContext,
+    ) {
+        self.strategies
+            .entry(user_id.to_string())
+            .or_default()
+            .push(RegisteredStrategy {
+                strategy,
+                context,
+                enabled: false,
+                mode: match self.promotion_criteria {
+                    Some(_) => TradingMode::Paper,
+                    None => TradingMode::Live,
+                },
+                paper_results: Vec::new(),
+            });
+    }
+
+    /// Enable or disable a user's strategy by name
+    ///
+    /// Returns false if the user has no strategy with that name.
+    pub fn set_enabled(&mut self, user_id: &str, strategy_name: &str, enabled: bool) -> bool {
+        self.find_mut(user_id, strategy_name)
+            .map(|registered| registered.enabled = enabled)
+            .is_some()
+    }
+
+    /// Names of a user's enabled strategies
+    pub fn enabled_strategies(&self, user_id: &str) -> Vec<&str> {
+        self.strategies
+            .get(user_id)
+            .map(|strategies| {
+                strategies
+                    .iter()
+                    .filter(|registered| registered.enabled)
+                    .map(|registered| registered.strategy.name())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Feed an observation to every enabled strategy
+    ///
+    /// Returns the emitted intents with the user and strategy they belong to
+    /// and the mode they should execute in.
+    pub fn on_observation(&mut self, observation: &MarketObservation) -> Vec<StrategySignal> {
+        let mut signals = Vec::new();
+
+        for (user_id, strategies) in &mut self.strategies {
+            for registered in strategies.iter_mut().filter(|registered| registered.enabled) {
+                if let Some(intent) = registered
+                    .strategy
+                    .on_observation(observation, &registered.context)
+                {
+                    signals.push(StrategySignal {
+                        user_id: user_id.clone(),
+                        strategy: registered.strategy.name().to_string(),
+                        mode: registered.mode,
+                        intent,
+                    });
+                }
+            }
+        }
+
+        signals
+    }
+}
+
+/// Runs strategy signals on the live or paper OODA loop by their mode
+pub struct StrategyRouter {
+    live: Arc<OodaLoop>,
+    paper: Arc<OodaLoop>,
+}
+
+impl StrategyRouter {
+    /// `paper` should be wired to a simulated venue; it applies the same
+    /// risk checks as `live`
+    pub fn new(live: Arc<OodaLoop>, paper: Arc<OodaLoop>) -> Self {
+        Self { live, paper }
+    }
+
+    pub async fn execute(&self, signal: StrategySignal) -> Result<ExecutionPlan, OodaLoopError> {
+        let ooda_loop = match signal.mode {
+            TradingMode::Paper => &self.paper,
+            TradingMode::Live => &self.live,
+        };
+        ooda_loop.execute_cycle(signal.intent).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decider::RiskDecider;
+    use crate::ooda::OodaLoop;
+    use prudentia::exchange::MockExchange;
+    use prudentia::risk::{MaxTradeRiskRule, RiskManagementProtocol};
+    use rust_decimal_macros::dec;
+    use std::sync::Arc;
+    use std::time::Instant;
+
+    fn observation(price: f64) -> MarketObservation {
+        MarketObservation {
+            symbol: "BTC/USDT".to_string(),
+            price,
+            volume: 100.0,
+            timestamp: Instant::now(),
+            atr: None,
+            depth: None,
+        }
+    }
+
+    fn context() -> StrategyContext {
+        StrategyContext {
+            account_equity: dec!(10000),
+            risk_percentage: dec!(0.02),
+        }
+    }
+
+    #[test]
+    fn test_crossover_rejects_invalid_periods() {
+        for (short_period, long_period) in [(0, 3), (3, 3), (5, 3)] {
+            assert_eq!(
+                MovingAverageCrossover::new("BTC/USDT", short_period, long_period).err(),
+                Some(StrategyError::InvalidPeriods { short_period, long_period })
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_crossover_intent_flows_through_risk_assessment() {
+        let mut registry = StrategyRegistry::new();
+        let strategy = MovingAverageCrossover::new("BTC/USDT", 2, 3).unwrap();
+        let name = strategy.name().to_string();
+        registry.register("trader-1", Box::new(strategy), context());
+
+        // Disabled strategies never see observations
+        assert!(registry.on_observation(&observation(100.0)).is_empty());
+        assert!(registry.set_enabled("trader-1", &name, true));
+
+        // Falling prices put the short average below the long average
+        for price in [100.0, 90.0, 80.0] {
+            assert!(registry.on_observation(&observation(price)).is_empty());
+        }
+
+        // A sharp rise crosses the short average back above the long one
+        let signals = registry.on_observation(&observation(120.0));
+        assert_eq!(signals.len(), 1);
+        let StrategySignal { user_id, strategy, mode, intent } = signals.into_iter().next().unwrap();
+        assert_eq!(user_id, "trader-1");
+        assert_eq!(strategy, name);
+        assert_eq!(mode, TradingMode::Live);
+        assert_eq!(intent.direction, TradeDirection::Long);
+
+        let protocol = Arc::new(RiskManagementProtocol::new().add_rule(MaxTradeRiskRule::new()));
+        let ooda_loop = OodaLoop::with_all_components(
+            Arc::new(MockExchange::new()),
+            Arc::new(RiskDecider::new(protocol)),
+        );
+
+        let plan = ooda_loop.execute_cycle(intent).await.unwrap();
+        assert!(plan.approved, "{}", plan.risk_assessment);
+        assert_eq!(plan.setup.symbol, "BTC/USDT");
+    }
+
+    #[tokio::test]
+    async fn test_unproven_strategy_trades_paper_until_promoted() {
+        let mut registry = StrategyRegistry::new().with_paper_until_proven(PromotionCriteria {
+            min_profitable_trades: 2,
+            expectancy_window: None,
+        });
+        let strategy = MovingAverageCrossover::new("BTC/USDT", 2, 3).unwrap();
+        let name = strategy.name().to_string();
+        registry.register("trader-1", Box::new(strategy), context());
+        registry.set_enabled("trader-1", &name, true);
+        assert_eq!(registry.mode("trader-1", &name), Some(TradingMode::Paper));
+
+        let live_exchange = Arc::new(MockExchange::new());
+        let paper_exchange = Arc::new(MockExchange::new());
+        let protocol = Arc::new(RiskManagementProtocol::new().add_rule(MaxTradeRiskRule::new()));
+        let decider = Arc::new(RiskDecider::new(protocol));
+        let router = StrategyRouter::new(
+            Arc::new(OodaLoop::with_all_components(live_exchange.clone(), decider.clone())),
+            Arc::new(OodaLoop::with_all_components(paper_exchange.clone(), decider)),
+        );
+
+        for price in [100.0, 90.0, 80.0] {
+            registry.on_observation(&observation(price));
+        }
+        let signal = registry.on_observation(&observation(120.0)).remove(0);
+        assert_eq!(signal.mode, TradingMode::Paper);
+        assert!(router.execute(signal).await.unwrap().approved);
+        assert_eq!(paper_exchange.get_placed_orders().await.len(), 1);
+        assert!(live_exchange.get_placed_orders().await.is_empty());
+
+        // One winner and one loser fall short of two profitable paper trades
+        assert_eq!(registry.record_paper_result("trader-1", &name, dec!(1.5)), Some(TradingMode::Paper));
+        assert_eq!(registry.record_paper_result("trader-1", &name, dec!(-1)), Some(TradingMode::Paper));
+        assert_eq!(registry.record_paper_result("trader-1", &name, dec!(2)), Some(TradingMode::Live));
+
+        // The next crossover, back below, executes live
+        registry.on_observation(&observation(60.0));
+        let signal = registry.on_observation(&observation(40.0)).remove(0);
+        assert_eq!(signal.intent.direction, TradeDirection::Short);
+        assert_eq!(signal.mode, TradingMode::Live);
+        assert!(router.execute(signal).await.unwrap().approved);
+        assert_eq!(live_exchange.get_placed_orders().await.len(), 1);
+        assert_eq!(paper_exchange.get_placed_orders().await.len(), 1);
+    }
+}
@@ -2,6 +2,7 @@
 
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
 use std::time::{Duration, Instant};
 use testudo_types::OrderSide;
 
@@ -14,6 +15,11 @@ pub struct TradeIntent {
     pub direction: TradeDirection,
     pub account_equity: Decimal,
     pub risk_percentage: Decimal,
+    /// Exchange to execute on while it is healthy; `None` uses the failover primary
+    pub preferred_exchange: Option<String>,
+    /// Spend this much quote currency instead of sizing from `risk_percentage`;
+    /// risk is checked against the position the amount actually buys
+    pub quote_amount: Option<Decimal>,
 }
 
 /// A snapshot of market conditions for a specific symbol.
@@ -23,6 +29,24 @@ pub struct MarketObservation {
     pub price: f64,
     pub volume: f64,
     pub timestamp: Instant,
+    /// Recent average true range, in price units, when the feed provides it.
+    pub atr: Option<f64>,
+    /// Top-of-book depth, when the feed provides it.
+    pub depth: Option<BookDepth>,
+}
+
+/// Quantity resting at the best bid and ask, in base units.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BookDepth {
+    pub bid_size: f64,
+    pub ask_size: f64,
+}
+
+impl BookDepth {
+    /// Quote-currency value of the thinner side of the book at `price`.
+    pub fn liquidity(&self, price: f64) -> f64 {
+        self.bid_size.min(self.ask_size) * price
+    }
 }
 
 /// A fully calculated trade setup, including position size.
@@ -37,6 +61,30 @@ pub struct TradeSetup {
     pub side: OrderSide,
 }
 
+impl TradeSetup {
+    /// Amount lost if the stop is hit, in quote currency.
+    ///
+    /// A stop at or beyond the entry (breakeven or locked-in profit) carries no risk.
+    pub fn risk_amount(&self) -> Decimal {
+        let distance = match self.side {
+            OrderSide::Buy => self.entry_price - self.stop_loss,
+            OrderSide::Sell => self.stop_loss - self.entry_price,
+        };
+        distance.max(Decimal::ZERO) * self.position_size
+    }
+
+    /// Limit price for a stop-limit protective order.
+    ///
+    /// The limit sits `slippage_tolerance` (a fraction of the stop price) past
+    /// the trigger in the direction of the exit, bounding the worst fill.
+    pub fn stop_limit_price(&self, slippage_tolerance: Decimal) -> Decimal {
+        match self.side {
+            OrderSide::Buy => self.stop_loss * (Decimal::ONE - slippage_tolerance),
+            OrderSide::Sell => self.stop_loss * (Decimal::ONE + slippage_tolerance),
+        }
+    }
+}
+
 /// A proposal generated by the Orientator, ready for risk assessment.
 #[derive(Debug, Clone)]
 pub struct TradeProposal {
@@ -54,12 +102,91 @@ pub struct ExecutionPlan {
     pub setup: TradeSetup,
     pub approved: bool,
     pub risk_assessment: String,
+    /// Account equity the setup was sized against
+    pub account_equity: Decimal,
+    /// Maximum fill slippage past the stop, as a fraction of the stop price.
+    /// When set, the stop is placed as a stop-limit order at that offset.
+    pub stop_slippage_tolerance: Option<Decimal>,
+    /// Rule violations behind a rejection; empty when approved
+    pub violations: Vec<prudentia::types::ProtocolViolation>,
+    /// Rules that failed to run during the assessment, with their errors
+    pub failed_rules: Vec<prudentia::risk::RuleFailure>,
+    /// Rules that ran and rejected the trade
+    pub rejecting_rules: Vec<String>,
+    /// Exchange requested by the trader, carried over from the intent
+    pub preferred_exchange: Option<String>,
+    /// Set when the position was shrunk to fit the portfolio risk budget
+    pub size_reduction: Option<SizeReduction>,
+    /// Id of the proposal the plan was decided on, and of the position it opens
+    pub position_id: uuid::Uuid,
+    /// Orders placed in the Act phase; `None` until the plan has executed
+    pub execution: Option<crate::executor::ExecutionResult>,
+}
+
+/// A position shrunk to fit the remaining portfolio risk budget
+#[derive(Debug, Clone, PartialEq)]
+pub struct SizeReduction {
+    pub requested_size: Decimal,
+    pub reduced_size: Decimal,
+    /// Risk of the reduced trade, as a fraction of account equity
+    pub reduced_risk: Decimal,
+    /// Human-readable explanation for the trader
+    pub notice: String,
+}
+
+/// Risk already open in positions correlated with a new trade.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CorrelatedExposure {
+    /// Open risk in the correlated positions, as a fraction of account equity.
+    pub open_risk: Decimal,
+    /// Estimated correlation between the new trade and that exposure (0 to 1).
+    pub correlation: Decimal,
+}
+
+impl CorrelatedExposure {
+    /// Portion of the open risk that moves with the new trade.
+    pub fn effective_risk(&self) -> Decimal {
+        self.open_risk * self.correlation.max(Decimal::ZERO).min(Decimal::ONE)
+    }
+}
+
+/// Exchange price rules for a symbol, used to produce exchange-valid prices.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolMetadata {
+    pub symbol: String,
+    /// Minimum price increment accepted by the exchange.
+    pub tick_size: Decimal,
+}
+
+impl SymbolMetadata {
+    pub fn new(symbol: impl Into<String>, tick_size: Decimal) -> Self {
+        Self {
+            symbol: symbol.into(),
+            tick_size,
+        }
+    }
+
+    /// Round a price down to the nearest valid tick.
+    pub fn floor_to_tick(&self, price: Decimal) -> Decimal {
+        if self.tick_size <= Decimal::ZERO {
+            return price;
+        }
+        (price / self.tick_size).floor() * self.tick_size
+    }
+
+    /// Round a price up to the nearest valid tick.
+    pub fn ceil_to_tick(&self, price: Decimal) -> Decimal {
+        if self.tick_size <= Decimal::ZERO {
+            return price;
+        }
+        (price / self.tick_size).ceil() * self.tick_size
+    }
 }
 
 // --- Enums and Metrics ---
 
 /// The direction of a trade.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TradeDirection {
     Long,
     Short,
@@ -84,6 +211,12 @@ pub struct LoopMetrics {
     pub total_latency: Option<Duration>,
     pub last_updated: Instant,
     pub last_execution_time: Option<DateTime<Utc>>,
+    /// Cycles aborted because position sizing exceeded its latency budget.
+    pub sizing_budget_overruns: u64,
+    /// Positions sized in the orient phase, including any over budget.
+    pub sizing_calculations: u64,
+    /// Time spent in those sizings, to derive their mean latency.
+    pub sizing_latency_total: Duration,
 }
 
 impl LoopMetrics {
@@ -96,6 +229,9 @@ impl LoopMetrics {
             total_latency: None,
             last_updated: Instant::now(),
             last_execution_time: None,
+            sizing_budget_overruns: 0,
+            sizing_calculations: 0,
+            sizing_latency_total: Duration::ZERO,
         }
     }
 }
@@ -106,6 +242,38 @@ impl Default for LoopMetrics {
     }
 }
 
+/// Latency budget for each OODA phase; phases over budget are logged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PhaseBudgets {
+    pub observe: Duration,
+    pub orient: Duration,
+    pub decide: Duration,
+    pub act: Duration,
+}
+
+impl PhaseBudgets {
+    pub fn for_phase(&self, phase: &OodaPhase) -> Duration {
+        match phase {
+            OodaPhase::Observe => self.observe,
+            OodaPhase::Orient => self.orient,
+            OodaPhase::Decide => self.decide,
+            OodaPhase::Act => self.act,
+        }
+    }
+}
+
+impl Default for PhaseBudgets {
+    /// The `[ooda]` performance targets in `config/default.toml`
+    fn default() -> Self {
+        Self {
+            observe: Duration::from_millis(20),
+            orient: Duration::from_millis(50),
+            decide: Duration::from_millis(30),
+            act: Duration::from_millis(100),
+        }
+    }
+}
+
 /// Error types for the decision process.
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum DecisionError {
@@ -79,7 +79,7 @@ async fn test_valid_trade_proposal_handling() {
     
     // Validate decision (could be approved or rejected depending on risk assessment)
     match decision_result.decision {
-        RiskDecision::Execute { approved_position_size, execution_priority } => {
+        RiskDecision::Execute { approved_position_size, execution_priority, .. } => {
             assert!(approved_position_size > Decimal::ZERO, "Position size should be positive");
             assert!(
                 matches!(execution_priority, ExecutionPriority::Standard | ExecutionPriority::Careful),
@@ -87,7 +87,7 @@ async fn test_valid_trade_proposal_handling() {
             );
             println!("Trade APPROVED: size={}, priority={:?}", approved_position_size, execution_priority);
         },
-        RiskDecision::Reject { rejection_reason, violation_count } => {
+        RiskDecision::Reject { rejection_reason, violation_count, .. } => {
             assert!(!rejection_reason.is_empty(), "Should have rejection reason");
             assert!(violation_count > 0, "Should have violations");
             println!("Trade REJECTED: {} violations - {}", violation_count, rejection_reason);
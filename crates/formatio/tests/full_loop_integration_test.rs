@@ -172,6 +172,8 @@ impl TestEnvironment {
             price: 50000.0,
             volume: 1000.0,
             timestamp: Instant::now(),
+            atr: None,
+            depth: None,
         }
     }
 
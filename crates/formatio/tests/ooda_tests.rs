@@ -307,6 +307,8 @@ async fn test_observation_result_helper_methods() {
         price: 50000.0,
         volume: 1000.0,
         timestamp: std::time::Instant::now(),
+        atr: None,
+        depth: None,
     };
     
     let success_result = ObservationResult {
@@ -348,6 +350,8 @@ async fn test_orientator_successful_trade_proposal_creation() {
         price: 50000.0,
         volume: 1000.0,
         timestamp: std::time::Instant::now(),
+        atr: None,
+        depth: None,
     };
     
     // Set up OODA loop in Orienting state (previous state transition from Observer)
@@ -416,6 +420,8 @@ async fn test_orientator_rejects_stale_market_data() {
         price: 50000.0,
         volume: 1000.0,
         timestamp: stale_timestamp,
+        atr: None,
+        depth: None,
     };
     
     // Set up OODA loop in Orienting state
@@ -464,6 +470,8 @@ async fn test_orientator_rejects_invalid_market_data() {
             price: 50000.0,
             volume: 1000.0,
             timestamp: std::time::Instant::now(),
+            atr: None,
+            depth: None,
         },
         // Zero price
         formatio::types::MarketObservation {
@@ -471,6 +479,8 @@ async fn test_orientator_rejects_invalid_market_data() {
             price: 0.0,
             volume: 1000.0,
             timestamp: std::time::Instant::now(),
+            atr: None,
+            depth: None,
         },
         // Negative price
         formatio::types::MarketObservation {
@@ -478,6 +488,8 @@ async fn test_orientator_rejects_invalid_market_data() {
             price: -100.0,
             volume: 1000.0,
             timestamp: std::time::Instant::now(),
+            atr: None,
+            depth: None,
         },
         // Negative volume
         formatio::types::MarketObservation {
@@ -485,6 +497,8 @@ async fn test_orientator_rejects_invalid_market_data() {
             price: 50000.0,
             volume: -500.0,
             timestamp: std::time::Instant::now(),
+            atr: None,
+            depth: None,
         },
     ];
     
@@ -532,6 +546,8 @@ async fn test_orientator_confidence_calculation() {
         price: 50000.0,
         volume: 5000.0, // High volume
         timestamp: std::time::Instant::now(),
+        atr: None,
+        depth: None,
     };
     
     let result = orientator.orient(
@@ -555,6 +571,8 @@ async fn test_orientator_confidence_calculation() {
         price: 50000.0,
         volume: 100.0, // Low volume
         timestamp: std::time::Instant::now() - Duration::from_secs(2), // Older data
+        atr: None,
+        depth: None,
     };
     
     let result2 = orientator.orient(
@@ -592,6 +610,8 @@ async fn test_orientator_with_custom_calculator() {
         price: 3000.0,
         volume: 2000.0,
         timestamp: std::time::Instant::now(),
+        atr: None,
+        depth: None,
     };
     
     // Trade setup parameters for higher risk scenario
@@ -640,6 +660,8 @@ async fn test_orientator_performance_timing() {
         price: 50000.0,
         volume: 1000.0,
         timestamp: std::time::Instant::now(),
+        atr: None,
+        depth: None,
     };
     
     // Execute orientation multiple times to test consistency
@@ -0,0 +1,35 @@
+//! WebSocket frame encoding benchmarks
+//!
+//! Compares JSON text frames against MessagePack binary frames for the
+//! price ticks that dominate streaming traffic.
+
+use chrono::Utc;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use imperium::websocket::MessageEncoding;
+use imperium::WebSocketMessage;
+use rust_decimal_macros::dec;
+
+fn price_update() -> WebSocketMessage {
+    WebSocketMessage::PriceUpdate {
+        symbol: "BTCUSDT".to_string(),
+        bid_price: dec!(49999.50),
+        ask_price: dec!(50000.50),
+        last_price: dec!(50000.00),
+        timestamp: Utc::now(),
+    }
+}
+
+fn bench_encoding(c: &mut Criterion) {
+    let message = price_update();
+
+    c.bench_function("encode_price_update_json", |b| {
+        b.iter(|| MessageEncoding::Json.encode(black_box(&message)).unwrap())
+    });
+
+    c.bench_function("encode_price_update_msgpack", |b| {
+        b.iter(|| MessageEncoding::Msgpack.encode(black_box(&message)).unwrap())
+    });
+}
+
+criterion_group!(benches, bench_encoding);
+criterion_main!(benches);
@@ -0,0 +1,201 @@
+//! Risk alert delivery
+//!
+//! Alerts raised by Prudentia are persisted to the `system_events` audit log
+//! and then pushed to the user's live WebSocket connections. Other
+//! user-facing notifications go through a `Notifier`: the live connections
+//! by default, or a webhook the user has configured.
+
+use async_trait::async_trait;
+use prudentia::{AdverseExcursionAlert, DailyLossAlert, DailyLossAlertLevel};
+use serde_json::json;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::api::ApiState;
+use crate::database::{EventSeverity, SystemEvent};
+use crate::types::WebSocketMessage;
+use crate::websocket::ConnectionManager;
+use crate::{ImperiumError, Result};
+
+/// How long a webhook has to accept a notification
+pub const DEFAULT_WEBHOOK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Delivers user-facing notifications such as daily summaries
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    /// Deliver a message to a user, returning the number of deliveries
+    async fn notify(&self, user_id: &str, message: &WebSocketMessage) -> Result<usize>;
+}
+
+/// Notifications go to the user's live WebSocket connections
+#[async_trait]
+impl Notifier for ConnectionManager {
+    async fn notify(&self, user_id: &str, message: &WebSocketMessage) -> Result<usize> {
+        Ok(self.send_to_user(user_id, message))
+    }
+}
+
+/// Notifications are POSTed as JSON to a user-configured URL
+///
+/// The body carries the user id and the message in its WebSocket encoding.
+/// Anything but a 2xx response, or no response within
+/// `DEFAULT_WEBHOOK_TIMEOUT`, counts as a failed delivery.
+pub struct WebhookNotifier {
+    url: String,
+    client: reqwest::Client,
+}
+
+impl WebhookNotifier {
+    /// Deliver to `url` through `client`, which may be shared between webhooks
+    pub fn new(url: impl Into<String>, client: reqwest::Client) -> Self {
+        Self { url: url.into(), client }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+#[async_trait]
+impl Notifier for WebhookNotifier {
+    async fn notify(&self, user_id: &str, message: &WebSocketMessage) -> Result<usize> {
+        let response = self
+            .client
+            .post(&self.url)
+            .timeout(DEFAULT_WEBHOOK_TIMEOUT)
+            .json(&json!({ "user_id": user_id, "message": message }))
+            .send()
+            .await
+            .map_err(|err| ImperiumError::NotificationFailed {
+                reason: format!("webhook unreachable: {}", err),
+            })?;
+
+        if !response.status().is_success() {
+            return Err(ImperiumError::NotificationFailed {
+                reason: format!("webhook responded with {}", response.status()),
+            });
+        }
+        Ok(1)
+    }
+}
+
+/// Build the audit log entry for a daily loss alert
+pub fn daily_loss_event(user_id: &str, alert: &DailyLossAlert) -> SystemEvent {
+    let severity = match alert.level {
+        DailyLossAlertLevel::Warning => EventSeverity::Warn,
+        DailyLossAlertLevel::Critical => EventSeverity::Critical,
+    };
+
+    SystemEvent {
+        event_type: "DAILY_LOSS_ALERT".to_string(),
+        severity,
+        component: "prudentia".to_string(),
+        message: alert.message(),
+        metadata: serde_json::to_value(alert).ok(),
+        user_id: Uuid::parse_str(user_id).ok(),
+    }
+}
+
+/// Audit a daily loss alert and broadcast it to the user's connections
+///
+/// Returns the number of connections the alert was delivered to.
+pub async fn publish_daily_loss_alert(
+    api_state: &ApiState,
+    connections: &ConnectionManager,
+    user_id: &str,
+    alert: &DailyLossAlert,
+) -> Result<usize> {
+    api_state.audit(daily_loss_event(user_id, alert)).await?;
+
+    Ok(connections.send_to_user(user_id, &WebSocketMessage::from(alert)))
+}
+
+/// Build the audit log entry for a position whose MAE neared its stop
+pub fn adverse_excursion_event(user_id: &str, alert: &AdverseExcursionAlert) -> SystemEvent {
+    SystemEvent {
+        event_type: "ADVERSE_EXCURSION_ALERT".to_string(),
+        severity: EventSeverity::Warn,
+        component: "prudentia".to_string(),
+        message: alert.message(),
+        metadata: serde_json::to_value(alert).ok(),
+        user_id: Uuid::parse_str(user_id).ok(),
+    }
+}
+
+/// Audit an adverse excursion alert and broadcast it to the user's connections
+///
+/// Returns the number of connections the alert was delivered to.
+pub async fn publish_adverse_excursion_alert(
+    api_state: &ApiState,
+    connections: &ConnectionManager,
+    user_id: &str,
+    alert: &AdverseExcursionAlert,
+) -> Result<usize> {
+    api_state.audit(adverse_excursion_event(user_id, alert)).await?;
+
+    Ok(connections.send_to_user(user_id, &WebSocketMessage::from(alert)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::AlertSeverity;
+    use crate::websocket::MessageEncoding;
+    use axum::extract::ws::Message;
+    use prudentia::TestudoProtocol;
+    use rust_decimal_macros::dec;
+
+    #[tokio::test]
+    async fn test_daily_loss_warning_reaches_client_once() {
+        let api_state = ApiState::new();
+        let connections = ConnectionManager::new();
+        let (_, mut rx) = connections.register("trader-1", MessageEncoding::Json);
+
+        let mut protocol = TestudoProtocol::new();
+        protocol.record_trade_outcome("BTCUSDT", dec!(0.02), true, Some(dec!(450)));
+
+        // Only the first check after crossing 80% produces an alert
+        let alerts: Vec<_> = (0..3)
+            .filter_map(|_| protocol.check_daily_loss_alert(dec!(10000)))
+            .collect();
+        assert_eq!(alerts.len(), 1);
+
+        assert_eq!(publish_daily_loss_alert(&api_state, &connections, "trader-1", &alerts[0]).await.unwrap(), 1);
+        let audited = api_state.audit_events();
+        assert_eq!(audited.len(), 1);
+        assert_eq!(audited[0].event_type, "DAILY_LOSS_ALERT");
+        assert_eq!(audited[0].severity, EventSeverity::Warn);
+
+        let frame = match rx.recv().await.unwrap() {
+            Message::Text(text) => serde_json::from_str::<WebSocketMessage>(&text).unwrap(),
+            other => panic!("Expected text frame, got: {:?}", other),
+        };
+
+        let WebSocketMessage::Sequenced { message, .. } = frame else {
+            panic!("Expected a sequenced frame, got: {:?}", frame);
+        };
+        match *message {
+            WebSocketMessage::RiskAlert { severity, rule, .. } => {
+                assert_eq!(severity, AlertSeverity::Warning);
+                assert_eq!(rule, "DailyLossLimit");
+            }
+            other => panic!("Expected RiskAlert, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_critical_alert_maps_to_critical_event() {
+        let alert = DailyLossAlert {
+            level: DailyLossAlertLevel::Critical,
+            daily_loss: dec!(500),
+            daily_limit: dec!(500),
+            utilization: dec!(1),
+        };
+
+        assert_eq!(daily_loss_event("trader-1", &alert).severity, EventSeverity::Critical);
+        assert!(matches!(
+            WebSocketMessage::from(&alert),
+            WebSocketMessage::RiskAlert { severity: AlertSeverity::Critical, .. }
+        ));
+    }
+}
@@ -1,10 +1,3551 @@
-//! API routes (placeholder)
+//! REST API routes mounted under `/api/v1`
+//!
+//! Handlers depend only on `ApiState`, extracted from the application state
+//! through `FromRef`, so the router can be exercised without a database.
+//!
+//! Every route belongs to a timeout category: fast reads time out quickly,
+//! while trade execution gets longer to complete the OODA cycle. Timed-out
+//! requests return 504 and drop the handler, cancelling any in-flight cycle.
 
-use axum::Router;
-use crate::AppState;
+use axum::{
+    error_handling::HandleErrorLayer,
+    extract::{FromRef, Path, Query, State},
+    http::{header, HeaderMap},
+    response::{IntoResponse, Response},
+    routing::{get, post, put},
+    BoxError, Json, Router,
+};
+use chrono::NaiveDate;
+use formatio::{
+    ActCommitment, Executor, OodaController, SharedProtocol, TradeDirection, TradeSetup,
+};
+use prudentia::risk::protocol::ProtocolStatus;
+use prudentia::{
+    CommissionSchedule, ConfigFormat, ExchangeAdapterTrait, ExchangeManager, MaxPortfolioRiskRule, MaxPositionUnitsRule,
+    OpenPosition,
+    ProtocolLimits, RiskProfile, RuleOutcomeCounts, TestudoProtocol, TradeSide,
+};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use sqlx::PgPool;
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    sync::{Arc, RwLock},
+    time::{Duration, Instant, SystemTime},
+};
+use testudo_types::{min_valid_stop, stop_too_close, OrderSide};
+use tokio::sync::{Mutex, Semaphore};
+use tokio::task::AbortHandle;
+use uuid::Uuid;
+use tower::ServiceBuilder;
+use tracing::warn;
 
-pub struct ApiState;
+use crate::alerts::{Notifier, WebhookNotifier};
+use crate::auth::AuthContext;
+use crate::cache::{prometheus_labelled_metric, prometheus_metric, CalculatorStats, SizingCache};
+use crate::database::{
+    backfill_r_multiples, record_imported_positions, record_system_event, record_trade_execution,
+    EventSeverity, SystemEvent, TradeExecutionRecord, R_BACKFILL_BATCH_SIZE,
+};
+use crate::fx::FxRates;
+use crate::idempotency::{self, Claim, IdempotencyStore, IDEMPOTENCY_KEY_HEADER};
+use crate::lifecycle::{self, PositionBook};
+use crate::reports::{DailyReports, DailySummary};
+use crate::types::{
+    AlertSeverity, BackfillResponse, ClosePositionRequest, ClosePositionResponse, ConfigSnapshot, CredentialsCheck, ExchangeStatus, ExecuteTradeRequest, ExecuteTradeResponse, ImpersonationResponse, ModifyBracketRequest, ModifyBracketResponse,
+    ImportPositionsRequest, ImportPositionsResponse, ImportedPositionSummary, NotTradableReason, NotificationTestResult, PortfolioHeat,
+    PortfolioResponse, PortfolioSnapshot, ProtocolStatusSummary, RecentAssessment, RiskSettings, SizingExplanation,
+    SizingExplanationRequest, StopValidation, StopValidationRequest, SymbolTradability, UserConfiguration,
+    WebSocketMessage,
+};
+use crate::websocket::ConnectionManager;
+use crate::{ApiResponse, AppState, FieldError, ImperiumError, Result};
+
+/// Default number of OODA cycles that may run concurrently
+pub const DEFAULT_MAX_CONCURRENT_CYCLES: usize = 4;
+
+/// Trade requests per user that may wait for an OODA cycle slot
+pub const DEFAULT_MAX_PENDING_CYCLES: usize = 4;
+
+/// Minimum time between changes to a user's risk settings
+///
+/// Stops limits being loosened trade by trade in the middle of a losing run.
+pub const DEFAULT_RISK_SETTINGS_COOLDOWN: Duration = Duration::from_secs(300);
+
+/// Risk assessments kept per user for support
+pub const RECENT_ASSESSMENT_LIMIT: usize = 20;
+
+/// Audit events kept in memory; the `system_events` table holds the full log
+pub const AUDIT_LOG_CAPACITY: usize = 1000;
+
+/// Request timeouts by route category
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestTimeouts {
+    /// Reads served from memory or a single query
+    pub read: Duration,
+    /// Trade execution, which runs a full OODA cycle against the exchange
+    pub trade_execution: Duration,
+}
+
+impl Default for RequestTimeouts {
+    fn default() -> Self {
+        Self {
+            read: Duration::from_secs(2),
+            trade_execution: Duration::from_secs(10),
+        }
+    }
+}
+
+/// Shared state for REST API handlers
+pub struct ApiState {
+    user_configurations: RwLock<HashMap<String, UserConfiguration>>,
+    trading_controller: Option<Arc<OodaController>>,
+    cycle_slots: Arc<Semaphore>,
+    max_concurrent_cycles: usize,
+    /// Each user's trade requests waiting for a cycle slot
+    pending_cycles: Arc<RwLock<HashMap<String, usize>>>,
+    max_pending_cycles: usize,
+    reports: DailyReports,
+    portfolios: RwLock<HashMap<String, PortfolioSnapshot>>,
+    open_positions: RwLock<HashMap<String, Vec<OpenPosition>>>,
+    /// Positions opened by trade executions, until their final exit
+    positions: PositionBook,
+    /// Modifies brackets for breakeven stop moves; none are made without it
+    breakeven_executor: Option<Arc<Executor>>,
+    /// Modifies brackets at a trader's request; none are made without it
+    bracket_executor: Option<Arc<Executor>>,
+    /// Portfolio rule kept in step with executed positions and their P&L
+    portfolio_rule: Option<MaxPortfolioRiskRule>,
+    /// Unit cap rule kept in step with the quantities executed positions hold
+    units_rule: Option<MaxPositionUnitsRule>,
+    fx_rates: FxRates,
+    exchange: Option<Arc<dyn ExchangeAdapterTrait + Send + Sync>>,
+    exchange_manager: Option<Arc<ExchangeManager>>,
+    /// Each user's protocol state, created from their limits on first use and
+    /// shared with the OODA cycles that trade for them
+    protocols: Mutex<HashMap<String, SharedProtocol>>,
+    db_pool: Option<PgPool>,
+    recent_assessments: RwLock<HashMap<String, VecDeque<RecentAssessment>>>,
+    audit_log: RwLock<VecDeque<SystemEvent>>,
+    /// Completed trade executions, replayed for retried idempotency keys
+    idempotency: Arc<IdempotencyStore<ExecuteTradeResponse>>,
+    /// Delivers notifications for users without a webhook configured
+    notifier: Option<Arc<dyn Notifier>>,
+    /// HTTP client shared by every user's webhook notifier
+    webhook_client: reqwest::Client,
+    /// Recent sizing explanations, served again for identical inputs
+    sizing_cache: SizingCache<SizingExplanation>,
+    /// Exchange fee rates used to price trades
+    commissions: CommissionSchedule,
+    /// Live WebSocket connections, reported on `/metrics` and sent position events
+    connections: Option<Arc<ConnectionManager>>,
+    /// When each user's risk settings last changed
+    settings_changed_at: RwLock<HashMap<String, Instant>>,
+    risk_settings_cooldown: Duration,
+}
+
+impl Default for ApiState {
+    fn default() -> Self {
+        Self {
+            user_configurations: RwLock::new(HashMap::new()),
+            trading_controller: None,
+            cycle_slots: Arc::new(Semaphore::new(DEFAULT_MAX_CONCURRENT_CYCLES)),
+            max_concurrent_cycles: DEFAULT_MAX_CONCURRENT_CYCLES,
+            pending_cycles: Arc::new(RwLock::new(HashMap::new())),
+            max_pending_cycles: DEFAULT_MAX_PENDING_CYCLES,
+            reports: DailyReports::new(),
+            portfolios: RwLock::new(HashMap::new()),
+            open_positions: RwLock::new(HashMap::new()),
+            positions: PositionBook::new(),
+            breakeven_executor: None,
+            bracket_executor: None,
+            portfolio_rule: None,
+            units_rule: None,
+            fx_rates: FxRates::new(),
+            exchange: None,
+            exchange_manager: None,
+            protocols: Mutex::new(HashMap::new()),
+            db_pool: None,
+            recent_assessments: RwLock::new(HashMap::new()),
+            audit_log: RwLock::new(VecDeque::new()),
+            idempotency: Arc::new(IdempotencyStore::new()),
+            notifier: None,
+            webhook_client: reqwest::Client::new(),
+            sizing_cache: SizingCache::new(),
+            commissions: CommissionSchedule::default(),
+            connections: None,
+            settings_changed_at: RwLock::new(HashMap::new()),
+            risk_settings_cooldown: DEFAULT_RISK_SETTINGS_COOLDOWN,
+        }
+    }
+}
+
+impl ApiState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enable trade execution through the given OODA controller
+    ///
+    /// At most `max_concurrent_cycles` cycles run at once; further requests
+    /// wait for a slot within their timeout.
+    pub fn with_trading_controller(
+        mut self,
+        controller: Arc<OodaController>,
+        max_concurrent_cycles: usize,
+    ) -> Self {
+        self.trading_controller = Some(controller);
+        self.cycle_slots = Arc::new(Semaphore::new(max_concurrent_cycles));
+        self.max_concurrent_cycles = max_concurrent_cycles;
+        self
+    }
+
+    /// Reject a user's trade requests once `max_pending_cycles` are already queued
+    ///
+    /// Only requests waiting for a cycle slot count; cycles already running
+    /// do not.
+    pub fn with_max_pending_cycles(mut self, max_pending_cycles: usize) -> Self {
+        self.max_pending_cycles = max_pending_cycles;
+        self
+    }
+
+    /// Consult the given exchange for symbol listings and position prices
+    pub fn with_exchange(mut self, exchange: Arc<dyn ExchangeAdapterTrait + Send + Sync>) -> Self {
+        self.exchange = Some(exchange);
+        self
+    }
+
+    /// The exchange consulted for listings and prices, when configured
+    pub fn exchange(&self) -> Option<Arc<dyn ExchangeAdapterTrait + Send + Sync>> {
+        self.exchange.clone()
+    }
+
+    /// Move executed positions' stops to breakeven through `executor`
+    ///
+    /// Use the executor trades are placed through, so the moves follow the
+    /// same execution mode and safety limits.
+    pub fn with_breakeven(mut self, executor: Arc<Executor>) -> Self {
+        self.breakeven_executor = Some(executor);
+        self
+    }
+
+    pub fn breakeven_executor(&self) -> Option<Arc<Executor>> {
+        self.breakeven_executor.clone()
+    }
+
+    /// Modify open positions' brackets through `executor` when traders ask to
+    ///
+    /// As with breakeven moves, use the executor trades are placed through.
+    pub fn with_bracket_executor(mut self, executor: Arc<Executor>) -> Self {
+        self.bracket_executor = Some(executor);
+        self
+    }
+
+    /// Keep `rule` in step with executed positions
+    ///
+    /// Clones of the rule share its positions, so a clone added to the
+    /// decider's protocol counts them. Each price tick records the positions'
+    /// unrealized P&L and MAE through it.
+    pub fn with_portfolio_rule(mut self, rule: MaxPortfolioRiskRule) -> Self {
+        self.portfolio_rule = Some(rule);
+        self
+    }
+
+    pub fn portfolio_rule(&self) -> Option<&MaxPortfolioRiskRule> {
+        self.portfolio_rule.as_ref()
+    }
+
+    /// Keep `rule`'s held quantities in step with executed positions
+    ///
+    /// As with the portfolio rule, a clone added to the decider's protocol
+    /// sees each entry's fill and each exit.
+    pub fn with_units_rule(mut self, rule: MaxPositionUnitsRule) -> Self {
+        self.units_rule = Some(rule);
+        self
+    }
+
+    pub fn units_rule(&self) -> Option<&MaxPositionUnitsRule> {
+        self.units_rule.as_ref()
+    }
+
+    /// List the exchanges registered with the given manager
+    pub fn with_exchange_manager(mut self, exchange_manager: Arc<ExchangeManager>) -> Self {
+        self.exchange_manager = Some(exchange_manager);
+        self
+    }
+
+    /// Persist imported positions and protocol state to Postgres
+    pub fn with_db_pool(mut self, db_pool: PgPool) -> Self {
+        self.db_pool = Some(db_pool);
+        self
+    }
+
+    /// Deliver notifications through `notifier` unless a user configures a webhook
+    pub fn with_notifier(mut self, notifier: Arc<dyn Notifier>) -> Self {
+        self.notifier = Some(notifier);
+        self
+    }
+
+    /// Serve repeated sizing calculations from the cache for `ttl`
+    pub fn with_sizing_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.sizing_cache = SizingCache::with_ttl(ttl);
+        self
+    }
+
+    /// Price trade fees with `commissions` instead of the default tier
+    pub fn with_commission_schedule(mut self, commissions: CommissionSchedule) -> Self {
+        self.commissions = commissions;
+        self
+    }
+
+    /// Stream position events to `connections` and report their replay buffers on `/metrics`
+    pub fn with_connections(mut self, connections: Arc<ConnectionManager>) -> Self {
+        self.connections = Some(connections);
+        self
+    }
+
+    /// Live WebSocket connections, when configured
+    pub fn connections(&self) -> Option<Arc<ConnectionManager>> {
+        self.connections.clone()
+    }
+
+    /// Set the minimum time between changes to a user's risk settings
+    pub fn with_risk_settings_cooldown(mut self, cooldown: Duration) -> Self {
+        self.risk_settings_cooldown = cooldown;
+        self
+    }
+
+    /// Position sizing counters since startup, trade-path sizings included
+    pub async fn calculator_stats(&self) -> CalculatorStats {
+        match &self.trading_controller {
+            Some(controller) => {
+                let metrics = controller.metrics().await;
+                self.sizing_cache
+                    .stats_with_trade_sizing(metrics.sizing_calculations, metrics.sizing_latency_total)
+            }
+            None => self.sizing_cache.stats(),
+        }
+    }
+
+    /// The notifier a user's notifications go through, if any
+    pub fn notifier_for(&self, configuration: &UserConfiguration) -> Option<Arc<dyn Notifier>> {
+        match &configuration.notification_webhook {
+            Some(url) => Some(Arc::new(WebhookNotifier::new(url.clone(), self.webhook_client.clone()))),
+            None => self.notifier.clone(),
+        }
+    }
+
+    /// The notifier a user's notifications go through, outside a request
+    ///
+    /// Users without a stored configuration have no webhook, so they get
+    /// the default notifier.
+    pub fn notifier_for_user(&self, user_id: &str) -> Option<Arc<dyn Notifier>> {
+        let configuration = self.user_configurations.read().unwrap().get(user_id).cloned();
+        match configuration {
+            Some(configuration) => self.notifier_for(&configuration),
+            None => self.notifier.clone(),
+        }
+    }
+
+    /// A user's protocol status, if their protocol state has been created
+    pub async fn protocol_status(&self, user_id: &str) -> Option<ProtocolStatus> {
+        let status = self.protocol(user_id).await?.lock().await.get_status();
+        Some(status)
+    }
+
+    /// A user's protocol, if it has been created
+    pub async fn protocol(&self, user_id: &str) -> Option<SharedProtocol> {
+        self.protocols.lock().await.get(user_id).cloned()
+    }
+
+    /// The protocol a user's trades are checked against
+    ///
+    /// Created from the user's limits on first use; every later call returns
+    /// the same handle, so imports, settings changes and trade cycles all
+    /// act on one protocol state.
+    pub async fn protocol_for(&self, auth_context: &AuthContext) -> SharedProtocol {
+        self.protocols
+            .lock()
+            .await
+            .entry(auth_context.user_id.clone())
+            .or_insert_with(|| {
+                let limits = self.configuration_for(auth_context).protocol_limits;
+                Arc::new(Mutex::new(TestudoProtocol::with_limits(limits)))
+            })
+            .clone()
+    }
+
+    /// OODA cycle slots not currently in use
+    pub fn available_cycle_slots(&self) -> usize {
+        self.cycle_slots.available_permits()
+    }
+
+    /// A user's trade requests currently waiting for a cycle slot
+    pub fn pending_cycles(&self, user_id: &str) -> usize {
+        self.pending_cycles.read().unwrap().get(user_id).copied().unwrap_or(0)
+    }
+
+    /// Claim a place in the user's cycle queue, or fail if it is full
+    fn enqueue_cycle(&self, user_id: &str) -> Result<PendingCycle> {
+        let mut pending = self.pending_cycles.write().unwrap();
+        let queued = pending.entry(user_id.to_string()).or_insert(0);
+        if *queued >= self.max_pending_cycles {
+            return Err(ImperiumError::TooManyPendingCycles {
+                limit: self.max_pending_cycles,
+            });
+        }
+        *queued += 1;
+        Ok(PendingCycle {
+            pending: self.pending_cycles.clone(),
+            user_id: user_id.to_string(),
+        })
+    }
+
+    /// Maximum number of concurrent OODA cycles
+    pub fn max_concurrent_cycles(&self) -> usize {
+        self.max_concurrent_cycles
+    }
+
+    /// Effective configuration for a user
+    ///
+    /// Users without a stored configuration get the defaults for their risk profile.
+    pub fn configuration_for(&self, auth_context: &AuthContext) -> UserConfiguration {
+        self.user_configurations
+            .read()
+            .unwrap()
+            .get(&auth_context.user_id)
+            .cloned()
+            .unwrap_or_else(|| UserConfiguration::for_profile(auth_context.risk_profile))
+    }
+
+    /// Protocol limits for a user outside a request
+    ///
+    /// Without an auth context the risk profile is unknown, so users without
+    /// a stored configuration get the default limits.
+    pub fn limits_for_user(&self, user_id: &str) -> ProtocolLimits {
+        self.user_configurations
+            .read()
+            .unwrap()
+            .get(user_id)
+            .map(|configuration| configuration.protocol_limits.clone())
+            .unwrap_or_else(ProtocolLimits::default_limits)
+    }
+
+    /// Closed-trade journal and generated daily summaries
+    pub fn reports(&self) -> &DailyReports {
+        &self.reports
+    }
+
+    /// Positions opened by trade executions
+    pub fn positions(&self) -> &PositionBook {
+        &self.positions
+    }
+
+    /// Rates used to display amounts in users' base currencies
+    pub fn fx_rates(&self) -> &FxRates {
+        &self.fx_rates
+    }
+
+    /// Record a user's latest portfolio figures, in the quote currency
+    pub fn set_portfolio(&self, user_id: &str, snapshot: PortfolioSnapshot) {
+        self.portfolios
+            .write()
+            .unwrap()
+            .insert(user_id.to_string(), snapshot);
+    }
+
+    /// A user's latest portfolio figures, in the quote currency
+    pub fn portfolio(&self, user_id: &str) -> Option<PortfolioSnapshot> {
+        self.portfolios.read().unwrap().get(user_id).copied()
+    }
+
+    /// Record a user's open positions and the risk each carries
+    pub fn set_open_positions(&self, user_id: &str, positions: Vec<OpenPosition>) {
+        self.open_positions
+            .write()
+            .unwrap()
+            .insert(user_id.to_string(), positions);
+    }
+
+    /// A user's open positions; empty when none are recorded
+    pub fn open_positions(&self, user_id: &str) -> Vec<OpenPosition> {
+        self.open_positions
+            .read()
+            .unwrap()
+            .get(user_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Remember the outcome of a user's risk assessment
+    pub fn record_assessment(&self, user_id: &str, assessment: RecentAssessment) {
+        let mut recent = self.recent_assessments.write().unwrap();
+        let assessments = recent.entry(user_id.to_string()).or_default();
+        assessments.push_front(assessment);
+        assessments.truncate(RECENT_ASSESSMENT_LIMIT);
+    }
+
+    /// A user's recent risk assessments, most recent first
+    pub fn recent_assessments(&self, user_id: &str) -> Vec<RecentAssessment> {
+        self.recent_assessments
+            .read()
+            .unwrap()
+            .get(user_id)
+            .map(|assessments| assessments.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Append an event to the audit log
+    ///
+    /// The event is persisted to `system_events` when a database is
+    /// configured; a failed write is returned so callers can refuse the
+    /// audited action.
+    pub async fn audit(&self, event: SystemEvent) -> Result<()> {
+        if let Some(pool) = &self.db_pool {
+            record_system_event(pool, &event).await?;
+        }
+        let mut audit_log = self.audit_log.write().unwrap();
+        audit_log.push_back(event);
+        if audit_log.len() > AUDIT_LOG_CAPACITY {
+            audit_log.pop_front();
+        }
+        Ok(())
+    }
+
+    /// Recent audit events, oldest first
+    pub fn audit_events(&self) -> Vec<SystemEvent> {
+        self.audit_log.read().unwrap().iter().cloned().collect()
+    }
+
+    /// Store a user's configuration overrides
+    pub fn set_configuration(&self, user_id: &str, configuration: UserConfiguration) {
+        self.user_configurations
+            .write()
+            .unwrap()
+            .insert(user_id.to_string(), configuration);
+    }
+
+    /// Apply validated risk settings to a user's configuration and protocol
+    ///
+    /// Both are updated under the lock of the protocol the user's trades are
+    /// checked against, so no trade sees a mix of old and new limits. A
+    /// change within the cooldown of the previous one is refused.
+    pub async fn update_risk_settings(
+        &self,
+        auth_context: &AuthContext,
+        settings: RiskSettings,
+    ) -> Result<UserConfiguration> {
+        let protocol = self.protocol_for(auth_context).await;
+        let mut protocol = protocol.lock().await;
+        self.check_settings_cooldown(&auth_context.user_id)?;
+        self.settings_changed_at
+            .write()
+            .unwrap()
+            .insert(auth_context.user_id.clone(), Instant::now());
+        let configuration = {
+            let mut configurations = self.user_configurations.write().unwrap();
+            let current = configurations
+                .get(&auth_context.user_id)
+                .cloned()
+                .unwrap_or_else(|| UserConfiguration::for_profile(auth_context.risk_profile));
+            let updated = UserConfiguration {
+                risk_profile: settings.risk_profile,
+                sizing_method: settings.sizing_method,
+                ..current
+            }
+            .with_limits(settings.protocol_limits);
+            configurations.insert(auth_context.user_id.clone(), updated.clone());
+            updated
+        };
+
+        protocol.update_limits(configuration.protocol_limits.clone());
+        Ok(configuration)
+    }
+
+    /// Refuse a settings change within the cooldown of the previous one
+    pub fn check_settings_cooldown(&self, user_id: &str) -> Result<()> {
+        let changed_at = self.settings_changed_at.read().unwrap();
+        match changed_at.get(user_id).map(Instant::elapsed) {
+            Some(elapsed) if elapsed < self.risk_settings_cooldown => Err(ImperiumError::SettingsCooldown {
+                retry_after_secs: (self.risk_settings_cooldown - elapsed).as_secs().max(1),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// A user's configuration with the limits their trades are checked against
+    ///
+    /// The stored configuration and the protocol agree once the protocol
+    /// exists; reading the limits from the protocol reports what is actually
+    /// enforced either way.
+    pub async fn enforced_configuration(&self, auth_context: &AuthContext) -> UserConfiguration {
+        let limits = self.protocol_for(auth_context).await.lock().await.limits().clone();
+        self.configuration_for(auth_context).with_limits(limits)
+    }
+}
+
+/// A trade request's place in its user's cycle queue, released on drop
+///
+/// Dropping rather than decrementing explicitly keeps the count right when a
+/// timeout cancels the request while it waits.
+struct PendingCycle {
+    pending: Arc<RwLock<HashMap<String, usize>>>,
+    user_id: String,
+}
+
+impl Drop for PendingCycle {
+    fn drop(&mut self) {
+        let mut pending = self.pending.write().unwrap();
+        if let Some(queued) = pending.get_mut(&self.user_id) {
+            *queued -= 1;
+            if *queued == 0 {
+                pending.remove(&self.user_id);
+            }
+        }
+    }
+}
+
+impl FromRef<AppState> for Arc<ApiState> {
+    fn from_ref(state: &AppState) -> Self {
+        state.api_state.clone()
+    }
+}
+
+/// GET /api/v1/config/snapshot - Download the user's active configuration
+async fn config_snapshot_handler(
+    auth_context: AuthContext,
+    State(api_state): State<Arc<ApiState>>,
+) -> Json<ApiResponse<ConfigSnapshot>> {
+    let snapshot = ConfigSnapshot {
+        user_id: auth_context.user_id.clone(),
+        generated_at: chrono::Utc::now(),
+        configuration: api_state.configuration_for(&auth_context),
+    };
+
+    Json(ApiResponse::success(snapshot))
+}
+
+/// GET /api/v1/settings/risk - The user's editable risk settings
+async fn risk_settings_handler(
+    auth_context: AuthContext,
+    State(api_state): State<Arc<ApiState>>,
+) -> Json<ApiResponse<RiskSettings>> {
+    let configuration = api_state.configuration_for(&auth_context);
+    Json(ApiResponse::success(RiskSettings::from(&configuration)))
+}
+
+/// PUT /api/v1/settings/risk - Replace the user's risk settings
+///
+/// The settings are validated as a whole and applied at once; inconsistent
+/// limits reject the update with an error for each offending field.
+async fn update_risk_settings_handler(
+    auth_context: AuthContext,
+    State(api_state): State<Arc<ApiState>>,
+    Json(settings): Json<RiskSettings>,
+) -> Result<Json<ApiResponse<RiskSettings>>> {
+    settings
+        .protocol_limits
+        .validate()
+        .map_err(|errors| ImperiumError::ValidationFailed {
+            errors: errors.into_iter().map(FieldError::from).collect(),
+        })?;
+
+    let configuration = api_state.update_risk_settings(&auth_context, settings).await?;
+    Ok(Json(ApiResponse::success(RiskSettings::from(&configuration))))
+}
+
+/// GET /api/v1/portfolio - Portfolio figures in the user's base currency
+async fn portfolio_handler(
+    auth_context: AuthContext,
+    State(api_state): State<Arc<ApiState>>,
+) -> Result<Json<ApiResponse<PortfolioResponse>>> {
+    let snapshot = api_state
+        .portfolio(&auth_context.user_id)
+        .ok_or_else(|| ImperiumError::NotFound {
+            resource: "portfolio".to_string(),
+        })?;
+    let base_currency = api_state.configuration_for(&auth_context).base_currency;
+
+    Ok(Json(ApiResponse::success(PortfolioResponse::render(
+        &snapshot,
+        &base_currency,
+        api_state.fx_rates(),
+    ))))
+}
+
+/// GET /api/v1/portfolio/heat - Risk budget consumed by each open position
+async fn portfolio_heat_handler(
+    auth_context: AuthContext,
+    State(api_state): State<Arc<ApiState>>,
+) -> Result<Json<ApiResponse<PortfolioHeat>>> {
+    let snapshot = api_state
+        .portfolio(&auth_context.user_id)
+        .ok_or_else(|| ImperiumError::NotFound {
+            resource: "portfolio".to_string(),
+        })?;
+
+    Ok(Json(ApiResponse::success(PortfolioHeat::render(
+        &snapshot,
+        &api_state.open_positions(&auth_context.user_id),
+        &api_state.configuration_for(&auth_context),
+        api_state.fx_rates(),
+    ))))
+}
+
+/// Query parameters for GET /api/v1/reports/daily
+#[derive(Debug, Deserialize)]
+pub struct DailyReportParams {
+    pub date: NaiveDate,
+}
+
+/// GET /api/v1/reports/daily?date=YYYY-MM-DD - Fetch a past daily summary
+async fn daily_report_handler(
+    auth_context: AuthContext,
+    State(api_state): State<Arc<ApiState>>,
+    Query(params): Query<DailyReportParams>,
+) -> Result<Json<ApiResponse<DailySummary>>> {
+    let summary = api_state
+        .reports()
+        .summary(&auth_context.user_id, params.date)
+        .ok_or_else(|| ImperiumError::NotFound {
+            resource: format!("daily summary for {}", params.date),
+        })?;
+
+    Ok(Json(ApiResponse::success(summary)))
+}
+
+/// GET /api/v1/market/symbols/{symbol}/tradable - Check a symbol before order entry
+///
+/// A symbol is tradable when the exchange lists it and none of the user's
+/// symbol restrictions apply; every reason it is not is reported.
+async fn symbol_tradable_handler(
+    auth_context: AuthContext,
+    State(api_state): State<Arc<ApiState>>,
+    Path(symbol): Path<String>,
+) -> Result<Json<ApiResponse<SymbolTradability>>> {
+    let exchange = api_state.exchange.clone().ok_or_else(|| ImperiumError::InternalError {
+        message: "Exchange adapter not configured".to_string(),
+    })?;
+
+    let listed = exchange.is_symbol_supported(&symbol).await.map_err(|error| {
+        ImperiumError::InternalError {
+            message: format!("Symbol lookup failed: {}", error),
+        }
+    })?;
+
+    let mut reasons = Vec::new();
+    if !listed {
+        reasons.push(NotTradableReason::Delisted);
+    }
+    reasons.extend(
+        api_state
+            .configuration_for(&auth_context)
+            .symbol_restrictions
+            .violations_at(&symbol, chrono::Utc::now())
+            .into_iter()
+            .map(NotTradableReason::from),
+    );
+
+    Ok(Json(ApiResponse::success(SymbolTradability {
+        tradable: reasons.is_empty(),
+        symbol,
+        reasons,
+    })))
+}
+
+/// POST /api/v1/trades/validate-stop - Check a stop against the exchange's trigger rules
+///
+/// Some exchanges reject stops too close to the current price. A stop inside
+/// the minimum distance is reported as invalid with the closest valid stop.
+async fn validate_stop_handler(
+    _auth_context: AuthContext,
+    State(api_state): State<Arc<ApiState>>,
+    Json(request): Json<StopValidationRequest>,
+) -> Result<Json<ApiResponse<StopValidation>>> {
+    if request.stop_loss <= Decimal::ZERO {
+        return Err(ImperiumError::InvalidRequest {
+            field: "stop_loss".to_string(),
+            reason: "must be positive".to_string(),
+        });
+    }
+    let exchange = api_state.exchange.clone().ok_or_else(|| ImperiumError::InternalError {
+        message: "Exchange adapter not configured".to_string(),
+    })?;
+
+    let reference_price = match request.entry_price {
+        Some(price) => price,
+        None => {
+            exchange
+                .get_market_data(&request.symbol)
+                .await
+                .map_err(|error| ImperiumError::InternalError {
+                    message: format!("Market data lookup failed: {}", error),
+                })?
+                .last_price
+        }
+    };
+    let min_stop_distance = exchange
+        .min_stop_distance(&request.symbol)
+        .await
+        .map_err(|error| ImperiumError::InternalError {
+            message: format!("Stop-trigger rule lookup failed: {}", error),
+        })?;
+
+    let side = match request.direction {
+        TradeDirection::Long => OrderSide::Buy,
+        TradeDirection::Short => OrderSide::Sell,
+    };
+    let suggested_stop = min_stop_distance
+        .filter(|distance| stop_too_close(reference_price, request.stop_loss, side, *distance))
+        .map(|distance| min_valid_stop(reference_price, side, distance));
+
+    Ok(Json(ApiResponse::success(StopValidation {
+        symbol: request.symbol,
+        valid: suggested_stop.is_none(),
+        stop_loss: request.stop_loss,
+        reference_price,
+        min_stop_distance,
+        suggested_stop,
+    })))
+}
+
+/// POST /api/v1/calculator/explain - Break down the Van Tharp position size
+///
+/// Returns each step of the computation as exact decimals, then the user's
+/// protocol limits the sized trade was checked against. Nothing is placed.
+async fn explain_sizing_handler(
+    auth_context: AuthContext,
+    State(api_state): State<Arc<ApiState>>,
+    Json(request): Json<SizingExplanationRequest>,
+) -> Result<Json<ApiResponse<SizingExplanation>>> {
+    let invalid = |field: &str, reason: &str| ImperiumError::InvalidRequest {
+        field: field.to_string(),
+        reason: reason.to_string(),
+    };
+    if request.account_equity <= Decimal::ZERO {
+        return Err(invalid("account_equity", "must be positive"));
+    }
+    if request.risk_percentage <= Decimal::ZERO || request.risk_percentage > Decimal::ONE {
+        return Err(invalid("risk_percentage", "must be a fraction in (0, 1]"));
+    }
+    if request.entry_price <= Decimal::ZERO {
+        return Err(invalid("entry_price", "must be positive"));
+    }
+    let stop_on_loss_side = match request.direction {
+        TradeDirection::Long => request.stop_loss < request.entry_price,
+        TradeDirection::Short => request.stop_loss > request.entry_price,
+    };
+    if request.stop_loss <= Decimal::ZERO || !stop_on_loss_side {
+        return Err(invalid("stop_loss", "must be positive and on the losing side of entry_price"));
+    }
+
+    let limits = api_state.configuration_for(&auth_context).protocol_limits;
+    let key = idempotency::fingerprint(&(&request, &limits))?;
+    let explanation = api_state
+        .sizing_cache
+        .get_or_compute(key, || SizingExplanation::explain(&request, &limits, &api_state.commissions));
+    Ok(Json(ApiResponse::success(explanation)))
+}
+
+/// GET /api/v1/admin/stats/calculator - Position sizing counters
+///
+/// Calculation count, cache hits and misses, hit rate and mean latency since
+/// startup, for sizing the service and tuning the cache TTL.
+async fn calculator_stats_handler(
+    auth_context: AuthContext,
+    State(api_state): State<Arc<ApiState>>,
+) -> Result<Json<ApiResponse<CalculatorStats>>> {
+    if !auth_context.permissions.iter().any(|permission| permission == ADMIN_PERMISSION) {
+        return Err(ImperiumError::AuthorizationFailed {
+            required_role: ADMIN_PERMISSION.to_string(),
+        });
+    }
+
+    Ok(Json(ApiResponse::success(api_state.calculator_stats().await)))
+}
+
+/// GET /metrics - Prometheus exposition of the service counters
+async fn metrics_handler(State(api_state): State<Arc<ApiState>>) -> Response {
+    let mut metrics = api_state.calculator_stats().await.to_prometheus();
+    if let Some(connections) = &api_state.connections {
+        metrics.push_str(&prometheus_metric(
+            "testudo_ws_replay_memory_bytes",
+            "gauge",
+            "Encoded size of the messages held in WebSocket replay buffers",
+            connections.replay_memory_bytes() as f64,
+        ));
+    }
+    if let Some(controller) = &api_state.trading_controller {
+        metrics.push_str(&rule_outcome_metrics(&controller.rule_outcome_counts()));
+    }
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        metrics,
+    )
+        .into_response()
+}
+
+/// Pass, rejection and failure counts of each risk rule
+///
+/// A rule that keeps failing to run points at a bug or bad configuration,
+/// while one that keeps rejecting is doing its job; the outcome label keeps
+/// the two apart.
+fn rule_outcome_metrics(counts: &HashMap<String, RuleOutcomeCounts>) -> String {
+    let mut rules: Vec<_> = counts.iter().collect();
+    rules.sort_by(|a, b| a.0.cmp(b.0));
+    let samples: Vec<(String, f64)> = rules
+        .into_iter()
+        .flat_map(|(rule, counts)| {
+            [("passed", counts.passed), ("rejected", counts.rejected), ("failed", counts.failed)]
+                .map(|(outcome, value)| (format!("rule=\"{rule}\",outcome=\"{outcome}\""), value as f64))
+        })
+        .collect();
+    prometheus_labelled_metric("testudo_risk_rule_outcomes_total", "counter", "Risk rule runs by outcome", &samples)
+}
+
+/// POST /api/v1/notifications/test - Send a sample notification to the user
+///
+/// Goes through the same notifier daily summaries are delivered with. A
+/// failed delivery is reported in the body with its error, not as an error
+/// response.
+async fn test_notification_handler(
+    auth_context: AuthContext,
+    State(api_state): State<Arc<ApiState>>,
+) -> Result<Json<ApiResponse<NotificationTestResult>>> {
+    let configuration = api_state.configuration_for(&auth_context);
+    let notifier = api_state
+        .notifier_for(&configuration)
+        .ok_or_else(|| ImperiumError::InvalidRequest {
+            field: "notification_webhook".to_string(),
+            reason: "no notifier is configured".to_string(),
+        })?;
+
+    let message = WebSocketMessage::RiskAlert {
+        severity: AlertSeverity::Warning,
+        rule: "NotificationTest".to_string(),
+        message: "Test notification from Testudo".to_string(),
+        timestamp: chrono::Utc::now(),
+    };
+    let started = Instant::now();
+    let result = notifier.notify(&auth_context.user_id, &message).await;
+
+    Ok(Json(ApiResponse::success(NotificationTestResult::new(result, started.elapsed()))))
+}
+
+/// GET /api/v1/exchanges - Integrated exchanges with their capabilities and health
+///
+/// Statuses come from the failover health report. Only exchanges not checked
+/// within the health check interval are probed before it is read, so
+/// repeated requests do not hit every exchange.
+async fn exchanges_handler(
+    _auth_context: AuthContext,
+    State(api_state): State<Arc<ApiState>>,
+) -> Result<Json<ApiResponse<Vec<ExchangeStatus>>>> {
+    let manager = api_state.exchange_manager.clone().ok_or_else(|| {
+        ImperiumError::InternalError {
+            message: "Exchange manager not configured".to_string(),
+        }
+    })?;
+
+    manager.check_stale_health().await;
+    let report = manager.health_report().await;
+    let exchanges = manager
+        .adapters()
+        .await
+        .into_iter()
+        .map(|(name, adapter)| {
+            let status = report.iter().find(|status| status.name == name);
+            ExchangeStatus::new(name, adapter.capabilities(), status)
+        })
+        .collect();
+
+    Ok(Json(ApiResponse::success(exchanges)))
+}
+
+/// GET /api/v1/exchanges/{name}/credentials/check - Whether stored credentials authenticate
+///
+/// Fetches balances, the cheapest authenticated call, and reports only the
+/// outcome. An unreachable exchange is reported as unverified rather than
+/// invalid, since the credentials were never tested.
+///
+/// The keys checked are the service's own, which every user's trades are
+/// placed with, so the check is restricted to admins.
+async fn credentials_check_handler(
+    auth_context: AuthContext,
+    State(api_state): State<Arc<ApiState>>,
+    Path(name): Path<String>,
+) -> Result<Json<ApiResponse<CredentialsCheck>>> {
+    if !auth_context.permissions.iter().any(|permission| permission == ADMIN_PERMISSION) {
+        return Err(ImperiumError::AuthorizationFailed {
+            required_role: ADMIN_PERMISSION.to_string(),
+        });
+    }
+    let manager = api_state.exchange_manager.clone().ok_or_else(|| {
+        ImperiumError::InternalError {
+            message: "Exchange manager not configured".to_string(),
+        }
+    })?;
+    let adapter = manager.get_adapter(&name).await.ok_or_else(|| ImperiumError::NotFound {
+        resource: format!("exchange {}", name),
+    })?;
+
+    let result = adapter.get_all_balances().await.map(|_| ());
+    Ok(Json(ApiResponse::success(CredentialsCheck::new(name, result))))
+}
+
+/// POST /api/v1/trades/execute - Run an OODA cycle for a trade intent
+///
+/// The cycle runs on its own task while holding a concurrency slot. A request
+/// timeout before the cycle commits to its Act phase cancels it and releases
+/// the slot; after that the cycle is left to finish, so the trade it placed
+/// is still settled against the protocol, booked and recorded under its
+/// idempotency key.
+///
+/// A trade the risk protocol rejects is answered with 422 and the violations
+/// that caused it.
+///
+/// With an `Idempotency-Key` header, a retry with the same body replays the
+/// first successful result, and the same key with a different body is a 409.
+async fn execute_trade_handler(
+    auth_context: AuthContext,
+    State(api_state): State<Arc<ApiState>>,
+    headers: HeaderMap,
+    Json(request): Json<ExecuteTradeRequest>,
+) -> Result<Json<ApiResponse<ExecuteTradeResponse>>> {
+    let guard = match headers.get(IDEMPOTENCY_KEY_HEADER) {
+        Some(key) => {
+            let key = key.to_str().map_err(|_| ImperiumError::InvalidRequest {
+                field: IDEMPOTENCY_KEY_HEADER.to_string(),
+                reason: "must be visible ASCII".to_string(),
+            })?;
+            let fingerprint = idempotency::fingerprint(&request)?;
+            match api_state.idempotency.begin(&auth_context.user_id, key, fingerprint)? {
+                Claim::Replay(response) => return Ok(Json(ApiResponse::success(response))),
+                Claim::Started(guard) => Some(guard),
+            }
+        }
+        None => None,
+    };
+
+    let commitment = ActCommitment::new();
+    let cycle = tokio::spawn({
+        let commitment = commitment.clone();
+        async move {
+            let response = execute_trade(&auth_context, &api_state, request, &commitment).await?;
+            if let Some(guard) = guard {
+                guard.complete(response.clone());
+            }
+            Ok::<_, ImperiumError>(response)
+        }
+    });
+    let _abandon = AbandonOnDrop { commitment, cycle: cycle.abort_handle() };
+
+    let response = cycle.await.map_err(|e| ImperiumError::InternalError {
+        message: format!("Trade execution task failed: {}", e),
+    })??;
+    Ok(Json(ApiResponse::success(response)))
+}
+
+/// Cancels a trade's cycle when its request is dropped, unless it has
+/// committed to acting
+struct AbandonOnDrop {
+    commitment: ActCommitment,
+    cycle: AbortHandle,
+}
+
+impl Drop for AbandonOnDrop {
+    fn drop(&mut self) {
+        if self.commitment.abandon() {
+            self.cycle.abort();
+        }
+    }
+}
+
+async fn execute_trade(
+    auth_context: &AuthContext,
+    api_state: &ApiState,
+    request: ExecuteTradeRequest,
+    commitment: &ActCommitment,
+) -> Result<ExecuteTradeResponse> {
+    let controller = api_state.trading_controller.clone().ok_or_else(|| {
+        ImperiumError::InternalError {
+            message: "Trading controller not configured".to_string(),
+        }
+    })?;
+
+    let queued = api_state.enqueue_cycle(&auth_context.user_id)?;
+    let _slot = api_state.cycle_slots.clone().acquire_owned().await.map_err(|_| {
+        ImperiumError::InternalError {
+            message: "OODA cycle slots closed".to_string(),
+        }
+    })?;
+    drop(queued);
+
+    let mut intent = request.into_intent();
+    intent.preferred_exchange = api_state
+        .configuration_for(auth_context)
+        .exchange_routing
+        .exchange_for(&intent.symbol);
+
+    let protocol = api_state.protocol_for(auth_context).await;
+    let plan = controller
+        .execute_cycle_with_commitment(intent, &protocol, commitment)
+        .await
+        .map_err(|source| ImperiumError::TradingError { source })?;
+    api_state.record_assessment(&auth_context.user_id, RecentAssessment::from(&plan));
+
+    if !plan.approved {
+        return Err(ImperiumError::RiskRejected {
+            reason: plan.risk_assessment,
+            violations: plan.violations,
+            failed_rules: plan.failed_rules,
+            rejecting_rules: plan.rejecting_rules,
+        });
+    }
+
+    // The trade has been placed; a failure to publish its entry must not fail the request
+    if let Err(e) = lifecycle::open_position(api_state, &auth_context.user_id, &plan).await {
+        warn!("Failed to publish the entry of position {}: {}", plan.position_id, e);
+    }
+    if let Err(e) = record_execution(api_state, &auth_context.user_id, &plan, &protocol).await {
+        warn!("Failed to record the execution of position {}: {}", plan.position_id, e);
+    }
+    Ok(ExecuteTradeResponse::from(&plan))
+}
+
+/// Persist an executed plan with the user's protocol state, when a database is configured
+async fn record_execution(
+    api_state: &ApiState,
+    user_id: &str,
+    plan: &formatio::ExecutionPlan,
+    protocol: &SharedProtocol,
+) -> Result<()> {
+    let (Some(pool), Some(execution)) = (&api_state.db_pool, &plan.execution) else {
+        return Ok(());
+    };
+    let user_id = Uuid::parse_str(user_id).map_err(|_| ImperiumError::InternalError {
+        message: format!("User id {} is not a UUID", user_id),
+    })?;
+    let setup = &plan.setup;
+    let record = TradeExecutionRecord {
+        position_id: plan.position_id,
+        user_id,
+        symbol: setup.symbol.clone(),
+        exchange: execution.venue.clone(),
+        side: match setup.side {
+            OrderSide::Buy => TradeSide::Long,
+            OrderSide::Sell => TradeSide::Short,
+        },
+        entry_price: setup.entry_price,
+        stop_loss: setup.stop_loss,
+        take_profit: setup.take_profit,
+        account_equity: plan.account_equity,
+        risk_percentage: setup.risk_amount() / plan.account_equity,
+        calculated_position_size: setup.position_size,
+        executed_quantity: execution.filled_quantity,
+        execution_price: execution.average_entry_price,
+        exchange_order_id: Some(execution.order_id.clone()),
+        executed_at: execution.executed_at,
+    };
+    let status = protocol.lock().await.get_status();
+    record_trade_execution(pool, &record, &status).await?;
+    Ok(())
+}
+
+/// POST /api/v1/positions/:position_id/close - Exit part or all of an open position
+async fn close_position_handler(
+    auth_context: AuthContext,
+    State(api_state): State<Arc<ApiState>>,
+    Path(position_id): Path<Uuid>,
+    Json(request): Json<ClosePositionRequest>,
+) -> Result<Json<ApiResponse<ClosePositionResponse>>> {
+    let response = lifecycle::close_position(
+        &api_state,
+        &auth_context.user_id,
+        position_id,
+        request.quantity,
+        request.exit_price,
+    )
+    .await?;
+    Ok(Json(ApiResponse::success(response)))
+}
+
+/// PUT /api/v1/positions/:position_id/bracket - Move an open position's stop and/or take-profit
+async fn modify_bracket_handler(
+    auth_context: AuthContext,
+    State(api_state): State<Arc<ApiState>>,
+    Path(position_id): Path<Uuid>,
+    Json(request): Json<ModifyBracketRequest>,
+) -> Result<Json<ApiResponse<ModifyBracketResponse>>> {
+    let executor = api_state.bracket_executor.clone().ok_or_else(|| ImperiumError::InternalError {
+        message: "Bracket executor not configured".to_string(),
+    })?;
+    let response = lifecycle::modify_bracket(
+        &api_state,
+        &executor,
+        &auth_context.user_id,
+        position_id,
+        request.stop_loss,
+        request.take_profit,
+    )
+    .await?;
+    Ok(Json(ApiResponse::success(response)))
+}
+
+/// POST /api/v1/positions/import - Register positions opened elsewhere
+///
+/// Each position's risk runs from its entry to its current stop. The whole
+/// batch is validated before anything is registered, and positions are only
+/// added to the protocol the trade path enforces once they have been
+/// persisted. Positions sent with a `position_id` that is already tracked are
+/// skipped, so a failed import can be retried with the same ids.
+async fn import_positions_handler(
+    auth_context: AuthContext,
+    State(api_state): State<Arc<ApiState>>,
+    Json(request): Json<ImportPositionsRequest>,
+) -> Result<Json<ApiResponse<ImportPositionsResponse>>> {
+    let invalid = |field: &str, reason: &str| ImperiumError::InvalidRequest {
+        field: field.to_string(),
+        reason: reason.to_string(),
+    };
+    if request.positions.is_empty() {
+        return Err(invalid("positions", "must not be empty"));
+    }
+    if request.account_equity <= Decimal::ZERO {
+        return Err(invalid("account_equity", "must be positive"));
+    }
+    for position in &request.positions {
+        if position.quantity <= Decimal::ZERO {
+            return Err(invalid("quantity", &format!("must be positive for {}", position.symbol)));
+        }
+        if position.entry_price <= Decimal::ZERO || position.stop_loss <= Decimal::ZERO {
+            return Err(invalid("entry_price", &format!("prices must be positive for {}", position.symbol)));
+        }
+    }
+
+    let shared = api_state.protocol_for(&auth_context).await;
+    // Work on a copy so the lock is not held while the import is persisted
+    let mut protocol = shared.lock().await.clone();
+    // Warn against the limits trades are checked against, buffers and caps included
+    let limits = protocol.limits().clone();
+    let max_trade_risk = limits.enforced_max_individual_trade_risk();
+    let max_trade_loss = limits.enforced_max_trade_loss_amount();
+    let max_portfolio_risk = limits.effective_max_portfolio_risk(request.account_equity);
+
+    let now = chrono::Utc::now();
+    let mut summaries = Vec::new();
+    let mut records = Vec::new();
+    let mut warnings = Vec::new();
+    for position in &request.positions {
+        let side = match position.direction {
+            TradeDirection::Long => OrderSide::Buy,
+            TradeDirection::Short => OrderSide::Sell,
+        };
+        let risk_amount = TradeSetup {
+            symbol: position.symbol.clone(),
+            entry_price: position.entry_price,
+            stop_loss: position.stop_loss,
+            take_profit: None,
+            position_size: position.quantity,
+            side,
+        }
+        .risk_amount();
+        let risk_percentage = risk_amount / request.account_equity;
+        if risk_percentage > max_trade_risk {
+            warnings.push(format!(
+                "{} risks {:.2}% of equity, above the {:.2}% per-trade limit",
+                position.symbol,
+                risk_percentage * Decimal::from(100),
+                max_trade_risk * Decimal::from(100)
+            ));
+        }
+        if let Some(max_loss) = max_trade_loss.filter(|max_loss| risk_amount > *max_loss) {
+            warnings.push(format!(
+                "{} risks {:.2}, above the {:.2} per-trade loss cap",
+                position.symbol, risk_amount, max_loss
+            ));
+        }
+
+        let position_id = position.position_id.unwrap_or_else(Uuid::new_v4);
+        if protocol.tracked_position(position_id).is_none() {
+            protocol.track_external_position(position_id, &position.symbol, risk_percentage);
+        }
+        records.push(TradeExecutionRecord {
+            position_id,
+            user_id: Uuid::nil(),
+            symbol: position.symbol.clone(),
+            exchange: position.exchange.clone().unwrap_or_else(|| "external".to_string()),
+            side: match position.direction {
+                TradeDirection::Long => TradeSide::Long,
+                TradeDirection::Short => TradeSide::Short,
+            },
+            entry_price: position.entry_price,
+            stop_loss: position.stop_loss,
+            take_profit: None,
+            account_equity: request.account_equity,
+            risk_percentage,
+            calculated_position_size: position.quantity,
+            executed_quantity: position.quantity,
+            execution_price: position.entry_price,
+            exchange_order_id: None,
+            executed_at: now,
+        });
+        summaries.push(ImportedPositionSummary {
+            position_id,
+            symbol: position.symbol.clone(),
+            risk_amount,
+            risk_percentage,
+        });
+    }
+
+    let status = protocol.get_status();
+    if status.total_portfolio_risk > max_portfolio_risk {
+        warnings.push(format!(
+            "Portfolio risk is {:.2}%, above the {:.2}% limit; new trades will be rejected",
+            status.total_portfolio_risk * Decimal::from(100),
+            max_portfolio_risk * Decimal::from(100)
+        ));
+    }
+
+    if let Some(pool) = &api_state.db_pool {
+        let user_id = Uuid::parse_str(&auth_context.user_id).map_err(|_| ImperiumError::InternalError {
+            message: format!("User id {} is not a UUID", auth_context.user_id),
+        })?;
+        for record in &mut records {
+            record.user_id = user_id;
+        }
+        record_imported_positions(pool, user_id, &records, &status).await?;
+    }
+    {
+        let mut protocol = shared.lock().await;
+        for summary in &summaries {
+            if protocol.tracked_position(summary.position_id).is_none() {
+                protocol.track_external_position(summary.position_id, &summary.symbol, summary.risk_percentage);
+            }
+        }
+    }
+
+    let mut open_positions = api_state.open_positions(&auth_context.user_id);
+    let already_open: HashSet<String> = open_positions.iter().map(|position| position.id.clone()).collect();
+    let newly_imported = summaries
+        .iter()
+        .filter(|summary| !already_open.contains(&summary.position_id.to_string()));
+    open_positions.extend(newly_imported.map(|summary| OpenPosition {
+        id: summary.position_id.to_string(),
+        symbol: summary.symbol.clone(),
+        risk_amount: summary.risk_amount,
+        risk_percentage: summary.risk_percentage,
+        opened_at: SystemTime::now(),
+        unrealized_pnl: Decimal::ZERO,
+        max_adverse_excursion: Decimal::ZERO,
+    }));
+    api_state.set_open_positions(&auth_context.user_id, open_positions);
+
+    Ok(Json(ApiResponse::success(ImportPositionsResponse {
+        positions: summaries,
+        total_portfolio_risk: status.total_portfolio_risk,
+        max_portfolio_risk: limits.max_total_portfolio_risk,
+        open_positions: status.open_positions,
+        warnings,
+    })))
+}
+
+/// Permission required for admin maintenance endpoints
+pub const ADMIN_PERMISSION: &str = "admin";
+
+/// POST /api/v1/admin/trades/backfill-r - Compute missing R multiples
+///
+/// Safe to repeat: positions that already have an R multiple are skipped.
+async fn backfill_r_handler(
+    auth_context: AuthContext,
+    State(api_state): State<Arc<ApiState>>,
+) -> Result<Json<ApiResponse<BackfillResponse>>> {
+    if !auth_context.permissions.iter().any(|permission| permission == ADMIN_PERMISSION) {
+        return Err(ImperiumError::AuthorizationFailed {
+            required_role: ADMIN_PERMISSION.to_string(),
+        });
+    }
+    let pool = api_state.db_pool.as_ref().ok_or_else(|| ImperiumError::InternalError {
+        message: "Database not configured".to_string(),
+    })?;
+
+    let updated = backfill_r_multiples(pool, R_BACKFILL_BATCH_SIZE).await?;
+    Ok(Json(ApiResponse::success(BackfillResponse { updated })))
+}
+
+/// POST /api/v1/admin/impersonate/{user_id} - View a user's risk context
+///
+/// Read-only: returns the user's effective configuration, recent assessments
+/// and protocol status as that user would see them, but grants no session or
+/// trade capability. Every access is audited before anything is returned.
+async fn impersonate_handler(
+    auth_context: AuthContext,
+    State(api_state): State<Arc<ApiState>>,
+    Path(user_id): Path<String>,
+) -> Result<Json<ApiResponse<ImpersonationResponse>>> {
+    if !auth_context.permissions.iter().any(|permission| permission == ADMIN_PERMISSION) {
+        return Err(ImperiumError::AuthorizationFailed {
+            required_role: ADMIN_PERMISSION.to_string(),
+        });
+    }
+
+    api_state
+        .audit(SystemEvent {
+            event_type: "ADMIN_IMPERSONATION".to_string(),
+            severity: EventSeverity::Warn,
+            component: "imperium".to_string(),
+            message: format!("{} viewed the risk context of {}", auth_context.user_id, user_id),
+            metadata: Some(serde_json::json!({
+                "admin_user_id": auth_context.user_id,
+                "admin_session_id": auth_context.session_id,
+                "target_user_id": user_id,
+            })),
+            user_id: Uuid::parse_str(&user_id).ok(),
+        })
+        .await?;
+
+    let mut configuration = api_state
+        .user_configurations
+        .read()
+        .unwrap()
+        .get(&user_id)
+        .cloned()
+        .unwrap_or_else(|| UserConfiguration::for_profile(RiskProfile::Standard));
+    // Report the limits the user's trades are actually checked against
+    let mut protocol_status = None;
+    if let Some(protocol) = api_state.protocol(&user_id).await {
+        let protocol = protocol.lock().await;
+        configuration = configuration.with_limits(protocol.limits().clone());
+        protocol_status = Some(ProtocolStatusSummary::from(&protocol.get_status()));
+    }
+    let effective_rules = configuration.enabled_rules.clone();
+
+    Ok(Json(ApiResponse::success(ImpersonationResponse {
+        viewed_by: auth_context.user_id.clone(),
+        config: ConfigSnapshot {
+            user_id: user_id.clone(),
+            generated_at: chrono::Utc::now(),
+            configuration,
+        },
+        recent_assessments: api_state.recent_assessments(&user_id),
+        protocol_status,
+        effective_rules,
+    })))
+}
+
+/// Query parameters for the protocol configuration endpoints
+#[derive(Debug, Deserialize)]
+pub struct ProtocolConfigParams {
+    #[serde(default)]
+    pub format: ConfigFormat,
+}
+
+/// GET /api/v1/admin/protocol/config?format=toml|json - Export the active configuration
+///
+/// Returns the caller's risk profile, limits, sizing method and enabled rules
+/// as a bare document (TOML by default) suitable for committing and diffing.
+async fn export_protocol_config_handler(
+    auth_context: AuthContext,
+    State(api_state): State<Arc<ApiState>>,
+    Query(params): Query<ProtocolConfigParams>,
+) -> Result<Response> {
+    if !auth_context.permissions.iter().any(|permission| permission == ADMIN_PERMISSION) {
+        return Err(ImperiumError::AuthorizationFailed {
+            required_role: ADMIN_PERMISSION.to_string(),
+        });
+    }
+
+    let settings = RiskSettings::from(&api_state.enforced_configuration(&auth_context).await);
+    let document = settings.export(params.format)?;
+    Ok(([(header::CONTENT_TYPE, params.format.media_type())], document).into_response())
+}
+
+/// PUT /api/v1/admin/protocol/config?format=toml|json - Import a configuration
+///
+/// The document is parsed and its limits validated in full before anything
+/// is applied, so a rejected import leaves the active configuration as it
+/// was. Accepted imports are audited before they take effect.
+async fn import_protocol_config_handler(
+    auth_context: AuthContext,
+    State(api_state): State<Arc<ApiState>>,
+    Query(params): Query<ProtocolConfigParams>,
+    document: String,
+) -> Result<Json<ApiResponse<RiskSettings>>> {
+    if !auth_context.permissions.iter().any(|permission| permission == ADMIN_PERMISSION) {
+        return Err(ImperiumError::AuthorizationFailed {
+            required_role: ADMIN_PERMISSION.to_string(),
+        });
+    }
+
+    let settings = RiskSettings::load(params.format, &document)?;
+    api_state.check_settings_cooldown(&auth_context.user_id)?;
+    api_state
+        .audit(SystemEvent {
+            event_type: "PROTOCOL_CONFIG_IMPORTED".to_string(),
+            severity: EventSeverity::Warn,
+            component: "imperium".to_string(),
+            message: format!(
+                "{} imported a {} protocol configuration",
+                auth_context.user_id, params.format
+            ),
+            metadata: Some(serde_json::json!({
+                "admin_user_id": auth_context.user_id,
+                "admin_session_id": auth_context.session_id,
+                "format": params.format,
+                "risk_profile": settings.risk_profile,
+                "protocol_limits": settings.protocol_limits,
+            })),
+            user_id: Uuid::parse_str(&auth_context.user_id).ok(),
+        })
+        .await?;
+
+    let configuration = api_state.update_risk_settings(&auth_context, settings).await?;
+    Ok(Json(ApiResponse::success(RiskSettings::from(&configuration))))
+}
+
+fn timeout_response(timeout: Duration) -> Response {
+    warn!("Request exceeded {}ms timeout", timeout.as_millis());
+    ImperiumError::RequestTimeout {
+        timeout_ms: timeout.as_millis() as u64,
+    }
+    .into_response()
+}
+
+/// Apply a timeout to every route in `router`, answering 504 when it elapses
+fn with_timeout<S>(router: Router<S>, timeout: Duration) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    router.layer(
+        ServiceBuilder::new()
+            .layer(HandleErrorLayer::new(move |_: BoxError| async move {
+                timeout_response(timeout)
+            }))
+            .timeout(timeout),
+    )
+}
+
+/// API routes for any state that can provide `ApiState`
+pub fn routes<S>() -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+    Arc<ApiState>: FromRef<S>,
+{
+    routes_with_timeouts(RequestTimeouts::default())
+}
+
+/// API routes with explicit per-category timeouts
+pub fn routes_with_timeouts<S>(timeouts: RequestTimeouts) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+    Arc<ApiState>: FromRef<S>,
+{
+    let reads = Router::new()
+        .route("/config/snapshot", get(config_snapshot_handler))
+        .route("/settings/risk", get(risk_settings_handler).put(update_risk_settings_handler))
+        .route("/portfolio", get(portfolio_handler))
+        .route("/portfolio/heat", get(portfolio_heat_handler))
+        .route("/reports/daily", get(daily_report_handler))
+        .route("/market/symbols/:symbol/tradable", get(symbol_tradable_handler))
+        .route("/exchanges", get(exchanges_handler))
+        .route("/exchanges/:name/credentials/check", get(credentials_check_handler))
+        .route("/trades/validate-stop", post(validate_stop_handler))
+        .route("/calculator/explain", post(explain_sizing_handler))
+        .route("/notifications/test", post(test_notification_handler))
+        .route("/admin/impersonate/:user_id", post(impersonate_handler))
+        .route("/admin/stats/calculator", get(calculator_stats_handler))
+        .route(
+            "/admin/protocol/config",
+            get(export_protocol_config_handler).put(import_protocol_config_handler),
+        );
+    let trades = Router::new()
+        .route("/trades/execute", post(execute_trade_handler))
+        .route("/positions/import", post(import_positions_handler))
+        .route("/positions/:position_id/close", post(close_position_handler))
+        .route("/positions/:position_id/bracket", put(modify_bracket_handler))
+        .route("/admin/trades/backfill-r", post(backfill_r_handler));
+
+    with_timeout(reads, timeouts.read).merge(with_timeout(trades, timeouts.trade_execution))
+}
+
+/// Unauthenticated scrape endpoint, mounted at the root rather than under `/api/v1`
+pub fn metrics_routes<S>() -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+    Arc<ApiState>: FromRef<S>,
+{
+    Router::new().route("/metrics", get(metrics_handler))
+}
 
 pub fn create_router() -> Router<AppState> {
-    Router::new()
-}
\ No newline at end of file
+    routes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::{to_bytes, Body},
+        http::{Request, StatusCode},
+        Extension,
+    };
+    use crate::reports::ClosedTrade;
+    use crate::types::{ExchangeRouting, PortfolioSnapshot};
+    use chrono::{TimeZone, Utc};
+    use formatio::{OodaLoop, RiskDecider};
+    use prudentia::exchange::MockExchange;
+    use prudentia::{
+        ExchangeCapabilities, ExchangeFailoverConfig, MaxPositionUnitsRule, MaxTradeRiskRule,
+        RiskManagementProtocol, RiskProfile, SymbolRestrictionRule,
+    };
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+    use tower::ServiceExt;
+
+    fn auth_context(user_id: &str) -> AuthContext {
+        AuthContext {
+            user_id: user_id.to_string(),
+            session_id: "session-1".to_string(),
+            email: "trader@example.com".to_string(),
+            risk_profile: RiskProfile::Standard,
+            permissions: vec!["trade:execute".to_string()],
+        }
+    }
+
+    async fn get_snapshot(state: Arc<ApiState>, user_id: &str) -> serde_json::Value {
+        let app = routes::<Arc<ApiState>>()
+            .layer(Extension(auth_context(user_id)))
+            .with_state(state);
+
+        let response = app
+            .oneshot(Request::get("/config/snapshot").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_config_snapshot_reflects_overrides_and_flags() {
+        let state = Arc::new(ApiState::new());
+        let limits = ProtocolLimits {
+            max_open_positions: 2,
+            ..ProtocolLimits::default_limits()
+        };
+        state.set_configuration(
+            "trader-1",
+            UserConfiguration::for_profile(RiskProfile::Standard)
+                .with_limits(limits)
+                .with_feature_flag("paper_trading", true),
+        );
+
+        let body = get_snapshot(state, "trader-1").await;
+        let snapshot = &body["data"];
+
+        assert_eq!(snapshot["user_id"], "trader-1");
+        assert_eq!(snapshot["protocol_limits"]["max_open_positions"], 2);
+        assert_eq!(snapshot["feature_flags"]["paper_trading"], true);
+        assert_eq!(snapshot["sizing_method"], "van_tharp");
+        assert_eq!(snapshot["risk_profile"], "standard");
+
+        let open_positions_rule = snapshot["enabled_rules"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|rule| rule["name"] == "MaxOpenPositions")
+            .unwrap();
+        assert_eq!(open_positions_rule["parameters"]["max_open_positions"], "2");
+    }
+
+    #[tokio::test]
+    async fn test_config_snapshot_defaults_to_profile_limits() {
+        let body = get_snapshot(Arc::new(ApiState::new()), "trader-2").await;
+
+        let defaults = serde_json::to_value(ProtocolLimits::default_limits()).unwrap();
+        assert_eq!(body["data"]["protocol_limits"], defaults);
+    }
+
+    #[tokio::test]
+    async fn test_risk_settings_update_persists_or_reports_field_errors() {
+        let state = Arc::new(ApiState::new());
+        let app = routes::<Arc<ApiState>>()
+            .layer(Extension(auth_context("trader-1")))
+            .with_state(state.clone());
+
+        let put_settings = |settings: serde_json::Value| {
+            let request = Request::put("/settings/risk")
+                .header("content-type", "application/json")
+                .body(Body::from(settings.to_string()))
+                .unwrap();
+            let app = app.clone();
+            async move {
+                let response = app.oneshot(request).await.unwrap();
+                let status = response.status();
+                let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+                (status, serde_json::from_slice::<serde_json::Value>(&body).unwrap())
+            }
+        };
+
+        let response = app
+            .clone()
+            .oneshot(Request::get("/settings/risk").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let mut settings = serde_json::from_slice::<serde_json::Value>(&body).unwrap()["data"].take();
+        assert_eq!(settings["risk_profile"], "standard");
+
+        settings["protocol_limits"]["max_open_positions"] = 3.into();
+        let (status, body) = put_settings(settings.clone()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["protocol_limits"]["max_open_positions"], 3);
+        let stored = state.configuration_for(&auth_context("trader-1"));
+        assert_eq!(stored.protocol_limits.max_open_positions, 3);
+
+        // A single trade may not risk more than the whole portfolio
+        settings["protocol_limits"]["max_individual_trade_risk"] = "0.20".into();
+        let (status, body) = put_settings(settings).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["success"], false);
+        let field_errors = body["field_errors"].as_array().unwrap();
+        assert_eq!(field_errors.len(), 1);
+        assert_eq!(field_errors[0]["field"], "max_individual_trade_risk");
+        let stored = state.configuration_for(&auth_context("trader-1"));
+        assert_eq!(stored.protocol_limits.max_individual_trade_risk, dec!(0.06));
+    }
+
+    #[tokio::test]
+    async fn test_trades_after_a_settings_change_use_the_new_limits() {
+        let exchange = Arc::new(MockExchange::new());
+        let protocol = RiskManagementProtocol::new().add_rule(MaxTradeRiskRule::new());
+        let controller = Arc::new(OodaController::new(Arc::new(OodaLoop::with_all_components(
+            exchange.clone(),
+            Arc::new(RiskDecider::new(Arc::new(protocol))),
+        ))));
+        let state = Arc::new(ApiState::new().with_trading_controller(controller, 1));
+        let app = routes::<Arc<ApiState>>()
+            .layer(Extension(auth_context("trader-1")))
+            .with_state(state.clone());
+        let send = |request: Request<Body>| {
+            let app = app.clone();
+            async move { app.oneshot(request).await.unwrap().status() }
+        };
+        let put_settings = |max_trade_risk: &str| {
+            let mut settings = serde_json::to_value(RiskSettings::from(
+                &state.configuration_for(&auth_context("trader-1")),
+            ))
+            .unwrap();
+            settings["protocol_limits"]["max_individual_trade_risk"] = max_trade_risk.into();
+            Request::put("/settings/risk")
+                .header("content-type", "application/json")
+                .body(Body::from(settings.to_string()))
+                .unwrap()
+        };
+        let trade = || {
+            Request::post("/trades/execute")
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    serde_json::json!({
+                        "symbol": "BTC/USDT",
+                        "direction": "Long",
+                        "account_equity": "10000",
+                        "risk_percentage": "0.02",
+                    })
+                    .to_string(),
+                ))
+                .unwrap()
+        };
+
+        // Accepted under the default 6% per-trade limit
+        assert_eq!(send(trade()).await, StatusCode::OK);
+        let placed = exchange.get_placed_orders().await.len();
+
+        assert_eq!(send(put_settings("0.01")).await, StatusCode::OK);
+        assert_eq!(send(trade()).await, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(exchange.get_placed_orders().await.len(), placed);
+
+        // Loosening the limit straight away is refused
+        assert_eq!(send(put_settings("0.03")).await, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(send(trade()).await, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(
+            state.configuration_for(&auth_context("trader-1")).protocol_limits.max_individual_trade_risk,
+            dec!(0.01)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_protocol_config_exports_and_imports_validated_toml() {
+        let state = Arc::new(ApiState::new());
+        let admin = AuthContext {
+            permissions: vec![ADMIN_PERMISSION.to_string()],
+            ..auth_context("ops-1")
+        };
+        let send = |user: AuthContext, request: Request<Body>| {
+            let app = routes::<Arc<ApiState>>()
+                .layer(Extension(user))
+                .with_state(state.clone());
+            async move {
+                let response = app.oneshot(request).await.unwrap();
+                let status = response.status();
+                let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+                (status, String::from_utf8(body.to_vec()).unwrap())
+            }
+        };
+        let import = |document: String| {
+            Request::put("/admin/protocol/config?format=toml")
+                .header("content-type", "application/toml")
+                .body(Body::from(document))
+                .unwrap()
+        };
+
+        let export = || Request::get("/admin/protocol/config").body(Body::empty()).unwrap();
+        let (status, _) = send(auth_context("trader-1"), export()).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        let (status, document) = send(admin.clone(), export()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(document.contains("risk_profile = \"standard\""));
+
+        let edited = document.replace("max_open_positions = 5", "max_open_positions = 2");
+        let (status, _) = send(admin.clone(), import(edited)).await;
+        assert_eq!(status, StatusCode::OK);
+        let stored = state.configuration_for(&admin);
+        assert_eq!(stored.protocol_limits.max_open_positions, 2);
+        let enforced = state.protocol_for(&admin).await.lock().await.limits().clone();
+        assert_eq!(enforced.max_open_positions, 2);
+        assert_eq!(state.audit_events()[0].event_type, "PROTOCOL_CONFIG_IMPORTED");
+        let (_, exported) = send(admin.clone(), export()).await;
+        assert!(exported.contains("max_open_positions = 2"));
+
+        // Invalid limits are reported and nothing is applied or audited
+        let invalid = document.replace(
+            "max_total_portfolio_risk = \"0.10\"",
+            "max_total_portfolio_risk = \"0.01\"",
+        );
+        let (status, body) = send(admin.clone(), import(invalid)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(body["field_errors"][0]["field"], "max_individual_trade_risk");
+        assert_eq!(state.configuration_for(&admin).protocol_limits.max_total_portfolio_risk, dec!(0.10));
+        assert_eq!(state.audit_events().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_daily_report_returns_stored_summary_or_not_found() {
+        let state = Arc::new(ApiState::new());
+        state.reports().record_trade("trader-1", ClosedTrade {
+            symbol: "BTC/USDT".to_string(),
+            account_equity: dec!(10000),
+            realized_r: dec!(2),
+            pnl: dec!(200),
+            closed_at: Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap(),
+        });
+        let date = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        state.reports().generate("trader-1", date, &ProtocolLimits::default_limits());
+
+        let app = routes::<Arc<ApiState>>()
+            .layer(Extension(auth_context("trader-1")))
+            .with_state(state);
+
+        let response = app
+            .clone()
+            .oneshot(Request::get("/reports/daily?date=2024-03-01").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["data"]["trades_taken"], 1);
+        assert_eq!(body["data"]["win_rate"], "1");
+        assert_eq!(body["data"]["realized_r"], "2");
+
+        let response = app
+            .oneshot(Request::get("/reports/daily?date=2024-03-02").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_eur_base_currency_converts_portfolio_but_not_sizing() {
+        let protocol = RiskManagementProtocol::new().add_rule(MaxTradeRiskRule::new());
+        let decider = Arc::new(RiskDecider::new(Arc::new(protocol)));
+        let controller = Arc::new(OodaController::new(Arc::new(OodaLoop::with_all_components(
+            Arc::new(MockExchange::new()),
+            decider,
+        ))));
+        let state = Arc::new(ApiState::new().with_trading_controller(controller, 1));
+        state.set_configuration(
+            "trader-1",
+            UserConfiguration::for_profile(RiskProfile::Standard).with_base_currency("EUR"),
+        );
+        state.fx_rates().set_rate("EUR", dec!(0.92));
+        let snapshot = PortfolioSnapshot {
+            account_equity: dec!(10000),
+            realized_pnl: dec!(-250),
+            open_risk: dec!(200),
+            ..PortfolioSnapshot::default()
+        };
+        state.set_portfolio("trader-1", snapshot);
+
+        let app = routes::<Arc<ApiState>>()
+            .layer(Extension(auth_context("trader-1")))
+            .with_state(state.clone());
+
+        let response = app
+            .clone()
+            .oneshot(Request::get("/portfolio").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["data"]["currency"], "EUR");
+        assert_eq!(body["data"]["converted"], true);
+        assert_eq!(body["data"]["account_equity"], "9200.00");
+        assert_eq!(body["data"]["realized_pnl"], "-230.00");
+        assert_eq!(body["data"]["open_risk"], "184.00");
+        assert_eq!(state.portfolio("trader-1"), Some(snapshot));
+
+        // Sizing still runs on the native quote-currency equity
+        let request = Request::post("/trades/execute")
+            .header("content-type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "symbol": "BTC/USDT",
+                    "direction": "Long",
+                    "account_equity": "10000",
+                    "risk_percentage": "0.01",
+                })
+                .to_string(),
+            ))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["data"]["position_size"], "0.10");
+    }
+
+    #[tokio::test]
+    async fn test_idempotency_key_replays_only_the_same_body() {
+        let exchange = Arc::new(MockExchange::new());
+        let decider = Arc::new(RiskDecider::new(Arc::new(RiskManagementProtocol::new().add_rule(MaxTradeRiskRule::new()))));
+        let controller = Arc::new(OodaController::new(Arc::new(OodaLoop::with_all_components(
+            exchange.clone(),
+            decider,
+        ))));
+        let app = routes::<Arc<ApiState>>()
+            .layer(Extension(auth_context("trader-1")))
+            .with_state(Arc::new(ApiState::new().with_trading_controller(controller, 1)));
+        let execute = |key: &str, risk_percentage: &str| {
+            let request = Request::post("/trades/execute")
+                .header("content-type", "application/json")
+                .header(IDEMPOTENCY_KEY_HEADER, key)
+                .body(Body::from(
+                    serde_json::json!({
+                        "symbol": "BTC/USDT",
+                        "direction": "Long",
+                        "account_equity": "10000",
+                        "risk_percentage": risk_percentage,
+                    })
+                    .to_string(),
+                ))
+                .unwrap();
+            let app = app.clone();
+            async move {
+                let response = app.oneshot(request).await.unwrap();
+                let status = response.status();
+                let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+                (status, serde_json::from_slice::<serde_json::Value>(&body).unwrap())
+            }
+        };
+
+        let (status, first) = execute("order-1", "0.01").await;
+        assert_eq!(status, StatusCode::OK);
+
+        // A retry with the identical body replays the result without a new order
+        let (status, replayed) = execute("order-1", "0.01").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(replayed["data"], first["data"]);
+        assert_eq!(exchange.get_placed_orders().await.len(), 1);
+
+        // The same key with a different body is refused
+        let (status, body) = execute("order-1", "0.02").await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert!(body["error"].as_str().unwrap().contains("different request body"));
+        assert_eq!(exchange.get_placed_orders().await.len(), 1);
+
+        // A fresh key executes normally
+        let (status, _) = execute("order-2", "0.02").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(exchange.get_placed_orders().await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_portfolio_heat_contributions_sum_to_total() {
+        let state = Arc::new(ApiState::new());
+        let limits = ProtocolLimits {
+            max_total_portfolio_risk: dec!(0.06),
+            ..ProtocolLimits::default_limits()
+        };
+        state.set_configuration(
+            "trader-1",
+            UserConfiguration::for_profile(RiskProfile::Standard).with_limits(limits),
+        );
+        state.set_portfolio("trader-1", PortfolioSnapshot {
+            account_equity: dec!(10000),
+            open_risk: dec!(450),
+            open_positions: 3,
+            ..PortfolioSnapshot::default()
+        });
+        let position = |id: &str, symbol: &str, risk_amount| OpenPosition {
+            id: id.to_string(),
+            symbol: symbol.to_string(),
+            risk_amount,
+            risk_percentage: risk_amount / dec!(10000),
+            opened_at: std::time::SystemTime::now(),
+            unrealized_pnl: Decimal::ZERO,
+            max_adverse_excursion: Decimal::ZERO,
+        };
+        state.set_open_positions("trader-1", vec![
+            position("p1", "ETH/USDT", dec!(150)),
+            position("p2", "BTC/USDT", dec!(200)),
+            position("p3", "SOL/USDT", dec!(100)),
+        ]);
+
+        let app = routes::<Arc<ApiState>>()
+            .layer(Extension(auth_context("trader-1")))
+            .with_state(state);
+        let response = app
+            .oneshot(Request::get("/portfolio/heat").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let heat: PortfolioHeat = serde_json::from_value(
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap()["data"].clone(),
+        )
+        .unwrap();
+
+        let symbols: Vec<&str> = heat.positions.iter().map(|p| p.symbol.as_str()).collect();
+        assert_eq!(symbols, ["BTC/USDT", "ETH/USDT", "SOL/USDT"]);
+        let contributions: Decimal = heat.positions.iter().map(|p| p.risk_amount).sum();
+        assert_eq!(contributions, heat.total_risk);
+        assert_eq!(heat.total_risk, dec!(450));
+        assert_eq!(heat.risk_budget, dec!(600));
+        assert_eq!(heat.remaining_budget, dec!(150));
+        assert_eq!(heat.utilization, dec!(0.75));
+        assert_eq!(heat.positions[0].budget_share, dec!(200) / dec!(600));
+    }
+
+    #[tokio::test]
+    async fn test_restricted_symbol_is_not_tradable() {
+        let state = Arc::new(ApiState::new().with_exchange(Arc::new(MockExchange::new())));
+        state.set_configuration(
+            "trader-1",
+            UserConfiguration::for_profile(RiskProfile::Standard).with_symbol_restrictions(
+                SymbolRestrictionRule::new().with_restriction("ETH/USDT", "Compliance hold"),
+            ),
+        );
+
+        let app = routes::<Arc<ApiState>>()
+            .layer(Extension(auth_context("trader-1")))
+            .with_state(state);
+
+        let get_tradability = |symbol: &str| {
+            let uri = format!("/market/symbols/{}/tradable", symbol.replace('/', "%2F"));
+            let app = app.clone();
+            async move {
+                let response = app
+                    .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+                    .await
+                    .unwrap();
+                assert_eq!(response.status(), StatusCode::OK);
+                let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+                serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+            }
+        };
+
+        let body = get_tradability("ETH/USDT").await;
+        assert_eq!(body["data"]["symbol"], "ETH/USDT");
+        assert_eq!(body["data"]["tradable"], false);
+        assert_eq!(
+            body["data"]["reasons"],
+            serde_json::json!([{ "reason": "restricted", "detail": "Compliance hold" }])
+        );
+
+        let body = get_tradability("BTC/USDT").await;
+        assert_eq!(body["data"]["tradable"], true);
+        assert_eq!(body["data"]["reasons"], serde_json::json!([]));
+
+        let body = get_tradability("DOGE/USDT").await;
+        assert_eq!(body["data"]["tradable"], false);
+        assert_eq!(body["data"]["reasons"], serde_json::json!([{ "reason": "delisted" }]));
+    }
+
+    #[tokio::test]
+    async fn test_stop_inside_trigger_distance_suggests_minimum_stop() {
+        let exchange = Arc::new(MockExchange::new());
+        exchange.set_min_stop_distance("BTC/USDT", dec!(0.005)).await;
+        let app = routes::<Arc<ApiState>>()
+            .layer(Extension(auth_context("trader-1")))
+            .with_state(Arc::new(ApiState::new().with_exchange(exchange)));
+
+        let validate = |stop_loss: &str| {
+            let request = Request::post("/trades/validate-stop")
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    serde_json::json!({
+                        "symbol": "BTC/USDT",
+                        "direction": "Long",
+                        "stop_loss": stop_loss,
+                    })
+                    .to_string(),
+                ))
+                .unwrap();
+            let app = app.clone();
+            async move {
+                let response = app.oneshot(request).await.unwrap();
+                assert_eq!(response.status(), StatusCode::OK);
+                let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+                serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+            }
+        };
+
+        // 0.2% below the 50000 last price, inside the 0.5% minimum
+        let body = validate("49900").await;
+        assert_eq!(body["data"]["valid"], false);
+        assert_eq!(body["data"]["reference_price"], "50000.0");
+        assert_eq!(body["data"]["min_stop_distance"], "0.005");
+        assert_eq!(body["data"]["suggested_stop"], "49750.0000");
+
+        let body = validate("49000").await;
+        assert_eq!(body["data"]["valid"], true);
+        assert_eq!(body["data"]["suggested_stop"], serde_json::Value::Null);
+    }
+
+    #[tokio::test]
+    async fn test_sizing_breakdown_prices_the_entry_fee_from_the_schedule() {
+        let commissions: CommissionSchedule = serde_json::from_value(serde_json::json!({
+            "default_tier": { "maker": "0.001", "taker": "0.001" },
+            "exchanges": {
+                "binance": {
+                    "symbol_types": { "futures": { "maker": "0.0002", "taker": "0.0005" } },
+                    "symbols": { "BTCUSDT-PERP": "futures" },
+                },
+            },
+        }))
+        .unwrap();
+        let app = routes::<Arc<ApiState>>()
+            .layer(Extension(auth_context("trader-1")))
+            .with_state(Arc::new(ApiState::new().with_commission_schedule(commissions)));
+        let explain = |symbol: &str| {
+            let request = Request::post("/calculator/explain")
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    serde_json::json!({
+                        "direction": "Long",
+                        "account_equity": "10000",
+                        "risk_percentage": "0.02",
+                        "entry_price": "50000",
+                        "stop_loss": "49000",
+                        "exchange": "binance",
+                        "symbol": symbol,
+                    })
+                    .to_string(),
+                ))
+                .unwrap();
+            app.clone().oneshot(request)
+        };
+
+        let response = explain("BTCUSDT-PERP").await.unwrap();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let explanation: SizingExplanation = serde_json::from_value(body["data"].clone()).unwrap();
+        let fee = explanation.entry_fee.expect("entry fee for a named exchange and symbol");
+        // 0.2 BTC at 50000 is 10000 notional, at the futures taker rate
+        assert_eq!(fee.rate, dec!(0.0005));
+        assert_eq!(fee.fee, dec!(5));
+
+        let response = explain("UNLISTED/USDT").await.unwrap();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["data"]["entry_fee"]["rate"], "0.001");
+    }
+
+    #[tokio::test]
+    async fn test_sizing_breakdown_reconstructs_position_size() {
+        let app = routes::<Arc<ApiState>>()
+            .layer(Extension(auth_context("trader-1")))
+            .with_state(Arc::new(ApiState::new()));
+        let explain = |stop_loss: &str| {
+            let request = Request::post("/calculator/explain")
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    serde_json::json!({
+                        "direction": "Long",
+                        "account_equity": "10000",
+                        "risk_percentage": "0.02",
+                        "entry_price": "50000",
+                        "stop_loss": stop_loss,
+                        "take_profit": "56000",
+                    })
+                    .to_string(),
+                ))
+                .unwrap();
+            app.clone().oneshot(request)
+        };
+
+        let response = explain("48500").await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let explanation: SizingExplanation = serde_json::from_value(body["data"].clone()).unwrap();
+
+        let [risk_amount, stop_distance, position_size] = &explanation.steps[..] else {
+            panic!("Expected three steps, got: {:?}", explanation.steps);
+        };
+        assert_eq!(risk_amount.value, dec!(10000) * dec!(0.02));
+        assert_eq!(stop_distance.value, dec!(1500));
+        assert_eq!(position_size.formula, "risk_amount ÷ stop_distance = 200 ÷ 1500");
+        // 200 / 1500 does not terminate; the steps still multiply back exactly
+        assert_eq!(position_size.value, risk_amount.value / stop_distance.value);
+        assert_eq!(explanation.position_size, position_size.value);
+        assert_eq!((explanation.position_size * stop_distance.value).round_dp(20), dec!(200));
+
+        let rules: Vec<(&str, bool)> = explanation
+            .checks
+            .iter()
+            .map(|check| (check.rule.as_str(), check.passed))
+            .collect();
+        assert_eq!(rules, vec![("IndividualTradeRisk", true), ("MinRewardRiskRatio", true)]);
+
+        // A stop above a long entry is refused rather than sized
+        let response = explain("51000").await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_repeated_sizing_is_served_from_cache_and_counted() {
+        let admin = AuthContext {
+            permissions: vec![ADMIN_PERMISSION.to_string(), "trade:execute".to_string()],
+            ..auth_context("ops-1")
+        };
+        let controller = Arc::new(OodaController::new(Arc::new(OodaLoop::with_all_components(
+            Arc::new(MockExchange::new()),
+            Arc::new(RiskDecider::new(Arc::new(RiskManagementProtocol::new().add_rule(MaxTradeRiskRule::new())))),
+        ))));
+        let app = routes::<Arc<ApiState>>()
+            .merge(metrics_routes())
+            .layer(Extension(admin))
+            .with_state(Arc::new(ApiState::new().with_trading_controller(controller, 1)));
+        let get = |uri: &str| {
+            let app = app.clone();
+            let request = Request::get(uri).body(Body::empty()).unwrap();
+            async move {
+                let response = app.oneshot(request).await.unwrap();
+                assert_eq!(response.status(), StatusCode::OK);
+                let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+                String::from_utf8(body.to_vec()).unwrap()
+            }
+        };
+        let explain = |entry_price: &str| {
+            let request = Request::post("/calculator/explain")
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    serde_json::json!({
+                        "direction": "Long",
+                        "account_equity": "10000",
+                        "risk_percentage": "0.02",
+                        "entry_price": entry_price,
+                        "stop_loss": "48500",
+                    })
+                    .to_string(),
+                ))
+                .unwrap();
+            let app = app.clone();
+            async move { assert_eq!(app.oneshot(request).await.unwrap().status(), StatusCode::OK) }
+        };
+
+        // Three identical calculations and one different: two misses, two hits
+        for _ in 0..3 {
+            explain("50000").await;
+        }
+        explain("51000").await;
+
+        // A trade is sized in the orient phase, outside the cache
+        let trade = Request::post("/trades/execute")
+            .header("content-type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "symbol": "BTC/USDT",
+                    "direction": "Long",
+                    "account_equity": "10000",
+                    "risk_percentage": "0.01",
+                })
+                .to_string(),
+            ))
+            .unwrap();
+        assert_eq!(app.clone().oneshot(trade).await.unwrap().status(), StatusCode::OK);
+
+        let body: serde_json::Value = serde_json::from_str(&get("/admin/stats/calculator").await).unwrap();
+        let stats: CalculatorStats = serde_json::from_value(body["data"].clone()).unwrap();
+        assert_eq!(stats.calculations, 5);
+        assert_eq!(stats.trade_sizings, 1);
+        assert_eq!((stats.cache_hits, stats.cache_misses), (2, 2));
+        assert_eq!(stats.hit_rate, 0.5);
+        assert_eq!(stats.cache_entries, 2);
+        assert!(stats.average_latency_micros > 0.0);
+
+        let metrics = get("/metrics").await;
+        assert!(metrics.contains("testudo_sizing_calculations_total 5\n"));
+        assert!(metrics.contains("testudo_sizing_trade_calculations_total 1\n"));
+        assert!(metrics.contains("testudo_sizing_cache_hits_total 2\n"));
+        assert!(metrics.contains("testudo_sizing_cache_hit_rate 0.5\n"));
+    }
+
+    #[tokio::test]
+    async fn test_metrics_report_websocket_replay_memory() {
+        let connections = Arc::new(ConnectionManager::new());
+        let app = metrics_routes::<Arc<ApiState>>()
+            .with_state(Arc::new(ApiState::new().with_connections(connections.clone())));
+        let replay_bytes = || {
+            let app = app.clone();
+            async move {
+                let response = app.oneshot(Request::get("/metrics").body(Body::empty()).unwrap()).await.unwrap();
+                let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+                let metrics = String::from_utf8(body.to_vec()).unwrap();
+                metrics
+                    .lines()
+                    .find_map(|line| line.strip_prefix("testudo_ws_replay_memory_bytes "))
+                    .map(|value| value.parse::<usize>().unwrap())
+                    .expect("replay memory gauge")
+            }
+        };
+
+        assert_eq!(replay_bytes().await, 0);
+        let heartbeat = crate::types::WebSocketMessage::Heartbeat { timestamp: chrono::Utc::now() };
+        connections.send_to_user("trader-1", &heartbeat);
+        assert_eq!(replay_bytes().await, connections.replay_memory_bytes());
+        assert!(connections.replay_memory_bytes() > 0);
+    }
+
+    #[tokio::test]
+    async fn test_notification_test_fire_reports_delivery() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let webhook_url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let received = Arc::new(std::sync::Mutex::new(Vec::<serde_json::Value>::new()));
+        let sink = received.clone();
+        let webhook = Router::new().route(
+            "/hook",
+            post(move |Json(body): Json<serde_json::Value>| async move {
+                sink.lock().unwrap().push(body);
+            }),
+        );
+        tokio::spawn(async move { axum::serve(listener, webhook).await.unwrap() });
+
+        let state = Arc::new(ApiState::new());
+        let test_fire = |user_id: &str, webhook: Option<&str>| {
+            let mut configuration = UserConfiguration::for_profile(RiskProfile::Standard);
+            configuration.notification_webhook = webhook.map(str::to_string);
+            state.set_configuration(user_id, configuration);
+            routes::<Arc<ApiState>>()
+                .layer(Extension(auth_context(user_id)))
+                .with_state(state.clone())
+                .oneshot(Request::post("/notifications/test").body(Body::empty()).unwrap())
+        };
+        let result = |response: Response| async move {
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            serde_json::from_value::<NotificationTestResult>(body["data"].clone()).unwrap()
+        };
+
+        let delivered = result(test_fire("trader-1", Some(&webhook_url)).await.unwrap()).await;
+        assert!(delivered.delivered);
+        assert_eq!(delivered.deliveries, 1);
+        assert_eq!(delivered.error, None);
+        {
+            let received = received.lock().unwrap();
+            assert_eq!(received.len(), 1);
+            assert_eq!(received[0]["user_id"], "trader-1");
+            assert_eq!(received[0]["message"]["rule"], "NotificationTest");
+        }
+
+        let failed = result(test_fire("trader-2", Some("http://127.0.0.1:1/hook")).await.unwrap()).await;
+        assert!(!failed.delivered);
+        assert!(failed.error.unwrap().contains("webhook unreachable"));
+
+        // Without a webhook or a default notifier there is nothing to test
+        let response = test_fire("trader-3", None).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_exchanges_lists_capabilities_and_health() {
+        let manager = Arc::new(ExchangeManager::new(ExchangeFailoverConfig {
+            primary_exchange: "binance".to_string(),
+            backup_exchanges: vec!["kraken".to_string()],
+            health_check_interval_secs: 30,
+            health_check_jitter: false,
+        }));
+        let binance = MockExchange::with_name("binance".to_string()).with_capabilities(
+            ExchangeCapabilities { oco: true, iceberg: true, futures: true, quote_orders: true },
+        );
+        let kraken = Arc::new(MockExchange::with_name("kraken".to_string()));
+        kraken.set_health(false).await;
+        manager.add_adapter("binance", Arc::new(binance)).await;
+        manager.add_adapter("kraken", kraken.clone()).await;
+
+        let app = routes::<Arc<ApiState>>()
+            .layer(Extension(auth_context("trader-1")))
+            .with_state(Arc::new(ApiState::new().with_exchange_manager(manager)));
+        let list = || async {
+            let response = app
+                .clone()
+                .oneshot(Request::get("/exchanges").body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+        };
+        let body = list().await;
+
+        let exchanges = body["data"].as_array().unwrap();
+        assert_eq!(exchanges.len(), 2);
+        assert_eq!(exchanges[0]["name"], "binance");
+        assert_eq!(
+            exchanges[0]["capabilities"],
+            serde_json::json!({ "oco": true, "iceberg": true, "futures": true, "quote_orders": true })
+        );
+        assert_eq!(exchanges[0]["is_active"], true);
+        assert_eq!(exchanges[0]["healthy"], true);
+        assert_eq!(exchanges[1]["name"], "kraken");
+        assert_eq!(
+            exchanges[1]["capabilities"],
+            serde_json::json!({ "oco": true, "iceberg": false, "futures": false, "quote_orders": false })
+        );
+        assert_eq!(exchanges[1]["is_primary"], false);
+        assert_eq!(exchanges[1]["healthy"], false);
+
+        // Within the 30s interval the recorded health is served, not re-probed
+        kraken.set_health(true).await;
+        assert_eq!(list().await["data"][1]["healthy"], false);
+    }
+
+    #[tokio::test]
+    async fn test_credentials_check_reports_whether_keys_authenticate() {
+        let manager = Arc::new(ExchangeManager::new(ExchangeFailoverConfig {
+            primary_exchange: "binance".to_string(),
+            backup_exchanges: vec!["kraken".to_string()],
+            health_check_interval_secs: 30,
+            health_check_jitter: false,
+        }));
+        let kraken = MockExchange::with_name("kraken".to_string());
+        kraken.set_credentials_valid(false).await;
+        manager.add_adapter("binance", Arc::new(MockExchange::with_name("binance".to_string()))).await;
+        manager.add_adapter("kraken", Arc::new(kraken)).await;
+        let state = Arc::new(ApiState::new().with_exchange_manager(manager));
+        let admin = AuthContext {
+            permissions: vec![ADMIN_PERMISSION.to_string()],
+            ..auth_context("ops-1")
+        };
+
+        let check_as = |user: AuthContext, name: &str| {
+            let app = routes::<Arc<ApiState>>()
+                .layer(Extension(user))
+                .with_state(state.clone());
+            let uri = format!("/exchanges/{}/credentials/check", name);
+            async move {
+                let response = app.oneshot(Request::get(uri).body(Body::empty()).unwrap()).await.unwrap();
+                let status = response.status();
+                let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+                (status, serde_json::from_slice::<serde_json::Value>(&body).unwrap())
+            }
+        };
+        let check = |name: &str| check_as(admin.clone(), name);
+
+        // The service's keys are not checked for a trader
+        let (status, _) = check_as(auth_context("trader-1"), "binance").await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        let (status, body) = check("binance").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["exchange"], "binance");
+        assert_eq!(body["data"]["status"], "valid");
+        assert_eq!(body["data"]["error"], serde_json::Value::Null);
+
+        let (status, body) = check("kraken").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["status"], "invalid");
+        assert!(body["data"]["error"].as_str().unwrap().contains("Authentication failed"));
+
+        let (status, _) = check("coinbase").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_risk_rejection_returns_422_with_violations() {
+        let exchange = Arc::new(MockExchange::new());
+        // 1% of 10000 over a 2% stop at 50000 sizes at 0.10 BTC, over the cap
+        let protocol = RiskManagementProtocol::new()
+            .add_rule(MaxTradeRiskRule::new())
+            .add_rule(MaxPositionUnitsRule::new().with_cap("BTC/USDT", dec!(0.05)));
+        let controller = Arc::new(OodaController::new(Arc::new(OodaLoop::with_all_components(
+            exchange.clone(),
+            Arc::new(RiskDecider::new(Arc::new(protocol))),
+        ))));
+        let app = routes::<Arc<ApiState>>()
+            .layer(Extension(auth_context("trader-1")))
+            .with_state(Arc::new(ApiState::new().with_trading_controller(controller, 1)));
+
+        let request = Request::post("/trades/execute")
+            .header("content-type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "symbol": "BTC/USDT",
+                    "direction": "Long",
+                    "account_equity": "10000",
+                    "risk_percentage": "0.01",
+                })
+                .to_string(),
+            ))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["success"], false);
+        assert!(body["error"].as_str().unwrap().starts_with("Trade rejected by risk protocol"));
+        let violations = body["violations"].as_array().unwrap();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0]["rule_name"], "MaxPositionUnits");
+        assert_eq!(violations[0]["severity"], "Blocking");
+        assert!(exchange.get_placed_orders().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_rule_failures_are_reported_apart_from_rejections() {
+        #[derive(Debug)]
+        struct MisconfiguredRule;
+
+        impl prudentia::AssessmentRiskRule for MisconfiguredRule {
+            fn assess(
+                &self,
+                _proposal: &prudentia::TradeProposal,
+            ) -> std::result::Result<prudentia::RiskAssessment, prudentia::risk::assessment_rules::AssessmentError> {
+                Err(prudentia::risk::assessment_rules::AssessmentError::ConfigurationError {
+                    reason: "limits not loaded".to_string(),
+                })
+            }
+
+            fn rule_name(&self) -> &str {
+                "Misconfigured"
+            }
+
+            fn description(&self) -> &str {
+                "Always fails to run"
+            }
+        }
+
+        let exchange = Arc::new(MockExchange::new());
+        let protocol = RiskManagementProtocol::new()
+            .add_rule(MisconfiguredRule)
+            .add_rule(MaxTradeRiskRule::new())
+            .add_rule(MaxPositionUnitsRule::new().with_cap("BTC/USDT", dec!(0.05)));
+        let controller = Arc::new(OodaController::new(Arc::new(OodaLoop::with_all_components(
+            exchange.clone(),
+            Arc::new(RiskDecider::new(Arc::new(protocol))),
+        ))));
+        let state = Arc::new(ApiState::new().with_trading_controller(controller, 1));
+        let app = routes::<Arc<ApiState>>()
+            .layer(Extension(auth_context("trader-1")))
+            .with_state(state.clone());
+
+        let request = Request::post("/trades/execute")
+            .header("content-type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "symbol": "BTC/USDT",
+                    "direction": "Long",
+                    "account_equity": "10000",
+                    "risk_percentage": "0.01",
+                })
+                .to_string(),
+            ))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["failed_rules"], serde_json::json!([{
+            "rule_name": "Misconfigured",
+            "reason": "Assessment configuration error: limits not loaded",
+        }]));
+        assert_eq!(body["rejecting_rules"], serde_json::json!(["MaxPositionUnits"]));
+
+        let response = metrics_routes::<Arc<ApiState>>()
+            .with_state(state)
+            .oneshot(Request::get("/metrics").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let metrics = String::from_utf8(body.to_vec()).unwrap();
+        assert!(metrics.contains("testudo_risk_rule_outcomes_total{rule=\"Misconfigured\",outcome=\"failed\"} 1\n"));
+        assert!(metrics.contains("testudo_risk_rule_outcomes_total{rule=\"MaxPositionUnits\",outcome=\"rejected\"} 1\n"));
+        assert!(metrics.contains("testudo_risk_rule_outcomes_total{rule=\"MaxTradeRisk\",outcome=\"passed\"} 1\n"));
+    }
+
+    #[tokio::test]
+    async fn test_quote_amount_trade_executes_the_converted_position() {
+        let exchange = Arc::new(MockExchange::new());
+        let protocol = RiskManagementProtocol::new().add_rule(MaxTradeRiskRule::new());
+        let controller = Arc::new(OodaController::new(Arc::new(
+            OodaLoop::with_all_components(exchange.clone(), Arc::new(RiskDecider::new(Arc::new(protocol))))
+                .with_lot_step("BTC/USDT", dec!(0.001)),
+        )));
+        let state = Arc::new(ApiState::new().with_trading_controller(controller, 1));
+        let app = routes::<Arc<ApiState>>()
+            .layer(Extension(auth_context("trader-1")))
+            .with_state(state.clone());
+
+        let request = Request::post("/trades/execute")
+            .header("content-type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "symbol": "BTC/USDT",
+                    "direction": "Long",
+                    "account_equity": "10000",
+                    "risk_percentage": "0.01",
+                    "quote_amount": "500",
+                })
+                .to_string(),
+            ))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+        // $500 at the mock's 50,000 is 0.01 BTC, risking $10 over a 2% stop
+        assert_eq!(body["data"]["position_size"], "0.01");
+        assert_eq!(exchange.get_placed_orders().await[0].executed_quantity, dec!(0.01));
+        let status = state.protocol_status("trader-1").await.unwrap();
+        assert_eq!(status.total_portfolio_risk, dec!(0.001));
+    }
+
+    #[tokio::test]
+    async fn test_executed_position_publishes_its_fill_and_exits() {
+        use crate::websocket::MessageEncoding;
+        use axum::extract::ws::Message;
+
+        let exchange = Arc::new(MockExchange::new());
+        let protocol = RiskManagementProtocol::new().add_rule(MaxTradeRiskRule::new());
+        let controller = Arc::new(OodaController::new(Arc::new(OodaLoop::with_all_components(
+            exchange,
+            Arc::new(RiskDecider::new(Arc::new(protocol))),
+        ))));
+        let connections = Arc::new(ConnectionManager::new());
+        let (_, mut rx) = connections.register("trader-1", MessageEncoding::Json);
+        let state = Arc::new(
+            ApiState::new()
+                .with_trading_controller(controller, 1)
+                .with_connections(connections),
+        );
+        let app = routes::<Arc<ApiState>>()
+            .layer(Extension(auth_context("trader-1")))
+            .with_state(state.clone());
+        let post = |uri: String, body: serde_json::Value| {
+            Request::post(uri)
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        // 1% of 10,000 at the mock's 50,000 with a 2% stop: 0.1 BTC risking $100
+        let request = post("/trades/execute".to_string(), serde_json::json!({
+            "symbol": "BTC/USDT",
+            "direction": "Long",
+            "account_equity": "10000",
+            "risk_percentage": "0.01",
+        }));
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let position_id = body["data"]["position_id"].as_str().unwrap().to_string();
+        assert_eq!(state.open_positions("trader-1").len(), 1);
+
+        let close = |quantity: Option<&str>, exit_price: &str| {
+            let mut body = serde_json::json!({ "exit_price": exit_price });
+            if let Some(quantity) = quantity {
+                body["quantity"] = quantity.into();
+            }
+            post(format!("/positions/{}/close", position_id), body)
+        };
+        let response = app.clone().oneshot(close(Some("0.05"), "51000")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["data"]["stage"], "PartiallyClosed");
+        assert_eq!(body["data"]["pnl"], "50.000");
+        let status = state.protocol_status("trader-1").await.unwrap();
+        assert_eq!(status.total_portfolio_risk, dec!(0.005));
+
+        let response = app.clone().oneshot(close(None, "52000")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["data"]["stage"], "Closed");
+        assert_eq!(body["data"]["realized_pnl"], "150.000");
+        let status = state.protocol_status("trader-1").await.unwrap();
+        assert_eq!(status.total_portfolio_risk, Decimal::ZERO);
+        assert_eq!(status.open_positions, 0);
+        assert!(state.open_positions("trader-1").is_empty());
+
+        // The closed trade is journaled for the daily summary: $150 on $100 of risk
+        let summary = state
+            .reports()
+            .generate("trader-1", Utc::now().date_naive(), &ProtocolLimits::default());
+        assert_eq!(summary.trades_taken, 1);
+        assert_eq!(summary.realized_r, dec!(1.5));
+        assert_eq!(summary.realized_pnl, dec!(150));
+
+        let mut transitions = Vec::new();
+        while let Ok(Message::Text(text)) = rx.try_recv() {
+            let WebSocketMessage::Sequenced { message, .. } = serde_json::from_str(&text).unwrap() else {
+                panic!("Expected a sequenced frame, got: {}", text);
+            };
+            if let WebSocketMessage::PositionLifecycle(event) = *message {
+                assert_eq!(event.position_id.to_string(), position_id);
+                transitions.push(event.transition.event_type());
+            }
+        }
+        assert_eq!(transitions, [
+            "POSITION_ENTRY_SUBMITTED",
+            "POSITION_ENTRY_FILLED",
+            "POSITION_PARTIALLY_CLOSED",
+            "POSITION_CLOSED",
+        ]);
+
+        // The position is gone once closed
+        let response = app.oneshot(close(None, "52000")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_losing_close_raises_the_daily_loss_alert() {
+        use crate::websocket::MessageEncoding;
+        use axum::extract::ws::Message;
+
+        let exchange = Arc::new(MockExchange::new());
+        let protocol = RiskManagementProtocol::new().add_rule(MaxTradeRiskRule::new());
+        let controller = Arc::new(OodaController::new(Arc::new(OodaLoop::with_all_components(
+            exchange,
+            Arc::new(RiskDecider::new(Arc::new(protocol))),
+        ))));
+        let connections = Arc::new(ConnectionManager::new());
+        let (_, mut rx) = connections.register("trader-1", MessageEncoding::Json);
+        let state = Arc::new(
+            ApiState::new()
+                .with_trading_controller(controller, 1)
+                .with_connections(connections),
+        );
+        let app = routes::<Arc<ApiState>>()
+            .layer(Extension(auth_context("trader-1")))
+            .with_state(state.clone());
+
+        // 2% of 10,000 over a 2% stop at 50,000: 0.2 BTC with a 49,000 stop
+        let request = Request::post("/trades/execute")
+            .header("content-type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "symbol": "BTC/USDT",
+                    "direction": "Long",
+                    "account_equity": "10000",
+                    "risk_percentage": "0.02",
+                })
+                .to_string(),
+            ))
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let position_id = body["data"]["position_id"].as_str().unwrap();
+
+        // Stopped out with slippage at 48,000: a $400 loss is 80% of the 5% daily budget
+        let request = Request::post(format!("/positions/{}/close", position_id))
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::json!({ "exit_price": "48000" }).to_string()))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let status = state.protocol_status("trader-1").await.unwrap();
+        assert_eq!(status.daily_loss, dec!(400));
+        assert!(state.audit_events().iter().any(|event| event.event_type == "DAILY_LOSS_ALERT"));
+
+        let mut alerts = Vec::new();
+        while let Ok(Message::Text(text)) = rx.try_recv() {
+            let WebSocketMessage::Sequenced { message, .. } = serde_json::from_str(&text).unwrap() else {
+                panic!("Expected a sequenced frame, got: {}", text);
+            };
+            if let WebSocketMessage::RiskAlert { severity, rule, .. } = *message {
+                alerts.push((severity, rule));
+            }
+        }
+        assert_eq!(alerts, [(AlertSeverity::Warning, "DailyLossLimit".to_string())]);
+    }
+
+    #[tokio::test]
+    async fn test_price_feed_moves_executed_stop_to_breakeven() {
+        use crate::lifecycle::spawn_price_feed;
+        use crate::websocket::MessageEncoding;
+        use axum::extract::ws::Message;
+        use testudo_types::MarketData;
+
+        let exchange = Arc::new(MockExchange::new());
+        let protocol = RiskManagementProtocol::new().add_rule(MaxTradeRiskRule::new());
+        let controller = Arc::new(OodaController::new(Arc::new(OodaLoop::with_all_components(
+            exchange.clone(),
+            Arc::new(RiskDecider::new(Arc::new(protocol))),
+        ))));
+        let connections = Arc::new(ConnectionManager::new());
+        let (_, mut rx) = connections.register("trader-1", MessageEncoding::Json);
+        let state = Arc::new(
+            ApiState::new()
+                .with_trading_controller(controller, 1)
+                .with_connections(connections)
+                .with_exchange(exchange.clone())
+                .with_breakeven(Arc::new(Executor::new(exchange.clone()))),
+        );
+        let app = routes::<Arc<ApiState>>()
+            .layer(Extension(auth_context("trader-1")))
+            .with_state(state.clone());
+
+        // 0.1 BTC at 50,000 with a 49,000 stop: 1R is reached at 51,000
+        let request = Request::post("/trades/execute")
+            .header("content-type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "symbol": "BTC/USDT",
+                    "direction": "Long",
+                    "account_equity": "10000",
+                    "risk_percentage": "0.01",
+                })
+                .to_string(),
+            ))
+            .unwrap();
+        assert_eq!(app.oneshot(request).await.unwrap().status(), StatusCode::OK);
+        exchange
+            .set_market_data("BTC/USDT".to_string(), MarketData {
+                symbol: "BTC/USDT".to_string(),
+                bid_price: dec!(50990),
+                ask_price: dec!(51010),
+                last_price: dec!(51000),
+                volume_24h: dec!(1000),
+                timestamp: SystemTime::now(),
+            })
+            .await;
+
+        let feed = spawn_price_feed(state.clone(), Duration::from_millis(10));
+        let moved = tokio::time::timeout(Duration::from_secs(2), async {
+            loop {
+                let Some(Message::Text(text)) = rx.recv().await else {
+                    panic!("Connection closed before the stop moved");
+                };
+                let WebSocketMessage::Sequenced { message, .. } = serde_json::from_str(&text).unwrap() else {
+                    panic!("Expected a sequenced frame, got: {}", text);
+                };
+                if let WebSocketMessage::PositionLifecycle(event) = *message {
+                    if matches!(event.transition, lifecycle::PositionTransition::StopMoved { .. }) {
+                        return event;
+                    }
+                }
+            }
+        })
+        .await
+        .expect("stop should move to breakeven");
+        feed.abort();
+
+        assert_eq!(moved.transition, lifecycle::PositionTransition::StopMoved { from: dec!(49000), to: dec!(50000) });
+        assert_eq!((moved.risk_before, moved.risk_after), (dec!(100), Decimal::ZERO));
+        let status = state.protocol_status("trader-1").await.unwrap();
+        assert_eq!(status.total_portfolio_risk, Decimal::ZERO);
+        assert_eq!(state.open_positions("trader-1")[0].risk_amount, Decimal::ZERO);
+        assert_eq!(exchange.get_oco_modifications().await[0].stop_price, Some(dec!(50000)));
+    }
+
+    #[tokio::test]
+    async fn test_bracket_route_moves_the_stop_and_updates_tracked_risk() {
+        use testudo_types::MarketData;
+
+        let exchange = Arc::new(MockExchange::new());
+        let protocol = RiskManagementProtocol::new().add_rule(MaxTradeRiskRule::new());
+        let controller = Arc::new(OodaController::new(Arc::new(OodaLoop::with_all_components(
+            exchange.clone(),
+            Arc::new(RiskDecider::new(Arc::new(protocol))),
+        ))));
+        let state = Arc::new(
+            ApiState::new()
+                .with_trading_controller(controller, 1)
+                .with_bracket_executor(Arc::new(Executor::new(exchange.clone()))),
+        );
+        let app = routes::<Arc<ApiState>>()
+            .layer(Extension(auth_context("trader-1")))
+            .with_state(state.clone());
+
+        // 0.1 BTC at 50,000 with a 49,000 stop risks $100
+        let request = Request::post("/trades/execute")
+            .header("content-type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "symbol": "BTC/USDT",
+                    "direction": "Long",
+                    "account_equity": "10000",
+                    "risk_percentage": "0.01",
+                })
+                .to_string(),
+            ))
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let position_id = body["data"]["position_id"].as_str().unwrap().to_string();
+        exchange
+            .set_market_data("BTC/USDT".to_string(), MarketData {
+                symbol: "BTC/USDT".to_string(),
+                bid_price: dec!(50990),
+                ask_price: dec!(51010),
+                last_price: dec!(51000),
+                volume_24h: dec!(1000),
+                timestamp: SystemTime::now(),
+            })
+            .await;
+        let modify = |body: serde_json::Value| {
+            Request::put(format!("/positions/{}/bracket", position_id))
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        // A stop above the market is refused before the exchange is contacted
+        let response = app.clone().oneshot(modify(serde_json::json!({ "stop_loss": "52000" }))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert!(exchange.get_oco_modifications().await.is_empty());
+
+        // Moving the stop to breakeven leaves the position risk-free
+        let response = app
+            .clone()
+            .oneshot(modify(serde_json::json!({ "stop_loss": "50000", "take_profit": "53000" })))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["data"]["stop_loss"], "50000");
+        assert_eq!(body["data"]["take_profit"], "53000");
+        assert_eq!(body["data"]["open_risk"], "0");
+
+        let modifications = exchange.get_oco_modifications().await;
+        assert_eq!(modifications.len(), 1);
+        assert_eq!(modifications[0].stop_price, Some(dec!(50000)));
+        assert_eq!(modifications[0].take_profit_price, Some(dec!(53000)));
+        let status = state.protocol_status("trader-1").await.unwrap();
+        assert_eq!(status.total_portfolio_risk, Decimal::ZERO);
+        assert_eq!(state.open_positions("trader-1")[0].risk_amount, Decimal::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_unit_cap_follows_opened_and_closed_positions() {
+        let units_rule = MaxPositionUnitsRule::new().with_cap("BTC/USDT", dec!(0.15));
+        let protocol = RiskManagementProtocol::new()
+            .add_rule(MaxTradeRiskRule::new())
+            .add_rule(units_rule.clone());
+        let controller = Arc::new(OodaController::new(Arc::new(OodaLoop::with_all_components(
+            Arc::new(MockExchange::new()),
+            Arc::new(RiskDecider::new(Arc::new(protocol))),
+        ))));
+        let state = Arc::new(
+            ApiState::new()
+                .with_trading_controller(controller, 1)
+                .with_units_rule(units_rule.clone()),
+        );
+        let app = routes::<Arc<ApiState>>()
+            .layer(Extension(auth_context("trader-1")))
+            .with_state(state.clone());
+        let post = |uri: String, body: serde_json::Value| {
+            let request = Request::post(uri)
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap();
+            let app = app.clone();
+            async move {
+                let response = app.oneshot(request).await.unwrap();
+                let status = response.status();
+                let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+                (status, serde_json::from_slice::<serde_json::Value>(&body).unwrap())
+            }
+        };
+        // Each trade buys 0.1 BTC
+        let execute = || post("/trades/execute".to_string(), serde_json::json!({
+            "symbol": "BTC/USDT",
+            "direction": "Long",
+            "account_equity": "10000",
+            "risk_percentage": "0.01",
+        }));
+
+        let (status, body) = execute().await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(units_rule.open_quantity("BTC/USDT"), dec!(0.10));
+        let position_id = body["data"]["position_id"].as_str().unwrap().to_string();
+
+        // A second 0.1 BTC would hold 0.2 against the 0.15 cap
+        let (status, body) = execute().await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["violations"][0]["rule_name"], "MaxPositionUnits");
+
+        let close = serde_json::json!({ "exit_price": "51000" });
+        let (status, _) = post(format!("/positions/{}/close", position_id), close).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(units_rule.open_quantity("BTC/USDT"), Decimal::ZERO);
+
+        let (status, _) = execute().await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_price_dip_records_mae_and_alerts_near_the_stop() {
+        use crate::websocket::MessageEncoding;
+        use axum::extract::ws::Message;
+
+        let portfolio_rule = MaxPortfolioRiskRule::new();
+        let protocol = RiskManagementProtocol::new()
+            .add_rule(MaxTradeRiskRule::new())
+            .add_rule(portfolio_rule.clone());
+        let controller = Arc::new(OodaController::new(Arc::new(OodaLoop::with_all_components(
+            Arc::new(MockExchange::new()),
+            Arc::new(RiskDecider::new(Arc::new(protocol))),
+        ))));
+        let connections = Arc::new(ConnectionManager::new());
+        let (_, mut rx) = connections.register("trader-1", MessageEncoding::Json);
+        let state = Arc::new(
+            ApiState::new()
+                .with_trading_controller(controller, 1)
+                .with_connections(connections)
+                .with_portfolio_rule(portfolio_rule.clone()),
+        );
+        let app = routes::<Arc<ApiState>>()
+            .layer(Extension(auth_context("trader-1")))
+            .with_state(state.clone());
+
+        // 0.1 BTC at 50,000 with a 49,000 stop risks $100
+        let request = Request::post("/trades/execute")
+            .header("content-type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "symbol": "BTC/USDT",
+                    "direction": "Long",
+                    "account_equity": "10000",
+                    "risk_percentage": "0.01",
+                })
+                .to_string(),
+            ))
+            .unwrap();
+        assert_eq!(app.oneshot(request).await.unwrap().status(), StatusCode::OK);
+        assert_eq!(portfolio_rule.position_count(), 1);
+
+        // A dip to 49,100 is a $90 drawdown, 90% of the way to the stop
+        lifecycle::on_price(&state, "BTC/USDT", dec!(49100)).await;
+        lifecycle::on_price(&state, "BTC/USDT", dec!(50500)).await;
+
+        let position = &state.open_positions("trader-1")[0];
+        assert_eq!(position.unrealized_pnl, dec!(50));
+        assert_eq!(position.max_adverse_excursion, dec!(90));
+        let audited: Vec<_> = state
+            .audit_events()
+            .into_iter()
+            .filter(|event| event.event_type == "ADVERSE_EXCURSION_ALERT")
+            .collect();
+        assert_eq!(audited.len(), 1);
+
+        let mut alerts = Vec::new();
+        while let Ok(Message::Text(text)) = rx.try_recv() {
+            let WebSocketMessage::Sequenced { message, .. } = serde_json::from_str(&text).unwrap() else {
+                panic!("Expected a sequenced frame, got: {}", text);
+            };
+            if let WebSocketMessage::RiskAlert { severity, rule, .. } = *message {
+                alerts.push((severity, rule));
+            }
+        }
+        assert_eq!(alerts, [(AlertSeverity::Warning, "MaxAdverseExcursion".to_string())]);
+    }
+
+    #[tokio::test]
+    async fn test_preferred_exchange_is_used_for_execution() {
+        let manager = Arc::new(ExchangeManager::new(ExchangeFailoverConfig {
+            primary_exchange: "binance".to_string(),
+            backup_exchanges: vec!["kraken".to_string()],
+            health_check_interval_secs: 30,
+            health_check_jitter: false,
+        }));
+        let binance = Arc::new(MockExchange::with_name("binance".to_string()));
+        let kraken = Arc::new(MockExchange::with_name("kraken".to_string()));
+        manager.add_adapter("binance", binance.clone()).await;
+        manager.add_adapter("kraken", kraken.clone()).await;
+
+        let protocol = RiskManagementProtocol::new().add_rule(MaxTradeRiskRule::new());
+        let ooda_loop =
+            OodaLoop::with_all_components(binance.clone(), Arc::new(RiskDecider::new(Arc::new(protocol))))
+                .with_exchange_router(manager)
+                .unwrap();
+        let state = Arc::new(
+            ApiState::new().with_trading_controller(Arc::new(OodaController::new(Arc::new(ooda_loop))), 1),
+        );
+        state.set_configuration(
+            "trader-1",
+            UserConfiguration::for_profile(RiskProfile::Standard).with_exchange_routing(ExchangeRouting {
+                default_exchange: Some("kraken".to_string()),
+                ..ExchangeRouting::default()
+            }),
+        );
+
+        for user_id in ["trader-1", "trader-2"] {
+            let app = routes::<Arc<ApiState>>()
+                .layer(Extension(auth_context(user_id)))
+                .with_state(state.clone());
+            let request = Request::post("/trades/execute")
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    serde_json::json!({
+                        "symbol": "BTC/USDT",
+                        "direction": "Long",
+                        "account_equity": "10000",
+                        "risk_percentage": "0.01",
+                    })
+                    .to_string(),
+                ))
+                .unwrap();
+            let response = app.oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+
+        // trader-1 prefers kraken; trader-2 has no preference and uses the primary
+        assert_eq!(kraken.get_placed_orders().await.len(), 1);
+        assert_eq!(binance.get_placed_orders().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_imported_positions_add_to_portfolio_risk() {
+        let state = Arc::new(ApiState::new());
+        let app = routes::<Arc<ApiState>>()
+            .layer(Extension(auth_context("trader-1")))
+            .with_state(state.clone());
+
+        // Risks of $100, $150 and $50 on $10,000 equity
+        let request = Request::post("/positions/import")
+            .header("content-type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "account_equity": "10000",
+                    "positions": [
+                        { "symbol": "BTC/USDT", "direction": "Long", "quantity": "0.1",
+                          "entry_price": "50000", "stop_loss": "49000" },
+                        { "symbol": "ETH/USDT", "direction": "Long", "quantity": "1",
+                          "entry_price": "3000", "stop_loss": "2850" },
+                        { "symbol": "SOL/USDT", "direction": "Short", "quantity": "10",
+                          "entry_price": "100", "stop_loss": "105" },
+                    ],
+                })
+                .to_string(),
+            ))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let import = &body["data"];
+
+        let decimal = |value: &serde_json::Value| value.as_str().unwrap().parse::<Decimal>().unwrap();
+        let risks: Vec<Decimal> = import["positions"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|position| decimal(&position["risk_amount"]))
+            .collect();
+        assert_eq!(risks, [dec!(100), dec!(150), dec!(50)]);
+        assert_eq!(decimal(&import["total_portfolio_risk"]), dec!(0.03));
+        assert_eq!(import["open_positions"], 3);
+        assert_eq!(import["warnings"], serde_json::json!([]));
+
+        let status = state.protocol_status("trader-1").await.unwrap();
+        assert_eq!(status.total_portfolio_risk, dec!(0.03));
+        assert_eq!(status.open_positions, 3);
+        assert_eq!(state.open_positions("trader-1").len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_import_warnings_use_the_enforced_limits() {
+        let mut configuration = UserConfiguration::for_profile(RiskProfile::Standard);
+        configuration.protocol_limits.hard_limit_buffer = Some(dec!(0.95));
+        configuration.protocol_limits.max_trade_loss_amount = Some(dec!(500));
+        configuration.protocol_limits.max_total_portfolio_risk_amount = Some(dec!(500));
+        let state = Arc::new(ApiState::new());
+        state.set_configuration("trader-1", configuration);
+        let app = routes::<Arc<ApiState>>()
+            .layer(Extension(auth_context("trader-1")))
+            .with_state(state.clone());
+
+        // $580 at risk on $10,000 equity: under the raw 6% and 10% limits,
+        // above the buffered 5.7% and the $475 and $500 caps
+        let request = Request::post("/positions/import")
+            .header("content-type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "account_equity": "10000",
+                    "positions": [
+                        { "symbol": "BTC/USDT", "direction": "Long", "quantity": "0.58",
+                          "entry_price": "50000", "stop_loss": "49000" },
+                    ],
+                })
+                .to_string(),
+            ))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let warnings: Vec<&str> = body["data"]["warnings"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|warning| warning.as_str().unwrap())
+            .collect();
+        assert_eq!(warnings.len(), 3, "{:?}", warnings);
+        assert!(warnings[0].contains("above the 5.70% per-trade limit"));
+        assert!(warnings[1].contains("above the 475.00 per-trade loss cap"));
+        assert!(warnings[2].contains("above the 5.00% limit"));
+    }
+
+    #[tokio::test]
+    async fn test_imported_risk_is_enforced_on_later_trades() {
+        let exchange = Arc::new(MockExchange::new());
+        let protocol = RiskManagementProtocol::new().add_rule(MaxTradeRiskRule::new());
+        let controller = Arc::new(OodaController::new(Arc::new(OodaLoop::with_all_components(
+            exchange.clone(),
+            Arc::new(RiskDecider::new(Arc::new(protocol))),
+        ))));
+        let state = Arc::new(ApiState::new().with_trading_controller(controller, 1));
+        let app = routes::<Arc<ApiState>>()
+            .layer(Extension(auth_context("trader-1")))
+            .with_state(state.clone());
+
+        // $900 at risk on $10,000 equity leaves less than the 2% a new trade needs
+        let position_id = Uuid::new_v4();
+        let import = serde_json::json!({
+            "account_equity": "10000",
+            "positions": [
+                { "symbol": "ETH/USDT", "direction": "Long", "quantity": "6",
+                  "entry_price": "3000", "stop_loss": "2850", "position_id": position_id },
+            ],
+        })
+        .to_string();
+        for _ in 0..2 {
+            let request = Request::post("/positions/import")
+                .header("content-type", "application/json")
+                .body(Body::from(import.clone()))
+                .unwrap();
+            let response = app.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+        // The retried import did not count the position twice
+        let status = state.protocol_status("trader-1").await.unwrap();
+        assert_eq!(status.total_portfolio_risk, dec!(0.09));
+        assert_eq!(status.open_positions, 1);
+        assert_eq!(state.open_positions("trader-1").len(), 1);
+
+        let request = Request::post("/trades/execute")
+            .header("content-type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "symbol": "BTC/USDT",
+                    "direction": "Long",
+                    "account_equity": "10000",
+                    "risk_percentage": "0.02",
+                })
+                .to_string(),
+            ))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert!(exchange.get_placed_orders().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_backfill_requires_admin_permission() {
+        let app = routes::<Arc<ApiState>>()
+            .layer(Extension(auth_context("trader-1")))
+            .with_state(Arc::new(ApiState::new()));
+        let response = app
+            .oneshot(Request::post("/admin/trades/backfill-r").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_admin_impersonation_returns_context_and_is_audited() {
+        let state = Arc::new(ApiState::new());
+        state.set_configuration(
+            "trader-2",
+            UserConfiguration::for_profile(RiskProfile::Conservative),
+        );
+        state.record_assessment(
+            "trader-2",
+            RecentAssessment {
+                symbol: "BTC/USDT".to_string(),
+                approved: false,
+                risk_assessment: "Rejected: portfolio risk limit".to_string(),
+                violations: Vec::new(),
+                failed_rules: Vec::new(),
+                rejecting_rules: vec!["MaxPortfolioRisk".to_string()],
+                assessed_at: Utc::now(),
+            },
+        );
+        {
+            // The protocol enforces a tighter position cap than the stored configuration
+            let protocol = state.protocol_for(&auth_context("trader-2")).await;
+            let mut protocol = protocol.lock().await;
+            let limits = ProtocolLimits { max_open_positions: 1, ..protocol.limits().clone() };
+            protocol.update_limits(limits);
+            protocol.track_external_position(Uuid::new_v4(), "ETH/USDT", dec!(0.01));
+        }
+
+        let impersonate = |user: AuthContext| {
+            routes::<Arc<ApiState>>()
+                .layer(Extension(user))
+                .with_state(state.clone())
+                .oneshot(Request::post("/admin/impersonate/trader-2").body(Body::empty()).unwrap())
+        };
+
+        // Without admin permission nothing is returned or audited
+        let response = impersonate(auth_context("trader-1")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert!(state.audit_events().is_empty());
+
+        let admin = AuthContext {
+            permissions: vec![ADMIN_PERMISSION.to_string()],
+            ..auth_context("support-1")
+        };
+        let response = impersonate(admin).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let context = &body["data"];
+        assert_eq!(context["viewed_by"], "support-1");
+        assert_eq!(context["config"]["user_id"], "trader-2");
+        assert_eq!(context["config"]["risk_profile"], "conservative");
+        assert_eq!(context["recent_assessments"][0]["approved"], false);
+        assert_eq!(context["protocol_status"]["open_positions"], 1);
+        let open_positions_rule = context["effective_rules"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|rule| rule["name"] == "MaxOpenPositions")
+            .unwrap();
+        assert_eq!(open_positions_rule["parameters"]["max_open_positions"], "1");
+
+        let events = state.audit_events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type, "ADMIN_IMPERSONATION");
+        assert_eq!(events[0].metadata.as_ref().unwrap()["admin_user_id"], "support-1");
+        assert_eq!(events[0].metadata.as_ref().unwrap()["target_user_id"], "trader-2");
+    }
+
+    #[tokio::test]
+    async fn test_slow_trade_execution_times_out_and_releases_slot() {
+        // Market data takes far longer than the trade execution timeout
+        let exchange = Arc::new(MockExchange::new());
+        exchange.set_response_delay(Duration::from_secs(30)).await;
+
+        let decider = Arc::new(RiskDecider::new(Arc::new(RiskManagementProtocol::new())));
+        let controller = Arc::new(OodaController::new(Arc::new(OodaLoop::with_all_components(
+            exchange, decider,
+        ))));
+        let state = Arc::new(ApiState::new().with_trading_controller(controller, 1));
+
+        let timeouts = RequestTimeouts {
+            read: Duration::from_millis(50),
+            trade_execution: Duration::from_millis(100),
+        };
+        let app = routes_with_timeouts::<Arc<ApiState>>(timeouts)
+            .layer(Extension(auth_context("trader-1")))
+            .with_state(state.clone());
+
+        let request = Request::post("/trades/execute")
+            .header("content-type", "application/json")
+            .body(Body::from(
+                serde_json::json!({
+                    "symbol": "BTC/USDT",
+                    "direction": "Long",
+                    "account_equity": "10000",
+                    "risk_percentage": "0.02",
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error"], "Request timed out after 100ms");
+
+        // The cancelled cycle gives up its slot well before its market data arrives
+        tokio::time::timeout(Duration::from_secs(1), async {
+            while state.available_cycle_slots() < state.max_concurrent_cycles() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_trade_timing_out_during_act_still_completes_for_its_retry() {
+        // Market data is immediate but the exchange sits on the order
+        let exchange = Arc::new(MockExchange::new());
+        exchange.set_order_delay(Duration::from_millis(300)).await;
+
+        let decider = Arc::new(RiskDecider::new(Arc::new(
+            RiskManagementProtocol::new().add_rule(MaxTradeRiskRule::new()),
+        )));
+        let controller = Arc::new(OodaController::new(Arc::new(OodaLoop::with_all_components(
+            exchange.clone(),
+            decider,
+        ))));
+        let state = Arc::new(ApiState::new().with_trading_controller(controller, 1));
+
+        let timeouts = RequestTimeouts {
+            read: Duration::from_millis(50),
+            trade_execution: Duration::from_millis(100),
+        };
+        let app = routes_with_timeouts::<Arc<ApiState>>(timeouts)
+            .layer(Extension(auth_context("trader-1")))
+            .with_state(state.clone());
+        let execute = || {
+            let request = Request::post("/trades/execute")
+                .header("content-type", "application/json")
+                .header(IDEMPOTENCY_KEY_HEADER, "order-1")
+                .body(Body::from(
+                    serde_json::json!({
+                        "symbol": "BTC/USDT",
+                        "direction": "Long",
+                        "account_equity": "10000",
+                        "risk_percentage": "0.02",
+                    })
+                    .to_string(),
+                ))
+                .unwrap();
+            let app = app.clone();
+            async move {
+                let response = app.oneshot(request).await.unwrap();
+                let status = response.status();
+                let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+                (status, serde_json::from_slice::<serde_json::Value>(&body).unwrap())
+            }
+        };
+
+        let (status, body) = execute().await;
+        assert_eq!(status, StatusCode::GATEWAY_TIMEOUT, "{}", body);
+
+        // The order is still being placed, so a retry cannot start another
+        let (status, body) = execute().await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert!(body["error"].as_str().unwrap().contains("still in progress"));
+
+        while state.available_cycle_slots() < state.max_concurrent_cycles() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        // Once the cycle finishes, the retry replays the trade it placed
+        let (status, body) = execute().await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(exchange.get_submitted_orders().await.len(), 1);
+
+        let position_id: Uuid = body["data"]["position_id"].as_str().unwrap().parse().unwrap();
+        assert!(state.positions().position("trader-1", position_id).await.is_some());
+        let protocol = state.protocol_for(&auth_context("trader-1")).await;
+        let protocol = protocol.lock().await;
+        assert!(protocol.tracked_position(position_id).is_some());
+        assert_eq!(protocol.reserved_risk(), Decimal::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_full_cycle_queue_rejects_further_trades() {
+        // Market data never arrives in time, so the first cycle stays in flight
+        let exchange = Arc::new(MockExchange::new());
+        exchange.set_response_delay(Duration::from_secs(30)).await;
+
+        let decider = Arc::new(RiskDecider::new(Arc::new(RiskManagementProtocol::new())));
+        let controller = Arc::new(OodaController::new(Arc::new(OodaLoop::with_all_components(
+            exchange, decider,
+        ))));
+        let state = Arc::new(
+            ApiState::new()
+                .with_trading_controller(controller, 1)
+                .with_max_pending_cycles(1),
+        );
+        let app = routes::<Arc<ApiState>>()
+            .layer(Extension(auth_context("trader-1")))
+            .with_state(state.clone());
+        let execute = || {
+            let request = Request::post("/trades/execute")
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    serde_json::json!({
+                        "symbol": "BTC/USDT",
+                        "direction": "Long",
+                        "account_equity": "10000",
+                        "risk_percentage": "0.02",
+                    })
+                    .to_string(),
+                ))
+                .unwrap();
+            tokio::spawn(app.clone().oneshot(request))
+        };
+
+        let in_flight = execute();
+        while state.available_cycle_slots() > 0 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let queued = execute();
+        while state.pending_cycles("trader-1") == 0 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        let response = execute().await.unwrap().unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            body["error"],
+            "Too many in-flight trades: 1 already waiting for an OODA cycle slot"
+        );
+
+        // A cancelled request gives up its place in the queue
+        queued.abort();
+        let _ = queued.await;
+        assert_eq!(state.pending_cycles("trader-1"), 0);
+        in_flight.abort();
+    }
+}
@@ -14,7 +14,7 @@
 use anyhow::{anyhow, Result};
 use async_trait::async_trait;
 use axum::{
-    extract::{FromRequestParts, Path, Query, State},
+    extract::{FromRequestParts, Path, Query, Request, State},
     http::{header, request::Parts, HeaderValue, StatusCode},
     middleware::Next,
     response::{IntoResponse, Redirect, Response},
@@ -41,6 +41,8 @@ use tracing::{error, info, warn, debug};
 use url::Url;
 use uuid::Uuid;
 
+use crate::websocket::ConnectionManager;
+
 /// Authentication errors with recovery guidance per SOP-003
 #[derive(Error, Debug)]
 pub enum AuthError {
@@ -53,6 +55,9 @@ pub enum AuthError {
     #[error("JWKS refresh failed: {0}")]
     JwksRefreshFailed(String),
     
+    #[error("Cached JWKS exceeded maximum age: {0}")]
+    JwksExpired(String),
+    
     #[error("User session not found or expired")]
     SessionNotFound,
     
@@ -74,6 +79,7 @@ impl IntoResponse for AuthError {
             AuthError::SessionNotFound => (StatusCode::UNAUTHORIZED, "Session expired"),
             AuthError::InsufficientPermissions => (StatusCode::FORBIDDEN, "Insufficient permissions"),
             AuthError::ServiceUnavailable => (StatusCode::SERVICE_UNAVAILABLE, "Service temporarily unavailable"),
+            AuthError::JwksExpired(_) => (StatusCode::SERVICE_UNAVAILABLE, "Authentication service unavailable"),
             _ => (StatusCode::INTERNAL_SERVER_ERROR, "Authentication error"),
         };
         
@@ -143,14 +149,25 @@ pub struct OidcConfig {
     pub client_secret: String,
     pub redirect_uri: String,
     pub scope: String,
+    /// Oldest cached JWKS that may still be used when refreshes keep failing
+    pub jwks_max_age: std::time::Duration,
 }
 
+/// Interval between soft JWKS refreshes per SOP-003
+const JWKS_REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(300);
+
+/// Default hard limit on cached JWKS age
+pub const DEFAULT_JWKS_MAX_AGE: std::time::Duration = std::time::Duration::from_secs(3600);
+
 /// OIDC token validator with automatic JWKS refresh per SOP-003
 pub struct OidcValidator {
     config: OidcConfig,
     discovery: OidcDiscovery,
     jwks: Arc<RwLock<JwkSet>>,
     jwks_last_refresh: Arc<RwLock<Instant>>,
+    /// Start of the latest refresh attempt; held while a refresh is in flight
+    /// so concurrent validators wait for it instead of starting their own
+    jwks_refresh_attempt: tokio::sync::Mutex<Instant>,
     http_client: Client,
 }
 
@@ -184,23 +201,39 @@ impl OidcValidator {
         
         info!("Initial JWKS loaded with {} keys", jwks.keys.len());
         
-        Ok(Self {
+        Ok(Self::from_discovery(config, discovery, jwks, http_client))
+    }
+    
+    fn from_discovery(config: OidcConfig, discovery: OidcDiscovery, jwks: JwkSet, http_client: Client) -> Self {
+        Self {
             config,
             discovery,
             jwks: Arc::new(RwLock::new(jwks)),
             jwks_last_refresh: Arc::new(RwLock::new(Instant::now())),
+            jwks_refresh_attempt: tokio::sync::Mutex::new(Instant::now()),
             http_client,
-        })
+        }
     }
     
     /// Validate a JWT token and extract user claims
     pub async fn validate_token(&self, token: &str) -> Result<UserClaims, AuthError> {
         // Check if JWKS needs refresh (refresh every 5 minutes per SOP-003)
         if self.needs_jwks_refresh() {
-            if let Err(e) = self.refresh_jwks().await {
-                warn!("JWKS refresh failed, using cached keys: {}", e);
-                // Continue with cached keys per SOP-003 graceful degradation
-            }
+            self.refresh_jwks_once().await;
+        }
+        
+        // Graceful degradation ends once the cache is too old to trust
+        let jwks_age = self.jwks_last_refresh.read().unwrap().elapsed();
+        if jwks_age > self.config.jwks_max_age {
+            error!(
+                "Cached JWKS is {}s old, exceeding maximum age of {}s; rejecting tokens",
+                jwks_age.as_secs(),
+                self.config.jwks_max_age.as_secs()
+            );
+            return Err(AuthError::JwksExpired(format!(
+                "last refreshed {}s ago",
+                jwks_age.as_secs()
+            )));
         }
         
         // Decode token header to get key ID
@@ -248,10 +281,29 @@ impl OidcValidator {
         Ok(token_data.claims)
     }
     
-    /// Check if JWKS needs refresh (every 5 minutes)
+    /// Check if JWKS needs refresh (every 5 minutes, or sooner if the max age is shorter)
     fn needs_jwks_refresh(&self) -> bool {
         let last_refresh = *self.jwks_last_refresh.read().unwrap();
-        last_refresh.elapsed() > std::time::Duration::from_secs(300)
+        last_refresh.elapsed() > JWKS_REFRESH_INTERVAL.min(self.config.jwks_max_age)
+    }
+    
+    /// Refresh JWKS unless another validator already did while we waited
+    ///
+    /// Only one refresh runs at a time. Validators queued behind it reuse its
+    /// outcome, successful or not, so an expired cache costs the provider a
+    /// single request however many tokens arrive at once.
+    async fn refresh_jwks_once(&self) {
+        let waiting_since = Instant::now();
+        let mut last_attempt = self.jwks_refresh_attempt.lock().await;
+        if *last_attempt >= waiting_since || !self.needs_jwks_refresh() {
+            return;
+        }
+        
+        *last_attempt = Instant::now();
+        if let Err(e) = self.refresh_jwks().await {
+            warn!("JWKS refresh failed, using cached keys: {}", e);
+            // Continue with cached keys per SOP-003 graceful degradation
+        }
     }
     
     /// Refresh JWKS from the provider
@@ -304,8 +356,17 @@ impl OidcValidator {
 }
 
 /// Session manager for handling user sessions in Redis
+///
+/// By default a user may hold several sessions at once. In single-session
+/// mode, each login evicts the user's previous session, found through the
+/// `user:{id}:session` mapping, and closes the WebSocket connections it
+/// opened.
 pub struct SessionManager {
     redis_pool: redis::Client,
+    /// Evict a user's previous session on each new login
+    single_session: bool,
+    /// Live connections to close when their session is evicted
+    connections: Option<Arc<ConnectionManager>>,
 }
 
 impl SessionManager {
@@ -313,10 +374,29 @@ impl SessionManager {
         let redis_pool = redis::Client::open(redis_url)
             .map_err(|e| anyhow!("Failed to connect to Redis: {}", e))?;
         
-        Ok(Self { redis_pool })
+        Ok(Self {
+            redis_pool,
+            single_session: false,
+            connections: None,
+        })
+    }
+    
+    /// Allow each user only one active session, ending the prior one on login
+    pub fn with_single_session(mut self, enabled: bool) -> Self {
+        self.single_session = enabled;
+        self
+    }
+    
+    /// Close WebSocket connections of evicted sessions through `connections`
+    pub fn with_connections(mut self, connections: Arc<ConnectionManager>) -> Self {
+        self.connections = Some(connections);
+        self
     }
     
     /// Create a new session for authenticated user
+    ///
+    /// In single-session mode the user's previous session is deleted and its
+    /// WebSocket connections closed before the new one is recorded.
     pub async fn create_session(&self, claims: &UserClaims) -> Result<UserSession, AuthError> {
         let session_id = Uuid::new_v4().to_string();
         let now = Utc::now();
@@ -353,6 +433,16 @@ impl SessionManager {
         
         // Also store user ID mapping for quick lookups
         let user_key = format!("user:{}:session", claims.sub);
+        if self.single_session {
+            let previous: Option<String> = redis::cmd("GET")
+                .arg(&user_key)
+                .query_async(&mut conn)
+                .await
+                .map_err(|_| AuthError::ServiceUnavailable)?;
+            if let Some(previous) = previous {
+                self.evict_session(&claims.sub, &previous).await?;
+            }
+        }
         redis::cmd("SET")
             .arg(&user_key)
             .arg(&session_id)
@@ -420,6 +510,20 @@ impl SessionManager {
         Ok(())
     }
     
+    /// End a session superseded by a newer login, closing its connections
+    async fn evict_session(&self, user_id: &str, session_id: &str) -> Result<(), AuthError> {
+        self.delete_session(session_id).await?;
+        let closed = self
+            .connections
+            .as_ref()
+            .map_or(0, |connections| connections.close_session(session_id));
+        info!(
+            "Single-session mode: evicted session {} for user {} ({} connection(s) closed)",
+            session_id, user_id, closed
+        );
+        Ok(())
+    }
+    
     /// Delete a session
     pub async fn delete_session(&self, session_id: &str) -> Result<(), AuthError> {
         let mut conn = self.redis_pool
@@ -438,6 +542,13 @@ impl SessionManager {
     }
 }
 
+/// Header API clients name their login session in; browsers send the
+/// `session_id` cookie set at login instead
+pub const SESSION_ID_HEADER: &str = "x-session-id";
+
+/// Cookie the OAuth callback stores the session ID in
+const SESSION_COOKIE: &str = "session_id";
+
 /// Authentication middleware with SOP-003 recovery procedures
 pub struct AuthMiddleware {
     oidc_validator: Arc<OidcValidator>,
@@ -468,10 +579,32 @@ impl AuthMiddleware {
         Ok(auth_header[7..].to_string())
     }
     
+    /// Extract the login session the request was made under
+    fn extract_session_id(parts: &Parts) -> Result<String, AuthError> {
+        if let Some(value) = parts.headers.get(SESSION_ID_HEADER) {
+            return value
+                .to_str()
+                .map(str::to_string)
+                .map_err(|_| AuthError::InvalidToken("Invalid X-Session-Id header".to_string()));
+        }
+        
+        parts
+            .headers
+            .get_all(header::COOKIE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|cookies| cookies.split(';'))
+            .filter_map(|cookie| cookie.trim().split_once('='))
+            .find(|(name, value)| *name == SESSION_COOKIE && !value.is_empty())
+            .map(|(_, value)| value.to_string())
+            .ok_or(AuthError::SessionNotFound)
+    }
+    
     /// Validate authentication and create context
     pub async fn validate_request(&self, parts: &Parts) -> Result<AuthContext, AuthError> {
         // Extract token from Authorization header
         let token = Self::extract_bearer_token(parts)?;
+        let session_id = Self::extract_session_id(parts)?;
         
         // Validate token with OIDC provider (with SOP-003 recovery)
         let claims = match self.oidc_validator.validate_token(&token).await {
@@ -479,13 +612,13 @@ impl AuthMiddleware {
             Err(AuthError::ProviderUnreachable(_)) => {
                 warn!("OIDC provider unreachable, attempting session-only validation");
                 // SOP-003: Fallback to session validation if OIDC provider is down
-                return self.validate_with_session_fallback(&token).await;
+                return self.validate_with_session_fallback(&token, &session_id).await;
             }
             Err(e) => return Err(e),
         };
         
-        // Verify session exists and is valid
-        let session = self.verify_user_session(&claims.sub).await?;
+        // Verify the request's own session exists and belongs to the token's user
+        let session = self.verify_user_session(&claims.sub, &session_id).await?;
         
         // Update session activity
         if let Err(e) = self.session_manager.update_session_activity(&session.session_id).await {
@@ -503,7 +636,7 @@ impl AuthMiddleware {
     }
     
     /// Fallback validation using session only (SOP-003 recovery)
-    async fn validate_with_session_fallback(&self, token: &str) -> Result<AuthContext, AuthError> {
+    async fn validate_with_session_fallback(&self, token: &str, session_id: &str) -> Result<AuthContext, AuthError> {
         warn!("Using session fallback validation due to OIDC provider issues");
         
         // Try to decode token without verification (risky but necessary for recovery)
@@ -520,7 +653,7 @@ impl AuthMiddleware {
         };
         
         // Verify session exists
-        let session = self.verify_user_session(&claims.sub).await?;
+        let session = self.verify_user_session(&claims.sub, session_id).await?;
         
         // In fallback mode, use session data as authoritative
         Ok(AuthContext {
@@ -532,23 +665,36 @@ impl AuthMiddleware {
         })
     }
     
-    /// Verify user has valid session
-    async fn verify_user_session(&self, user_id: &str) -> Result<UserSession, AuthError> {
-        // Get session ID from user mapping
-        let mut conn = self.session_manager.redis_pool
-            .get_async_connection()
-            .await
-            .map_err(|_| AuthError::ServiceUnavailable)?;
-        
-        let user_key = format!("user:{}:session", user_id);
-        let session_id: Option<String> = redis::cmd("GET")
-            .arg(&user_key)
-            .query_async(&mut conn)
-            .await
-            .map_err(|_| AuthError::ServiceUnavailable)?;
-        
-        let session_id = session_id.ok_or(AuthError::SessionNotFound)?;
-        self.session_manager.get_session(&session_id).await
+    /// Verify `session_id` is a live session of `user_id`
+    ///
+    /// A user may hold several sessions, so each request is checked against
+    /// the session it names rather than the user's latest login.
+    async fn verify_user_session(&self, user_id: &str, session_id: &str) -> Result<UserSession, AuthError> {
+        let session = self.session_manager.get_session(session_id).await?;
+        if session.user_id != user_id {
+            warn!("Session {} does not belong to user {}", session_id, user_id);
+            return Err(AuthError::SessionNotFound);
+        }
+        Ok(session)
+    }
+}
+
+/// Require an authenticated user, storing the `AuthContext` for handlers
+///
+/// Use with `axum::middleware::from_fn_with_state`. Requests without a valid
+/// bearer token and session are rejected before reaching the handler.
+pub async fn require_auth(
+    State(auth_service): State<Arc<AuthService>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let (mut parts, body) = request.into_parts();
+    match auth_service.authenticate(&parts).await {
+        Ok(auth_context) => {
+            parts.extensions.insert(auth_context);
+            next.run(Request::from_parts(parts, body)).await
+        }
+        Err(e) => e.into_response(),
     }
 }
 
@@ -560,10 +706,14 @@ where
 {
     type Rejection = AuthError;
 
-    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
-        // This will be implemented when we add the middleware to the router
-        // For now, return an error
-        Err(AuthError::ServiceUnavailable)
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        // The authentication middleware validates the request and stores the
+        // resulting context in the request extensions
+        parts
+            .extensions
+            .get::<AuthContext>()
+            .cloned()
+            .ok_or_else(|| AuthError::InvalidToken("Request is not authenticated".to_string()))
     }
 }
 
@@ -579,6 +729,39 @@ pub struct AuthService {
     oidc_validator: Arc<OidcValidator>,
     session_manager: Arc<SessionManager>,
     http_client: Client,
+    token_exchange: TokenExchangePolicy,
+}
+
+/// Timeout and retry policy for exchanging an authorization code for tokens
+///
+/// Timeouts, connection failures, 429s and 5xx responses are retried with
+/// exponential backoff; any other 4xx is terminal and fails at once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenExchangePolicy {
+    /// Limit on each attempt, including reading the response
+    pub timeout: std::time::Duration,
+    /// Attempts allowed after the first
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled for each one after
+    pub initial_backoff: std::time::Duration,
+}
+
+impl Default for TokenExchangePolicy {
+    fn default() -> Self {
+        Self {
+            timeout: std::time::Duration::from_secs(10),
+            max_retries: 2,
+            initial_backoff: std::time::Duration::from_millis(250),
+        }
+    }
+}
+
+/// Outcome of a single token-exchange attempt that did not succeed
+enum TokenExchangeFailure {
+    /// Transient; another attempt may succeed
+    Retryable(String),
+    /// The provider refused the request; retrying will not help
+    Terminal(AuthError),
 }
 
 impl AuthService {
@@ -587,9 +770,22 @@ impl AuthService {
             oidc_validator,
             session_manager,
             http_client: Client::new(),
+            token_exchange: TokenExchangePolicy::default(),
         }
     }
     
+    pub fn with_token_exchange_policy(mut self, policy: TokenExchangePolicy) -> Self {
+        self.token_exchange = policy;
+        self
+    }
+    
+    /// Authenticate a request from its bearer token and session
+    pub async fn authenticate(&self, parts: &Parts) -> Result<AuthContext, AuthError> {
+        AuthMiddleware::new(self.oidc_validator.clone(), self.session_manager.clone())
+            .validate_request(parts)
+            .await
+    }
+    
     /// Generate OAuth authorization URL
     pub fn get_authorization_url(&self, state: &str) -> String {
         let mut auth_url = Url::parse(&self.oidc_validator.discovery.authorization_endpoint)
@@ -622,8 +818,42 @@ impl AuthService {
         Ok(session)
     }
     
-    /// Exchange authorization code for access token
+    /// Exchange authorization code for access token, retrying transient failures
     async fn exchange_code_for_tokens(&self, code: &str) -> Result<TokenResponse, AuthError> {
+        let policy = self.token_exchange;
+        let mut backoff = policy.initial_backoff;
+        let mut attempt = 0;
+        loop {
+            let failure = match tokio::time::timeout(policy.timeout, self.request_tokens(code)).await {
+                Ok(Ok(tokens)) => return Ok(tokens),
+                Ok(Err(failure)) => failure,
+                Err(_) => TokenExchangeFailure::Retryable(format!(
+                    "timed out after {}ms",
+                    policy.timeout.as_millis()
+                )),
+            };
+            
+            let reason = match failure {
+                TokenExchangeFailure::Terminal(error) => return Err(error),
+                TokenExchangeFailure::Retryable(reason) => reason,
+            };
+            if attempt >= policy.max_retries {
+                return Err(AuthError::ProviderUnreachable(format!(
+                    "Token exchange failed after {} attempt(s): {}",
+                    attempt + 1,
+                    reason
+                )));
+            }
+            
+            attempt += 1;
+            warn!("Token exchange attempt {} failed ({}); retrying in {}ms", attempt, reason, backoff.as_millis());
+            tokio::time::sleep(backoff).await;
+            backoff *= 2;
+        }
+    }
+    
+    /// A single POST to the provider's token endpoint
+    async fn request_tokens(&self, code: &str) -> Result<TokenResponse, TokenExchangeFailure> {
         let params = [
             ("client_id", self.oidc_validator.config.client_id.as_str()),
             ("client_secret", self.oidc_validator.config.client_secret.as_str()),
@@ -637,16 +867,26 @@ impl AuthService {
             .form(&params)
             .send()
             .await
-            .map_err(|e| AuthError::ProviderUnreachable(format!("Token exchange failed: {}", e)))?;
+            .map_err(|e| TokenExchangeFailure::Retryable(e.to_string()))?;
         
-        if !response.status().is_success() {
+        let status = response.status();
+        if status.is_server_error() || status.as_u16() == StatusCode::TOO_MANY_REQUESTS.as_u16() {
+            return Err(TokenExchangeFailure::Retryable(format!("provider returned {}", status)));
+        }
+        if !status.is_success() {
             let error_text = response.text().await.unwrap_or_default();
-            return Err(AuthError::InvalidToken(format!("Token exchange failed: {}", error_text)));
+            return Err(TokenExchangeFailure::Terminal(AuthError::InvalidToken(format!(
+                "Token exchange failed: {}",
+                error_text
+            ))));
         }
         
         response.json::<TokenResponse>()
             .await
-            .map_err(|e| AuthError::InvalidToken(format!("Failed to parse token response: {}", e)))
+            .map_err(|e| TokenExchangeFailure::Terminal(AuthError::InvalidToken(format!(
+                "Failed to parse token response: {}",
+                e
+            ))))
     }
 }
 
@@ -745,14 +985,28 @@ pub struct AuthState {
 
 impl AuthState {
     /// Initialize authentication state (async because of OIDC discovery)
+    ///
+    /// With `single_session`, each login ends the user's previous session
+    /// and closes its WebSocket connections in `connections`. Authorization
+    /// codes are exchanged for tokens under `token_exchange`.
     pub async fn new(
         oidc_config: OidcConfig,
         redis_url: &str,
+        single_session: bool,
+        connections: Arc<ConnectionManager>,
+        token_exchange: TokenExchangePolicy,
     ) -> Result<Self> {
-        let session_manager = Arc::new(SessionManager::new(redis_url)?);
+        let session_manager = Arc::new(
+            SessionManager::new(redis_url)?
+                .with_single_session(single_session)
+                .with_connections(connections),
+        );
         let oidc_validator = Arc::new(OidcValidator::new(oidc_config).await?);
         
-        let auth_service = Arc::new(AuthService::new(oidc_validator.clone(), session_manager.clone()));
+        let auth_service = Arc::new(
+            AuthService::new(oidc_validator.clone(), session_manager.clone())
+                .with_token_exchange_policy(token_exchange),
+        );
         let auth_middleware = Arc::new(AuthMiddleware::new(oidc_validator, session_manager));
         
         Ok(Self {
@@ -765,9 +1019,8 @@ impl AuthState {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use tokio::test;
     
-    #[test]
+    #[tokio::test]
     async fn test_oidc_config_creation() {
         let config = OidcConfig {
             provider_url: "http://localhost:8080/realms/testudo".to_string(),
@@ -775,6 +1028,7 @@ mod tests {
             client_secret: "test-secret".to_string(),
             redirect_uri: "http://localhost:3000/auth/callback".to_string(),
             scope: "openid profile email".to_string(),
+            jwks_max_age: DEFAULT_JWKS_MAX_AGE,
         };
         
         assert_eq!(config.client_id, "testudo-frontend");
@@ -817,4 +1071,268 @@ mod tests {
         assert_eq!(parsed.email, "test@example.com");
         assert_eq!(parsed.permissions.len(), 2);
     }
-}
\ No newline at end of file
+    
+    #[tokio::test]
+    async fn test_stale_jwks_hard_fails_while_refresh_is_failing() {
+        let config = OidcConfig {
+            provider_url: "http://127.0.0.1:1/realms/testudo".to_string(),
+            client_id: "testudo-frontend".to_string(),
+            client_secret: "test-secret".to_string(),
+            redirect_uri: "http://localhost:3000/auth/callback".to_string(),
+            scope: "openid profile email".to_string(),
+            jwks_max_age: std::time::Duration::from_millis(50),
+        };
+        // Nothing listens on port 1, so every refresh fails
+        let discovery = OidcDiscovery {
+            issuer: "http://127.0.0.1:1/realms/testudo".to_string(),
+            authorization_endpoint: "http://127.0.0.1:1/auth".to_string(),
+            token_endpoint: "http://127.0.0.1:1/token".to_string(),
+            userinfo_endpoint: "http://127.0.0.1:1/userinfo".to_string(),
+            jwks_uri: "http://127.0.0.1:1/certs".to_string(),
+            end_session_endpoint: None,
+        };
+        let validator = OidcValidator::from_discovery(config, discovery, JwkSet { keys: vec![] }, Client::new());
+        
+        // Fresh cache: the token itself is what gets rejected
+        let result = validator.validate_token("not-a-jwt").await;
+        assert!(matches!(result, Err(AuthError::InvalidToken(_))));
+        
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        
+        let result = validator.validate_token("not-a-jwt").await;
+        assert!(matches!(result, Err(AuthError::JwksExpired(_))));
+    }
+    
+    #[tokio::test]
+    async fn test_concurrent_validations_share_one_jwks_refresh() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        
+        // Slow JWKS endpoint counting how often it is fetched
+        let refreshes = Arc::new(AtomicUsize::new(0));
+        let counter = refreshes.clone();
+        let provider = Router::new().route("/certs", get(move || {
+            let counter = counter.clone();
+            async move {
+                counter.fetch_add(1, Ordering::SeqCst);
+                tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+                Json(serde_json::json!({ "keys": [] }))
+            }
+        }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let provider_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, provider).await.unwrap() });
+        
+        let config = OidcConfig {
+            provider_url: provider_url.clone(),
+            client_id: "testudo-frontend".to_string(),
+            client_secret: "test-secret".to_string(),
+            redirect_uri: "http://localhost:3000/auth/callback".to_string(),
+            scope: "openid profile email".to_string(),
+            jwks_max_age: std::time::Duration::from_millis(50),
+        };
+        let discovery = OidcDiscovery {
+            issuer: provider_url.clone(),
+            authorization_endpoint: format!("{}/auth", provider_url),
+            token_endpoint: format!("{}/token", provider_url),
+            userinfo_endpoint: format!("{}/userinfo", provider_url),
+            jwks_uri: format!("{}/certs", provider_url),
+            end_session_endpoint: None,
+        };
+        let validator = OidcValidator::from_discovery(config, discovery, JwkSet { keys: vec![] }, Client::new());
+        
+        // Let the cache expire, then validate many tokens at once
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        let results = futures::future::join_all(
+            (0..16).map(|_| validator.validate_token("not-a-jwt")),
+        )
+        .await;
+        
+        assert_eq!(refreshes.load(Ordering::SeqCst), 1);
+        // Every validator saw the refreshed cache rather than the expired one
+        assert!(results.iter().all(|result| matches!(result, Err(AuthError::InvalidToken(_)))));
+    }
+    
+    /// Auth service whose token endpoint is served by `provider`, counting calls
+    async fn service_with_token_endpoint(provider: Router) -> AuthService {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let provider_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, provider).await.unwrap() });
+        
+        let config = OidcConfig {
+            provider_url: provider_url.clone(),
+            client_id: "testudo-frontend".to_string(),
+            client_secret: "test-secret".to_string(),
+            redirect_uri: "http://localhost:3000/auth/callback".to_string(),
+            scope: "openid profile email".to_string(),
+            jwks_max_age: DEFAULT_JWKS_MAX_AGE,
+        };
+        let discovery = OidcDiscovery {
+            issuer: provider_url.clone(),
+            authorization_endpoint: format!("{}/auth", provider_url),
+            token_endpoint: format!("{}/token", provider_url),
+            userinfo_endpoint: format!("{}/userinfo", provider_url),
+            jwks_uri: format!("{}/certs", provider_url),
+            end_session_endpoint: None,
+        };
+        let validator = OidcValidator::from_discovery(config, discovery, JwkSet { keys: vec![] }, Client::new());
+        AuthService::new(Arc::new(validator), Arc::new(SessionManager::new("redis://127.0.0.1:1").unwrap()))
+            .with_token_exchange_policy(TokenExchangePolicy {
+                timeout: std::time::Duration::from_millis(50),
+                max_retries: 2,
+                initial_backoff: std::time::Duration::from_millis(1),
+            })
+    }
+    
+    #[tokio::test]
+    async fn test_token_exchange_retries_after_timeout() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        
+        // The first attempt hangs past the timeout; the retry is answered
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let counter = attempts.clone();
+        let provider = Router::new().route("/token", post(move || {
+            let counter = counter.clone();
+            async move {
+                if counter.fetch_add(1, Ordering::SeqCst) == 0 {
+                    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+                }
+                Json(serde_json::json!({ "access_token": "token-2", "token_type": "Bearer" }))
+            }
+        }));
+        let service = service_with_token_endpoint(provider).await;
+        
+        let tokens = service.exchange_code_for_tokens("code-1").await.unwrap();
+        assert_eq!(tokens.access_token, "token-2");
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    }
+    
+    #[tokio::test]
+    async fn test_token_exchange_client_error_is_not_retried() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let counter = attempts.clone();
+        let provider = Router::new().route("/token", post(move || {
+            let counter = counter.clone();
+            async move {
+                counter.fetch_add(1, Ordering::SeqCst);
+                (StatusCode::BAD_REQUEST, r#"{"error":"invalid_grant"}"#)
+            }
+        }));
+        let service = service_with_token_endpoint(provider).await;
+        
+        let result = service.exchange_code_for_tokens("used-code").await;
+        assert!(matches!(result, Err(AuthError::InvalidToken(message)) if message.contains("invalid_grant")));
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+    
+    /// In-memory stand-in for Redis speaking just the RESP commands sessions use
+    async fn session_store() -> String {
+        use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+        
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("redis://{}", listener.local_addr().unwrap());
+        let store = Arc::new(std::sync::Mutex::new(HashMap::<String, String>::new()));
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                let store = store.clone();
+                tokio::spawn(async move {
+                    let (reader, mut writer) = socket.into_split();
+                    let mut reader = BufReader::new(reader);
+                    let mut line = String::new();
+                    while reader.read_line(&mut line).await.unwrap_or(0) > 0 {
+                        // Each command is an array of bulk strings: *N then N x ($len, data)
+                        let count: usize = line.trim()[1..].parse().unwrap();
+                        let mut args = Vec::with_capacity(count);
+                        for _ in 0..count {
+                            line.clear();
+                            reader.read_line(&mut line).await.unwrap();
+                            let len: usize = line.trim()[1..].parse().unwrap();
+                            let mut data = vec![0; len + 2];
+                            reader.read_exact(&mut data).await.unwrap();
+                            args.push(String::from_utf8_lossy(&data[..len]).into_owned());
+                        }
+                        line.clear();
+                        
+                        let reply = {
+                            let mut store = store.lock().unwrap();
+                            match args[0].to_uppercase().as_str() {
+                                "SET" => {
+                                    store.insert(args[1].clone(), args[2].clone());
+                                    "+OK\r\n".to_string()
+                                }
+                                "GET" => match store.get(&args[1]) {
+                                    Some(value) => format!("${}\r\n{}\r\n", value.len(), value),
+                                    None => "$-1\r\n".to_string(),
+                                },
+                                "DEL" => format!(":{}\r\n", args[1..].iter().filter(|key| store.remove(*key).is_some()).count()),
+                                _ => "+OK\r\n".to_string(),
+                            }
+                        };
+                        if writer.write_all(reply.as_bytes()).await.is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+        url
+    }
+    
+    #[tokio::test]
+    async fn test_single_session_login_evicts_previous_session_and_connections() {
+        use crate::websocket::MessageEncoding;
+        use axum::extract::ws::Message;
+        
+        let claims = UserClaims {
+            sub: "user123".to_string(),
+            email: "test@example.com".to_string(),
+            name: "Test User".to_string(),
+            iss: "http://localhost:8080/realms/testudo".to_string(),
+            aud: "testudo-frontend".to_string(),
+            exp: Utc::now().timestamp() + 3600,
+            iat: Utc::now().timestamp(),
+            jti: "token123".to_string(),
+            risk_profile: RiskProfile::Standard,
+            account_equity: None,
+            max_position_count: 5,
+            daily_loss_limit: None,
+            permissions: vec!["trade:execute".to_string()],
+        };
+        let url = session_store().await;
+        
+        // Without the option, earlier sessions stay valid
+        let sessions = SessionManager::new(&url).unwrap();
+        let first = sessions.create_session(&claims).await.unwrap();
+        sessions.create_session(&claims).await.unwrap();
+        assert!(sessions.get_session(&first.session_id).await.is_ok());
+        
+        let connections = Arc::new(ConnectionManager::new());
+        let sessions = SessionManager::new(&url)
+            .unwrap()
+            .with_single_session(true)
+            .with_connections(connections.clone());
+        let first = sessions.create_session(&claims).await.unwrap();
+        let (_, mut first_socket) =
+            connections.register_session("user123", &first.session_id, MessageEncoding::Json);
+        
+        let second = sessions.create_session(&claims).await.unwrap();
+        let (_, mut second_socket) =
+            connections.register_session("user123", &second.session_id, MessageEncoding::Json);
+        
+        assert!(matches!(
+            sessions.get_session(&first.session_id).await,
+            Err(AuthError::SessionNotFound)
+        ));
+        assert_eq!(sessions.get_session(&second.session_id).await.unwrap().user_id, "user123");
+        
+        // The first session's socket is told to close, then its stream ends
+        match first_socket.recv().await {
+            Some(Message::Close(Some(frame))) => assert_eq!(frame.code, axum::extract::ws::close_code::POLICY),
+            other => panic!("Expected a close frame, got: {:?}", other),
+        }
+        assert!(first_socket.recv().await.is_none());
+        assert_eq!(connections.connection_count(), 1);
+        assert!(second_socket.try_recv().is_err());
+    }
+}
@@ -1 +1,243 @@
-//! cache (placeholder)
+//! Position sizing result cache
+//!
+//! Clients recompute the same sizing as a trader adjusts a form, so
+//! `POST /calculator/explain` results are kept for a short TTL and served
+//! again for identical inputs. Every request is counted as a hit or a miss
+//! along with how long it took to serve, so the hit rate and latency can be
+//! watched through `/metrics` and `GET /admin/stats/calculator` while the TTL
+//! is tuned. Trades sized in the OODA orient phase are counted alongside.
+
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// How long a computed sizing is served from the cache
+pub const DEFAULT_SIZING_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Most sizing results held at once; the oldest are evicted beyond it
+pub const DEFAULT_SIZING_CACHE_CAPACITY: usize = 10_000;
+
+#[derive(Default)]
+struct Counters {
+    hits: u64,
+    misses: u64,
+    total_latency: Duration,
+}
+
+/// Cached results with their keys in the order they were stored
+///
+/// Every entry lives for the same TTL, so the oldest entries are the first
+/// to expire and can be pruned from the front without scanning the map. A
+/// key stored again after expiring appears twice in `by_age`; only the copy
+/// matching the map's timestamp evicts it.
+struct Entries<T> {
+    results: HashMap<String, (T, Instant)>,
+    by_age: VecDeque<(String, Instant)>,
+}
+
+impl<T> Entries<T> {
+    fn evict_oldest(&mut self) {
+        if let Some((key, stored_at)) = self.by_age.pop_front() {
+            if self.results.get(&key).is_some_and(|(_, current)| *current == stored_at) {
+                self.results.remove(&key);
+            }
+        }
+    }
+}
+
+/// Sizing results keyed by a fingerprint of their inputs
+pub struct SizingCache<T> {
+    ttl: Duration,
+    capacity: usize,
+    entries: Mutex<Entries<T>>,
+    counters: Mutex<Counters>,
+}
+
+impl<T: Clone> SizingCache<T> {
+    pub fn new() -> Self {
+        Self::with_ttl(DEFAULT_SIZING_CACHE_TTL)
+    }
+
+    pub fn with_ttl(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            capacity: DEFAULT_SIZING_CACHE_CAPACITY,
+            entries: Mutex::new(Entries {
+                results: HashMap::new(),
+                by_age: VecDeque::new(),
+            }),
+            counters: Mutex::new(Counters::default()),
+        }
+    }
+
+    /// Hold at most `capacity` results, evicting the oldest first
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    /// Return the cached result for `key`, computing and storing it on a miss
+    pub fn get_or_compute(&self, key: String, compute: impl FnOnce() -> T) -> T {
+        let started = Instant::now();
+        let cached = {
+            let mut entries = self.entries.lock().unwrap();
+            while entries.by_age.front().is_some_and(|(_, stored_at)| stored_at.elapsed() > self.ttl) {
+                entries.evict_oldest();
+            }
+            entries.results.get(&key).map(|(result, _)| result.clone())
+        };
+        let hit = cached.is_some();
+        let result = cached.unwrap_or_else(|| {
+            let result = compute();
+            let stored_at = Instant::now();
+            let mut entries = self.entries.lock().unwrap();
+            entries.results.insert(key.clone(), (result.clone(), stored_at));
+            entries.by_age.push_back((key, stored_at));
+            while entries.results.len() > self.capacity {
+                entries.evict_oldest();
+            }
+            result
+        });
+
+        let mut counters = self.counters.lock().unwrap();
+        if hit {
+            counters.hits += 1;
+        } else {
+            counters.misses += 1;
+        }
+        counters.total_latency += started.elapsed();
+        result
+    }
+
+    /// Counters accumulated since the cache was created
+    pub fn stats(&self) -> CalculatorStats {
+        self.stats_with_trade_sizing(0, Duration::ZERO)
+    }
+
+    /// Counters including `trade_sizings` positions sized on the trade path
+    ///
+    /// Trades are sized in the OODA orient phase rather than through this
+    /// cache; they count towards the calculations and mean latency but not
+    /// towards the hit rate.
+    pub fn stats_with_trade_sizing(&self, trade_sizings: u64, trade_latency: Duration) -> CalculatorStats {
+        let counters = self.counters.lock().unwrap();
+        let requests = counters.hits + counters.misses;
+        let calculations = requests + trade_sizings;
+        let ratio = |total: f64, count: u64| if count == 0 { 0.0 } else { total / count as f64 };
+        CalculatorStats {
+            calculations,
+            trade_sizings,
+            cache_hits: counters.hits,
+            cache_misses: counters.misses,
+            hit_rate: ratio(counters.hits as f64, requests),
+            average_latency_micros: ratio(
+                (counters.total_latency + trade_latency).as_secs_f64() * 1_000_000.0,
+                calculations,
+            ),
+            cache_entries: self.entries.lock().unwrap().results.len(),
+            cache_ttl_secs: self.ttl.as_secs(),
+        }
+    }
+}
+
+impl<T: Clone> Default for SizingCache<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Position sizing counters, as served by `GET /admin/stats/calculator`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CalculatorStats {
+    /// Sizing calculations served, cached or not, including trade sizings
+    pub calculations: u64,
+    /// Positions sized in the orient phase of an executed trade
+    pub trade_sizings: u64,
+    pub cache_hits: u64,
+    pub cache_misses: u64,
+    /// Fraction of calculator requests served from the cache, 0 before the first
+    pub hit_rate: f64,
+    /// Mean time to serve a calculation, hits and misses alike
+    pub average_latency_micros: f64,
+    /// Results currently held, including any not yet pruned after expiry
+    pub cache_entries: usize,
+    pub cache_ttl_secs: u64,
+}
+
+impl CalculatorStats {
+    /// Prometheus text exposition of the counters
+    pub fn to_prometheus(&self) -> String {
+        let metrics = [
+            ("testudo_sizing_calculations_total", "counter", "Position sizing calculations served", self.calculations as f64),
+            ("testudo_sizing_trade_calculations_total", "counter", "Positions sized while executing a trade", self.trade_sizings as f64),
+            ("testudo_sizing_cache_hits_total", "counter", "Sizing calculations served from the cache", self.cache_hits as f64),
+            ("testudo_sizing_cache_misses_total", "counter", "Sizing calculations computed afresh", self.cache_misses as f64),
+            ("testudo_sizing_cache_hit_rate", "gauge", "Fraction of sizing calculations served from the cache", self.hit_rate),
+            ("testudo_sizing_latency_average_seconds", "gauge", "Mean time to serve a sizing calculation", self.average_latency_micros / 1_000_000.0),
+            ("testudo_sizing_cache_entries", "gauge", "Sizing results currently cached", self.cache_entries as f64),
+        ];
+        metrics
+            .iter()
+            .map(|(name, kind, help, value)| prometheus_metric(name, kind, help, *value))
+            .collect()
+    }
+}
+
+/// One metric in the Prometheus text exposition format
+pub fn prometheus_metric(name: &str, kind: &str, help: &str, value: f64) -> String {
+    format!("# HELP {name} {help}\n# TYPE {name} {kind}\n{name} {value}\n")
+}
+
+/// One metric with a sample per label set, e.g. `rule="MaxTradeRisk"`
+pub fn prometheus_labelled_metric(name: &str, kind: &str, help: &str, samples: &[(String, f64)]) -> String {
+    let mut metric = format!("# HELP {name} {help}\n# TYPE {name} {kind}\n");
+    for (labels, value) in samples {
+        metric.push_str(&format!("{name}{{{labels}}} {value}\n"));
+    }
+    metric
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_evicts_oldest_results_beyond_capacity() {
+        let cache = SizingCache::new().with_capacity(2);
+        let computed = std::cell::Cell::new(0);
+        let size = |key: &str| {
+            cache.get_or_compute(key.to_string(), || {
+                computed.set(computed.get() + 1);
+                key.len()
+            })
+        };
+
+        size("a");
+        size("bb");
+        size("ccc");
+        assert_eq!(cache.stats().cache_entries, 2);
+
+        // "a" was evicted to make room, the two newest are still served
+        size("bb");
+        size("ccc");
+        assert_eq!(computed.get(), 3);
+        size("a");
+        assert_eq!(computed.get(), 4);
+        assert_eq!(cache.stats().cache_entries, 2);
+    }
+
+    #[test]
+    fn test_expired_results_are_pruned_and_recomputed() {
+        let cache = SizingCache::with_ttl(Duration::from_millis(10));
+        assert_eq!(cache.get_or_compute("a".to_string(), || 1), 1);
+        std::thread::sleep(Duration::from_millis(20));
+
+        assert_eq!(cache.get_or_compute("b".to_string(), || 2), 2);
+        assert_eq!(cache.stats().cache_entries, 1);
+        assert_eq!(cache.get_or_compute("a".to_string(), || 3), 3);
+        assert_eq!(cache.stats().cache_misses, 3);
+    }
+}
@@ -1,7 +1,392 @@
-use serde::Deserialize;
+use config::{Config, ConfigError, File, FileFormat};
+use formatio::DEFAULT_MAX_CLOCK_SKEW;
+use imperium::api::RequestTimeouts;
+use imperium::auth::{OidcConfig, TokenExchangePolicy, DEFAULT_JWKS_MAX_AGE};
+use imperium::database::DEFAULT_SLOW_QUERY_THRESHOLD;
+use imperium::middleware::DEFAULT_COMPRESSION_MIN_SIZE;
+use imperium::{AppConfig, DEFAULT_REPLAY_CAPACITY};
+use prudentia::CommissionSchedule;
+use serde::de::DeserializeOwned;
+use std::time::Duration;
+use thiserror::Error;
+use url::Url;
 
-#[derive(Debug, Deserialize, Clone)]
+/// Startup configuration failures, naming the offending key
+#[derive(Debug, Error)]
+pub enum SettingsError {
+    #[error("Failed to read configuration: {0}")]
+    Source(#[from] ConfigError),
+    #[error("Missing required setting `{key}`")]
+    MissingField { key: &'static str },
+    #[error("Invalid value for `{key}`: {reason}")]
+    InvalidValue { key: &'static str, reason: String },
+}
+
+/// Server settings validated at load time
+#[derive(Debug, Clone)]
 pub struct Settings {
+    pub server_host: String,
+    pub server_port: u16,
+    pub compression_min_size: u16,
+    pub request_timeouts: RequestTimeouts,
     pub database_url: String,
+    pub database_pool_size: u32,
+    pub slow_query_threshold: Duration,
     pub redis_url: String,
+    pub jwt_secret: String,
+    pub jwt_expiration_hours: u32,
+    /// End a user's previous session on each new login
+    pub single_session: bool,
+    pub cors_allowed_origins: Vec<String>,
+    pub rate_limit_requests_per_minute: u32,
+    pub websocket_max_connections: u32,
+    pub websocket_heartbeat_interval: u32,
+    /// Messages kept per user for clients resuming after a reconnect
+    pub websocket_replay_capacity: usize,
+    pub ooda_max_clock_skew: Duration,
+    pub oidc: OidcConfig,
+    /// Timeout and retries for exchanging authorization codes for tokens
+    pub token_exchange: TokenExchangePolicy,
+}
+
+impl Settings {
+    /// Load and validate settings from a TOML configuration file
+    pub fn new(config_file: &str) -> Result<Self, SettingsError> {
+        let config = Config::builder()
+            .add_source(File::new(config_file, FileFormat::Toml))
+            .build()?;
+        Self::from_config(&config)
+    }
+
+    fn from_config(config: &Config) -> Result<Self, SettingsError> {
+        let server_port: i64 = optional(config, "server.port", 3000)?;
+        let server_port = u16::try_from(server_port)
+            .ok()
+            .filter(|port| *port > 0)
+            .ok_or_else(|| SettingsError::InvalidValue {
+                key: "server.port",
+                reason: format!("{} is not a port between 1 and 65535", server_port),
+            })?;
+
+        let compression_min_size: i64 =
+            optional(config, "server.compression_min_size", i64::from(DEFAULT_COMPRESSION_MIN_SIZE))?;
+        let compression_min_size = u16::try_from(compression_min_size).map_err(|_| SettingsError::InvalidValue {
+            key: "server.compression_min_size",
+            reason: format!("{} is not a size between 0 and 65535 bytes", compression_min_size),
+        })?;
+
+        let defaults = RequestTimeouts::default();
+        let request_timeouts = RequestTimeouts {
+            read: optional_duration(config, "server.read_timeout", defaults.read)?,
+            trade_execution: optional_duration(config, "server.trade_execution_timeout", defaults.trade_execution)?,
+        };
+
+        let database_url: String = required(config, "database.url")?;
+        check_url("database.url", &database_url, &["postgres", "postgresql"])?;
+        let redis_url: String = required(config, "redis.url")?;
+        check_url("redis.url", &redis_url, &["redis", "rediss"])?;
+
+        let database_pool_size: u32 = optional(config, "database.max_connections", 20)?;
+        if database_pool_size == 0 {
+            return Err(SettingsError::InvalidValue {
+                key: "database.max_connections",
+                reason: "must be greater than 0".to_string(),
+            });
+        }
+
+        let slow_query_threshold =
+            optional_duration(config, "database.slow_query_threshold", DEFAULT_SLOW_QUERY_THRESHOLD)?;
+
+        let jwt_expiration_hours: u32 = optional(config, "security.jwt_expiration_hours", 24)?;
+        if jwt_expiration_hours == 0 {
+            return Err(SettingsError::InvalidValue {
+                key: "security.jwt_expiration_hours",
+                reason: "must be greater than 0".to_string(),
+            });
+        }
+
+        let single_session: bool = optional(config, "security.single_session", false)?;
+
+        let cors_allowed_origins: Vec<String> = optional(config, "cors.allowed_origins", Vec::new())?;
+        for origin in &cors_allowed_origins {
+            check_origin(origin)?;
+        }
+
+        let rate_limit_requests_per_minute: u32 = optional(config, "rate_limiting.requests_per_minute", 60)?;
+        if rate_limit_requests_per_minute == 0 {
+            return Err(SettingsError::InvalidValue {
+                key: "rate_limiting.requests_per_minute",
+                reason: "must be greater than 0".to_string(),
+            });
+        }
+
+        let websocket_max_connections: u32 = optional(config, "websocket.max_connections", 500)?;
+        if websocket_max_connections == 0 {
+            return Err(SettingsError::InvalidValue {
+                key: "websocket.max_connections",
+                reason: "must be greater than 0".to_string(),
+            });
+        }
+
+        let websocket_replay_capacity: usize =
+            optional(config, "websocket.replay_capacity", DEFAULT_REPLAY_CAPACITY)?;
+        if websocket_replay_capacity == 0 {
+            return Err(SettingsError::InvalidValue {
+                key: "websocket.replay_capacity",
+                reason: "must be greater than 0".to_string(),
+            });
+        }
+
+        let ooda_max_clock_skew = optional_duration(config, "ooda.max_clock_skew", DEFAULT_MAX_CLOCK_SKEW)?;
+
+        let provider_url: String = required(config, "oidc.provider_url")?;
+        check_url("oidc.provider_url", &provider_url, &["http", "https"])?;
+        let oidc = OidcConfig {
+            provider_url,
+            client_id: required(config, "oidc.client_id")?,
+            client_secret: optional(config, "oidc.client_secret", String::new())?,
+            redirect_uri: required(config, "oidc.redirect_uri")?,
+            scope: optional(config, "oidc.scope", "openid profile email".to_string())?,
+            jwks_max_age: optional_duration(config, "oidc.jwks_max_age", DEFAULT_JWKS_MAX_AGE)?,
+        };
+
+        let defaults = TokenExchangePolicy::default();
+        let token_exchange = TokenExchangePolicy {
+            timeout: optional_duration(config, "oidc.token_exchange_timeout", defaults.timeout)?,
+            max_retries: optional(config, "oidc.token_exchange_max_retries", defaults.max_retries)?,
+            initial_backoff: optional_duration(
+                config,
+                "oidc.token_exchange_initial_backoff",
+                defaults.initial_backoff,
+            )?,
+        };
+
+        Ok(Self {
+            server_host: optional(config, "server.host", "0.0.0.0".to_string())?,
+            server_port,
+            compression_min_size,
+            request_timeouts,
+            database_url,
+            database_pool_size,
+            slow_query_threshold,
+            redis_url,
+            jwt_secret: optional(config, "security.jwt_secret", String::new())?,
+            jwt_expiration_hours,
+            single_session,
+            cors_allowed_origins,
+            rate_limit_requests_per_minute,
+            websocket_max_connections,
+            websocket_heartbeat_interval: optional(config, "websocket.heartbeat_interval", 30)?,
+            websocket_replay_capacity,
+            ooda_max_clock_skew,
+            oidc,
+            token_exchange,
+        })
+    }
+
+    /// Configuration the API server is built with
+    pub fn app_config(&self) -> AppConfig {
+        AppConfig {
+            server_host: self.server_host.clone(),
+            server_port: self.server_port,
+            database_url: self.database_url.clone(),
+            database_pool_size: self.database_pool_size,
+            redis_url: self.redis_url.clone(),
+            jwt_secret: self.jwt_secret.clone(),
+            jwt_expiration_hours: self.jwt_expiration_hours,
+            cors_allowed_origins: self.cors_allowed_origins.clone(),
+            rate_limit_requests_per_minute: self.rate_limit_requests_per_minute,
+            websocket_max_connections: self.websocket_max_connections,
+            websocket_heartbeat_interval: self.websocket_heartbeat_interval,
+            compression_min_size: self.compression_min_size,
+            request_timeouts: self.request_timeouts,
+        }
+    }
+}
+
+fn required<T: DeserializeOwned>(config: &Config, key: &'static str) -> Result<T, SettingsError> {
+    match config.get::<T>(key) {
+        Ok(value) => Ok(value),
+        Err(ConfigError::NotFound(_)) => Err(SettingsError::MissingField { key }),
+        Err(error) => Err(SettingsError::InvalidValue {
+            key,
+            reason: error.to_string(),
+        }),
+    }
+}
+
+fn optional<T: DeserializeOwned>(config: &Config, key: &'static str, default: T) -> Result<T, SettingsError> {
+    match required(config, key) {
+        Err(SettingsError::MissingField { .. }) => Ok(default),
+        result => result,
+    }
+}
+
+/// A positive duration such as `"500ms"`, `"2s"`, `"10m"` or `"1h"`
+fn optional_duration(config: &Config, key: &'static str, default: Duration) -> Result<Duration, SettingsError> {
+    let value: String = match required(config, key) {
+        Err(SettingsError::MissingField { .. }) => return Ok(default),
+        result => result?,
+    };
+    let invalid = || SettingsError::InvalidValue {
+        key,
+        reason: format!("`{}` is not a positive duration such as \"500ms\", \"2s\" or \"10m\"", value),
+    };
+    let split = value.find(|c: char| !c.is_ascii_digit()).ok_or_else(invalid)?;
+    let amount: u64 = value[..split].parse().map_err(|_| invalid())?;
+    let duration = match &value[split..] {
+        "ms" => Duration::from_millis(amount),
+        "s" => Duration::from_secs(amount),
+        "m" => Duration::from_secs(amount * 60),
+        "h" => Duration::from_secs(amount * 3600),
+        _ => return Err(invalid()),
+    };
+    if duration.is_zero() {
+        return Err(invalid());
+    }
+    Ok(duration)
+}
+
+fn check_url(key: &'static str, value: &str, schemes: &[&str]) -> Result<(), SettingsError> {
+    let url = Url::parse(value).map_err(|error| SettingsError::InvalidValue {
+        key,
+        reason: format!("`{}` is not a URL: {}", value, error),
+    })?;
+    if !schemes.contains(&url.scheme()) {
+        return Err(SettingsError::InvalidValue {
+            key,
+            reason: format!("scheme `{}` is not one of {}", url.scheme(), schemes.join(", ")),
+        });
+    }
+    Ok(())
+}
+
+/// An origin is a scheme and host, optionally with a port, and nothing else
+fn check_origin(origin: &str) -> Result<(), SettingsError> {
+    let invalid = |reason: String| SettingsError::InvalidValue {
+        key: "cors.allowed_origins",
+        reason,
+    };
+    let url = Url::parse(origin).map_err(|error| invalid(format!("`{}` is not a URL: {}", origin, error)))?;
+    if !matches!(url.scheme(), "http" | "https") || url.host().is_none() {
+        return Err(invalid(format!("`{}` must be an http(s) origin", origin)));
+    }
+    if url.origin().ascii_serialization() != origin.trim_end_matches('/') {
+        return Err(invalid(format!("`{}` must not include a path, query or credentials", origin)));
+    }
+    Ok(())
+}
+
+/// Load the `[commissions]` section of the configuration file
+///
+/// A file without the section yields the default schedule, so fee estimates
+/// are always available.
+pub fn load_commission_schedule(config_file: &str) -> Result<CommissionSchedule, ConfigError> {
+    let config = Config::builder()
+        .add_source(File::new(config_file, FileFormat::Toml))
+        .build()?;
+
+    match config.get::<CommissionSchedule>("commissions") {
+        Err(ConfigError::NotFound(_)) => Ok(CommissionSchedule::default()),
+        result => result,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn settings_from(toml: &str) -> Result<Settings, SettingsError> {
+        let config = Config::builder()
+            .add_source(File::from_str(toml, FileFormat::Toml))
+            .build()
+            .unwrap();
+        Settings::from_config(&config)
+    }
+
+    #[test]
+    fn test_default_config_settings_are_valid() {
+        let settings = Settings::new("../../config/default.toml").unwrap();
+
+        assert_eq!(settings.server_port, 3000);
+        assert_eq!(settings.database_pool_size, 20);
+        assert_eq!(settings.cors_allowed_origins.len(), 2);
+        assert_eq!(settings.oidc.client_id, "testudo-frontend");
+        assert_eq!(settings.app_config().compression_min_size, 1024);
+        assert_eq!(settings.request_timeouts.read, Duration::from_secs(2));
+        assert_eq!(settings.request_timeouts.trade_execution, Duration::from_secs(10));
+        assert_eq!(settings.slow_query_threshold, Duration::from_millis(10));
+        assert_eq!(settings.ooda_max_clock_skew, Duration::from_secs(1));
+        assert_eq!(settings.websocket_replay_capacity, 256);
+        assert_eq!(settings.oidc.jwks_max_age, Duration::from_secs(3600));
+        assert_eq!(settings.token_exchange, TokenExchangePolicy::default());
+        assert!(!settings.single_session);
+    }
+
+    #[test]
+    fn test_missing_database_url_names_the_field() {
+        let error = settings_from("[redis]\nurl = \"redis://localhost:6379\"").unwrap_err();
+
+        assert!(matches!(error, SettingsError::MissingField { key: "database.url" }));
+        assert_eq!(error.to_string(), "Missing required setting `database.url`");
+    }
+
+    #[test]
+    fn test_out_of_range_values_are_rejected() {
+        let base = "[database]\nurl = \"postgres://localhost/testudo\"\n[redis]\nurl = \"redis://localhost\"\n";
+
+        let error = settings_from(&format!("{}[server]\nport = 70000\n", base)).unwrap_err();
+        assert!(matches!(error, SettingsError::InvalidValue { key: "server.port", .. }));
+        assert_eq!(
+            error.to_string(),
+            "Invalid value for `server.port`: 70000 is not a port between 1 and 65535"
+        );
+
+        let error = settings_from(&format!("{}[security]\njwt_expiration_hours = -1\n", base)).unwrap_err();
+        assert!(matches!(error, SettingsError::InvalidValue { key: "security.jwt_expiration_hours", .. }));
+
+        let error = settings_from(&format!("{}[cors]\nallowed_origins = [\"localhost:3000\"]\n", base)).unwrap_err();
+        assert!(matches!(error, SettingsError::InvalidValue { key: "cors.allowed_origins", .. }));
+
+        let error = settings_from(&format!("{}[server]\ncompression_min_size = 100000\n", base)).unwrap_err();
+        assert!(matches!(error, SettingsError::InvalidValue { key: "server.compression_min_size", .. }));
+
+        let error = settings_from(&format!("{}[server]\nread_timeout = \"2 seconds\"\n", base)).unwrap_err();
+        assert!(matches!(error, SettingsError::InvalidValue { key: "server.read_timeout", .. }));
+    }
+
+    #[test]
+    fn test_duration_and_limit_keys_are_validated() {
+        let redis = "[redis]\nurl = \"redis://localhost\"\n";
+        let database = "[database]\nurl = \"postgres://localhost/testudo\"\n";
+        let oidc = "[oidc]\nprovider_url = \"http://localhost:8080\"\nclient_id = \"testudo\"\nredirect_uri = \"http://localhost:3000/auth/callback\"\n";
+        let cases = [
+            (format!("{}{}[server]\ntrade_execution_timeout = \"0s\"\n", database, redis), "server.trade_execution_timeout"),
+            (format!("{}{}slow_query_threshold = \"fast\"\n", redis, database), "database.slow_query_threshold"),
+            (format!("{}{}[ooda]\nmax_clock_skew = 1\n", database, redis), "ooda.max_clock_skew"),
+            (format!("{}{}[rate_limiting]\nrequests_per_minute = 0\n", database, redis), "rate_limiting.requests_per_minute"),
+            (format!("{}{}[websocket]\nmax_connections = 0\n", database, redis), "websocket.max_connections"),
+            (format!("{}{}[websocket]\nreplay_capacity = 0\n", database, redis), "websocket.replay_capacity"),
+            (format!("{}{}{}jwks_max_age = \"0s\"\n", database, redis, oidc), "oidc.jwks_max_age"),
+            (format!("{}{}{}token_exchange_timeout = \"10\"\n", database, redis, oidc), "oidc.token_exchange_timeout"),
+            (format!("{}{}{}token_exchange_max_retries = -1\n", database, redis, oidc), "oidc.token_exchange_max_retries"),
+        ];
+
+        for (toml, expected_key) in cases {
+            match settings_from(&toml).unwrap_err() {
+                SettingsError::InvalidValue { key, .. } => assert_eq!(key, expected_key),
+                other => panic!("Expected an invalid `{}`, got: {}", expected_key, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_default_config_commission_schedule() {
+        let schedule = load_commission_schedule("../../config/default.toml").unwrap();
+
+        assert_eq!(schedule.rates_for("binance", "BTC/USDT").taker, dec!(0.001));
+        assert_eq!(schedule.rates_for("binance", "BTCUSDT-PERP").taker, dec!(0.0005));
+        assert_eq!(schedule.rates_for("binance", "UNLISTED/USDT"), schedule.default_tier);
+    }
 }
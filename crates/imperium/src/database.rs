@@ -1 +1,583 @@
-//! database (placeholder)
+//! Postgres persistence
+//!
+//! Thin query layer over the schema in `migrations/`. Every function takes the
+//! pool (or an open transaction) explicitly so handlers stay easy to test.
+//!
+//! Queries slower than the slow-query threshold are logged at warn level with
+//! their operation name and duration, to help find regressions in production.
+
+use chrono::{DateTime, Utc};
+use prudentia::risk::protocol::ProtocolStatus;
+use prudentia::TradeSide;
+use rust_decimal::Decimal;
+use sqlx::{PgPool, Postgres, Transaction};
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::{ImperiumError, Result};
+
+/// Default slow-query threshold, the 99th-percentile query target
+pub const DEFAULT_SLOW_QUERY_THRESHOLD: Duration = Duration::from_millis(10);
+
+static SLOW_QUERY_THRESHOLD_MS: AtomicU64 =
+    AtomicU64::new(DEFAULT_SLOW_QUERY_THRESHOLD.as_millis() as u64);
+
+/// Set the duration above which queries are logged as slow
+pub fn set_slow_query_threshold(threshold: Duration) {
+    SLOW_QUERY_THRESHOLD_MS.store(threshold.as_millis() as u64, Ordering::Relaxed);
+}
+
+/// Duration above which queries are logged as slow
+pub fn slow_query_threshold() -> Duration {
+    Duration::from_millis(SLOW_QUERY_THRESHOLD_MS.load(Ordering::Relaxed))
+}
+
+/// Run a query, warning if it takes longer than `threshold`
+pub async fn log_if_slow<F: Future>(operation: &str, threshold: Duration, query: F) -> F::Output {
+    let started = Instant::now();
+    let output = query.await;
+    let elapsed = started.elapsed();
+    if elapsed > threshold {
+        warn!(
+            "Slow query '{}' took {}ms (threshold {}ms)",
+            operation,
+            elapsed.as_millis(),
+            threshold.as_millis()
+        );
+    }
+    output
+}
+
+async fn timed<F: Future>(operation: &str, query: F) -> F::Output {
+    log_if_slow(operation, slow_query_threshold(), query).await
+}
+
+/// Severity values accepted by the `system_events` table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventSeverity {
+    Debug,
+    Info,
+    Warn,
+    Error,
+    Critical,
+}
+
+impl EventSeverity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventSeverity::Debug => "DEBUG",
+            EventSeverity::Info => "INFO",
+            EventSeverity::Warn => "WARN",
+            EventSeverity::Error => "ERROR",
+            EventSeverity::Critical => "CRITICAL",
+        }
+    }
+}
+
+/// A row in the `system_events` audit log
+#[derive(Debug, Clone)]
+pub struct SystemEvent {
+    pub event_type: String,
+    pub severity: EventSeverity,
+    pub component: String,
+    pub message: String,
+    pub metadata: Option<serde_json::Value>,
+    pub user_id: Option<Uuid>,
+}
+
+/// Append an event to the `system_events` audit log
+pub async fn record_system_event(pool: &PgPool, event: &SystemEvent) -> Result<Uuid> {
+    let query = sqlx::query_scalar::<_, Uuid>(
+        "INSERT INTO system_events (event_type, severity, component, message, metadata, user_id)
+         VALUES ($1, $2, $3, $4, $5, $6)
+         RETURNING id",
+    )
+    .bind(&event.event_type)
+    .bind(event.severity.as_str())
+    .bind(&event.component)
+    .bind(&event.message)
+    .bind(&event.metadata)
+    .bind(event.user_id)
+    .fetch_one(pool);
+
+    timed("record system event", query)
+        .await
+        .map_err(|e| ImperiumError::DatabaseError {
+            operation: format!("record system event: {}", e),
+        })
+}
+
+/// Everything written when a trade execution is recorded
+///
+/// `position_id` is chosen by the caller (normally the `TradeProposal` id) so
+/// that retrying a failed recording cannot create a duplicate position.
+#[derive(Debug, Clone)]
+pub struct TradeExecutionRecord {
+    pub position_id: Uuid,
+    pub user_id: Uuid,
+    pub symbol: String,
+    pub exchange: String,
+    pub side: TradeSide,
+    pub entry_price: Decimal,
+    pub stop_loss: Decimal,
+    pub take_profit: Option<Decimal>,
+    pub account_equity: Decimal,
+    pub risk_percentage: Decimal,
+    pub calculated_position_size: Decimal,
+    pub executed_quantity: Decimal,
+    pub execution_price: Decimal,
+    pub exchange_order_id: Option<String>,
+    pub executed_at: DateTime<Utc>,
+}
+
+fn side_column(side: TradeSide) -> &'static str {
+    match side {
+        TradeSide::Long => "BUY",
+        TradeSide::Short => "SELL",
+    }
+}
+
+fn database_error(operation: &str, error: sqlx::Error) -> ImperiumError {
+    ImperiumError::DatabaseError {
+        operation: format!("{}: {}", operation, error),
+    }
+}
+
+/// Record a trade execution atomically
+///
+/// The position row, the ACT-phase execution row and the user's protocol state
+/// are written in a single transaction: either all of them are committed or,
+/// on any error, the transaction is rolled back and nothing is written.
+/// Recording the same `position_id` twice is a no-op, so callers may retry.
+pub async fn record_trade_execution(
+    pool: &PgPool,
+    record: &TradeExecutionRecord,
+    protocol_status: &ProtocolStatus,
+) -> Result<Uuid> {
+    let mut tx = timed("begin trade recording", pool.begin())
+        .await
+        .map_err(|e| database_error("begin trade recording", e))?;
+
+    let already_recorded = timed(
+        "check existing position",
+        sqlx::query_scalar::<_, bool>("SELECT EXISTS (SELECT 1 FROM positions WHERE id = $1)")
+            .bind(record.position_id)
+            .fetch_one(&mut *tx),
+    )
+    .await
+    .map_err(|e| database_error("check existing position", e))?;
+
+    if already_recorded {
+        warn!("Trade {} already recorded, skipping duplicate write", record.position_id);
+        return Ok(record.position_id);
+    }
+
+    // Dropping the transaction on error rolls back every write below
+    insert_position(&mut tx, record).await?;
+    insert_execution(&mut tx, record).await?;
+    upsert_protocol_state(&mut tx, record.user_id, protocol_status).await?;
+
+    timed("commit trade recording", tx.commit())
+        .await
+        .map_err(|e| database_error("commit trade recording", e))?;
+
+    info!("Recorded trade execution {} for user {}", record.position_id, record.user_id);
+    Ok(record.position_id)
+}
+
+/// Record positions imported from another platform atomically
+///
+/// Imported positions are already open, so only their position rows are
+/// written (there is no execution to audit), together with the user's
+/// protocol state. Positions whose id is already recorded are skipped, so an
+/// import retried with the same position ids writes each position once; the
+/// number of newly written positions is returned.
+pub async fn record_imported_positions(
+    pool: &PgPool,
+    user_id: Uuid,
+    records: &[TradeExecutionRecord],
+    protocol_status: &ProtocolStatus,
+) -> Result<usize> {
+    let mut tx = timed("begin position import", pool.begin())
+        .await
+        .map_err(|e| database_error("begin position import", e))?;
+
+    let mut imported = 0;
+    for record in records {
+        let already_recorded = timed(
+            "check existing position",
+            sqlx::query_scalar::<_, bool>("SELECT EXISTS (SELECT 1 FROM positions WHERE id = $1)")
+                .bind(record.position_id)
+                .fetch_one(&mut *tx),
+        )
+        .await
+        .map_err(|e| database_error("check existing position", e))?;
+
+        if !already_recorded {
+            insert_position(&mut tx, record).await?;
+            imported += 1;
+        }
+    }
+    upsert_protocol_state(&mut tx, user_id, protocol_status).await?;
+
+    timed("commit position import", tx.commit())
+        .await
+        .map_err(|e| database_error("commit position import", e))?;
+
+    info!("Imported {} position(s) for user {}", imported, user_id);
+    Ok(imported)
+}
+
+/// Number of positions updated per R-multiple backfill batch
+pub const R_BACKFILL_BATCH_SIZE: i64 = 500;
+
+/// Fill in `r_multiple` for closed positions recorded before it was tracked
+///
+/// R is the realized P&L divided by the position's risk amount. Positions
+/// that already have an R, or carried no risk, are left untouched, so the
+/// backfill is safe to re-run. Rows are updated `batch_size` at a time to
+/// keep each transaction short; the total number updated is returned.
+pub async fn backfill_r_multiples(pool: &PgPool, batch_size: i64) -> Result<u64> {
+    let mut updated = 0;
+    loop {
+        let query = sqlx::query(
+            "UPDATE positions SET r_multiple = ROUND(realized_pnl / risk_amount, 4)
+             WHERE id IN (
+                SELECT id FROM positions
+                WHERE r_multiple IS NULL
+                  AND status = 'CLOSED'
+                  AND realized_pnl IS NOT NULL
+                  AND risk_amount > 0
+                LIMIT $1
+             )",
+        )
+        .bind(batch_size)
+        .execute(pool);
+
+        let batch = timed("backfill r multiples", query)
+            .await
+            .map_err(|e| database_error("backfill r multiples", e))?
+            .rows_affected();
+        if batch == 0 {
+            break;
+        }
+        updated += batch;
+    }
+
+    info!("Backfilled R multiples for {} position(s)", updated);
+    Ok(updated)
+}
+
+async fn insert_position(
+    tx: &mut Transaction<'_, Postgres>,
+    record: &TradeExecutionRecord,
+) -> Result<()> {
+    let query = sqlx::query(
+        "INSERT INTO positions (
+            id, user_id, symbol, exchange, side,
+            entry_price, stop_loss, take_profit, account_equity_at_entry, risk_percentage,
+            calculated_position_size, actual_position_size, average_entry_price,
+            entry_order_ids, status, entered_at
+         ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, 'OPEN', $15)",
+    )
+    .bind(record.position_id)
+    .bind(record.user_id)
+    .bind(&record.symbol)
+    .bind(&record.exchange)
+    .bind(side_column(record.side))
+    .bind(record.entry_price)
+    .bind(record.stop_loss)
+    .bind(record.take_profit)
+    .bind(record.account_equity)
+    .bind(record.risk_percentage)
+    .bind(record.calculated_position_size)
+    .bind(record.executed_quantity)
+    .bind(record.execution_price)
+    .bind(record.exchange_order_id.iter().cloned().collect::<Vec<_>>())
+    .bind(record.executed_at)
+    .execute(&mut **tx);
+
+    timed("insert position", query)
+        .await
+        .map_err(|e| database_error("insert position", e))?;
+
+    Ok(())
+}
+
+async fn insert_execution(
+    tx: &mut Transaction<'_, Postgres>,
+    record: &TradeExecutionRecord,
+) -> Result<()> {
+    let query = sqlx::query(
+        "INSERT INTO trade_executions (
+            position_id, ooda_phase, phase_start_time, phase_duration_ms, phase_success,
+            calculated_size, exchange_order_id, execution_price, executed_quantity
+         ) VALUES ($1, 'ACT', $2, 0, TRUE, $3, $4, $5, $6)",
+    )
+    .bind(record.position_id)
+    .bind(record.executed_at)
+    .bind(record.calculated_position_size)
+    .bind(&record.exchange_order_id)
+    .bind(record.execution_price)
+    .bind(record.executed_quantity)
+    .execute(&mut **tx);
+
+    timed("insert trade execution", query)
+        .await
+        .map_err(|e| database_error("insert trade execution", e))?;
+
+    Ok(())
+}
+
+async fn upsert_protocol_state(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    status: &ProtocolStatus,
+) -> Result<()> {
+    let query = sqlx::query(
+        "INSERT INTO protocol_state (
+            user_id, total_portfolio_risk, open_positions, consecutive_losses,
+            daily_loss, circuit_breaker_active, updated_at
+         ) VALUES ($1, $2, $3, $4, $5, $6, NOW())
+         ON CONFLICT (user_id) DO UPDATE SET
+            total_portfolio_risk = EXCLUDED.total_portfolio_risk,
+            open_positions = EXCLUDED.open_positions,
+            consecutive_losses = EXCLUDED.consecutive_losses,
+            daily_loss = EXCLUDED.daily_loss,
+            circuit_breaker_active = EXCLUDED.circuit_breaker_active,
+            updated_at = EXCLUDED.updated_at",
+    )
+    .bind(user_id)
+    .bind(status.total_portfolio_risk)
+    .bind(status.open_positions as i32)
+    .bind(status.consecutive_losses as i32)
+    .bind(status.daily_loss)
+    .bind(status.circuit_breaker_active)
+    .execute(&mut **tx);
+
+    timed("upsert protocol state", query)
+        .await
+        .map_err(|e| database_error("upsert protocol state", e))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prudentia::TestudoProtocol;
+    use rust_decimal_macros::dec;
+    use std::sync::{Arc, Mutex};
+    use tracing_subscriber::fmt::MakeWriter;
+
+    /// Collects formatted log output for assertions
+    #[derive(Clone, Default)]
+    struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+    impl CapturedLogs {
+        fn contents(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    impl std::io::Write for CapturedLogs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> MakeWriter<'a> for CapturedLogs {
+        type Writer = Self;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    async fn test_pool() -> PgPool {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set for database tests");
+        let pool = PgPool::connect(&url).await.expect("Failed to connect to test database");
+        sqlx::migrate!("../../migrations").run(&pool).await.expect("Failed to run migrations");
+        pool
+    }
+
+    async fn create_test_user(pool: &PgPool) -> Uuid {
+        sqlx::query_scalar::<_, Uuid>(
+            "INSERT INTO user_accounts (email, password_hash) VALUES ($1, 'test') RETURNING id",
+        )
+        .bind(format!("{}@example.com", Uuid::new_v4()))
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    }
+
+    fn test_record(user_id: Uuid) -> TradeExecutionRecord {
+        TradeExecutionRecord {
+            position_id: Uuid::new_v4(),
+            user_id,
+            symbol: "BTCUSDT".to_string(),
+            exchange: "binance".to_string(),
+            side: TradeSide::Long,
+            entry_price: dec!(50000),
+            stop_loss: dec!(48000),
+            take_profit: Some(dec!(54000)),
+            account_equity: dec!(10000),
+            risk_percentage: dec!(0.02),
+            calculated_position_size: dec!(0.1),
+            executed_quantity: dec!(0.1),
+            execution_price: dec!(50000),
+            exchange_order_id: Some("order-1".to_string()),
+            executed_at: Utc::now(),
+        }
+    }
+
+    async fn protocol_open_positions(pool: &PgPool, user_id: Uuid) -> i32 {
+        sqlx::query_scalar("SELECT open_positions FROM protocol_state WHERE user_id = $1")
+            .bind(user_id)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    async fn position_count(pool: &PgPool, position_id: Uuid) -> i64 {
+        sqlx::query_scalar("SELECT COUNT(*) FROM positions WHERE id = $1")
+            .bind(position_id)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_slow_query_logs_warning_with_duration() {
+        let logs = CapturedLogs::default();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(logs.clone())
+            .with_ansi(false)
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let threshold = Duration::from_millis(10);
+        log_if_slow("load positions", threshold, tokio::time::sleep(Duration::from_millis(30))).await;
+        log_if_slow("load settings", threshold, async {}).await;
+
+        let output = logs.contents();
+        assert!(output.contains("WARN"), "{}", output);
+        assert!(!output.contains("load settings"), "{}", output);
+        let took_ms: u64 = output
+            .split("Slow query 'load positions' took ")
+            .nth(1)
+            .and_then(|rest| rest.split("ms").next())
+            .and_then(|ms| ms.parse().ok())
+            .unwrap_or_else(|| panic!("No slow-query warning in: {}", output));
+        assert!(took_ms >= 30);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a Postgres database at DATABASE_URL"]
+    async fn test_failed_recording_leaves_no_partial_rows() {
+        let pool = test_pool().await;
+        let user_id = create_test_user(&pool).await;
+        let status = TestudoProtocol::new().get_status();
+
+        // The position insert succeeds but the execution insert then fails
+        // (order id exceeds VARCHAR(100)), forcing a rollback mid-transaction
+        let mut record = test_record(user_id);
+        record.exchange_order_id = Some("x".repeat(101));
+
+        assert!(record_trade_execution(&pool, &record, &status).await.is_err());
+        assert_eq!(position_count(&pool, record.position_id).await, 0);
+
+        let state_rows: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM protocol_state WHERE user_id = $1")
+            .bind(user_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(state_rows, 0);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a Postgres database at DATABASE_URL"]
+    async fn test_retrying_recording_is_idempotent() {
+        let pool = test_pool().await;
+        let user_id = create_test_user(&pool).await;
+        let status = TestudoProtocol::new().get_status();
+        let record = test_record(user_id);
+
+        record_trade_execution(&pool, &record, &status).await.unwrap();
+        record_trade_execution(&pool, &record, &status).await.unwrap();
+
+        assert_eq!(position_count(&pool, record.position_id).await, 1);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a Postgres database at DATABASE_URL"]
+    async fn test_imported_positions_are_recorded_once() {
+        let pool = test_pool().await;
+        let user_id = create_test_user(&pool).await;
+        let records = vec![test_record(user_id), test_record(user_id)];
+
+        let mut protocol = TestudoProtocol::new();
+        for record in &records {
+            protocol.track_external_position(record.position_id, &record.symbol, record.risk_percentage);
+        }
+        let status = protocol.get_status();
+
+        assert_eq!(record_imported_positions(&pool, user_id, &records, &status).await.unwrap(), 2);
+        assert_eq!(record_imported_positions(&pool, user_id, &records, &status).await.unwrap(), 0);
+        for record in &records {
+            assert_eq!(position_count(&pool, record.position_id).await, 1);
+        }
+        assert_eq!(protocol_open_positions(&pool, user_id).await, 2);
+    }
+
+    async fn close_position(pool: &PgPool, position_id: Uuid, realized_pnl: Decimal, r_multiple: Option<Decimal>) {
+        sqlx::query("UPDATE positions SET status = 'CLOSED', realized_pnl = $2, r_multiple = $3 WHERE id = $1")
+            .bind(position_id)
+            .bind(realized_pnl)
+            .bind(r_multiple)
+            .execute(pool)
+            .await
+            .unwrap();
+    }
+
+    async fn r_multiple(pool: &PgPool, position_id: Uuid) -> Option<Decimal> {
+        sqlx::query_scalar("SELECT r_multiple FROM positions WHERE id = $1")
+            .bind(position_id)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a Postgres database at DATABASE_URL"]
+    async fn test_backfill_fills_missing_r_multiples_only() {
+        let pool = test_pool().await;
+        let user_id = create_test_user(&pool).await;
+        let status = TestudoProtocol::new().get_status();
+
+        // Each record risks 2% of 10000 = 200
+        let records = [test_record(user_id), test_record(user_id), test_record(user_id)];
+        for record in &records {
+            record_trade_execution(&pool, record, &status).await.unwrap();
+        }
+        close_position(&pool, records[0].position_id, dec!(500), None).await;
+        close_position(&pool, records[1].position_id, dec!(-200), None).await;
+        close_position(&pool, records[2].position_id, dec!(300), Some(dec!(9))).await;
+
+        // Batches of one exercise the batching loop
+        backfill_r_multiples(&pool, 1).await.unwrap();
+        assert_eq!(r_multiple(&pool, records[0].position_id).await, Some(dec!(2.5)));
+        assert_eq!(r_multiple(&pool, records[1].position_id).await, Some(dec!(-1)));
+        assert_eq!(r_multiple(&pool, records[2].position_id).await, Some(dec!(9)));
+
+        assert_eq!(backfill_r_multiples(&pool, 1).await.unwrap(), 0);
+    }
+}
@@ -0,0 +1,100 @@
+//! String serialization for `Decimal` API fields
+//!
+//! Monetary values are sent as JSON strings (`"50000.25"`) so JavaScript
+//! clients never coerce them through a float. Use on fields with
+//! `#[serde(with = "crate::decimal_string")]`, or the `option` submodule for
+//! `Option<Decimal>`. This holds regardless of which `rust_decimal` serde
+//! features other crates enable.
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Deserializer, Serializer};
+
+pub fn serialize<S>(value: &Decimal, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.collect_str(value)
+}
+
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Decimal, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = String::deserialize(deserializer)?;
+    value.parse().map_err(serde::de::Error::custom)
+}
+
+/// String serialization for `Option<Decimal>`, with `None` as `null`
+pub mod option {
+    use super::*;
+
+    pub fn serialize<S>(value: &Option<Decimal>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match value {
+            Some(value) => serializer.collect_str(value),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Decimal>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Option::<String>::deserialize(deserializer)?
+            .map(|value| value.parse().map_err(serde::de::Error::custom))
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::ExecuteTradeResponse;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_prices_and_sizes_serialize_as_exact_strings() {
+        let response = ExecuteTradeResponse {
+            symbol: "BTC/USDT".to_string(),
+            approved: true,
+            entry_price: dec!(50000.12345678),
+            stop_loss: dec!(49000.1),
+            take_profit: Some(dec!(52000)),
+            position_size: dec!(0.20000001),
+            risk_assessment: "Trade approved by Testudo Protocol".to_string(),
+            requested_position_size: None,
+            position_id: uuid::Uuid::nil(),
+            failed_rules: Vec::new(),
+        };
+
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["entry_price"], "50000.12345678");
+        assert_eq!(json["stop_loss"], "49000.1");
+        assert_eq!(json["take_profit"], "52000");
+        assert_eq!(json["position_size"], "0.20000001");
+
+        let parsed: ExecuteTradeResponse = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.entry_price, dec!(50000.12345678));
+        assert_eq!(parsed.take_profit, Some(dec!(52000)));
+        assert_eq!(parsed.position_size, dec!(0.20000001));
+    }
+
+    #[test]
+    fn test_missing_take_profit_serializes_as_null() {
+        let json = serde_json::json!({
+            "symbol": "BTC/USDT",
+            "approved": false,
+            "entry_price": "50000",
+            "stop_loss": "49000",
+            "take_profit": null,
+            "position_size": "0",
+            "risk_assessment": "Trade rejected",
+            "position_id": "00000000-0000-0000-0000-000000000000",
+        });
+
+        let parsed: ExecuteTradeResponse = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(parsed.take_profit, None);
+        assert_eq!(serde_json::to_value(&parsed).unwrap(), json);
+    }
+}
@@ -0,0 +1,90 @@
+//! Display-currency conversion for API responses
+//!
+//! Sizing, risk and P&L are always computed in the native quote currency.
+//! Users may choose a different base currency (EUR, BTC, ...) for display;
+//! amounts are converted only when a response is rendered. When no rate is
+//! known the amounts are shown unconverted in the quote currency rather than
+//! failing the request.
+
+use rust_decimal::Decimal;
+use std::{collections::HashMap, sync::RwLock};
+
+/// Native quote currency all amounts are computed in
+pub const QUOTE_CURRENCY: &str = "USDT";
+
+/// Conversion rates from the quote currency to display currencies
+#[derive(Default)]
+pub struct FxRates {
+    /// Units of the keyed currency per one unit of the quote currency
+    rates: RwLock<HashMap<String, Decimal>>,
+}
+
+/// Result of converting an amount for display
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Conversion {
+    pub rate: Decimal,
+    /// False when the rate was missing and the quote currency is used instead
+    pub converted: bool,
+}
+
+impl Conversion {
+    pub fn apply(&self, amount: Decimal) -> Decimal {
+        amount * self.rate
+    }
+}
+
+impl FxRates {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the rate for a currency, in units per one quote-currency unit
+    pub fn set_rate(&self, currency: &str, rate: Decimal) {
+        self.rates
+            .write()
+            .unwrap()
+            .insert(currency.to_ascii_uppercase(), rate);
+    }
+
+    /// Rate for a currency, if known
+    ///
+    /// The quote currency itself always converts at 1.
+    pub fn rate(&self, currency: &str) -> Option<Decimal> {
+        if currency.eq_ignore_ascii_case(QUOTE_CURRENCY) {
+            return Some(Decimal::ONE);
+        }
+        self.rates
+            .read()
+            .unwrap()
+            .get(&currency.to_ascii_uppercase())
+            .copied()
+            .filter(|rate| *rate > Decimal::ZERO)
+    }
+
+    /// Conversion into `currency`, falling back to the quote currency
+    pub fn conversion_to(&self, currency: &str) -> Conversion {
+        match self.rate(currency) {
+            Some(rate) => Conversion { rate, converted: true },
+            None => Conversion {
+                rate: Decimal::ONE,
+                converted: false,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_missing_rate_falls_back_to_quote_currency() {
+        let rates = FxRates::new();
+        rates.set_rate("eur", dec!(0.92));
+
+        assert_eq!(rates.conversion_to("EUR").apply(dec!(100)), dec!(92.00));
+        assert_eq!(rates.conversion_to("USDT"), Conversion { rate: Decimal::ONE, converted: true });
+        assert_eq!(rates.conversion_to("BTC"), Conversion { rate: Decimal::ONE, converted: false });
+    }
+}
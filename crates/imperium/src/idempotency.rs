@@ -0,0 +1,158 @@
+//! Idempotency keys for trade execution
+//!
+//! Clients retry `POST /trades/execute` after timeouts and dropped
+//! connections. Sending an `Idempotency-Key` header makes a retry return the
+//! original result instead of placing a second order. Each key is stored with
+//! a SHA-256 fingerprint of the request body, so a key that arrives again with
+//! a different body, whether through a client bug or a replayed key, is
+//! refused as a conflict rather than answered with an unrelated trade.
+//!
+//! Only successful results are kept. A failed request, or one cancelled
+//! before its trade was placed, releases its key so the client can retry it;
+//! a trade already being placed keeps its key until it finishes.
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use crate::{ImperiumError, Result};
+
+/// Header carrying the client's idempotency key
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// How long a completed result is replayed for
+pub const DEFAULT_IDEMPOTENCY_TTL: Duration = Duration::from_secs(24 * 3600);
+
+/// Hex SHA-256 of a request's canonical JSON encoding
+///
+/// Hashing the parsed request rather than the raw bytes means whitespace and
+/// key order do not change the fingerprint.
+pub fn fingerprint<T: Serialize>(request: &T) -> Result<String> {
+    let encoded = serde_json::to_vec(request).map_err(|err| ImperiumError::InternalError {
+        message: format!("Failed to fingerprint request: {}", err),
+    })?;
+    Ok(Sha256::digest(&encoded).iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+enum EntryState<T> {
+    InFlight,
+    Completed(T),
+}
+
+struct Entry<T> {
+    fingerprint: String,
+    state: EntryState<T>,
+    created_at: Instant,
+}
+
+type EntryKey = (String, String);
+
+/// Results of idempotent requests, keyed by user and idempotency key
+pub struct IdempotencyStore<T> {
+    ttl: Duration,
+    entries: Mutex<HashMap<EntryKey, Entry<T>>>,
+}
+
+impl<T: Clone> IdempotencyStore<T> {
+    pub fn new() -> Self {
+        Self::with_ttl(DEFAULT_IDEMPOTENCY_TTL)
+    }
+
+    pub fn with_ttl(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Claim `key` for a request with the given fingerprint
+    ///
+    /// A key seen before with the same fingerprint replays its result; one
+    /// seen with a different fingerprint, or still being processed, is a
+    /// conflict. Otherwise the returned guard holds the key until the result
+    /// is recorded with [`IdempotencyGuard::complete`].
+    pub fn begin(self: &Arc<Self>, user_id: &str, key: &str, fingerprint: String) -> Result<Claim<T>> {
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|_, entry| entry.created_at.elapsed() <= self.ttl);
+
+        let entry_key = (user_id.to_string(), key.to_string());
+        if let Some(entry) = entries.get(&entry_key) {
+            let conflict = |reason: &str| ImperiumError::IdempotencyConflict {
+                key: key.to_string(),
+                reason: reason.to_string(),
+            };
+            if entry.fingerprint != fingerprint {
+                return Err(conflict("key was already used with a different request body"));
+            }
+            return match &entry.state {
+                EntryState::Completed(result) => Ok(Claim::Replay(result.clone())),
+                EntryState::InFlight => Err(conflict("a request with this key is still in progress")),
+            };
+        }
+
+        entries.insert(
+            entry_key.clone(),
+            Entry {
+                fingerprint,
+                state: EntryState::InFlight,
+                created_at: Instant::now(),
+            },
+        );
+        Ok(Claim::Started(IdempotencyGuard {
+            store: self.clone(),
+            entry_key: Some(entry_key),
+        }))
+    }
+
+    /// Number of keys currently held
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T: Clone> Default for IdempotencyStore<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Outcome of claiming an idempotency key
+pub enum Claim<T> {
+    /// First use of the key; process the request
+    Started(IdempotencyGuard<T>),
+    /// The key already completed with this body; return its result
+    Replay(T),
+}
+
+/// Holds an in-flight idempotency key, releasing it if dropped uncompleted
+pub struct IdempotencyGuard<T> {
+    store: Arc<IdempotencyStore<T>>,
+    entry_key: Option<EntryKey>,
+}
+
+impl<T> IdempotencyGuard<T> {
+    /// Record the result to replay for later requests with this key
+    pub fn complete(mut self, result: T) {
+        if let Some(entry_key) = self.entry_key.take() {
+            if let Some(entry) = self.store.entries.lock().unwrap().get_mut(&entry_key) {
+                entry.state = EntryState::Completed(result);
+            }
+        }
+    }
+}
+
+impl<T> Drop for IdempotencyGuard<T> {
+    fn drop(&mut self) {
+        if let Some(entry_key) = self.entry_key.take() {
+            self.store.entries.lock().unwrap().remove(&entry_key);
+        }
+    }
+}
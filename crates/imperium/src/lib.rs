@@ -31,9 +31,15 @@ pub mod handlers;
 pub mod database;
 pub mod cache;
 pub mod types;
+pub mod alerts;
+pub mod lifecycle;
+pub mod decimal_string;
+pub mod fx;
+pub mod idempotency;
+pub mod reports;
 
 pub use api::{create_router, ApiState};
-pub use websocket::{WebSocketHandler, ConnectionManager};
+pub use websocket::{WebSocketHandler, ConnectionManager, ReplayOutcome, DEFAULT_REPLAY_CAPACITY};
 pub use auth::{
     OidcValidator, SessionManager, AuthMiddleware, 
     UserClaims, AuthContext, AuthService, AuthState
@@ -44,7 +50,7 @@ pub use handlers::{
 };
 pub use types::{
     ApiError, PaginationParams, 
-    WebSocketMessage, UserSession
+    WebSocketMessage, AlertSeverity, UserSession
 };
 
 use axum::{
@@ -84,11 +90,17 @@ pub enum ImperiumError {
     #[error("Invalid request: {field} - {reason}")]
     InvalidRequest { field: String, reason: String },
     
+    #[error("Validation failed for {} field(s)", .errors.len())]
+    ValidationFailed { errors: Vec<FieldError> },
+    
     #[error("Rate limit exceeded: {limit} requests per {window}")]
     RateLimitExceeded { limit: u32, window: String },
     
     #[error("WebSocket connection error: {reason}")]
     WebSocketError { reason: String },
+
+    #[error("Notification delivery failed: {reason}")]
+    NotificationFailed { reason: String },
     
     #[error("Exchange operation failed: {source}")]
     ExchangeError { source: prudentia::PrudentiaError },
@@ -99,6 +111,29 @@ pub enum ImperiumError {
     #[error("Risk calculation failed: {source}")]
     RiskError { source: disciplina::PositionSizingError },
     
+    #[error("Trade rejected by risk protocol: {reason}")]
+    RiskRejected {
+        reason: String,
+        violations: Vec<prudentia::ProtocolViolation>,
+        failed_rules: Vec<prudentia::RuleFailure>,
+        rejecting_rules: Vec<String>,
+    },
+    
+    #[error("Not found: {resource}")]
+    NotFound { resource: String },
+    
+    #[error("Idempotency key {key} conflict: {reason}")]
+    IdempotencyConflict { key: String, reason: String },
+    
+    #[error("Request timed out after {timeout_ms}ms")]
+    RequestTimeout { timeout_ms: u64 },
+    
+    #[error("Too many in-flight trades: {limit} already waiting for an OODA cycle slot")]
+    TooManyPendingCycles { limit: usize },
+    
+    #[error("Risk settings were changed recently; retry in {retry_after_secs}s")]
+    SettingsCooldown { retry_after_secs: u64 },
+    
     #[error("Internal server error: {message}")]
     InternalError { message: String },
 }
@@ -132,6 +167,9 @@ pub struct AppState {
     
     /// Authentication service with OIDC validator
     pub auth_service: Arc<AuthService>,
+    
+    /// REST API handler state
+    pub api_state: Arc<ApiState>,
 }
 
 /// Configuration for the Imperium API server
@@ -161,6 +199,12 @@ pub struct AppConfig {
     /// WebSocket settings
     pub websocket_max_connections: u32,
     pub websocket_heartbeat_interval: u32,
+    
+    /// Minimum response size, in bytes, before REST responses are compressed
+    pub compression_min_size: u16,
+    
+    /// Per-route-category request timeouts
+    pub request_timeouts: api::RequestTimeouts,
 }
 
 /// Standard API response wrapper
@@ -169,15 +213,61 @@ pub struct ApiResponse<T> {
     pub success: bool,
     pub data: Option<T>,
     pub error: Option<String>,
+    /// Rule violations behind a risk rejection
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub violations: Vec<prudentia::ProtocolViolation>,
+    /// Rules that failed to run during the assessment behind a rejection
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failed_rules: Vec<prudentia::RuleFailure>,
+    /// Rules that ran and rejected the trade
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rejecting_rules: Vec<String>,
+    /// Per-field problems behind a validation failure
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub field_errors: Vec<FieldError>,
     pub timestamp: chrono::DateTime<chrono::Utc>,
 }
 
+/// A request field that failed validation, and why
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldError {
+    pub field: String,
+    pub reason: String,
+}
+
+impl From<prudentia::LimitValidationError> for FieldError {
+    fn from(error: prudentia::LimitValidationError) -> Self {
+        Self { field: error.field, reason: error.reason }
+    }
+}
+
+impl From<prudentia::ConfigFormatError> for ImperiumError {
+    fn from(error: prudentia::ConfigFormatError) -> Self {
+        match error {
+            prudentia::ConfigFormatError::Invalid(errors) => ImperiumError::ValidationFailed {
+                errors: errors.into_iter().map(FieldError::from).collect(),
+            },
+            prudentia::ConfigFormatError::Parse { .. } => ImperiumError::InvalidRequest {
+                field: "body".to_string(),
+                reason: error.to_string(),
+            },
+            prudentia::ConfigFormatError::Serialize { .. } => ImperiumError::InternalError {
+                message: error.to_string(),
+            },
+        }
+    }
+}
+
 impl<T> ApiResponse<T> {
     pub fn success(data: T) -> Self {
         Self {
             success: true,
             data: Some(data),
             error: None,
+            violations: Vec::new(),
+            failed_rules: Vec::new(),
+            rejecting_rules: Vec::new(),
+            field_errors: Vec::new(),
             timestamp: chrono::Utc::now(),
         }
     }
@@ -187,33 +277,131 @@ impl<T> ApiResponse<T> {
             success: false,
             data: None,
             error: Some(message),
+            violations: Vec::new(),
+            failed_rules: Vec::new(),
+            rejecting_rules: Vec::new(),
+            field_errors: Vec::new(),
             timestamp: chrono::Utc::now(),
         }
     }
+    
+    pub fn with_violations(mut self, violations: Vec<prudentia::ProtocolViolation>) -> Self {
+        self.violations = violations;
+        self
+    }
+    
+    /// Report which rules failed to run apart from those that rejected
+    pub fn with_rule_outcomes(mut self, failed_rules: Vec<prudentia::RuleFailure>, rejecting_rules: Vec<String>) -> Self {
+        self.failed_rules = failed_rules;
+        self.rejecting_rules = rejecting_rules;
+        self
+    }
+    
+    pub fn with_field_errors(mut self, field_errors: Vec<FieldError>) -> Self {
+        self.field_errors = field_errors;
+        self
+    }
 }
 
-/// Implement IntoResponse for ImperiumError
-impl IntoResponse for ImperiumError {
-    fn into_response(self) -> Response {
-        let (status, message) = match &self {
-            ImperiumError::AuthenticationFailed { .. } => {
-                (StatusCode::UNAUTHORIZED, self.to_string())
+impl ImperiumError {
+    /// HTTP status this error is reported with
+    ///
+    /// Risk rejections and protocol violations are the client's to fix (422),
+    /// stale market data may succeed on retry (409), and only genuine
+    /// internal faults are reported as 500.
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            ImperiumError::AuthenticationFailed { .. } => StatusCode::UNAUTHORIZED,
+            ImperiumError::AuthorizationFailed { .. } => StatusCode::FORBIDDEN,
+            ImperiumError::InvalidRequest { .. } | ImperiumError::ValidationFailed { .. } => {
+                StatusCode::BAD_REQUEST
             },
-            ImperiumError::AuthorizationFailed { .. } => {
-                (StatusCode::FORBIDDEN, self.to_string())
+            ImperiumError::NotFound { .. } => StatusCode::NOT_FOUND,
+            ImperiumError::IdempotencyConflict { .. } => StatusCode::CONFLICT,
+            ImperiumError::RateLimitExceeded { .. }
+            | ImperiumError::TooManyPendingCycles { .. }
+            | ImperiumError::SettingsCooldown { .. } => StatusCode::TOO_MANY_REQUESTS,
+            ImperiumError::RequestTimeout { .. } => StatusCode::GATEWAY_TIMEOUT,
+            ImperiumError::NotificationFailed { .. } => StatusCode::BAD_GATEWAY,
+            ImperiumError::RiskRejected { .. } | ImperiumError::RiskError { .. } => {
+                StatusCode::UNPROCESSABLE_ENTITY
             },
-            ImperiumError::InvalidRequest { .. } => {
-                (StatusCode::BAD_REQUEST, self.to_string())
+            ImperiumError::TradingError { source } => formatio_status(source),
+            ImperiumError::ExchangeError { source } => prudentia_status(source),
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+fn formatio_status(error: &formatio::FormatioError) -> StatusCode {
+    use formatio::{DecisionError, ExecutorError, FormatioError, OodaLoopError};
+    
+    match error {
+        FormatioError::StaleMarketData { .. } => StatusCode::CONFLICT,
+        FormatioError::OodaLoopError { source } => match source {
+            OodaLoopError::InvalidObservation { .. } => StatusCode::CONFLICT,
+            OodaLoopError::OrientFailed { source } => orientation_status(source),
+            OodaLoopError::ExecutionNotApproved | OodaLoopError::StopTooClose { .. } => {
+                StatusCode::UNPROCESSABLE_ENTITY
             },
-            ImperiumError::RateLimitExceeded { .. } => {
-                (StatusCode::TOO_MANY_REQUESTS, self.to_string())
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        },
+        FormatioError::OrientationError { source } => orientation_status(source),
+        FormatioError::DecisionError { source: DecisionError::InvalidProposal(_) } => {
+            StatusCode::UNPROCESSABLE_ENTITY
+        },
+        FormatioError::ExecutorError { source: ExecutorError::InvalidModification(_) } => {
+            StatusCode::UNPROCESSABLE_ENTITY
+        },
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+fn orientation_status(error: &formatio::OrientationError) -> StatusCode {
+    match error {
+        // Stale, divergent or malformed market data
+        formatio::OrientationError::InvalidObservation(_) => StatusCode::CONFLICT,
+        formatio::OrientationError::PositionSizingFailed(_) => StatusCode::UNPROCESSABLE_ENTITY,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+fn prudentia_status(error: &prudentia::PrudentiaError) -> StatusCode {
+    use prudentia::PrudentiaError;
+    
+    match error {
+        PrudentiaError::RiskValidationFailure { .. }
+        | PrudentiaError::ProtocolViolation { .. }
+        | PrudentiaError::CircuitBreakerActive { .. }
+        | PrudentiaError::PositionSizingFailure { .. }
+        | PrudentiaError::PortfolioRiskExceeded { .. }
+        | PrudentiaError::DailyLossLimitExceeded { .. }
+        | PrudentiaError::InvalidTradeProposal { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+/// Implement IntoResponse for ImperiumError
+impl IntoResponse for ImperiumError {
+    fn into_response(self) -> Response {
+        let status = self.status_code();
+        let message = if status == StatusCode::INTERNAL_SERVER_ERROR {
+            "Internal server error".to_string()
+        } else {
+            self.to_string()
+        };
+        
+        let response = match self {
+            ImperiumError::RiskRejected { violations, failed_rules, rejecting_rules, .. } => {
+                ApiResponse::<()>::error(message)
+                    .with_violations(violations)
+                    .with_rule_outcomes(failed_rules, rejecting_rules)
             },
-            _ => {
-                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".to_string())
+            ImperiumError::ValidationFailed { errors } => {
+                ApiResponse::<()>::error(message).with_field_errors(errors)
             },
+            _ => ApiResponse::<()>::error(message),
         };
-        
-        let response = ApiResponse::<()>::error(message);
         (status, Json(response)).into_response()
     }
 }
@@ -221,12 +409,28 @@ impl IntoResponse for ImperiumError {
 /// Create the main application router with all middleware and routes
 pub fn create_app_router(state: AppState) -> axum::Router {
     axum::Router::new()
-        .nest("/api/v1", api::create_router())
-        .nest("/ws", websocket::create_router())
+        .nest(
+            "/api/v1",
+            api::routes_with_timeouts(state.config.request_timeouts).layer(
+                axum::middleware::from_fn_with_state(
+                    state.auth_service.clone(),
+                    auth::require_auth,
+                ),
+            ),
+        )
+        .nest(
+            "/ws",
+            websocket::create_router().layer(axum::middleware::from_fn_with_state(
+                state.auth_service.clone(),
+                auth::require_auth,
+            )),
+        )
+        .merge(api::metrics_routes())
         .layer(
             ServiceBuilder::new()
                 .layer(TraceLayer::new_for_http())
                 .layer(CorsLayer::permissive()) // Configure properly for production
+                .layer(middleware::compression_layer(state.config.compression_min_size))
                 // TODO: Add middleware when implementations are complete
                 // .layer(middleware::AuthMiddleware::new(state.auth_service.clone()))
                 // .layer(middleware::RateLimitMiddleware::new(
@@ -283,4 +487,30 @@ mod tests {
         assert!(response.data.is_none());
         assert_eq!(response.error, Some("test error".to_string()));
     }
+    
+    #[test]
+    fn test_wrapped_errors_map_to_client_statuses() {
+        let stale = ImperiumError::TradingError {
+            source: formatio::FormatioError::StaleMarketData {
+                symbol: "BTC/USDT".to_string(),
+                age_ms: 7000,
+            },
+        };
+        assert_eq!(stale.status_code(), StatusCode::CONFLICT);
+        
+        let violation = ImperiumError::ExchangeError {
+            source: prudentia::PrudentiaError::PortfolioRiskExceeded {
+                current: rust_decimal::Decimal::from(12),
+                limit: rust_decimal::Decimal::from(10),
+            },
+        };
+        assert_eq!(violation.status_code(), StatusCode::UNPROCESSABLE_ENTITY);
+        
+        let internal = ImperiumError::TradingError {
+            source: formatio::FormatioError::OodaLoopError {
+                source: formatio::OodaLoopError::NoExecutorConfigured,
+            },
+        };
+        assert_eq!(internal.status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,872 @@
+//! Position lifecycle events
+//!
+//! Every transition a position goes through, from entry submission to its
+//! final exit, is recorded in the `system_events` audit log and pushed to the
+//! owner's WebSocket connections as a `PositionLifecycle` frame. Each event
+//! carries the position's open risk before and after the transition, so
+//! either stream gives a complete timeline of the position for review.
+//!
+//! Positions opened by a trade execution are kept in the [`PositionBook`]
+//! until they fully close, so their exits can be settled against the
+//! trader's protocol. A price feed records their unrealized P&L and moves
+//! their stops to breakeven as they gain (see [`spawn_price_feed`]).
+
+use chrono::{DateTime, Utc};
+use formatio::{BreakevenAutomation, BreakevenWatch, ExecutionPlan, Executor, TradeDirection, TradeSetup};
+use prudentia::{ExitError, ExitReason, OpenPosition};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use testudo_types::OrderSide;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::alerts::{
+    adverse_excursion_event, daily_loss_event, publish_adverse_excursion_alert, publish_daily_loss_alert,
+};
+use crate::api::ApiState;
+use crate::database::{EventSeverity, SystemEvent};
+use crate::reports::ClosedTrade;
+use crate::types::{ClosePositionResponse, ModifyBracketResponse, WebSocketMessage};
+use crate::websocket::ConnectionManager;
+use crate::{ImperiumError, Result};
+
+/// Where a position is in its lifecycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PositionStage {
+    /// Entry submitted but not yet filled
+    Pending,
+    /// Entry filled, full quantity held
+    Open,
+    /// Part of the quantity exited
+    PartiallyClosed,
+    /// Fully exited
+    Closed,
+}
+
+/// What happened to a position
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum PositionTransition {
+    EntrySubmitted,
+    EntryFilled {
+        #[serde(with = "crate::decimal_string")]
+        fill_price: Decimal,
+    },
+    StopMoved {
+        #[serde(with = "crate::decimal_string")]
+        from: Decimal,
+        #[serde(with = "crate::decimal_string")]
+        to: Decimal,
+    },
+    /// Price reached the take-profit; the exit fill follows as its own event
+    TargetHit {
+        #[serde(with = "crate::decimal_string")]
+        price: Decimal,
+    },
+    PartiallyClosed {
+        #[serde(with = "crate::decimal_string")]
+        quantity: Decimal,
+        #[serde(with = "crate::decimal_string")]
+        exit_price: Decimal,
+        #[serde(with = "crate::decimal_string")]
+        remaining_quantity: Decimal,
+    },
+    Closed {
+        #[serde(with = "crate::decimal_string")]
+        quantity: Decimal,
+        #[serde(with = "crate::decimal_string")]
+        exit_price: Decimal,
+    },
+}
+
+impl PositionTransition {
+    /// `system_events` event type the transition is audited under
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            PositionTransition::EntrySubmitted => "POSITION_ENTRY_SUBMITTED",
+            PositionTransition::EntryFilled { .. } => "POSITION_ENTRY_FILLED",
+            PositionTransition::StopMoved { .. } => "POSITION_STOP_MOVED",
+            PositionTransition::TargetHit { .. } => "POSITION_TARGET_HIT",
+            PositionTransition::PartiallyClosed { .. } => "POSITION_PARTIALLY_CLOSED",
+            PositionTransition::Closed { .. } => "POSITION_CLOSED",
+        }
+    }
+}
+
+/// One transition in a position's timeline
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PositionLifecycleEvent {
+    pub position_id: Uuid,
+    pub symbol: String,
+    pub transition: PositionTransition,
+    /// Stage before the transition; `None` for the entry submission
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<PositionStage>,
+    pub to: PositionStage,
+    /// Loss at the stop for the quantity held, in quote currency
+    #[serde(with = "crate::decimal_string")]
+    pub risk_before: Decimal,
+    #[serde(with = "crate::decimal_string")]
+    pub risk_after: Decimal,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl PositionLifecycleEvent {
+    /// Human-readable description for the audit log
+    pub fn message(&self) -> String {
+        let what = match &self.transition {
+            PositionTransition::EntrySubmitted => "entry submitted".to_string(),
+            PositionTransition::EntryFilled { fill_price } => format!("entry filled at {}", fill_price),
+            PositionTransition::StopMoved { from, to } => format!("stop moved from {} to {}", from, to),
+            PositionTransition::TargetHit { price } => format!("take-profit reached at {}", price),
+            PositionTransition::PartiallyClosed { quantity, exit_price, remaining_quantity } => format!(
+                "{} closed at {}, {} remaining",
+                quantity, exit_price, remaining_quantity
+            ),
+            PositionTransition::Closed { quantity, exit_price } => {
+                format!("closed {} at {}", quantity, exit_price)
+            }
+        };
+        format!(
+            "{} position {} {}; risk ${} -> ${}",
+            self.symbol, self.position_id, what, self.risk_before, self.risk_after
+        )
+    }
+}
+
+/// A position's current stage and risk, producing an event per transition
+///
+/// Transitions that do not apply to the current stage (filling an entry
+/// twice, moving the stop of a closed position) are refused and leave the
+/// position unchanged.
+#[derive(Debug, Clone)]
+pub struct PositionLifecycle {
+    position_id: Uuid,
+    symbol: String,
+    direction: TradeDirection,
+    stage: PositionStage,
+    quantity: Decimal,
+    entry_price: Decimal,
+    stop_loss: Decimal,
+}
+
+impl PositionLifecycle {
+    /// Start tracking a submitted entry, returning its first event
+    pub fn submit(
+        position_id: Uuid,
+        symbol: &str,
+        direction: TradeDirection,
+        quantity: Decimal,
+        entry_price: Decimal,
+        stop_loss: Decimal,
+    ) -> (Self, PositionLifecycleEvent) {
+        let lifecycle = Self {
+            position_id,
+            symbol: symbol.to_string(),
+            direction,
+            stage: PositionStage::Pending,
+            quantity,
+            entry_price,
+            stop_loss,
+        };
+        let event = lifecycle.event(PositionTransition::EntrySubmitted, None, Decimal::ZERO);
+        (lifecycle, event)
+    }
+
+    /// The entry filled at `fill_price`
+    pub fn fill(&mut self, fill_price: Decimal) -> Result<PositionLifecycleEvent> {
+        self.require(&[PositionStage::Pending], "fill")?;
+        let risk_before = self.open_risk();
+        self.entry_price = fill_price;
+        self.transition(PositionTransition::EntryFilled { fill_price }, PositionStage::Open, risk_before)
+    }
+
+    /// The stop was moved to `stop_loss`, e.g. to breakeven
+    pub fn move_stop(&mut self, stop_loss: Decimal) -> Result<PositionLifecycleEvent> {
+        self.require(&[PositionStage::Open, PositionStage::PartiallyClosed], "move the stop of")?;
+        let risk_before = self.open_risk();
+        let from = std::mem::replace(&mut self.stop_loss, stop_loss);
+        self.transition(PositionTransition::StopMoved { from, to: stop_loss }, self.stage, risk_before)
+    }
+
+    /// Price reached the take-profit at `price`
+    pub fn target_hit(&mut self, price: Decimal) -> Result<PositionLifecycleEvent> {
+        self.require(&[PositionStage::Open, PositionStage::PartiallyClosed], "mark the target of")?;
+        let risk_before = self.open_risk();
+        self.transition(PositionTransition::TargetHit { price }, self.stage, risk_before)
+    }
+
+    /// `quantity` was exited at `exit_price`; exiting all of it closes the position
+    pub fn close(&mut self, quantity: Decimal, exit_price: Decimal) -> Result<PositionLifecycleEvent> {
+        self.require(&[PositionStage::Open, PositionStage::PartiallyClosed], "close")?;
+        if quantity <= Decimal::ZERO || quantity > self.quantity {
+            return Err(ImperiumError::InvalidRequest {
+                field: "quantity".to_string(),
+                reason: format!("must be positive and at most the {} held", self.quantity),
+            });
+        }
+
+        let risk_before = self.open_risk();
+        self.quantity -= quantity;
+        if self.quantity.is_zero() {
+            self.transition(PositionTransition::Closed { quantity, exit_price }, PositionStage::Closed, risk_before)
+        } else {
+            let transition = PositionTransition::PartiallyClosed {
+                quantity,
+                exit_price,
+                remaining_quantity: self.quantity,
+            };
+            self.transition(transition, PositionStage::PartiallyClosed, risk_before)
+        }
+    }
+
+    pub fn stage(&self) -> PositionStage {
+        self.stage
+    }
+
+    pub fn quantity(&self) -> Decimal {
+        self.quantity
+    }
+
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    /// Profit or loss of exiting `quantity` at `price`
+    pub fn pnl_at(&self, quantity: Decimal, price: Decimal) -> Decimal {
+        match self.direction {
+            TradeDirection::Long => (price - self.entry_price) * quantity,
+            TradeDirection::Short => (self.entry_price - price) * quantity,
+        }
+    }
+
+    /// Why an exit at `price` happens: a stop-out at or past the stop, otherwise manual
+    pub fn exit_reason(&self, price: Decimal) -> ExitReason {
+        let stopped_out = match self.direction {
+            TradeDirection::Long => price <= self.stop_loss,
+            TradeDirection::Short => price >= self.stop_loss,
+        };
+        if stopped_out {
+            ExitReason::StopLoss
+        } else {
+            ExitReason::Manual
+        }
+    }
+
+    /// The remaining position as a bracket setup, without its take-profit
+    fn setup(&self) -> TradeSetup {
+        TradeSetup {
+            symbol: self.symbol.clone(),
+            entry_price: self.entry_price,
+            stop_loss: self.stop_loss,
+            take_profit: None,
+            position_size: self.quantity,
+            side: match self.direction {
+                TradeDirection::Long => OrderSide::Buy,
+                TradeDirection::Short => OrderSide::Sell,
+            },
+        }
+    }
+
+    /// Loss if the remaining quantity stops out; zero once the stop is at or past the entry
+    pub fn open_risk(&self) -> Decimal {
+        if self.stage == PositionStage::Closed {
+            return Decimal::ZERO;
+        }
+        let distance = match self.direction {
+            TradeDirection::Long => self.entry_price - self.stop_loss,
+            TradeDirection::Short => self.stop_loss - self.entry_price,
+        };
+        (distance * self.quantity).max(Decimal::ZERO)
+    }
+
+    fn require(&self, stages: &[PositionStage], action: &str) -> Result<()> {
+        if stages.contains(&self.stage) {
+            return Ok(());
+        }
+        Err(ImperiumError::InvalidRequest {
+            field: "position".to_string(),
+            reason: format!("cannot {} position {} while it is {:?}", action, self.position_id, self.stage),
+        })
+    }
+
+    fn transition(
+        &mut self,
+        transition: PositionTransition,
+        to: PositionStage,
+        risk_before: Decimal,
+    ) -> Result<PositionLifecycleEvent> {
+        let from = std::mem::replace(&mut self.stage, to);
+        Ok(self.event(transition, Some(from), risk_before))
+    }
+
+    fn event(
+        &self,
+        transition: PositionTransition,
+        from: Option<PositionStage>,
+        risk_before: Decimal,
+    ) -> PositionLifecycleEvent {
+        PositionLifecycleEvent {
+            position_id: self.position_id,
+            symbol: self.symbol.clone(),
+            transition,
+            from,
+            to: self.stage,
+            risk_before,
+            risk_after: self.open_risk(),
+            timestamp: Utc::now(),
+        }
+    }
+}
+
+/// Build the audit log entry for a lifecycle event
+pub fn lifecycle_audit_event(user_id: &str, event: &PositionLifecycleEvent) -> SystemEvent {
+    SystemEvent {
+        event_type: event.transition.event_type().to_string(),
+        severity: EventSeverity::Info,
+        component: "imperium".to_string(),
+        message: event.message(),
+        metadata: serde_json::to_value(event).ok(),
+        user_id: Uuid::parse_str(user_id).ok(),
+    }
+}
+
+/// Audit a lifecycle event and stream it to the user's connections
+///
+/// The event is only streamed once it has been audited. Returns the number
+/// of connections it was delivered to.
+pub async fn publish_position_event(
+    api_state: &ApiState,
+    connections: &ConnectionManager,
+    user_id: &str,
+    event: PositionLifecycleEvent,
+) -> Result<usize> {
+    api_state.audit(lifecycle_audit_event(user_id, &event)).await?;
+
+    Ok(connections.send_to_user(user_id, &WebSocketMessage::PositionLifecycle(event)))
+}
+
+/// Audit a lifecycle event, streaming it when the state has connections
+async fn publish(api_state: &ApiState, user_id: &str, event: PositionLifecycleEvent) -> Result<usize> {
+    match api_state.connections() {
+        Some(connections) => publish_position_event(api_state, &connections, user_id, event).await,
+        None => api_state.audit(lifecycle_audit_event(user_id, &event)).await.map(|()| 0),
+    }
+}
+
+/// Default time between the price feed's polls of the exchange
+pub const DEFAULT_PRICE_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// A position opened by a trade execution
+#[derive(Debug, Clone)]
+struct BookedPosition {
+    user_id: String,
+    lifecycle: PositionLifecycle,
+    /// Entry order whose bracket is watched for the breakeven move
+    entry_order_id: String,
+    /// Exchange the entry was placed on, where its bracket is modified
+    venue: String,
+    /// Equity the position was sized against
+    account_equity: Decimal,
+    /// Loss at the original stop for the full entry quantity
+    initial_risk: Decimal,
+    /// Profit or loss of the exits so far
+    realized_pnl: Decimal,
+}
+
+/// Positions opened through the API, held until their final exit
+#[derive(Default)]
+pub struct PositionBook {
+    positions: Mutex<HashMap<Uuid, BookedPosition>>,
+    /// Each user's breakeven watches, settled against their own protocol
+    breakeven: Mutex<HashMap<String, Arc<BreakevenAutomation>>>,
+}
+
+impl PositionBook {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Symbols with a booked position
+    pub async fn symbols(&self) -> BTreeSet<String> {
+        let positions = self.positions.lock().await;
+        positions.values().map(|booked| booked.lifecycle.symbol().to_string()).collect()
+    }
+
+    async fn breakeven_for(&self, user_id: &str, executor: &Arc<Executor>) -> Arc<BreakevenAutomation> {
+        self.breakeven
+            .lock()
+            .await
+            .entry(user_id.to_string())
+            .or_insert_with(|| Arc::new(BreakevenAutomation::new(executor.clone())))
+            .clone()
+    }
+
+    /// A user's position, if it is still open
+    pub async fn position(&self, user_id: &str, position_id: Uuid) -> Option<PositionLifecycle> {
+        let positions = self.positions.lock().await;
+        positions
+            .get(&position_id)
+            .filter(|booked| booked.user_id == user_id)
+            .map(|booked| booked.lifecycle.clone())
+    }
+}
+
+/// Book the position an executed plan opened and publish its entry
+///
+/// The entry is recorded as submitted and then filled at the execution's
+/// average price; the position also joins the user's open positions and,
+/// when breakeven moves are enabled, is watched for one.
+pub async fn open_position(api_state: &ApiState, user_id: &str, plan: &ExecutionPlan) -> Result<()> {
+    let Some(execution) = &plan.execution else {
+        return Ok(());
+    };
+    let setup = &plan.setup;
+    let direction = match setup.side {
+        OrderSide::Buy => TradeDirection::Long,
+        OrderSide::Sell => TradeDirection::Short,
+    };
+    let (mut lifecycle, submitted) = PositionLifecycle::submit(
+        plan.position_id,
+        &setup.symbol,
+        direction,
+        setup.position_size,
+        setup.entry_price,
+        setup.stop_loss,
+    );
+    let filled = lifecycle.fill(execution.average_entry_price)?;
+    let initial_risk = lifecycle.open_risk();
+
+    let mut positions = api_state.positions().positions.lock().await;
+    positions.insert(
+        plan.position_id,
+        BookedPosition {
+            user_id: user_id.to_string(),
+            lifecycle,
+            entry_order_id: execution.order_id.clone(),
+            venue: execution.venue.clone(),
+            account_equity: plan.account_equity,
+            initial_risk,
+            realized_pnl: Decimal::ZERO,
+        },
+    );
+    let open_position = OpenPosition {
+        id: plan.position_id.to_string(),
+        symbol: setup.symbol.clone(),
+        risk_amount: initial_risk,
+        risk_percentage: initial_risk / plan.account_equity,
+        opened_at: SystemTime::now(),
+        unrealized_pnl: Decimal::ZERO,
+        max_adverse_excursion: Decimal::ZERO,
+    };
+    if let Some(rule) = api_state.portfolio_rule() {
+        rule.add_open_position(open_position.clone());
+    }
+    if let Some(rule) = api_state.units_rule() {
+        rule.add_open_quantity(&setup.symbol, setup.position_size);
+    }
+    let mut open_positions = api_state.open_positions(user_id);
+    open_positions.push(open_position);
+    api_state.set_open_positions(user_id, open_positions);
+    drop(positions);
+
+    if let Some(executor) = api_state.breakeven_executor() {
+        let watch = BreakevenWatch::new(&execution.order_id, setup.clone(), plan.account_equity)
+            .with_position_id(plan.position_id)
+            .with_venue(&execution.venue);
+        api_state.positions().breakeven_for(user_id, &executor).await.watch(watch).await;
+    }
+
+    publish(api_state, user_id, submitted).await?;
+    publish(api_state, user_id, filled).await?;
+    Ok(())
+}
+
+/// Exit `quantity` of a user's position at `exit_price`, all of it when `None`
+///
+/// A partial exit shrinks the position's tracked risk; the final exit closes
+/// it in the protocol, which may refuse a discretionary close inside the
+/// minimum hold time, and journals the trade for the daily summary. The
+/// exit's event is published either way, followed by a daily loss alert
+/// when the loss just recorded crosses a threshold.
+pub async fn close_position(
+    api_state: &ApiState,
+    user_id: &str,
+    position_id: Uuid,
+    quantity: Option<Decimal>,
+    exit_price: Decimal,
+) -> Result<ClosePositionResponse> {
+    let not_found = || ImperiumError::NotFound {
+        resource: format!("Position {}", position_id),
+    };
+    let mut positions = api_state.positions().positions.lock().await;
+    let mut booked = positions
+        .get(&position_id)
+        .filter(|booked| booked.user_id == user_id)
+        .cloned()
+        .ok_or_else(not_found)?;
+
+    let quantity = quantity.unwrap_or_else(|| booked.lifecycle.quantity());
+    let pnl = booked.lifecycle.pnl_at(quantity, exit_price);
+    let reason = booked.lifecycle.exit_reason(exit_price);
+    let event = booked.lifecycle.close(quantity, exit_price)?;
+    booked.realized_pnl += pnl;
+    let closed = booked.lifecycle.stage() == PositionStage::Closed;
+
+    let mut daily_loss_alert = None;
+    if let Some(protocol) = api_state.protocol(user_id).await {
+        let mut protocol = protocol.lock().await;
+        if closed {
+            let loss = (booked.realized_pnl < Decimal::ZERO).then(|| -booked.realized_pnl);
+            match protocol.close_tracked_position(position_id, reason, loss.is_some(), loss) {
+                Ok(_) => {}
+                Err(ExitError::NotOpen { .. }) => {
+                    warn!("Position {} closed without being tracked by the protocol", position_id);
+                }
+                Err(e) => {
+                    return Err(ImperiumError::InvalidRequest {
+                        field: "position".to_string(),
+                        reason: e.to_string(),
+                    });
+                }
+            }
+            daily_loss_alert = protocol.check_daily_loss_alert(booked.account_equity);
+        } else {
+            protocol.set_tracked_risk(position_id, booked.lifecycle.open_risk() / booked.account_equity);
+        }
+    }
+
+    let response = ClosePositionResponse {
+        position_id,
+        symbol: booked.lifecycle.symbol().to_string(),
+        stage: booked.lifecycle.stage(),
+        closed_quantity: quantity,
+        remaining_quantity: booked.lifecycle.quantity(),
+        pnl,
+        realized_pnl: booked.realized_pnl,
+    };
+    let remaining_risk = booked.lifecycle.open_risk();
+    if let Some(rule) = api_state.units_rule() {
+        rule.remove_open_quantity(&response.symbol, quantity);
+    }
+    if closed {
+        let realized_r = if booked.initial_risk.is_zero() {
+            Decimal::ZERO
+        } else {
+            booked.realized_pnl / booked.initial_risk
+        };
+        api_state.reports().record_trade(user_id, ClosedTrade {
+            symbol: response.symbol.clone(),
+            account_equity: booked.account_equity,
+            realized_r,
+            pnl: booked.realized_pnl,
+            closed_at: Utc::now(),
+        });
+        if let Some(automation) = api_state.positions().breakeven.lock().await.get(user_id) {
+            automation.unwatch(&booked.entry_order_id).await;
+        }
+        positions.remove(&position_id);
+
+        let id = position_id.to_string();
+        let mut open_positions = api_state.open_positions(user_id);
+        open_positions.retain(|position| position.id != id);
+        api_state.set_open_positions(user_id, open_positions);
+        if let Some(rule) = api_state.portfolio_rule() {
+            rule.remove_open_position(&id);
+        }
+    } else {
+        positions.insert(position_id, booked);
+        set_open_risk(api_state, user_id, position_id, remaining_risk);
+    }
+    drop(positions);
+
+    publish(api_state, user_id, event).await?;
+    if let Some(alert) = daily_loss_alert {
+        match api_state.connections() {
+            Some(connections) => {
+                publish_daily_loss_alert(api_state, &connections, user_id, &alert).await?;
+            }
+            None => api_state.audit(daily_loss_event(user_id, &alert)).await?,
+        }
+    }
+    Ok(response)
+}
+
+/// Move the stop and/or take-profit of a user's open position
+///
+/// The bracket is modified on the exchange through `executor`, which refuses
+/// legs on the wrong side of the market. Only then does a new stop reach the
+/// position's tracked risk, in the protocol and the open positions alike,
+/// and its `StopMoved` event is published. A position whose stop the trader
+/// moved is no longer watched for the breakeven move.
+pub async fn modify_bracket(
+    api_state: &ApiState,
+    executor: &Executor,
+    user_id: &str,
+    position_id: Uuid,
+    stop_loss: Option<Decimal>,
+    take_profit: Option<Decimal>,
+) -> Result<ModifyBracketResponse> {
+    let mut positions = api_state.positions().positions.lock().await;
+    let booked = positions
+        .get_mut(&position_id)
+        .filter(|booked| booked.user_id == user_id)
+        .ok_or_else(|| ImperiumError::NotFound {
+            resource: format!("Position {}", position_id),
+        })?;
+    if !matches!(booked.lifecycle.stage(), PositionStage::Open | PositionStage::PartiallyClosed) {
+        return Err(ImperiumError::InvalidRequest {
+            field: "position".to_string(),
+            reason: format!(
+                "cannot modify the bracket of position {} while it is {:?}",
+                position_id,
+                booked.lifecycle.stage()
+            ),
+        });
+    }
+
+    let modification = executor
+        .modify_bracket(
+            &booked.entry_order_id,
+            &booked.lifecycle.setup(),
+            Some(&booked.venue),
+            stop_loss,
+            take_profit,
+        )
+        .await
+        .map_err(|source| ImperiumError::TradingError { source: source.into() })?;
+
+    let event = match stop_loss {
+        Some(stop_loss) => Some(booked.lifecycle.move_stop(stop_loss)?),
+        None => None,
+    };
+    let open_risk = booked.lifecycle.open_risk();
+    let account_equity = booked.account_equity;
+    let entry_order_id = booked.entry_order_id.clone();
+    if event.is_some() {
+        if let Some(protocol) = api_state.protocol(user_id).await {
+            protocol.lock().await.set_tracked_risk(position_id, open_risk / account_equity);
+        }
+        set_open_risk(api_state, user_id, position_id, open_risk);
+    }
+    drop(positions);
+
+    if let Some(event) = event {
+        if let Some(automation) = api_state.positions().breakeven.lock().await.get(user_id) {
+            automation.unwatch(&entry_order_id).await;
+        }
+        publish(api_state, user_id, event).await?;
+    }
+    Ok(ModifyBracketResponse {
+        position_id,
+        symbol: modification.setup.symbol,
+        stop_loss: modification.setup.stop_loss,
+        take_profit: modification.setup.take_profit,
+        open_risk,
+    })
+}
+
+/// Update the risk a user's open position is recorded with
+fn set_open_risk(api_state: &ApiState, user_id: &str, position_id: Uuid, risk: Decimal) {
+    update_open_position(api_state, user_id, position_id, |position| {
+        position.risk_amount = risk;
+        None::<()>
+    });
+}
+
+/// Apply `update` to a user's open position and mirror it into the portfolio rule
+fn update_open_position<T>(
+    api_state: &ApiState,
+    user_id: &str,
+    position_id: Uuid,
+    update: impl FnOnce(&mut OpenPosition) -> Option<T>,
+) -> Option<T> {
+    let id = position_id.to_string();
+    let mut open_positions = api_state.open_positions(user_id);
+    let position = open_positions.iter_mut().find(|position| position.id == id)?;
+    let updated = update(position);
+    if let Some(rule) = api_state.portfolio_rule() {
+        rule.add_open_position(position.clone());
+    }
+    api_state.set_open_positions(user_id, open_positions);
+    updated
+}
+
+/// Apply a price tick to the booked positions in `symbol`
+///
+/// Each position's unrealized P&L and MAE are recorded, and a position whose
+/// MAE has neared its stop raises an adverse excursion alert. Positions that
+/// have reached their breakeven trigger then have their stops moved to
+/// entry, and each move is published as a `StopMoved` event. A failure for
+/// one user's positions is logged and does not affect others.
+pub async fn on_price(api_state: &ApiState, symbol: &str, price: Decimal) {
+    // Marked under the positions lock, which a close also holds, so a
+    // position closed meanwhile is not written back to the open positions
+    let alerts: Vec<_> = {
+        let positions = api_state.positions().positions.lock().await;
+        positions
+            .iter()
+            .filter(|(_, booked)| booked.lifecycle.symbol() == symbol)
+            .filter_map(|(position_id, booked)| {
+                let unrealized_pnl = booked.lifecycle.pnl_at(booked.lifecycle.quantity(), price);
+                let alert = update_open_position(api_state, &booked.user_id, *position_id, |position| {
+                    position.record_pnl(unrealized_pnl);
+                    api_state.portfolio_rule()?.update_position(&position.id, unrealized_pnl)
+                })?;
+                Some((booked.user_id.clone(), *position_id, alert))
+            })
+            .collect()
+    };
+    for (user_id, position_id, alert) in alerts {
+        warn!("{}", alert.message());
+        let published = match api_state.connections() {
+            Some(connections) => publish_adverse_excursion_alert(api_state, &connections, &user_id, &alert)
+                .await
+                .map(|_| ()),
+            None => api_state.audit(adverse_excursion_event(&user_id, &alert)).await,
+        };
+        if let Err(e) = published {
+            warn!("Failed to publish the adverse excursion alert of position {}: {}", position_id, e);
+        }
+    }
+
+    let automations: Vec<_> = api_state
+        .positions()
+        .breakeven
+        .lock()
+        .await
+        .iter()
+        .map(|(user_id, automation)| (user_id.clone(), automation.clone()))
+        .collect();
+
+    for (user_id, automation) in automations {
+        let Some(protocol) = api_state.protocol(&user_id).await else {
+            continue;
+        };
+        for moved in automation.on_price(symbol, price, &protocol).await {
+            let Some(position_id) = moved.position_id else {
+                continue;
+            };
+            let event = {
+                let mut positions = api_state.positions().positions.lock().await;
+                let Some(booked) = positions.get_mut(&position_id) else {
+                    continue;
+                };
+                let event = booked.lifecycle.move_stop(moved.new_stop);
+                set_open_risk(api_state, &user_id, position_id, booked.lifecycle.open_risk());
+                event
+            };
+            let published = match event {
+                Ok(event) => publish(api_state, &user_id, event).await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = published {
+                warn!("Failed to publish the breakeven move of position {}: {}", position_id, e);
+            }
+        }
+    }
+}
+
+/// Poll the exchange for the price of every booked position's symbol
+///
+/// Every `interval` (normally `DEFAULT_PRICE_POLL_INTERVAL`) each price is
+/// applied with [`on_price`]. Without an exchange adapter there is nothing
+/// to poll and the ticks are skipped.
+pub fn spawn_price_feed(api_state: Arc<ApiState>, interval: Duration) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            let Some(exchange) = api_state.exchange() else {
+                continue;
+            };
+            for symbol in api_state.positions().symbols().await {
+                match exchange.get_market_data(&symbol).await {
+                    Ok(market) => on_price(&api_state, &symbol, market.last_price).await,
+                    Err(e) => warn!("Failed to fetch the price of {}: {:?}", symbol, e),
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::websocket::MessageEncoding;
+    use axum::extract::ws::Message;
+    use rust_decimal_macros::dec;
+
+    #[tokio::test]
+    async fn test_full_lifecycle_is_audited_and_streamed_in_order() {
+        let api_state = ApiState::new();
+        let connections = ConnectionManager::new();
+        let (_, mut rx) = connections.register("trader-1", MessageEncoding::Json);
+
+        let (mut position, submitted) = PositionLifecycle::submit(
+            Uuid::new_v4(),
+            "BTCUSDT",
+            TradeDirection::Long,
+            dec!(0.2),
+            dec!(50000),
+            dec!(49000),
+        );
+        let events = vec![
+            submitted,
+            position.fill(dec!(50000)).unwrap(),
+            position.move_stop(dec!(50000)).unwrap(),
+            position.close(dec!(0.1), dec!(51000)).unwrap(),
+            position.close(dec!(0.1), dec!(52000)).unwrap(),
+        ];
+        for event in events.clone() {
+            assert_eq!(publish_position_event(&api_state, &connections, "trader-1", event).await.unwrap(), 1);
+        }
+
+        let audited: Vec<_> = api_state.audit_events().iter().map(|e| e.event_type.clone()).collect();
+        assert_eq!(audited, [
+            "POSITION_ENTRY_SUBMITTED",
+            "POSITION_ENTRY_FILLED",
+            "POSITION_STOP_MOVED",
+            "POSITION_PARTIALLY_CLOSED",
+            "POSITION_CLOSED",
+        ]);
+
+        let mut streamed = Vec::new();
+        while let Ok(Message::Text(text)) = rx.try_recv() {
+            let WebSocketMessage::Sequenced { message, .. } = serde_json::from_str(&text).unwrap() else {
+                panic!("Expected a sequenced frame, got: {}", text);
+            };
+            match *message {
+                WebSocketMessage::PositionLifecycle(event) => streamed.push(event),
+                other => panic!("Expected PositionLifecycle, got: {:?}", other),
+            }
+        }
+        assert_eq!(streamed, events);
+
+        let stages: Vec<_> = streamed.iter().map(|e| (e.from, e.to)).collect();
+        assert_eq!(stages, [
+            (None, PositionStage::Pending),
+            (Some(PositionStage::Pending), PositionStage::Open),
+            (Some(PositionStage::Open), PositionStage::Open),
+            (Some(PositionStage::Open), PositionStage::PartiallyClosed),
+            (Some(PositionStage::PartiallyClosed), PositionStage::Closed),
+        ]);
+        let risk: Vec<_> = streamed.iter().map(|e| (e.risk_before, e.risk_after)).collect();
+        assert_eq!(risk, [
+            (dec!(0), dec!(200)),
+            (dec!(200), dec!(200)),
+            (dec!(200), dec!(0)),
+            (dec!(0), dec!(0)),
+            (dec!(0), dec!(0)),
+        ]);
+
+        // A closed position accepts no further transitions
+        assert!(position.move_stop(dec!(51000)).is_err());
+        assert_eq!(position.stage(), PositionStage::Closed);
+    }
+}
@@ -1,8 +1,26 @@
 //! middleware (placeholder)
 
 use std::sync::Arc;
+use tower_http::compression::{
+    predicate::{And, DefaultPredicate, Predicate, SizeAbove},
+    CompressionLayer,
+};
 // // use crate::auth::JwtAuth;
 
+/// Default minimum response body size, in bytes, before compression applies
+pub const DEFAULT_COMPRESSION_MIN_SIZE: u16 = 1024;
+
+/// Response compression negotiated through `Accept-Encoding`
+///
+/// Supports gzip and brotli. Bodies smaller than `min_size` bytes are sent
+/// uncompressed, as are images, gRPC and server-sent event streams.
+pub fn compression_layer(min_size: u16) -> CompressionLayer<And<DefaultPredicate, SizeAbove>> {
+    CompressionLayer::new()
+        .gzip(true)
+        .br(true)
+        .compress_when(DefaultPredicate::new().and(SizeAbove::new(min_size)))
+}
+
 pub struct AuthMiddleware;
 pub struct RateLimitMiddleware;
 
@@ -23,3 +41,71 @@ impl RateLimitMiddleware {
 //     .layer(AuthMiddleware::new(auth_service))
 //     .layer(RateLimitMiddleware::new(100))
 // )
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::{to_bytes, Body},
+        http::{header, Request},
+        routing::get,
+        Json, Router,
+    };
+    use std::io::Read;
+    use tower::ServiceExt;
+
+    fn trade_history_app() -> Router {
+        let trades: Vec<serde_json::Value> = (0..500)
+            .map(|i| serde_json::json!({
+                "id": i,
+                "symbol": "BTCUSDT",
+                "side": "Long",
+                "entry_price": "50000",
+                "stop_loss": "48000",
+                "status": "CLOSED",
+            }))
+            .collect();
+
+        Router::new()
+            .route("/trades/history", get(move || async move { Json(trades.clone()) }))
+            .route("/ping", get(|| async { "ok" }))
+            .layer(compression_layer(DEFAULT_COMPRESSION_MIN_SIZE))
+    }
+
+    async fn send(path: &str, accept_encoding: Option<&str>) -> axum::response::Response {
+        let mut request = Request::get(path);
+        if let Some(encoding) = accept_encoding {
+            request = request.header(header::ACCEPT_ENCODING, encoding);
+        }
+        trade_history_app()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_large_response_gzip_compressed_when_accepted() {
+        let response = send("/trades/history", Some("gzip")).await;
+        assert_eq!(response.headers()[header::CONTENT_ENCODING], "gzip");
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let mut json = String::new();
+        flate2::read::GzDecoder::new(&body[..]).read_to_string(&mut json).unwrap();
+        let trades: Vec<serde_json::Value> = serde_json::from_str(&json).unwrap();
+        assert_eq!(trades.len(), 500);
+    }
+
+    #[tokio::test]
+    async fn test_response_plain_without_accept_encoding() {
+        let response = send("/trades/history", None).await;
+        assert!(response.headers().get(header::CONTENT_ENCODING).is_none());
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let trades: Vec<serde_json::Value> = serde_json::from_slice(&body).unwrap();
+        assert_eq!(trades.len(), 500);
+
+        // Small bodies stay uncompressed even when the client accepts gzip
+        let response = send("/ping", Some("gzip")).await;
+        assert!(response.headers().get(header::CONTENT_ENCODING).is_none());
+    }
+}
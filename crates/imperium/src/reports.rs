@@ -0,0 +1,321 @@
+//! Per-user daily trading summaries
+//!
+//! Closed trades are journaled as they happen. When the trading day resets,
+//! each user's trades for the finished day are aggregated into a
+//! `DailySummary`, stored for later retrieval and delivered through the
+//! user's `Notifier`.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use prudentia::ProtocolLimits;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use crate::api::ApiState;
+use crate::types::WebSocketMessage;
+
+/// Length of a trading day, matching Prudentia's daily reset
+pub const DAILY_RESET_INTERVAL: Duration = Duration::from_secs(24 * 3600);
+
+/// A trade closed during the trading day
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClosedTrade {
+    pub symbol: String,
+    /// Account equity when the trade was entered
+    pub account_equity: Decimal,
+    /// Realized profit or loss in R multiples of the initial risk
+    pub realized_r: Decimal,
+    /// Realized profit or loss in quote currency
+    pub pnl: Decimal,
+    pub closed_at: DateTime<Utc>,
+}
+
+/// Aggregate figures for one user's trading day
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DailySummary {
+    pub user_id: String,
+    pub date: NaiveDate,
+    pub trades_taken: u32,
+    pub wins: u32,
+    /// Fraction of trades that closed in profit
+    #[serde(with = "crate::decimal_string")]
+    pub win_rate: Decimal,
+    /// Sum of the day's R multiples
+    #[serde(with = "crate::decimal_string")]
+    pub realized_r: Decimal,
+    #[serde(with = "crate::decimal_string")]
+    pub realized_pnl: Decimal,
+    /// Largest peak-to-trough fall in cumulative P&L during the day
+    #[serde(with = "crate::decimal_string")]
+    pub max_drawdown: Decimal,
+    /// Fraction of the daily loss budget consumed by the day's net loss
+    #[serde(with = "crate::decimal_string")]
+    pub budget_used: Decimal,
+}
+
+impl DailySummary {
+    /// Aggregate a day's trades, in closing order
+    ///
+    /// The daily loss budget is `max_daily_loss` of the equity at the day's
+    /// first trade.
+    pub fn from_trades(
+        user_id: &str,
+        date: NaiveDate,
+        trades: &[ClosedTrade],
+        limits: &ProtocolLimits,
+    ) -> Self {
+        let trades_taken = trades.len() as u32;
+        let wins = trades.iter().filter(|trade| trade.pnl > Decimal::ZERO).count() as u32;
+        let win_rate = if trades_taken == 0 {
+            Decimal::ZERO
+        } else {
+            Decimal::from(wins) / Decimal::from(trades_taken)
+        };
+
+        let mut cumulative = Decimal::ZERO;
+        let mut peak = Decimal::ZERO;
+        let mut max_drawdown = Decimal::ZERO;
+        for trade in trades {
+            cumulative += trade.pnl;
+            peak = peak.max(cumulative);
+            max_drawdown = max_drawdown.max(peak - cumulative);
+        }
+
+        let daily_budget = trades
+            .first()
+            .map(|trade| trade.account_equity * limits.max_daily_loss)
+            .unwrap_or_default();
+        let budget_used = if daily_budget > Decimal::ZERO {
+            (-cumulative).max(Decimal::ZERO) / daily_budget
+        } else {
+            Decimal::ZERO
+        };
+
+        Self {
+            user_id: user_id.to_string(),
+            date,
+            trades_taken,
+            wins,
+            win_rate,
+            realized_r: trades.iter().map(|trade| trade.realized_r).sum(),
+            realized_pnl: cumulative,
+            max_drawdown,
+            budget_used,
+        }
+    }
+}
+
+/// Journal of closed trades and the summaries generated from them
+#[derive(Default)]
+pub struct DailyReports {
+    trades: RwLock<HashMap<String, Vec<ClosedTrade>>>,
+    summaries: RwLock<HashMap<(String, NaiveDate), DailySummary>>,
+}
+
+impl DailyReports {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Journal a closed trade for a user
+    pub fn record_trade(&self, user_id: &str, trade: ClosedTrade) {
+        self.trades
+            .write()
+            .unwrap()
+            .entry(user_id.to_string())
+            .or_default()
+            .push(trade);
+    }
+
+    /// Users with journaled trades
+    pub fn users(&self) -> Vec<String> {
+        self.trades.read().unwrap().keys().cloned().collect()
+    }
+
+    /// Generate and store a user's summary for a date
+    pub fn generate(&self, user_id: &str, date: NaiveDate, limits: &ProtocolLimits) -> DailySummary {
+        let day_trades: Vec<_> = self
+            .trades
+            .read()
+            .unwrap()
+            .get(user_id)
+            .map(|trades| {
+                trades
+                    .iter()
+                    .filter(|trade| trade.closed_at.date_naive() == date)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let summary = DailySummary::from_trades(user_id, date, &day_trades, limits);
+        self.summaries
+            .write()
+            .unwrap()
+            .insert((user_id.to_string(), date), summary.clone());
+        summary
+    }
+
+    /// A previously generated summary
+    pub fn summary(&self, user_id: &str, date: NaiveDate) -> Option<DailySummary> {
+        self.summaries
+            .read()
+            .unwrap()
+            .get(&(user_id.to_string(), date))
+            .cloned()
+    }
+}
+
+/// Generate every user's summary for `date` and deliver it
+///
+/// Each summary goes through the user's own notifier (see
+/// [`ApiState::notifier_for_user`]). Returns the generated summaries.
+/// Delivery failures are logged and do not prevent the summary from being
+/// stored.
+pub async fn close_trading_day(api_state: &ApiState, date: NaiveDate) -> Vec<DailySummary> {
+    let reports = api_state.reports();
+    let mut summaries = Vec::new();
+
+    for user_id in reports.users() {
+        let summary = reports.generate(&user_id, date, &api_state.limits_for_user(&user_id));
+        let message = WebSocketMessage::DailySummary(summary.clone());
+
+        match api_state.notifier_for_user(&user_id) {
+            Some(notifier) => {
+                if let Err(e) = notifier.notify(&user_id, &message).await {
+                    warn!("Failed to deliver daily summary to user {}: {}", user_id, e);
+                }
+            }
+            None => debug!("No notifier for user {}; daily summary stored only", user_id),
+        }
+        summaries.push(summary);
+    }
+
+    info!("📊 Generated {} daily summaries for {}", summaries.len(), date);
+    summaries
+}
+
+/// Close each trading day as the daily reset passes
+///
+/// Every `interval` (normally `DAILY_RESET_INTERVAL`) the day that just
+/// ended is summarized and delivered.
+pub fn spawn_daily_summary_task(api_state: Arc<ApiState>, interval: Duration) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        // The first tick completes immediately; the first day has not ended yet
+        ticker.tick().await;
+        let mut day_start = Utc::now();
+
+        loop {
+            ticker.tick().await;
+            close_trading_day(&api_state, day_start.date_naive()).await;
+            day_start = Utc::now();
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::websocket::{ConnectionManager, MessageEncoding};
+    use crate::types::UserConfiguration;
+    use axum::extract::ws::Message;
+    use chrono::TimeZone;
+    use rust_decimal_macros::dec;
+
+    fn trade(hour: u32, r: Decimal, pnl: Decimal) -> ClosedTrade {
+        ClosedTrade {
+            symbol: "BTC/USDT".to_string(),
+            account_equity: dec!(10000),
+            realized_r: r,
+            pnl,
+            closed_at: Utc.with_ymd_and_hms(2024, 3, 1, hour, 0, 0).unwrap(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_day_of_trades_produces_correct_summary() {
+        let connections = Arc::new(ConnectionManager::new());
+        let (_, mut rx) = connections.register("trader-1", MessageEncoding::Json);
+        let state = ApiState::new().with_notifier(connections.clone());
+        let reports = state.reports();
+        reports.record_trade("trader-1", trade(9, dec!(2), dec!(200)));
+        reports.record_trade("trader-1", trade(11, dec!(-1), dec!(-100)));
+        reports.record_trade("trader-1", trade(13, dec!(-1), dec!(-100)));
+        reports.record_trade("trader-1", trade(15, dec!(-1), dec!(-100)));
+        // A trade on the following day is not part of the summary
+        reports.record_trade("trader-1", ClosedTrade {
+            closed_at: Utc.with_ymd_and_hms(2024, 3, 2, 1, 0, 0).unwrap(),
+            ..trade(0, dec!(3), dec!(300))
+        });
+
+        let date = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        let summaries = close_trading_day(&state, date).await;
+        assert_eq!(summaries.len(), 1);
+
+        let summary = &summaries[0];
+        assert_eq!(summary.trades_taken, 4);
+        assert_eq!(summary.wins, 1);
+        assert_eq!(summary.win_rate, dec!(0.25));
+        assert_eq!(summary.realized_r, dec!(-1));
+        assert_eq!(summary.realized_pnl, dec!(-100));
+        assert_eq!(summary.max_drawdown, dec!(300));
+        // 100 net loss of a 500 budget (5% of 10000)
+        assert_eq!(summary.budget_used, dec!(0.2));
+
+        assert_eq!(reports.summary("trader-1", date).as_ref(), Some(summary));
+
+        let delivered = match rx.recv().await.unwrap() {
+            Message::Text(text) => serde_json::from_str::<WebSocketMessage>(&text).unwrap(),
+            other => panic!("Expected text frame, got: {:?}", other),
+        };
+        assert_eq!(
+            delivered,
+            WebSocketMessage::Sequenced { sequence: 1, message: Box::new(WebSocketMessage::DailySummary(summary.clone())) }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_summary_goes_to_the_webhook_the_user_configured() {
+        use axum::{routing::post, Json, Router};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let webhook_url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let received = Arc::new(RwLock::new(Vec::<serde_json::Value>::new()));
+        let sink = received.clone();
+        let webhook = Router::new().route(
+            "/hook",
+            post(move |Json(body): Json<serde_json::Value>| async move {
+                sink.write().unwrap().push(body);
+            }),
+        );
+        tokio::spawn(async move { axum::serve(listener, webhook).await.unwrap() });
+
+        let connections = Arc::new(ConnectionManager::new());
+        let (_, mut rx) = connections.register("trader-1", MessageEncoding::Json);
+        let state = ApiState::new().with_notifier(connections.clone());
+        state.set_configuration(
+            "trader-1",
+            UserConfiguration {
+                notification_webhook: Some(webhook_url),
+                ..UserConfiguration::for_profile(prudentia::RiskProfile::Standard)
+            },
+        );
+        state.reports().record_trade("trader-1", trade(9, dec!(2), dec!(200)));
+
+        close_trading_day(&state, NaiveDate::from_ymd_opt(2024, 3, 1).unwrap()).await;
+
+        let received = received.read().unwrap();
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0]["user_id"], "trader-1");
+        assert_eq!(received[0]["message"]["type"], "DailySummary");
+        // The webhook replaces, not supplements, the live connections
+        assert!(rx.try_recv().is_err());
+    }
+}
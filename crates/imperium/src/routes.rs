@@ -1,5 +0,0 @@
-use axum::Router;
-
-pub fn create_router() -> Router {
-    Router::new()
-}
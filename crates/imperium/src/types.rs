@@ -1,6 +1,1169 @@
 //! types (placeholder)
 
+use chrono::{DateTime, Utc};
+use formatio::{ExecutionPlan, OodaState, TradeDirection, TradeIntent};
+use prudentia::risk::protocol::ProtocolStatus;
+use prudentia::types::protocol_limits::ProtocolLimitViolation;
+use prudentia::{
+    AdverseExcursionAlert, CommissionSchedule, ConfigFormat, ConfigFormatError, DailyLossAlert, DailyLossAlertLevel, ExchangeCapabilities,
+    ExchangeHealthStatus, FeePreview, Liquidity, OpenPosition, ProtocolLimits, ProtocolViolation, RiskProfile,
+    SymbolRestrictionRule, SymbolRestrictionViolation,
+};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use testudo_types::ExchangeError;
+use uuid::Uuid;
+
+use crate::fx::FxRates;
+use crate::lifecycle::{PositionLifecycleEvent, PositionStage};
+use crate::reports::DailySummary;
+
 pub struct ApiError;
 pub struct PaginationParams;
-pub struct WebSocketMessage;
-pub struct UserSession;
\ No newline at end of file
+pub struct UserSession;
+
+/// Real-time message pushed to WebSocket clients
+///
+/// Messages are tagged with a `type` discriminator so that both JSON and
+/// binary (MessagePack) clients decode the same structure.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum WebSocketMessage {
+    /// Live price tick for a symbol
+    PriceUpdate {
+        symbol: String,
+        #[serde(with = "crate::decimal_string")]
+        bid_price: Decimal,
+        #[serde(with = "crate::decimal_string")]
+        ask_price: Decimal,
+        #[serde(with = "crate::decimal_string")]
+        last_price: Decimal,
+        timestamp: DateTime<Utc>,
+    },
+
+    /// Change to one of the connected user's open positions
+    PositionUpdate {
+        symbol: String,
+        direction: TradeDirection,
+        #[serde(with = "crate::decimal_string")]
+        quantity: Decimal,
+        #[serde(with = "crate::decimal_string")]
+        entry_price: Decimal,
+        #[serde(with = "crate::decimal_string")]
+        stop_loss: Decimal,
+        #[serde(with = "crate::decimal_string")]
+        unrealized_pnl: Decimal,
+        timestamp: DateTime<Utc>,
+    },
+
+    /// Risk warning or critical alert for the connected user
+    RiskAlert {
+        severity: AlertSeverity,
+        rule: String,
+        message: String,
+        timestamp: DateTime<Utc>,
+    },
+
+    /// End-of-day trading summary for the connected user
+    DailySummary(DailySummary),
+
+    /// An OODA cycle for `symbol` moved between phases
+    OodaTransition {
+        symbol: String,
+        from: String,
+        to: String,
+        /// Failure reason when `to` is `Failed`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        reason: Option<String>,
+        timestamp: DateTime<Utc>,
+    },
+
+    /// One of the connected user's positions changed stage, stop or size
+    PositionLifecycle(PositionLifecycleEvent),
+
+    /// The circuit breaker was manually reset and trading may resume
+    CircuitBreakerReset {
+        reset_by: String,
+        timestamp: DateTime<Utc>,
+    },
+
+    /// First frame on a new connection; `latest_sequence` is the resume point
+    /// for messages sent from here on
+    Hello {
+        connection_id: Uuid,
+        latest_sequence: u64,
+        timestamp: DateTime<Utc>,
+    },
+
+    /// A client frame that could not be decoded or is not a command
+    Error {
+        code: String,
+        message: String,
+        timestamp: DateTime<Utc>,
+    },
+
+    /// Keep-alive frame sent at the configured heartbeat interval
+    Heartbeat {
+        timestamp: DateTime<Utc>,
+    },
+
+    /// Client command asking for an immediate `PortfolioSnapshot` frame
+    RequestSnapshot,
+
+    /// Full portfolio state, sent in reply to `RequestSnapshot`
+    PortfolioSnapshot(PortfolioSnapshotFrame),
+
+    /// Client command asking for messages sent after `last_sequence`
+    Resume {
+        last_sequence: u64,
+    },
+
+    /// A message sent to the connected user, numbered so the client can
+    /// `Resume` from the last sequence it saw
+    Sequenced {
+        sequence: u64,
+        message: Box<WebSocketMessage>,
+    },
+
+    /// A buffered message re-sent in reply to `Resume`
+    Replay {
+        sequence: u64,
+        message: Box<WebSocketMessage>,
+    },
+
+    /// Sent after the replayed messages; later live messages follow `latest_sequence`
+    ReplayComplete {
+        latest_sequence: u64,
+    },
+
+    /// The resume point was evicted from the replay buffer; the client must
+    /// re-sync with `RequestSnapshot`
+    SnapshotRequired {
+        latest_sequence: u64,
+    },
+}
+
+impl WebSocketMessage {
+    /// Frame announcing that an OODA cycle moved from `from` to `to`
+    pub fn ooda_transition(symbol: &str, from: &OodaState, to: &OodaState) -> Self {
+        let reason = match to {
+            OodaState::Failed(reason) => Some(reason.clone()),
+            _ => None,
+        };
+        WebSocketMessage::OodaTransition {
+            symbol: symbol.to_string(),
+            from: ooda_phase(from).to_string(),
+            to: ooda_phase(to).to_string(),
+            reason,
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// Reply to a client frame that was not understood
+    pub fn error(code: &str, message: impl Into<String>) -> Self {
+        WebSocketMessage::Error {
+            code: code.to_string(),
+            message: message.into(),
+            timestamp: Utc::now(),
+        }
+    }
+}
+
+fn ooda_phase(state: &OodaState) -> &'static str {
+    match state {
+        OodaState::Idle => "Idle",
+        OodaState::Observing => "Observing",
+        OodaState::Orienting => "Orienting",
+        OodaState::Deciding => "Deciding",
+        OodaState::Acting => "Acting",
+        OodaState::Completed => "Completed",
+        OodaState::Failed(_) => "Failed",
+    }
+}
+
+/// Severity of a risk alert pushed to clients
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AlertSeverity {
+    Warning,
+    Critical,
+}
+
+impl From<&DailyLossAlert> for WebSocketMessage {
+    fn from(alert: &DailyLossAlert) -> Self {
+        let severity = match alert.level {
+            DailyLossAlertLevel::Warning => AlertSeverity::Warning,
+            DailyLossAlertLevel::Critical => AlertSeverity::Critical,
+        };
+
+        WebSocketMessage::RiskAlert {
+            severity,
+            rule: "DailyLossLimit".to_string(),
+            message: alert.message(),
+            timestamp: Utc::now(),
+        }
+    }
+}
+
+impl From<&AdverseExcursionAlert> for WebSocketMessage {
+    fn from(alert: &AdverseExcursionAlert) -> Self {
+        WebSocketMessage::RiskAlert {
+            severity: AlertSeverity::Warning,
+            rule: "MaxAdverseExcursion".to_string(),
+            message: alert.message(),
+            timestamp: Utc::now(),
+        }
+    }
+}
+
+/// Position sizing method applied to a user's trades
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SizingMethod {
+    /// Position Size = (Account Equity × Risk %) ÷ (Entry − Stop)
+    #[default]
+    VanTharp,
+}
+
+/// A risk rule enabled for a user, with the parameters it enforces
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuleConfiguration {
+    pub name: String,
+    pub parameters: BTreeMap<String, String>,
+}
+
+impl RuleConfiguration {
+    fn new<const N: usize>(name: &str, parameters: [(&str, String); N]) -> Self {
+        Self {
+            name: name.to_string(),
+            parameters: parameters
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect(),
+        }
+    }
+}
+
+/// Exchange a user's orders are routed to while it is healthy
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExchangeRouting {
+    /// Preferred exchange for every symbol; `None` uses the failover primary
+    #[serde(default)]
+    pub default_exchange: Option<String>,
+    /// Per-symbol preferences taking precedence over the default
+    #[serde(default)]
+    pub symbol_overrides: BTreeMap<String, String>,
+}
+
+impl ExchangeRouting {
+    /// Preferred exchange for a symbol, if any
+    pub fn exchange_for(&self, symbol: &str) -> Option<String> {
+        self.symbol_overrides
+            .get(symbol)
+            .or(self.default_exchange.as_ref())
+            .cloned()
+    }
+}
+
+/// Effective risk configuration for a user
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserConfiguration {
+    pub risk_profile: RiskProfile,
+    pub protocol_limits: ProtocolLimits,
+    pub enabled_rules: Vec<RuleConfiguration>,
+    pub sizing_method: SizingMethod,
+    pub feature_flags: BTreeMap<String, bool>,
+    /// Currency amounts are displayed in; computation stays in the quote currency
+    #[serde(default = "default_base_currency")]
+    pub base_currency: String,
+    /// Symbols the user may not trade, outright or during blackouts
+    #[serde(default)]
+    pub symbol_restrictions: SymbolRestrictionRule,
+    /// Preferred execution venues; unhealthy venues fail over to the primary
+    #[serde(default)]
+    pub exchange_routing: ExchangeRouting,
+    /// URL notifications are POSTed to instead of the user's live connections
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notification_webhook: Option<String>,
+}
+
+fn default_base_currency() -> String {
+    crate::fx::QUOTE_CURRENCY.to_string()
+}
+
+impl UserConfiguration {
+    /// Default configuration for a risk profile
+    pub fn for_profile(risk_profile: RiskProfile) -> Self {
+        let protocol_limits = match risk_profile {
+            RiskProfile::Conservative => ProtocolLimits::conservative_limits(),
+            RiskProfile::Standard => ProtocolLimits::default_limits(),
+            RiskProfile::Aggressive => ProtocolLimits::aggressive_limits(),
+        };
+
+        Self {
+            risk_profile,
+            enabled_rules: Self::rules_for_limits(&protocol_limits),
+            protocol_limits,
+            sizing_method: SizingMethod::default(),
+            feature_flags: BTreeMap::new(),
+            base_currency: default_base_currency(),
+            symbol_restrictions: SymbolRestrictionRule::default(),
+            exchange_routing: ExchangeRouting::default(),
+            notification_webhook: None,
+        }
+    }
+
+    /// Replace the protocol limits, keeping the rule parameters in sync
+    pub fn with_limits(mut self, protocol_limits: ProtocolLimits) -> Self {
+        self.enabled_rules = Self::rules_for_limits(&protocol_limits);
+        self.protocol_limits = protocol_limits;
+        self
+    }
+
+    /// Display amounts in a different currency
+    pub fn with_base_currency(mut self, currency: &str) -> Self {
+        self.base_currency = currency.to_ascii_uppercase();
+        self
+    }
+
+    /// Replace the user's symbol restrictions
+    pub fn with_symbol_restrictions(mut self, symbol_restrictions: SymbolRestrictionRule) -> Self {
+        self.symbol_restrictions = symbol_restrictions;
+        self
+    }
+
+    /// Replace the user's exchange routing preferences
+    pub fn with_exchange_routing(mut self, exchange_routing: ExchangeRouting) -> Self {
+        self.exchange_routing = exchange_routing;
+        self
+    }
+
+    /// Enable or disable a feature flag
+    pub fn with_feature_flag(mut self, flag: &str, enabled: bool) -> Self {
+        self.feature_flags.insert(flag.to_string(), enabled);
+        self
+    }
+
+    fn rules_for_limits(limits: &ProtocolLimits) -> Vec<RuleConfiguration> {
+        vec![
+            RuleConfiguration::new("MaxTradeRisk", [
+                ("max_individual_trade_risk", limits.max_individual_trade_risk.to_string()),
+                ("min_individual_trade_risk", limits.min_individual_trade_risk.to_string()),
+            ]),
+            RuleConfiguration::new("MaxPortfolioRisk", [
+                ("max_total_portfolio_risk", limits.max_total_portfolio_risk.to_string()),
+            ]),
+            RuleConfiguration::new("DailyLossLimit", [
+                ("max_daily_loss", limits.max_daily_loss.to_string()),
+            ]),
+            RuleConfiguration::new("ConsecutiveLossLimit", [
+                ("max_consecutive_losses", limits.max_consecutive_losses.to_string()),
+            ]),
+            RuleConfiguration::new("MinRewardRisk", [
+                ("min_reward_risk_ratio", limits.min_reward_risk_ratio.to_string()),
+                ("missing_take_profit_policy", format!("{:?}", limits.missing_take_profit_policy)),
+                ("exit_strategy", format!("{:?}", limits.exit_strategy)),
+            ]),
+            RuleConfiguration::new("MaxOpenPositions", [
+                ("max_open_positions", limits.max_open_positions.to_string()),
+                ("strict", limits.strict_max_open_positions.to_string()),
+            ]),
+        ]
+    }
+}
+
+/// A user's editable risk settings, read and written as one document
+///
+/// `enabled_rules` is derived from the limits: it is reported for display
+/// and ignored on update.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RiskSettings {
+    pub risk_profile: RiskProfile,
+    pub protocol_limits: ProtocolLimits,
+    pub sizing_method: SizingMethod,
+    #[serde(default)]
+    pub enabled_rules: Vec<RuleConfiguration>,
+}
+
+impl From<&UserConfiguration> for RiskSettings {
+    fn from(configuration: &UserConfiguration) -> Self {
+        Self {
+            risk_profile: configuration.risk_profile,
+            protocol_limits: configuration.protocol_limits.clone(),
+            sizing_method: configuration.sizing_method,
+            enabled_rules: configuration.enabled_rules.clone(),
+        }
+    }
+}
+
+impl RiskSettings {
+    /// Write the settings as a document for version control
+    pub fn export(&self, format: ConfigFormat) -> Result<String, ConfigFormatError> {
+        format.serialize(self)
+    }
+
+    /// Load settings from an exported document, validating the limits
+    pub fn load(format: ConfigFormat, document: &str) -> Result<Self, ConfigFormatError> {
+        let settings: Self = format.deserialize(document)?;
+        settings
+            .protocol_limits
+            .validate()
+            .map_err(ConfigFormatError::Invalid)?;
+        Ok(settings)
+    }
+}
+
+/// Downloadable snapshot of a user's active configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigSnapshot {
+    pub user_id: String,
+    pub generated_at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub configuration: UserConfiguration,
+}
+
+/// Body of a trade execution request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecuteTradeRequest {
+    pub symbol: String,
+    pub direction: TradeDirection,
+    #[serde(with = "crate::decimal_string")]
+    pub account_equity: Decimal,
+    #[serde(with = "crate::decimal_string")]
+    pub risk_percentage: Decimal,
+    /// Spend this much quote currency ("buy $500 of BTC") instead of sizing
+    /// from `risk_percentage`; the resulting position's risk is still checked
+    #[serde(default, with = "crate::decimal_string::option")]
+    pub quote_amount: Option<Decimal>,
+}
+
+impl ExecuteTradeRequest {
+    pub fn into_intent(self) -> TradeIntent {
+        TradeIntent {
+            symbol: self.symbol,
+            direction: self.direction,
+            account_equity: self.account_equity,
+            risk_percentage: self.risk_percentage,
+            preferred_exchange: None,
+            quote_amount: self.quote_amount,
+        }
+    }
+}
+
+/// Outcome of an executed OODA cycle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecuteTradeResponse {
+    pub symbol: String,
+    pub approved: bool,
+    #[serde(with = "crate::decimal_string")]
+    pub entry_price: Decimal,
+    #[serde(with = "crate::decimal_string")]
+    pub stop_loss: Decimal,
+    #[serde(with = "crate::decimal_string::option")]
+    pub take_profit: Option<Decimal>,
+    #[serde(with = "crate::decimal_string")]
+    pub position_size: Decimal,
+    pub risk_assessment: String,
+    /// Size originally requested, present only when the position was shrunk
+    /// to fit the remaining portfolio risk budget
+    #[serde(
+        default,
+        with = "crate::decimal_string::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub requested_position_size: Option<Decimal>,
+    /// Id of the opened position, used to close it
+    pub position_id: Uuid,
+    /// Rules that failed to run; reported apart from rules that rejected
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failed_rules: Vec<prudentia::RuleFailure>,
+}
+
+impl From<&ExecutionPlan> for ExecuteTradeResponse {
+    fn from(plan: &ExecutionPlan) -> Self {
+        Self {
+            symbol: plan.setup.symbol.clone(),
+            approved: plan.approved,
+            entry_price: plan.setup.entry_price,
+            stop_loss: plan.setup.stop_loss,
+            take_profit: plan.setup.take_profit,
+            position_size: plan.setup.position_size,
+            risk_assessment: plan.risk_assessment.clone(),
+            requested_position_size: plan.size_reduction.as_ref().map(|reduction| reduction.requested_size),
+            position_id: plan.position_id,
+            failed_rules: plan.failed_rules.clone(),
+        }
+    }
+}
+
+/// Body of a request to exit an open position
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClosePositionRequest {
+    #[serde(with = "crate::decimal_string")]
+    pub exit_price: Decimal,
+    /// Quantity to exit; the whole remaining position when omitted
+    #[serde(default, with = "crate::decimal_string::option")]
+    pub quantity: Option<Decimal>,
+}
+
+/// Outcome of exiting part or all of a position
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClosePositionResponse {
+    pub position_id: Uuid,
+    pub symbol: String,
+    pub stage: PositionStage,
+    #[serde(with = "crate::decimal_string")]
+    pub closed_quantity: Decimal,
+    #[serde(with = "crate::decimal_string")]
+    pub remaining_quantity: Decimal,
+    /// Profit or loss of this exit
+    #[serde(with = "crate::decimal_string")]
+    pub pnl: Decimal,
+    /// Profit or loss of every exit of the position so far
+    #[serde(with = "crate::decimal_string")]
+    pub realized_pnl: Decimal,
+}
+
+/// Body of a request to move an open position's bracket legs
+///
+/// At least one of the legs must be given; an omitted leg is left in place.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModifyBracketRequest {
+    #[serde(default, with = "crate::decimal_string::option")]
+    pub stop_loss: Option<Decimal>,
+    #[serde(default, with = "crate::decimal_string::option")]
+    pub take_profit: Option<Decimal>,
+}
+
+/// Outcome of moving a position's bracket legs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModifyBracketResponse {
+    pub position_id: Uuid,
+    pub symbol: String,
+    #[serde(with = "crate::decimal_string")]
+    pub stop_loss: Decimal,
+    /// New take-profit; absent when the request left it in place
+    #[serde(with = "crate::decimal_string::option")]
+    pub take_profit: Option<Decimal>,
+    /// Loss if the position stops out at the new stop
+    #[serde(with = "crate::decimal_string")]
+    pub open_risk: Decimal,
+}
+
+/// A position opened on another platform, to be registered with the protocol
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportedPosition {
+    pub symbol: String,
+    pub direction: TradeDirection,
+    #[serde(with = "crate::decimal_string")]
+    pub quantity: Decimal,
+    #[serde(with = "crate::decimal_string")]
+    pub entry_price: Decimal,
+    /// The position's current stop; risk is measured from entry to here
+    #[serde(with = "crate::decimal_string")]
+    pub stop_loss: Decimal,
+    /// Exchange holding the position, when known
+    #[serde(default)]
+    pub exchange: Option<String>,
+    /// Caller-chosen id; an import retried with the same ids skips positions
+    /// already registered instead of counting them twice
+    #[serde(default)]
+    pub position_id: Option<Uuid>,
+}
+
+/// Body of a bulk position import
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportPositionsRequest {
+    /// Account equity the positions' risk is measured against
+    #[serde(with = "crate::decimal_string")]
+    pub account_equity: Decimal,
+    pub positions: Vec<ImportedPosition>,
+}
+
+/// An imported position as registered with the protocol
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportedPositionSummary {
+    pub position_id: Uuid,
+    pub symbol: String,
+    /// Loss if the stop is hit, in quote currency
+    #[serde(with = "crate::decimal_string")]
+    pub risk_amount: Decimal,
+    /// Risk as a fraction of account equity
+    #[serde(with = "crate::decimal_string")]
+    pub risk_percentage: Decimal,
+}
+
+/// Outcome of a bulk position import
+///
+/// Imported positions already exist, so they are registered even when they
+/// break the protocol limits; `warnings` says which limits are exceeded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportPositionsResponse {
+    pub positions: Vec<ImportedPositionSummary>,
+    #[serde(with = "crate::decimal_string")]
+    pub total_portfolio_risk: Decimal,
+    #[serde(with = "crate::decimal_string")]
+    pub max_portfolio_risk: Decimal,
+    pub open_positions: u32,
+    pub warnings: Vec<String>,
+}
+
+/// Outcome of a maintenance backfill
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackfillResponse {
+    /// Rows updated; zero when nothing was left to backfill
+    pub updated: u64,
+}
+
+/// Outcome of a recent risk assessment, kept for support
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecentAssessment {
+    pub symbol: String,
+    pub approved: bool,
+    pub risk_assessment: String,
+    pub violations: Vec<ProtocolViolation>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failed_rules: Vec<prudentia::RuleFailure>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rejecting_rules: Vec<String>,
+    pub assessed_at: DateTime<Utc>,
+}
+
+impl From<&ExecutionPlan> for RecentAssessment {
+    fn from(plan: &ExecutionPlan) -> Self {
+        Self {
+            symbol: plan.setup.symbol.clone(),
+            approved: plan.approved,
+            risk_assessment: plan.risk_assessment.clone(),
+            violations: plan.violations.clone(),
+            failed_rules: plan.failed_rules.clone(),
+            rejecting_rules: plan.rejecting_rules.clone(),
+            assessed_at: Utc::now(),
+        }
+    }
+}
+
+/// Protocol state figures exposed through the API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProtocolStatusSummary {
+    #[serde(with = "crate::decimal_string")]
+    pub total_portfolio_risk: Decimal,
+    pub consecutive_losses: u32,
+    #[serde(with = "crate::decimal_string")]
+    pub daily_loss: Decimal,
+    pub open_positions: u32,
+    pub pending_positions: u32,
+    pub circuit_breaker_active: bool,
+    #[serde(with = "crate::decimal_string")]
+    pub risk_utilization: Decimal,
+}
+
+impl From<&ProtocolStatus> for ProtocolStatusSummary {
+    fn from(status: &ProtocolStatus) -> Self {
+        Self {
+            total_portfolio_risk: status.total_portfolio_risk,
+            consecutive_losses: status.consecutive_losses,
+            daily_loss: status.daily_loss,
+            open_positions: status.open_positions,
+            pending_positions: status.pending_positions,
+            circuit_breaker_active: status.circuit_breaker_active,
+            risk_utilization: status.risk_utilization,
+        }
+    }
+}
+
+/// Read-only view of another user's risk context for support staff
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImpersonationResponse {
+    /// Admin who requested the view
+    pub viewed_by: String,
+    pub config: ConfigSnapshot,
+    /// Most recent first
+    pub recent_assessments: Vec<RecentAssessment>,
+    /// `None` until the user's protocol state has been created
+    pub protocol_status: Option<ProtocolStatusSummary>,
+    /// Rules the user's trades are checked against, with their parameters
+    pub effective_rules: Vec<RuleConfiguration>,
+}
+
+/// A user's portfolio figures in the native quote currency
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct PortfolioSnapshot {
+    pub account_equity: Decimal,
+    pub realized_pnl: Decimal,
+    pub open_risk: Decimal,
+    pub open_positions: u32,
+    pub circuit_breaker_active: bool,
+}
+
+/// Portfolio figures rendered in the user's base currency
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PortfolioResponse {
+    /// Currency the amounts are expressed in
+    pub currency: String,
+    /// False when no FX rate was available and amounts are in the quote currency
+    pub converted: bool,
+    #[serde(with = "crate::decimal_string")]
+    pub account_equity: Decimal,
+    #[serde(with = "crate::decimal_string")]
+    pub realized_pnl: Decimal,
+    #[serde(with = "crate::decimal_string")]
+    pub open_risk: Decimal,
+}
+
+impl PortfolioResponse {
+    /// Render a snapshot in `base_currency` using the known FX rates
+    pub fn render(snapshot: &PortfolioSnapshot, base_currency: &str, rates: &FxRates) -> Self {
+        let conversion = rates.conversion_to(base_currency);
+        let currency = if conversion.converted { base_currency } else { crate::fx::QUOTE_CURRENCY };
+
+        Self {
+            currency: currency.to_string(),
+            converted: conversion.converted,
+            account_equity: conversion.apply(snapshot.account_equity),
+            realized_pnl: conversion.apply(snapshot.realized_pnl),
+            open_risk: conversion.apply(snapshot.open_risk),
+        }
+    }
+}
+
+/// One open position's share of the portfolio risk budget
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PositionHeat {
+    pub position_id: String,
+    pub symbol: String,
+    /// Loss at the stop, in the display currency
+    #[serde(with = "crate::decimal_string")]
+    pub risk_amount: Decimal,
+    /// Fraction of the portfolio risk budget this position consumes
+    #[serde(with = "crate::decimal_string")]
+    pub budget_share: Decimal,
+}
+
+/// Portfolio heat: how much of the risk budget open positions consume
+///
+/// Positions are ordered hottest first for heat-map rendering.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PortfolioHeat {
+    /// Currency the amounts are expressed in
+    pub currency: String,
+    /// False when no FX rate was available and amounts are in the quote currency
+    pub converted: bool,
+    pub positions: Vec<PositionHeat>,
+    /// Sum of the positions' risk amounts
+    #[serde(with = "crate::decimal_string")]
+    pub total_risk: Decimal,
+    /// Maximum portfolio risk allowed by the protocol limits
+    #[serde(with = "crate::decimal_string")]
+    pub risk_budget: Decimal,
+    #[serde(with = "crate::decimal_string")]
+    pub remaining_budget: Decimal,
+    /// Fraction of the risk budget consumed; above 1 when over budget
+    #[serde(with = "crate::decimal_string")]
+    pub utilization: Decimal,
+}
+
+impl PortfolioHeat {
+    /// Render open positions against the user's risk budget
+    pub fn render(
+        snapshot: &PortfolioSnapshot,
+        positions: &[OpenPosition],
+        configuration: &UserConfiguration,
+        rates: &FxRates,
+    ) -> Self {
+        let conversion = rates.conversion_to(&configuration.base_currency);
+        let currency = if conversion.converted {
+            configuration.base_currency.as_str()
+        } else {
+            crate::fx::QUOTE_CURRENCY
+        };
+        let risk_budget = conversion
+            .apply(configuration.protocol_limits.max_total_portfolio_risk * snapshot.account_equity);
+        let share_of_budget = |amount: Decimal| {
+            if risk_budget > Decimal::ZERO {
+                amount / risk_budget
+            } else {
+                Decimal::ZERO
+            }
+        };
+
+        let mut heat: Vec<PositionHeat> = positions
+            .iter()
+            .map(|position| {
+                let risk_amount = conversion.apply(position.risk_amount);
+                PositionHeat {
+                    position_id: position.id.clone(),
+                    symbol: position.symbol.clone(),
+                    risk_amount,
+                    budget_share: share_of_budget(risk_amount),
+                }
+            })
+            .collect();
+        heat.sort_by(|a, b| b.risk_amount.cmp(&a.risk_amount).then_with(|| a.symbol.cmp(&b.symbol)));
+        let total_risk: Decimal = heat.iter().map(|position| position.risk_amount).sum();
+
+        Self {
+            currency: currency.to_string(),
+            converted: conversion.converted,
+            positions: heat,
+            total_risk,
+            risk_budget,
+            remaining_budget: (risk_budget - total_risk).max(Decimal::ZERO),
+            utilization: share_of_budget(total_risk),
+        }
+    }
+}
+
+/// Full portfolio state for a (re)connecting WebSocket client
+///
+/// Incremental updates only make sense on top of current state, so clients
+/// request one of these first.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PortfolioSnapshotFrame {
+    /// The same figures served by GET /api/v1/portfolio
+    pub portfolio: PortfolioResponse,
+    pub open_positions: u32,
+    /// Positions that may still be opened under the protocol limit
+    pub remaining_position_slots: u32,
+    /// Portfolio risk still available under the protocol limit, in the display currency
+    #[serde(with = "crate::decimal_string")]
+    pub remaining_risk_budget: Decimal,
+    pub circuit_breaker_active: bool,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl PortfolioSnapshotFrame {
+    /// Render a snapshot against the user's configuration and the known FX rates
+    pub fn render(
+        snapshot: &PortfolioSnapshot,
+        configuration: &UserConfiguration,
+        rates: &FxRates,
+    ) -> Self {
+        let limits = &configuration.protocol_limits;
+        let risk_budget = limits.max_total_portfolio_risk * snapshot.account_equity - snapshot.open_risk;
+        let conversion = rates.conversion_to(&configuration.base_currency);
+
+        Self {
+            portfolio: PortfolioResponse::render(snapshot, &configuration.base_currency, rates),
+            open_positions: snapshot.open_positions,
+            remaining_position_slots: limits.max_open_positions.saturating_sub(snapshot.open_positions),
+            remaining_risk_budget: conversion.apply(risk_budget.max(Decimal::ZERO)),
+            circuit_breaker_active: snapshot.circuit_breaker_active,
+            timestamp: Utc::now(),
+        }
+    }
+}
+
+/// Why a symbol cannot be traded right now
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "reason", rename_all = "snake_case")]
+pub enum NotTradableReason {
+    /// The exchange does not list the symbol (delisted or halted)
+    Delisted,
+    /// The symbol is on the user's restriction list
+    Restricted { detail: String },
+    /// The symbol is in a trading blackout window
+    Blackout { ends_at: DateTime<Utc> },
+}
+
+impl From<SymbolRestrictionViolation> for NotTradableReason {
+    fn from(violation: SymbolRestrictionViolation) -> Self {
+        match violation {
+            SymbolRestrictionViolation::Restricted { reason } => {
+                NotTradableReason::Restricted { detail: reason }
+            }
+            SymbolRestrictionViolation::Blackout { ends_at } => NotTradableReason::Blackout { ends_at },
+        }
+    }
+}
+
+/// Whether a symbol may be traded, checked before order entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolTradability {
+    pub symbol: String,
+    pub tradable: bool,
+    /// Empty when `tradable` is true
+    pub reasons: Vec<NotTradableReason>,
+}
+
+/// A proposed stop to check against the exchange's stop-trigger rules
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StopValidationRequest {
+    pub symbol: String,
+    pub direction: TradeDirection,
+    #[serde(with = "crate::decimal_string")]
+    pub stop_loss: Decimal,
+    /// Price the stop is measured from; the last traded price when absent
+    #[serde(default, with = "crate::decimal_string::option")]
+    pub entry_price: Option<Decimal>,
+}
+
+/// Whether the exchange would accept a stop, and the closest one it would
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StopValidation {
+    pub symbol: String,
+    pub valid: bool,
+    #[serde(with = "crate::decimal_string")]
+    pub stop_loss: Decimal,
+    #[serde(with = "crate::decimal_string")]
+    pub reference_price: Decimal,
+    /// Minimum trigger distance as a fraction of price; absent when unrestricted
+    #[serde(with = "crate::decimal_string::option")]
+    pub min_stop_distance: Option<Decimal>,
+    /// Closest valid stop, present only when `valid` is false
+    #[serde(with = "crate::decimal_string::option")]
+    pub suggested_stop: Option<Decimal>,
+}
+
+/// A trade to break down the Van Tharp position size for
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SizingExplanationRequest {
+    pub direction: TradeDirection,
+    #[serde(with = "crate::decimal_string")]
+    pub account_equity: Decimal,
+    /// Fraction of equity risked, e.g. 0.02 for 2%
+    #[serde(with = "crate::decimal_string")]
+    pub risk_percentage: Decimal,
+    #[serde(with = "crate::decimal_string")]
+    pub entry_price: Decimal,
+    #[serde(with = "crate::decimal_string")]
+    pub stop_loss: Decimal,
+    #[serde(default, with = "crate::decimal_string::option")]
+    pub take_profit: Option<Decimal>,
+    /// Exchange and symbol to estimate the entry fee on; both are needed
+    #[serde(default)]
+    pub exchange: Option<String>,
+    #[serde(default)]
+    pub symbol: Option<String>,
+}
+
+/// One labelled step of the Van Tharp computation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FormulaStep {
+    pub label: String,
+    /// The step with the request's values substituted in
+    pub formula: String,
+    #[serde(with = "crate::decimal_string")]
+    pub value: Decimal,
+}
+
+impl FormulaStep {
+    fn new(label: &str, formula: String, value: Decimal) -> Self {
+        Self {
+            label: label.to_string(),
+            formula,
+            value,
+        }
+    }
+}
+
+/// A protocol limit checked against the sized trade
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SizingCheck {
+    pub rule: String,
+    pub passed: bool,
+    /// Why the check failed; absent when it passed
+    pub detail: Option<String>,
+}
+
+impl SizingCheck {
+    fn new(rule: &str, result: std::result::Result<(), ProtocolLimitViolation>) -> Self {
+        Self {
+            rule: rule.to_string(),
+            passed: result.is_ok(),
+            detail: result.err().map(|violation| violation.to_string()),
+        }
+    }
+}
+
+/// Step-by-step Van Tharp sizing with the protocol checks it was held to
+///
+/// Position Size = (Account Equity × Risk %) ÷ |Entry − Stop|, in exact
+/// decimals, so the steps reproduce `position_size` without rounding.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SizingExplanation {
+    pub steps: Vec<FormulaStep>,
+    #[serde(with = "crate::decimal_string")]
+    pub position_size: Decimal,
+    pub checks: Vec<SizingCheck>,
+    /// Taker fee on the entry, when the request names an exchange and symbol
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entry_fee: Option<FeePreview>,
+}
+
+impl SizingExplanation {
+    /// Break down the sizing of `request` and check it against `limits`
+    ///
+    /// The stop must lie on the loss side of the entry. The entry fee is
+    /// priced from `commissions` at the taker rate, as for a market order.
+    pub fn explain(
+        request: &SizingExplanationRequest,
+        limits: &ProtocolLimits,
+        commissions: &CommissionSchedule,
+    ) -> Self {
+        let risk_amount = request.account_equity * request.risk_percentage;
+        let stop_distance = (request.entry_price - request.stop_loss).abs();
+        let position_size = risk_amount / stop_distance;
+        let steps = vec![
+            FormulaStep::new(
+                "Risk amount",
+                format!(
+                    "account_equity × risk_percentage = {} × {}",
+                    request.account_equity.normalize(),
+                    request.risk_percentage.normalize()
+                ),
+                risk_amount,
+            ),
+            FormulaStep::new(
+                "Stop distance",
+                format!(
+                    "|entry_price − stop_loss| = |{} − {}|",
+                    request.entry_price.normalize(),
+                    request.stop_loss.normalize()
+                ),
+                stop_distance,
+            ),
+            FormulaStep::new(
+                "Position size",
+                format!(
+                    "risk_amount ÷ stop_distance = {} ÷ {}",
+                    risk_amount.normalize(),
+                    stop_distance.normalize()
+                ),
+                position_size,
+            ),
+        ];
+
+        let mut checks = vec![SizingCheck::new(
+            "IndividualTradeRisk",
+            limits.validate_individual_trade_risk(request.risk_percentage),
+        )];
+        if limits.max_trade_loss_amount.is_some() {
+            checks.push(SizingCheck::new(
+                "MaxTradeLossAmount",
+                limits.validate_trade_loss_amount(risk_amount),
+            ));
+        }
+        if limits.min_position_notional.is_some() {
+            checks.push(SizingCheck::new(
+                "MinPositionNotional",
+                limits.validate_position_notional(position_size * request.entry_price),
+            ));
+        }
+        if let Some(take_profit) = request.take_profit {
+            let reward_risk = (take_profit - request.entry_price).abs() / stop_distance;
+            checks.push(SizingCheck::new(
+                "MinRewardRiskRatio",
+                limits.validate_reward_risk_ratio(reward_risk),
+            ));
+        }
+
+        let entry_fee = request.exchange.as_deref().zip(request.symbol.as_deref()).map(|(exchange, symbol)| {
+            commissions.preview(exchange, symbol, position_size, request.entry_price, Liquidity::Taker)
+        });
+
+        Self {
+            steps,
+            position_size,
+            checks,
+            entry_fee,
+        }
+    }
+}
+
+/// An integrated exchange, what it supports and how it is doing
+#[derive(Debug, Clone, Serialize)]
+pub struct ExchangeStatus {
+    pub name: String,
+    pub capabilities: ExchangeCapabilities,
+    pub is_primary: bool,
+    pub is_active: bool,
+    /// `None` when the exchange has not been health-checked
+    pub healthy: Option<bool>,
+    pub checked_at: Option<DateTime<Utc>>,
+    pub error: Option<String>,
+}
+
+impl ExchangeStatus {
+    pub fn new(name: String, capabilities: ExchangeCapabilities, status: Option<&ExchangeHealthStatus>) -> Self {
+        let health = status.and_then(|status| status.health.as_ref());
+
+        Self {
+            name,
+            capabilities,
+            is_primary: status.is_some_and(|status| status.is_primary),
+            is_active: status.is_some_and(|status| status.is_active),
+            healthy: health.map(|health| health.healthy),
+            checked_at: health.map(|health| health.checked_at),
+            error: health.and_then(|health| health.error.clone()),
+        }
+    }
+}
+
+/// Outcome of test-firing a user's notifier
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationTestResult {
+    pub delivered: bool,
+    /// Number of deliveries the notifier reported
+    pub deliveries: usize,
+    pub latency_ms: u64,
+    pub error: Option<String>,
+}
+
+impl NotificationTestResult {
+    pub fn new(result: crate::Result<usize>, latency: std::time::Duration) -> Self {
+        let latency_ms = latency.as_millis() as u64;
+        match result {
+            Ok(deliveries) => Self {
+                delivered: true,
+                deliveries,
+                latency_ms,
+                error: None,
+            },
+            Err(error) => Self {
+                delivered: false,
+                deliveries: 0,
+                latency_ms,
+                error: Some(error.to_string()),
+            },
+        }
+    }
+}
+
+/// Outcome of authenticating with the stored exchange credentials
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CredentialStatus {
+    Valid,
+    Invalid,
+    /// The exchange could not be reached, so the credentials were not tested
+    Unverified,
+}
+
+/// Result of a credentials check; never includes the keys themselves
+#[derive(Debug, Clone, Serialize)]
+pub struct CredentialsCheck {
+    pub exchange: String,
+    pub status: CredentialStatus,
+    pub checked_at: DateTime<Utc>,
+    pub error: Option<String>,
+}
+
+impl CredentialsCheck {
+    pub fn new(exchange: String, result: Result<(), ExchangeError>) -> Self {
+        let (status, error) = match result {
+            Ok(()) => (CredentialStatus::Valid, None),
+            Err(error @ ExchangeError::AuthenticationError { .. }) => {
+                (CredentialStatus::Invalid, Some(error.to_string()))
+            }
+            Err(error) => (CredentialStatus::Unverified, Some(error.to_string())),
+        };
+
+        Self {
+            exchange,
+            status,
+            checked_at: Utc::now(),
+            error,
+        }
+    }
+}
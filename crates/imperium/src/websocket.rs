@@ -1,11 +1,849 @@
-//! websocket (placeholder)
+//! WebSocket real-time streaming
+//!
+//! Clients negotiate their frame encoding when connecting:
+//! - `json` (default): text frames, used by the browser frontend
+//! - `msgpack`: compact MessagePack binary frames for tick-heavy clients
+//!
+//! The `ConnectionManager` remembers the negotiated encoding for each
+//! connection and encodes every outgoing `WebSocketMessage` accordingly.
+//!
+//! Every connection opens with a `Hello` frame. Clients may send commands in
+//! either encoding. `RequestSnapshot` is answered on the same connection with
+//! a full `PortfolioSnapshot` frame; a frame that is not a command is
+//! answered with `Error`.
+//!
+//! Messages sent to a user are numbered, delivered in a `Sequenced`
+//! envelope and kept in a bounded per-user replay buffer so a reconnecting
+//! client can `Resume` from the last sequence it saw. Once the buffer is full the oldest messages are evicted;
+//! resuming from an evicted point is answered with `SnapshotRequired`.
 
-use axum::Router;
-use crate::AppState;
+use axum::{
+    extract::{
+        ws::{close_code, CloseFrame, Message, WebSocket, WebSocketUpgrade},
+        Query, State,
+    },
+    response::IntoResponse,
+    routing::get,
+    Router,
+};
+use futures::{SinkExt, StreamExt};
+use serde::Deserialize;
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, RwLock},
+};
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
 
-pub struct WebSocketHandler;
-pub struct ConnectionManager;
+use crate::api::ApiState;
+use crate::auth::AuthContext;
+use crate::types::{PortfolioSnapshotFrame, WebSocketMessage};
+use crate::{AppState, ImperiumError};
+
+/// Frame encoding negotiated per connection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MessageEncoding {
+    /// JSON text frames (browser default)
+    #[default]
+    Json,
+    /// MessagePack binary frames
+    #[serde(alias = "messagepack")]
+    Msgpack,
+}
+
+impl MessageEncoding {
+    /// Encode a message into a WebSocket frame for this encoding
+    pub fn encode(&self, message: &WebSocketMessage) -> Result<Message, ImperiumError> {
+        match self {
+            MessageEncoding::Json => serde_json::to_string(message)
+                .map(Message::Text)
+                .map_err(|e| ImperiumError::WebSocketError {
+                    reason: format!("JSON encoding failed: {}", e),
+                }),
+            MessageEncoding::Msgpack => rmp_serde::to_vec_named(message)
+                .map(Message::Binary)
+                .map_err(|e| ImperiumError::WebSocketError {
+                    reason: format!("MessagePack encoding failed: {}", e),
+                }),
+        }
+    }
+
+    /// Decode a client frame, whichever encoding it was sent in
+    ///
+    /// Returns `None` for control frames that carry no message.
+    pub fn decode(frame: &Message) -> Option<Result<WebSocketMessage, ImperiumError>> {
+        match frame {
+            Message::Text(text) => Some(serde_json::from_str(text).map_err(|e| {
+                ImperiumError::WebSocketError {
+                    reason: format!("JSON decoding failed: {}", e),
+                }
+            })),
+            Message::Binary(bytes) => Some(rmp_serde::from_slice(bytes).map_err(|e| {
+                ImperiumError::WebSocketError {
+                    reason: format!("MessagePack decoding failed: {}", e),
+                }
+            })),
+            _ => None,
+        }
+    }
+}
+
+/// Query parameters accepted when opening a WebSocket connection
+#[derive(Debug, Default, Deserialize)]
+pub struct ConnectParams {
+    #[serde(default)]
+    pub encoding: MessageEncoding,
+}
+
+/// A single registered client connection
+struct ClientConnection {
+    user_id: String,
+    /// Login session the connection was opened under, if known
+    session_id: Option<String>,
+    encoding: MessageEncoding,
+    sender: mpsc::UnboundedSender<Message>,
+}
+
+/// Replay messages retained per user unless configured otherwise
+pub const DEFAULT_REPLAY_CAPACITY: usize = 256;
+
+/// Answer to a resume request
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplayOutcome {
+    /// Messages sent after the requested sequence, oldest first
+    Messages(Vec<(u64, WebSocketMessage)>),
+    /// The requested point is no longer buffered
+    SnapshotRequired,
+}
+
+/// Bounded ring buffer of the most recent messages sent to one user
+struct ReplayBuffer {
+    capacity: usize,
+    next_sequence: u64,
+    /// Sequence, message and its encoded JSON size
+    messages: VecDeque<(u64, WebSocketMessage, usize)>,
+    bytes: usize,
+}
+
+impl ReplayBuffer {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            next_sequence: 1,
+            messages: VecDeque::new(),
+            bytes: 0,
+        }
+    }
+
+    /// Record a message, evicting the oldest when full, and return its sequence
+    fn push(&mut self, message: &WebSocketMessage) -> u64 {
+        let sequence = self.next_sequence;
+        self.next_sequence += 1;
+
+        let size = serde_json::to_vec(message).map(|bytes| bytes.len()).unwrap_or(0);
+        self.messages.push_back((sequence, message.clone(), size));
+        self.bytes += size;
+        self.evict();
+        sequence
+    }
+
+    fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.evict();
+    }
+
+    fn evict(&mut self) {
+        while self.messages.len() > self.capacity {
+            if let Some((_, _, size)) = self.messages.pop_front() {
+                self.bytes -= size;
+            }
+        }
+    }
+
+    fn latest_sequence(&self) -> u64 {
+        self.next_sequence - 1
+    }
+
+    fn since(&self, last_sequence: u64) -> ReplayOutcome {
+        let oldest = self
+            .messages
+            .front()
+            .map(|(sequence, _, _)| *sequence)
+            .unwrap_or(self.next_sequence);
+
+        // A point ahead of the stream comes from an earlier server lifetime
+        if last_sequence > self.latest_sequence() || last_sequence + 1 < oldest {
+            return ReplayOutcome::SnapshotRequired;
+        }
+
+        ReplayOutcome::Messages(
+            self.messages
+                .iter()
+                .filter(|(sequence, _, _)| *sequence > last_sequence)
+                .map(|(sequence, message, _)| (*sequence, message.clone()))
+                .collect(),
+        )
+    }
+}
+
+/// Tracks live connections and encodes outgoing messages per connection
+pub struct ConnectionManager {
+    connections: RwLock<HashMap<Uuid, ClientConnection>>,
+    replay: RwLock<HashMap<String, ReplayBuffer>>,
+    /// Capacity for users without an explicit override
+    replay_capacity: usize,
+}
+
+impl Default for ConnectionManager {
+    fn default() -> Self {
+        Self {
+            connections: RwLock::new(HashMap::new()),
+            replay: RwLock::new(HashMap::new()),
+            replay_capacity: DEFAULT_REPLAY_CAPACITY,
+        }
+    }
+}
+
+impl ConnectionManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the replay buffer capacity used for every user by default
+    pub fn with_replay_capacity(mut self, capacity: usize) -> Self {
+        self.replay_capacity = capacity;
+        self
+    }
+
+    /// Override the replay buffer capacity for one user
+    pub fn set_replay_capacity(&self, user_id: &str, capacity: usize) {
+        self.replay
+            .write()
+            .unwrap()
+            .entry(user_id.to_string())
+            .or_insert_with(|| ReplayBuffer::new(capacity))
+            .set_capacity(capacity);
+    }
+
+    /// Messages sent to a user after `last_sequence`
+    pub fn replay_since(&self, user_id: &str, last_sequence: u64) -> ReplayOutcome {
+        match self.replay.read().unwrap().get(user_id) {
+            Some(buffer) => buffer.since(last_sequence),
+            None if last_sequence == 0 => ReplayOutcome::Messages(Vec::new()),
+            None => ReplayOutcome::SnapshotRequired,
+        }
+    }
+
+    /// Sequence of the last message sent to a user, 0 if none
+    pub fn latest_sequence(&self, user_id: &str) -> u64 {
+        self.replay
+            .read()
+            .unwrap()
+            .get(user_id)
+            .map_or(0, ReplayBuffer::latest_sequence)
+    }
+
+    /// Approximate memory held by all replay buffers, in encoded JSON bytes
+    pub fn replay_memory_bytes(&self) -> usize {
+        self.replay.read().unwrap().values().map(|buffer| buffer.bytes).sum()
+    }
+
+    /// Register a connection and return its ID and outgoing frame receiver
+    pub fn register(
+        &self,
+        user_id: &str,
+        encoding: MessageEncoding,
+    ) -> (Uuid, mpsc::UnboundedReceiver<Message>) {
+        self.insert(user_id, None, encoding)
+    }
+
+    /// Register a connection opened under a login session
+    ///
+    /// Connections registered this way are closed by [`close_session`](Self::close_session).
+    pub fn register_session(
+        &self,
+        user_id: &str,
+        session_id: &str,
+        encoding: MessageEncoding,
+    ) -> (Uuid, mpsc::UnboundedReceiver<Message>) {
+        self.insert(user_id, Some(session_id.to_string()), encoding)
+    }
+
+    fn insert(
+        &self,
+        user_id: &str,
+        session_id: Option<String>,
+        encoding: MessageEncoding,
+    ) -> (Uuid, mpsc::UnboundedReceiver<Message>) {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let connection_id = Uuid::new_v4();
+
+        self.connections.write().unwrap().insert(
+            connection_id,
+            ClientConnection {
+                user_id: user_id.to_string(),
+                session_id,
+                encoding,
+                sender,
+            },
+        );
+
+        debug!("Registered WebSocket connection {} for user {} ({:?})", connection_id, user_id, encoding);
+        (connection_id, receiver)
+    }
+
+    /// Remove a connection
+    pub fn unregister(&self, connection_id: &Uuid) {
+        self.connections.write().unwrap().remove(connection_id);
+    }
+
+    /// Close every connection opened under `session_id`, returning how many
+    ///
+    /// Each connection is sent a policy-violation close frame and then
+    /// dropped, which ends its outgoing stream.
+    pub fn close_session(&self, session_id: &str) -> usize {
+        let mut connections = self.connections.write().unwrap();
+        let closing: Vec<Uuid> = connections
+            .iter()
+            .filter(|(_, connection)| connection.session_id.as_deref() == Some(session_id))
+            .map(|(connection_id, _)| *connection_id)
+            .collect();
+
+        for connection_id in &closing {
+            if let Some(connection) = connections.remove(connection_id) {
+                let _ = connection.sender.send(Message::Close(Some(CloseFrame {
+                    code: close_code::POLICY,
+                    reason: "Session ended by a newer login".into(),
+                })));
+            }
+        }
+
+        if !closing.is_empty() {
+            info!("Closed {} WebSocket connection(s) for ended session {}", closing.len(), session_id);
+        }
+        closing.len()
+    }
+
+    /// Number of live connections
+    pub fn connection_count(&self) -> usize {
+        self.connections.read().unwrap().len()
+    }
+
+    /// Send a message to every connection of a user, returning deliveries
+    ///
+    /// The message is recorded in the user's replay buffer even when no
+    /// connection is live, so a reconnecting client can resume, and is
+    /// delivered wrapped in `Sequenced` with its sequence number.
+    pub fn send_to_user(&self, user_id: &str, message: &WebSocketMessage) -> usize {
+        let sequence = self
+            .replay
+            .write()
+            .unwrap()
+            .entry(user_id.to_string())
+            .or_insert_with(|| ReplayBuffer::new(self.replay_capacity))
+            .push(message);
+        let sequenced = WebSocketMessage::Sequenced {
+            sequence,
+            message: Box::new(message.clone()),
+        };
+        self.dispatch(&sequenced, |connection| connection.user_id == user_id)
+    }
+
+    /// Send a message to all connections, returning deliveries
+    pub fn broadcast(&self, message: &WebSocketMessage) -> usize {
+        self.dispatch(message, |_| true)
+    }
+
+    /// Send a message to a single connection, returning whether it was delivered
+    pub fn send_to_connection(&self, connection_id: &Uuid, message: &WebSocketMessage) -> bool {
+        let connections = self.connections.read().unwrap();
+        let Some(connection) = connections.get(connection_id) else {
+            return false;
+        };
+
+        match connection.encoding.encode(message) {
+            Ok(frame) => connection.sender.send(frame).is_ok(),
+            Err(e) => {
+                warn!("Failed to encode WebSocket message: {}", e);
+                false
+            }
+        }
+    }
+
+    fn dispatch<F>(&self, message: &WebSocketMessage, filter: F) -> usize
+    where
+        F: Fn(&ClientConnection) -> bool,
+    {
+        let connections = self.connections.read().unwrap();
+        let mut json_frame = None;
+        let mut msgpack_frame = None;
+        let mut delivered = 0;
+
+        for (connection_id, connection) in connections.iter().filter(|(_, c)| filter(c)) {
+            // Encode at most once per encoding
+            let slot = match connection.encoding {
+                MessageEncoding::Json => &mut json_frame,
+                MessageEncoding::Msgpack => &mut msgpack_frame,
+            };
+            if slot.is_none() {
+                match connection.encoding.encode(message) {
+                    Ok(frame) => *slot = Some(frame),
+                    Err(e) => {
+                        warn!("Failed to encode WebSocket message: {}", e);
+                        continue;
+                    }
+                }
+            }
+
+            if let Some(frame) = slot.as_ref() {
+                if connection.sender.send(frame.clone()).is_ok() {
+                    delivered += 1;
+                } else {
+                    debug!("WebSocket connection {} closed before delivery", connection_id);
+                }
+            }
+        }
+
+        delivered
+    }
+}
+
+/// WebSocket entry point shared through `AppState`
+pub struct WebSocketHandler {
+    connections: Arc<ConnectionManager>,
+}
+
+impl WebSocketHandler {
+    pub fn new(connections: Arc<ConnectionManager>) -> Self {
+        Self { connections }
+    }
+
+    pub fn connections(&self) -> &Arc<ConnectionManager> {
+        &self.connections
+    }
+
+    /// Pump frames for an upgraded socket until either side closes
+    async fn handle_socket(
+        &self,
+        socket: WebSocket,
+        auth_context: AuthContext,
+        encoding: MessageEncoding,
+        api_state: Arc<ApiState>,
+    ) {
+        let user_id = auth_context.user_id.clone();
+        let (connection_id, mut outgoing) =
+            self.connections.register_session(&user_id, &auth_context.session_id, encoding);
+        let (mut sink, mut stream) = socket.split();
+        self.connections.send_to_connection(
+            &connection_id,
+            &WebSocketMessage::Hello {
+                connection_id,
+                latest_sequence: self.connections.latest_sequence(&user_id),
+                timestamp: chrono::Utc::now(),
+            },
+        );
+
+        let forward = tokio::spawn(async move {
+            while let Some(frame) = outgoing.recv().await {
+                if sink.send(frame).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        while let Some(Ok(frame)) = stream.next().await {
+            if let Message::Close(_) = frame {
+                break;
+            }
+            self.handle_frame(&connection_id, &auth_context, &api_state, &frame);
+        }
+
+        forward.abort();
+        self.connections.unregister(&connection_id);
+        info!("WebSocket connection {} for user {} closed", connection_id, user_id);
+    }
+
+    /// Act on a frame received from a client
+    fn handle_frame(
+        &self,
+        connection_id: &Uuid,
+        auth_context: &AuthContext,
+        api_state: &ApiState,
+        frame: &Message,
+    ) {
+        let message = match MessageEncoding::decode(frame) {
+            Some(Ok(message)) => message,
+            Some(Err(e)) => {
+                warn!("Ignoring undecodable frame on connection {}: {}", connection_id, e);
+                self.connections.send_to_connection(
+                    connection_id,
+                    &WebSocketMessage::error("invalid_frame", e.to_string()),
+                );
+                return;
+            }
+            None => return,
+        };
+
+        match message {
+            WebSocketMessage::RequestSnapshot => {
+                let Some(snapshot) = api_state.portfolio(&auth_context.user_id) else {
+                    warn!("No portfolio recorded for user {}; snapshot not sent", auth_context.user_id);
+                    return;
+                };
+                let frame = PortfolioSnapshotFrame::render(
+                    &snapshot,
+                    &api_state.configuration_for(auth_context),
+                    api_state.fx_rates(),
+                );
+                self.connections
+                    .send_to_connection(connection_id, &WebSocketMessage::PortfolioSnapshot(frame));
+            }
+            WebSocketMessage::Resume { last_sequence } => {
+                let user_id = &auth_context.user_id;
+                let latest_sequence = self.connections.latest_sequence(user_id);
+                match self.connections.replay_since(user_id, last_sequence) {
+                    ReplayOutcome::Messages(messages) => {
+                        for (sequence, message) in messages {
+                            self.connections.send_to_connection(
+                                connection_id,
+                                &WebSocketMessage::Replay { sequence, message: Box::new(message) },
+                            );
+                        }
+                        self.connections.send_to_connection(
+                            connection_id,
+                            &WebSocketMessage::ReplayComplete { latest_sequence },
+                        );
+                    }
+                    ReplayOutcome::SnapshotRequired => {
+                        debug!("Resume point {} evicted for user {}; snapshot required", last_sequence, user_id);
+                        self.connections.send_to_connection(
+                            connection_id,
+                            &WebSocketMessage::SnapshotRequired { latest_sequence },
+                        );
+                    }
+                }
+            }
+            other => {
+                debug!("Ignoring client message on connection {}: {:?}", connection_id, other);
+                self.connections.send_to_connection(
+                    connection_id,
+                    &WebSocketMessage::error("unsupported_command", "Only RequestSnapshot and Resume are accepted"),
+                );
+            }
+        }
+    }
+}
+
+impl Default for WebSocketHandler {
+    fn default() -> Self {
+        Self::new(Arc::new(ConnectionManager::new()))
+    }
+}
+
+/// GET /ws - Upgrade to a WebSocket, negotiating the frame encoding
+async fn websocket_handler(
+    ws: WebSocketUpgrade,
+    auth_context: AuthContext,
+    Query(params): Query<ConnectParams>,
+    State(state): State<AppState>,
+) -> impl IntoResponse {
+    let handler = state.websocket_manager.clone();
+    let api_state = state.api_state.clone();
+    ws.on_upgrade(move |socket| async move {
+        handler
+            .handle_socket(socket, auth_context, params.encoding, api_state)
+            .await
+    })
+}
 
 pub fn create_router() -> Router<AppState> {
-    Router::new()
-}
\ No newline at end of file
+    Router::new().route("/", get(websocket_handler))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api;
+    use crate::types::{AlertSeverity, PortfolioResponse, PortfolioSnapshot, UserConfiguration};
+    use axum::{
+        body::{to_bytes, Body},
+        http::Request,
+        Extension,
+    };
+    use chrono::Utc;
+    use prudentia::RiskProfile;
+    use rust_decimal_macros::dec;
+    use tower::ServiceExt;
+
+    fn price_update() -> WebSocketMessage {
+        WebSocketMessage::PriceUpdate {
+            symbol: "BTCUSDT".to_string(),
+            bid_price: dec!(49999.5),
+            ask_price: dec!(50000.5),
+            last_price: dec!(50000),
+            timestamp: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_encoding_negotiation_defaults_to_json() {
+        let params: ConnectParams = serde_json::from_str("{}").unwrap();
+        assert_eq!(params.encoding, MessageEncoding::Json);
+
+        let params: ConnectParams = serde_json::from_str(r#"{"encoding":"msgpack"}"#).unwrap();
+        assert_eq!(params.encoding, MessageEncoding::Msgpack);
+    }
+
+    #[test]
+    fn test_every_variant_serializes_with_its_type_tag() {
+        use formatio::{OodaState, TradeDirection};
+
+        let timestamp = Utc::now();
+        let variants = [
+            (price_update(), "PriceUpdate"),
+            (
+                WebSocketMessage::PositionUpdate {
+                    symbol: "BTCUSDT".to_string(),
+                    direction: TradeDirection::Long,
+                    quantity: dec!(0.1),
+                    entry_price: dec!(48000),
+                    stop_loss: dec!(46000),
+                    unrealized_pnl: dec!(200),
+                    timestamp,
+                },
+                "PositionUpdate",
+            ),
+            (
+                WebSocketMessage::RiskAlert {
+                    severity: AlertSeverity::Warning,
+                    rule: "DailyLossLimit".to_string(),
+                    message: "Daily loss at 80% of limit".to_string(),
+                    timestamp,
+                },
+                "RiskAlert",
+            ),
+            (
+                WebSocketMessage::ooda_transition(
+                    "BTCUSDT",
+                    &OodaState::Deciding,
+                    &OodaState::Failed("risk rejected".to_string()),
+                ),
+                "OodaTransition",
+            ),
+            (
+                WebSocketMessage::PositionLifecycle(
+                    crate::lifecycle::PositionLifecycle::submit(
+                        Uuid::new_v4(),
+                        "BTCUSDT",
+                        TradeDirection::Long,
+                        dec!(0.1),
+                        dec!(48000),
+                        dec!(46000),
+                    )
+                    .1,
+                ),
+                "PositionLifecycle",
+            ),
+            (
+                WebSocketMessage::CircuitBreakerReset { reset_by: "trader-1".to_string(), timestamp },
+                "CircuitBreakerReset",
+            ),
+            (
+                WebSocketMessage::Hello { connection_id: Uuid::new_v4(), latest_sequence: 7, timestamp },
+                "Hello",
+            ),
+            (WebSocketMessage::error("invalid_frame", "expected JSON"), "Error"),
+            (
+                WebSocketMessage::Sequenced { sequence: 3, message: Box::new(price_update()) },
+                "Sequenced",
+            ),
+        ];
+
+        for (message, tag) in variants {
+            let json = serde_json::to_value(&message).unwrap();
+            assert_eq!(json["type"], tag);
+            assert_eq!(serde_json::from_value::<WebSocketMessage>(json).unwrap(), message);
+        }
+
+        let transition = serde_json::to_value(WebSocketMessage::ooda_transition(
+            "BTCUSDT",
+            &OodaState::Deciding,
+            &OodaState::Failed("risk rejected".to_string()),
+        ))
+        .unwrap();
+        assert_eq!(transition["from"], "Deciding");
+        assert_eq!(transition["to"], "Failed");
+        assert_eq!(transition["reason"], "risk rejected");
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_command_is_answered_with_error() {
+        let auth_context = AuthContext {
+            user_id: "trader-1".to_string(),
+            session_id: "session-1".to_string(),
+            email: "trader-1@example.com".to_string(),
+            risk_profile: RiskProfile::Standard,
+            permissions: vec!["trade:execute".to_string()],
+        };
+        let handler = WebSocketHandler::default();
+        let (connection_id, mut outgoing) =
+            handler.connections().register("trader-1", MessageEncoding::Json);
+        let heartbeat = serde_json::to_string(&WebSocketMessage::Heartbeat { timestamp: Utc::now() }).unwrap();
+        handler.handle_frame(&connection_id, &auth_context, &ApiState::new(), &Message::Text(heartbeat));
+        handler.handle_frame(&connection_id, &auth_context, &ApiState::new(), &Message::Text("{".to_string()));
+
+        for expected in ["unsupported_command", "invalid_frame"] {
+            match outgoing.recv().await.unwrap() {
+                Message::Text(text) => match serde_json::from_str::<WebSocketMessage>(&text).unwrap() {
+                    WebSocketMessage::Error { code, .. } => assert_eq!(code, expected),
+                    other => panic!("Expected an error frame, got: {:?}", other),
+                },
+                other => panic!("Expected text frame, got: {:?}", other),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_binary_client_receives_equivalent_msgpack_frames() {
+        let manager = ConnectionManager::new();
+        let (_, mut json_rx) = manager.register("trader-1", MessageEncoding::Json);
+        let (_, mut binary_rx) = manager.register("trader-1", MessageEncoding::Msgpack);
+
+        let message = price_update();
+        assert_eq!(manager.send_to_user("trader-1", &message), 2);
+
+        let json_message: WebSocketMessage = match json_rx.recv().await.unwrap() {
+            Message::Text(text) => serde_json::from_str(&text).unwrap(),
+            other => panic!("Expected text frame, got: {:?}", other),
+        };
+        let binary_message: WebSocketMessage = match binary_rx.recv().await.unwrap() {
+            Message::Binary(bytes) => rmp_serde::from_slice(&bytes).unwrap(),
+            other => panic!("Expected binary frame, got: {:?}", other),
+        };
+
+        assert_eq!(binary_message, json_message);
+        assert_eq!(binary_message, WebSocketMessage::Sequenced { sequence: 1, message: Box::new(message) });
+    }
+
+    #[tokio::test]
+    async fn test_send_to_user_only_reaches_that_user() {
+        let manager = ConnectionManager::new();
+        let (_, mut own_rx) = manager.register("trader-1", MessageEncoding::Json);
+        let (_, mut other_rx) = manager.register("trader-2", MessageEncoding::Json);
+
+        let heartbeat = WebSocketMessage::Heartbeat { timestamp: Utc::now() };
+        assert_eq!(manager.send_to_user("trader-1", &heartbeat), 1);
+        assert_eq!(manager.send_to_user("trader-1", &heartbeat), 1);
+
+        // Live frames carry the sequence a reconnecting client resumes from
+        for expected in [1, 2] {
+            match own_rx.recv().await.unwrap() {
+                Message::Text(text) => match serde_json::from_str::<WebSocketMessage>(&text).unwrap() {
+                    WebSocketMessage::Sequenced { sequence, message } => {
+                        assert_eq!(sequence, expected);
+                        assert_eq!(*message, heartbeat);
+                    }
+                    other => panic!("Expected a sequenced frame, got: {:?}", other),
+                },
+                other => panic!("Expected text frame, got: {:?}", other),
+            }
+        }
+        assert!(other_rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_full_replay_buffer_evicts_oldest_and_requires_snapshot() {
+        let manager = ConnectionManager::new().with_replay_capacity(8);
+        manager.set_replay_capacity("trader-1", 3);
+
+        for _ in 0..5 {
+            manager.send_to_user("trader-1", &price_update());
+        }
+        assert_eq!(manager.latest_sequence("trader-1"), 5);
+
+        // Sequences 1 and 2 were evicted; resuming after 2 replays 3..=5
+        let ReplayOutcome::Messages(messages) = manager.replay_since("trader-1", 2) else {
+            panic!("Expected buffered messages");
+        };
+        let sequences: Vec<u64> = messages.iter().map(|(sequence, _)| *sequence).collect();
+        assert_eq!(sequences, vec![3, 4, 5]);
+        assert_eq!(manager.replay_since("trader-1", 1), ReplayOutcome::SnapshotRequired);
+        assert!(manager.replay_memory_bytes() > 0);
+
+        let auth_context = AuthContext {
+            user_id: "trader-1".to_string(),
+            session_id: "session-1".to_string(),
+            email: "trader-1@example.com".to_string(),
+            risk_profile: RiskProfile::Standard,
+            permissions: vec!["trade:execute".to_string()],
+        };
+        let handler = WebSocketHandler::new(Arc::new(manager));
+        let (connection_id, mut outgoing) =
+            handler.connections().register("trader-1", MessageEncoding::Json);
+        let command = serde_json::to_string(&WebSocketMessage::Resume { last_sequence: 1 }).unwrap();
+        handler.handle_frame(&connection_id, &auth_context, &ApiState::new(), &Message::Text(command));
+
+        let frame = match outgoing.recv().await.unwrap() {
+            Message::Text(text) => serde_json::from_str::<WebSocketMessage>(&text).unwrap(),
+            other => panic!("Expected text frame, got: {:?}", other),
+        };
+        assert_eq!(frame, WebSocketMessage::SnapshotRequired { latest_sequence: 5 });
+    }
+
+    #[tokio::test]
+    async fn test_request_snapshot_matches_rest_portfolio() {
+        let auth_context = AuthContext {
+            user_id: "trader-1".to_string(),
+            session_id: "session-1".to_string(),
+            email: "trader-1@example.com".to_string(),
+            risk_profile: RiskProfile::Standard,
+            permissions: vec!["trade:execute".to_string()],
+        };
+        let api_state = Arc::new(ApiState::new());
+        api_state.set_configuration(
+            "trader-1",
+            UserConfiguration::for_profile(RiskProfile::Standard).with_base_currency("EUR"),
+        );
+        api_state.fx_rates().set_rate("EUR", dec!(0.92));
+        api_state.set_portfolio(
+            "trader-1",
+            PortfolioSnapshot {
+                account_equity: dec!(10000),
+                realized_pnl: dec!(-250),
+                open_risk: dec!(200),
+                open_positions: 2,
+                circuit_breaker_active: false,
+            },
+        );
+
+        let handler = WebSocketHandler::default();
+        let (connection_id, mut outgoing) =
+            handler.connections().register("trader-1", MessageEncoding::Json);
+        let command = serde_json::to_string(&WebSocketMessage::RequestSnapshot).unwrap();
+        assert_eq!(command, r#"{"type":"RequestSnapshot"}"#);
+        handler.handle_frame(&connection_id, &auth_context, &api_state, &Message::Text(command));
+
+        let frame = match outgoing.recv().await.unwrap() {
+            Message::Text(text) => serde_json::from_str::<WebSocketMessage>(&text).unwrap(),
+            other => panic!("Expected text frame, got: {:?}", other),
+        };
+        let WebSocketMessage::PortfolioSnapshot(snapshot) = frame else {
+            panic!("Expected a portfolio snapshot, got: {:?}", frame);
+        };
+        assert_eq!(snapshot.open_positions, 2);
+        assert!(!snapshot.circuit_breaker_active);
+
+        let rest = api::routes::<Arc<ApiState>>()
+            .layer(Extension(auth_context))
+            .with_state(api_state)
+            .oneshot(Request::get("/portfolio").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let body = to_bytes(rest.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let rest_portfolio: PortfolioResponse = serde_json::from_value(body["data"].clone()).unwrap();
+        assert_eq!(snapshot.portfolio, rest_portfolio);
+    }
+}
@@ -0,0 +1,294 @@
+//! Full-stack test harness for the Imperium router
+//!
+//! `TestApp::spawn` serves `create_app_router` on a local port with every
+//! external dependency replaced: a `MockExchange`, a lazily connected
+//! Postgres pool (never touched by the routes under test), an in-process
+//! fake Redis and a fake OIDC provider that signs tokens with a test key.
+//! Tests then drive real HTTP requests through auth, the OODA cycle and
+//! the exchange.
+
+use axum::{routing::get, Json, Router};
+use chrono::Utc;
+use formatio::{OodaController, OodaLoop, RiskDecider};
+use imperium::{
+    api::{ApiState, RequestTimeouts},
+    auth::{
+        OidcConfig, OidcValidator, SessionManager, UserClaims, DEFAULT_JWKS_MAX_AGE, SESSION_ID_HEADER,
+    },
+    create_app_router, AppConfig, AppState, AuthService, ConnectionManager, WebSocketHandler,
+};
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use prudentia::exchange::MockExchange;
+use prudentia::{
+    ExchangeFailoverConfig, FailoverManager, MaxTradeRiskRule, RiskManagementProtocol, RiskProfile,
+};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+};
+use uuid::Uuid;
+
+const CLIENT_ID: &str = "testudo-frontend";
+const KEY_ID: &str = "test-key";
+const SIGNING_KEY_PEM: &[u8] = include_bytes!("../fixtures/test_rsa_key.pem");
+/// Base64url modulus of the public half of `SIGNING_KEY_PEM`
+const SIGNING_KEY_MODULUS: &str = "wvQ7DhUuGzM-EE1YETAtqwNTp5kOeTFHqR1ptfARpR71GMZm2sw04eQjPeg6jB09MVYFYzjFW23QQPr7--4Jo-n4BUf-zXmvnf5hQ24IJ5177D3XbfDTd1ZYNXcrjZ2iSGkJ8GqraSRW7_s0y5-oKreER06nCSCtr6mfyz7B9q3-9iVQEV2jB9yUzKGOJV_7sl15idWiskCIP0zNgwUccXY2F85TrM7Xcyj-flUXawkdaPM-hLDhDXICNO6TWmouRl22dJQAZfn6U7ZDxvWiy2EL44dczboVDtdz2lkRttQUVt5Qdg_1YAoOrQI7r9ZbodIdF7rX07OjmV7bApAXQQ";
+
+/// A running Imperium server wired to test doubles
+pub struct TestApp {
+    pub base_url: String,
+    pub client: reqwest::Client,
+    pub exchange: Arc<MockExchange>,
+    issuer: String,
+    sessions: Arc<SessionManager>,
+}
+
+impl TestApp {
+    pub async fn spawn() -> Self {
+        let redis_url = spawn_fake_redis().await;
+        let issuer = spawn_fake_oidc_provider().await;
+
+        let oidc_validator = Arc::new(
+            OidcValidator::new(OidcConfig {
+                provider_url: issuer.clone(),
+                client_id: CLIENT_ID.to_string(),
+                client_secret: "test-secret".to_string(),
+                redirect_uri: "http://localhost:3000/auth/callback".to_string(),
+                scope: "openid profile email".to_string(),
+                jwks_max_age: DEFAULT_JWKS_MAX_AGE,
+            })
+            .await
+            .expect("OIDC discovery against the fake provider"),
+        );
+        let sessions = Arc::new(SessionManager::new(&redis_url).unwrap());
+
+        let exchange = Arc::new(MockExchange::new());
+        let protocol = Arc::new(RiskManagementProtocol::new().add_rule(MaxTradeRiskRule::new()));
+        let trading_controller = Arc::new(OodaController::new(Arc::new(
+            OodaLoop::with_all_components(exchange.clone(), Arc::new(RiskDecider::new(protocol))),
+        )));
+
+        let config = AppConfig {
+            server_host: "127.0.0.1".to_string(),
+            server_port: 0,
+            database_url: "postgres://testudo@127.0.0.1:1/testudo".to_string(),
+            database_pool_size: 1,
+            redis_url: redis_url.clone(),
+            jwt_secret: "unused".to_string(),
+            jwt_expiration_hours: 24,
+            cors_allowed_origins: Vec::new(),
+            rate_limit_requests_per_minute: 600,
+            websocket_max_connections: 10,
+            websocket_heartbeat_interval: 30,
+            compression_min_size: 1024,
+            request_timeouts: RequestTimeouts::default(),
+        };
+
+        let state = AppState {
+            db_pool: sqlx::postgres::PgPoolOptions::new()
+                .connect_lazy(&config.database_url)
+                .unwrap(),
+            cache: redis::aio::ConnectionManager::new(redis::Client::open(redis_url).unwrap())
+                .await
+                .unwrap(),
+            risk_calculator: Arc::new(disciplina::PositionSizingCalculator::new()),
+            trading_controller: trading_controller.clone(),
+            exchange_manager: Arc::new(FailoverManager::new(ExchangeFailoverConfig {
+                primary_exchange: "mock".to_string(),
+                backup_exchanges: Vec::new(),
+                health_check_interval_secs: 30,
+                health_check_jitter: false,
+            })),
+            websocket_manager: Arc::new(WebSocketHandler::new(Arc::new(ConnectionManager::new()))),
+            config,
+            auth_service: Arc::new(AuthService::new(oidc_validator, sessions.clone())),
+            api_state: Arc::new(ApiState::new().with_trading_controller(trading_controller, 4)),
+        };
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, create_app_router(state)).await.unwrap();
+        });
+
+        Self {
+            base_url,
+            client: reqwest::Client::new(),
+            exchange,
+            issuer,
+            sessions,
+        }
+    }
+
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    /// Create a session for a user and return credentials bound to it
+    pub async fn login(&self, user_id: &str) -> Login {
+        let now = Utc::now().timestamp();
+        let claims = UserClaims {
+            sub: user_id.to_string(),
+            email: format!("{}@example.com", user_id),
+            name: user_id.to_string(),
+            iss: self.issuer.clone(),
+            aud: CLIENT_ID.to_string(),
+            exp: now + 3600,
+            iat: now,
+            jti: Uuid::new_v4().to_string(),
+            risk_profile: RiskProfile::Standard,
+            account_equity: Some("10000".to_string()),
+            max_position_count: 5,
+            daily_loss_limit: None,
+            permissions: vec!["trade:execute".to_string()],
+        };
+        let session = self.sessions.create_session(&claims).await.unwrap();
+
+        let header = Header {
+            kid: Some(KEY_ID.to_string()),
+            ..Header::new(Algorithm::RS256)
+        };
+        Login {
+            token: jsonwebtoken::encode(&header, &claims, &EncodingKey::from_rsa_pem(SIGNING_KEY_PEM).unwrap())
+                .unwrap(),
+            session_id: session.session_id,
+        }
+    }
+
+    /// End a session, as logging out does
+    pub async fn logout(&self, login: &Login) {
+        self.sessions.delete_session(&login.session_id).await.unwrap();
+    }
+}
+
+/// A bearer token and the login session it was issued under
+#[derive(Debug, Clone)]
+pub struct Login {
+    pub token: String,
+    pub session_id: String,
+}
+
+impl Login {
+    /// Authenticate `request` with this login
+    pub fn authorize(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        request
+            .bearer_auth(&self.token)
+            .header(SESSION_ID_HEADER, &self.session_id)
+    }
+}
+
+/// Serve OIDC discovery and JWKS for the test signing key, returning the issuer URL
+async fn spawn_fake_oidc_provider() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let issuer = format!("http://{}", listener.local_addr().unwrap());
+
+    let discovery = serde_json::json!({
+        "issuer": issuer,
+        "authorization_endpoint": format!("{}/auth", issuer),
+        "token_endpoint": format!("{}/token", issuer),
+        "userinfo_endpoint": format!("{}/userinfo", issuer),
+        "jwks_uri": format!("{}/jwks", issuer),
+    });
+    let jwks = serde_json::json!({
+        "keys": [{
+            "kty": "RSA",
+            "kid": KEY_ID,
+            "alg": "RS256",
+            "use": "sig",
+            "n": SIGNING_KEY_MODULUS,
+            "e": "AQAB",
+        }]
+    });
+
+    let provider = Router::new()
+        .route("/.well-known/openid_configuration", get(move || async move { Json(discovery) }))
+        .route("/jwks", get(move || async move { Json(jwks) }));
+    tokio::spawn(async move {
+        axum::serve(listener, provider).await.unwrap();
+    });
+
+    issuer
+}
+
+type RedisStore = Arc<Mutex<HashMap<String, String>>>;
+
+/// Serve the subset of the Redis protocol used for sessions, returning its URL
+///
+/// Supports GET, SET (expiry arguments are ignored), DEL and PING; any other
+/// command is acknowledged with OK.
+async fn spawn_fake_redis() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("redis://{}", listener.local_addr().unwrap());
+    let store = RedisStore::default();
+
+    tokio::spawn(async move {
+        loop {
+            let (socket, _) = listener.accept().await.unwrap();
+            tokio::spawn(serve_redis_connection(socket, store.clone()));
+        }
+    });
+
+    url
+}
+
+async fn serve_redis_connection(socket: TcpStream, store: RedisStore) {
+    let (reader, mut writer) = socket.into_split();
+    let mut reader = BufReader::new(reader);
+
+    while let Some(command) = read_redis_command(&mut reader).await {
+        let reply = execute_redis_command(&command, &store);
+        if writer.write_all(reply.as_bytes()).await.is_err() {
+            return;
+        }
+    }
+}
+
+async fn read_redis_command<R>(reader: &mut BufReader<R>) -> Option<Vec<String>>
+where
+    R: tokio::io::AsyncRead + Unpin,
+{
+    let mut line = String::new();
+    if reader.read_line(&mut line).await.ok()? == 0 {
+        return None;
+    }
+    let count: usize = line.trim_end().strip_prefix('*')?.parse().ok()?;
+
+    let mut args = Vec::with_capacity(count);
+    for _ in 0..count {
+        line.clear();
+        reader.read_line(&mut line).await.ok()?;
+        let len: usize = line.trim_end().strip_prefix('$')?.parse().ok()?;
+
+        let mut bulk = vec![0; len + 2];
+        reader.read_exact(&mut bulk).await.ok()?;
+        bulk.truncate(len);
+        args.push(String::from_utf8(bulk).ok()?);
+    }
+
+    Some(args)
+}
+
+fn execute_redis_command(command: &[String], store: &RedisStore) -> String {
+    let mut store = store.lock().unwrap();
+
+    match command[0].to_ascii_uppercase().as_str() {
+        "PING" => "+PONG\r\n".to_string(),
+        "GET" => match store.get(&command[1]) {
+            Some(value) => format!("${}\r\n{}\r\n", value.len(), value),
+            None => "$-1\r\n".to_string(),
+        },
+        "SET" => {
+            store.insert(command[1].clone(), command[2].clone());
+            "+OK\r\n".to_string()
+        }
+        "DEL" => {
+            let removed = command[1..].iter().filter(|key| store.remove(*key).is_some()).count();
+            format!(":{}\r\n", removed)
+        }
+        _ => "+OK\r\n".to_string(),
+    }
+}
@@ -0,0 +1,161 @@
+//! End-to-end HTTP tests through `create_app_router`
+
+mod common;
+
+use common::TestApp;
+use reqwest::StatusCode;
+
+fn trade_request() -> serde_json::Value {
+    serde_json::json!({
+        "symbol": "BTC/USDT",
+        "direction": "Long",
+        "account_equity": "10000",
+        "risk_percentage": "0.01",
+    })
+}
+
+#[tokio::test]
+async fn test_authenticated_trade_submission_executes_on_exchange() {
+    let app = TestApp::spawn().await;
+    let login = app.login("trader-1").await;
+
+    let response = login
+        .authorize(app.client.post(app.url("/api/v1/trades/execute")))
+        .json(&trade_request())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body: serde_json::Value = response.json().await.unwrap();
+    let plan = &body["data"];
+    assert_eq!(plan["symbol"], "BTC/USDT");
+    assert_eq!(plan["approved"], true, "{}", plan["risk_assessment"]);
+    assert_eq!(plan["entry_price"], "50000");
+    // 1% of 10000 risked over a 2% stop at 50000
+    assert_eq!(plan["position_size"], "0.10");
+
+    let orders = app.exchange.get_placed_orders().await;
+    assert_eq!(orders.len(), 1);
+    assert_eq!(orders[0].symbol, "BTC/USDT");
+}
+
+#[tokio::test]
+async fn test_trade_submission_without_token_is_rejected() {
+    let app = TestApp::spawn().await;
+
+    let response = app
+        .client
+        .post(app.url("/api/v1/trades/execute"))
+        .json(&trade_request())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert!(app.exchange.get_placed_orders().await.is_empty());
+}
+
+#[tokio::test]
+async fn test_each_request_is_bound_to_the_session_it_names() {
+    let app = TestApp::spawn().await;
+    let first = app.login("trader-1").await;
+    let second = app.login("trader-1").await;
+    let other_user = app.login("trader-2").await;
+    app.logout(&second).await;
+
+    let status = |request: reqwest::RequestBuilder| async move { request.send().await.unwrap().status() };
+    let settings = || app.client.get(app.url("/api/v1/settings/risk"));
+
+    // The earlier session stays valid after a later one of the same user ends
+    assert_eq!(status(first.authorize(settings())).await, StatusCode::OK);
+    assert_eq!(status(second.authorize(settings())).await, StatusCode::UNAUTHORIZED);
+
+    // A token cannot borrow another user's session, or go without one
+    let borrowed = common::Login {
+        session_id: other_user.session_id.clone(),
+        ..first.clone()
+    };
+    assert_eq!(status(borrowed.authorize(settings())).await, StatusCode::UNAUTHORIZED);
+    assert_eq!(status(settings().bearer_auth(&first.token)).await, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_every_api_route_requires_a_session() {
+    use reqwest::Method;
+
+    let app = TestApp::spawn().await;
+    let position = "/api/v1/positions/00000000-0000-0000-0000-000000000000";
+    let routes = [
+        (Method::GET, "/api/v1/config/snapshot".to_string()),
+        (Method::GET, "/api/v1/settings/risk".to_string()),
+        (Method::PUT, "/api/v1/settings/risk".to_string()),
+        (Method::GET, "/api/v1/portfolio".to_string()),
+        (Method::GET, "/api/v1/portfolio/heat".to_string()),
+        (Method::GET, "/api/v1/reports/daily".to_string()),
+        (Method::GET, "/api/v1/market/symbols/BTCUSDT/tradable".to_string()),
+        (Method::GET, "/api/v1/exchanges".to_string()),
+        (Method::GET, "/api/v1/exchanges/mock/credentials/check".to_string()),
+        (Method::POST, "/api/v1/trades/validate-stop".to_string()),
+        (Method::POST, "/api/v1/calculator/explain".to_string()),
+        (Method::POST, "/api/v1/notifications/test".to_string()),
+        (Method::POST, "/api/v1/admin/impersonate/trader-2".to_string()),
+        (Method::GET, "/api/v1/admin/stats/calculator".to_string()),
+        (Method::GET, "/api/v1/admin/protocol/config".to_string()),
+        (Method::PUT, "/api/v1/admin/protocol/config".to_string()),
+        (Method::POST, "/api/v1/trades/execute".to_string()),
+        (Method::POST, "/api/v1/positions/import".to_string()),
+        (Method::POST, format!("{}/close", position)),
+        (Method::PUT, format!("{}/bracket", position)),
+        (Method::POST, "/api/v1/admin/trades/backfill-r".to_string()),
+        (Method::GET, "/ws".to_string()),
+    ];
+
+    for (method, path) in routes {
+        let response = app.client.request(method.clone(), app.url(&path)).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED, "{} {}", method, path);
+    }
+    assert!(app.exchange.get_placed_orders().await.is_empty());
+}
+
+#[tokio::test]
+async fn test_a_trader_session_reaches_reads_but_not_admin_routes() {
+    let app = TestApp::spawn().await;
+    let login = app.login("trader-1").await;
+    let status = |request: reqwest::RequestBuilder| async move { request.send().await.unwrap().status() };
+
+    for path in ["/api/v1/config/snapshot", "/api/v1/settings/risk"] {
+        let request = login.authorize(app.client.get(app.url(path)));
+        assert_eq!(status(request).await, StatusCode::OK, "GET {}", path);
+    }
+
+    // Authenticated is not authorized: admin routes need the admin role
+    let request = login.authorize(app.client.get(app.url("/api/v1/admin/stats/calculator")));
+    assert_eq!(status(request).await, StatusCode::FORBIDDEN);
+    let request = login.authorize(app.client.post(app.url("/api/v1/admin/impersonate/trader-2")));
+    assert_eq!(status(request).await, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_calculator_explains_the_van_tharp_size() {
+    let app = TestApp::spawn().await;
+    let login = app.login("trader-1").await;
+
+    let response = login
+        .authorize(app.client.post(app.url("/api/v1/calculator/explain")))
+        .json(&serde_json::json!({
+            "direction": "Long",
+            "account_equity": "10000",
+            "risk_percentage": "0.01",
+            "entry_price": "50000",
+            "stop_loss": "49000",
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body: serde_json::Value = response.json().await.unwrap();
+    // $100 of risk over a $1000 stop distance
+    assert_eq!(body["data"]["position_size"], "0.10");
+    assert!(app.exchange.get_placed_orders().await.is_empty());
+}
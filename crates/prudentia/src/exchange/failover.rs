@@ -1,5 +1,15 @@
 //! Failover manager for exchange outages and health monitoring
 
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use rand::Rng;
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::Duration;
+
+/// Largest change jitter makes to the health check interval, as a fraction of it
+pub const HEALTH_CHECK_JITTER: f64 = 0.2;
+
 /// Configuration for exchange failover behavior
 #[derive(Debug, Clone)]
 pub struct ExchangeFailoverConfig {
@@ -9,6 +19,21 @@ pub struct ExchangeFailoverConfig {
     pub backup_exchanges: Vec<String>,
     /// Health check interval in seconds
     pub health_check_interval_secs: u64,
+    /// Spread each interval by up to ±[`HEALTH_CHECK_JITTER`] so that
+    /// instances started together do not check the exchanges in lockstep
+    pub health_check_jitter: bool,
+}
+
+impl ExchangeFailoverConfig {
+    /// Delay before the next health check
+    pub fn next_health_check_delay(&self) -> Duration {
+        let interval = Duration::from_secs(self.health_check_interval_secs);
+        if !self.health_check_jitter {
+            return interval;
+        }
+        let factor = rand::thread_rng().gen_range(1.0 - HEALTH_CHECK_JITTER..=1.0 + HEALTH_CHECK_JITTER);
+        interval.mul_f64(factor)
+    }
 }
 
 /// Manages exchange failover and health monitoring
@@ -18,6 +43,29 @@ pub struct FailoverManager {
     config: ExchangeFailoverConfig,
     /// Current primary exchange (may differ from config if failed over)
     current_primary: String,
+    /// Latest health check result by exchange name
+    health: RwLock<HashMap<String, ExchangeHealth>>,
+}
+
+/// Result of the latest health check against an exchange
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExchangeHealth {
+    pub healthy: bool,
+    pub checked_at: DateTime<Utc>,
+    /// Why the check failed, when it could not reach the exchange at all
+    pub error: Option<String>,
+}
+
+/// An exchange's role in failover together with its latest health
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExchangeHealthStatus {
+    pub name: String,
+    /// Configured as the primary exchange
+    pub is_primary: bool,
+    /// Currently receiving orders
+    pub is_active: bool,
+    /// `None` until the exchange has been checked
+    pub health: Option<ExchangeHealth>,
 }
 
 impl FailoverManager {
@@ -27,9 +75,52 @@ impl FailoverManager {
         Self {
             config,
             current_primary,
+            health: RwLock::new(HashMap::new()),
         }
     }
     
+    /// Record the outcome of a health check
+    pub fn record_health(&self, exchange: &str, health: ExchangeHealth) {
+        self.health.write().unwrap().insert(exchange.to_string(), health);
+    }
+    
+    /// Latest health of every known exchange
+    ///
+    /// Configured exchanges come first in failover priority order, followed
+    /// by any other checked exchanges by name.
+    pub fn health_report(&self) -> Vec<ExchangeHealthStatus> {
+        let health = self.health.read().unwrap();
+        let configured: Vec<&String> = std::iter::once(&self.config.primary_exchange)
+            .chain(self.config.backup_exchanges.iter())
+            .collect();
+        let mut unconfigured: Vec<&String> = health
+            .keys()
+            .filter(|name| !configured.contains(name))
+            .collect();
+        unconfigured.sort();
+        
+        configured
+            .into_iter()
+            .chain(unconfigured)
+            .map(|name| ExchangeHealthStatus {
+                name: name.clone(),
+                is_primary: *name == self.config.primary_exchange,
+                is_active: *name == self.current_primary,
+                health: health.get(name).cloned(),
+            })
+            .collect()
+    }
+    
+    /// Configured time between health checks, without jitter
+    pub fn health_check_interval(&self) -> Duration {
+        Duration::from_secs(self.config.health_check_interval_secs)
+    }
+    
+    /// Delay before the next health check, jittered when configured
+    pub fn next_health_check_delay(&self) -> Duration {
+        self.config.next_health_check_delay()
+    }
+    
     /// Get the current primary exchange name
     pub fn get_primary_exchange_name(&self) -> String {
         self.current_primary.clone()
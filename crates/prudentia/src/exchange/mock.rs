@@ -4,14 +4,15 @@
 //! of the OODA loop and risk management systems.
 
 use testudo_types::{
-    AccountBalance, ExchangeAdapterTrait, ExchangeError, MarketData, 
-    OrderResult, OrderStatus, TradeOrder, OrderSide
+    AccountBalance, ExchangeAdapterTrait, ExchangeCapabilities, ExchangeError, MarketData, 
+    OcoModification, OrderResult, OrderStatus, OrderType, TradeOrder, OrderSide
 };
 use async_trait::async_trait;
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{SystemTime, Duration};
 use tokio::sync::RwLock;
+use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 
 /// Mock exchange state for testing
@@ -23,12 +24,32 @@ pub struct MockExchangeState {
     pub balances: HashMap<String, AccountBalance>,
     /// Orders placed (order_id -> OrderResult)
     pub orders: HashMap<String, OrderResult>,
+    /// OCO modifications received, in arrival order
+    pub oco_modifications: Vec<OcoModification>,
     /// Whether the exchange is healthy
     pub is_healthy: bool,
+    /// Number of health checks received
+    pub health_checks: u64,
     /// Counter for generating order IDs
     pub order_counter: u64,
     /// Simulated response delay for testing timeouts
     pub response_delay: Option<Duration>,
+    /// Simulated delay before each order is accepted
+    pub order_delay: Option<Duration>,
+    /// Minimum stop-trigger distance by symbol, as a fraction of price
+    pub min_stop_distances: HashMap<String, Decimal>,
+    /// Average true range by symbol, in price units
+    pub average_true_ranges: HashMap<String, Decimal>,
+    /// Whether account calls authenticate; market data stays public
+    pub credentials_valid: bool,
+    /// Accepted orders as submitted, in arrival order
+    pub submitted_orders: Vec<TradeOrder>,
+    /// Order types refused with `InvalidOrder`
+    pub rejected_order_types: Vec<OrderType>,
+    /// 1-based order attempts refused with `InvalidOrder`
+    pub rejected_order_attempts: Vec<u64>,
+    /// Number of orders received, accepted or not
+    pub order_attempts: u64,
 }
 
 impl Default for MockExchangeState {
@@ -88,9 +109,19 @@ impl Default for MockExchangeState {
             market_data,
             balances,
             orders: HashMap::new(),
+            oco_modifications: Vec::new(),
             is_healthy: true,
+            health_checks: 0,
             order_counter: 1000,
             response_delay: None,
+            order_delay: None,
+            min_stop_distances: HashMap::new(),
+            average_true_ranges: HashMap::new(),
+            credentials_valid: true,
+            submitted_orders: Vec::new(),
+            rejected_order_types: Vec::new(),
+            rejected_order_attempts: Vec::new(),
+            order_attempts: 0,
         }
     }
 }
@@ -99,6 +130,7 @@ impl Default for MockExchangeState {
 pub struct MockExchange {
     state: Arc<RwLock<MockExchangeState>>,
     name: String,
+    capabilities: ExchangeCapabilities,
 }
 
 impl MockExchange {
@@ -107,6 +139,7 @@ impl MockExchange {
         Self {
             state: Arc::new(RwLock::new(MockExchangeState::default())),
             name: "MockExchange".to_string(),
+            capabilities: Self::default_capabilities(),
         }
     }
     
@@ -115,6 +148,21 @@ impl MockExchange {
         Self {
             state: Arc::new(RwLock::new(MockExchangeState::default())),
             name,
+            capabilities: Self::default_capabilities(),
+        }
+    }
+    
+    /// Advertise a different set of capabilities
+    pub fn with_capabilities(mut self, capabilities: ExchangeCapabilities) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
+    
+    /// The mock supports OCO modification but no iceberg or futures orders
+    fn default_capabilities() -> ExchangeCapabilities {
+        ExchangeCapabilities {
+            oco: true,
+            ..ExchangeCapabilities::default()
         }
     }
     
@@ -136,12 +184,23 @@ impl MockExchange {
         state.is_healthy = is_healthy;
     }
     
+    /// Number of health checks received so far
+    pub async fn health_check_count(&self) -> u64 {
+        self.state.read().await.health_checks
+    }
+    
     /// Get all orders placed on this mock exchange
     pub async fn get_placed_orders(&self) -> Vec<OrderResult> {
         let state = self.state.read().await;
         state.orders.values().cloned().collect()
     }
     
+    /// Get all OCO modifications received by this mock exchange
+    pub async fn get_oco_modifications(&self) -> Vec<OcoModification> {
+        let state = self.state.read().await;
+        state.oco_modifications.clone()
+    }
+    
     /// Clear all orders (useful for test cleanup)
     pub async fn clear_orders(&self) {
         let mut state = self.state.write().await;
@@ -154,6 +213,48 @@ impl MockExchange {
         state.response_delay = Some(delay);
     }
     
+    /// Delay every order by `delay` before it is accepted
+    pub async fn set_order_delay(&self, delay: Duration) {
+        let mut state = self.state.write().await;
+        state.order_delay = Some(delay);
+    }
+    
+    /// Reject stops closer than `distance` (a fraction of price) for a symbol
+    pub async fn set_min_stop_distance(&self, symbol: &str, distance: Decimal) {
+        let mut state = self.state.write().await;
+        state.min_stop_distances.insert(symbol.to_string(), distance);
+    }
+    
+    /// Report `atr` as the recent average true range of a symbol
+    pub async fn set_average_true_range(&self, symbol: &str, atr: Decimal) {
+        let mut state = self.state.write().await;
+        state.average_true_ranges.insert(symbol.to_string(), atr);
+    }
+    
+    /// Accept or reject the API credentials on account calls
+    pub async fn set_credentials_valid(&self, valid: bool) {
+        let mut state = self.state.write().await;
+        state.credentials_valid = valid;
+    }
+    
+    /// Accepted orders as submitted, in arrival order
+    pub async fn get_submitted_orders(&self) -> Vec<TradeOrder> {
+        let state = self.state.read().await;
+        state.submitted_orders.clone()
+    }
+    
+    /// Refuse every order of `order_type`
+    pub async fn reject_order_type(&self, order_type: OrderType) {
+        let mut state = self.state.write().await;
+        state.rejected_order_types.push(order_type);
+    }
+    
+    /// Refuse the `attempt`-th order received (1-based), whatever its type
+    pub async fn reject_order_attempt(&self, attempt: u64) {
+        let mut state = self.state.write().await;
+        state.rejected_order_attempts.push(attempt);
+    }
+    
     /// Clear response delay
     pub async fn clear_response_delay(&self) {
         let mut state = self.state.write().await;
@@ -209,6 +310,10 @@ impl ExchangeAdapterTrait for MockExchange {
     }
     
     async fn place_order(&self, order: &TradeOrder) -> Result<OrderResult, ExchangeError> {
+        let order_delay = self.state.read().await.order_delay;
+        if let Some(delay) = order_delay {
+            tokio::time::sleep(delay).await;
+        }
         let mut state = self.state.write().await;
         
         if !state.is_healthy {
@@ -217,6 +322,21 @@ impl ExchangeAdapterTrait for MockExchange {
             });
         }
         
+        if !state.credentials_valid {
+            return Err(ExchangeError::AuthenticationError {
+                message: "Invalid API key or signature".to_string(),
+            });
+        }
+        
+        state.order_attempts += 1;
+        if state.rejected_order_types.contains(&order.order_type)
+            || state.rejected_order_attempts.contains(&state.order_attempts)
+        {
+            return Err(ExchangeError::InvalidOrder {
+                reason: format!("Mock exchange rejected {:?} order", order.order_type),
+            });
+        }
+        
         // Extract asset from symbol (e.g., "BTC/USDT" -> "USDT" for buy, "BTC" for sell)
         let asset = if order.side == OrderSide::Buy {
             order.symbol.split('/').nth(1).unwrap_or("USDT")
@@ -227,8 +347,30 @@ impl ExchangeAdapterTrait for MockExchange {
         // Check balance
         let balance = state.balances.get(asset).ok_or(ExchangeError::InsufficientBalance)?;
         
+        let executed_price = order.price.unwrap_or_else(|| {
+            state
+                .market_data
+                .get(&order.symbol)
+                .map(|d| d.last_price)
+                .unwrap_or(dec!(50000.0))
+        });
+        
+        // Quote-sized orders fill whatever base the quote amount buys
+        let executed_quantity = match order.quote_quantity {
+            Some(_) if !self.capabilities.quote_orders => {
+                return Err(ExchangeError::InvalidOrder {
+                    reason: "Quote-quantity orders are not supported".to_string(),
+                });
+            }
+            Some(quote_amount) => quote_amount / executed_price,
+            None => order.quantity,
+        };
+        
         // Simple balance check (in real implementation would be more complex)
-        let required = order.quantity * order.price.unwrap_or(dec!(50000.0));
+        let required = match order.quote_quantity {
+            Some(quote_amount) => quote_amount,
+            None => order.quantity * order.price.unwrap_or(dec!(50000.0)),
+        };
         if balance.free < required && order.side == OrderSide::Buy {
             return Err(ExchangeError::InsufficientBalance);
         }
@@ -243,24 +385,41 @@ impl ExchangeAdapterTrait for MockExchange {
             client_order_id: order.client_order_id.clone(),
             symbol: order.symbol.clone(),
             status: OrderStatus::Filled,  // Mock always fills immediately
-            executed_quantity: order.quantity,
-            executed_price: order.price.unwrap_or_else(|| {
-                state
-                    .market_data
-                    .get(&order.symbol)
-                    .map(|d| d.last_price)
-                    .unwrap_or(dec!(50000.0))
-            }),
-            commission: order.quantity * dec!(0.001),  // 0.1% commission
+            executed_quantity,
+            executed_price,
+            commission: executed_quantity * dec!(0.001),  // 0.1% commission
             timestamp: SystemTime::now(),
         };
         
         // Store order
         state.orders.insert(order_id.clone(), result.clone());
+        state.submitted_orders.push(order.clone());
         
         Ok(result)
     }
     
+    async fn modify_oco_order(&self, modification: &OcoModification) -> Result<OrderResult, ExchangeError> {
+        let mut state = self.state.write().await;
+        
+        if !state.is_healthy {
+            return Err(ExchangeError::ConnectionError {
+                message: "Mock exchange is unhealthy".to_string(),
+            });
+        }
+        
+        let order = state
+            .orders
+            .get(&modification.order_id)
+            .cloned()
+            .ok_or_else(|| ExchangeError::OrderNotFound {
+                order_id: modification.order_id.clone(),
+            })?;
+        
+        state.oco_modifications.push(modification.clone());
+        
+        Ok(order)
+    }
+    
     async fn cancel_order(&self, order_id: &str) -> Result<(), ExchangeError> {
         let mut state = self.state.write().await;
         
@@ -308,6 +467,12 @@ impl ExchangeAdapterTrait for MockExchange {
             });
         }
         
+        if !state.credentials_valid {
+            return Err(ExchangeError::AuthenticationError {
+                message: "Invalid API key or signature".to_string(),
+            });
+        }
+        
         state
             .balances
             .get(asset)
@@ -324,11 +489,18 @@ impl ExchangeAdapterTrait for MockExchange {
             });
         }
         
+        if !state.credentials_valid {
+            return Err(ExchangeError::AuthenticationError {
+                message: "Invalid API key or signature".to_string(),
+            });
+        }
+        
         Ok(state.balances.values().cloned().collect())
     }
     
     async fn health_check(&self) -> Result<bool, ExchangeError> {
-        let state = self.state.read().await;
+        let mut state = self.state.write().await;
+        state.health_checks += 1;
         Ok(state.is_healthy)
     }
     
@@ -336,6 +508,10 @@ impl ExchangeAdapterTrait for MockExchange {
         &self.name
     }
     
+    fn capabilities(&self) -> ExchangeCapabilities {
+        self.capabilities
+    }
+    
     async fn is_symbol_supported(&self, symbol: &str) -> Result<bool, ExchangeError> {
         let state = self.state.read().await;
         
@@ -347,12 +523,22 @@ impl ExchangeAdapterTrait for MockExchange {
         
         Ok(state.market_data.contains_key(symbol))
     }
+    
+    async fn min_stop_distance(&self, symbol: &str) -> Result<Option<Decimal>, ExchangeError> {
+        let state = self.state.read().await;
+        Ok(state.min_stop_distances.get(symbol).copied())
+    }
+    
+    async fn average_true_range(&self, symbol: &str) -> Result<Option<Decimal>, ExchangeError> {
+        let state = self.state.read().await;
+        Ok(state.average_true_ranges.get(symbol).copied())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use testudo_types::{OrderSide, OrderType};
+    use testudo_types::OrderSide;
     
     #[tokio::test]
     async fn test_mock_exchange_market_data() {
@@ -379,6 +565,7 @@ mod tests {
             quantity: dec!(0.01),
             price: Some(dec!(50000.0)),
             stop_price: None,
+            quote_quantity: None,
             client_order_id: "TEST-001".to_string(),
         };
         
@@ -435,6 +622,7 @@ mod tests {
             quantity: dec!(0.01),
             price: Some(dec!(50000.0)),
             stop_price: None,
+            quote_quantity: None,
             client_order_id: "TEST-002".to_string(),
         };
         
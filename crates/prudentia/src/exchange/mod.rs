@@ -12,7 +12,9 @@ pub use testudo_types::*;
 
 pub use binance::{BinanceAdapter, ExchangeConfig};
 pub use circuit_breaker::{CircuitBreaker, CircuitBreakerState};
-pub use failover::{FailoverManager, ExchangeFailoverConfig};
+pub use failover::{
+    ExchangeFailoverConfig, ExchangeHealth, ExchangeHealthStatus, FailoverManager, HEALTH_CHECK_JITTER,
+};
 pub use mock::MockExchange;
 pub use rate_limiter::ExchangeRateLimiter;
 
@@ -55,12 +57,109 @@ impl ExchangeManager {
         let primary_name = failover_manager.get_primary_exchange_name();
         self.get_adapter(&primary_name).await
     }
+
+    /// Adapter to execute on, honouring a preferred exchange
+    ///
+    /// The preferred exchange is used while it is registered and passes a
+    /// health check; otherwise execution fails over to the current primary.
+    pub async fn route_adapter(&self, preferred: Option<&str>) -> Option<Arc<dyn ExchangeAdapterTrait + Send + Sync>> {
+        if let Some(name) = preferred {
+            if let Some(adapter) = self.get_adapter(name).await {
+                if adapter.health_check().await.unwrap_or(false) {
+                    return Some(adapter);
+                }
+                tracing::warn!("Preferred exchange {} is unhealthy, routing to failover primary", name);
+            }
+        }
+        self.get_primary_adapter().await
+    }
+
+    /// Every registered adapter, ordered by name
+    pub async fn adapters(&self) -> Vec<(String, Arc<dyn ExchangeAdapterTrait + Send + Sync>)> {
+        let adapters = self.adapters.read().await;
+        let mut adapters: Vec<_> = adapters
+            .iter()
+            .map(|(name, adapter)| (name.clone(), adapter.clone()))
+            .collect();
+        adapters.sort_by(|a, b| a.0.cmp(&b.0));
+        adapters
+    }
+
+    /// Health-check every adapter and record the results for failover
+    pub async fn check_health(&self) {
+        for (name, adapter) in self.adapters().await {
+            self.check_adapter_health(&name, adapter.as_ref()).await;
+        }
+    }
+
+    /// Health-check only the adapters not checked within the health check interval
+    ///
+    /// Readers of the health report can call this on every read: exchanges
+    /// already covered by recent (or background) checks are not probed again.
+    pub async fn check_stale_health(&self) {
+        let (interval, report) = {
+            let failover_manager = self.failover_manager.read().await;
+            (failover_manager.health_check_interval(), failover_manager.health_report())
+        };
+        for (name, adapter) in self.adapters().await {
+            let checked_at = report
+                .iter()
+                .find(|status| status.name == name)
+                .and_then(|status| status.health.as_ref())
+                .map(|health| health.checked_at);
+            let fresh = match checked_at.map(|checked_at| (chrono::Utc::now() - checked_at).to_std()) {
+                Some(Ok(age)) => age < interval,
+                // Checked "in the future": the clock moved back, trust the result
+                Some(Err(_)) => true,
+                None => false,
+            };
+            if !fresh {
+                self.check_adapter_health(&name, adapter.as_ref()).await;
+            }
+        }
+    }
+
+    async fn check_adapter_health(&self, name: &str, adapter: &(dyn ExchangeAdapterTrait + Send + Sync)) {
+        let health = match adapter.health_check().await {
+            Ok(healthy) => ExchangeHealth {
+                healthy,
+                checked_at: chrono::Utc::now(),
+                error: None,
+            },
+            Err(e) => ExchangeHealth {
+                healthy: false,
+                checked_at: chrono::Utc::now(),
+                error: Some(e.to_string()),
+            },
+        };
+        self.failover_manager.read().await.record_health(name, health);
+    }
+
+    /// Check every adapter's health now, then again after each (jittered) interval
+    ///
+    /// The first check runs immediately so failover decisions have health
+    /// data from startup instead of a full interval later.
+    pub fn spawn_health_checks(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                self.check_health().await;
+                let delay = self.failover_manager.read().await.next_health_check_delay();
+                tokio::time::sleep(delay).await;
+            }
+        })
+    }
+
+    /// Latest health of every exchange, from the failover manager
+    pub async fn health_report(&self) -> Vec<ExchangeHealthStatus> {
+        self.failover_manager.read().await.health_report()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use rust_decimal_macros::dec;
+    use std::time::Duration;
 
     #[tokio::test]
     async fn test_exchange_manager() {
@@ -68,6 +167,7 @@ mod tests {
             primary_exchange: "mock1".to_string(),
             backup_exchanges: vec!["mock2".to_string()],
             health_check_interval_secs: 60,
+            health_check_jitter: false,
         };
         
         let manager = ExchangeManager::new(failover_config);
@@ -86,4 +186,90 @@ mod tests {
         let primary = manager.get_primary_adapter().await.unwrap();
         assert_eq!(primary.exchange_name(), "mock1");
     }
+
+    #[tokio::test]
+    async fn test_routing_prefers_healthy_preferred_exchange() {
+        let manager = ExchangeManager::new(ExchangeFailoverConfig {
+            primary_exchange: "mock1".to_string(),
+            backup_exchanges: vec!["mock2".to_string()],
+            health_check_interval_secs: 60,
+            health_check_jitter: false,
+        });
+        let mock2 = Arc::new(MockExchange::with_name("mock2".to_string()));
+        manager.add_adapter("mock1", Arc::new(MockExchange::with_name("mock1".to_string()))).await;
+        manager.add_adapter("mock2", mock2.clone()).await;
+
+        let routed = manager.route_adapter(Some("mock2")).await.unwrap();
+        assert_eq!(routed.exchange_name(), "mock2");
+        let routed = manager.route_adapter(None).await.unwrap();
+        assert_eq!(routed.exchange_name(), "mock1");
+
+        // An unhealthy or unknown preference fails over to the primary
+        mock2.set_health(false).await;
+        let routed = manager.route_adapter(Some("mock2")).await.unwrap();
+        assert_eq!(routed.exchange_name(), "mock1");
+        let routed = manager.route_adapter(Some("unknown")).await.unwrap();
+        assert_eq!(routed.exchange_name(), "mock1");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_health_checks_run_at_startup_then_at_jittered_intervals() {
+        let config = ExchangeFailoverConfig {
+            primary_exchange: "mock1".to_string(),
+            backup_exchanges: Vec::new(),
+            health_check_interval_secs: 60,
+            health_check_jitter: true,
+        };
+        let fixed = ExchangeFailoverConfig { health_check_jitter: false, ..config.clone() };
+        assert_eq!(fixed.next_health_check_delay(), Duration::from_secs(60));
+
+        let manager = Arc::new(ExchangeManager::new(config));
+        let mock = Arc::new(MockExchange::with_name("mock1".to_string()));
+        manager.add_adapter("mock1", mock.clone()).await;
+
+        // Step the paused clock a second at a time, noting when each check lands
+        let start = tokio::time::Instant::now();
+        let checks_task = manager.clone().spawn_health_checks();
+        let mut checks = Vec::new();
+        while checks.len() < 8 {
+            tokio::task::yield_now().await;
+            if mock.health_check_count().await > checks.len() as u64 {
+                checks.push(tokio::time::Instant::now());
+            }
+            tokio::time::advance(Duration::from_secs(1)).await;
+        }
+        checks_task.abort();
+
+        assert_eq!(checks[0], start, "initial check should not wait an interval");
+        // 60s ± 20%, observed to the nearest second
+        for pair in checks.windows(2) {
+            let gap = pair[1] - pair[0];
+            assert!(gap >= Duration::from_secs(47) && gap <= Duration::from_secs(73), "{:?}", gap);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_health_report_follows_failover_priority() {
+        let manager = ExchangeManager::new(ExchangeFailoverConfig {
+            primary_exchange: "mock1".to_string(),
+            backup_exchanges: vec!["mock2".to_string(), "mock3".to_string()],
+            health_check_interval_secs: 60,
+            health_check_jitter: false,
+        });
+        let mock2 = Arc::new(MockExchange::with_name("mock2".to_string()));
+        mock2.set_health(false).await;
+        manager.add_adapter("mock1", Arc::new(MockExchange::with_name("mock1".to_string()))).await;
+        manager.add_adapter("mock2", mock2).await;
+
+        manager.check_health().await;
+        let report = manager.health_report().await;
+
+        let names: Vec<&str> = report.iter().map(|status| status.name.as_str()).collect();
+        assert_eq!(names, ["mock1", "mock2", "mock3"]);
+        assert!(report[0].is_primary && report[0].is_active);
+        assert!(report[0].health.as_ref().unwrap().healthy);
+        assert!(!report[1].health.as_ref().unwrap().healthy);
+        // Configured but never registered, so never checked
+        assert_eq!(report[2].health, None);
+    }
 }
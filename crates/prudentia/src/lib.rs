@@ -71,27 +71,43 @@ pub mod exchange;
 // Re-export core risk management types and functions
 pub use types::{
     TradeProposal, TradeSide, RiskAssessment, ApprovalStatus, 
-    ProtocolViolation, ViolationSeverity, ProtocolLimits, RiskProfile
+    ProtocolViolation, SuggestedAction, ViolationSeverity, ProtocolLimits, LimitValidationError, MissingTakeProfitPolicy, ExitStrategy, StressTest, RiskProfile,
+    ConfigFormat, ConfigFormatError,
+    CommissionSchedule, FeePreview, FeeRates, Liquidity, SymbolType
 };
 
 pub use risk::{
-    RiskEngine, RiskValidator, TestudoProtocol, RiskValidationResult,
-    RiskRule, RiskViolation, TradeRiskAssessment,
+    RiskEngine, RiskValidator, TestudoProtocol, Outcome, PositionState, TrackedPosition, Reservation, ReservationError, ExitReason, ExitError, RiskValidationResult,
+    RiskRule, RiskViolation, TradeRiskAssessment, TradeTag, TradeTags, TIGHT_STOP_THRESHOLD,
+    SymbolRestrictionRule, SymbolRestrictionViolation,
     AssessmentRiskRule, MaxTradeRiskRule,  // Task 2: New RiskRule trait and implementation
+    RewardRiskAssessmentRule,  // Composable reward/risk minimum
+    AsyncRiskRule,  // Rules that await live data
     RiskManagementProtocol, ProtocolAssessmentResult, ProtocolDecision,  // Task 3: Risk Management Protocol
-    ProtocolError, RuleAssessmentResult,   // Task 3: Supporting types
-    MaxPortfolioRiskRule, OpenPosition, DailyLossLimitRule, ConsecutiveLossLimitRule  // Task 4a, 4b & 4c: Portfolio-level risk management
+    ProtocolError, RuleAssessmentResult, RuleOutcome, RuleFailure, RuleOutcomeCounts,   // Task 3: Supporting types
+    RuleClass, AdvisoryPolicy,  // Hard vs advisory rule aggregation
+    ProtocolStatus, ProtocolStatusChange,  // Status snapshots and their deltas
+    MaxPortfolioRiskRule, OpenPosition, DailyLossLimitRule, ConsecutiveLossLimitRule,  // Task 4a, 4b & 4c: Portfolio-level risk management
+    LossCooldownRule,  // Self-clearing pause after each loss
+    MaxDrawdownRule,  // High-water-mark drawdown halt
+    MaxPositionUnitsRule,  // Absolute per-symbol unit caps
+    MinVolumeRule,  // Liquidity filter on 24h volume
+    CorrelatedGroupRule,  // Open-position caps across correlated symbols
+    MaxSymbolExposureRule,  // Per-symbol cap on open risk
 };
 
 pub use monitoring::{
     PortfolioTracker, PortfolioRiskMetrics, ConsecutiveLossTracker,
-    CircuitBreakerState, CircuitBreakerAction, RealTimeRiskMetrics
+    CircuitBreakerState, CircuitBreakerAction, RealTimeRiskMetrics,
+    DailyLossMonitor, DailyLossAlert, DailyLossAlertLevel,
+    AdverseExcursionMonitor, AdverseExcursionAlert
 };
 
 // Legacy exchange integration exports (for backward compatibility)
 pub use exchange::{
     ExchangeAdapterTrait, BinanceAdapter, ExchangeConfig,
-    CircuitBreaker, ExchangeRateLimiter, FailoverManager, ExchangeFailoverConfig
+    CircuitBreaker, ExchangeRateLimiter, FailoverManager, ExchangeFailoverConfig,
+    ExchangeCapabilities, ExchangeHealth, ExchangeHealthStatus, ExchangeManager
 };
 
 use rust_decimal::Decimal;
@@ -0,0 +1,154 @@
+//! Daily loss limit cool-down alerts
+//!
+//! Warns traders when the realized daily loss approaches the protocol's daily
+//! budget, giving them a chance to stop before the hard limit is reached.
+//! Each threshold fires at most once per trading day.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+/// Severity of a daily loss alert
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DailyLossAlertLevel {
+    /// Daily loss crossed the cool-down warning threshold
+    Warning,
+    /// Daily loss reached the full daily budget
+    Critical,
+}
+
+/// Alert raised when the daily loss crosses a threshold
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DailyLossAlert {
+    pub level: DailyLossAlertLevel,
+    pub daily_loss: Decimal,
+    pub daily_limit: Decimal,
+    /// Fraction of the daily budget consumed (1.0 = limit reached)
+    pub utilization: Decimal,
+}
+
+impl DailyLossAlert {
+    /// Human-readable alert description
+    pub fn message(&self) -> String {
+        match self.level {
+            DailyLossAlertLevel::Warning => format!(
+                "Daily loss ${} has used {}% of the ${} daily budget - consider stopping for the day",
+                self.daily_loss,
+                (self.utilization * dec!(100)).round_dp(1),
+                self.daily_limit
+            ),
+            DailyLossAlertLevel::Critical => format!(
+                "Daily loss ${} has reached the ${} daily limit - trading should stop for the day",
+                self.daily_loss, self.daily_limit
+            ),
+        }
+    }
+}
+
+/// Tracks which daily loss thresholds have already fired today
+#[derive(Debug, Clone)]
+pub struct DailyLossMonitor {
+    warning_threshold: Decimal,
+    warning_sent: bool,
+    critical_sent: bool,
+}
+
+impl DailyLossMonitor {
+    /// Create a monitor warning at `warning_threshold` of the daily budget (e.g. 0.80)
+    pub fn new(warning_threshold: Decimal) -> Self {
+        Self {
+            warning_threshold,
+            warning_sent: false,
+            critical_sent: false,
+        }
+    }
+
+    /// Check the current daily loss and return an alert for a newly crossed threshold
+    ///
+    /// If both thresholds are crossed at once only the critical alert is raised.
+    pub fn check(&mut self, daily_loss: Decimal, daily_limit: Decimal) -> Option<DailyLossAlert> {
+        if daily_limit <= Decimal::ZERO {
+            return None;
+        }
+
+        let utilization = daily_loss / daily_limit;
+
+        let level = if utilization >= Decimal::ONE && !self.critical_sent {
+            self.critical_sent = true;
+            self.warning_sent = true;
+            DailyLossAlertLevel::Critical
+        } else if utilization >= self.warning_threshold && !self.warning_sent {
+            self.warning_sent = true;
+            DailyLossAlertLevel::Warning
+        } else {
+            return None;
+        };
+
+        Some(DailyLossAlert {
+            level,
+            daily_loss,
+            daily_limit,
+            utilization,
+        })
+    }
+
+    /// Re-arm both thresholds (called at the start of each trading day)
+    pub fn reset(&mut self) {
+        self.warning_sent = false;
+        self.critical_sent = false;
+    }
+
+    /// Configured warning threshold as a fraction of the daily budget
+    pub fn warning_threshold(&self) -> Decimal {
+        self.warning_threshold
+    }
+}
+
+impl Default for DailyLossMonitor {
+    fn default() -> Self {
+        Self::new(dec!(0.80))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_warning_fires_once_then_critical() {
+        let mut monitor = DailyLossMonitor::new(dec!(0.80));
+        let limit = dec!(500);
+
+        assert!(monitor.check(dec!(300), limit).is_none());
+
+        // Crossing 80% fires exactly one warning
+        let alert = monitor.check(dec!(400), limit).unwrap();
+        assert_eq!(alert.level, DailyLossAlertLevel::Warning);
+        assert_eq!(alert.utilization, dec!(0.8));
+        assert!(monitor.check(dec!(450), limit).is_none());
+
+        // Crossing 100% fires the critical alert once
+        let alert = monitor.check(dec!(500), limit).unwrap();
+        assert_eq!(alert.level, DailyLossAlertLevel::Critical);
+        assert!(monitor.check(dec!(600), limit).is_none());
+    }
+
+    #[test]
+    fn test_jump_past_limit_raises_only_critical() {
+        let mut monitor = DailyLossMonitor::default();
+
+        let alert = monitor.check(dec!(550), dec!(500)).unwrap();
+        assert_eq!(alert.level, DailyLossAlertLevel::Critical);
+        assert!(monitor.check(dec!(560), dec!(500)).is_none());
+    }
+
+    #[test]
+    fn test_reset_rearms_thresholds() {
+        let mut monitor = DailyLossMonitor::default();
+        assert!(monitor.check(dec!(400), dec!(500)).is_some());
+
+        monitor.reset();
+        let alert = monitor.check(dec!(400), dec!(500)).unwrap();
+        assert_eq!(alert.level, DailyLossAlertLevel::Warning);
+    }
+}
@@ -0,0 +1,94 @@
+//! Maximum adverse excursion (MAE) alerts
+//!
+//! A position's MAE is the worst unrealized loss it has shown since it was
+//! opened. A position that runs most of the way to its stop often goes on to
+//! stop out, so the trader is warned once when MAE reaches a set fraction of
+//! the stop distance, i.e. of the position's risk amount.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use crate::risk::OpenPosition;
+
+/// Alert raised when a position's MAE nears its stop
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AdverseExcursionAlert {
+    pub position_id: String,
+    pub symbol: String,
+    /// Worst unrealized loss so far, as a positive dollar amount
+    pub max_adverse_excursion: Decimal,
+    /// Loss at the stop, in dollars
+    pub risk_amount: Decimal,
+    /// Fraction of the stop distance the MAE has covered (1.0 = at the stop)
+    pub utilization: Decimal,
+}
+
+impl AdverseExcursionAlert {
+    /// Human-readable alert description
+    pub fn message(&self) -> String {
+        format!(
+            "{} position {} has drawn down ${}, {}% of the way to its ${} stop",
+            self.symbol,
+            self.position_id,
+            self.max_adverse_excursion,
+            (self.utilization * dec!(100)).round_dp(1),
+            self.risk_amount
+        )
+    }
+}
+
+/// Tracks which positions have already been warned about their MAE
+#[derive(Debug, Clone)]
+pub struct AdverseExcursionMonitor {
+    warning_threshold: Decimal,
+    alerted: HashSet<String>,
+}
+
+impl AdverseExcursionMonitor {
+    /// Create a monitor warning at `warning_threshold` of the stop distance (e.g. 0.80)
+    pub fn new(warning_threshold: Decimal) -> Self {
+        Self {
+            warning_threshold,
+            alerted: HashSet::new(),
+        }
+    }
+
+    /// Check a position's recorded MAE, alerting the first time it crosses the threshold
+    pub fn check(&mut self, position: &OpenPosition) -> Option<AdverseExcursionAlert> {
+        if position.risk_amount <= Decimal::ZERO || self.alerted.contains(&position.id) {
+            return None;
+        }
+
+        let utilization = position.max_adverse_excursion / position.risk_amount;
+        if utilization < self.warning_threshold {
+            return None;
+        }
+
+        self.alerted.insert(position.id.clone());
+        Some(AdverseExcursionAlert {
+            position_id: position.id.clone(),
+            symbol: position.symbol.clone(),
+            max_adverse_excursion: position.max_adverse_excursion,
+            risk_amount: position.risk_amount,
+            utilization,
+        })
+    }
+
+    /// Forget a closed position so its id can be reused
+    pub fn forget(&mut self, position_id: &str) {
+        self.alerted.remove(position_id);
+    }
+
+    /// Configured warning threshold as a fraction of the stop distance
+    pub fn warning_threshold(&self) -> Decimal {
+        self.warning_threshold
+    }
+}
+
+impl Default for AdverseExcursionMonitor {
+    fn default() -> Self {
+        Self::new(dec!(0.80))
+    }
+}
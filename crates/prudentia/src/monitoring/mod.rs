@@ -6,7 +6,11 @@
 pub mod portfolio_tracker;
 pub mod loss_tracker;
 pub mod metrics;
+pub mod daily_loss_monitor;
+pub mod excursion_monitor;
 
 pub use portfolio_tracker::{PortfolioTracker, PortfolioRiskMetrics};
 pub use loss_tracker::{ConsecutiveLossTracker, CircuitBreakerState, CircuitBreakerAction};
-pub use metrics::{RealTimeRiskMetrics, RiskMetricsCalculator};
\ No newline at end of file
+pub use metrics::{RealTimeRiskMetrics, RiskMetricsCalculator};
+pub use daily_loss_monitor::{DailyLossMonitor, DailyLossAlert, DailyLossAlertLevel};
+pub use excursion_monitor::{AdverseExcursionMonitor, AdverseExcursionAlert};
\ No newline at end of file
@@ -233,6 +233,101 @@ impl TradeRiskAssessment {
     }
 }
 
+/// Stop distance, as a fraction of entry, below which a stop counts as tight
+pub const TIGHT_STOP_THRESHOLD: Decimal = dec!(0.02);
+
+/// Journal tag derived from a trade's setup characteristics
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TradeTag {
+    /// Reward/risk of at least 3:1 (see `is_high_conviction`)
+    HighConviction,
+    /// Stop less than `TIGHT_STOP_THRESHOLD` away from entry
+    TightStop,
+    /// Trade taken against the prevailing trend
+    CounterTrend,
+    /// No take-profit target set
+    NoTarget,
+}
+
+impl TradeTag {
+    /// Tag label as stored in the journal
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TradeTag::HighConviction => "high-conviction",
+            TradeTag::TightStop => "tight-stop",
+            TradeTag::CounterTrend => "counter-trend",
+            TradeTag::NoTarget => "no-target",
+        }
+    }
+}
+
+impl TradeRiskAssessment {
+    /// Classify the setup into journal tags
+    ///
+    /// `prevailing_trend` is the direction of the market trend, when known;
+    /// a trade on the opposite side is tagged counter-trend.
+    pub fn auto_tags(&self, side: TradeSide, prevailing_trend: Option<TradeSide>) -> Vec<TradeTag> {
+        let mut tags = Vec::new();
+        
+        if self.is_high_conviction() {
+            tags.push(TradeTag::HighConviction);
+        }
+        if self.stop_loss_percentage.abs() < TIGHT_STOP_THRESHOLD {
+            tags.push(TradeTag::TightStop);
+        }
+        if prevailing_trend.is_some_and(|trend| trend != side) {
+            tags.push(TradeTag::CounterTrend);
+        }
+        if self.reward_distance.is_none() {
+            tags.push(TradeTag::NoTarget);
+        }
+        
+        tags
+    }
+}
+
+/// Automatic and user-supplied tags journaled together for a trade
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TradeTags {
+    /// Tags derived from the setup by `TradeRiskAssessment::auto_tags`
+    pub auto: Vec<TradeTag>,
+    /// Free-form tags added by the user
+    pub user: Vec<String>,
+}
+
+impl TradeTags {
+    /// Start from the automatic classification of a trade
+    pub fn classify(
+        assessment: &TradeRiskAssessment,
+        side: TradeSide,
+        prevailing_trend: Option<TradeSide>,
+    ) -> Self {
+        Self {
+            auto: assessment.auto_tags(side, prevailing_trend),
+            user: Vec::new(),
+        }
+    }
+    
+    /// Add a user tag, ignoring duplicates of existing tags
+    pub fn with_user_tag(mut self, tag: impl Into<String>) -> Self {
+        let tag = tag.into();
+        if !self.labels().contains(&tag) {
+            self.user.push(tag);
+        }
+        self
+    }
+    
+    /// All tag labels, automatic first
+    pub fn labels(&self) -> Vec<String> {
+        self.auto
+            .iter()
+            .map(|tag| tag.as_str().to_string())
+            .chain(self.user.iter().cloned())
+            .collect()
+    }
+}
+
 /// Risk analysis utilities
 pub struct RiskAnalyzer;
 
@@ -459,6 +554,28 @@ mod tests {
         assert_eq!(assessment.reward_risk_ratio.unwrap(), dec!(3));
     }
     
+    #[test]
+    fn test_auto_tags_from_setup() {
+        // 3:1 reward/risk with a 4% stop
+        let high_conviction_proposal = TradeProposal::new(
+            "BTCUSDT".to_string(),
+            TradeSide::Long,
+            PricePoint::new(dec!(50000)).unwrap(),
+            PricePoint::new(dec!(48000)).unwrap(),
+            Some(PricePoint::new(dec!(56000)).unwrap()),
+            AccountEquity::new(dec!(10000)).unwrap(),
+            RiskPercentage::new(dec!(0.02)).unwrap(),
+        ).unwrap();
+        let position_size = PositionSize::new(dec!(100)).unwrap();
+        let assessment = TradeRiskAssessment::new(&high_conviction_proposal, position_size);
+        
+        let tags = TradeTags::classify(&assessment, TradeSide::Long, Some(TradeSide::Short))
+            .with_user_tag("breakout");
+        assert!(tags.auto.contains(&TradeTag::HighConviction));
+        assert!(!tags.auto.contains(&TradeTag::TightStop));
+        assert_eq!(tags.labels(), vec!["high-conviction", "counter-trend", "breakout"]);
+    }
+    
     #[test]
     fn test_risk_analyzer_portfolio_risk() {
         let proposal1 = create_test_proposal();
@@ -3,10 +3,12 @@
 //! This module implements a RiskRule trait with an assess method that returns
 //! a RiskAssessment, following TDD principles and Roman military discipline.
 
-use crate::types::{TradeProposal, RiskAssessment, ProtocolLimits, ViolationSeverity, ProtocolViolation};
+use crate::risk::rules::{self, RiskRule as _};
+use crate::types::{
+    TradeProposal, TradeSide, RiskAssessment, ProtocolLimits, ViolationSeverity, ProtocolViolation, SuggestedAction,
+};
 use disciplina::PositionSizingCalculator;
 use rust_decimal::Decimal;
-use std::sync::Arc;
 use thiserror::Error;
 
 /// Errors that can occur during risk assessment
@@ -38,6 +40,12 @@ pub trait RiskRule: Send + Sync + std::fmt::Debug {
     
     /// Get a description of what this rule assesses
     fn description(&self) -> &str;
+    
+    /// Record the P&L of a closed trade
+    ///
+    /// Stateful rules update the state later assessments read; stateless
+    /// rules ignore it.
+    fn record_outcome(&self, _pnl: Decimal) {}
 }
 
 /// Task 2: MaxTradeRiskRule implementation
@@ -49,7 +57,7 @@ pub struct MaxTradeRiskRule {
     /// Protocol limits for risk validation
     limits: ProtocolLimits,
     /// Van Tharp position sizing calculator
-    position_calculator: Arc<PositionSizingCalculator>,
+    position_calculator: PositionSizingCalculator,
 }
 
 impl MaxTradeRiskRule {
@@ -62,7 +70,7 @@ impl MaxTradeRiskRule {
     pub fn with_limits(limits: ProtocolLimits) -> Self {
         Self {
             limits,
-            position_calculator: Arc::new(PositionSizingCalculator::new()),
+            position_calculator: PositionSizingCalculator::new(),
         }
     }
     
@@ -107,20 +115,21 @@ impl RiskRule for MaxTradeRiskRule {
         );
         
         // Step 4: Check if risk exceeds maximum allowed
-        if proposal.risk_percentage.value() > self.limits.max_individual_trade_risk {
+        let max_risk = self.limits.enforced_max_individual_trade_risk();
+        if proposal.risk_percentage.value() > max_risk {
             let violation = ProtocolViolation::new(
                 self.rule_name().to_string(),
                 ViolationSeverity::Critical,
                 format!(
                     "Individual trade risk {}% exceeds maximum allowed {}%",
                     proposal.risk_percentage.value() * Decimal::from(100),
-                    self.limits.max_individual_trade_risk * Decimal::from(100)
+                    max_risk * Decimal::from(100)
                 ),
                 proposal.risk_percentage.value(),
-                self.limits.max_individual_trade_risk,
+                max_risk,
                 format!(
                     "Reduce position risk to maximum {}% of account equity",
-                    self.limits.max_individual_trade_risk * Decimal::from(100)
+                    max_risk * Decimal::from(100)
                 ),
             );
             assessment.add_violation(violation);
@@ -138,7 +147,7 @@ impl RiskRule for MaxTradeRiskRule {
             format!(
                 "Trade rejected: Risk {}% exceeds maximum {}% - position sizing would violate Testudo Protocol",
                 proposal.risk_percentage.value() * Decimal::from(100),
-                self.limits.max_individual_trade_risk * Decimal::from(100)
+                max_risk * Decimal::from(100)
             )
         };
         
@@ -160,13 +169,146 @@ impl Default for MaxTradeRiskRule {
     }
 }
 
+/// Reward/risk minimum as a composable assessment rule
+///
+/// Assessment counterpart of [`rules::MinRewardRiskRatioRule`], which does
+/// the checking so both paths agree: a take-profit too close to entry is
+/// rejected with the nearest take-profit that meets the minimum, and a
+/// missing take-profit is only flagged when the user's missing take profit
+/// policy asks for it (never under trailing exits).
+#[derive(Debug, Clone)]
+pub struct RewardRiskAssessmentRule {
+    /// Protocol limits supplying the minimum ratio and take-profit policy
+    limits: ProtocolLimits,
+    /// Van Tharp position sizing calculator
+    position_calculator: PositionSizingCalculator,
+}
+
+impl RewardRiskAssessmentRule {
+    /// Create a new RewardRiskAssessmentRule with the default limits
+    pub fn new() -> Self {
+        Self::with_limits(ProtocolLimits::default())
+    }
+
+    /// Create a RewardRiskAssessmentRule enforcing the given limits
+    pub fn with_limits(limits: ProtocolLimits) -> Self {
+        Self {
+            limits,
+            position_calculator: PositionSizingCalculator::new(),
+        }
+    }
+
+    /// Create a RewardRiskAssessmentRule with an explicit minimum ratio
+    pub fn with_min_ratio(min_ratio: Decimal) -> Self {
+        Self::with_limits(ProtocolLimits {
+            min_reward_risk_ratio: min_ratio,
+            ..ProtocolLimits::default()
+        })
+    }
+
+    /// Configured minimum reward/risk ratio
+    pub fn min_ratio(&self) -> Decimal {
+        self.limits.min_reward_risk_ratio
+    }
+
+    /// Closest take-profit that meets the minimum ratio
+    pub fn required_take_profit(&self, proposal: &TradeProposal) -> Decimal {
+        let reward_distance = proposal.risk_distance() * self.min_ratio();
+        match proposal.side {
+            TradeSide::Long => proposal.entry_price.value() + reward_distance,
+            TradeSide::Short => proposal.entry_price.value() - reward_distance,
+        }
+    }
+}
+
+impl RiskRule for RewardRiskAssessmentRule {
+    fn assess(&self, proposal: &TradeProposal) -> Result<RiskAssessment, AssessmentError> {
+        let position_size = self.position_calculator
+            .calculate_position_size(
+                proposal.account_equity,
+                proposal.risk_percentage,
+                proposal.entry_price,
+                proposal.stop_loss,
+            )
+            .map_err(|e| AssessmentError::PositionSizingFailure {
+                reason: e.to_string()
+            })?;
+
+        let risk_amount = position_size.value() * proposal.risk_distance();
+        let portfolio_impact = risk_amount / proposal.account_equity.value();
+        let ratio = proposal.risk_reward_ratio();
+
+        let mut assessment = RiskAssessment::new(
+            proposal.id,
+            position_size,
+            risk_amount,
+            proposal.risk_percentage.value(),
+            ratio,
+            portfolio_impact,
+        );
+
+        let min_ratio = self.min_ratio();
+        let required_take_profit = self.required_take_profit(proposal);
+        let reasoning = match rules::MinRewardRiskRatioRule::new(self.limits.clone()).validate(proposal) {
+            Ok(()) => match ratio {
+                Some(ratio) => format!(
+                    "Trade approved: Reward/risk ratio {:.2}:1 meets minimum {}:1",
+                    ratio, min_ratio
+                ),
+                None => "Trade approved: no take-profit required by the exit strategy".to_string(),
+            },
+            Err(violation) => {
+                let severity = violation.severity;
+                assessment.add_violation(
+                    ProtocolViolation::new(
+                        violation.rule_name,
+                        severity,
+                        violation.description,
+                        violation.current_value,
+                        violation.limit_value,
+                        format!("Set a take-profit at {} or beyond", required_take_profit),
+                    )
+                    .with_hint(SuggestedAction::SetTakeProfit { to: required_take_profit }),
+                );
+                match ratio {
+                    Some(ratio) => format!(
+                        "Trade rejected: Reward/risk ratio {:.2}:1 below minimum {}:1 - take-profit of {} required",
+                        ratio, min_ratio, required_take_profit
+                    ),
+                    None => format!(
+                        "Trade flagged ({:?}): no take-profit to measure against the {}:1 minimum",
+                        severity, min_ratio
+                    ),
+                }
+            }
+        };
+
+        Ok(assessment.with_reasoning(reasoning))
+    }
+
+    fn rule_name(&self) -> &str {
+        "MinRewardRiskRatio"
+    }
+
+    fn description(&self) -> &str {
+        "Validates that a trade's take-profit offers at least the minimum reward/risk ratio"
+    }
+}
+
+impl Default for RewardRiskAssessmentRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::{TradeSide, ApprovalStatus};
+    use crate::types::{TradeSide, ApprovalStatus, ExitStrategy, MissingTakeProfitPolicy};
     use disciplina::{AccountEquity, RiskPercentage, PricePoint};
     use rust_decimal_macros::dec;
     use proptest::prelude::*;
+    use rust_decimal::prelude::FromPrimitive;
 
     fn create_valid_trade_proposal() -> TradeProposal {
         TradeProposal::new(
@@ -302,6 +444,68 @@ mod tests {
         assert_eq!(assessment.risk_amount, expected_risk_amount);
     }
 
+    #[test]
+    fn test_min_reward_risk_ratio_suggests_qualifying_take_profit() {
+        let rule = RewardRiskAssessmentRule::with_min_ratio(dec!(2));
+        let proposal = |take_profit: Option<Decimal>| {
+            TradeProposal::new(
+                "ETHUSDT".to_string(),
+                TradeSide::Long,
+                PricePoint::new(dec!(3000)).unwrap(),
+                PricePoint::new(dec!(2900)).unwrap(),
+                take_profit.map(|price| PricePoint::new(price).unwrap()),
+                AccountEquity::new(dec!(10000)).unwrap(),
+                RiskPercentage::new(dec!(0.01)).unwrap(),
+            ).unwrap()
+        };
+
+        // The default proposal is exactly 2:1
+        assert!(rule.assess(&create_valid_trade_proposal()).unwrap().violations.is_empty());
+
+        // $100 of risk against $150 of reward is 1.5:1; 2:1 needs a target at 3200
+        let assessment = rule.assess(&proposal(Some(dec!(3150)))).unwrap();
+        assert_eq!(assessment.approval_status, ApprovalStatus::RequiresReduction);
+        let violation = &assessment.violations[0];
+        assert_eq!(violation.severity, ViolationSeverity::High);
+        assert_eq!(violation.current_value, dec!(1.5));
+        assert_eq!(violation.hint, Some(SuggestedAction::SetTakeProfit { to: dec!(3200) }));
+
+        // The default policy allows trades without a take-profit
+        let assessment = rule.assess(&proposal(None)).unwrap();
+        assert!(assessment.is_approved());
+        assert!(assessment.violations.is_empty());
+    }
+
+    #[test]
+    fn test_reward_risk_assessment_follows_missing_take_profit_policy() {
+        let no_target = TradeProposal::new(
+            "ETHUSDT".to_string(),
+            TradeSide::Long,
+            PricePoint::new(dec!(3000)).unwrap(),
+            PricePoint::new(dec!(2900)).unwrap(),
+            None,
+            AccountEquity::new(dec!(10000)).unwrap(),
+            RiskPercentage::new(dec!(0.01)).unwrap(),
+        ).unwrap();
+        let warning = ProtocolLimits {
+            missing_take_profit_policy: MissingTakeProfitPolicy::Warning,
+            ..ProtocolLimits::default()
+        };
+
+        let assessment = RewardRiskAssessmentRule::with_limits(warning.clone()).assess(&no_target).unwrap();
+        assert!(assessment.is_approved());
+        assert_eq!(assessment.violations[0].severity, ViolationSeverity::Warning);
+        assert_eq!(assessment.violations[0].hint, Some(SuggestedAction::SetTakeProfit { to: dec!(3200) }));
+
+        // Users on trailing exits are not asked for a target
+        let trailing = ProtocolLimits {
+            exit_strategy: ExitStrategy::Trailing,
+            ..warning
+        };
+        let assessment = RewardRiskAssessmentRule::with_limits(trailing).assess(&no_target).unwrap();
+        assert!(assessment.violations.is_empty());
+    }
+
     // Property-based tests for mathematical accuracy (following Testudo Protocol)
     proptest! {
         #[test]
@@ -0,0 +1,48 @@
+//! Asynchronous risk rules
+//!
+//! Some rules need live data to assess a trade: current prices, correlations,
+//! or balances fetched from an exchange or the database. `AsyncRiskRule` lets
+//! those rules await their queries instead of blocking the caller. Every
+//! synchronous [`RiskRule`] is also an `AsyncRiskRule` through a blanket
+//! implementation, so both kinds can be mixed in one `RiskManagementProtocol`.
+
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+
+use crate::risk::assessment_rules::{AssessmentError, RiskRule};
+use crate::types::{RiskAssessment, TradeProposal};
+
+/// A risk rule whose assessment may await live data
+#[async_trait]
+pub trait AsyncRiskRule: Send + Sync + std::fmt::Debug {
+    /// Assess a trade proposal and return a complete risk assessment
+    async fn assess(&self, proposal: &TradeProposal) -> Result<RiskAssessment, AssessmentError>;
+
+    /// Get the name of this rule for logging and identification
+    fn rule_name(&self) -> &str;
+
+    /// Get a description of what this rule assesses
+    fn description(&self) -> &str;
+
+    /// Record the P&L of a closed trade
+    fn record_outcome(&self, _pnl: Decimal) {}
+}
+
+#[async_trait]
+impl<T: RiskRule + ?Sized> AsyncRiskRule for T {
+    async fn assess(&self, proposal: &TradeProposal) -> Result<RiskAssessment, AssessmentError> {
+        RiskRule::assess(self, proposal)
+    }
+
+    fn rule_name(&self) -> &str {
+        RiskRule::rule_name(self)
+    }
+
+    fn description(&self) -> &str {
+        RiskRule::description(self)
+    }
+
+    fn record_outcome(&self, pnl: Decimal) {
+        RiskRule::record_outcome(self, pnl)
+    }
+}
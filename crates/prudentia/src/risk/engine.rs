@@ -6,12 +6,11 @@
 use crate::types::{TradeProposal, RiskAssessment, ProtocolLimits, ApprovalStatus, ProtocolViolation, ViolationSeverity};
 use crate::risk::rules::RiskRule;
 use crate::risk::rules::{
-    MaxIndividualTradeRiskRule, MinIndividualTradeRiskRule, MinRewardRiskRatioRule,
-    StopLossDirectionRule, TakeProfitDirectionRule, ValidSymbolRule,
+    MaxIndividualTradeRiskRule, MaxRewardRiskRatioRule, MinIndividualTradeRiskRule,
+    MinRewardRiskRatioRule, StopLossDirectionRule, TakeProfitDirectionRule, ValidSymbolRule,
 };
 use disciplina::{PositionSizingCalculator, PositionSize};
 use rust_decimal::Decimal;
-use std::sync::Arc;
 use tracing::{info, warn, error};
 
 /// Comprehensive risk validation engine
@@ -22,7 +21,7 @@ use tracing::{info, warn, error};
 #[derive(Debug)]
 pub struct RiskEngine {
     /// Van Tharp position sizing calculator
-    position_calculator: Arc<PositionSizingCalculator>,
+    position_calculator: PositionSizingCalculator,
     /// Protocol limits configuration
     protocol_limits: ProtocolLimits,
     /// List of risk rules to apply (ordered by priority)
@@ -38,7 +37,7 @@ impl RiskEngine {
     
     /// Create a new risk engine with custom protocol limits
     pub fn with_limits(protocol_limits: ProtocolLimits) -> Self {
-        let position_calculator = Arc::new(PositionSizingCalculator::new());
+        let position_calculator = PositionSizingCalculator::new();
         
         // Create standard risk rules with the given limits
         let mut risk_rules: Vec<Box<dyn RiskRule>> = vec![
@@ -47,6 +46,7 @@ impl RiskEngine {
             Box::new(TakeProfitDirectionRule),
             Box::new(MaxIndividualTradeRiskRule::new(protocol_limits.clone())),
             Box::new(MinRewardRiskRatioRule::new(protocol_limits.clone())),
+            Box::new(MaxRewardRiskRatioRule::new(protocol_limits.clone())),
             Box::new(MinIndividualTradeRiskRule::new(protocol_limits.clone())),
         ];
         
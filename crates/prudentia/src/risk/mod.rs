@@ -7,19 +7,25 @@
 pub mod rules;
 pub mod assessment;
 pub mod assessment_rules; // Task 2: New RiskRule trait with assess method
+pub mod async_rules;
 pub mod portfolio_rules; // Task 4a: Portfolio-level risk rules
 pub mod protocol;
 pub mod validator;
 pub mod engine;
 
-pub use rules::{RiskRule, RiskViolation};
-pub use assessment::TradeRiskAssessment;
-pub use assessment_rules::{RiskRule as AssessmentRiskRule, MaxTradeRiskRule}; // Task 2 exports
-pub use portfolio_rules::{MaxPortfolioRiskRule, OpenPosition, DailyLossLimitRule, ConsecutiveLossLimitRule}; // Task 4a, 4b & 4c exports
+pub use rules::{
+    RiskRule, RiskViolation, SymbolBlackout, SymbolRestriction, SymbolRestrictionRule,
+    SymbolRestrictionViolation,
+};
+pub use assessment::{TradeRiskAssessment, TradeTag, TradeTags, TIGHT_STOP_THRESHOLD};
+pub use assessment_rules::{RiskRule as AssessmentRiskRule, MaxTradeRiskRule, RewardRiskAssessmentRule}; // Task 2 exports
+pub use async_rules::AsyncRiskRule;
+pub use portfolio_rules::{MaxPortfolioRiskRule, OpenPosition, DailyLossLimitRule, ConsecutiveLossLimitRule, LossCooldownRule, MaxDrawdownRule, MaxPositionUnitsRule, MinVolumeRule, CorrelatedGroupRule, MaxSymbolExposureRule}; // Task 4a, 4b & 4c exports
 pub use protocol::{
-    TestudoProtocol, 
+    TestudoProtocol, Outcome, PositionState, TrackedPosition, Reservation, ReservationError, ExitReason, ExitError,
     RiskManagementProtocol, ProtocolAssessmentResult, ProtocolDecision, 
-    ProtocolError, RuleAssessmentResult  // Task 3 exports
+    ProtocolError, RuleAssessmentResult, RuleOutcome, RuleFailure, RuleOutcomeCounts,  // Task 3 exports
+    RuleClass, AdvisoryPolicy, ProtocolStatus, ProtocolStatusChange
 };
 pub use validator::{RiskValidator, RiskValidationResult};
 pub use engine::RiskEngine;
\ No newline at end of file
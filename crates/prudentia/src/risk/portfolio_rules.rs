@@ -3,14 +3,18 @@
 //! This module implements portfolio-level risk rules that consider the aggregate
 //! risk across all positions, following Roman discipline in capital allocation.
 
+use crate::monitoring::{AdverseExcursionAlert, AdverseExcursionMonitor};
 use crate::risk::assessment_rules::{RiskRule, AssessmentError};
-use crate::types::{TradeProposal, RiskAssessment, ProtocolLimits, ViolationSeverity, ProtocolViolation};
+use crate::types::{
+    TradeProposal, RiskAssessment, ProtocolLimits, ViolationSeverity, ProtocolViolation, SuggestedAction,
+};
 use disciplina::PositionSizingCalculator;
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
-use std::sync::Arc;
-use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::collections::{HashMap, HashSet};
 use std::time::SystemTime;
+use testudo_types::MarketData;
 
 /// Represents an open position for portfolio risk calculations
 #[derive(Debug, Clone)]
@@ -27,6 +31,45 @@ pub struct OpenPosition {
     pub opened_at: SystemTime,
     /// Current unrealized P&L
     pub unrealized_pnl: Decimal,
+    /// Worst unrealized loss since opening (MAE), as a positive dollar amount
+    pub max_adverse_excursion: Decimal,
+}
+
+impl OpenPosition {
+    /// Record the latest unrealized P&L, deepening the MAE on a new low
+    pub fn record_pnl(&mut self, unrealized_pnl: Decimal) {
+        self.unrealized_pnl = unrealized_pnl;
+        self.max_adverse_excursion = self.max_adverse_excursion.max(-unrealized_pnl);
+    }
+}
+
+/// Size `proposal` with Van Tharp sizing and open an assessment on it
+///
+/// The assessment carries no violations yet; each rule adds its own.
+fn sized_assessment(
+    calculator: &PositionSizingCalculator,
+    proposal: &TradeProposal,
+) -> Result<RiskAssessment, AssessmentError> {
+    let position_size = calculator
+        .calculate_position_size(
+            proposal.account_equity,
+            proposal.risk_percentage,
+            proposal.entry_price,
+            proposal.stop_loss,
+        )
+        .map_err(|e| AssessmentError::PositionSizingFailure {
+            reason: e.to_string()
+        })?;
+    
+    let risk_amount = position_size.value() * proposal.risk_distance();
+    Ok(RiskAssessment::new(
+        proposal.id,
+        position_size,
+        risk_amount,
+        proposal.risk_percentage.value(),
+        proposal.risk_reward_ratio(),
+        risk_amount / proposal.account_equity.value(),
+    ))
 }
 
 /// Task 4a: MaxPortfolioRiskRule implementation
@@ -34,18 +77,68 @@ pub struct OpenPosition {
 /// This rule ensures that the total portfolio risk exposure across all open
 /// positions plus the proposed new trade does not exceed the maximum allowed
 /// portfolio risk percentage as defined by the Testudo Protocol.
+///
+/// Clones track the same open positions, so a rule registered with a
+/// `RiskManagementProtocol` sees fills recorded through the handle the
+/// caller kept.
 #[derive(Debug, Clone)]
 pub struct MaxPortfolioRiskRule {
     /// Protocol limits for validation
     limits: ProtocolLimits,
     /// Van Tharp position sizing calculator
-    position_calculator: Arc<PositionSizingCalculator>,
+    position_calculator: PositionSizingCalculator,
+    /// Open positions and the cached risk derived from them
+    state: Arc<Mutex<PortfolioState>>,
+}
+
+#[derive(Debug)]
+struct PortfolioState {
     /// Current open positions for portfolio risk calculation
     open_positions: HashMap<String, OpenPosition>,
     /// Cache of current total portfolio risk percentage
     cached_portfolio_risk: Decimal,
     /// Last time portfolio risk was calculated
     last_calculation: SystemTime,
+    /// Warns once per position when its MAE nears the stop
+    excursion_monitor: AdverseExcursionMonitor,
+}
+
+impl PortfolioState {
+    fn new() -> Self {
+        Self {
+            open_positions: HashMap::new(),
+            cached_portfolio_risk: Decimal::ZERO,
+            last_calculation: SystemTime::now(),
+            excursion_monitor: AdverseExcursionMonitor::default(),
+        }
+    }
+    
+    fn add_open_position(&mut self, position: OpenPosition) {
+        self.open_positions.insert(position.id.clone(), position);
+        self.invalidate_cache();
+    }
+    
+    fn remove_open_position(&mut self, position_id: &str) -> Option<OpenPosition> {
+        let removed = self.open_positions.remove(position_id);
+        if removed.is_some() {
+            self.excursion_monitor.forget(position_id);
+            self.invalidate_cache();
+        }
+        removed
+    }
+    
+    /// Calculate total portfolio risk across all open positions
+    fn portfolio_risk(&self) -> Decimal {
+        self.open_positions
+            .values()
+            .map(|position| position.risk_percentage)
+            .sum()
+    }
+    
+    /// Invalidate cached portfolio risk calculation
+    fn invalidate_cache(&mut self) {
+        self.last_calculation = SystemTime::UNIX_EPOCH; // Force recalculation
+    }
 }
 
 impl MaxPortfolioRiskRule {
@@ -58,12 +151,16 @@ impl MaxPortfolioRiskRule {
     pub fn with_limits(limits: ProtocolLimits) -> Self {
         Self {
             limits,
-            position_calculator: Arc::new(PositionSizingCalculator::new()),
-            open_positions: HashMap::new(),
-            cached_portfolio_risk: Decimal::ZERO,
-            last_calculation: SystemTime::now(),
+            position_calculator: PositionSizingCalculator::new(),
+            state: Arc::new(Mutex::new(PortfolioState::new())),
         }
     }
+
+    /// Warn when a position's MAE reaches `threshold` of its stop distance
+    pub fn with_mae_warning_threshold(self, threshold: Decimal) -> Self {
+        self.state.lock().unwrap().excursion_monitor = AdverseExcursionMonitor::new(threshold);
+        self
+    }
     
     /// Create a conservative MaxPortfolioRiskRule for new traders
     pub fn conservative() -> Self {
@@ -76,104 +173,108 @@ impl MaxPortfolioRiskRule {
     }
     
     /// Add an open position to the portfolio tracking
-    pub fn add_open_position(&mut self, position: OpenPosition) {
-        self.open_positions.insert(position.id.clone(), position);
-        self.invalidate_cache();
+    pub fn add_open_position(&self, position: OpenPosition) {
+        self.state.lock().unwrap().add_open_position(position);
     }
     
     /// Remove an open position (when closed)
-    pub fn remove_open_position(&mut self, position_id: &str) -> Option<OpenPosition> {
-        let removed = self.open_positions.remove(position_id);
-        if removed.is_some() {
-            self.invalidate_cache();
-        }
-        removed
+    pub fn remove_open_position(&self, position_id: &str) -> Option<OpenPosition> {
+        self.state.lock().unwrap().remove_open_position(position_id)
     }
     
-    /// Update an existing position's risk or P&L
-    pub fn update_position(&mut self, position_id: &str, unrealized_pnl: Decimal) {
-        if let Some(position) = self.open_positions.get_mut(position_id) {
-            position.unrealized_pnl = unrealized_pnl;
-            self.invalidate_cache();
-        }
+    /// Update an existing position's P&L and MAE
+    ///
+    /// Returns an alert the first time the position's MAE reaches the
+    /// warning threshold of its stop distance.
+    pub fn update_position(&self, position_id: &str, unrealized_pnl: Decimal) -> Option<AdverseExcursionAlert> {
+        let mut state = self.state.lock().unwrap();
+        let state = &mut *state;
+        let position = state.open_positions.get_mut(position_id)?;
+        position.record_pnl(unrealized_pnl);
+        let alert = state.excursion_monitor.check(position);
+        state.invalidate_cache();
+        alert
     }
     
     /// Get current total portfolio risk percentage
-    pub fn current_portfolio_risk(&mut self) -> Decimal {
+    pub fn current_portfolio_risk(&self) -> Decimal {
+        let mut state = self.state.lock().unwrap();
         // Use cached value if recent (within 1 second)
-        if self.last_calculation.elapsed().unwrap_or_default().as_secs() < 1 {
-            return self.cached_portfolio_risk;
+        if state.last_calculation.elapsed().unwrap_or_default().as_secs() < 1 {
+            return state.cached_portfolio_risk;
         }
         
         // Recalculate portfolio risk
-        self.cached_portfolio_risk = self.calculate_portfolio_risk();
-        self.last_calculation = SystemTime::now();
-        self.cached_portfolio_risk
+        state.cached_portfolio_risk = state.portfolio_risk();
+        state.last_calculation = SystemTime::now();
+        state.cached_portfolio_risk
     }
     
-    /// Calculate total portfolio risk across all open positions
-    fn calculate_portfolio_risk(&self) -> Decimal {
-        self.open_positions
+    /// Fewest open positions, largest risk first, whose closure frees `excess_risk`
+    fn positions_to_close(state: &PortfolioState, excess_risk: Decimal) -> u32 {
+        let mut risks: Vec<Decimal> = state.open_positions
             .values()
             .map(|position| position.risk_percentage)
-            .sum()
+            .collect();
+        risks.sort_by(|a, b| b.cmp(a));
+        
+        let mut freed = Decimal::ZERO;
+        let mut count = 0;
+        for risk in risks {
+            if freed >= excess_risk {
+                break;
+            }
+            freed += risk;
+            count += 1;
+        }
+        count
     }
     
     /// Get number of open positions
     pub fn position_count(&self) -> usize {
-        self.open_positions.len()
+        self.state.lock().unwrap().open_positions.len()
     }
     
     /// Get total risk amount in dollars across all positions
     pub fn total_risk_amount(&self) -> Decimal {
-        self.open_positions
+        self.state
+            .lock()
+            .unwrap()
+            .open_positions
             .values()
             .map(|position| position.risk_amount)
             .sum()
     }
-    
-    /// Invalidate cached portfolio risk calculation
-    fn invalidate_cache(&mut self) {
-        self.last_calculation = SystemTime::UNIX_EPOCH; // Force recalculation
-    }
 }
 
 impl RiskRule for MaxPortfolioRiskRule {
     fn assess(&self, proposal: &TradeProposal) -> Result<RiskAssessment, AssessmentError> {
-        // Step 1: Calculate position size for the proposed trade
-        let position_size = self.position_calculator
-            .calculate_position_size(
-                proposal.account_equity,
-                proposal.risk_percentage,
-                proposal.entry_price,
-                proposal.stop_loss,
-            )
-            .map_err(|e| AssessmentError::PositionSizingFailure { 
-                reason: e.to_string() 
-            })?;
-        
-        // Step 2: Calculate risk metrics for proposed trade
-        let risk_distance = proposal.risk_distance();
-        let risk_amount = position_size.value() * risk_distance;
-        let trade_risk_percentage = proposal.risk_percentage.value();
-        let portfolio_impact = risk_amount / proposal.account_equity.value();
-        
-        // Step 3: Calculate current portfolio risk
-        let current_portfolio_risk = self.calculate_portfolio_risk();
+        // Step 1: Size the proposed trade and open the assessment
+        let mut assessment = sized_assessment(&self.position_calculator, proposal)?;
+        let position_size = assessment.position_size;
+        let trade_risk_percentage = assessment.risk_percentage;
+        
+        // Step 2: Calculate current portfolio risk
+        let state = self.state.lock().unwrap();
+        let current_portfolio_risk = state.portfolio_risk();
         let projected_portfolio_risk = current_portfolio_risk + trade_risk_percentage;
         
-        // Step 4: Create initial assessment
-        let mut assessment = RiskAssessment::new(
-            proposal.id,
-            position_size,
-            risk_amount,
-            trade_risk_percentage,
-            proposal.risk_reward_ratio(),
-            portfolio_impact,
-        );
-        
-        // Step 5: Check if projected portfolio risk exceeds limits
+        // Step 3: Check if projected portfolio risk exceeds limits
         if projected_portfolio_risk > self.limits.max_total_portfolio_risk {
+            let available_budget = self.limits.max_total_portfolio_risk - current_portfolio_risk;
+            let hint = if available_budget > Decimal::ZERO {
+                // Size scales linearly with risk, so shrink it to fit the budget
+                SuggestedAction::ReducePositionSize {
+                    to: position_size.value() * available_budget / trade_risk_percentage,
+                }
+            } else {
+                SuggestedAction::ClosePositions {
+                    count: Self::positions_to_close(
+                        &state,
+                        projected_portfolio_risk - self.limits.max_total_portfolio_risk,
+                    ),
+                }
+            };
             let violation = ProtocolViolation::new(
                 self.rule_name().to_string(),
                 ViolationSeverity::Critical,
@@ -188,13 +289,13 @@ impl RiskRule for MaxPortfolioRiskRule {
                 self.limits.max_total_portfolio_risk,
                 format!(
                     "Reduce position size or close existing positions. Available risk budget: {:.1}%",
-                    (self.limits.max_total_portfolio_risk - current_portfolio_risk) * dec!(100)
+                    available_budget * dec!(100)
                 ),
-            );
+            ).with_hint(hint);
             assessment.add_violation(violation);
         }
         
-        // Step 6: Add portfolio context to reasoning
+        // Step 4: Add portfolio context to reasoning
         let reasoning = if assessment.is_approved() {
             format!(
                 "Portfolio risk approved: Adding {:.1}% trade risk to current {:.1}% portfolio risk = {:.1}% total (within {:.1}% limit). {} positions currently open.",
@@ -202,7 +303,7 @@ impl RiskRule for MaxPortfolioRiskRule {
                 current_portfolio_risk * dec!(100),
                 projected_portfolio_risk * dec!(100),
                 self.limits.max_total_portfolio_risk * dec!(100),
-                self.open_positions.len()
+                state.open_positions.len()
             )
         } else {
             format!(
@@ -236,22 +337,48 @@ impl Default for MaxPortfolioRiskRule {
 /// 
 /// This rule tracks daily profit and loss and prevents trades that would cause
 /// the daily loss to exceed configured limits. Automatically resets at market open.
+///
+/// The day's P&L lives behind a shared lock, so clones share it and outcomes
+/// recorded through any handle count toward the next assessment.
 #[derive(Debug, Clone)]
 pub struct DailyLossLimitRule {
     /// Protocol limits for validation
     limits: ProtocolLimits,
     /// Van Tharp position sizing calculator
-    position_calculator: Arc<PositionSizingCalculator>,
-    /// Current daily profit/loss (negative = loss)
-    daily_pnl: Decimal,
+    position_calculator: PositionSizingCalculator,
     /// Maximum daily loss allowed (positive value, e.g., 1000 = $1000 max loss)
     max_daily_loss: Decimal,
+    /// Trading session timezone offset in hours (default: UTC)
+    timezone_offset_hours: i8,
+    /// The current trading day's P&L and trade count
+    state: Arc<Mutex<DailyLossState>>,
+}
+
+#[derive(Debug)]
+struct DailyLossState {
+    /// Current daily profit/loss (negative = loss)
+    daily_pnl: Decimal,
     /// Date when daily P&L was last reset (for automatic reset)
     last_reset_date: SystemTime,
     /// Number of trades taken today (for context)
     daily_trade_count: u32,
-    /// Trading session timezone offset in hours (default: UTC)
-    timezone_offset_hours: i8,
+}
+
+impl DailyLossState {
+    /// Current daily loss (negative P&L, returned as positive value)
+    fn daily_loss(&self) -> Decimal {
+        if self.daily_pnl < Decimal::ZERO {
+            self.daily_pnl.abs()
+        } else {
+            Decimal::ZERO
+        }
+    }
+    
+    fn reset(&mut self) {
+        self.daily_pnl = Decimal::ZERO;
+        self.daily_trade_count = 0;
+        self.last_reset_date = SystemTime::now();
+    }
 }
 
 impl DailyLossLimitRule {
@@ -264,12 +391,14 @@ impl DailyLossLimitRule {
     pub fn with_daily_limit(max_daily_loss: Decimal) -> Self {
         Self {
             limits: ProtocolLimits::default(),
-            position_calculator: Arc::new(PositionSizingCalculator::new()),
-            daily_pnl: Decimal::ZERO,
+            position_calculator: PositionSizingCalculator::new(),
             max_daily_loss,
-            last_reset_date: SystemTime::now(),
-            daily_trade_count: 0,
             timezone_offset_hours: 0, // UTC default
+            state: Arc::new(Mutex::new(DailyLossState {
+                daily_pnl: Decimal::ZERO,
+                last_reset_date: SystemTime::now(),
+                daily_trade_count: 0,
+            })),
         }
     }
     
@@ -290,31 +419,28 @@ impl DailyLossLimitRule {
     }
     
     /// Record a completed trade's P&L
-    pub fn record_trade_pnl(&mut self, pnl: Decimal) {
-        self.check_daily_reset();
-        self.daily_pnl += pnl;
-        self.daily_trade_count += 1;
+    pub fn record_trade_pnl(&self, pnl: Decimal) {
+        let mut state = self.current_day();
+        state.daily_pnl += pnl;
+        state.daily_trade_count += 1;
     }
     
     /// Get current daily P&L
-    pub fn current_daily_pnl(&mut self) -> Decimal {
-        self.check_daily_reset();
-        self.daily_pnl
+    pub fn current_daily_pnl(&self) -> Decimal {
+        self.current_day().daily_pnl
     }
     
     /// Get current daily loss (negative P&L, returned as positive value)
-    pub fn current_daily_loss(&mut self) -> Decimal {
-        let pnl = self.current_daily_pnl();
-        if pnl < Decimal::ZERO {
-            pnl.abs() // Return loss as positive value
-        } else {
-            Decimal::ZERO
-        }
+    pub fn current_daily_loss(&self) -> Decimal {
+        self.current_day().daily_loss()
     }
     
     /// Get available loss budget remaining today
-    pub fn available_loss_budget(&mut self) -> Decimal {
-        let current_loss = self.current_daily_loss();
+    pub fn available_loss_budget(&self) -> Decimal {
+        self.loss_budget_after(self.current_daily_loss())
+    }
+    
+    fn loss_budget_after(&self, current_loss: Decimal) -> Decimal {
         if current_loss >= self.max_daily_loss {
             Decimal::ZERO
         } else {
@@ -323,13 +449,19 @@ impl DailyLossLimitRule {
     }
     
     /// Get number of trades taken today
-    pub fn daily_trade_count(&mut self) -> u32 {
-        self.check_daily_reset();
-        self.daily_trade_count
+    pub fn daily_trade_count(&self) -> u32 {
+        self.current_day().daily_trade_count
+    }
+    
+    /// Lock the daily state, first resetting it if a new trading day has begun
+    fn current_day(&self) -> std::sync::MutexGuard<'_, DailyLossState> {
+        let mut state = self.state.lock().unwrap();
+        self.check_daily_reset(&mut state);
+        state
     }
     
     /// Check if we need to reset daily counters (new trading day)
-    fn check_daily_reset(&mut self) {
+    fn check_daily_reset(&self, state: &mut DailyLossState) {
         let now = SystemTime::now();
         
         // Calculate market open time for today in the configured timezone
@@ -339,16 +471,16 @@ impl DailyLossLimitRule {
             .as_secs();
         
         let today_market_open_seconds = self.calculate_market_open_seconds(seconds_since_epoch);
-        let last_reset_seconds = self.last_reset_date.duration_since(SystemTime::UNIX_EPOCH)
+        let last_reset_seconds = state.last_reset_date.duration_since(SystemTime::UNIX_EPOCH)
             .unwrap_or_default()
             .as_secs();
         
         // Reset if it's been more than 24 hours or if we've crossed market open
         if seconds_since_epoch >= today_market_open_seconds && 
            last_reset_seconds < today_market_open_seconds {
-            self.daily_pnl = Decimal::ZERO;
-            self.daily_trade_count = 0;
-            self.last_reset_date = now;
+            state.daily_pnl = Decimal::ZERO;
+            state.daily_trade_count = 0;
+            state.last_reset_date = now;
         }
     }
     
@@ -371,54 +503,29 @@ impl DailyLossLimitRule {
     }
     
     /// Manually reset daily counters (for testing or manual intervention)
-    pub fn reset_daily_counters(&mut self) {
-        self.daily_pnl = Decimal::ZERO;
-        self.daily_trade_count = 0;
-        self.last_reset_date = SystemTime::now();
+    pub fn reset_daily_counters(&self) {
+        self.state.lock().unwrap().reset();
     }
 }
 
 impl RiskRule for DailyLossLimitRule {
     fn assess(&self, proposal: &TradeProposal) -> Result<RiskAssessment, AssessmentError> {
-        // Create mutable copy to check daily reset
-        let mut mutable_self = self.clone();
-        mutable_self.check_daily_reset();
-        
-        // Step 1: Calculate position size for the proposed trade
-        let position_size = mutable_self.position_calculator
-            .calculate_position_size(
-                proposal.account_equity,
-                proposal.risk_percentage,
-                proposal.entry_price,
-                proposal.stop_loss,
-            )
-            .map_err(|e| AssessmentError::PositionSizingFailure { 
-                reason: e.to_string() 
-            })?;
-        
-        // Step 2: Calculate potential loss from this trade
-        let risk_distance = proposal.risk_distance();
-        let potential_trade_loss = position_size.value() * risk_distance;
-        let trade_risk_percentage = proposal.risk_percentage.value();
-        let portfolio_impact = potential_trade_loss / proposal.account_equity.value();
-        
-        // Step 3: Calculate projected daily loss if this trade hits stop loss
-        let current_daily_loss = mutable_self.current_daily_loss();
-        let projected_daily_loss = current_daily_loss + potential_trade_loss;
+        // Hold the day's state for the whole assessment so a concurrent
+        // outcome cannot land between reading the loss and the budget
+        let state = self.current_day();
         
-        // Step 4: Create initial assessment
-        let mut assessment = RiskAssessment::new(
-            proposal.id,
-            position_size,
-            potential_trade_loss,
-            trade_risk_percentage,
-            proposal.risk_reward_ratio(),
-            portfolio_impact,
-        );
+        // Step 1: Size the proposed trade and open the assessment
+        let mut assessment = sized_assessment(&self.position_calculator, proposal)?;
+        let position_size = assessment.position_size;
+        let potential_trade_loss = assessment.risk_amount;
         
-        // Step 5: Check if projected daily loss exceeds limits
-        let available_budget = mutable_self.available_loss_budget();
-        if projected_daily_loss > mutable_self.max_daily_loss {
+        // Step 2: Calculate projected daily loss if this trade hits stop loss
+        let current_daily_loss = state.daily_loss();
+        let projected_daily_loss = current_daily_loss + potential_trade_loss;
+        
+        // Step 3: Check if projected daily loss exceeds limits
+        let available_budget = self.loss_budget_after(current_daily_loss);
+        if projected_daily_loss > self.max_daily_loss {
             
             let violation = ProtocolViolation::new(
                 self.rule_name().to_string(),
@@ -428,35 +535,41 @@ impl RiskRule for DailyLossLimitRule {
                     projected_daily_loss,
                     current_daily_loss,
                     potential_trade_loss,
-                    mutable_self.max_daily_loss
+                    self.max_daily_loss
                 ),
                 projected_daily_loss,
-                mutable_self.max_daily_loss,
+                self.max_daily_loss,
                 if available_budget > Decimal::ZERO {
                     format!("Reduce position size to risk no more than ${:.2}", available_budget)
                 } else {
                     "Stop trading for today - daily loss limit reached".to_string()
                 },
-            );
+            ).with_hint(if available_budget > Decimal::ZERO {
+                SuggestedAction::ReducePositionSize {
+                    to: position_size.value() * available_budget / potential_trade_loss,
+                }
+            } else {
+                SuggestedAction::StopTradingToday
+            });
             assessment.add_violation(violation);
         }
         
-        // Step 6: Add daily context to reasoning
+        // Step 4: Add daily context to reasoning
         let reasoning = if assessment.is_approved() {
             format!(
                 "Daily loss approved: Current daily P&L ${:.2}, potential trade loss ${:.2}, projected daily loss ${:.2} within ${:.2} limit. {} trades taken today.",
-                mutable_self.daily_pnl,
+                state.daily_pnl,
                 potential_trade_loss,
                 projected_daily_loss,
-                mutable_self.max_daily_loss,
-                mutable_self.daily_trade_count
+                self.max_daily_loss,
+                state.daily_trade_count
             )
         } else {
             format!(
                 "Daily loss violation: Current daily loss ${:.2}, potential trade loss ${:.2} would exceed ${:.2} limit. Available budget: ${:.2}",
                 current_daily_loss,
                 potential_trade_loss,
-                mutable_self.max_daily_loss,
+                self.max_daily_loss,
                 available_budget
             )
         };
@@ -471,6 +584,10 @@ impl RiskRule for DailyLossLimitRule {
     fn description(&self) -> &str {
         "Validates that daily loss does not exceed configured daily loss limits"
     }
+    
+    fn record_outcome(&self, pnl: Decimal) {
+        self.record_trade_pnl(pnl);
+    }
 }
 
 impl Default for DailyLossLimitRule {
@@ -479,6 +596,578 @@ impl Default for DailyLossLimitRule {
     }
 }
 
+/// Absolute per-symbol position cap
+///
+/// Independent of risk percentage, some desks limit how many units of a
+/// symbol may be held at once (regulatory or risk-committee limits). This
+/// rule blocks any trade whose size, added to the quantity already open in
+/// that symbol, would exceed the cap. Symbols without a cap are unrestricted.
+/// Symbols are matched case-insensitively. Clones share the held quantities,
+/// so holdings recorded through one are seen by a clone added to a protocol.
+#[derive(Debug, Clone)]
+pub struct MaxPositionUnitsRule {
+    /// Van Tharp position sizing calculator
+    position_calculator: PositionSizingCalculator,
+    /// Maximum units that may be held, keyed by upper-cased symbol
+    caps: HashMap<String, Decimal>,
+    /// Units currently held, keyed by upper-cased symbol
+    open_quantity: Arc<Mutex<HashMap<String, Decimal>>>,
+}
+
+impl MaxPositionUnitsRule {
+    /// Create a rule with no caps configured
+    pub fn new() -> Self {
+        Self {
+            position_calculator: PositionSizingCalculator::new(),
+            caps: HashMap::new(),
+            open_quantity: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+    
+    /// Cap the units of `symbol` that may be held
+    pub fn with_cap(mut self, symbol: impl Into<String>, max_units: Decimal) -> Self {
+        self.caps.insert(symbol.into().to_ascii_uppercase(), max_units);
+        self
+    }
+    
+    /// Record the quantity currently held in a symbol
+    pub fn set_open_quantity(&self, symbol: impl Into<String>, quantity: Decimal) {
+        self.open_quantity.lock().unwrap().insert(symbol.into().to_ascii_uppercase(), quantity);
+    }
+    
+    /// Record `quantity` more units held in a symbol, as when a position opens
+    pub fn add_open_quantity(&self, symbol: &str, quantity: Decimal) {
+        *self.open_quantity.lock().unwrap().entry(symbol.to_ascii_uppercase()).or_default() += quantity;
+    }
+    
+    /// Record `quantity` fewer units held in a symbol, as when a position is exited
+    pub fn remove_open_quantity(&self, symbol: &str, quantity: Decimal) {
+        let mut open_quantity = self.open_quantity.lock().unwrap();
+        let symbol = symbol.to_ascii_uppercase();
+        if let Some(held) = open_quantity.get_mut(&symbol) {
+            *held -= quantity;
+            if *held <= Decimal::ZERO {
+                open_quantity.remove(&symbol);
+            }
+        }
+    }
+    
+    /// Units currently held in a symbol
+    pub fn open_quantity(&self, symbol: &str) -> Decimal {
+        self.open_quantity
+            .lock()
+            .unwrap()
+            .get(&symbol.to_ascii_uppercase())
+            .copied()
+            .unwrap_or(Decimal::ZERO)
+    }
+    
+    /// Configured cap for a symbol, if any
+    pub fn cap(&self, symbol: &str) -> Option<Decimal> {
+        self.caps.get(&symbol.to_ascii_uppercase()).copied()
+    }
+}
+
+impl RiskRule for MaxPositionUnitsRule {
+    fn assess(&self, proposal: &TradeProposal) -> Result<RiskAssessment, AssessmentError> {
+        let mut assessment = sized_assessment(&self.position_calculator, proposal)?;
+        let position_size = assessment.position_size;
+        
+        let Some(cap) = self.cap(&proposal.symbol) else {
+            return Ok(assessment.with_reasoning(format!(
+                "Position units approved: no unit cap configured for {}",
+                proposal.symbol
+            )));
+        };
+        
+        let held = self.open_quantity(&proposal.symbol);
+        let projected = held + position_size.value();
+        let remaining = cap - held;
+        
+        if projected > cap {
+            let mut violation = ProtocolViolation::new(
+                self.rule_name().to_string(),
+                ViolationSeverity::Blocking,
+                format!(
+                    "Holdings in {} would reach {} units (held {} + trade {}) exceeding cap of {}",
+                    proposal.symbol, projected, held, position_size.value(), cap
+                ),
+                projected,
+                cap,
+                if remaining > Decimal::ZERO {
+                    format!("Reduce position size to at most {} units", remaining)
+                } else {
+                    format!("Unit cap for {} reached - close existing holdings first", proposal.symbol)
+                },
+            );
+            if remaining > Decimal::ZERO {
+                violation = violation.with_hint(SuggestedAction::ReducePositionSize { to: remaining });
+            }
+            assessment.add_violation(violation);
+        }
+        
+        let reasoning = if assessment.is_approved() {
+            format!(
+                "Position units approved: {} units held + {} proposed = {} within {} cap for {}",
+                held, position_size.value(), projected, cap, proposal.symbol
+            )
+        } else {
+            format!(
+                "Position units violation: {} units held + {} proposed would exceed {} cap for {}",
+                held, position_size.value(), cap, proposal.symbol
+            )
+        };
+        
+        Ok(assessment.with_reasoning(reasoning))
+    }
+    
+    fn rule_name(&self) -> &str {
+        "MaxPositionUnits"
+    }
+    
+    fn description(&self) -> &str {
+        "Blocks trades that would push holdings in a symbol beyond its absolute unit cap"
+    }
+}
+
+impl Default for MaxPositionUnitsRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Minimum 24h volume filter
+///
+/// Thinly traded symbols gap through stops and fill with heavy slippage, so
+/// they are dangerous at any position size. This rule blocks trades on
+/// symbols whose last observed 24h volume is below the threshold, and on
+/// symbols with no observation yet, since their liquidity is unknown.
+///
+/// Observed volumes live behind a shared lock, so a clone kept by the market
+/// data feed updates the rule registered with the protocol.
+#[derive(Debug, Clone)]
+pub struct MinVolumeRule {
+    /// Van Tharp position sizing calculator
+    position_calculator: PositionSizingCalculator,
+    /// Minimum 24h volume, in the units the exchange reports
+    min_volume_24h: Decimal,
+    /// Last observed 24h volume, keyed by symbol
+    volumes: Arc<Mutex<HashMap<String, Decimal>>>,
+}
+
+impl MinVolumeRule {
+    /// Create a rule requiring at least `min_volume_24h` of 24h volume
+    pub fn new(min_volume_24h: Decimal) -> Self {
+        Self {
+            position_calculator: PositionSizingCalculator::new(),
+            min_volume_24h,
+            volumes: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+    
+    /// Record the 24h volume from a market observation
+    pub fn observe(&self, market_data: &MarketData) {
+        self.volumes
+            .lock()
+            .unwrap()
+            .insert(market_data.symbol.clone(), market_data.volume_24h);
+    }
+    
+    /// Last observed 24h volume for a symbol
+    pub fn volume(&self, symbol: &str) -> Option<Decimal> {
+        self.volumes.lock().unwrap().get(symbol).copied()
+    }
+    
+    /// Configured minimum 24h volume
+    pub fn min_volume_24h(&self) -> Decimal {
+        self.min_volume_24h
+    }
+}
+
+impl RiskRule for MinVolumeRule {
+    fn assess(&self, proposal: &TradeProposal) -> Result<RiskAssessment, AssessmentError> {
+        let mut assessment = sized_assessment(&self.position_calculator, proposal)?;
+        
+        let observed = self.volume(&proposal.symbol);
+        let volume = observed.unwrap_or(Decimal::ZERO);
+        if volume < self.min_volume_24h {
+            let description = match observed {
+                Some(volume) => format!(
+                    "24h volume in {} is {}, below the required minimum of {}",
+                    proposal.symbol, volume, self.min_volume_24h
+                ),
+                None => format!(
+                    "No 24h volume observed for {}; at least {} is required",
+                    proposal.symbol, self.min_volume_24h
+                ),
+            };
+            assessment.add_violation(ProtocolViolation::new(
+                self.rule_name().to_string(),
+                ViolationSeverity::Blocking,
+                description,
+                volume,
+                self.min_volume_24h,
+                format!("Trade a more liquid symbol than {}", proposal.symbol),
+            ));
+        }
+        
+        let reasoning = if assessment.is_approved() {
+            format!(
+                "Volume approved: {} traded {} in 24h, above the {} minimum",
+                proposal.symbol, volume, self.min_volume_24h
+            )
+        } else {
+            format!(
+                "Volume violation: {} traded {} in 24h, below the {} minimum",
+                proposal.symbol, volume, self.min_volume_24h
+            )
+        };
+        
+        Ok(assessment.with_reasoning(reasoning))
+    }
+    
+    fn rule_name(&self) -> &str {
+        "MinVolume"
+    }
+    
+    fn description(&self) -> &str {
+        "Blocks trades on symbols whose 24h volume is below the liquidity threshold"
+    }
+}
+
+/// Symbols that move together, with a cap on positions held across them
+#[derive(Debug, Clone)]
+struct CorrelatedGroup {
+    members: HashSet<String>,
+    max_open_positions: u32,
+}
+
+/// Open-position cap across groups of correlated symbols
+///
+/// Positions in symbols that move together (e.g. the majors) act as one
+/// larger bet, so per-symbol caps understate the exposure. This rule blocks a
+/// trade when any group containing its symbol already holds the group's
+/// maximum number of open positions. Symbols outside every group are
+/// unrestricted.
+///
+/// Only per-symbol counts are kept, and clones share them, so opening and
+/// closing through a kept handle moves the counts the registered rule uses.
+#[derive(Debug, Clone)]
+pub struct CorrelatedGroupRule {
+    /// Van Tharp position sizing calculator
+    position_calculator: PositionSizingCalculator,
+    /// Configured groups, keyed by group name
+    groups: HashMap<String, CorrelatedGroup>,
+    /// Open positions, counted per symbol
+    open_positions: Arc<Mutex<HashMap<String, u32>>>,
+}
+
+impl CorrelatedGroupRule {
+    /// Create a rule with no groups configured
+    pub fn new() -> Self {
+        Self {
+            position_calculator: PositionSizingCalculator::new(),
+            groups: HashMap::new(),
+            open_positions: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+    
+    /// Define a group of correlated symbols holding at most `max_open_positions` at once
+    pub fn with_group<I, S>(mut self, name: impl Into<String>, members: I, max_open_positions: u32) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.groups.insert(name.into(), CorrelatedGroup {
+            members: members.into_iter().map(Into::into).collect(),
+            max_open_positions,
+        });
+        self
+    }
+    
+    /// Record a position opened in `symbol`
+    pub fn position_opened(&self, symbol: &str) {
+        *self.open_positions.lock().unwrap().entry(symbol.to_string()).or_default() += 1;
+    }
+    
+    /// Record a position closed in `symbol`
+    pub fn position_closed(&self, symbol: &str) {
+        let mut open_positions = self.open_positions.lock().unwrap();
+        if let Some(count) = open_positions.get_mut(symbol) {
+            *count = count.saturating_sub(1);
+            if *count == 0 {
+                open_positions.remove(symbol);
+            }
+        }
+    }
+    
+    /// Open positions across the members of a group; `None` for an unknown group
+    pub fn open_positions_in_group(&self, group: &str) -> Option<u32> {
+        let group = self.groups.get(group)?;
+        let open_positions = self.open_positions.lock().unwrap();
+        Some(group.members.iter().filter_map(|symbol| open_positions.get(symbol)).sum())
+    }
+    
+    /// Names of the groups `symbol` belongs to, sorted
+    pub fn groups_for(&self, symbol: &str) -> Vec<&str> {
+        let mut groups: Vec<&str> = self.groups
+            .iter()
+            .filter(|(_, group)| group.members.contains(symbol))
+            .map(|(name, _)| name.as_str())
+            .collect();
+        groups.sort_unstable();
+        groups
+    }
+}
+
+impl RiskRule for CorrelatedGroupRule {
+    fn assess(&self, proposal: &TradeProposal) -> Result<RiskAssessment, AssessmentError> {
+        let mut assessment = sized_assessment(&self.position_calculator, proposal)?;
+        
+        let groups = self.groups_for(&proposal.symbol);
+        if groups.is_empty() {
+            return Ok(assessment.with_reasoning(format!(
+                "Correlated groups approved: {} is not in a correlated group",
+                proposal.symbol
+            )));
+        }
+        
+        for name in &groups {
+            let cap = self.groups[*name].max_open_positions;
+            let open = self.open_positions_in_group(name).unwrap_or(0);
+            if open >= cap {
+                let excess = open + 1 - cap;
+                assessment.add_violation(
+                    ProtocolViolation::new(
+                        self.rule_name().to_string(),
+                        ViolationSeverity::Blocking,
+                        format!(
+                            "Correlated group {} already holds {} open position(s), the cap is {}",
+                            name, open, cap
+                        ),
+                        Decimal::from(open + 1),
+                        Decimal::from(cap),
+                        format!("Close {} position(s) in group {} before trading {}", excess, name, proposal.symbol),
+                    )
+                    .with_hint(SuggestedAction::ClosePositions { count: excess }),
+                );
+            }
+        }
+        
+        let reasoning = if assessment.is_approved() {
+            format!(
+                "Correlated groups approved: {} is within the cap of group(s) {}",
+                proposal.symbol,
+                groups.join(", ")
+            )
+        } else {
+            format!(
+                "Correlated group violation: {} would exceed the open position cap of its group",
+                proposal.symbol
+            )
+        };
+        
+        Ok(assessment.with_reasoning(reasoning))
+    }
+    
+    fn rule_name(&self) -> &str {
+        "CorrelatedGroup"
+    }
+    
+    fn description(&self) -> &str {
+        "Blocks trades that would exceed the open position cap of a correlated symbol group"
+    }
+}
+
+impl Default for CorrelatedGroupRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Per-symbol cap on open risk
+///
+/// `MaxPortfolioRiskRule` bounds the total, but the whole budget could still
+/// sit in one asset. This rule sums the risk of the open positions in the
+/// proposal's symbol and rejects a trade that would push that symbol's
+/// aggregate past the cap, so no single asset dominates the portfolio.
+/// Symbols are matched case-insensitively.
+///
+/// Build it with `for_portfolio` to read the positions a
+/// `MaxPortfolioRiskRule` already tracks instead of recording every fill twice.
+#[derive(Debug, Clone)]
+pub struct MaxSymbolExposureRule {
+    /// Van Tharp position sizing calculator
+    position_calculator: PositionSizingCalculator,
+    /// Maximum open risk in any one symbol, as a fraction of account equity
+    max_symbol_risk: Decimal,
+    /// Open positions, possibly shared with a `MaxPortfolioRiskRule`
+    state: Arc<Mutex<PortfolioState>>,
+}
+
+impl MaxSymbolExposureRule {
+    /// Create a rule capping each symbol at 4% portfolio risk
+    pub fn new() -> Self {
+        Self::tracking(Arc::new(Mutex::new(PortfolioState::new())))
+    }
+    
+    /// Create a rule reading the open positions `portfolio` tracks
+    ///
+    /// Positions added to or removed from either rule are seen by both.
+    pub fn for_portfolio(portfolio: &MaxPortfolioRiskRule) -> Self {
+        Self::tracking(Arc::clone(&portfolio.state))
+    }
+    
+    fn tracking(state: Arc<Mutex<PortfolioState>>) -> Self {
+        Self {
+            position_calculator: PositionSizingCalculator::new(),
+            max_symbol_risk: dec!(0.04),
+            state,
+        }
+    }
+    
+    /// Cap each symbol's open risk at `max_symbol_risk` of account equity
+    pub fn with_symbol_cap(mut self, max_symbol_risk: Decimal) -> Self {
+        self.max_symbol_risk = max_symbol_risk;
+        self
+    }
+    
+    /// Add an open position to the exposure tracking
+    pub fn add_open_position(&self, position: OpenPosition) {
+        self.state.lock().unwrap().add_open_position(position);
+    }
+    
+    /// Remove an open position (when closed)
+    pub fn remove_open_position(&self, position_id: &str) -> Option<OpenPosition> {
+        self.state.lock().unwrap().remove_open_position(position_id)
+    }
+    
+    /// Open risk in `symbol`, as a fraction of account equity
+    pub fn symbol_exposure(&self, symbol: &str) -> Decimal {
+        Self::exposure_in(&self.state.lock().unwrap().open_positions, symbol)
+    }
+    
+    /// Configured per-symbol cap
+    pub fn symbol_cap(&self) -> Decimal {
+        self.max_symbol_risk
+    }
+    
+    fn exposure_in(open_positions: &HashMap<String, OpenPosition>, symbol: &str) -> Decimal {
+        open_positions
+            .values()
+            .filter(|position| position.symbol.eq_ignore_ascii_case(symbol))
+            .map(|position| position.risk_percentage)
+            .sum()
+    }
+    
+    /// Fewest positions in `symbol`, largest risk first, whose closure frees `excess_risk`
+    fn positions_to_close(open_positions: &HashMap<String, OpenPosition>, symbol: &str, excess_risk: Decimal) -> u32 {
+        let mut risks: Vec<Decimal> = open_positions
+            .values()
+            .filter(|position| position.symbol.eq_ignore_ascii_case(symbol))
+            .map(|position| position.risk_percentage)
+            .collect();
+        risks.sort_by(|a, b| b.cmp(a));
+        
+        let mut freed = Decimal::ZERO;
+        let mut count = 0;
+        for risk in risks {
+            if freed >= excess_risk {
+                break;
+            }
+            freed += risk;
+            count += 1;
+        }
+        count
+    }
+}
+
+impl RiskRule for MaxSymbolExposureRule {
+    fn assess(&self, proposal: &TradeProposal) -> Result<RiskAssessment, AssessmentError> {
+        let mut assessment = sized_assessment(&self.position_calculator, proposal)?;
+        let position_size = assessment.position_size;
+        let trade_risk_percentage = assessment.risk_percentage;
+        
+        let state = self.state.lock().unwrap();
+        let open_positions = &state.open_positions;
+        let current_exposure = Self::exposure_in(open_positions, &proposal.symbol);
+        let projected_exposure = current_exposure + trade_risk_percentage;
+        let available_budget = self.max_symbol_risk - current_exposure;
+        
+        if projected_exposure > self.max_symbol_risk {
+            let hint = if available_budget > Decimal::ZERO {
+                SuggestedAction::ReducePositionSize {
+                    to: position_size.value() * available_budget / trade_risk_percentage,
+                }
+            } else {
+                SuggestedAction::ClosePositions {
+                    count: Self::positions_to_close(
+                        open_positions,
+                        &proposal.symbol,
+                        projected_exposure - self.max_symbol_risk,
+                    ),
+                }
+            };
+            let violation = ProtocolViolation::new(
+                self.rule_name().to_string(),
+                ViolationSeverity::Critical,
+                format!(
+                    "{} exposure would reach {:.1}% (current {:.1}% + trade {:.1}%) exceeding per-symbol maximum {:.1}%",
+                    proposal.symbol,
+                    projected_exposure * dec!(100),
+                    current_exposure * dec!(100),
+                    trade_risk_percentage * dec!(100),
+                    self.max_symbol_risk * dec!(100)
+                ),
+                projected_exposure,
+                self.max_symbol_risk,
+                format!(
+                    "Reduce position size or close existing {} positions. Available {} budget: {:.1}%",
+                    proposal.symbol,
+                    proposal.symbol,
+                    available_budget.max(Decimal::ZERO) * dec!(100)
+                ),
+            ).with_hint(hint);
+            assessment.add_violation(violation);
+        }
+        
+        let reasoning = if assessment.is_approved() {
+            format!(
+                "Symbol exposure approved: {} at {:.1}% + {:.1}% trade = {:.1}% within {:.1}% per-symbol cap",
+                proposal.symbol,
+                current_exposure * dec!(100),
+                trade_risk_percentage * dec!(100),
+                projected_exposure * dec!(100),
+                self.max_symbol_risk * dec!(100)
+            )
+        } else {
+            format!(
+                "Symbol exposure violation: {} at {:.1}% + {:.1}% trade would exceed {:.1}% per-symbol cap",
+                proposal.symbol,
+                current_exposure * dec!(100),
+                trade_risk_percentage * dec!(100),
+                self.max_symbol_risk * dec!(100)
+            )
+        };
+        
+        Ok(assessment.with_reasoning(reasoning))
+    }
+    
+    fn rule_name(&self) -> &str {
+        "MaxSymbolExposure"
+    }
+    
+    fn description(&self) -> &str {
+        "Validates that open risk in any one symbol does not exceed the per-symbol cap"
+    }
+}
+
+impl Default for MaxSymbolExposureRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -544,7 +1233,7 @@ mod tests {
 
     #[test]
     fn test_portfolio_position_management() {
-        let mut rule = MaxPortfolioRiskRule::new();
+        let rule = MaxPortfolioRiskRule::new();
         
         // Add some open positions
         let position1 = OpenPosition {
@@ -554,6 +1243,7 @@ mod tests {
             risk_percentage: dec!(0.02), // 2%
             opened_at: SystemTime::now(),
             unrealized_pnl: dec!(50),
+            max_adverse_excursion: dec!(0),
         };
         
         let position2 = OpenPosition {
@@ -563,6 +1253,7 @@ mod tests {
             risk_percentage: dec!(0.015), // 1.5%
             opened_at: SystemTime::now(),
             unrealized_pnl: dec!(-25),
+            max_adverse_excursion: dec!(0),
         };
         
         rule.add_open_position(position1);
@@ -582,7 +1273,7 @@ mod tests {
 
     #[test]
     fn test_portfolio_risk_limit_enforcement() {
-        let mut rule = MaxPortfolioRiskRule::new(); // 10% max portfolio risk
+        let rule = MaxPortfolioRiskRule::new(); // 10% max portfolio risk
         
         // Add positions totaling 7% risk
         let existing_position = OpenPosition {
@@ -592,6 +1283,7 @@ mod tests {
             risk_percentage: dec!(0.07), // 7%
             opened_at: SystemTime::now(),
             unrealized_pnl: dec!(0),
+            max_adverse_excursion: dec!(0),
         };
         rule.add_open_position(existing_position);
         
@@ -603,27 +1295,255 @@ mod tests {
         
         let assessment = result.unwrap();
         assert!(!assessment.is_approved());
-        assert!(!assessment.violations.is_empty());
-        
-        // Verify the violation details
+        assert!(!assessment.violations.is_empty());
+        
+        // Verify the violation details
+        let violation = &assessment.violations[0];
+        assert_eq!(violation.rule_name, "MaxPortfolioRisk");
+        assert_eq!(violation.severity, ViolationSeverity::Critical);
+        assert!(violation.description.contains("12.0%"));
+        assert!(violation.description.contains("maximum 10.0%"));
+        assert_eq!(violation.current_value, dec!(0.12)); // 12%
+        assert_eq!(violation.limit_value, dec!(0.10)); // 10% limit
+        
+        // Verify rejection reasoning
+        let reasoning = assessment.reasoning.unwrap();
+        assert!(reasoning.contains("violation"));
+        assert!(reasoning.contains("Available budget: 3.0%")); // 10% - 7% = 3%
+    }
+
+    #[test]
+    fn test_portfolio_risk_violation_hints_target_size() {
+        let rule = MaxPortfolioRiskRule::new(); // 10% max portfolio risk
+        rule.add_open_position(OpenPosition {
+            id: "existing".to_string(),
+            symbol: "BTCUSDT".to_string(),
+            risk_amount: dec!(700),
+            risk_percentage: dec!(0.07),
+            opened_at: SystemTime::now(),
+            unrealized_pnl: dec!(0),
+            max_adverse_excursion: dec!(0),
+        });
+
+        // 5% risk over a $5 stop sizes at 100 units; only 3% of budget remains
+        let assessment = rule.assess(&create_test_proposal(dec!(0.05))).unwrap();
+        let violation = &assessment.violations[0];
+        assert_eq!(violation.hint, Some(SuggestedAction::ReducePositionSize { to: dec!(60) }));
+
+        let json = serde_json::to_value(violation).unwrap();
+        assert_eq!(json["hint"]["type"], "reduce_position_size");
+
+        // With the budget used up, positions must be closed instead
+        rule.add_open_position(OpenPosition {
+            id: "second".to_string(),
+            symbol: "ETHUSDT".to_string(),
+            risk_amount: dec!(300),
+            risk_percentage: dec!(0.03),
+            opened_at: SystemTime::now(),
+            unrealized_pnl: dec!(0),
+            max_adverse_excursion: dec!(0),
+        });
+        let assessment = rule.assess(&create_test_proposal(dec!(0.02))).unwrap();
+        assert_eq!(
+            assessment.violations[0].hint,
+            Some(SuggestedAction::ClosePositions { count: 1 })
+        );
+    }
+
+    #[test]
+    fn test_position_units_cap_counts_open_quantity() {
+        let rule = MaxPositionUnitsRule::new().with_cap("BTCUSDT", dec!(1));
+        // Holdings recorded through a clone reach the rule
+        let feed = rule.clone();
+        feed.set_open_quantity("BTCUSDT", dec!(0.8));
+
+        // Over a $1000 stop, 5% of 10000 sizes at 0.5 BTC and 1% at 0.1 BTC
+        let proposal = |risk_pct| TradeProposal::new(
+            "BTCUSDT".to_string(),
+            TradeSide::Long,
+            PricePoint::new(dec!(10000)).unwrap(),
+            PricePoint::new(dec!(9000)).unwrap(),
+            None,
+            AccountEquity::new(dec!(10000)).unwrap(),
+            RiskPercentage::new(risk_pct).unwrap(),
+        ).unwrap();
+
+        let assessment = rule.assess(&proposal(dec!(0.05))).unwrap();
+        assert_eq!(assessment.approval_status, ApprovalStatus::Blocked);
+        let violation = &assessment.violations[0];
+        assert_eq!(violation.rule_name, "MaxPositionUnits");
+        assert_eq!(violation.current_value, dec!(1.3));
+        assert_eq!(violation.hint, Some(SuggestedAction::ReducePositionSize { to: dec!(0.2) }));
+
+        let assessment = rule.assess(&proposal(dec!(0.01))).unwrap();
+        assert!(assessment.is_approved());
+        assert!(assessment.violations.is_empty());
+
+        // Symbols match regardless of case
+        let mut lower = proposal(dec!(0.05));
+        lower.symbol = "btcusdt".to_string();
+        assert_eq!(rule.assess(&lower).unwrap().approval_status, ApprovalStatus::Blocked);
+        rule.set_open_quantity("btcusdt", dec!(0.9));
+        assert_eq!(rule.open_quantity("BTCUSDT"), dec!(0.9));
+
+        // Opened and exited positions move the holdings
+        feed.add_open_quantity("BTCUSDT", dec!(0.05));
+        assert_eq!(rule.open_quantity("BTCUSDT"), dec!(0.95));
+        feed.remove_open_quantity("BTCUSDT", dec!(0.95));
+        assert_eq!(rule.open_quantity("BTCUSDT"), Decimal::ZERO);
+
+        // Other symbols are uncapped
+        rule.set_open_quantity("ETHUSDT", dec!(100));
+        let mut eth = proposal(dec!(0.05));
+        eth.symbol = "ETHUSDT".to_string();
+        assert!(rule.assess(&eth).unwrap().is_approved());
+    }
+
+    #[test]
+    fn test_min_volume_blocks_illiquid_symbols() {
+        let rule = MinVolumeRule::new(dec!(1000000));
+        let feed = rule.clone();
+        let observation = |symbol: &str, volume_24h| MarketData {
+            symbol: symbol.to_string(),
+            bid_price: dec!(9999),
+            ask_price: dec!(10001),
+            last_price: dec!(10000),
+            volume_24h,
+            timestamp: SystemTime::now(),
+        };
+        feed.observe(&observation("BTCUSDT", dec!(25000000)));
+        feed.observe(&observation("THINUSDT", dec!(40000)));
+
+        let proposal = |symbol: &str| TradeProposal::new(
+            symbol.to_string(),
+            TradeSide::Long,
+            PricePoint::new(dec!(10000)).unwrap(),
+            PricePoint::new(dec!(9000)).unwrap(),
+            None,
+            AccountEquity::new(dec!(10000)).unwrap(),
+            RiskPercentage::new(dec!(0.01)).unwrap(),
+        ).unwrap();
+
+        assert!(rule.assess(&proposal("BTCUSDT")).unwrap().is_approved());
+
+        let assessment = rule.assess(&proposal("THINUSDT")).unwrap();
+        assert_eq!(assessment.approval_status, ApprovalStatus::Blocked);
+        let violation = &assessment.violations[0];
+        assert_eq!(violation.rule_name, "MinVolume");
+        assert_eq!(violation.current_value, dec!(40000));
+        assert_eq!(violation.limit_value, dec!(1000000));
+        assert!(violation.description.contains("40000"));
+
+        // Unknown liquidity is treated as illiquid
+        let assessment = rule.assess(&proposal("NEWUSDT")).unwrap();
+        assert_eq!(assessment.approval_status, ApprovalStatus::Blocked);
+    }
+
+    #[test]
+    fn test_correlated_group_caps_open_positions_across_members() {
+        let rule = CorrelatedGroupRule::new()
+            .with_group("majors", ["BTCUSDT", "ETHUSDT", "SOLUSDT", "BNBUSDT"], 3);
+        let fills = rule.clone();
+        let proposal = |symbol: &str| TradeProposal::new(
+            symbol.to_string(),
+            TradeSide::Long,
+            PricePoint::new(dec!(100)).unwrap(),
+            PricePoint::new(dec!(95)).unwrap(),
+            None,
+            AccountEquity::new(dec!(10000)).unwrap(),
+            RiskPercentage::new(dec!(0.01)).unwrap(),
+        ).unwrap();
+
+        for symbol in ["BTCUSDT", "ETHUSDT", "SOLUSDT"] {
+            assert!(rule.assess(&proposal(symbol)).unwrap().is_approved());
+            fills.position_opened(symbol);
+        }
+        assert_eq!(rule.open_positions_in_group("majors"), Some(3));
+
+        // A fourth major is one bet too many
+        let assessment = rule.assess(&proposal("BNBUSDT")).unwrap();
+        assert_eq!(assessment.approval_status, ApprovalStatus::Blocked);
+        let violation = &assessment.violations[0];
+        assert_eq!(violation.rule_name, "CorrelatedGroup");
+        assert_eq!(violation.current_value, dec!(4));
+        assert_eq!(violation.limit_value, dec!(3));
+        assert_eq!(violation.hint, Some(SuggestedAction::ClosePositions { count: 1 }));
+
+        // An uncorrelated symbol is unaffected
+        assert!(rule.assess(&proposal("XAUUSDT")).unwrap().is_approved());
+
+        // Closing a major frees a slot in the group
+        fills.position_closed("ETHUSDT");
+        assert!(rule.assess(&proposal("BNBUSDT")).unwrap().is_approved());
+    }
+
+    #[test]
+    fn test_symbol_exposure_caps_aggregate_risk_per_symbol() {
+        let rule = MaxSymbolExposureRule::new().with_symbol_cap(dec!(0.04));
+        let fills = rule.clone();
+        let position = |id: &str, symbol: &str, risk_percentage| OpenPosition {
+            id: id.to_string(),
+            symbol: symbol.to_string(),
+            risk_amount: risk_percentage * dec!(10000),
+            risk_percentage,
+            opened_at: SystemTime::now(),
+            unrealized_pnl: dec!(0),
+            max_adverse_excursion: dec!(0),
+        };
+        fills.add_open_position(position("btc-1", "BTCUSDT", dec!(0.015)));
+        // A fill recorded in lower case still counts towards BTCUSDT
+        fills.add_open_position(position("btc-2", "btcusdt", dec!(0.01)));
+        fills.add_open_position(position("eth-1", "ETHUSDT", dec!(0.03)));
+        assert_eq!(rule.symbol_exposure("BTCUSDT"), dec!(0.025));
+
+        // 2.5% + 1% stays under the 4% cap
+        assert!(rule.assess(&create_test_proposal(dec!(0.01))).unwrap().is_approved());
+
+        // 2.5% + 2% would put 4.5% in BTC alone, though the portfolio is at 5.5%
+        let assessment = rule.assess(&create_test_proposal(dec!(0.02))).unwrap();
+        assert!(!assessment.is_approved());
         let violation = &assessment.violations[0];
-        assert_eq!(violation.rule_name, "MaxPortfolioRisk");
-        assert_eq!(violation.severity, ViolationSeverity::Critical);
-        assert!(violation.description.contains("12.0%"));
-        assert!(violation.description.contains("maximum 10.0%"));
-        assert_eq!(violation.current_value, dec!(0.12)); // 12%
-        assert_eq!(violation.limit_value, dec!(0.10)); // 10% limit
+        assert_eq!(violation.rule_name, "MaxSymbolExposure");
+        assert_eq!(violation.current_value, dec!(0.045));
+        assert_eq!(violation.limit_value, dec!(0.04));
+        assert!(violation.description.contains("current 2.5%"));
+        assert!(violation.description.contains("reach 4.5%"));
+        // The 1.5% of budget left allows three quarters of the 40-unit size
+        assert_eq!(violation.hint, Some(SuggestedAction::ReducePositionSize { to: dec!(30) }));
+
+        // Closing a BTC position frees room in that symbol only
+        fills.remove_open_position("btc-1");
+        assert!(rule.assess(&create_test_proposal(dec!(0.02))).unwrap().is_approved());
+    }
+
+    #[test]
+    fn test_symbol_exposure_reads_the_portfolio_positions() {
+        let portfolio = MaxPortfolioRiskRule::new();
+        let rule = MaxSymbolExposureRule::for_portfolio(&portfolio).with_symbol_cap(dec!(0.04));
+        portfolio.add_open_position(OpenPosition {
+            id: "btc-1".to_string(),
+            symbol: "BTCUSDT".to_string(),
+            risk_amount: dec!(300),
+            risk_percentage: dec!(0.03),
+            opened_at: SystemTime::now(),
+            unrealized_pnl: dec!(0),
+            max_adverse_excursion: dec!(0),
+        });
         
-        // Verify rejection reasoning
-        let reasoning = assessment.reasoning.unwrap();
-        assert!(reasoning.contains("violation"));
-        assert!(reasoning.contains("Available budget: 3.0%")); // 10% - 7% = 3%
+        // A fill recorded on the portfolio rule counts towards the symbol cap
+        assert_eq!(rule.symbol_exposure("BTCUSDT"), dec!(0.03));
+        assert!(!rule.assess(&create_test_proposal(dec!(0.02))).unwrap().is_approved());
+        
+        // Closing through the exposure rule clears it from the portfolio too
+        rule.remove_open_position("btc-1");
+        assert_eq!(portfolio.position_count(), 0);
+        assert!(rule.assess(&create_test_proposal(dec!(0.02))).unwrap().is_approved());
     }
 
     #[test]
     fn test_conservative_portfolio_stricter_limits() {
-        let mut conservative_rule = MaxPortfolioRiskRule::conservative(); // 5% max portfolio
-        let mut standard_rule = MaxPortfolioRiskRule::new(); // 10% max portfolio
+        let conservative_rule = MaxPortfolioRiskRule::conservative(); // 5% max portfolio
+        let standard_rule = MaxPortfolioRiskRule::new(); // 10% max portfolio
         
         // Add existing positions
         let existing_position = OpenPosition {
@@ -633,6 +1553,7 @@ mod tests {
             risk_percentage: dec!(0.03), // 3%
             opened_at: SystemTime::now(),
             unrealized_pnl: dec!(0),
+            max_adverse_excursion: dec!(0),
         };
         
         conservative_rule.add_open_position(existing_position.clone());
@@ -652,7 +1573,7 @@ mod tests {
 
     #[test]
     fn test_portfolio_position_updates() {
-        let mut rule = MaxPortfolioRiskRule::new();
+        let rule = MaxPortfolioRiskRule::new();
         
         let position = OpenPosition {
             id: "test".to_string(),
@@ -661,6 +1582,7 @@ mod tests {
             risk_percentage: dec!(0.02),
             opened_at: SystemTime::now(),
             unrealized_pnl: dec!(0),
+            max_adverse_excursion: dec!(0),
         };
         
         rule.add_open_position(position);
@@ -669,13 +1591,47 @@ mod tests {
         rule.update_position("test", dec!(100));
         
         // Verify P&L was updated
-        let updated_position = &rule.open_positions["test"];
-        assert_eq!(updated_position.unrealized_pnl, dec!(100));
+        let state = rule.state.lock().unwrap();
+        assert_eq!(state.open_positions["test"].unrealized_pnl, dec!(100));
+    }
+
+    #[test]
+    fn test_price_dip_records_mae_and_warns_near_stop() {
+        let rule = MaxPortfolioRiskRule::new().with_mae_warning_threshold(dec!(0.75));
+
+        // Long 0.1 BTC at 50,000 with a 48,000 stop risks $200
+        rule.add_open_position(OpenPosition {
+            id: "dip".to_string(),
+            symbol: "BTCUSDT".to_string(),
+            risk_amount: dec!(200),
+            risk_percentage: dec!(0.02),
+            opened_at: SystemTime::now(),
+            unrealized_pnl: dec!(0),
+            max_adverse_excursion: dec!(0),
+        });
+
+        // 49,500 then a bounce to 50,300: MAE stays at the $50 low
+        assert!(rule.update_position("dip", dec!(-50)).is_none());
+        assert!(rule.update_position("dip", dec!(30)).is_none());
+        assert_eq!(rule.state.lock().unwrap().open_positions["dip"].max_adverse_excursion, dec!(50));
+
+        // A dip to 48,400 is 80% of the way to the stop
+        let alert = rule.update_position("dip", dec!(-160)).unwrap();
+        assert_eq!(alert.max_adverse_excursion, dec!(160));
+        assert_eq!(alert.utilization, dec!(0.8));
+        assert!(alert.message().contains("80.0%"));
+
+        // The warning fires once; MAE keeps deepening
+        assert!(rule.update_position("dip", dec!(-180)).is_none());
+        assert!(rule.update_position("dip", dec!(-20)).is_none());
+        let position = rule.remove_open_position("dip").unwrap();
+        assert_eq!(position.max_adverse_excursion, dec!(180));
+        assert_eq!(position.unrealized_pnl, dec!(-20));
     }
 
     #[test]
     fn test_portfolio_risk_caching() {
-        let mut rule = MaxPortfolioRiskRule::new();
+        let rule = MaxPortfolioRiskRule::new();
         
         let position = OpenPosition {
             id: "test".to_string(),
@@ -684,16 +1640,17 @@ mod tests {
             risk_percentage: dec!(0.02),
             opened_at: SystemTime::now(),
             unrealized_pnl: dec!(0),
+            max_adverse_excursion: dec!(0),
         };
         rule.add_open_position(position);
         
         // First call should calculate and cache
         let risk1 = rule.current_portfolio_risk();
-        let time1 = rule.last_calculation;
+        let time1 = rule.state.lock().unwrap().last_calculation;
         
         // Second call within 1 second should use cache
         let risk2 = rule.current_portfolio_risk();
-        let time2 = rule.last_calculation;
+        let time2 = rule.state.lock().unwrap().last_calculation;
         
         assert_eq!(risk1, risk2);
         assert_eq!(time1, time2); // Time shouldn't change if cached
@@ -741,7 +1698,7 @@ mod tests {
 
     #[test]
     fn test_daily_loss_pnl_tracking() {
-        let mut rule = DailyLossLimitRule::new();
+        let rule = DailyLossLimitRule::new();
         
         // Record some trades
         rule.record_trade_pnl(dec!(50)); // Win $50
@@ -757,7 +1714,7 @@ mod tests {
 
     #[test]
     fn test_daily_loss_limit_enforcement() {
-        let mut rule = DailyLossLimitRule::new(); // $1000 limit
+        let rule = DailyLossLimitRule::new(); // $1000 limit
         
         // Record losses bringing us close to limit
         rule.record_trade_pnl(dec!(-850)); // $850 loss
@@ -785,7 +1742,7 @@ mod tests {
 
     #[test]
     fn test_daily_loss_budget_exhausted() {
-        let mut rule = DailyLossLimitRule::conservative(); // $500 limit
+        let rule = DailyLossLimitRule::conservative(); // $500 limit
         
         // Record losses that exhaust the budget
         rule.record_trade_pnl(dec!(-500)); // Exactly at limit
@@ -808,7 +1765,7 @@ mod tests {
 
     #[test]
     fn test_daily_loss_with_profits() {
-        let mut rule = DailyLossLimitRule::new(); // $1000 limit
+        let rule = DailyLossLimitRule::new(); // $1000 limit
         
         // Record mixed results with net profit
         rule.record_trade_pnl(dec!(-200)); // Loss
@@ -828,7 +1785,7 @@ mod tests {
 
     #[test]
     fn test_daily_reset_functionality() {
-        let mut rule = DailyLossLimitRule::new();
+        let rule = DailyLossLimitRule::new();
         
         // Record some activity
         rule.record_trade_pnl(dec!(-500));
@@ -858,7 +1815,7 @@ mod tests {
 
     #[test]
     fn test_daily_loss_reasoning_messages() {
-        let mut rule = DailyLossLimitRule::new();
+        let rule = DailyLossLimitRule::new();
         rule.record_trade_pnl(dec!(-200)); // $200 current loss
         
         let proposal = create_test_proposal(dec!(0.01)); // Small trade
@@ -888,14 +1845,25 @@ mod tests {
 /// # Roman Military Principle
 /// Like Roman generals who would halt an attack after consecutive defeats to regroup,
 /// this rule enforces strategic withdrawal to prevent total destruction of capital.
+///
+/// # Shared State
+/// The loss streak lives behind a shared lock, so clones share it: outcomes
+/// recorded through any handle, including `RiskManagementProtocol::record_trade_outcome`,
+/// trip the breaker for the next assessment.
 #[derive(Debug, Clone)]
 pub struct ConsecutiveLossLimitRule {
     /// Protocol limits for validation
     limits: ProtocolLimits,
     
     /// Position size calculator for risk assessment
-    position_calculator: Arc<PositionSizingCalculator>,
+    position_calculator: PositionSizingCalculator,
     
+    /// Loss streak and circuit breaker status
+    state: Arc<Mutex<ConsecutiveLossState>>,
+}
+
+#[derive(Debug)]
+struct ConsecutiveLossState {
     /// Current count of consecutive losing trades
     consecutive_losses: u32,
     
@@ -915,6 +1883,15 @@ pub struct ConsecutiveLossLimitRule {
     total_trades_tracked: u32,
 }
 
+impl ConsecutiveLossState {
+    fn reset_consecutive_losses(&mut self) {
+        self.consecutive_losses = 0;
+        self.consecutive_loss_amount = Decimal::ZERO;
+        self.circuit_breaker_active = false;
+        self.halt_reason = None;
+    }
+}
+
 impl ConsecutiveLossLimitRule {
     /// Create new consecutive loss limit rule with default limits
     pub fn new() -> Self {
@@ -935,13 +1912,15 @@ impl ConsecutiveLossLimitRule {
     pub fn with_limits(limits: ProtocolLimits) -> Self {
         Self {
             limits,
-            position_calculator: Arc::new(PositionSizingCalculator::new()),
-            consecutive_losses: 0,
-            consecutive_loss_amount: Decimal::ZERO,
-            last_loss_timestamp: SystemTime::UNIX_EPOCH,
-            circuit_breaker_active: false,
-            halt_reason: None,
-            total_trades_tracked: 0,
+            position_calculator: PositionSizingCalculator::new(),
+            state: Arc::new(Mutex::new(ConsecutiveLossState {
+                consecutive_losses: 0,
+                consecutive_loss_amount: Decimal::ZERO,
+                last_loss_timestamp: SystemTime::UNIX_EPOCH,
+                circuit_breaker_active: false,
+                halt_reason: None,
+                total_trades_tracked: 0,
+            })),
         }
     }
     
@@ -952,82 +1931,84 @@ impl ConsecutiveLossLimitRule {
     ///
     /// # Returns
     /// * True if circuit breaker was triggered by this trade
-    pub fn record_trade_outcome(&mut self, pnl: Decimal) -> bool {
-        self.total_trades_tracked += 1;
-        let was_active = self.circuit_breaker_active;
+    pub fn record_trade_outcome(&self, pnl: Decimal) -> bool {
+        let mut state = self.state.lock().unwrap();
+        state.total_trades_tracked += 1;
+        let was_active = state.circuit_breaker_active;
         
         if pnl < Decimal::ZERO {
             // Loss recorded
-            self.consecutive_losses += 1;
-            self.consecutive_loss_amount += pnl.abs();
-            self.last_loss_timestamp = SystemTime::now();
+            state.consecutive_losses += 1;
+            state.consecutive_loss_amount += pnl.abs();
+            state.last_loss_timestamp = SystemTime::now();
             
             // Check if we need to trigger circuit breaker
-            if self.consecutive_losses >= self.limits.max_consecutive_losses {
-                self.circuit_breaker_active = true;
-                self.halt_reason = Some(format!(
+            if state.consecutive_losses >= self.limits.max_consecutive_losses {
+                state.circuit_breaker_active = true;
+                state.halt_reason = Some(format!(
                     "Circuit breaker triggered: {} consecutive losses totaling ${:.2}",
-                    self.consecutive_losses,
-                    self.consecutive_loss_amount
+                    state.consecutive_losses,
+                    state.consecutive_loss_amount
                 ));
             }
         } else if pnl > Decimal::ZERO {
             // Win recorded - reset consecutive loss tracking
-            self.reset_consecutive_losses();
+            state.reset_consecutive_losses();
         }
         // Note: Break-even trades (pnl == 0) don't affect consecutive loss count
         
         // Return true if circuit breaker was just triggered
-        !was_active && self.circuit_breaker_active
+        !was_active && state.circuit_breaker_active
     }
     
     /// Manually reset consecutive loss tracking and circuit breaker
     ///
     /// This should be called after trader review and strategy adjustment.
     /// Used for resuming trading after circuit breaker activation.
-    pub fn reset_consecutive_losses(&mut self) {
-        self.consecutive_losses = 0;
-        self.consecutive_loss_amount = Decimal::ZERO;
-        self.circuit_breaker_active = false;
-        self.halt_reason = None;
+    pub fn reset_consecutive_losses(&self) {
+        self.state.lock().unwrap().reset_consecutive_losses();
     }
     
     /// Get current consecutive loss count
     pub fn consecutive_losses(&self) -> u32 {
-        self.consecutive_losses
+        self.state.lock().unwrap().consecutive_losses
     }
     
     /// Get total loss amount from consecutive losses
     pub fn consecutive_loss_amount(&self) -> Decimal {
-        self.consecutive_loss_amount
+        self.state.lock().unwrap().consecutive_loss_amount
     }
     
     /// Check if circuit breaker is currently active
     pub fn is_circuit_breaker_active(&self) -> bool {
-        self.circuit_breaker_active
+        self.state.lock().unwrap().circuit_breaker_active
     }
     
     /// Get the reason for circuit breaker activation
-    pub fn halt_reason(&self) -> Option<&str> {
-        self.halt_reason.as_deref()
+    pub fn halt_reason(&self) -> Option<String> {
+        self.state.lock().unwrap().halt_reason.clone()
     }
     
     /// Get timestamp of last loss
     pub fn last_loss_timestamp(&self) -> SystemTime {
-        self.last_loss_timestamp
+        self.state.lock().unwrap().last_loss_timestamp
     }
     
     /// Get total number of trades tracked
     pub fn total_trades_tracked(&self) -> u32 {
-        self.total_trades_tracked
+        self.state.lock().unwrap().total_trades_tracked
     }
     
     /// Calculate time since last loss
     pub fn time_since_last_loss(&self) -> Option<std::time::Duration> {
-        if self.last_loss_timestamp == SystemTime::UNIX_EPOCH {
+        Self::elapsed_since_loss(self.last_loss_timestamp())
+    }
+    
+    fn elapsed_since_loss(last_loss_timestamp: SystemTime) -> Option<std::time::Duration> {
+        if last_loss_timestamp == SystemTime::UNIX_EPOCH {
             None
         } else {
-            SystemTime::now().duration_since(self.last_loss_timestamp).ok()
+            SystemTime::now().duration_since(last_loss_timestamp).ok()
         }
     }
 }
@@ -1040,48 +2021,30 @@ impl Default for ConsecutiveLossLimitRule {
 
 impl RiskRule for ConsecutiveLossLimitRule {
     fn assess(&self, proposal: &TradeProposal) -> Result<RiskAssessment, AssessmentError> {
-        // Step 1: Calculate position size and potential loss (standard risk assessment)
-        let position_size = self.position_calculator.calculate_position_size(
-            proposal.account_equity,
-            proposal.risk_percentage,
-            proposal.entry_price,
-            proposal.stop_loss,
-        ).map_err(|e| AssessmentError::PositionSizingFailure { reason: e.to_string() })?;
-        
-        let potential_trade_loss = position_size.value() * (proposal.entry_price.value() - proposal.stop_loss.value()).abs();
-        let trade_risk_percentage = potential_trade_loss / proposal.account_equity.value();
-        let portfolio_impact = trade_risk_percentage; // For individual assessment
-        
-        // Step 2: Create initial assessment
-        let mut assessment = RiskAssessment::new(
-            proposal.id,
-            position_size,
-            potential_trade_loss,
-            trade_risk_percentage,
-            proposal.risk_reward_ratio(),
-            portfolio_impact,
-        );
+        // Step 1: Size the proposed trade and open the assessment
+        let mut assessment = sized_assessment(&self.position_calculator, proposal)?;
         
-        // Step 3: Check circuit breaker status
-        if self.circuit_breaker_active {
+        // Step 2: Check circuit breaker status
+        let state = self.state.lock().unwrap();
+        if state.circuit_breaker_active {
             // Circuit breaker is active - block all trades
             let violation = ProtocolViolation::new(
                 self.rule_name().to_string(),
                 ViolationSeverity::Critical,
                 format!(
                     "Circuit breaker active: {} consecutive losses (limit: {}). Trading halted for risk management.",
-                    self.consecutive_losses,
+                    state.consecutive_losses,
                     self.limits.max_consecutive_losses
                 ),
-                Decimal::from(self.consecutive_losses),
+                Decimal::from(state.consecutive_losses),
                 Decimal::from(self.limits.max_consecutive_losses),
                 "Review trading strategy and manually reset circuit breaker to resume trading".to_string(),
             );
             
             assessment.add_violation(violation);
-        } else if self.consecutive_losses > 0 {
+        } else if state.consecutive_losses > 0 {
             // Not at limit yet, but warn about approaching danger
-            let remaining_losses = self.limits.max_consecutive_losses - self.consecutive_losses;
+            let remaining_losses = self.limits.max_consecutive_losses - state.consecutive_losses;
             
             if remaining_losses <= 1 {
                 // One loss away from circuit breaker
@@ -1090,31 +2053,31 @@ impl RiskRule for ConsecutiveLossLimitRule {
                     ViolationSeverity::Warning,
                     format!(
                         "Warning: {} consecutive losses recorded. Circuit breaker will trigger after {} more loss(es).",
-                        self.consecutive_losses,
+                        state.consecutive_losses,
                         remaining_losses
                     ),
-                    Decimal::from(self.consecutive_losses),
+                    Decimal::from(state.consecutive_losses),
                     Decimal::from(self.limits.max_consecutive_losses),
-                    format!("Consider reducing position size or reviewing strategy. Total consecutive losses: ${:.2}", self.consecutive_loss_amount),
+                    format!("Consider reducing position size or reviewing strategy. Total consecutive losses: ${:.2}", state.consecutive_loss_amount),
                 );
                 
                 assessment.add_violation(violation);
             }
         }
         
-        // Step 4: Set reasoning
-        let time_since_last_loss = self.time_since_last_loss()
+        // Step 3: Set reasoning
+        let time_since_last_loss = Self::elapsed_since_loss(state.last_loss_timestamp)
             .map(|d| format!("{:.1} minutes ago", d.as_secs_f64() / 60.0))
             .unwrap_or_else(|| "N/A".to_string());
         
         assessment = assessment.with_reasoning(format!(
             "Consecutive Loss Analysis: {} consecutive losses out of {} limit. Last loss: {}. Total consecutive loss amount: ${:.2}. Circuit breaker: {}. Total trades tracked: {}.",
-            self.consecutive_losses,
+            state.consecutive_losses,
             self.limits.max_consecutive_losses,
             time_since_last_loss,
-            self.consecutive_loss_amount,
-            if self.circuit_breaker_active { "ACTIVE" } else { "inactive" },
-            self.total_trades_tracked
+            state.consecutive_loss_amount,
+            if state.circuit_breaker_active { "ACTIVE" } else { "inactive" },
+            state.total_trades_tracked
         ));
         
         Ok(assessment)
@@ -1127,6 +2090,10 @@ impl RiskRule for ConsecutiveLossLimitRule {
     fn description(&self) -> &str {
         "Enforces circuit breaker protection by blocking trades after consecutive losses exceed protocol limits, preventing emotional revenge trading and protecting capital from psychological pitfalls."
     }
+    
+    fn record_outcome(&self, pnl: Decimal) {
+        self.record_trade_outcome(pnl);
+    }
 }
 
 // ===== CONSECUTIVE LOSS LIMIT TESTS =====
@@ -1135,6 +2102,7 @@ impl RiskRule for ConsecutiveLossLimitRule {
 mod consecutive_loss_tests {
     use super::*;
     use std::time::SystemTime;
+    use crate::risk::protocol::RiskManagementProtocol;
     use crate::types::TradeSide;
     use disciplina::{AccountEquity, RiskPercentage, PricePoint};
 
@@ -1176,7 +2144,7 @@ mod consecutive_loss_tests {
     
     #[test]
     fn test_recording_consecutive_losses() {
-        let mut rule = ConsecutiveLossLimitRule::new();
+        let rule = ConsecutiveLossLimitRule::new();
         
         // Record first loss - should not trigger circuit breaker
         let triggered = rule.record_trade_outcome(dec!(-100));
@@ -1204,7 +2172,7 @@ mod consecutive_loss_tests {
     
     #[test]
     fn test_winning_trade_resets_consecutive_losses() {
-        let mut rule = ConsecutiveLossLimitRule::new();
+        let rule = ConsecutiveLossLimitRule::new();
         
         // Record some losses
         rule.record_trade_outcome(dec!(-100));
@@ -1222,7 +2190,7 @@ mod consecutive_loss_tests {
     
     #[test]
     fn test_breakeven_trades_dont_affect_count() {
-        let mut rule = ConsecutiveLossLimitRule::new();
+        let rule = ConsecutiveLossLimitRule::new();
         
         // Record some losses
         rule.record_trade_outcome(dec!(-100));
@@ -1238,7 +2206,7 @@ mod consecutive_loss_tests {
     
     #[test]
     fn test_circuit_breaker_blocks_new_trades() {
-        let mut rule = ConsecutiveLossLimitRule::new();
+        let rule = ConsecutiveLossLimitRule::new();
         
         // Trigger circuit breaker
         rule.record_trade_outcome(dec!(-100));
@@ -1263,7 +2231,7 @@ mod consecutive_loss_tests {
     
     #[test]
     fn test_warning_when_approaching_limit() {
-        let mut rule = ConsecutiveLossLimitRule::new(); // Default limit is 3
+        let rule = ConsecutiveLossLimitRule::new(); // Default limit is 3
         
         // Record 2 losses - should generate warning (1 away from limit)
         rule.record_trade_outcome(dec!(-100));
@@ -1285,7 +2253,7 @@ mod consecutive_loss_tests {
     
     #[test]
     fn test_manual_reset_functionality() {
-        let mut rule = ConsecutiveLossLimitRule::new();
+        let rule = ConsecutiveLossLimitRule::new();
         
         // Trigger circuit breaker
         rule.record_trade_outcome(dec!(-100));
@@ -1312,8 +2280,8 @@ mod consecutive_loss_tests {
     
     #[test]
     fn test_conservative_rule_triggers_earlier() {
-        let mut conservative_rule = ConsecutiveLossLimitRule::conservative(); // Limit: 2
-        let mut standard_rule = ConsecutiveLossLimitRule::new(); // Limit: 3
+        let conservative_rule = ConsecutiveLossLimitRule::conservative(); // Limit: 2
+        let standard_rule = ConsecutiveLossLimitRule::new(); // Limit: 3
         
         // Record 2 losses for both
         conservative_rule.record_trade_outcome(dec!(-100));
@@ -1353,7 +2321,7 @@ mod consecutive_loss_tests {
     
     #[test]
     fn test_time_tracking_functionality() {
-        let mut rule = ConsecutiveLossLimitRule::new();
+        let rule = ConsecutiveLossLimitRule::new();
         
         // Initially no time tracking
         assert!(rule.time_since_last_loss().is_none());
@@ -1374,7 +2342,7 @@ mod consecutive_loss_tests {
     
     #[test]
     fn test_consecutive_loss_reasoning_messages() {
-        let mut rule = ConsecutiveLossLimitRule::new();
+        let rule = ConsecutiveLossLimitRule::new();
         rule.record_trade_outcome(dec!(-100));
         rule.record_trade_outcome(dec!(-200));
         
@@ -1388,4 +2356,400 @@ mod consecutive_loss_tests {
         assert!(reasoning.contains("Circuit breaker: inactive"));
         assert!(reasoning.contains("Total trades tracked: 2"));
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_losses_recorded_through_protocol_trip_the_shared_breaker() {
+        let rule = ConsecutiveLossLimitRule::new(); // Default limit is 3
+        let protocol = RiskManagementProtocol::new()
+            .add_rule_ref(Arc::new(rule.clone()))
+            .add_rule(DailyLossLimitRule::new());
+        let proposal = create_test_proposal(dec!(0.01));
+        assert!(protocol.assess_trade(&proposal).unwrap().is_approved());
+        
+        for _ in 0..3 {
+            protocol.record_trade_outcome(dec!(-100));
+        }
+        
+        // The protocol's copy and the caller's handle share one streak
+        assert!(rule.is_circuit_breaker_active());
+        let result = protocol.assess_trade(&proposal).unwrap();
+        assert!(result.is_rejected());
+        assert!(result
+            .critical_violations()
+            .iter()
+            .any(|violation| violation.rule_name == "ConsecutiveLossLimit"));
+    }
+}
+
+// ===== MAX DRAWDOWN RULE =====
+
+/// Halt new trades once the account falls too far below its peak equity
+///
+/// Drawdown is measured from a running high-water mark: every equity reading
+/// passed to [`record_equity`](Self::record_equity) that sets a new high
+/// becomes the peak, and the current drawdown is the fraction of that peak
+/// since lost. While the drawdown exceeds `limits.max_drawdown`, every
+/// proposal is rejected; recovering to a new high clears it.
+///
+/// # Shared State
+/// The peak and latest equity live behind a shared lock, so clones share
+/// them: a rule added to a `RiskManagementProtocol` sees equity recorded
+/// through the handle the caller kept.
+#[derive(Debug, Clone)]
+pub struct MaxDrawdownRule {
+    /// Protocol limits for validation
+    limits: ProtocolLimits,
+    /// Van Tharp position sizing calculator
+    position_calculator: PositionSizingCalculator,
+    /// High-water mark and latest equity reading
+    state: Arc<Mutex<DrawdownState>>,
+}
+
+#[derive(Debug, Default)]
+struct DrawdownState {
+    /// Highest equity recorded so far
+    peak_equity: Option<Decimal>,
+    /// Most recent equity recorded
+    current_equity: Option<Decimal>,
+}
+
+impl DrawdownState {
+    /// Fraction of the peak lost since it was set (0 when at or above the peak)
+    fn drawdown(&self) -> Decimal {
+        match (self.peak_equity, self.current_equity) {
+            (Some(peak), Some(current)) if peak > Decimal::ZERO && current < peak => {
+                (peak - current) / peak
+            }
+            _ => Decimal::ZERO,
+        }
+    }
+}
+
+impl MaxDrawdownRule {
+    /// Create a new MaxDrawdownRule with default limits (10% drawdown)
+    pub fn new() -> Self {
+        Self::with_limits(ProtocolLimits::default())
+    }
+
+    /// Create a conservative MaxDrawdownRule (5% drawdown)
+    pub fn conservative() -> Self {
+        Self::with_limits(ProtocolLimits::conservative_limits())
+    }
+
+    /// Create an aggressive MaxDrawdownRule (15% drawdown)
+    pub fn aggressive() -> Self {
+        Self::with_limits(ProtocolLimits::aggressive_limits())
+    }
+
+    /// Create a MaxDrawdownRule with custom limits
+    pub fn with_limits(limits: ProtocolLimits) -> Self {
+        Self {
+            limits,
+            position_calculator: PositionSizingCalculator::new(),
+            state: Arc::new(Mutex::new(DrawdownState::default())),
+        }
+    }
+
+    /// Record the latest account equity, raising the high-water mark on a new high
+    pub fn record_equity(&self, equity: Decimal) {
+        let mut state = self.state.lock().unwrap();
+        if state.peak_equity.is_none_or(|peak| equity > peak) {
+            state.peak_equity = Some(equity);
+        }
+        state.current_equity = Some(equity);
+    }
+
+    /// Highest equity recorded, or `None` before the first reading
+    pub fn peak_equity(&self) -> Option<Decimal> {
+        self.state.lock().unwrap().peak_equity
+    }
+
+    /// Current drawdown from the peak, as a fraction of peak equity
+    pub fn current_drawdown(&self) -> Decimal {
+        self.state.lock().unwrap().drawdown()
+    }
+
+    /// Whether the drawdown currently blocks new trades
+    pub fn is_halted(&self) -> bool {
+        self.current_drawdown() > self.limits.max_drawdown
+    }
+}
+
+impl Default for MaxDrawdownRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RiskRule for MaxDrawdownRule {
+    fn assess(&self, proposal: &TradeProposal) -> Result<RiskAssessment, AssessmentError> {
+        let mut assessment = sized_assessment(&self.position_calculator, proposal)?;
+
+        let state = self.state.lock().unwrap();
+        let drawdown = state.drawdown();
+        let peak = state
+            .peak_equity
+            .map(|peak| format!("${:.2}", peak))
+            .unwrap_or_else(|| "not yet recorded".to_string());
+
+        if drawdown > self.limits.max_drawdown {
+            let violation = ProtocolViolation::new(
+                self.rule_name().to_string(),
+                ViolationSeverity::Critical,
+                format!(
+                    "Drawdown {:.2}% from peak equity {} exceeds maximum {:.2}%. Trading halted until equity recovers.",
+                    drawdown * dec!(100),
+                    peak,
+                    self.limits.max_drawdown * dec!(100)
+                ),
+                drawdown,
+                self.limits.max_drawdown,
+                "Stop opening positions and review the strategy; trading resumes when equity makes a new high".to_string(),
+            );
+            assessment.add_violation(violation);
+        }
+
+        let reasoning = if assessment.is_approved() {
+            format!(
+                "Drawdown approved: Current drawdown {:.2}% from peak equity {} within {:.2}% limit.",
+                drawdown * dec!(100),
+                peak,
+                self.limits.max_drawdown * dec!(100)
+            )
+        } else {
+            format!(
+                "Drawdown violation: Current drawdown {:.2}% from peak equity {} exceeds {:.2}% limit.",
+                drawdown * dec!(100),
+                peak,
+                self.limits.max_drawdown * dec!(100)
+            )
+        };
+
+        Ok(assessment.with_reasoning(reasoning))
+    }
+
+    fn rule_name(&self) -> &str {
+        "MaxDrawdown"
+    }
+
+    fn description(&self) -> &str {
+        "Blocks new trades while the account's drawdown from its peak equity exceeds the protocol's maximum drawdown"
+    }
+}
+
+// ===== MAX DRAWDOWN TESTS =====
+
+#[cfg(test)]
+mod max_drawdown_tests {
+    use super::*;
+    use crate::types::TradeSide;
+    use disciplina::{AccountEquity, RiskPercentage, PricePoint};
+
+    fn create_test_proposal() -> TradeProposal {
+        TradeProposal::new(
+            "BTCUSDT".to_string(),
+            TradeSide::Long,
+            PricePoint::new(dec!(100)).unwrap(),
+            PricePoint::new(dec!(95)).unwrap(),
+            Some(PricePoint::new(dec!(110)).unwrap()),
+            AccountEquity::new(dec!(10000)).unwrap(),
+            RiskPercentage::new(dec!(0.01)).unwrap(),
+        ).unwrap()
+    }
+
+    #[test]
+    fn test_drawdown_beyond_limit_halts_until_new_high() {
+        let rule = MaxDrawdownRule::new(); // 10% limit
+        let proposal = create_test_proposal();
+        assert_eq!(rule.current_drawdown(), Decimal::ZERO);
+        assert!(rule.assess(&proposal).unwrap().is_approved());
+
+        rule.record_equity(dec!(10000));
+        rule.record_equity(dec!(12000));
+        rule.record_equity(dec!(11000));
+        assert_eq!(rule.peak_equity(), Some(dec!(12000)));
+        assert!(rule.assess(&proposal).unwrap().is_approved());
+
+        // 12,000 -> 10,200 is a 15% drawdown
+        rule.record_equity(dec!(10200));
+        assert_eq!(rule.current_drawdown(), dec!(0.15));
+        let assessment = rule.assess(&proposal).unwrap();
+        assert!(!assessment.is_approved());
+        let reasoning = assessment.reasoning.unwrap();
+        assert!(reasoning.contains("15.00%"));
+        assert!(reasoning.contains("peak equity $12000.00"));
+
+        // Recovering to 11,500 is still below the old peak, not a new high
+        rule.record_equity(dec!(11500));
+        assert_eq!(rule.peak_equity(), Some(dec!(12000)));
+        assert!(rule.assess(&proposal).unwrap().is_approved());
+
+        // A new high resets the mark; drawdown is measured from it
+        rule.record_equity(dec!(13000));
+        assert_eq!(rule.current_drawdown(), Decimal::ZERO);
+        rule.record_equity(dec!(11600));
+        assert_eq!(rule.peak_equity(), Some(dec!(13000)));
+        assert!(rule.is_halted());
+    }
+}
+
+// ===== LOSS COOLDOWN RULE =====
+
+/// Enforce a cooling-off period after every losing trade
+///
+/// Unlike the consecutive-loss circuit breaker, a single loss is enough, and
+/// no reset is needed: proposals submitted within `cooldown` of the most
+/// recent loss are rejected, and trading resumes on its own once the period
+/// has passed. Wins and break-even trades leave the cooldown untouched.
+///
+/// # Shared State
+/// The last loss time lives behind a shared lock, so clones share it:
+/// outcomes recorded through `RiskManagementProtocol::record_trade_outcome`
+/// start the cooldown for the next assessment.
+#[derive(Debug, Clone)]
+pub struct LossCooldownRule {
+    /// Time after a loss during which new trades are rejected
+    cooldown: std::time::Duration,
+    /// Van Tharp position sizing calculator
+    position_calculator: PositionSizingCalculator,
+    /// When the most recent losing trade was recorded
+    last_loss: Arc<Mutex<Option<SystemTime>>>,
+}
+
+impl LossCooldownRule {
+    /// Create a rule rejecting trades for `cooldown` after each loss
+    pub fn new(cooldown: std::time::Duration) -> Self {
+        Self {
+            cooldown,
+            position_calculator: PositionSizingCalculator::new(),
+            last_loss: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Record the outcome of a trade, starting the cooldown on a loss
+    ///
+    /// # Arguments
+    /// * `pnl` - Profit/loss amount (positive for profit, negative for loss)
+    pub fn record_trade_outcome(&self, pnl: Decimal) {
+        if pnl < Decimal::ZERO {
+            *self.last_loss.lock().unwrap() = Some(SystemTime::now());
+        }
+    }
+
+    /// Configured cooldown period
+    pub fn cooldown(&self) -> std::time::Duration {
+        self.cooldown
+    }
+
+    /// Time left before trading resumes, or `None` outside a cooldown
+    pub fn remaining_cooldown(&self) -> Option<std::time::Duration> {
+        let last_loss = (*self.last_loss.lock().unwrap())?;
+        // A clock that stepped backwards counts as no time elapsed
+        let elapsed = SystemTime::now().duration_since(last_loss).unwrap_or_default();
+        self.cooldown.checked_sub(elapsed).filter(|remaining| !remaining.is_zero())
+    }
+
+    /// Whether a recent loss currently blocks new trades
+    pub fn is_cooling_down(&self) -> bool {
+        self.remaining_cooldown().is_some()
+    }
+}
+
+impl RiskRule for LossCooldownRule {
+    fn assess(&self, proposal: &TradeProposal) -> Result<RiskAssessment, AssessmentError> {
+        let mut assessment = sized_assessment(&self.position_calculator, proposal)?;
+
+        let reasoning = match self.remaining_cooldown() {
+            Some(remaining) => {
+                let resumes_at = SystemTime::now() + remaining;
+                let violation = ProtocolViolation::new(
+                    self.rule_name().to_string(),
+                    ViolationSeverity::Critical,
+                    format!(
+                        "Loss cooldown active: {}s of the {}s cooldown remain after the last losing trade.",
+                        remaining.as_secs(),
+                        self.cooldown.as_secs()
+                    ),
+                    Decimal::from(remaining.as_secs()),
+                    Decimal::from(self.cooldown.as_secs()),
+                    "Wait for the cooldown to expire before opening a new position".to_string(),
+                )
+                .with_hint(SuggestedAction::WaitUntil { time: resumes_at.into() });
+                assessment.add_violation(violation);
+
+                format!(
+                    "Loss cooldown violation: {:.1} minutes of the {:.1} minute cooldown remaining.",
+                    remaining.as_secs_f64() / 60.0,
+                    self.cooldown.as_secs_f64() / 60.0
+                )
+            }
+            None => format!(
+                "Loss cooldown approved: No loss within the last {:.1} minutes.",
+                self.cooldown.as_secs_f64() / 60.0
+            ),
+        };
+
+        Ok(assessment.with_reasoning(reasoning))
+    }
+
+    fn rule_name(&self) -> &str {
+        "LossCooldown"
+    }
+
+    fn description(&self) -> &str {
+        "Rejects new trades for a fixed cooldown period after each losing trade, clearing automatically once the period elapses"
+    }
+
+    fn record_outcome(&self, pnl: Decimal) {
+        self.record_trade_outcome(pnl);
+    }
+}
+
+// ===== LOSS COOLDOWN TESTS =====
+
+#[cfg(test)]
+mod loss_cooldown_tests {
+    use super::*;
+    use crate::types::TradeSide;
+    use disciplina::{AccountEquity, RiskPercentage, PricePoint};
+    use std::time::Duration;
+
+    fn create_test_proposal() -> TradeProposal {
+        TradeProposal::new(
+            "BTCUSDT".to_string(),
+            TradeSide::Long,
+            PricePoint::new(dec!(100)).unwrap(),
+            PricePoint::new(dec!(95)).unwrap(),
+            Some(PricePoint::new(dec!(110)).unwrap()),
+            AccountEquity::new(dec!(10000)).unwrap(),
+            RiskPercentage::new(dec!(0.01)).unwrap(),
+        ).unwrap()
+    }
+
+    #[test]
+    fn test_cooldown_after_loss_clears_once_elapsed() {
+        let rule = LossCooldownRule::new(Duration::from_millis(200));
+        let proposal = create_test_proposal();
+        assert!(rule.assess(&proposal).unwrap().is_approved());
+
+        // Wins never start a cooldown
+        rule.record_trade_outcome(dec!(150));
+        assert!(!rule.is_cooling_down());
+
+        rule.record_trade_outcome(dec!(-100));
+        let remaining = rule.remaining_cooldown().unwrap();
+        assert!(remaining <= rule.cooldown());
+        let assessment = rule.assess(&proposal).unwrap();
+        assert!(!assessment.is_approved());
+        assert!(matches!(
+            assessment.violations[0].hint,
+            Some(SuggestedAction::WaitUntil { .. })
+        ));
+        assert!(assessment.reasoning.unwrap().contains("minute cooldown remaining"));
+
+        // No manual reset: the cooldown lapses on its own
+        std::thread::sleep(Duration::from_millis(250));
+        assert_eq!(rule.remaining_cooldown(), None);
+        assert!(rule.assess(&proposal).unwrap().is_approved());
+    }
+}
@@ -9,14 +9,18 @@
 //! enforces the core Testudo Protocol limits.
 
 use crate::risk::assessment_rules::{RiskRule, AssessmentError};
+use crate::risk::async_rules;
 use crate::types::{ProtocolLimits, ProtocolViolation, TradeProposal, RiskAssessment, ApprovalStatus, ViolationSeverity};
-use crate::types::protocol_limits::ProtocolLimitViolation;
+use crate::types::protocol_limits::{ProtocolLimitViolation, CircuitBreakerScope};
+use crate::monitoring::{DailyLossMonitor, DailyLossAlert};
 use rust_decimal::Decimal;
-use std::collections::HashMap;
-use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Arc, Mutex};
 use std::time::{SystemTime, Duration};
 use thiserror::Error;
 use tracing::{debug, info, warn, error, instrument};
+use uuid::Uuid;
 
 //=============================================================================
 // RISK MANAGEMENT PROTOCOL - Task 3 Implementation
@@ -46,13 +50,145 @@ pub enum ProtocolError {
 #[derive(Debug, Clone)]
 pub struct RiskManagementProtocol {
     /// Collection of risk rules to apply to trade proposals
-    risk_rules: Vec<Arc<dyn RiskRule>>,
+    risk_rules: Vec<ProtocolRule>,
     
     /// Name identifier for logging and debugging
     protocol_name: String,
     
     /// Whether to stop on first critical violation or collect all violations
     fail_fast: bool,
+
+    /// Whether to skip the remaining rules once a hard rule rejects the trade
+    fail_on_critical: bool,
+
+    /// Classification of each rule, parallel to `risk_rules`
+    rule_classes: Vec<RuleClass>,
+
+    /// How critical violations from advisory rules are aggregated
+    advisory_policy: AdvisoryPolicy,
+
+    /// Per-rule outcome counts across assessments, shared by clones
+    rule_outcomes: Arc<Mutex<HashMap<String, RuleOutcomeCounts>>>,
+}
+
+/// A configured rule, assessed inline or awaited
+#[derive(Debug, Clone)]
+enum ProtocolRule {
+    Sync(Arc<dyn RiskRule>),
+    Async(Arc<dyn async_rules::AsyncRiskRule>),
+}
+
+impl ProtocolRule {
+    fn rule_name(&self) -> &str {
+        match self {
+            ProtocolRule::Sync(rule) => rule.rule_name(),
+            ProtocolRule::Async(rule) => rule.rule_name(),
+        }
+    }
+
+    fn record_outcome(&self, pnl: Decimal) {
+        match self {
+            ProtocolRule::Sync(rule) => rule.record_outcome(pnl),
+            ProtocolRule::Async(rule) => rule.record_outcome(pnl),
+        }
+    }
+}
+
+/// Running totals while a protocol assesses one proposal
+struct AssessmentTally {
+    start_time: std::time::Instant,
+    rule_results: Vec<RuleAssessmentResult>,
+    consolidated_violations: Vec<ProtocolViolation>,
+    critical_violations: u32,
+    warnings: u32,
+    assessment_failures: u32,
+    advisory_rules: u32,
+    advisory_objections: u32,
+    advisory_critical: u32,
+    failed_rules: Vec<RuleFailure>,
+    rejecting_rules: Vec<String>,
+    /// Primary assessment from first successful rule (for position sizing baseline)
+    primary_assessment: Option<RiskAssessment>,
+    short_circuited_by: Option<String>,
+}
+
+impl AssessmentTally {
+    fn new() -> Self {
+        Self {
+            start_time: std::time::Instant::now(),
+            rule_results: Vec::new(),
+            consolidated_violations: Vec::new(),
+            critical_violations: 0,
+            warnings: 0,
+            assessment_failures: 0,
+            advisory_rules: 0,
+            advisory_objections: 0,
+            advisory_critical: 0,
+            failed_rules: Vec::new(),
+            rejecting_rules: Vec::new(),
+            primary_assessment: None,
+            short_circuited_by: None,
+        }
+    }
+
+    /// Record a rule left unrun after an earlier hard rejection
+    fn skip(&mut self, rule_name: &str) {
+        let rejected_by = self.short_circuited_by.as_deref().unwrap_or_default();
+        self.rule_results.push(RuleAssessmentResult {
+            rule_name: rule_name.to_string(),
+            outcome: RuleOutcome::Skipped {
+                reason: format!("{} already rejected the trade", rejected_by),
+            },
+            execution_time_ms: 0,
+        });
+    }
+}
+
+/// How often a rule passed, rejected a trade, or failed to run
+///
+/// A rejection is the rule working as intended; a failure means the rule
+/// could not assess the trade at all and usually points to a bug or bad
+/// configuration in the rule itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RuleOutcomeCounts {
+    /// Ran without raising a critical or blocking violation
+    pub passed: u64,
+    /// Ran and raised a critical or blocking violation
+    pub rejected: u64,
+    /// Returned an error instead of an assessment
+    pub failed: u64,
+}
+
+/// A rule that returned an error instead of an assessment
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleFailure {
+    pub rule_name: String,
+    pub reason: String,
+}
+
+/// Whether a rule's critical violations are absolute or subject to aggregation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RuleClass {
+    /// Hard limit: any critical violation rejects the trade
+    #[default]
+    Hard,
+
+    /// Advisory: critical violations are votes aggregated by the `AdvisoryPolicy`
+    Advisory,
+}
+
+/// Aggregation policy for critical violations raised by advisory rules
+///
+/// Hard-limit rules are never subject to this policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AdvisoryPolicy {
+    /// Any advisory rule with a critical violation rejects the trade
+    #[default]
+    AnyReject,
+
+    /// Reject only when more than half of the advisory rules object;
+    /// otherwise their violations are downgraded to warnings
+    Majority,
 }
 
 /// The result of assessing a trade proposal through the complete protocol
@@ -69,16 +205,62 @@ pub struct ProtocolAssessmentResult {
     
     /// Detailed reasoning for the protocol decision
     pub decision_reasoning: String,
+    
+    /// Rules that failed to run, with their errors
+    pub failed_rules: Vec<RuleFailure>,
+    
+    /// Rules that ran and raised a critical or blocking violation
+    pub rejecting_rules: Vec<String>,
+}
+
+/// What a single rule produced during an assessment
+#[derive(Debug, Clone)]
+pub enum RuleOutcome {
+    /// The rule ran and assessed the trade
+    Assessed(RiskAssessment),
+    /// The rule ran but returned an error instead of an assessment
+    Failed(AssessmentError),
+    /// The rule was not run, because an earlier hard rule already rejected the trade
+    Skipped { reason: String },
+}
+
+impl From<Result<RiskAssessment, AssessmentError>> for RuleOutcome {
+    fn from(result: Result<RiskAssessment, AssessmentError>) -> Self {
+        match result {
+            Ok(assessment) => RuleOutcome::Assessed(assessment),
+            Err(error) => RuleOutcome::Failed(error),
+        }
+    }
 }
 
 /// Individual risk rule assessment result
 #[derive(Debug, Clone)]
 pub struct RuleAssessmentResult {
     pub rule_name: String,
-    pub assessment: Result<RiskAssessment, AssessmentError>,
+    pub outcome: RuleOutcome,
     pub execution_time_ms: u64,
 }
 
+impl RuleAssessmentResult {
+    /// The rule's assessment, if it ran successfully
+    pub fn assessment(&self) -> Option<&RiskAssessment> {
+        match &self.outcome {
+            RuleOutcome::Assessed(assessment) => Some(assessment),
+            _ => None,
+        }
+    }
+    
+    /// Whether the rule ran but failed to produce an assessment
+    pub fn failed(&self) -> bool {
+        matches!(self.outcome, RuleOutcome::Failed(_))
+    }
+    
+    /// Whether the rule was skipped rather than run
+    pub fn was_skipped(&self) -> bool {
+        matches!(self.outcome, RuleOutcome::Skipped { .. })
+    }
+}
+
 /// Protocol-level decision enumeration
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ProtocolDecision {
@@ -102,6 +284,10 @@ impl RiskManagementProtocol {
             risk_rules: Vec::new(),
             protocol_name: "RiskManagementProtocol".to_string(),
             fail_fast: false,
+            fail_on_critical: false,
+            rule_classes: Vec::new(),
+            advisory_policy: AdvisoryPolicy::default(),
+            rule_outcomes: Arc::new(Mutex::new(HashMap::new())),
         }
     }
     
@@ -111,6 +297,10 @@ impl RiskManagementProtocol {
             risk_rules: Vec::new(),
             protocol_name: name,
             fail_fast,
+            fail_on_critical: false,
+            rule_classes: Vec::new(),
+            advisory_policy: AdvisoryPolicy::default(),
+            rule_outcomes: Arc::new(Mutex::new(HashMap::new())),
         }
     }
     
@@ -120,16 +310,67 @@ impl RiskManagementProtocol {
     /// add cheaper rules first (like individual trade limits) before expensive
     /// rules (like portfolio-wide calculations).
     pub fn add_rule<R: RiskRule + 'static>(mut self, rule: R) -> Self {
-        self.risk_rules.push(Arc::new(rule));
+        self.risk_rules.push(ProtocolRule::Sync(Arc::new(rule)));
+        self.rule_classes.push(RuleClass::Hard);
         self
     }
     
     /// Add a risk rule by Arc reference (for sharing rules across protocols)
     pub fn add_rule_ref(mut self, rule: Arc<dyn RiskRule>) -> Self {
-        self.risk_rules.push(rule);
+        self.risk_rules.push(ProtocolRule::Sync(rule));
+        self.rule_classes.push(RuleClass::Hard);
+        self
+    }
+    
+    /// Add a hard-limit rule that is awaited during assessment
+    ///
+    /// Use this for rules that query live data. A protocol holding async
+    /// rules must be assessed with [`assess_trade_async`](Self::assess_trade_async).
+    pub fn add_async_rule<R: async_rules::AsyncRiskRule + 'static>(mut self, rule: R) -> Self {
+        self.risk_rules.push(ProtocolRule::Async(Arc::new(rule)));
+        self.rule_classes.push(RuleClass::Hard);
+        self
+    }
+    
+    /// Add an advisory risk rule whose critical violations follow the advisory policy
+    pub fn add_advisory_rule<R: RiskRule + 'static>(mut self, rule: R) -> Self {
+        self.risk_rules.push(ProtocolRule::Sync(Arc::new(rule)));
+        self.rule_classes.push(RuleClass::Advisory);
+        self
+    }
+    
+    /// Set how critical violations from advisory rules are aggregated
+    pub fn with_advisory_policy(mut self, policy: AdvisoryPolicy) -> Self {
+        self.advisory_policy = policy;
+        self
+    }
+    
+    /// Stop running rules once a hard rule raises a critical or blocking violation
+    ///
+    /// The trade is rejected regardless of what later rules find, so skipping
+    /// them saves their latency. Skipped rules still appear in `rule_results`,
+    /// with a [`Skipped`](RuleOutcome::Skipped) outcome and no execution
+    /// time. Advisory rules never short-circuit, since their objections are
+    /// only counted once every advisory rule has voted.
+    pub fn with_fail_on_critical(mut self, fail_on_critical: bool) -> Self {
+        self.fail_on_critical = fail_on_critical;
         self
     }
     
+    /// Get the advisory aggregation policy
+    pub fn advisory_policy(&self) -> AdvisoryPolicy {
+        self.advisory_policy
+    }
+    
+    /// Outcome counts for every rule that has been assessed, by rule name
+    pub fn rule_outcome_counts(&self) -> HashMap<String, RuleOutcomeCounts> {
+        self.rule_outcomes.lock().unwrap().clone()
+    }
+    
+    fn record_rule_outcome(&self, rule_name: &str, record: impl FnOnce(&mut RuleOutcomeCounts)) {
+        record(self.rule_outcomes.lock().unwrap().entry(rule_name.to_string()).or_default());
+    }
+    
     /// Get the number of configured risk rules
     pub fn rule_count(&self) -> usize {
         self.risk_rules.len()
@@ -140,13 +381,79 @@ impl RiskManagementProtocol {
         &self.protocol_name
     }
     
+    /// Record a closed trade's P&L with every rule
+    ///
+    /// Stateful rules share their state with the handles they were added
+    /// from, so the outcome counts toward the next `assess_trade`.
+    pub fn record_trade_outcome(&self, pnl: Decimal) {
+        for rule in &self.risk_rules {
+            rule.record_outcome(pnl);
+        }
+    }
+    
     /// Assess a trade proposal against all configured risk rules
     /// 
     /// This is the main entry point for trade validation. It runs all risk rules
     /// and consolidates their assessments into a single protocol decision.
     /// This implements the core requirement for Task 3.
+    ///
+    /// Protocols holding async rules cannot be assessed synchronously; use
+    /// [`assess_trade_async`](Self::assess_trade_async) for them.
     #[instrument(skip(self, proposal), fields(proposal_id = %proposal.id, symbol = %proposal.symbol))]
     pub fn assess_trade(&self, proposal: &TradeProposal) -> Result<ProtocolAssessmentResult, ProtocolError> {
+        if let Some(rule) = self.risk_rules.iter().find(|rule| matches!(rule, ProtocolRule::Async(_))) {
+            return Err(ProtocolError::ConfigurationError {
+                reason: format!(
+                    "Rule '{}' is async; assess the trade with assess_trade_async",
+                    rule.rule_name()
+                ),
+            });
+        }
+        
+        let mut tally = self.begin_assessment(proposal)?;
+        for (rule, class) in self.risk_rules.iter().zip(&self.rule_classes) {
+            if tally.short_circuited_by.is_some() {
+                tally.skip(rule.rule_name());
+                continue;
+            }
+            
+            debug!("Executing risk rule: {}", rule.rule_name());
+            let rule_start = std::time::Instant::now();
+            let assessment_result = match rule {
+                ProtocolRule::Sync(rule) => rule.assess(proposal),
+                ProtocolRule::Async(_) => unreachable!("async rules are rejected above"),
+            };
+            self.record_rule_result(&mut tally, rule.rule_name(), *class, assessment_result, rule_start)?;
+        }
+        self.finish_assessment(proposal, tally)
+    }
+    
+    /// Assess a trade proposal, awaiting async rules and running sync ones inline
+    ///
+    /// Rules run one at a time in the order they were added, exactly as in
+    /// [`assess_trade`](Self::assess_trade), so fail-fast and fail-on-critical
+    /// behave the same.
+    #[instrument(skip(self, proposal), fields(proposal_id = %proposal.id, symbol = %proposal.symbol))]
+    pub async fn assess_trade_async(&self, proposal: &TradeProposal) -> Result<ProtocolAssessmentResult, ProtocolError> {
+        let mut tally = self.begin_assessment(proposal)?;
+        for (rule, class) in self.risk_rules.iter().zip(&self.rule_classes) {
+            if tally.short_circuited_by.is_some() {
+                tally.skip(rule.rule_name());
+                continue;
+            }
+            
+            debug!("Executing risk rule: {}", rule.rule_name());
+            let rule_start = std::time::Instant::now();
+            let assessment_result = match rule {
+                ProtocolRule::Sync(rule) => rule.assess(proposal),
+                ProtocolRule::Async(rule) => rule.assess(proposal).await,
+            };
+            self.record_rule_result(&mut tally, rule.rule_name(), *class, assessment_result, rule_start)?;
+        }
+        self.finish_assessment(proposal, tally)
+    }
+    
+    fn begin_assessment(&self, proposal: &TradeProposal) -> Result<AssessmentTally, ProtocolError> {
         if self.risk_rules.is_empty() {
             error!("No risk rules configured for protocol '{}'", self.protocol_name);
             return Err(ProtocolError::NoRulesConfigured);
@@ -158,76 +465,146 @@ impl RiskManagementProtocol {
             proposal.symbol
         );
         
-        let start_time = std::time::Instant::now();
-        let mut rule_results = Vec::new();
-        let mut consolidated_violations = Vec::new();
-        let mut critical_violations = 0;
-        let mut warnings = 0;
-        let mut assessment_failures = 0;
-        
-        // Primary assessment from first successful rule (for position sizing baseline)
-        let mut primary_assessment: Option<RiskAssessment> = None;
-        
-        // Execute each risk rule
-        for rule in &self.risk_rules {
-            let rule_start = std::time::Instant::now();
-            let rule_name = rule.rule_name().to_string();
-            
-            debug!("Executing risk rule: {}", rule_name);
-            
-            let assessment_result = rule.assess(proposal);
-            let execution_time = rule_start.elapsed().as_millis() as u64;
-            
-            match &assessment_result {
-                Ok(assessment) => {
-                    // Use the first successful assessment as the primary one for position sizing
-                    if primary_assessment.is_none() {
-                        primary_assessment = Some(assessment.clone());
-                    }
-                    
-                    // Collect violations from this rule
-                    for violation in &assessment.violations {
-                        match violation.severity {
-                            ViolationSeverity::Critical => critical_violations += 1,
-                            ViolationSeverity::Warning => warnings += 1,
-                            ViolationSeverity::High => warnings += 1,
-                            ViolationSeverity::Blocking => critical_violations += 1,
-                        }
-                        consolidated_violations.push(violation.clone());
+        Ok(AssessmentTally::new())
+    }
+    
+    /// Fold one rule's outcome into the tally
+    fn record_rule_result(
+        &self,
+        tally: &mut AssessmentTally,
+        rule_name: &str,
+        class: RuleClass,
+        assessment_result: Result<RiskAssessment, AssessmentError>,
+        rule_start: std::time::Instant,
+    ) -> Result<(), ProtocolError> {
+        let execution_time = rule_start.elapsed().as_millis() as u64;
+        let rule_name = rule_name.to_string();
+        
+        match &assessment_result {
+            Ok(assessment) => {
+                // Use the first successful assessment as the primary one for position sizing
+                if tally.primary_assessment.is_none() {
+                    tally.primary_assessment = Some(assessment.clone());
+                }
+                
+                // Collect violations from this rule
+                let mut rule_critical = 0;
+                for violation in &assessment.violations {
+                    match violation.severity {
+                        ViolationSeverity::Critical => rule_critical += 1,
+                        ViolationSeverity::Warning => tally.warnings += 1,
+                        ViolationSeverity::High => tally.warnings += 1,
+                        ViolationSeverity::Blocking => rule_critical += 1,
                     }
-                    
-                    debug!(
-                        "Rule '{}' completed in {}ms - violations: {}", 
-                        rule_name, 
-                        execution_time,
-                        assessment.violations.len()
-                    );
+                    tally.consolidated_violations.push(violation.clone());
                 }
-                Err(error) => {
-                    assessment_failures += 1;
+                
+                if rule_critical > 0 {
+                    self.record_rule_outcome(&rule_name, |counts| counts.rejected += 1);
+                    tally.rejecting_rules.push(rule_name.clone());
                     warn!(
-                        "Risk rule '{}' failed: {} (execution time: {}ms)",
+                        rule = %rule_name,
+                        outcome = "rejected",
+                        "Risk rule '{}' rejected the trade with {} critical violation(s)",
                         rule_name,
-                        error,
-                        execution_time
+                        rule_critical
                     );
-                    
-                    // If fail_fast is enabled and this is a critical failure, abort
-                    if self.fail_fast {
-                        return Err(ProtocolError::RuleAssessmentFailure {
-                            rule_name: rule_name.clone(),
-                            reason: error.to_string(),
-                        });
+                } else {
+                    self.record_rule_outcome(&rule_name, |counts| counts.passed += 1);
+                }
+                
+                match class {
+                    RuleClass::Hard => {
+                        tally.critical_violations += rule_critical;
+                        if self.fail_on_critical && rule_critical > 0 {
+                            tally.short_circuited_by = Some(rule_name.clone());
+                        }
+                    }
+                    RuleClass::Advisory => {
+                        tally.advisory_rules += 1;
+                        tally.advisory_critical += rule_critical;
+                        if rule_critical > 0 {
+                            tally.advisory_objections += 1;
+                        }
                     }
                 }
+                
+                debug!(
+                    "Rule '{}' completed in {}ms - violations: {}", 
+                    rule_name, 
+                    execution_time,
+                    assessment.violations.len()
+                );
             }
-            
-            // Store the individual rule result
-            rule_results.push(RuleAssessmentResult {
-                rule_name,
-                assessment: assessment_result,
-                execution_time_ms: execution_time,
-            });
+            Err(error) => {
+                tally.assessment_failures += 1;
+                if class == RuleClass::Advisory {
+                    tally.advisory_rules += 1;
+                }
+                self.record_rule_outcome(&rule_name, |counts| counts.failed += 1);
+                tally.failed_rules.push(RuleFailure {
+                    rule_name: rule_name.clone(),
+                    reason: error.to_string(),
+                });
+                error!(
+                    rule = %rule_name,
+                    outcome = "failed",
+                    "Risk rule '{}' failed to run: {} (execution time: {}ms)",
+                    rule_name,
+                    error,
+                    execution_time
+                );
+                
+                // If fail_fast is enabled and this is a critical failure, abort
+                if self.fail_fast {
+                    return Err(ProtocolError::RuleAssessmentFailure {
+                        rule_name: rule_name.clone(),
+                        reason: error.to_string(),
+                    });
+                }
+            }
+        }
+        
+        // Store the individual rule result
+        tally.rule_results.push(RuleAssessmentResult {
+            rule_name,
+            outcome: assessment_result.into(),
+            execution_time_ms: execution_time,
+        });
+        Ok(())
+    }
+    
+    /// Turn a completed tally into the protocol decision
+    fn finish_assessment(&self, proposal: &TradeProposal, tally: AssessmentTally) -> Result<ProtocolAssessmentResult, ProtocolError> {
+        let AssessmentTally {
+            start_time,
+            rule_results,
+            consolidated_violations,
+            mut critical_violations,
+            mut warnings,
+            assessment_failures,
+            advisory_rules,
+            advisory_objections,
+            advisory_critical,
+            failed_rules,
+            rejecting_rules,
+            primary_assessment,
+            short_circuited_by: _,
+        } = tally;
+        
+        // Aggregate advisory objections; hard-limit violations are already counted
+        let advisory_rejects = match self.advisory_policy {
+            AdvisoryPolicy::AnyReject => advisory_objections > 0,
+            AdvisoryPolicy::Majority => advisory_objections * 2 > advisory_rules,
+        };
+        if advisory_rejects {
+            critical_violations += advisory_critical;
+        } else if advisory_critical > 0 {
+            debug!(
+                "{} of {} advisory rule(s) objected - below {:?} threshold, treating as warnings",
+                advisory_objections, advisory_rules, self.advisory_policy
+            );
+            warnings += advisory_critical;
         }
         
         // Determine protocol decision
@@ -268,6 +645,8 @@ impl RiskManagementProtocol {
             rule_results,
             protocol_decision,
             decision_reasoning,
+            failed_rules,
+            rejecting_rules,
         })
     }
     
@@ -352,12 +731,18 @@ impl RiskManagementProtocol {
                     c
                 )
             }
-            (_, _, f) if f > 0 => {
+            (0, _, f) if f > 0 => {
                 format!(
                     "Trade assessment failed: {} risk rule(s) failed to execute. Manual review required.",
                     f
                 )
             }
+            (c, _, f) if f > 0 => {
+                format!(
+                    "Trade assessment failed: {} risk rule(s) failed to execute and {} critical violation(s) were raised by rules that ran. Manual review required.",
+                    f, c
+                )
+            }
             _ => {
                 "Trade assessment completed with mixed results. Review individual rule results for details.".to_string()
             }
@@ -397,6 +782,29 @@ fn convert_limit_violation(violation: ProtocolLimitViolation) -> ProtocolViolati
                 format!("Consider increasing position size to at least {}%", limit * Decimal::from(100)),
             )
         },
+        PLV::ExceedsMaxTradeLossAmount { current, limit } => {
+            ProtocolViolation::new(
+                "MaxTradeLossAmount".to_string(),
+                ViolationSeverity::Blocking,
+                format!("Potential trade loss {} exceeds maximum of {} per trade", current, limit),
+                current,
+                limit,
+                format!("Reduce position size so at most {} is lost at the stop", limit),
+            )
+        },
+        PLV::BelowMinPositionNotional { current, limit } => {
+            ProtocolViolation::new(
+                "MinPositionNotional".to_string(),
+                ViolationSeverity::Critical,
+                format!("Position notional {} is below the minimum of {}", current, limit),
+                current,
+                limit,
+                format!(
+                    "Skip this trade, or use a tighter stop or more risk so the position is worth at least {}",
+                    limit
+                ),
+            )
+        },
         PLV::ExceedsMaxPortfolioRisk { current, limit } => {
             ProtocolViolation::new(
                 "MaxPortfolioRisk".to_string(),
@@ -491,10 +899,25 @@ impl ProtocolAssessmentResult {
     /// Get the number of rules that failed to execute
     pub fn failed_rule_count(&self) -> usize {
         self.rule_results.iter()
-            .filter(|r| r.assessment.is_err())
+            .filter(|r| r.failed())
+            .count()
+    }
+    
+    /// Number of rules that ran, successfully or not; skipped rules are left out
+    pub fn executed_rule_count(&self) -> usize {
+        self.rule_results.iter()
+            .filter(|r| !r.was_skipped())
             .count()
     }
     
+    /// Names of the rules skipped after an earlier rule rejected the trade
+    pub fn skipped_rules(&self) -> Vec<&str> {
+        self.rule_results.iter()
+            .filter(|r| r.was_skipped())
+            .map(|r| r.rule_name.as_str())
+            .collect()
+    }
+    
     /// Get total assessment execution time
     pub fn total_execution_time_ms(&self) -> u64 {
         self.rule_results.iter()
@@ -534,6 +957,116 @@ pub struct TestudoProtocol {
     circuit_breaker_active: bool,
     /// Timestamp when circuit breaker was activated
     circuit_breaker_activated_at: Option<SystemTime>,
+    /// Trial state after the cooldown, when `circuit_breaker_trial_risk` is set
+    half_open: Option<HalfOpenTrial>,
+    /// Daily loss cool-down alert tracking
+    daily_loss_monitor: DailyLossMonitor,
+    /// Per-symbol circuit breakers (used with `CircuitBreakerScope::PerSymbol`)
+    symbol_breakers: HashMap<String, SymbolCircuitBreaker>,
+    /// Positions tracked through their lifecycle, keyed by proposal id
+    tracked_positions: HashMap<Uuid, TrackedPosition>,
+    /// Risk reserved for cycles that have not executed yet, keyed by proposal id
+    reservations: HashMap<Uuid, Reservation>,
+}
+
+/// Portfolio risk set aside for a trade between its check and its execution
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Reservation {
+    pub position_id: Uuid,
+    pub risk: Decimal,
+    pub reserved_at: SystemTime,
+}
+
+/// Why a risk reservation could not be made
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum ReservationError {
+    #[error("Insufficient risk budget: requested {requested}, available {available}")]
+    InsufficientBudget { requested: Decimal, available: Decimal },
+    
+    #[error("Risk already reserved for position {position_id}")]
+    AlreadyReserved { position_id: Uuid },
+}
+
+/// Lifecycle state of a tracked position
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PositionState {
+    /// Entry submitted but not yet filled
+    Pending,
+    /// Entry filled
+    Open,
+    /// Exit submitted but not yet filled
+    Closing,
+    /// Position fully exited
+    Closed,
+}
+
+/// A position whose risk is tracked by the protocol
+#[derive(Debug, Clone)]
+pub struct TrackedPosition {
+    pub id: Uuid,
+    pub symbol: String,
+    pub risk: Decimal,
+    pub state: PositionState,
+    pub submitted_at: SystemTime,
+    /// When the entry filled; `None` while pending
+    pub opened_at: Option<SystemTime>,
+}
+
+/// Why a position is being exited
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitReason {
+    /// Discretionary close requested by the trader
+    Manual,
+    /// Take-profit target reached
+    TakeProfit,
+    /// Stop-loss triggered; always permitted
+    StopLoss,
+}
+
+/// Why an exit could not be started
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum ExitError {
+    #[error("Position {position_id} is not open")]
+    NotOpen { position_id: Uuid },
+    
+    #[error("Position {position_id} held {held_secs}s, minimum hold time is {min_hold_secs}s")]
+    MinimumHoldNotElapsed { position_id: Uuid, held_secs: u64, min_hold_secs: u64 },
+}
+
+/// A closed trade applied by [`TestudoProtocol::simulate_outcomes`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    /// Winning trade that released `trade_risk` of portfolio risk
+    Win { symbol: String, trade_risk: Decimal },
+    /// Losing trade that released `trade_risk` and lost `loss_amount`
+    Loss { symbol: String, trade_risk: Decimal, loss_amount: Decimal },
+}
+
+impl Outcome {
+    pub fn win(symbol: &str, trade_risk: Decimal) -> Self {
+        Outcome::Win { symbol: symbol.to_string(), trade_risk }
+    }
+    
+    pub fn loss(symbol: &str, trade_risk: Decimal, loss_amount: Decimal) -> Self {
+        Outcome::Loss { symbol: symbol.to_string(), trade_risk, loss_amount }
+    }
+}
+
+/// Progress of the single trial trade a half-open breaker allows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HalfOpenTrial {
+    /// Waiting for the trial trade to be placed
+    Awaiting,
+    /// Trial trade reserved or placed under this position id; further
+    /// trades wait for its outcome
+    InFlight(Uuid),
+}
+
+/// Consecutive-loss state for a single symbol
+#[derive(Debug, Clone, Default)]
+struct SymbolCircuitBreaker {
+    consecutive_losses: u32,
+    activated_at: Option<SystemTime>,
 }
 
 impl TestudoProtocol {
@@ -544,6 +1077,8 @@ impl TestudoProtocol {
     
     /// Create a new Testudo Protocol enforcer with custom limits
     pub fn with_limits(limits: ProtocolLimits) -> Self {
+        let daily_loss_monitor = DailyLossMonitor::new(limits.daily_loss_warning_threshold);
+        
         Self {
             limits,
             portfolio_exposure: HashMap::new(),
@@ -555,6 +1090,11 @@ impl TestudoProtocol {
             open_positions: 0,
             circuit_breaker_active: false,
             circuit_breaker_activated_at: None,
+            half_open: None,
+            daily_loss_monitor,
+            symbol_breakers: HashMap::new(),
+            tracked_positions: HashMap::new(),
+            reservations: HashMap::new(),
         }
     }
     
@@ -574,74 +1114,235 @@ impl TestudoProtocol {
         
         // Reset daily tracking if needed
         self.reset_daily_tracking_if_needed();
+        self.expire_stale_reservations();
         
         // Check if circuit breaker should be reset
         self.check_circuit_breaker_reset();
         
-        // 1. Check circuit breaker status
-        if self.circuit_breaker_active {
+        // 1. Check circuit breaker status (account-wide or for this symbol)
+        let (breaker_active, consecutive_losses) = self.circuit_breaker_for(&proposal.symbol);
+        if breaker_active {
             violations.push(ProtocolViolation::new(
                 "ExceedsMaxConsecutiveLosses".to_string(),
                 ViolationSeverity::Critical,
-                format!("Consecutive losses {} exceeds limit {}", self.consecutive_losses, self.limits.max_consecutive_losses),
-                Decimal::from(self.consecutive_losses),
+                format!("Consecutive losses {} exceeds limit {}", consecutive_losses, self.limits.max_consecutive_losses),
+                Decimal::from(consecutive_losses),
                 Decimal::from(self.limits.max_consecutive_losses),
                 "Wait for winning trade to reset consecutive loss counter".to_string(),
             ));
         }
         
+        // 1b. A half-open breaker allows one trial trade, capped in size
+        match (self.half_open, self.limits.circuit_breaker_trial_risk) {
+            (Some(HalfOpenTrial::InFlight(_)), _) => {
+                violations.push(ProtocolViolation::new(
+                    "CircuitBreakerTrialInFlight".to_string(),
+                    ViolationSeverity::Critical,
+                    "Circuit breaker is half-open and its trial trade is still open".to_string(),
+                    Decimal::ONE,
+                    Decimal::ONE,
+                    "Wait for the trial trade to close before trading again".to_string(),
+                ));
+            }
+            (Some(HalfOpenTrial::Awaiting), Some(trial_risk)) if proposal.risk_percentage.value() > trial_risk => {
+                violations.push(ProtocolViolation::new(
+                    "ExceedsCircuitBreakerTrialRisk".to_string(),
+                    ViolationSeverity::Critical,
+                    format!(
+                        "Trial trade risk {}% exceeds half-open cap {}%",
+                        proposal.risk_percentage.value() * Decimal::from(100),
+                        trial_risk * Decimal::from(100)
+                    ),
+                    proposal.risk_percentage.value(),
+                    trial_risk,
+                    "Reduce position size for the trial trade after the circuit breaker cooldown".to_string(),
+                ));
+            }
+            _ => {}
+        }
+        
         // 2. Validate individual trade risk
         if let Err(violation) = self.limits.validate_individual_trade_risk(proposal.risk_percentage.value()) {
             violations.push(convert_limit_violation(violation));
         }
+        let potential_loss = proposal.risk_percentage.value() * proposal.account_equity.value();
+        if let Err(violation) = self.limits.validate_trade_loss_amount(potential_loss) {
+            violations.push(convert_limit_violation(violation));
+        }
+        let risk_distance = proposal.risk_distance();
+        if risk_distance > Decimal::ZERO {
+            let notional = potential_loss / risk_distance * proposal.entry_price.value();
+            if let Err(violation) = self.limits.validate_position_notional(notional) {
+                violations.push(convert_limit_violation(violation));
+            }
+        }
         
-        // 3. Calculate potential new portfolio risk
+        // 3. Calculate potential new portfolio risk, including risk reserved
+        // by other in-flight cycles
         let trade_risk = proposal.risk_percentage.value();
-        let potential_portfolio_risk = self.total_portfolio_risk + trade_risk;
-        
-        if let Err(violation) = self.limits.validate_portfolio_risk(potential_portfolio_risk) {
-            violations.push(convert_limit_violation(violation));
+        let reserved_by_others: Decimal = self.reservations
+            .values()
+            .filter(|r| r.position_id != proposal.id)
+            .map(|r| r.risk)
+            .sum();
+        let potential_portfolio_risk = self.total_portfolio_risk + reserved_by_others + trade_risk;
+        let max_portfolio_risk = self.limits.effective_max_portfolio_risk(proposal.account_equity.value());
+        
+        if potential_portfolio_risk > max_portfolio_risk {
+            violations.push(convert_limit_violation(ProtocolLimitViolation::ExceedsMaxPortfolioRisk {
+                current: potential_portfolio_risk,
+                limit: max_portfolio_risk,
+            }));
         }
         
         // 4. Check consecutive losses
-        if let Err(violation) = self.limits.validate_consecutive_losses(self.consecutive_losses) {
+        if let Err(violation) = self.limits.validate_consecutive_losses(consecutive_losses) {
             violations.push(convert_limit_violation(violation));
         }
         
-        // 5. Check open positions limit
-        if self.open_positions >= self.limits.max_open_positions {
-            violations.push(ProtocolViolation::new(
-                "ExceedsMaxOpenPositions".to_string(),
-                ViolationSeverity::High,
-                format!("Open positions {} exceeds recommended limit {}", self.open_positions, self.limits.max_open_positions),
-                Decimal::from(self.open_positions),
-                Decimal::from(self.limits.max_open_positions),
-                "Consider closing some positions before opening new ones".to_string(),
-            ));
+        // 5. Check open positions limit (hard cap in strict mode)
+        // Pending entries count once they outlast the grace period, unless
+        // they have a cap of their own
+        let open_positions = self.counted_open_positions();
+        if open_positions >= self.limits.max_open_positions {
+            let violation = if self.limits.strict_max_open_positions {
+                ProtocolViolation::new(
+                    "ExceedsMaxOpenPositions".to_string(),
+                    ViolationSeverity::Blocking,
+                    format!("Open positions {} at hard cap {}", open_positions, self.limits.max_open_positions),
+                    Decimal::from(open_positions),
+                    Decimal::from(self.limits.max_open_positions),
+                    "Close an existing position before opening a new one".to_string(),
+                )
+            } else {
+                ProtocolViolation::new(
+                    "ExceedsMaxOpenPositions".to_string(),
+                    ViolationSeverity::High,
+                    format!("Open positions {} exceeds recommended limit {}", open_positions, self.limits.max_open_positions),
+                    Decimal::from(open_positions),
+                    Decimal::from(self.limits.max_open_positions),
+                    "Consider closing some positions before opening new ones".to_string(),
+                )
+            };
+            violations.push(violation);
+        }
+        
+        // 5b. Check the separate cap on unfilled entries
+        if let Some(max_pending_orders) = self.limits.max_pending_orders {
+            let pending_positions = self.pending_positions();
+            if pending_positions >= max_pending_orders {
+                violations.push(ProtocolViolation::new(
+                    "ExceedsMaxPendingOrders".to_string(),
+                    ViolationSeverity::Blocking,
+                    format!("Pending orders {} at cap {}", pending_positions, max_pending_orders),
+                    Decimal::from(pending_positions),
+                    Decimal::from(max_pending_orders),
+                    "Wait for pending entries to fill or cancel some before placing another".to_string(),
+                ));
+            }
         }
         
-        // 6. Check reward/risk ratio if take profit is set
+        // 6. Check reward/risk ratio if take profit is set, else apply the missing take profit policy
         if let Some(ratio) = proposal.risk_reward_ratio() {
             if let Err(violation) = self.limits.validate_reward_risk_ratio(ratio) {
                 violations.push(convert_limit_violation(violation));
             }
+        } else if let Some(severity) = self.limits.missing_take_profit_severity() {
+            violations.push(ProtocolViolation::new(
+                "MissingTakeProfit".to_string(),
+                severity,
+                "Trade has no take profit, so reward-to-risk cannot be checked".to_string(),
+                Decimal::ZERO,
+                self.limits.min_reward_risk_ratio,
+                "Set a take profit target or relax the missing take profit policy".to_string(),
+            ));
         }
         
         // 7. Check daily loss limit
         let potential_daily_loss = self.daily_loss + trade_risk * proposal.account_equity.value();
         let daily_loss_percentage = potential_daily_loss / proposal.account_equity.value();
         
-        if daily_loss_percentage > self.limits.max_daily_loss {
+        let max_daily_loss = self.limits.effective_max_daily_loss(proposal.account_equity.value());
+        
+        if daily_loss_percentage > max_daily_loss {
             violations.push(ProtocolViolation::new(
                 "ExceedsMaxDailyLoss".to_string(),
                 ViolationSeverity::Critical,
-                format!("Daily loss {}% exceeds limit {}%", daily_loss_percentage * Decimal::from(100), self.limits.max_daily_loss * Decimal::from(100)),
+                format!("Daily loss {}% exceeds limit {}%", daily_loss_percentage * Decimal::from(100), max_daily_loss * Decimal::from(100)),
                 daily_loss_percentage,
-                self.limits.max_daily_loss,
+                max_daily_loss,
                 "Stop trading for the day to prevent further losses".to_string(),
             ));
         }
         
+        // 8. Check realized daily loss plus open and reserved risk against the combined budget
+        if let Some(max_combined) = self.limits.max_daily_loss_with_open_risk {
+            let combined_exposure = daily_loss_percentage + self.total_portfolio_risk + reserved_by_others;
+            
+            if combined_exposure > max_combined {
+                violations.push(ProtocolViolation::new(
+                    "ExceedsMaxDailyLossWithOpenRisk".to_string(),
+                    ViolationSeverity::Critical,
+                    format!(
+                        "Daily loss plus open risk {}% exceeds limit {}%",
+                        combined_exposure * Decimal::from(100),
+                        max_combined * Decimal::from(100)
+                    ),
+                    combined_exposure,
+                    max_combined,
+                    "Close or tighten open positions before adding new risk today".to_string(),
+                ));
+            }
+        }
+        
+        // 9. Check trade risk against its share of the remaining daily budget
+        if let Some(max_share) = self.limits.max_trade_share_of_remaining_daily_budget {
+            let account_equity = proposal.account_equity.value();
+            let remaining_budget = self.remaining_daily_budget(account_equity);
+            let trade_risk_amount = trade_risk * account_equity;
+            let allowed_risk_amount = remaining_budget * max_share;
+            
+            if trade_risk_amount > allowed_risk_amount {
+                violations.push(ProtocolViolation::new(
+                    "ExceedsRemainingDailyBudgetShare".to_string(),
+                    ViolationSeverity::High,
+                    format!(
+                        "Trade risk ${:.2} exceeds {}% of remaining daily budget ${:.2}",
+                        trade_risk_amount,
+                        max_share * Decimal::from(100),
+                        remaining_budget
+                    ),
+                    trade_risk_amount,
+                    allowed_risk_amount,
+                    "Reduce position size to spread the remaining daily budget across trades".to_string(),
+                ));
+            }
+        }
+        
+        // 10. Stress test: every open position and this trade stopping out at
+        // once, each filling beyond its stop, on top of today's realized loss
+        if let Some(stress_test) = self.limits.stress_test {
+            let stressed_risk = (self.total_portfolio_risk + reserved_by_others + trade_risk)
+                * (Decimal::ONE + stress_test.stop_overshoot);
+            let stress_loss = daily_loss_percentage - trade_risk + stressed_risk;
+            
+            if stress_loss > stress_test.max_loss {
+                violations.push(ProtocolViolation::new(
+                    "ExceedsStressLossLimit".to_string(),
+                    ViolationSeverity::Critical,
+                    format!(
+                        "Simultaneous stop-out with {}% overshoot would lose {}%, exceeding stress limit {}%",
+                        stress_test.stop_overshoot * Decimal::from(100),
+                        stress_loss * Decimal::from(100),
+                        stress_test.max_loss * Decimal::from(100)
+                    ),
+                    stress_loss,
+                    stress_test.max_loss,
+                    "Reduce position size or close positions to lower tail risk".to_string(),
+                ));
+            }
+        }
+        
         if violations.is_empty() {
             info!("Trade proposal {} passed Testudo Protocol validation", proposal.id);
             Ok(())
@@ -659,19 +1360,8 @@ impl TestudoProtocol {
     pub fn record_trade_execution(&mut self, proposal: &TradeProposal) {
         let trade_risk = proposal.risk_percentage.value();
         
-        // Add to portfolio exposure
-        let current_exposure = self.portfolio_exposure
-            .get(&proposal.symbol)
-            .copied()
-            .unwrap_or(Decimal::ZERO);
-        
-        self.portfolio_exposure.insert(
-            proposal.symbol.clone(),
-            current_exposure + trade_risk,
-        );
-        
-        // Update total portfolio risk
-        self.total_portfolio_risk += trade_risk;
+        self.add_exposure(&proposal.symbol, trade_risk);
+        self.start_trial_if_half_open(proposal.id);
         
         // Increment open positions
         self.open_positions += 1;
@@ -685,49 +1375,458 @@ impl TestudoProtocol {
         );
     }
     
-    /// Record a trade outcome (win or loss)
-    pub fn record_trade_outcome(&mut self, symbol: &str, trade_risk: Decimal, was_loss: bool, loss_amount: Option<Decimal>) {
-        // Remove from portfolio exposure
-        if let Some(current_exposure) = self.portfolio_exposure.get_mut(symbol) {
-            *current_exposure = (*current_exposure - trade_risk).max(Decimal::ZERO);
-            if current_exposure.is_zero() {
-                self.portfolio_exposure.remove(symbol);
-            }
+    /// Reserve portfolio risk for a trade before it executes
+    ///
+    /// The check and the reservation happen under the same `&mut self`, so
+    /// two cycles sharing a protocol cannot both claim the last of the budget.
+    /// Reserved risk counts against the portfolio limit until the reservation
+    /// is committed on execution, released on abort, or outlives the
+    /// pending-entry grace period. The budget is the portfolio limit in force
+    /// at `account_equity`, so an absolute cap is honoured here as it is when
+    /// the trade is validated.
+    pub fn reserve_risk(
+        &mut self,
+        position_id: Uuid,
+        risk: Decimal,
+        account_equity: Decimal,
+    ) -> Result<Reservation, ReservationError> {
+        self.expire_stale_reservations();
+        if self.reservations.contains_key(&position_id) {
+            return Err(ReservationError::AlreadyReserved { position_id });
         }
         
-        // Update total portfolio risk
-        self.total_portfolio_risk = (self.total_portfolio_risk - trade_risk).max(Decimal::ZERO);
-        
-        // Decrement open positions
-        self.open_positions = self.open_positions.saturating_sub(1);
-        
-        // Handle consecutive loss tracking
-        if was_loss {
-            self.consecutive_losses += 1;
-            self.last_loss_time = Some(SystemTime::now());
-            
-            // Add to daily loss if amount is provided
-            if let Some(loss) = loss_amount {
-                self.daily_loss += loss;
-            }
-            
-            // Activate circuit breaker if limit reached
-            if self.consecutive_losses >= self.limits.max_consecutive_losses {
-                self.activate_circuit_breaker();
-            }
-            
+        let available = self.remaining_risk_budget(account_equity);
+        if risk > available {
             warn!(
-                "Recorded loss for {}: consecutive_losses={}, daily_loss=${:.2}",
-                symbol, self.consecutive_losses, self.daily_loss
-            );
-        } else {
-            // Reset consecutive losses on win
-            self.consecutive_losses = 0;
-            self.last_loss_time = None;
-            
-            info!(
-                "Recorded win for {}: consecutive losses reset, daily_loss=${:.2}",
-                symbol, self.daily_loss
+                "Risk reservation for {} refused: requested {:.2}%, available {:.2}%",
+                position_id,
+                risk * Decimal::from(100),
+                available * Decimal::from(100)
+            );
+            return Err(ReservationError::InsufficientBudget { requested: risk, available });
+        }
+        
+        let reservation = Reservation {
+            position_id,
+            risk,
+            reserved_at: SystemTime::now(),
+        };
+        self.reservations.insert(position_id, reservation);
+        self.start_trial_if_half_open(position_id);
+        debug!("Reserved {:.2}% risk for {}", risk * Decimal::from(100), position_id);
+        Ok(reservation)
+    }
+    
+    /// Turn a reservation into open exposure once its trade has executed
+    ///
+    /// The position is tracked as open under `position_id`, so it can later
+    /// be closed with [`Self::close_tracked_position`]. Returns false if no
+    /// reservation exists for the position.
+    pub fn commit_reservation(&mut self, position_id: Uuid, symbol: &str) -> bool {
+        let Some(reservation) = self.reservations.remove(&position_id) else {
+            return false;
+        };
+        
+        self.add_exposure(symbol, reservation.risk);
+        self.start_trial_if_half_open(position_id);
+        self.open_positions += 1;
+        self.tracked_positions.insert(position_id, TrackedPosition {
+            id: position_id,
+            symbol: symbol.to_string(),
+            risk: reservation.risk,
+            state: PositionState::Open,
+            submitted_at: reservation.reserved_at,
+            opened_at: Some(SystemTime::now()),
+        });
+        info!(
+            "Committed {:.2}% reserved risk for {} on {}: total_portfolio_risk={:.2}%",
+            reservation.risk * Decimal::from(100),
+            position_id,
+            symbol,
+            self.total_portfolio_risk * Decimal::from(100)
+        );
+        true
+    }
+    
+    /// Return reserved risk to the budget when its cycle aborts
+    pub fn release_reservation(&mut self, position_id: Uuid) -> Option<Reservation> {
+        let released = self.reservations.remove(&position_id);
+        if released.is_some() {
+            self.cancel_trial(position_id);
+            debug!("Released risk reservation for {}", position_id);
+        }
+        released
+    }
+    
+    /// Total risk reserved but not yet executed
+    pub fn reserved_risk(&self) -> Decimal {
+        self.reservations
+            .values()
+            .filter(|r| !self.reservation_expired(r))
+            .map(|r| r.risk)
+            .sum()
+    }
+    
+    /// Whether a reservation has outlived the pending-entry grace period
+    fn reservation_expired(&self, reservation: &Reservation) -> bool {
+        let grace_period = Duration::from_secs(self.limits.pending_entry_grace_period_secs);
+        SystemTime::now().duration_since(reservation.reserved_at).unwrap_or_default() > grace_period
+    }
+    
+    /// Drop reservations whose cycle never committed or released them
+    ///
+    /// A cycle that dies between reserving and executing would otherwise hold
+    /// its share of the budget for good.
+    fn expire_stale_reservations(&mut self) {
+        let expired: Vec<Uuid> = self.reservations
+            .values()
+            .filter(|r| self.reservation_expired(r))
+            .map(|r| r.position_id)
+            .collect();
+        for position_id in expired {
+            self.reservations.remove(&position_id);
+            self.cancel_trial(position_id);
+            warn!("Risk reservation for {} expired before it was committed", position_id);
+        }
+    }
+    
+    /// Record an entry that has been submitted but not yet filled
+    ///
+    /// The entry's risk counts toward the portfolio immediately, but it only
+    /// counts as an open position once filled (or once it has been pending
+    /// longer than the configured grace period).
+    pub fn record_pending_entry(&mut self, proposal: &TradeProposal) {
+        let trade_risk = proposal.risk_percentage.value();
+        self.add_exposure(&proposal.symbol, trade_risk);
+        self.start_trial_if_half_open(proposal.id);
+        
+        self.tracked_positions.insert(proposal.id, TrackedPosition {
+            id: proposal.id,
+            symbol: proposal.symbol.clone(),
+            risk: trade_risk,
+            state: PositionState::Pending,
+            submitted_at: SystemTime::now(),
+            opened_at: None,
+        });
+        
+        info!(
+            "Recorded pending entry for {}: risk={:.2}%, total_portfolio_risk={:.2}%",
+            proposal.symbol,
+            trade_risk * Decimal::from(100),
+            self.total_portfolio_risk * Decimal::from(100)
+        );
+    }
+    
+    /// Track a position opened outside the protocol, e.g. imported or manual
+    ///
+    /// The position is open at once and carries `risk` (a fraction of account
+    /// equity, usually measured from the current price to its stop).
+    pub fn track_external_position(&mut self, position_id: Uuid, symbol: &str, risk: Decimal) {
+        self.add_exposure(symbol, risk);
+        self.open_positions += 1;
+        self.tracked_positions.insert(position_id, TrackedPosition {
+            id: position_id,
+            symbol: symbol.to_string(),
+            risk,
+            state: PositionState::Open,
+            submitted_at: SystemTime::now(),
+            opened_at: Some(SystemTime::now()),
+        });
+        
+        info!(
+            "Tracking external position in {}: risk={:.2}%, total_portfolio_risk={:.2}%",
+            symbol,
+            risk * Decimal::from(100),
+            self.total_portfolio_risk * Decimal::from(100)
+        );
+    }
+    
+    /// Mark a pending entry as filled, moving it to `Open`
+    ///
+    /// Returns false if the position is unknown or not pending.
+    pub fn mark_entry_filled(&mut self, position_id: Uuid) -> bool {
+        match self.tracked_positions.get_mut(&position_id) {
+            Some(position) if position.state == PositionState::Pending => {
+                position.state = PositionState::Open;
+                position.opened_at = Some(SystemTime::now());
+                self.open_positions += 1;
+                info!("Entry filled for {}: open_positions={}", position.symbol, self.open_positions);
+                true
+            }
+            _ => false,
+        }
+    }
+    
+    /// Mark an open position as closing once its exit has been submitted
+    ///
+    /// Held to the same minimum hold time as [`Self::request_exit`].
+    pub fn mark_position_closing(&mut self, position_id: Uuid, reason: ExitReason) -> Result<(), ExitError> {
+        self.request_exit(position_id, reason)
+    }
+    
+    /// Start exiting an open position, enforcing the minimum hold time
+    ///
+    /// Manual and take-profit exits are refused until the position has been
+    /// open for `min_hold_time_secs`; stop-loss exits always proceed. On
+    /// success the position is marked closing.
+    pub fn request_exit(&mut self, position_id: Uuid, reason: ExitReason) -> Result<(), ExitError> {
+        match self.tracked_positions.get(&position_id) {
+            Some(position) if position.state == PositionState::Open => {
+                self.check_min_hold(position, reason)?;
+            }
+            _ => return Err(ExitError::NotOpen { position_id }),
+        }
+        
+        if let Some(position) = self.tracked_positions.get_mut(&position_id) {
+            position.state = PositionState::Closing;
+        }
+        Ok(())
+    }
+    
+    /// Refuse a discretionary exit of a position held less than the minimum
+    fn check_min_hold(&self, position: &TrackedPosition, reason: ExitReason) -> Result<(), ExitError> {
+        if reason == ExitReason::StopLoss {
+            return Ok(());
+        }
+        
+        let min_hold = Duration::from_secs(self.limits.min_hold_time_secs);
+        let held = position.opened_at
+            .and_then(|opened_at| SystemTime::now().duration_since(opened_at).ok())
+            .unwrap_or_default();
+        if held < min_hold {
+            warn!(
+                "Refusing {:?} exit of {} after {}s: minimum hold is {}s",
+                reason, position.symbol, held.as_secs(), min_hold.as_secs()
+            );
+            return Err(ExitError::MinimumHoldNotElapsed {
+                position_id: position.id,
+                held_secs: held.as_secs(),
+                min_hold_secs: min_hold.as_secs(),
+            });
+        }
+        Ok(())
+    }
+    
+    /// Close a tracked position and stop tracking it
+    ///
+    /// Closing a pending entry (e.g. a cancelled order) only releases its risk;
+    /// closing an open or closing position records the trade outcome. An open
+    /// position is held to the minimum hold time unless `reason` is a stop.
+    pub fn close_tracked_position(
+        &mut self,
+        position_id: Uuid,
+        reason: ExitReason,
+        was_loss: bool,
+        loss_amount: Option<Decimal>,
+    ) -> Result<TrackedPosition, ExitError> {
+        let Some(tracked) = self.tracked_positions.get(&position_id) else {
+            return Err(ExitError::NotOpen { position_id });
+        };
+        if tracked.state == PositionState::Open {
+            self.check_min_hold(tracked, reason)?;
+        }
+        let mut position = self.tracked_positions
+            .remove(&position_id)
+            .ok_or(ExitError::NotOpen { position_id })?;
+        
+        match position.state {
+            PositionState::Pending => {
+                self.remove_exposure(&position.symbol, position.risk);
+                self.cancel_trial(position_id);
+            }
+            PositionState::Open | PositionState::Closing => {
+                self.apply_trade_outcome(Some(position_id), &position.symbol, position.risk, was_loss, loss_amount);
+            }
+            PositionState::Closed => {}
+        }
+        
+        position.state = PositionState::Closed;
+        Ok(position)
+    }
+    
+    /// A tracked position by id
+    pub fn tracked_position(&self, position_id: Uuid) -> Option<&TrackedPosition> {
+        self.tracked_positions.get(&position_id)
+    }
+    
+    /// Positions counted against `max_open_positions`
+    ///
+    /// Filled positions always count; pending entries count once they have
+    /// been waiting longer than the grace period, unless `max_pending_orders`
+    /// caps them separately.
+    fn counted_open_positions(&self) -> u32 {
+        if self.limits.max_pending_orders.is_some() {
+            return self.open_positions;
+        }
+        
+        let grace_period = Duration::from_secs(self.limits.pending_entry_grace_period_secs);
+        let stale_pending = self.tracked_positions
+            .values()
+            .filter(|p| p.state == PositionState::Pending)
+            .filter(|p| SystemTime::now().duration_since(p.submitted_at).unwrap_or_default() > grace_period)
+            .count() as u32;
+        
+        self.open_positions + stale_pending
+    }
+    
+    fn pending_positions(&self) -> u32 {
+        self.tracked_positions
+            .values()
+            .filter(|p| p.state == PositionState::Pending)
+            .count() as u32
+    }
+    
+    fn add_exposure(&mut self, symbol: &str, trade_risk: Decimal) {
+        *self.portfolio_exposure.entry(symbol.to_string()).or_insert(Decimal::ZERO) += trade_risk;
+        self.total_portfolio_risk += trade_risk;
+    }
+    
+    fn remove_exposure(&mut self, symbol: &str, trade_risk: Decimal) {
+        if let Some(current_exposure) = self.portfolio_exposure.get_mut(symbol) {
+            *current_exposure = (*current_exposure - trade_risk).max(Decimal::ZERO);
+            if current_exposure.is_zero() {
+                self.portfolio_exposure.remove(symbol);
+            }
+        }
+        self.total_portfolio_risk = (self.total_portfolio_risk - trade_risk).max(Decimal::ZERO);
+    }
+    
+    /// Replace the tracked risk of an open position
+    ///
+    /// Used when a position's stop is moved after entry, e.g. to breakeven.
+    /// The position stays open even when its risk drops to zero.
+    pub fn update_position_risk(&mut self, symbol: &str, previous_risk: Decimal, new_risk: Decimal) {
+        let exposure = self.portfolio_exposure
+            .entry(symbol.to_string())
+            .or_insert(Decimal::ZERO);
+        *exposure = (*exposure - previous_risk + new_risk).max(Decimal::ZERO);
+        
+        self.total_portfolio_risk = (self.total_portfolio_risk - previous_risk + new_risk).max(Decimal::ZERO);
+        
+        info!(
+            "Updated position risk for {}: {:.2}% -> {:.2}%, total_portfolio_risk={:.2}%",
+            symbol,
+            previous_risk * Decimal::from(100),
+            new_risk * Decimal::from(100),
+            self.total_portfolio_risk * Decimal::from(100)
+        );
+    }
+    
+    /// Replace the risk of a tracked position
+    ///
+    /// Used once an entry has filled at a different size or price than the
+    /// one its risk was reserved for. Returns false for an unknown position.
+    pub fn set_tracked_risk(&mut self, position_id: Uuid, risk: Decimal) -> bool {
+        let Some(position) = self.tracked_positions.get_mut(&position_id) else {
+            return false;
+        };
+        let previous_risk = std::mem::replace(&mut position.risk, risk);
+        let symbol = position.symbol.clone();
+        self.update_position_risk(&symbol, previous_risk, risk);
+        true
+    }
+    
+    /// Record a trade outcome (win or loss)
+    ///
+    /// Without a position id the outcome cannot be the half-open trial's, so
+    /// it never resolves one; see [`Self::record_position_outcome`].
+    pub fn record_trade_outcome(&mut self, symbol: &str, trade_risk: Decimal, was_loss: bool, loss_amount: Option<Decimal>) {
+        self.apply_trade_outcome(None, symbol, trade_risk, was_loss, loss_amount);
+    }
+    
+    /// Record the outcome of the trade placed under `position_id`
+    ///
+    /// When that trade is the half-open trial, a win closes the breaker and
+    /// a loss re-opens it.
+    pub fn record_position_outcome(
+        &mut self,
+        position_id: Uuid,
+        symbol: &str,
+        trade_risk: Decimal,
+        was_loss: bool,
+        loss_amount: Option<Decimal>,
+    ) {
+        self.apply_trade_outcome(Some(position_id), symbol, trade_risk, was_loss, loss_amount);
+    }
+    
+    fn apply_trade_outcome(
+        &mut self,
+        position_id: Option<Uuid>,
+        symbol: &str,
+        trade_risk: Decimal,
+        was_loss: bool,
+        loss_amount: Option<Decimal>,
+    ) {
+        let was_trial = matches!(
+            (self.half_open, position_id),
+            (Some(HalfOpenTrial::InFlight(trial)), Some(id)) if trial == id
+        );
+        if was_trial {
+            self.half_open = None;
+        }
+        self.remove_exposure(symbol, trade_risk);
+        
+        // Decrement open positions
+        self.open_positions = self.open_positions.saturating_sub(1);
+        
+        // Handle consecutive loss tracking
+        if was_loss {
+            self.consecutive_losses += 1;
+            self.last_loss_time = Some(SystemTime::now());
+            
+            // Add to daily loss if amount is provided
+            if let Some(loss) = loss_amount {
+                self.daily_loss += loss;
+            }
+            
+            // Activate circuit breaker if limit reached; a losing trial
+            // re-opens a half-open breaker for another cooldown
+            match self.limits.circuit_breaker_scope {
+                CircuitBreakerScope::AccountWide => {
+                    if was_trial {
+                        warn!("Half-open trial trade on {} lost; re-opening circuit breaker.", symbol);
+                        self.activate_circuit_breaker();
+                    } else if self.consecutive_losses >= self.limits.max_consecutive_losses {
+                        self.activate_circuit_breaker();
+                    }
+                }
+                CircuitBreakerScope::PerSymbol => {
+                    let max_losses = self.limits.max_consecutive_losses;
+                    let breaker = self.symbol_breakers.entry(symbol.to_string()).or_default();
+                    breaker.consecutive_losses += 1;
+                    
+                    if breaker.consecutive_losses >= max_losses && breaker.activated_at.is_none() {
+                        breaker.activated_at = Some(SystemTime::now());
+                        warn!(
+                            "🚨 CIRCUIT BREAKER ACTIVATED for {}: {} consecutive losses. Trading in {} halted.",
+                            symbol, breaker.consecutive_losses, symbol
+                        );
+                    }
+                }
+            }
+            
+            warn!(
+                "Recorded loss for {}: consecutive_losses={}, daily_loss=${:.2}",
+                symbol, self.consecutive_losses, self.daily_loss
+            );
+        } else {
+            // Reset consecutive losses on win; a breaker that has already
+            // tripped stays open until its cooldown or a manual reset
+            self.consecutive_losses = 0;
+            self.last_loss_time = None;
+            if let Some(breaker) = self.symbol_breakers.get_mut(symbol) {
+                breaker.consecutive_losses = 0;
+                if breaker.activated_at.is_none() {
+                    self.symbol_breakers.remove(symbol);
+                }
+            }
+            if was_trial {
+                info!("✅ Half-open trial trade on {} won; circuit breaker closed.", symbol);
+            }
+            
+            info!(
+                "Recorded win for {}: consecutive losses reset, daily_loss=${:.2}",
+                symbol, self.daily_loss
             );
         }
         
@@ -739,6 +1838,46 @@ impl TestudoProtocol {
         );
     }
     
+    /// Simulate a sequence of trade outcomes without changing this protocol
+    ///
+    /// The outcomes are applied in order to a copy of the current state, and the
+    /// status after each one is returned, showing when the circuit breaker would
+    /// trip and how the daily budget depletes.
+    ///
+    /// A tripped breaker only lets trading resume once its cooldown ends, so a
+    /// win while it is open is taken as the trade that followed the cooldown:
+    /// the half-open trial for an account-wide breaker, which the win closes.
+    pub fn simulate_outcomes(&self, outcomes: &[Outcome]) -> Vec<ProtocolStatus> {
+        let mut simulation = self.clone();
+        
+        outcomes
+            .iter()
+            .map(|outcome| {
+                match outcome {
+                    Outcome::Win { symbol, trade_risk } => {
+                        let position_id = Uuid::new_v4();
+                        if simulation.circuit_breaker_for(symbol).0 {
+                            match simulation.limits.circuit_breaker_scope {
+                                CircuitBreakerScope::AccountWide => {
+                                    simulation.enter_half_open();
+                                    simulation.start_trial_if_half_open(position_id);
+                                }
+                                CircuitBreakerScope::PerSymbol => {
+                                    simulation.symbol_breakers.remove(symbol.as_str());
+                                }
+                            }
+                        }
+                        simulation.record_position_outcome(position_id, symbol, *trade_risk, false, None);
+                    }
+                    Outcome::Loss { symbol, trade_risk, loss_amount } => {
+                        simulation.record_trade_outcome(symbol, *trade_risk, true, Some(*loss_amount));
+                    }
+                }
+                simulation.get_status()
+            })
+            .collect()
+    }
+    
     /// Activate the circuit breaker
     fn activate_circuit_breaker(&mut self) {
         if !self.circuit_breaker_active {
@@ -752,22 +1891,85 @@ impl TestudoProtocol {
         }
     }
     
+    /// Circuit breaker state that applies to a symbol: (active, consecutive losses)
+    fn circuit_breaker_for(&self, symbol: &str) -> (bool, u32) {
+        match self.limits.circuit_breaker_scope {
+            CircuitBreakerScope::AccountWide => (self.circuit_breaker_active, self.consecutive_losses),
+            CircuitBreakerScope::PerSymbol => self.symbol_breakers
+                .get(symbol)
+                .map(|b| (b.activated_at.is_some(), b.consecutive_losses))
+                .unwrap_or((false, 0)),
+        }
+    }
+    
     /// Check if circuit breaker should be reset (after timeout or manual intervention)
     fn check_circuit_breaker_reset(&mut self) {
+        // Per-symbol breakers expire on the same 1 hour timeout
+        self.symbol_breakers.retain(|symbol, breaker| match breaker.activated_at {
+            Some(activated_at) => {
+                let expired = SystemTime::now().duration_since(activated_at).unwrap_or_default()
+                    > Duration::from_secs(3600);
+                if expired {
+                    info!("✅ Circuit breaker for {} reset. Trading can resume.", symbol);
+                }
+                !expired
+            }
+            None => true,
+        });
+        
         if self.circuit_breaker_active {
             if let Some(activated_at) = self.circuit_breaker_activated_at {
                 let elapsed = SystemTime::now().duration_since(activated_at).unwrap_or_default();
                 
-                // Reset after 1 hour (configurable in real system)
+                // Reset after 1 hour (configurable in real system), or go
+                // half-open and allow a trial trade when one is configured
                 if elapsed > Duration::from_secs(3600) {
-                    self.reset_circuit_breaker();
+                    if self.limits.circuit_breaker_trial_risk.is_some() {
+                        self.enter_half_open();
+                    } else {
+                        self.reset_circuit_breaker();
+                    }
                 }
             }
         }
     }
     
+    /// Move an open breaker to half-open after its cooldown
+    ///
+    /// The loss streak is cleared, since the trial's own outcome decides
+    /// whether the breaker closes or re-opens.
+    fn enter_half_open(&mut self) {
+        self.circuit_breaker_active = false;
+        self.circuit_breaker_activated_at = None;
+        self.consecutive_losses = 0;
+        self.half_open = Some(HalfOpenTrial::Awaiting);
+        
+        info!("Circuit breaker half-open: one trial trade allowed.");
+    }
+    
+    /// Make `position_id` the trial trade if the breaker is awaiting one
+    fn start_trial_if_half_open(&mut self, position_id: Uuid) {
+        if self.half_open == Some(HalfOpenTrial::Awaiting) {
+            self.half_open = Some(HalfOpenTrial::InFlight(position_id));
+        }
+    }
+    
+    /// Wait for a new trial when `position_id`'s trial never traded
+    fn cancel_trial(&mut self, position_id: Uuid) {
+        if self.half_open == Some(HalfOpenTrial::InFlight(position_id)) {
+            self.half_open = Some(HalfOpenTrial::Awaiting);
+            info!("Half-open trial {} cancelled; awaiting another trial trade.", position_id);
+        }
+    }
+    
     /// Manually reset the circuit breaker (admin function)
+    ///
+    /// Clears the account-wide breaker, including a half-open trial, and
+    /// every per-symbol breaker.
     pub fn reset_circuit_breaker(&mut self) {
+        self.symbol_breakers.clear();
+        self.half_open = None;
+        
         if self.circuit_breaker_active {
             self.circuit_breaker_active = false;
             self.circuit_breaker_activated_at = None;
@@ -787,10 +1989,23 @@ impl TestudoProtocol {
         if elapsed > Duration::from_secs(24 * 3600) {
             self.daily_loss = Decimal::ZERO;
             self.last_daily_reset = now;
+            self.daily_loss_monitor.reset();
             info!("🌅 Daily risk tracking reset");
         }
     }
     
+    /// Check whether the daily loss has crossed a cool-down threshold
+    ///
+    /// Returns a warning once the loss reaches `daily_loss_warning_threshold` of
+    /// the daily budget and a critical alert once the budget is exhausted. Each
+    /// alert is raised at most once per trading day.
+    pub fn check_daily_loss_alert(&mut self, account_equity: Decimal) -> Option<DailyLossAlert> {
+        self.reset_daily_tracking_if_needed();
+        
+        let daily_limit = account_equity * self.limits.effective_max_daily_loss(account_equity);
+        self.daily_loss_monitor.check(self.daily_loss, daily_limit)
+    }
+    
     /// Get current protocol status
     pub fn get_status(&self) -> ProtocolStatus {
         ProtocolStatus {
@@ -798,7 +2013,9 @@ impl TestudoProtocol {
             consecutive_losses: self.consecutive_losses,
             daily_loss: self.daily_loss,
             open_positions: self.open_positions,
+            pending_positions: self.pending_positions(),
             circuit_breaker_active: self.circuit_breaker_active,
+            circuit_breaker_half_open: self.half_open.is_some(),
             risk_utilization: self.total_portfolio_risk / self.limits.max_total_portfolio_risk,
             days_since_last_reset: SystemTime::now()
                 .duration_since(self.last_daily_reset)
@@ -813,6 +2030,18 @@ impl TestudoProtocol {
         &self.limits
     }
     
+    /// Replace the limits when a user edits their settings
+    ///
+    /// Tracked exposure, losses and positions are kept; the new limits apply
+    /// from the next check.
+    pub fn update_limits(&mut self, limits: ProtocolLimits) {
+        if limits.daily_loss_warning_threshold != self.limits.daily_loss_warning_threshold {
+            self.daily_loss_monitor = DailyLossMonitor::new(limits.daily_loss_warning_threshold);
+        }
+        info!("Protocol limits updated: {:?}", limits);
+        self.limits = limits;
+    }
+    
     /// Check if trading is currently allowed
     pub fn is_trading_allowed(&mut self) -> bool {
         self.reset_daily_tracking_if_needed();
@@ -820,9 +2049,19 @@ impl TestudoProtocol {
         !self.circuit_breaker_active
     }
     
-    /// Calculate remaining risk budget
-    pub fn remaining_risk_budget(&self) -> Decimal {
-        (self.limits.max_total_portfolio_risk - self.total_portfolio_risk).max(Decimal::ZERO)
+    /// Check if trading is currently allowed for a specific symbol
+    ///
+    /// With account-wide scope this is the same as `is_trading_allowed`.
+    pub fn is_symbol_trading_allowed(&mut self, symbol: &str) -> bool {
+        self.reset_daily_tracking_if_needed();
+        self.check_circuit_breaker_reset();
+        !self.circuit_breaker_for(symbol).0
+    }
+    
+    /// Calculate remaining risk budget at `account_equity`, net of outstanding reservations
+    pub fn remaining_risk_budget(&self, account_equity: Decimal) -> Decimal {
+        let max_portfolio_risk = self.limits.effective_max_portfolio_risk(account_equity);
+        (max_portfolio_risk - self.total_portfolio_risk - self.reserved_risk()).max(Decimal::ZERO)
     }
     
     /// Calculate remaining daily loss budget
@@ -831,7 +2070,7 @@ impl TestudoProtocol {
             return Decimal::ZERO;
         }
         
-        let daily_limit = account_equity * self.limits.max_daily_loss;
+        let daily_limit = account_equity * self.limits.effective_max_daily_loss(account_equity);
         (daily_limit - self.daily_loss).max(Decimal::ZERO)
     }
 }
@@ -849,16 +2088,106 @@ pub struct ProtocolStatus {
     pub consecutive_losses: u32,
     pub daily_loss: Decimal,
     pub open_positions: u32,
+    /// Submitted entries not yet filled; their risk is included in `total_portfolio_risk`
+    pub pending_positions: u32,
     pub circuit_breaker_active: bool,
+    /// Cooldown over; one size-capped trial trade decides whether the breaker closes
+    pub circuit_breaker_half_open: bool,
     pub risk_utilization: Decimal, // Percentage of max risk used
     pub days_since_last_reset: u64,
     pub portfolio_exposure: HashMap<String, Decimal>,
 }
 
+/// One difference between two protocol status snapshots
+///
+/// Risk and loss changes carry both values and the signed change, so a client
+/// can render "risk +2%" without keeping the previous snapshot itself.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ProtocolStatusChange {
+    PortfolioRiskChanged { from: Decimal, to: Decimal, change: Decimal },
+    PositionsOpened { count: u32, open_positions: u32 },
+    PositionsClosed { count: u32, open_positions: u32 },
+    PendingPositionsChanged { from: u32, to: u32 },
+    ConsecutiveLossesChanged { from: u32, to: u32 },
+    DailyLossChanged { from: Decimal, to: Decimal, change: Decimal },
+    CircuitBreakerTripped,
+    CircuitBreakerReset,
+    CircuitBreakerHalfOpened,
+    /// Exposure on a symbol; a symbol absent from a snapshot counts as zero
+    SymbolExposureChanged { symbol: String, from: Decimal, to: Decimal },
+}
+
+impl ProtocolStatus {
+    /// Changes from this status to `after`, empty when nothing changed
+    ///
+    /// Derived values such as `risk_utilization` and the day counter are not reported.
+    pub fn diff(&self, after: &ProtocolStatus) -> Vec<ProtocolStatusChange> {
+        let mut changes = Vec::new();
+
+        if after.total_portfolio_risk != self.total_portfolio_risk {
+            changes.push(ProtocolStatusChange::PortfolioRiskChanged {
+                from: self.total_portfolio_risk,
+                to: after.total_portfolio_risk,
+                change: after.total_portfolio_risk - self.total_portfolio_risk,
+            });
+        }
+        if after.open_positions > self.open_positions {
+            changes.push(ProtocolStatusChange::PositionsOpened {
+                count: after.open_positions - self.open_positions,
+                open_positions: after.open_positions,
+            });
+        } else if after.open_positions < self.open_positions {
+            changes.push(ProtocolStatusChange::PositionsClosed {
+                count: self.open_positions - after.open_positions,
+                open_positions: after.open_positions,
+            });
+        }
+        if after.pending_positions != self.pending_positions {
+            changes.push(ProtocolStatusChange::PendingPositionsChanged {
+                from: self.pending_positions,
+                to: after.pending_positions,
+            });
+        }
+        if after.consecutive_losses != self.consecutive_losses {
+            changes.push(ProtocolStatusChange::ConsecutiveLossesChanged {
+                from: self.consecutive_losses,
+                to: after.consecutive_losses,
+            });
+        }
+        if after.daily_loss != self.daily_loss {
+            changes.push(ProtocolStatusChange::DailyLossChanged {
+                from: self.daily_loss,
+                to: after.daily_loss,
+                change: after.daily_loss - self.daily_loss,
+            });
+        }
+        match (self.circuit_breaker_active, after.circuit_breaker_active) {
+            (false, true) => changes.push(ProtocolStatusChange::CircuitBreakerTripped),
+            (true, false) => changes.push(ProtocolStatusChange::CircuitBreakerReset),
+            _ => {}
+        }
+        if after.circuit_breaker_half_open && !self.circuit_breaker_half_open {
+            changes.push(ProtocolStatusChange::CircuitBreakerHalfOpened);
+        }
+
+        let symbols: BTreeSet<&String> = self.portfolio_exposure.keys().chain(after.portfolio_exposure.keys()).collect();
+        for symbol in symbols {
+            let from = self.portfolio_exposure.get(symbol).copied().unwrap_or_default();
+            let to = after.portfolio_exposure.get(symbol).copied().unwrap_or_default();
+            if from != to {
+                changes.push(ProtocolStatusChange::SymbolExposureChanged { symbol: symbol.clone(), from, to });
+            }
+        }
+
+        changes
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::TradeSide;
+    use crate::types::{StressTest, TradeSide};
     use disciplina::{AccountEquity, RiskPercentage, PricePoint};
     use rust_decimal_macros::dec;
     
@@ -951,6 +2280,152 @@ mod tests {
         assert!(result.is_err());
     }
     
+    #[test]
+    fn test_half_open_breaker_allows_one_capped_trial() {
+        let limits = ProtocolLimits {
+            circuit_breaker_trial_risk: Some(dec!(0.01)),
+            ..ProtocolLimits::default_limits()
+        };
+        let mut protocol = TestudoProtocol::with_limits(limits);
+        let trip = |protocol: &mut TestudoProtocol| {
+            for _ in 0..3 {
+                protocol.record_trade_outcome("BTCUSDT", dec!(0.01), true, Some(dec!(100)));
+            }
+            assert!(!protocol.is_trading_allowed());
+            // Backdate the activation past the one hour cooldown
+            protocol.circuit_breaker_activated_at = Some(SystemTime::now() - Duration::from_secs(3601));
+        };
+        
+        trip(&mut protocol);
+        assert!(protocol.is_trading_allowed());
+        assert!(protocol.get_status().circuit_breaker_half_open);
+        
+        // The trial is capped in size, and only one may be open
+        let violations = protocol.validate_trade(&create_test_proposal(dec!(0.02))).unwrap_err();
+        assert!(violations.iter().any(|v| v.rule_name == "ExceedsCircuitBreakerTrialRisk"));
+        let trial = create_test_proposal(dec!(0.01));
+        assert!(protocol.validate_trade(&trial).is_ok());
+        protocol.record_trade_execution(&trial);
+        let violations = protocol.validate_trade(&create_test_proposal(dec!(0.01))).unwrap_err();
+        assert!(violations.iter().any(|v| v.rule_name == "CircuitBreakerTrialInFlight"));
+        
+        // Another position's outcome leaves the trial in flight
+        protocol.record_trade_outcome("ETHUSDT", dec!(0.01), false, None);
+        let violations = protocol.validate_trade(&create_test_proposal(dec!(0.01))).unwrap_err();
+        assert!(violations.iter().any(|v| v.rule_name == "CircuitBreakerTrialInFlight"));
+        
+        // A winning trial closes the breaker
+        protocol.record_position_outcome(trial.id, "BTCUSDT", dec!(0.01), false, None);
+        let status = protocol.get_status();
+        assert!(!status.circuit_breaker_active);
+        assert!(!status.circuit_breaker_half_open);
+        assert!(protocol.validate_trade(&create_test_proposal(dec!(0.02))).is_ok());
+        
+        // A losing trial re-opens it for another cooldown
+        trip(&mut protocol);
+        assert!(protocol.is_trading_allowed());
+        protocol.record_trade_execution(&trial);
+        protocol.record_position_outcome(trial.id, "BTCUSDT", dec!(0.01), true, Some(dec!(100)));
+        let status = protocol.get_status();
+        assert!(status.circuit_breaker_active);
+        assert!(!status.circuit_breaker_half_open);
+        assert!(!protocol.is_trading_allowed());
+    }
+    
+    #[test]
+    fn test_cancelled_half_open_trial_awaits_another() {
+        let limits = ProtocolLimits {
+            circuit_breaker_trial_risk: Some(dec!(0.01)),
+            ..ProtocolLimits::default_limits()
+        };
+        let mut protocol = TestudoProtocol::with_limits(limits);
+        for _ in 0..3 {
+            protocol.record_trade_outcome("BTCUSDT", dec!(0.01), true, Some(dec!(100)));
+        }
+        assert!(!protocol.is_trading_allowed());
+        protocol.circuit_breaker_activated_at = Some(SystemTime::now() - Duration::from_secs(3601));
+        assert!(protocol.is_trading_allowed());
+        
+        // A released reservation frees the trial slot
+        let reserved = create_test_proposal(dec!(0.01));
+        protocol.reserve_risk(reserved.id, dec!(0.01), dec!(10000)).unwrap();
+        assert!(protocol.validate_trade(&create_test_proposal(dec!(0.01))).is_err());
+        protocol.release_reservation(reserved.id);
+        assert!(protocol.validate_trade(&create_test_proposal(dec!(0.01))).is_ok());
+        
+        // So does a pending trial entry that is cancelled before filling
+        let pending = create_test_proposal(dec!(0.01));
+        protocol.record_pending_entry(&pending);
+        assert!(protocol.validate_trade(&create_test_proposal(dec!(0.01))).is_err());
+        protocol.close_tracked_position(pending.id, ExitReason::Manual, false, None).unwrap();
+        let status = protocol.get_status();
+        assert!(status.circuit_breaker_half_open);
+        assert!(!status.circuit_breaker_active);
+        assert!(protocol.validate_trade(&create_test_proposal(dec!(0.01))).is_ok());
+    }
+    
+    #[test]
+    fn test_simultaneous_stop_out_stress_blocks_trade() {
+        let limits = ProtocolLimits {
+            stress_test: Some(StressTest { max_loss: dec!(0.10), stop_overshoot: dec!(0.5) }),
+            ..ProtocolLimits::default_limits()
+        };
+        let mut protocol = TestudoProtocol::with_limits(limits);
+        let open = create_test_proposal(dec!(0.04));
+        assert!(protocol.validate_trade(&open).is_ok());
+        protocol.record_trade_execution(&open);
+        
+        // 4% open + 4% new = 8% is inside the 6% per-trade and 10% portfolio
+        // limits, but stopping out together at 50% overshoot loses 12%
+        let violations = protocol.validate_trade(&create_test_proposal(dec!(0.04))).unwrap_err();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].rule_name, "ExceedsStressLossLimit");
+        assert_eq!(violations[0].current_value, dec!(0.12));
+        assert_eq!(violations[0].limit_value, dec!(0.10));
+        
+        // 4% + 2% stresses to exactly 9%
+        assert!(protocol.validate_trade(&create_test_proposal(dec!(0.02))).is_ok());
+    }
+    
+    #[test]
+    fn test_per_symbol_circuit_breaker_scope() {
+        let limits = ProtocolLimits {
+            circuit_breaker_scope: CircuitBreakerScope::PerSymbol,
+            ..ProtocolLimits::default_limits()
+        };
+        let mut protocol = TestudoProtocol::with_limits(limits);
+        
+        // Three BTC losses trip the BTC breaker only
+        for _ in 0..3 {
+            protocol.record_trade_outcome("BTCUSDT", dec!(0.01), true, Some(dec!(50)));
+        }
+        
+        assert!(!protocol.is_symbol_trading_allowed("BTCUSDT"));
+        assert!(protocol.is_symbol_trading_allowed("ETHUSDT"));
+        assert!(protocol.is_trading_allowed());
+        
+        let btc_violations = protocol.validate_trade(&create_test_proposal(dec!(0.01))).unwrap_err();
+        assert!(btc_violations.iter().any(|v| v.rule_name == "ExceedsMaxConsecutiveLosses"));
+        
+        let eth_proposal = TradeProposal::new(
+            "ETHUSDT".to_string(),
+            TradeSide::Long,
+            PricePoint::new(dec!(3000)).unwrap(),
+            PricePoint::new(dec!(2800)).unwrap(),
+            Some(PricePoint::new(dec!(3400)).unwrap()),
+            AccountEquity::new(dec!(10000)).unwrap(),
+            RiskPercentage::new(dec!(0.01)).unwrap(),
+        ).unwrap();
+        assert!(protocol.validate_trade(&eth_proposal).is_ok());
+        
+        // Account-wide scope (default) halts everything after the same losses
+        let mut account_wide = TestudoProtocol::new();
+        for _ in 0..3 {
+            account_wide.record_trade_outcome("BTCUSDT", dec!(0.01), true, Some(dec!(50)));
+        }
+        assert!(account_wide.validate_trade(&eth_proposal).is_err());
+    }
+    
     #[test]
     fn test_consecutive_loss_reset_on_win() {
         let mut protocol = TestudoProtocol::new();
@@ -970,25 +2445,346 @@ mod tests {
         assert!(protocol.is_trading_allowed());
     }
     
+    #[test]
+    fn test_simulate_outcomes_shows_breaker_trip_and_reset() {
+        let protocol = TestudoProtocol::new();
+        let outcomes = [
+            Outcome::loss("BTCUSDT", dec!(0.02), dec!(200)),
+            Outcome::loss("BTCUSDT", dec!(0.02), dec!(200)),
+            Outcome::loss("BTCUSDT", dec!(0.02), dec!(200)),
+            Outcome::win("BTCUSDT", dec!(0.02)),
+        ];
+        
+        let statuses = protocol.simulate_outcomes(&outcomes);
+        assert_eq!(statuses.len(), 4);
+        
+        // A win after the trip is the trial that closes the breaker
+        let breaker: Vec<bool> = statuses.iter().map(|s| s.circuit_breaker_active).collect();
+        assert_eq!(breaker, vec![false, false, true, false]);
+        assert_eq!(statuses[2].consecutive_losses, 3);
+        assert_eq!(statuses[2].daily_loss, dec!(600));
+        assert_eq!(statuses[3].consecutive_losses, 0);
+        
+        // The real protocol is untouched by the simulation
+        let status = protocol.get_status();
+        assert_eq!(status.consecutive_losses, 0);
+        assert_eq!(status.daily_loss, Decimal::ZERO);
+    }
+    
+    #[test]
+    fn test_pending_entry_counts_toward_risk_until_filled() {
+        let mut protocol = TestudoProtocol::new();
+        let proposal = create_test_proposal(dec!(0.02));
+        
+        protocol.record_pending_entry(&proposal);
+        
+        let status = protocol.get_status();
+        assert_eq!(status.total_portfolio_risk, dec!(0.02));
+        assert_eq!(status.pending_positions, 1);
+        assert_eq!(status.open_positions, 0);
+        assert_eq!(protocol.tracked_position(proposal.id).unwrap().state, PositionState::Pending);
+        
+        assert!(protocol.mark_entry_filled(proposal.id));
+        
+        let status = protocol.get_status();
+        assert_eq!(status.total_portfolio_risk, dec!(0.02));
+        assert_eq!(status.pending_positions, 0);
+        assert_eq!(status.open_positions, 1);
+        assert_eq!(protocol.tracked_position(proposal.id).unwrap().state, PositionState::Open);
+        
+        // A second fill of the same entry is ignored
+        assert!(!protocol.mark_entry_filled(proposal.id));
+        
+        let closed = protocol.close_tracked_position(proposal.id, ExitReason::TakeProfit, false, None).unwrap();
+        assert_eq!(closed.state, PositionState::Closed);
+        assert_eq!(protocol.get_status().total_portfolio_risk, Decimal::ZERO);
+        assert_eq!(protocol.get_status().open_positions, 0);
+    }
+    
+    #[test]
+    fn test_absolute_daily_loss_cap_binds_before_percentage() {
+        // 5% of 10000 allows 500 of daily loss; the absolute cap allows 300
+        let limits = ProtocolLimits {
+            max_daily_loss_amount: Some(dec!(300)),
+            ..ProtocolLimits::default()
+        };
+        let mut protocol = TestudoProtocol::with_limits(limits.clone());
+        let proposal = create_test_proposal(dec!(0.04)); // 400 at risk
+        
+        let violations = protocol.validate_trade(&proposal).unwrap_err();
+        let daily = violations.iter().find(|v| v.rule_name == "ExceedsMaxDailyLoss").unwrap();
+        assert_eq!(daily.limit_value, dec!(0.03));
+        assert_eq!(protocol.remaining_daily_budget(dec!(10000)), dec!(300));
+        
+        // The percentage limit alone would have allowed the trade
+        assert!(TestudoProtocol::new().validate_trade(&proposal).is_ok());
+        
+        // On a smaller account the percentage is the stricter limit
+        assert_eq!(limits.effective_max_daily_loss(dec!(5000)), dec!(0.05));
+    }
+    
+    #[test]
+    fn test_absolute_trade_loss_ceiling_blocks_percentage_valid_trade() {
+        // 2% of a $1M account is within the 6% limit but risks $20,000
+        let limits = ProtocolLimits {
+            max_trade_loss_amount: Some(dec!(5000)),
+            ..ProtocolLimits::default()
+        };
+        let mut protocol = TestudoProtocol::with_limits(limits);
+        let proposal = TradeProposal::new(
+            "BTCUSDT".to_string(),
+            TradeSide::Long,
+            PricePoint::new(dec!(50000)).unwrap(),
+            PricePoint::new(dec!(48000)).unwrap(),
+            Some(PricePoint::new(dec!(54000)).unwrap()),
+            AccountEquity::new(dec!(1000000)).unwrap(),
+            RiskPercentage::new(dec!(0.02)).unwrap(),
+        ).unwrap();
+        
+        let violations = protocol.validate_trade(&proposal).unwrap_err();
+        let ceiling = violations.iter().find(|v| v.rule_name == "MaxTradeLossAmount").unwrap();
+        assert_eq!(ceiling.severity, ViolationSeverity::Blocking);
+        assert_eq!(ceiling.current_value, dec!(20000));
+        assert_eq!(ceiling.limit_value, dec!(5000));
+        
+        assert!(TestudoProtocol::new().validate_trade(&proposal).is_ok());
+    }
+    
+    #[test]
+    fn test_sub_floor_position_size_is_rejected() {
+        let limits = ProtocolLimits {
+            min_position_notional: Some(dec!(100)),
+            ..ProtocolLimits::default()
+        };
+        let mut protocol = TestudoProtocol::with_limits(limits);
+        // 1% of $1,000 over a 10,000 stop sizes 0.001 BTC, a $50 position
+        let proposal = TradeProposal::new(
+            "BTCUSDT".to_string(),
+            TradeSide::Long,
+            PricePoint::new(dec!(50000)).unwrap(),
+            PricePoint::new(dec!(40000)).unwrap(),
+            Some(PricePoint::new(dec!(70000)).unwrap()),
+            AccountEquity::new(dec!(1000)).unwrap(),
+            RiskPercentage::new(dec!(0.01)).unwrap(),
+        ).unwrap();
+        
+        let violations = protocol.validate_trade(&proposal).unwrap_err();
+        assert_eq!(violations.len(), 1);
+        let floor = &violations[0];
+        assert_eq!(floor.rule_name, "MinPositionNotional");
+        assert_eq!(floor.severity, ViolationSeverity::Critical);
+        assert_eq!(floor.current_value, dec!(50));
+        assert_eq!(floor.limit_value, dec!(100));
+        assert!(floor.suggested_action.contains("100"));
+        
+        assert!(TestudoProtocol::new().validate_trade(&proposal).is_ok());
+    }
+    
+    #[test]
+    fn test_minimum_hold_time_blocks_early_manual_close() {
+        let limits = ProtocolLimits {
+            min_hold_time_secs: 300,
+            ..ProtocolLimits::default()
+        };
+        let mut protocol = TestudoProtocol::with_limits(limits);
+        let scalp = Uuid::new_v4();
+        let stopped = Uuid::new_v4();
+        protocol.track_external_position(scalp, "BTCUSDT", dec!(0.01));
+        protocol.track_external_position(stopped, "ETHUSDT", dec!(0.01));
+        
+        assert!(matches!(
+            protocol.request_exit(scalp, ExitReason::Manual),
+            Err(ExitError::MinimumHoldNotElapsed { min_hold_secs: 300, .. })
+        ));
+        assert_eq!(protocol.tracked_position(scalp).unwrap().state, PositionState::Open);
+        
+        // Closing outright, or marking an exit as submitted, is held back too
+        assert!(matches!(
+            protocol.close_tracked_position(scalp, ExitReason::TakeProfit, false, None),
+            Err(ExitError::MinimumHoldNotElapsed { .. })
+        ));
+        assert!(protocol.mark_position_closing(scalp, ExitReason::Manual).is_err());
+        assert_eq!(protocol.get_status().open_positions, 2);
+        
+        // Stop-loss exits are never held back
+        protocol.request_exit(stopped, ExitReason::StopLoss).unwrap();
+        assert_eq!(protocol.tracked_position(stopped).unwrap().state, PositionState::Closing);
+        let closed = protocol.close_tracked_position(stopped, ExitReason::StopLoss, true, Some(dec!(100))).unwrap();
+        assert_eq!(closed.state, PositionState::Closed);
+        
+        // Once held past the minimum, a manual close goes ahead
+        protocol.tracked_positions.get_mut(&scalp).unwrap().opened_at =
+            Some(SystemTime::now() - Duration::from_secs(301));
+        protocol.request_exit(scalp, ExitReason::Manual).unwrap();
+        assert_eq!(protocol.tracked_position(scalp).unwrap().state, PositionState::Closing);
+    }
+    
+    #[test]
+    fn test_stale_pending_entry_counts_as_open_position() {
+        let limits = ProtocolLimits {
+            max_open_positions: 1,
+            pending_entry_grace_period_secs: 0,
+            ..ProtocolLimits::default()
+        }
+        .with_strict_max_open_positions(true);
+        let mut protocol = TestudoProtocol::with_limits(limits);
+        
+        protocol.record_pending_entry(&create_test_proposal(dec!(0.02)));
+        std::thread::sleep(Duration::from_millis(5));
+        
+        let violations = protocol.validate_trade(&create_test_proposal(dec!(0.02))).unwrap_err();
+        assert!(violations.iter().any(|v| v.rule_name == "ExceedsMaxOpenPositions"));
+    }
+    
+    #[test]
+    fn test_pending_orders_have_their_own_cap() {
+        let limits = ProtocolLimits {
+            max_open_positions: 2,
+            pending_entry_grace_period_secs: 0,
+            max_pending_orders: Some(3),
+            ..ProtocolLimits::default()
+        }
+        .with_strict_max_open_positions(true);
+        let mut protocol = TestudoProtocol::with_limits(limits);
+        
+        let mut entries = Vec::new();
+        for _ in 0..3 {
+            let proposal = create_test_proposal(dec!(0.02));
+            protocol.validate_trade(&proposal).unwrap();
+            protocol.record_pending_entry(&proposal);
+            entries.push(proposal.id);
+        }
+        std::thread::sleep(Duration::from_millis(5));
+        
+        // Three stale pending entries hit the pending cap, not the open-position cap
+        let violations = protocol.validate_trade(&create_test_proposal(dec!(0.01))).unwrap_err();
+        assert!(violations.iter().any(|v| v.rule_name == "ExceedsMaxPendingOrders"));
+        assert!(!violations.iter().any(|v| v.rule_name == "ExceedsMaxOpenPositions"));
+        
+        // Filling two frees pending slots but fills the open-position cap
+        assert!(protocol.mark_entry_filled(entries[0]));
+        assert!(protocol.mark_entry_filled(entries[1]));
+        let status = protocol.get_status();
+        assert_eq!((status.open_positions, status.pending_positions), (2, 1));
+        let violations = protocol.validate_trade(&create_test_proposal(dec!(0.01))).unwrap_err();
+        assert!(violations.iter().any(|v| v.rule_name == "ExceedsMaxOpenPositions"));
+        assert!(!violations.iter().any(|v| v.rule_name == "ExceedsMaxPendingOrders"));
+    }
+    
+    #[test]
+    fn test_concurrent_reservations_cannot_exceed_budget() {
+        let protocol = Arc::new(std::sync::Mutex::new(TestudoProtocol::new())); // 10% budget
+        let first = create_test_proposal(dec!(0.06));
+        let second = create_test_proposal(dec!(0.06));
+        
+        let handles: Vec<_> = [first.id, second.id]
+            .into_iter()
+            .map(|position_id| {
+                let protocol = protocol.clone();
+                std::thread::spawn(move || protocol.lock().unwrap().reserve_risk(position_id, dec!(0.06), dec!(10000)))
+            })
+            .collect();
+        let results: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        
+        assert_eq!(results.iter().filter(|r| r.is_ok()).count(), 1);
+        let failure = results.iter().find_map(|r| r.clone().err()).unwrap();
+        assert_eq!(
+            failure,
+            ReservationError::InsufficientBudget { requested: dec!(0.06), available: dec!(0.04) }
+        );
+        
+        let mut protocol = protocol.lock().unwrap();
+        let winner = results.iter().find_map(|r| r.as_ref().ok()).unwrap().position_id;
+        let loser = if winner == first.id { &second } else { &first };
+        
+        // The portfolio check sees the reservation before it executes
+        assert_eq!(protocol.reserved_risk(), dec!(0.06));
+        let violations = protocol.validate_trade(loser).unwrap_err();
+        assert!(violations.iter().any(|v| v.rule_name == "MaxPortfolioRisk"));
+        
+        assert!(protocol.commit_reservation(winner, "BTCUSDT"));
+        assert_eq!(protocol.reserved_risk(), Decimal::ZERO);
+        assert_eq!(protocol.get_status().total_portfolio_risk, dec!(0.06));
+        assert_eq!(protocol.get_status().open_positions, 1);
+        assert_eq!(protocol.tracked_position(winner).unwrap().state, PositionState::Open);
+        assert!(!protocol.commit_reservation(winner, "BTCUSDT"));
+        
+        // Releasing an aborted reservation returns its budget
+        protocol.reserve_risk(loser.id, dec!(0.03), dec!(10000)).unwrap();
+        assert_eq!(protocol.remaining_risk_budget(dec!(10000)), dec!(0.01));
+        assert!(protocol.release_reservation(loser.id).is_some());
+        assert_eq!(protocol.remaining_risk_budget(dec!(10000)), dec!(0.04));
+    }
+    
+    #[test]
+    fn test_reservation_expires_after_grace_period() {
+        let limits = ProtocolLimits {
+            pending_entry_grace_period_secs: 0,
+            ..ProtocolLimits::default()
+        };
+        let mut protocol = TestudoProtocol::with_limits(limits);
+        let abandoned = create_test_proposal(dec!(0.06));
+        
+        protocol.reserve_risk(abandoned.id, dec!(0.06), dec!(10000)).unwrap();
+        std::thread::sleep(Duration::from_millis(5));
+        
+        // The abandoned cycle no longer holds any of the budget
+        assert_eq!(protocol.reserved_risk(), Decimal::ZERO);
+        assert_eq!(protocol.remaining_risk_budget(dec!(10000)), dec!(0.10));
+        assert!(protocol.reserve_risk(Uuid::new_v4(), dec!(0.10), dec!(10000)).is_ok());
+        assert!(!protocol.commit_reservation(abandoned.id, "BTCUSDT"));
+        assert_eq!(protocol.get_status().total_portfolio_risk, Decimal::ZERO);
+    }
+    
+    #[test]
+    fn test_filled_risk_replaces_reserved_risk() {
+        let mut protocol = TestudoProtocol::new();
+        let position_id = Uuid::new_v4();
+        protocol.reserve_risk(position_id, dec!(0.02), dec!(10000)).unwrap();
+        assert!(protocol.commit_reservation(position_id, "BTCUSDT"));
+        
+        // The entry filled smaller than reserved
+        assert!(protocol.set_tracked_risk(position_id, dec!(0.015)));
+        assert_eq!(protocol.tracked_position(position_id).unwrap().risk, dec!(0.015));
+        assert_eq!(protocol.get_status().total_portfolio_risk, dec!(0.015));
+        assert_eq!(protocol.get_status().portfolio_exposure["BTCUSDT"], dec!(0.015));
+        assert!(!protocol.set_tracked_risk(Uuid::new_v4(), dec!(0.01)));
+    }
+    
+    #[test]
+    fn test_reservation_honours_absolute_portfolio_cap() {
+        let limits = ProtocolLimits {
+            max_total_portfolio_risk_amount: Some(dec!(500)),
+            ..ProtocolLimits::default()
+        };
+        let mut protocol = TestudoProtocol::with_limits(limits);
+        
+        // $500 of $10,000 is 5%, tighter than the 10% percentage limit
+        assert_eq!(protocol.remaining_risk_budget(dec!(10000)), dec!(0.05));
+        assert!(protocol.reserve_risk(Uuid::new_v4(), dec!(0.06), dec!(10000)).is_err());
+        assert!(protocol.reserve_risk(Uuid::new_v4(), dec!(0.05), dec!(10000)).is_ok());
+        assert_eq!(protocol.remaining_risk_budget(dec!(10000)), Decimal::ZERO);
+    }
+    
     #[test]
     fn test_risk_budget_calculations() {
         let mut protocol = TestudoProtocol::new();
         let proposal = create_test_proposal(dec!(0.04)); // 4% risk
         
         // Initial budget should be 10% (full limit)
-        assert_eq!(protocol.remaining_risk_budget(), dec!(0.10));
+        assert_eq!(protocol.remaining_risk_budget(dec!(10000)), dec!(0.10));
         
         // Execute trade
         protocol.record_trade_execution(&proposal);
         
         // Budget should now be 6% (10% - 4%)
-        assert_eq!(protocol.remaining_risk_budget(), dec!(0.06));
+        assert_eq!(protocol.remaining_risk_budget(dec!(10000)), dec!(0.06));
         
         // Close the trade
         protocol.record_trade_outcome("BTCUSDT", dec!(0.04), false, None);
         
         // Budget should return to 10%
-        assert_eq!(protocol.remaining_risk_budget(), dec!(0.10));
+        assert_eq!(protocol.remaining_risk_budget(dec!(10000)), dec!(0.10));
     }
     
     #[test]
@@ -1007,6 +2803,107 @@ mod tests {
         assert_eq!(protocol.remaining_daily_budget(account_equity), dec!(150));
     }
     
+    #[test]
+    fn test_trade_share_of_remaining_daily_budget() {
+        let limits = ProtocolLimits {
+            max_trade_share_of_remaining_daily_budget: Some(dec!(0.40)),
+            ..ProtocolLimits::default_limits()
+        };
+        let mut protocol = TestudoProtocol::with_limits(limits);
+        
+        // $500 daily budget (5% of $10,000) with $300 already lost leaves $200
+        protocol.record_trade_outcome("ETHUSDT", dec!(0.03), true, Some(dec!(300)));
+        assert_eq!(protocol.remaining_daily_budget(dec!(10000)), dec!(200));
+        
+        // 1.2% of $10,000 = $120, which is 60% of the remaining budget
+        let violations = protocol.validate_trade(&create_test_proposal(dec!(0.012))).unwrap_err();
+        assert!(violations.iter().any(|v| v.rule_name == "ExceedsRemainingDailyBudgetShare"));
+        
+        // 0.6% of $10,000 = $60, which is 30% of the remaining budget
+        assert!(protocol.validate_trade(&create_test_proposal(dec!(0.006))).is_ok());
+    }
+    
+    #[test]
+    fn test_daily_loss_with_open_risk_blocks_trade() {
+        let limits = ProtocolLimits {
+            max_daily_loss_with_open_risk: Some(dec!(0.05)),
+            ..ProtocolLimits::default_limits()
+        };
+        let mut protocol = TestudoProtocol::with_limits(limits);
+        let mut unguarded = TestudoProtocol::new();
+        
+        let open_trade = TradeProposal::new(
+            "ETHUSDT".to_string(),
+            TradeSide::Long,
+            PricePoint::new(dec!(3000)).unwrap(),
+            PricePoint::new(dec!(2800)).unwrap(),
+            None,
+            AccountEquity::new(dec!(10000)).unwrap(),
+            RiskPercentage::new(dec!(0.03)).unwrap(),
+        ).unwrap();
+        
+        for p in [&mut protocol, &mut unguarded] {
+            // Small realized loss (1%) but 3% still open
+            p.record_trade_execution(&create_test_proposal(dec!(0.01)));
+            p.record_trade_outcome("BTCUSDT", dec!(0.01), true, Some(dec!(100)));
+            p.record_trade_execution(&open_trade);
+        }
+        
+        // 1% realized + 3% open + 2% new = 6% > 5% combined budget
+        let proposal = create_test_proposal(dec!(0.02));
+        let violations = protocol.validate_trade(&proposal).unwrap_err();
+        assert!(violations.iter().any(|v|
+            v.rule_name == "ExceedsMaxDailyLossWithOpenRisk" && v.severity == ViolationSeverity::Critical
+        ));
+        
+        // Realized-only daily loss check alone allows the trade
+        assert!(unguarded.validate_trade(&proposal).is_ok());
+    }
+    
+    #[test]
+    fn test_daily_loss_with_open_risk_counts_reserved_risk() {
+        let limits = ProtocolLimits {
+            max_daily_loss_with_open_risk: Some(dec!(0.05)),
+            ..ProtocolLimits::default_limits()
+        };
+        let mut protocol = TestudoProtocol::with_limits(limits);
+        protocol.record_trade_execution(&create_test_proposal(dec!(0.01)));
+        protocol.record_trade_outcome("BTCUSDT", dec!(0.01), true, Some(dec!(100)));
+        
+        // 1% realized + 2% new fits the 5% combined budget on its own
+        let proposal = create_test_proposal(dec!(0.02));
+        assert!(protocol.validate_trade(&proposal).is_ok());
+        
+        // Another in-flight cycle holding 3% pushes it to 6%
+        protocol.reserve_risk(Uuid::new_v4(), dec!(0.03), dec!(10000)).unwrap();
+        let violations = protocol.validate_trade(&proposal).unwrap_err();
+        assert!(violations.iter().any(|v| v.rule_name == "ExceedsMaxDailyLossWithOpenRisk"));
+        
+    }
+    
+    #[test]
+    fn test_daily_loss_cool_down_alerts() {
+        use crate::monitoring::DailyLossAlertLevel;
+        
+        let mut protocol = TestudoProtocol::new();
+        let account_equity = dec!(10000); // Daily budget: $500
+        
+        protocol.record_trade_outcome("BTCUSDT", dec!(0.02), true, Some(dec!(300)));
+        assert!(protocol.check_daily_loss_alert(account_equity).is_none());
+        
+        // Crossing 80% of the budget fires exactly one warning
+        protocol.record_trade_outcome("ETHUSDT", dec!(0.02), true, Some(dec!(100)));
+        let alert = protocol.check_daily_loss_alert(account_equity).unwrap();
+        assert_eq!(alert.level, DailyLossAlertLevel::Warning);
+        assert!(protocol.check_daily_loss_alert(account_equity).is_none());
+        
+        // Crossing 100% fires the critical alert
+        protocol.record_trade_outcome("ADAUSDT", dec!(0.02), true, Some(dec!(100)));
+        let alert = protocol.check_daily_loss_alert(account_equity).unwrap();
+        assert_eq!(alert.level, DailyLossAlertLevel::Critical);
+        assert!(protocol.check_daily_loss_alert(account_equity).is_none());
+    }
+    
     #[test]
     fn test_portfolio_exposure_tracking() {
         let mut protocol = TestudoProtocol::new();
@@ -1039,7 +2936,46 @@ mod tests {
         assert_eq!(status.portfolio_exposure.get("ETHUSDT"), Some(&dec!(0.02)));
         assert_eq!(status.total_portfolio_risk, dec!(0.02));
     }
-    
+
+    #[test]
+    fn test_open_positions_warning_allows_trade_by_default() {
+        let mut protocol = TestudoProtocol::new();
+        let proposal = create_test_proposal(dec!(0.005));
+
+        for _ in 0..protocol.limits().max_open_positions {
+            protocol.record_trade_execution(&proposal);
+        }
+
+        let violations = protocol.validate_trade(&proposal).unwrap_err();
+        let open_positions = violations.iter()
+            .find(|v| v.rule_name == "ExceedsMaxOpenPositions")
+            .expect("Expected open positions violation");
+
+        assert_eq!(open_positions.severity, ViolationSeverity::High);
+        assert!(!violations.iter().any(|v| v.severity == ViolationSeverity::Blocking));
+    }
+
+    #[test]
+    fn test_strict_open_positions_cap_rejects_trade() {
+        let limits = ProtocolLimits::default_limits().with_strict_max_open_positions(true);
+        let mut protocol = TestudoProtocol::with_limits(limits);
+        let proposal = create_test_proposal(dec!(0.005));
+
+        for _ in 0..protocol.limits().max_open_positions - 1 {
+            protocol.record_trade_execution(&proposal);
+        }
+
+        // Below the cap the trade is accepted
+        assert!(protocol.validate_trade(&proposal).is_ok());
+        protocol.record_trade_execution(&proposal);
+
+        // At the cap the trade is hard-rejected
+        let violations = protocol.validate_trade(&proposal).unwrap_err();
+        assert!(violations.iter().any(|v|
+            v.rule_name == "ExceedsMaxOpenPositions" && v.severity == ViolationSeverity::Blocking
+        ));
+    }
+
     //=============================================================================
     // RISK MANAGEMENT PROTOCOL TESTS - Task 3
     //=============================================================================
@@ -1099,7 +3035,7 @@ mod tests {
         
         // Verify assessment details
         assert_eq!(result.rule_results.len(), 1);
-        assert!(result.rule_results[0].assessment.is_ok());
+        assert!(result.rule_results[0].assessment().is_some());
         assert_eq!(result.rule_results[0].rule_name, "MaxTradeRisk");
         assert!(result.rule_results[0].execution_time_ms > 0);
         
@@ -1172,10 +3108,10 @@ mod tests {
         assert_eq!(protocol.rule_count(), 2);
         
         // First rule (standard) should pass
-        assert!(result.rule_results[0].assessment.is_ok());
+        assert!(result.rule_results[0].assessment().is_some());
         
         // Second rule (conservative) should also pass for 2% risk
-        assert!(result.rule_results[1].assessment.is_ok());
+        assert!(result.rule_results[1].assessment().is_some());
         
         // Overall result should be approved
         assert!(result.is_approved());
@@ -1208,13 +3144,13 @@ mod tests {
         let result = protocol.assess_trade(&moderate_risk_proposal).unwrap();
         
         // First rule should pass
-        assert!(result.rule_results[0].assessment.is_ok());
-        let first_assessment = result.rule_results[0].assessment.as_ref().unwrap();
+        assert!(result.rule_results[0].assessment().is_some());
+        let first_assessment = result.rule_results[0].assessment().unwrap();
         assert!(first_assessment.is_approved());
         
         // Second rule should pass but create violations
-        assert!(result.rule_results[1].assessment.is_ok());
-        let second_assessment = result.rule_results[1].assessment.as_ref().unwrap();
+        assert!(result.rule_results[1].assessment().is_some());
+        let second_assessment = result.rule_results[1].assessment().unwrap();
         assert!(!second_assessment.is_approved()); // Conservative rule rejects 3%
         
         // Overall result should be rejected due to conservative rule violation
@@ -1222,6 +3158,47 @@ mod tests {
         assert!(!result.violations().is_empty());
     }
     
+    #[test]
+    fn test_majority_policy_overrides_advisory_but_not_hard_limits() {
+        use crate::risk::assessment_rules::MaxTradeRiskRule;
+        
+        // 3% risk - standard rule passes, conservative rule objects
+        let moderate_risk_proposal = TradeProposal::new(
+            "ETHUSDT".to_string(),
+            TradeSide::Long,
+            PricePoint::new(dec!(3000)).unwrap(),
+            PricePoint::new(dec!(2910)).unwrap(),
+            None,
+            AccountEquity::new(dec!(10000)).unwrap(),
+            RiskPercentage::new(dec!(0.03)).unwrap(),
+        ).unwrap();
+        
+        // One of two advisory rules objecting is not a majority
+        let advisory_only = RiskManagementProtocol::new()
+            .with_advisory_policy(AdvisoryPolicy::Majority)
+            .add_advisory_rule(MaxTradeRiskRule::new())
+            .add_advisory_rule(MaxTradeRiskRule::conservative());
+        let result = advisory_only.assess_trade(&moderate_risk_proposal).unwrap();
+        assert!(result.is_approved());
+        assert_eq!(result.protocol_decision, ProtocolDecision::ApprovedWithWarnings);
+        
+        // The same objection from a hard-limit rule always rejects
+        let hard_limit = RiskManagementProtocol::new()
+            .with_advisory_policy(AdvisoryPolicy::Majority)
+            .add_advisory_rule(MaxTradeRiskRule::new())
+            .add_rule(MaxTradeRiskRule::conservative());
+        let result = hard_limit.assess_trade(&moderate_risk_proposal).unwrap();
+        assert!(result.is_rejected());
+        assert_eq!(result.protocol_decision, ProtocolDecision::Rejected);
+        
+        // The default policy keeps any-reject semantics for advisory rules
+        let any_reject = RiskManagementProtocol::new()
+            .add_advisory_rule(MaxTradeRiskRule::new())
+            .add_advisory_rule(MaxTradeRiskRule::conservative());
+        assert_eq!(any_reject.advisory_policy(), AdvisoryPolicy::AnyReject);
+        assert!(any_reject.assess_trade(&moderate_risk_proposal).unwrap().is_rejected());
+    }
+    
     #[test]
     fn test_protocol_assessment_result_methods() {
         use crate::risk::assessment_rules::MaxTradeRiskRule;
@@ -1264,12 +3241,12 @@ mod tests {
         
         // All should have executed and returned results
         for rule_result in &result.rule_results {
-            assert!(rule_result.assessment.is_ok());
+            assert!(rule_result.assessment().is_some());
             assert!(rule_result.execution_time_ms > 0);
         }
         
         // The primary assessment should come from the first rule
-        let first_assessment = result.rule_results[0].assessment.as_ref().unwrap();
+        let first_assessment = result.rule_results[0].assessment().unwrap();
         assert_eq!(result.assessment.position_size, first_assessment.position_size);
         assert_eq!(result.assessment.risk_percentage, first_assessment.risk_percentage);
     }
@@ -1354,4 +3331,178 @@ mod tests {
         assert_eq!(protocol.rule_count(), 0);
         assert_eq!(protocol.name(), "RiskManagementProtocol");
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_status_diff_reports_opened_position() {
+        let mut protocol = TestudoProtocol::new();
+        let before = protocol.get_status();
+        assert!(before.diff(&protocol.get_status()).is_empty());
+        
+        protocol.record_trade_execution(&create_test_proposal(dec!(0.02)));
+        let after = protocol.get_status();
+        
+        assert_eq!(before.diff(&after), vec![
+            ProtocolStatusChange::PortfolioRiskChanged { from: dec!(0), to: dec!(0.02), change: dec!(0.02) },
+            ProtocolStatusChange::PositionsOpened { count: 1, open_positions: 1 },
+            ProtocolStatusChange::SymbolExposureChanged {
+                symbol: "BTCUSDT".to_string(),
+                from: dec!(0),
+                to: dec!(0.02),
+            },
+        ]);
+        
+        // Reversed, the same snapshots describe the position closing
+        assert!(after.diff(&before).contains(&ProtocolStatusChange::PositionsClosed { count: 1, open_positions: 0 }));
+    }
+    
+    #[test]
+    fn test_rule_failures_are_reported_apart_from_rejections() {
+        use crate::risk::assessment_rules::{AssessmentError, MaxTradeRiskRule};
+        
+        #[derive(Debug)]
+        struct MisconfiguredRule;
+        
+        impl RiskRule for MisconfiguredRule {
+            fn assess(&self, _proposal: &TradeProposal) -> Result<RiskAssessment, AssessmentError> {
+                Err(AssessmentError::ConfigurationError { reason: "limits not loaded".to_string() })
+            }
+            
+            fn rule_name(&self) -> &str {
+                "Misconfigured"
+            }
+            
+            fn description(&self) -> &str {
+                "Always fails to run"
+            }
+        }
+        
+        let protocol = RiskManagementProtocol::new()
+            .add_rule(MisconfiguredRule)
+            .add_rule(MaxTradeRiskRule::conservative())
+            .add_rule(MaxTradeRiskRule::aggressive());
+        
+        // 3% risk breaks the conservative 2% cap but not the aggressive 10% one
+        let mut proposal = create_test_proposal_for_protocol();
+        proposal.risk_percentage = RiskPercentage::new(dec!(0.03)).unwrap();
+        let result = protocol.assess_trade(&proposal).unwrap();
+        
+        assert_eq!(result.protocol_decision, ProtocolDecision::AssessmentFailed);
+        assert_eq!(result.failed_rules, vec![RuleFailure {
+            rule_name: "Misconfigured".to_string(),
+            reason: "Assessment configuration error: limits not loaded".to_string(),
+        }]);
+        assert_eq!(result.rejecting_rules, vec!["MaxTradeRisk".to_string()]);
+        
+        // Both MaxTradeRisk instances share a name: one passed, one rejected
+        let counts = protocol.rule_outcome_counts();
+        assert_eq!(counts["Misconfigured"], RuleOutcomeCounts { passed: 0, rejected: 0, failed: 1 });
+        assert_eq!(counts["MaxTradeRisk"], RuleOutcomeCounts { passed: 1, rejected: 1, failed: 0 });
+    }
+    
+    #[test]
+    fn test_fail_on_critical_skips_rules_after_rejection() {
+        use crate::risk::assessment_rules::{AssessmentError, MaxTradeRiskRule};
+        
+        #[derive(Debug)]
+        struct SlowRule;
+        
+        impl RiskRule for SlowRule {
+            fn assess(&self, proposal: &TradeProposal) -> Result<RiskAssessment, AssessmentError> {
+                std::thread::sleep(Duration::from_millis(20));
+                MaxTradeRiskRule::aggressive().assess(proposal)
+            }
+            
+            fn rule_name(&self) -> &str {
+                "Slow"
+            }
+            
+            fn description(&self) -> &str {
+                "Expensive rule that should not run once the trade is rejected"
+            }
+        }
+        
+        let protocol = RiskManagementProtocol::new()
+            .add_rule(MaxTradeRiskRule::aggressive())
+            .add_rule(MaxTradeRiskRule::conservative())
+            .add_rule(SlowRule)
+            .with_fail_on_critical(true);
+        
+        // 3% risk breaks only the conservative 2% cap, the second rule
+        let mut proposal = create_test_proposal_for_protocol();
+        proposal.risk_percentage = RiskPercentage::new(dec!(0.03)).unwrap();
+        let result = protocol.assess_trade(&proposal).unwrap();
+        
+        assert_eq!(result.protocol_decision, ProtocolDecision::Rejected);
+        assert_eq!(result.rule_results.len(), 3);
+        assert_eq!(result.skipped_rules(), vec!["Slow"]);
+        assert!(matches!(
+            &result.rule_results[2].outcome,
+            RuleOutcome::Skipped { reason } if reason == "MaxTradeRisk already rejected the trade"
+        ));
+        assert_eq!(result.executed_rule_count(), 2);
+        assert_eq!(result.failed_rule_count(), 0);
+        assert!(result.failed_rules.is_empty());
+        // The skipped rule's 20ms never ran, so it is not in the total
+        assert!(result.total_execution_time_ms() < 20);
+        assert!(!protocol.rule_outcome_counts().contains_key("Slow"));
+        
+        // Without the option every rule runs
+        let protocol = RiskManagementProtocol::new()
+            .add_rule(MaxTradeRiskRule::conservative())
+            .add_rule(SlowRule);
+        let result = protocol.assess_trade(&proposal).unwrap();
+        assert!(result.skipped_rules().is_empty());
+        assert!(result.total_execution_time_ms() >= 20);
+    }
+    
+    #[tokio::test]
+    async fn test_async_rules_are_awaited_alongside_sync_rules() {
+        use crate::risk::assessment_rules::{AssessmentError, MaxTradeRiskRule};
+        use crate::risk::async_rules::AsyncRiskRule;
+        
+        /// Stands in for a rule that fetches the live price before assessing
+        #[derive(Debug)]
+        struct LivePriceRule;
+        
+        #[async_trait::async_trait]
+        impl AsyncRiskRule for LivePriceRule {
+            async fn assess(&self, proposal: &TradeProposal) -> Result<RiskAssessment, AssessmentError> {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                RiskRule::assess(&MaxTradeRiskRule::conservative(), proposal)
+            }
+            
+            fn rule_name(&self) -> &str {
+                "LivePrice"
+            }
+            
+            fn description(&self) -> &str {
+                "Assesses the trade against a freshly fetched price"
+            }
+        }
+        
+        let protocol = RiskManagementProtocol::new()
+            .add_rule(MaxTradeRiskRule::aggressive())
+            .add_async_rule(LivePriceRule);
+        
+        // 3% risk passes the aggressive cap but not the awaited conservative one
+        let mut proposal = create_test_proposal_for_protocol();
+        proposal.risk_percentage = RiskPercentage::new(dec!(0.03)).unwrap();
+        let result = protocol.assess_trade_async(&proposal).await.unwrap();
+        
+        assert_eq!(result.protocol_decision, ProtocolDecision::Rejected);
+        assert_eq!(result.rule_results.len(), 2);
+        assert_eq!(result.rejecting_rules, vec!["LivePrice"]);
+        assert!(result.rule_results.iter().all(|r| r.assessment().is_some()));
+        
+        // Sync assessment cannot await the live rule
+        assert!(matches!(
+            protocol.assess_trade(&proposal),
+            Err(ProtocolError::ConfigurationError { reason }) if reason.contains("LivePrice")
+        ));
+        
+        // Sync rules adapt to AsyncRiskRule through the blanket impl
+        let adapted = RiskManagementProtocol::new().add_async_rule(MaxTradeRiskRule::aggressive());
+        let result = adapted.assess_trade_async(&proposal).await.unwrap();
+        assert_eq!(result.protocol_decision, ProtocolDecision::Approved);
+    }
+}
@@ -4,7 +4,9 @@
 //! that are applied to trade proposals during validation.
 
 use crate::types::{TradeProposal, ProtocolLimits, ProtocolViolation, ViolationSeverity};
+use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Core trait for risk validation rules
@@ -92,21 +94,22 @@ impl MaxIndividualTradeRiskRule {
 impl RiskRule for MaxIndividualTradeRiskRule {
     fn validate(&self, proposal: &TradeProposal) -> Result<(), RiskViolation> {
         let risk_percentage = proposal.risk_percentage.value();
+        let max_risk = self.limits.enforced_max_individual_trade_risk();
         
-        if risk_percentage > self.limits.max_individual_trade_risk {
+        if risk_percentage > max_risk {
             return Err(RiskViolation::new(
                 self.rule_name().to_string(),
                 ViolationSeverity::Critical,
                 format!(
                     "Individual trade risk {}% exceeds maximum allowed {}%",
                     risk_percentage * Decimal::from(100),
-                    self.limits.max_individual_trade_risk * Decimal::from(100)
+                    max_risk * Decimal::from(100)
                 ),
                 risk_percentage,
-                self.limits.max_individual_trade_risk,
+                max_risk,
                 format!(
                     "Reduce position risk to maximum {}% of account equity",
-                    self.limits.max_individual_trade_risk * Decimal::from(100)
+                    max_risk * Decimal::from(100)
                 ),
             ));
         }
@@ -191,7 +194,20 @@ impl MinRewardRiskRatioRule {
 
 impl RiskRule for MinRewardRiskRatioRule {
     fn validate(&self, proposal: &TradeProposal) -> Result<(), RiskViolation> {
-        // Only validate if take profit is set
+        if proposal.take_profit.is_none() {
+            // Trades can run without a target unless the user's policy says otherwise
+            if let Some(severity) = self.limits.missing_take_profit_severity() {
+                return Err(RiskViolation::new(
+                    self.rule_name().to_string(),
+                    severity,
+                    "Trade has no take profit, so reward-to-risk cannot be checked".to_string(),
+                    Decimal::ZERO,
+                    self.limits.min_reward_risk_ratio,
+                    "Set a take profit target or relax the missing take profit policy".to_string(),
+                ));
+            }
+        }
+        
         if let Some(ratio) = proposal.risk_reward_ratio() {
             if ratio < self.limits.min_reward_risk_ratio {
                 return Err(RiskViolation::new(
@@ -211,8 +227,6 @@ impl RiskRule for MinRewardRiskRatioRule {
                 ));
             }
         }
-        // If no take profit is set, we don't validate (trade can run without target)
-        
         Ok(())
     }
     
@@ -229,6 +243,58 @@ impl RiskRule for MinRewardRiskRatioRule {
     }
 }
 
+/// Rule that flags implausibly high reward-to-risk ratios
+///
+/// A 50:1 target is far more often a mistyped take profit than a real
+/// setup, so the trade is warned rather than blocked and the trader is asked
+/// to confirm the target.
+#[derive(Debug, Clone)]
+pub struct MaxRewardRiskRatioRule {
+    limits: ProtocolLimits,
+}
+
+impl MaxRewardRiskRatioRule {
+    pub fn new(limits: ProtocolLimits) -> Self {
+        Self { limits }
+    }
+}
+
+impl RiskRule for MaxRewardRiskRatioRule {
+    fn validate(&self, proposal: &TradeProposal) -> Result<(), RiskViolation> {
+        let (Some(ceiling), Some(ratio)) = (self.limits.max_reward_risk_ratio, proposal.risk_reward_ratio()) else {
+            return Ok(());
+        };
+        
+        if ratio > ceiling {
+            return Err(RiskViolation::new(
+                self.rule_name().to_string(),
+                ViolationSeverity::Warning,
+                format!(
+                    "Reward-to-risk ratio {:.2} above plausible ceiling {:.2}",
+                    ratio,
+                    ceiling
+                ),
+                ratio,
+                ceiling,
+                "Confirm the take profit target is not mistyped".to_string(),
+            ));
+        }
+        Ok(())
+    }
+    
+    fn rule_name(&self) -> &str {
+        "MaxRewardRiskRatio"
+    }
+    
+    fn priority(&self) -> u8 {
+        5 // Lower priority - a sanity warning, not a blocker
+    }
+    
+    fn description(&self) -> &str {
+        "Flags take profit targets with an implausibly high reward-to-risk ratio"
+    }
+}
+
 /// Rule that validates stop loss direction is correct for trade side
 #[derive(Debug, Clone)]
 pub struct StopLossDirectionRule;
@@ -379,10 +445,133 @@ impl RiskRule for ValidSymbolRule {
     }
 }
 
+/// A symbol a user may not trade, with the reason shown to them
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SymbolRestriction {
+    pub symbol: String,
+    pub reason: String,
+}
+
+/// A window during which a symbol may not be traded (e.g. around news)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SymbolBlackout {
+    pub symbol: String,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+}
+
+/// Why a symbol restriction rule refuses a symbol
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SymbolRestrictionViolation {
+    Restricted { reason: String },
+    Blackout { ends_at: DateTime<Utc> },
+}
+
+/// Rule that refuses trades on restricted symbols and during blackouts
+///
+/// Symbols are matched case-insensitively.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SymbolRestrictionRule {
+    #[serde(default)]
+    pub restricted: Vec<SymbolRestriction>,
+    #[serde(default)]
+    pub blackouts: Vec<SymbolBlackout>,
+}
+
+impl SymbolRestrictionRule {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Restrict a symbol outright
+    pub fn with_restriction(mut self, symbol: &str, reason: &str) -> Self {
+        self.restricted.push(SymbolRestriction {
+            symbol: symbol.to_string(),
+            reason: reason.to_string(),
+        });
+        self
+    }
+    
+    /// Block a symbol between two instants
+    pub fn with_blackout(mut self, symbol: &str, starts_at: DateTime<Utc>, ends_at: DateTime<Utc>) -> Self {
+        self.blackouts.push(SymbolBlackout {
+            symbol: symbol.to_string(),
+            starts_at,
+            ends_at,
+        });
+        self
+    }
+    
+    /// Every restriction that applies to `symbol` at `now`
+    pub fn violations_at(&self, symbol: &str, now: DateTime<Utc>) -> Vec<SymbolRestrictionViolation> {
+        let restrictions = self
+            .restricted
+            .iter()
+            .filter(|restriction| restriction.symbol.eq_ignore_ascii_case(symbol))
+            .map(|restriction| SymbolRestrictionViolation::Restricted {
+                reason: restriction.reason.clone(),
+            });
+        let blackouts = self
+            .blackouts
+            .iter()
+            .filter(|blackout| {
+                blackout.symbol.eq_ignore_ascii_case(symbol)
+                    && blackout.starts_at <= now
+                    && now < blackout.ends_at
+            })
+            .map(|blackout| SymbolRestrictionViolation::Blackout {
+                ends_at: blackout.ends_at,
+            });
+        
+        restrictions.chain(blackouts).collect()
+    }
+}
+
+impl RiskRule for SymbolRestrictionRule {
+    fn validate(&self, proposal: &TradeProposal) -> Result<(), RiskViolation> {
+        let Some(violation) = self.violations_at(&proposal.symbol, Utc::now()).into_iter().next() else {
+            return Ok(());
+        };
+        
+        let (message, recommendation) = match violation {
+            SymbolRestrictionViolation::Restricted { reason } => (
+                format!("Symbol {} is restricted: {}", proposal.symbol, reason),
+                "Choose a symbol that is not on your restriction list".to_string(),
+            ),
+            SymbolRestrictionViolation::Blackout { ends_at } => (
+                format!("Symbol {} is in a trading blackout until {}", proposal.symbol, ends_at),
+                format!("Wait until {} to trade {}", ends_at, proposal.symbol),
+            ),
+        };
+        
+        Err(RiskViolation::new(
+            self.rule_name().to_string(),
+            ViolationSeverity::Blocking,
+            message,
+            Decimal::ONE,
+            Decimal::ZERO,
+            recommendation,
+        ))
+    }
+    
+    fn rule_name(&self) -> &str {
+        "SymbolRestriction"
+    }
+    
+    fn priority(&self) -> u8 {
+        0 // Highest priority - the symbol may not be traded at all
+    }
+    
+    fn description(&self) -> &str {
+        "Refuses trades on restricted symbols and during symbol blackouts"
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::{TradeSide};
+    use crate::risk::TestudoProtocol;
+    use crate::types::{ExitStrategy, MissingTakeProfitPolicy, TradeSide};
     use disciplina::{AccountEquity, RiskPercentage, PricePoint};
     use rust_decimal_macros::dec;
     
@@ -454,6 +643,103 @@ mod tests {
         assert_eq!(violation.severity, ViolationSeverity::High);
     }
     
+    #[test]
+    fn test_implausible_reward_risk_ratio_warns() {
+        let limits = ProtocolLimits {
+            max_reward_risk_ratio: Some(dec!(20)),
+            ..ProtocolLimits::default()
+        };
+        let rule = MaxRewardRiskRatioRule::new(limits);
+        let ratio_proposal = |take_profit| TradeProposal::new(
+            "BTCUSDT".to_string(),
+            TradeSide::Long,
+            PricePoint::new(dec!(50000)).unwrap(),
+            PricePoint::new(dec!(49900)).unwrap(), // 100 risk
+            Some(PricePoint::new(take_profit).unwrap()),
+            AccountEquity::new(dec!(10000)).unwrap(),
+            RiskPercentage::new(dec!(0.02)).unwrap(),
+        ).unwrap();
+        
+        // 6000 reward = 60:1, most likely a typo
+        let violation = rule.validate(&ratio_proposal(dec!(56000))).unwrap_err();
+        assert_eq!(violation.rule_name, "MaxRewardRiskRatio");
+        assert_eq!(violation.severity, ViolationSeverity::Warning);
+        assert_eq!(violation.current_value, dec!(60));
+        
+        // 300 reward = 3:1
+        assert!(rule.validate(&ratio_proposal(dec!(50300))).is_ok());
+        
+        // Disabled by default
+        let unbounded = MaxRewardRiskRatioRule::new(ProtocolLimits::default());
+        assert!(unbounded.validate(&ratio_proposal(dec!(56000))).is_ok());
+    }
+    
+    #[test]
+    fn test_missing_take_profit_policy() {
+        let trailing_exit_proposal = TradeProposal::new(
+            "BTCUSDT".to_string(),
+            TradeSide::Long,
+            PricePoint::new(dec!(50000)).unwrap(),
+            PricePoint::new(dec!(48000)).unwrap(),
+            None, // Exit managed by a trailing stop
+            AccountEquity::new(dec!(10000)).unwrap(),
+            RiskPercentage::new(dec!(0.02)).unwrap(),
+        ).unwrap();
+        
+        let allowed = ProtocolLimits {
+            missing_take_profit_policy: MissingTakeProfitPolicy::Allowed,
+            ..ProtocolLimits::default()
+        };
+        assert!(MinRewardRiskRatioRule::new(allowed).validate(&trailing_exit_proposal).is_ok());
+        
+        let blocking = ProtocolLimits {
+            missing_take_profit_policy: MissingTakeProfitPolicy::Blocking,
+            ..ProtocolLimits::default()
+        };
+        let violation = MinRewardRiskRatioRule::new(blocking.clone())
+            .validate(&trailing_exit_proposal)
+            .unwrap_err();
+        assert_eq!(violation.rule_name, "MinRewardRiskRatio");
+        assert_eq!(violation.severity, ViolationSeverity::Blocking);
+        
+        // The policy never affects trades that do set a take profit
+        assert!(MinRewardRiskRatioRule::new(blocking.clone()).validate(&create_test_proposal()).is_ok());
+        
+        // Under the same policy, a user who has opted into trailing exits is not penalised
+        let trailing = ProtocolLimits {
+            exit_strategy: ExitStrategy::Trailing,
+            ..blocking
+        };
+        assert!(MinRewardRiskRatioRule::new(trailing.clone()).validate(&trailing_exit_proposal).is_ok());
+        assert!(TestudoProtocol::with_limits(trailing).validate_trade(&trailing_exit_proposal).is_ok());
+        let violations = TestudoProtocol::with_limits(ProtocolLimits {
+            missing_take_profit_policy: MissingTakeProfitPolicy::Blocking,
+            ..ProtocolLimits::default()
+        })
+        .validate_trade(&trailing_exit_proposal)
+        .unwrap_err();
+        assert!(violations.iter().any(|violation| violation.rule_name == "MissingTakeProfit"));
+    }
+    
+    #[test]
+    fn test_symbol_restriction_rule() {
+        let now = Utc::now();
+        let rule = SymbolRestrictionRule::new()
+            .with_restriction("btcusdt", "Compliance hold")
+            .with_blackout("ETHUSDT", now - chrono::Duration::minutes(5), now + chrono::Duration::minutes(5));
+        
+        let violation = rule.validate(&create_test_proposal()).unwrap_err();
+        assert_eq!(violation.rule_name, "SymbolRestriction");
+        assert_eq!(violation.severity, ViolationSeverity::Blocking);
+        
+        assert_eq!(
+            rule.violations_at("ETHUSDT", now),
+            vec![SymbolRestrictionViolation::Blackout { ends_at: now + chrono::Duration::minutes(5) }]
+        );
+        assert!(rule.violations_at("ETHUSDT", now + chrono::Duration::minutes(10)).is_empty());
+        assert!(rule.violations_at("SOLUSDT", now).is_empty());
+    }
+    
     #[test]
     fn test_stop_loss_direction_rule() {
         let rule = StopLossDirectionRule;
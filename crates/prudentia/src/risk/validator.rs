@@ -207,10 +207,10 @@ impl RiskValidator {
         }
     }
     
-    /// Get remaining risk budget
-    pub fn remaining_risk_budget(&self) -> Result<rust_decimal::Decimal, String> {
+    /// Get remaining risk budget at `account_equity`
+    pub fn remaining_risk_budget(&self, account_equity: rust_decimal::Decimal) -> Result<rust_decimal::Decimal, String> {
         match self.protocol.lock() {
-            Ok(protocol) => Ok(protocol.remaining_risk_budget()),
+            Ok(protocol) => Ok(protocol.remaining_risk_budget(account_equity)),
             Err(e) => {
                 error!("Failed to get risk budget: {}", e);
                 Err("Failed to access protocol state".to_string())
@@ -461,7 +461,7 @@ mod tests {
         let proposal = create_test_proposal(dec!(0.04)); // 4% risk
         
         // Initial budgets
-        let risk_budget = validator.remaining_risk_budget().unwrap();
+        let risk_budget = validator.remaining_risk_budget(dec!(10000)).unwrap();
         let daily_budget = validator.remaining_daily_budget(dec!(10000)).unwrap();
         
         assert_eq!(risk_budget, dec!(0.10)); // 10% max portfolio risk
@@ -471,7 +471,7 @@ mod tests {
         validator.record_trade_execution(&proposal).unwrap();
         
         // Check updated budgets
-        let risk_budget = validator.remaining_risk_budget().unwrap();
+        let risk_budget = validator.remaining_risk_budget(dec!(10000)).unwrap();
         assert_eq!(risk_budget, dec!(0.06)); // 10% - 4% = 6%
         
         // Record a loss to update daily budget
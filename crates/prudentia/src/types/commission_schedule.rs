@@ -0,0 +1,233 @@
+//! Per-exchange commission schedule used by the fee model
+//!
+//! Fees differ by exchange and by the kind of instrument traded (spot vs
+//! futures), and accounts on a VIP tier get discounted rates. The schedule is
+//! loaded once at startup and consulted whenever a trade's fees are estimated.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Kind of instrument a symbol represents, which determines its fee tier
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SymbolType {
+    Spot,
+    Futures,
+}
+
+/// Whether an order adds liquidity (maker) or removes it (taker)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Liquidity {
+    Maker,
+    Taker,
+}
+
+/// Maker and taker fee rates as fractions of notional (0.001 = 0.1%)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FeeRates {
+    pub maker: Decimal,
+    pub taker: Decimal,
+}
+
+impl FeeRates {
+    pub const fn new(maker: Decimal, taker: Decimal) -> Self {
+        Self { maker, taker }
+    }
+
+    /// Rate charged for the given liquidity side
+    pub fn rate(&self, liquidity: Liquidity) -> Decimal {
+        match liquidity {
+            Liquidity::Maker => self.maker,
+            Liquidity::Taker => self.taker,
+        }
+    }
+}
+
+/// Commission rates for a single exchange
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExchangeCommissions {
+    /// VIP tier of the account on this exchange, if any
+    #[serde(default)]
+    pub vip_tier: Option<String>,
+
+    /// Base rates by symbol type
+    #[serde(default)]
+    pub symbol_types: HashMap<SymbolType, FeeRates>,
+
+    /// Discounted rates by VIP tier name, overriding the base rates
+    #[serde(default)]
+    pub vip_tiers: HashMap<String, HashMap<SymbolType, FeeRates>>,
+
+    /// Symbol classification; symbols not listed use the default tier
+    #[serde(default)]
+    pub symbols: HashMap<String, SymbolType>,
+}
+
+impl ExchangeCommissions {
+    /// Symbol type for a symbol, matched case-insensitively
+    ///
+    /// Configuration loaders may lowercase table keys, so `BTC/USDT` and
+    /// `btc/usdt` refer to the same symbol.
+    pub fn symbol_type(&self, symbol: &str) -> Option<SymbolType> {
+        self.symbols.get(symbol).copied().or_else(|| {
+            self.symbols
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(symbol))
+                .map(|(_, symbol_type)| *symbol_type)
+        })
+    }
+
+    fn vip_rates(&self, symbol_type: SymbolType) -> Option<&FeeRates> {
+        let tier = self.vip_tier.as_ref()?;
+        self.vip_tiers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(tier))
+            .and_then(|(_, rates)| rates.get(&symbol_type))
+    }
+}
+
+/// Commission schedule for every configured exchange
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommissionSchedule {
+    /// Rates used for unknown exchanges, symbols or symbol types
+    pub default_tier: FeeRates,
+
+    /// Per-exchange rates keyed by exchange name (e.g. "binance")
+    #[serde(default)]
+    pub exchanges: HashMap<String, ExchangeCommissions>,
+}
+
+impl Default for CommissionSchedule {
+    fn default() -> Self {
+        Self {
+            default_tier: FeeRates::new(dec!(0.001), dec!(0.001)),
+            exchanges: HashMap::new(),
+        }
+    }
+}
+
+/// Estimated fees for a prospective trade
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeePreview {
+    pub exchange: String,
+    pub symbol: String,
+    /// `None` when the symbol is not classified and the default tier applied
+    pub symbol_type: Option<SymbolType>,
+    pub liquidity: Liquidity,
+    pub rate: Decimal,
+    pub notional: Decimal,
+    pub fee: Decimal,
+}
+
+impl CommissionSchedule {
+    /// Effective rates for a symbol on an exchange
+    ///
+    /// Resolution order: the account's VIP tier, then the exchange's base
+    /// rates for the symbol type, then the default tier.
+    pub fn rates_for(&self, exchange: &str, symbol: &str) -> FeeRates {
+        self.resolve(exchange, symbol).1
+    }
+
+    /// Preview the fee for trading `quantity` of `symbol` at `price`
+    pub fn preview(
+        &self,
+        exchange: &str,
+        symbol: &str,
+        quantity: Decimal,
+        price: Decimal,
+        liquidity: Liquidity,
+    ) -> FeePreview {
+        let (symbol_type, rates) = self.resolve(exchange, symbol);
+        let notional = quantity * price;
+        let rate = rates.rate(liquidity);
+
+        FeePreview {
+            exchange: exchange.to_string(),
+            symbol: symbol.to_string(),
+            symbol_type,
+            liquidity,
+            rate,
+            notional,
+            fee: notional * rate,
+        }
+    }
+
+    fn resolve(&self, exchange: &str, symbol: &str) -> (Option<SymbolType>, FeeRates) {
+        let Some(commissions) = self.exchanges.get(exchange) else {
+            return (None, self.default_tier);
+        };
+        let Some(symbol_type) = commissions.symbol_type(symbol) else {
+            return (None, self.default_tier);
+        };
+
+        let rates = commissions
+            .vip_rates(symbol_type)
+            .or_else(|| commissions.symbol_types.get(&symbol_type))
+            .copied()
+            .unwrap_or(self.default_tier);
+
+        (Some(symbol_type), rates)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_schedule() -> CommissionSchedule {
+        let binance = ExchangeCommissions {
+            vip_tier: None,
+            symbol_types: HashMap::from([
+                (SymbolType::Spot, FeeRates::new(dec!(0.001), dec!(0.001))),
+                (SymbolType::Futures, FeeRates::new(dec!(0.0002), dec!(0.0005))),
+            ]),
+            vip_tiers: HashMap::from([(
+                "vip1".to_string(),
+                HashMap::from([(SymbolType::Futures, FeeRates::new(dec!(0.00016), dec!(0.0004)))]),
+            )]),
+            symbols: HashMap::from([
+                ("BTC/USDT".to_string(), SymbolType::Spot),
+                ("BTCUSDT-PERP".to_string(), SymbolType::Futures),
+            ]),
+        };
+
+        CommissionSchedule {
+            default_tier: FeeRates::new(dec!(0.002), dec!(0.0025)),
+            exchanges: HashMap::from([("binance".to_string(), binance)]),
+        }
+    }
+
+    #[test]
+    fn test_preview_uses_taker_fee_for_exchange_and_symbol_type() {
+        let schedule = test_schedule();
+
+        let preview = schedule.preview("binance", "BTCUSDT-PERP", dec!(0.1), dec!(50000), Liquidity::Taker);
+        assert_eq!(preview.symbol_type, Some(SymbolType::Futures));
+        assert_eq!(preview.rate, dec!(0.0005));
+        assert_eq!(preview.fee, dec!(2.5));
+    }
+
+    #[test]
+    fn test_unknown_symbol_falls_back_to_default_tier() {
+        let schedule = test_schedule();
+
+        let preview = schedule.preview("binance", "DOGE/USDT", dec!(1000), dec!(0.1), Liquidity::Taker);
+        assert_eq!(preview.symbol_type, None);
+        assert_eq!(preview.rate, dec!(0.0025));
+
+        assert_eq!(schedule.rates_for("kraken", "BTC/USDT"), schedule.default_tier);
+    }
+
+    #[test]
+    fn test_vip_tier_overrides_base_rates() {
+        let mut schedule = test_schedule();
+        schedule.exchanges.get_mut("binance").unwrap().vip_tier = Some("vip1".to_string());
+
+        assert_eq!(schedule.rates_for("binance", "BTCUSDT-PERP").taker, dec!(0.0004));
+        // Spot has no vip1 override, so the base rate still applies
+        assert_eq!(schedule.rates_for("binance", "BTC/USDT").taker, dec!(0.001));
+    }
+}
@@ -0,0 +1,143 @@
+//! TOML and JSON documents for protocol configuration
+//!
+//! Operators keep risk configurations under version control and diff them
+//! across environments, so limits can be written to and loaded from text.
+//! Loading always validates: a document that parses but holds inconsistent
+//! limits is rejected rather than applied.
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::fmt;
+
+use super::{LimitValidationError, ProtocolLimits};
+
+/// Text format of an exported configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigFormat {
+    #[default]
+    Toml,
+    Json,
+}
+
+impl ConfigFormat {
+    /// Media type a document in this format is served as
+    pub fn media_type(&self) -> &'static str {
+        match self {
+            ConfigFormat::Toml => "application/toml",
+            ConfigFormat::Json => "application/json",
+        }
+    }
+
+    /// Write `value` as a document in this format
+    pub fn serialize<T: Serialize>(&self, value: &T) -> Result<String, ConfigFormatError> {
+        let result = match self {
+            ConfigFormat::Toml => toml::to_string_pretty(value).map_err(|err| err.to_string()),
+            ConfigFormat::Json => serde_json::to_string_pretty(value).map_err(|err| err.to_string()),
+        };
+        result.map_err(|message| ConfigFormatError::Serialize { format: *self, message })
+    }
+
+    /// Read a value from a document in this format
+    pub fn deserialize<T: DeserializeOwned>(&self, document: &str) -> Result<T, ConfigFormatError> {
+        let result = match self {
+            ConfigFormat::Toml => toml::from_str(document).map_err(|err| err.to_string()),
+            ConfigFormat::Json => serde_json::from_str(document).map_err(|err| err.to_string()),
+        };
+        result.map_err(|message| ConfigFormatError::Parse { format: *self, message })
+    }
+}
+
+impl fmt::Display for ConfigFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigFormat::Toml => write!(f, "TOML"),
+            ConfigFormat::Json => write!(f, "JSON"),
+        }
+    }
+}
+
+/// A configuration document that could not be written or loaded
+#[derive(Debug, thiserror::Error, Clone, PartialEq)]
+pub enum ConfigFormatError {
+    #[error("Failed to write {format} configuration: {message}")]
+    Serialize { format: ConfigFormat, message: String },
+
+    #[error("Invalid {format} configuration: {message}")]
+    Parse { format: ConfigFormat, message: String },
+
+    #[error("Configuration has {} invalid limit(s)", .0.len())]
+    Invalid(Vec<LimitValidationError>),
+}
+
+impl ProtocolLimits {
+    /// Write the limits as a document in `format`
+    pub fn export(&self, format: ConfigFormat) -> Result<String, ConfigFormatError> {
+        format.serialize(self)
+    }
+
+    /// Load limits from a document in `format`, validating them as a whole
+    pub fn load(format: ConfigFormat, document: &str) -> Result<Self, ConfigFormatError> {
+        let limits: Self = format.deserialize(document)?;
+        limits.validate().map_err(ConfigFormatError::Invalid)?;
+        Ok(limits)
+    }
+
+    pub fn to_toml(&self) -> Result<String, ConfigFormatError> {
+        self.export(ConfigFormat::Toml)
+    }
+
+    pub fn to_json(&self) -> Result<String, ConfigFormatError> {
+        self.export(ConfigFormat::Json)
+    }
+
+    pub fn from_toml(document: &str) -> Result<Self, ConfigFormatError> {
+        Self::load(ConfigFormat::Toml, document)
+    }
+
+    pub fn from_json(document: &str) -> Result<Self, ConfigFormatError> {
+        Self::load(ConfigFormat::Json, document)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{CircuitBreakerScope, MissingTakeProfitPolicy};
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_custom_limits_round_trip_through_toml() {
+        let limits = ProtocolLimits {
+            max_individual_trade_risk: dec!(0.03),
+            max_trade_loss_amount: Some(dec!(2500)),
+            max_total_portfolio_risk: dec!(0.08),
+            circuit_breaker_scope: CircuitBreakerScope::PerSymbol,
+            max_reward_risk_ratio: Some(dec!(12)),
+            missing_take_profit_policy: MissingTakeProfitPolicy::Warning,
+            strict_max_open_positions: true,
+            max_daily_loss_with_open_risk: Some(dec!(0.04)),
+            ..ProtocolLimits::conservative_limits()
+        };
+
+        let document = limits.to_toml().unwrap();
+        assert!(document.contains("max_trade_loss_amount = \"2500\""));
+        assert_eq!(ProtocolLimits::from_toml(&document).unwrap(), limits);
+        assert_eq!(ProtocolLimits::from_json(&limits.to_json().unwrap()).unwrap(), limits);
+
+        // A document that parses but breaks the limits is not loaded
+        let inconsistent = document.replace(
+            "max_total_portfolio_risk = \"0.08\"",
+            "max_total_portfolio_risk = \"0.02\"",
+        );
+        match ProtocolLimits::from_toml(&inconsistent) {
+            Err(ConfigFormatError::Invalid(errors)) => {
+                assert!(errors.iter().any(|error| error.field == "max_individual_trade_risk"));
+            }
+            other => panic!("expected validation errors, got {:?}", other),
+        }
+        assert!(matches!(
+            ProtocolLimits::from_toml("max_individual_trade_risk = "),
+            Err(ConfigFormatError::Parse { format: ConfigFormat::Toml, .. })
+        ));
+    }
+}
@@ -8,8 +8,16 @@ pub mod trade_proposal;
 pub mod risk_assessment;
 pub mod protocol_limits;
 pub mod risk_profile;
+pub mod commission_schedule;
+pub mod config_format;
 
 pub use trade_proposal::{TradeProposal, TradeSide};
-pub use risk_assessment::{RiskAssessment, ApprovalStatus, ProtocolViolation, ViolationSeverity};
-pub use protocol_limits::ProtocolLimits;
-pub use risk_profile::RiskProfile;
\ No newline at end of file
+pub use risk_assessment::{
+    RiskAssessment, ApprovalStatus, ProtocolViolation, SuggestedAction, ViolationSeverity,
+};
+pub use protocol_limits::{ProtocolLimits, CircuitBreakerScope, LimitValidationError, MissingTakeProfitPolicy, ExitStrategy, StressTest};
+pub use risk_profile::RiskProfile;
+pub use commission_schedule::{
+    CommissionSchedule, ExchangeCommissions, FeePreview, FeeRates, Liquidity, SymbolType,
+};
+pub use config_format::{ConfigFormat, ConfigFormatError};
@@ -8,6 +8,8 @@ use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 use serde::{Deserialize, Serialize};
 
+use super::ViolationSeverity;
+
 /// Core Testudo Protocol limits (IMMUTABLE)
 ///
 /// These limits are designed to prevent account blowups and enforce disciplined
@@ -23,29 +25,188 @@ pub struct ProtocolLimits {
     /// This ensures trades are meaningful and not overly conservative
     pub min_individual_trade_risk: Decimal,
     
+    /// Maximum loss per individual trade in quote currency (default: disabled)
+    /// On large accounts a small percentage is still a large sum; trades risking more are blocked
+    #[serde(default)]
+    pub max_trade_loss_amount: Option<Decimal>,
+    
+    /// Minimum position notional in quote currency (default: disabled)
+    /// A nearly spent budget or a very wide stop can size a dust position not worth its fees; smaller trades are rejected
+    #[serde(default)]
+    pub min_position_notional: Option<Decimal>,
+    
+    /// Fraction of the per-trade hard caps actually enforced (default: disabled, i.e. 100%)
+    /// Lot rounding can push a trade's real risk slightly above its computed risk; enforcing 0.95 of the caps keeps the true caps unbreached
+    #[serde(default)]
+    pub hard_limit_buffer: Option<Decimal>,
+    
     /// Maximum total portfolio risk across all open positions (default: 10%)
     /// This prevents overexposure from multiple correlated positions
     pub max_total_portfolio_risk: Decimal,
     
+    /// Maximum total portfolio risk in quote currency (default: disabled)
+    /// A fixed cap that does not grow with equity; the stricter of this and the percentage applies
+    #[serde(default)]
+    pub max_total_portfolio_risk_amount: Option<Decimal>,
+    
     /// Maximum number of consecutive losing trades before circuit breaker (default: 3)
     /// This protects against emotional revenge trading and system failures
     pub max_consecutive_losses: u32,
     
+    /// Scope of the consecutive-loss circuit breaker (default: account-wide)
+    /// Per-symbol scope halts only the instrument with the losing streak
+    #[serde(default)]
+    pub circuit_breaker_scope: CircuitBreakerScope,
+    
+    /// Risk cap for the single trial trade allowed once the breaker cools down (default: disabled)
+    /// When set, the account-wide breaker goes half-open instead of closing; a losing trial re-opens it
+    #[serde(default)]
+    pub circuit_breaker_trial_risk: Option<Decimal>,
+    
     /// Minimum reward-to-risk ratio for trades (default: 2.0)
     /// This ensures trades have positive expected value over time
     pub min_reward_risk_ratio: Decimal,
     
+    /// Reward-to-risk ratio above which a target is flagged as implausible (default: disabled)
+    /// Usually a mistyped take profit; the trade is warned, not blocked, so the trader can confirm
+    #[serde(default)]
+    pub max_reward_risk_ratio: Option<Decimal>,
+    
+    /// How a trade without a take profit is treated (default: allowed)
+    /// Trailing-stop traders exit without a fixed target, so stricter desks opt in
+    #[serde(default)]
+    pub missing_take_profit_policy: MissingTakeProfitPolicy,
+    
+    /// How the user exits trades (default: fixed targets)
+    /// Trailing-exit users are exempt from the missing take profit policy
+    #[serde(default)]
+    pub exit_strategy: ExitStrategy,
+    
     /// Maximum number of open positions allowed simultaneously (default: 5)
     /// This prevents over-diversification and unmanageable portfolio complexity
     pub max_open_positions: u32,
     
+    /// Treat `max_open_positions` as a hard cap (default: false)
+    /// When set, exceeding the cap is a Blocking violation instead of a warning
+    #[serde(default)]
+    pub strict_max_open_positions: bool,
+    
+    /// Seconds an unfilled entry may stay pending before it counts as open (default: 30)
+    /// Pending entries always count toward portfolio risk; this only affects the position count
+    #[serde(default = "default_pending_entry_grace_period_secs")]
+    pub pending_entry_grace_period_secs: u64,
+    
+    /// Maximum number of unfilled entries allowed at once (default: disabled)
+    /// When set, pending entries count only against this cap and never as open positions
+    #[serde(default)]
+    pub max_pending_orders: Option<u32>,
+    
+    /// Seconds a position must be held before it may be closed (default: 0, disabled)
+    /// Discourages scalping churn; stop-loss exits always fire regardless
+    #[serde(default)]
+    pub min_hold_time_secs: u64,
+    
     /// Maximum daily loss limit as percentage of account (default: 5%)
     /// This provides daily circuit breaker protection
     pub max_daily_loss: Decimal,
     
+    /// Maximum daily loss in quote currency (default: disabled)
+    /// A fixed cap that does not grow with equity; the stricter of this and the percentage applies
+    #[serde(default)]
+    pub max_daily_loss_amount: Option<Decimal>,
+    
+    /// Maximum realized daily loss plus open-position risk as percentage of account (default: disabled)
+    /// This prevents carrying open risk that would blow the daily budget if stopped out
+    #[serde(default)]
+    pub max_daily_loss_with_open_risk: Option<Decimal>,
+    
+    /// Maximum share of the remaining daily loss budget a single trade may risk (default: disabled)
+    /// This forces the day's budget to be spread over several trades
+    #[serde(default)]
+    pub max_trade_share_of_remaining_daily_budget: Option<Decimal>,
+    
+    /// Fraction of the daily loss budget that triggers a cool-down warning (default: 80%)
+    /// This gives traders a chance to stop before the hard daily limit
+    #[serde(default = "default_daily_loss_warning_threshold")]
+    pub daily_loss_warning_threshold: Decimal,
+    
     /// Maximum drawdown before trading halt (default: 10%)
     /// This prevents deep portfolio drawdowns
     pub max_drawdown: Decimal,
+    
+    /// Pre-trade stress test of every position stopping out at once (default: disabled)
+    /// Stops gap in fast markets, so the stressed loss can exceed the linear portfolio risk
+    #[serde(default)]
+    pub stress_test: Option<StressTest>,
+}
+
+/// Adverse scenario checked before each trade
+///
+/// Every open position and the new trade are assumed to hit their stops
+/// together, each filling `stop_overshoot` (a fraction of its stop distance)
+/// beyond the stop. Today's realized loss is added, and the trade is blocked
+/// if the total exceeds `max_loss` of account equity.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StressTest {
+    /// Largest stressed loss allowed, as a fraction of account equity
+    pub max_loss: Decimal,
+    /// Fill beyond each stop, as a fraction of the stop distance (0.5 = 50% worse)
+    #[serde(default)]
+    pub stop_overshoot: Decimal,
+}
+
+/// Scope at which consecutive losses trip the circuit breaker
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum CircuitBreakerScope {
+    /// Losses on any symbol count toward a single account-wide breaker
+    #[default]
+    AccountWide,
+    /// Each symbol has its own breaker; other symbols keep trading
+    PerSymbol,
+}
+
+/// Severity of the violation raised for a trade without a take profit
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum MissingTakeProfitPolicy {
+    /// Reject the trade outright
+    Blocking,
+    /// Require the trade to be modified before execution
+    High,
+    /// Let the trade proceed with a warning
+    Warning,
+    /// Accept the trade; the reward/risk check is skipped
+    #[default]
+    Allowed,
+}
+
+impl MissingTakeProfitPolicy {
+    /// Violation severity for a missing take profit, or `None` when allowed
+    pub const fn severity(&self) -> Option<ViolationSeverity> {
+        match self {
+            MissingTakeProfitPolicy::Blocking => Some(ViolationSeverity::Blocking),
+            MissingTakeProfitPolicy::High => Some(ViolationSeverity::High),
+            MissingTakeProfitPolicy::Warning => Some(ViolationSeverity::Warning),
+            MissingTakeProfitPolicy::Allowed => None,
+        }
+    }
+}
+
+/// How a user closes trades, which decides whether a take profit is expected
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum ExitStrategy {
+    /// Trades exit at a fixed take profit target
+    #[default]
+    Fixed,
+    /// Trades exit on a trailing stop and have no fixed target
+    Trailing,
+}
+
+fn default_daily_loss_warning_threshold() -> Decimal {
+    dec!(0.80)
+}
+
+fn default_pending_entry_grace_period_secs() -> u64 {
+    30
 }
 
 impl ProtocolLimits {
@@ -58,12 +219,30 @@ impl ProtocolLimits {
         ProtocolLimits {
             max_individual_trade_risk: dec!(0.06),    // 6%
             min_individual_trade_risk: dec!(0.005),   // 0.5%
+            max_trade_loss_amount: None,
+            min_position_notional: None,
+            hard_limit_buffer: None,
             max_total_portfolio_risk: dec!(0.10),     // 10%
+            max_total_portfolio_risk_amount: None,
             max_consecutive_losses: 3,
+            circuit_breaker_scope: CircuitBreakerScope::AccountWide,
+            circuit_breaker_trial_risk: None,
             min_reward_risk_ratio: dec!(2.0),         // 2:1 minimum
+            max_reward_risk_ratio: None,
+            missing_take_profit_policy: MissingTakeProfitPolicy::Allowed,
+            exit_strategy: ExitStrategy::Fixed,
             max_open_positions: 5,
+            strict_max_open_positions: false,
+            pending_entry_grace_period_secs: 30,
+            max_pending_orders: None,
+            min_hold_time_secs: 0,
             max_daily_loss: dec!(0.05),               // 5%
+            max_daily_loss_amount: None,
+            max_daily_loss_with_open_risk: None,
+            max_trade_share_of_remaining_daily_budget: None,
+            daily_loss_warning_threshold: dec!(0.80), // Warn at 80% of daily budget
             max_drawdown: dec!(0.10),                 // 10%
+            stress_test: None,
         }
     }
     
@@ -75,12 +254,30 @@ impl ProtocolLimits {
         ProtocolLimits {
             max_individual_trade_risk: dec!(0.02),    // 2% (reduced from 6%)
             min_individual_trade_risk: dec!(0.005),   // 0.5%
+            max_trade_loss_amount: None,
+            min_position_notional: None,
+            hard_limit_buffer: None,
             max_total_portfolio_risk: dec!(0.05),     // 5% (reduced from 10%)
+            max_total_portfolio_risk_amount: None,
             max_consecutive_losses: 2,                // Lower tolerance
+            circuit_breaker_scope: CircuitBreakerScope::AccountWide,
+            circuit_breaker_trial_risk: None,
             min_reward_risk_ratio: dec!(3.0),         // Higher requirement
+            max_reward_risk_ratio: None,
+            missing_take_profit_policy: MissingTakeProfitPolicy::Allowed,
+            exit_strategy: ExitStrategy::Fixed,
             max_open_positions: 3,                    // Fewer positions
+            strict_max_open_positions: false,
+            pending_entry_grace_period_secs: 30,
+            max_pending_orders: None,
+            min_hold_time_secs: 0,
             max_daily_loss: dec!(0.02),               // 2% (reduced from 5%)
+            max_daily_loss_amount: None,
+            max_daily_loss_with_open_risk: None,
+            max_trade_share_of_remaining_daily_budget: None,
+            daily_loss_warning_threshold: dec!(0.80), // Warn at 80% of daily budget
             max_drawdown: dec!(0.05),                 // 5% (reduced from 10%)
+            stress_test: None,
         }
     }
     
@@ -93,21 +290,159 @@ impl ProtocolLimits {
         ProtocolLimits {
             max_individual_trade_risk: dec!(0.10),    // 10% (increased from 6%)
             min_individual_trade_risk: dec!(0.01),    // 1%
+            max_trade_loss_amount: None,
+            min_position_notional: None,
+            hard_limit_buffer: None,
             max_total_portfolio_risk: dec!(0.15),     // 15% (increased from 10%)
+            max_total_portfolio_risk_amount: None,
             max_consecutive_losses: 5,                // Higher tolerance
+            circuit_breaker_scope: CircuitBreakerScope::AccountWide,
+            circuit_breaker_trial_risk: None,
             min_reward_risk_ratio: dec!(1.5),         // Lower requirement
+            max_reward_risk_ratio: None,
+            missing_take_profit_policy: MissingTakeProfitPolicy::Allowed,
+            exit_strategy: ExitStrategy::Fixed,
             max_open_positions: 8,                    // More positions allowed
+            strict_max_open_positions: false,
+            pending_entry_grace_period_secs: 30,
+            max_pending_orders: None,
+            min_hold_time_secs: 0,
             max_daily_loss: dec!(0.08),               // 8% (increased from 5%)
+            max_daily_loss_amount: None,
+            max_daily_loss_with_open_risk: None,
+            max_trade_share_of_remaining_daily_budget: None,
+            daily_loss_warning_threshold: dec!(0.80), // Warn at 80% of daily budget
             max_drawdown: dec!(0.15),                 // 15% (increased from 10%)
+            stress_test: None,
+        }
+    }
+    
+    /// Enable or disable the strict open positions hard cap
+    ///
+    /// Strict desks use this to hard-reject trades beyond `max_open_positions`
+    /// rather than allowing them with a warning.
+    pub const fn with_strict_max_open_positions(mut self, strict: bool) -> ProtocolLimits {
+        self.strict_max_open_positions = strict;
+        self
+    }
+    
+    /// Check the limits are in range and consistent with each other
+    ///
+    /// Every problem is reported, each against the field to correct, so a
+    /// settings form can flag them all at once.
+    pub fn validate(&self) -> Result<(), Vec<LimitValidationError>> {
+        let mut errors = Vec::new();
+        let mut reject = |field: &str, reason: String| {
+            errors.push(LimitValidationError { field: field.to_string(), reason });
+        };
+        
+        for (field, value) in [
+            ("max_individual_trade_risk", self.max_individual_trade_risk),
+            ("min_individual_trade_risk", self.min_individual_trade_risk),
+            ("max_total_portfolio_risk", self.max_total_portfolio_risk),
+            ("max_daily_loss", self.max_daily_loss),
+            ("daily_loss_warning_threshold", self.daily_loss_warning_threshold),
+            ("max_drawdown", self.max_drawdown),
+        ] {
+            if value <= Decimal::ZERO || value > Decimal::ONE {
+                reject(field, format!("must be a fraction in (0, 1], got {}", value));
+            }
+        }
+        for (field, value) in [
+            ("max_daily_loss_with_open_risk", self.max_daily_loss_with_open_risk),
+            ("max_trade_share_of_remaining_daily_budget", self.max_trade_share_of_remaining_daily_budget),
+            ("hard_limit_buffer", self.hard_limit_buffer),
+        ] {
+            if let Some(value) = value.filter(|v| *v <= Decimal::ZERO || *v > Decimal::ONE) {
+                reject(field, format!("must be a fraction in (0, 1], got {}", value));
+            }
+        }
+        for (field, value) in [
+            ("max_trade_loss_amount", self.max_trade_loss_amount),
+            ("min_position_notional", self.min_position_notional),
+            ("max_total_portfolio_risk_amount", self.max_total_portfolio_risk_amount),
+            ("max_daily_loss_amount", self.max_daily_loss_amount),
+        ] {
+            if let Some(value) = value.filter(|v| *v <= Decimal::ZERO) {
+                reject(field, format!("must be positive, got {}", value));
+            }
+        }
+        
+        if self.min_individual_trade_risk > self.max_individual_trade_risk {
+            reject("min_individual_trade_risk", format!(
+                "{} exceeds max_individual_trade_risk {}",
+                self.min_individual_trade_risk, self.max_individual_trade_risk
+            ));
+        }
+        if self.max_individual_trade_risk > self.max_total_portfolio_risk {
+            reject("max_individual_trade_risk", format!(
+                "{} exceeds max_total_portfolio_risk {}",
+                self.max_individual_trade_risk, self.max_total_portfolio_risk
+            ));
+        }
+        if self.max_consecutive_losses == 0 {
+            reject("max_consecutive_losses", "must be at least 1".to_string());
+        }
+        if self.max_open_positions == 0 {
+            reject("max_open_positions", "must be at least 1".to_string());
+        }
+        if let Some(stress_test) = self.stress_test {
+            if stress_test.max_loss <= Decimal::ZERO || stress_test.max_loss > Decimal::ONE {
+                reject("stress_test.max_loss", format!("must be a fraction in (0, 1], got {}", stress_test.max_loss));
+            }
+            if stress_test.stop_overshoot < Decimal::ZERO {
+                reject("stress_test.stop_overshoot", format!("must not be negative, got {}", stress_test.stop_overshoot));
+            }
+        }
+        if let Some(trial_risk) = self.circuit_breaker_trial_risk {
+            if trial_risk <= Decimal::ZERO || trial_risk > self.max_individual_trade_risk {
+                reject("circuit_breaker_trial_risk", format!(
+                    "{} must be positive and at most max_individual_trade_risk {}",
+                    trial_risk, self.max_individual_trade_risk
+                ));
+            }
+        }
+        if self.max_pending_orders == Some(0) {
+            reject("max_pending_orders", "must be at least 1".to_string());
+        }
+        if self.min_reward_risk_ratio < Decimal::ZERO {
+            reject("min_reward_risk_ratio", format!("must not be negative, got {}", self.min_reward_risk_ratio));
+        }
+        if let Some(ceiling) = self.max_reward_risk_ratio.filter(|c| *c <= self.min_reward_risk_ratio) {
+            reject("max_reward_risk_ratio", format!(
+                "{} must exceed min_reward_risk_ratio {}",
+                ceiling, self.min_reward_risk_ratio
+            ));
+        }
+        
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
         }
     }
     
+    /// Per-trade risk limit trades are checked against, after the hard limit buffer
+    pub fn enforced_max_individual_trade_risk(&self) -> Decimal {
+        self.apply_hard_limit_buffer(self.max_individual_trade_risk)
+    }
+    
+    /// Per-trade loss cap trades are checked against, after the hard limit buffer
+    pub fn enforced_max_trade_loss_amount(&self) -> Option<Decimal> {
+        self.max_trade_loss_amount.map(|limit| self.apply_hard_limit_buffer(limit))
+    }
+    
+    fn apply_hard_limit_buffer(&self, hard_cap: Decimal) -> Decimal {
+        self.hard_limit_buffer.map_or(hard_cap, |buffer| hard_cap * buffer)
+    }
+    
     /// Validate that a risk percentage complies with individual trade limits
     pub fn validate_individual_trade_risk(&self, risk_percentage: Decimal) -> Result<(), ProtocolLimitViolation> {
-        if risk_percentage > self.max_individual_trade_risk {
+        let max_risk = self.enforced_max_individual_trade_risk();
+        if risk_percentage > max_risk {
             return Err(ProtocolLimitViolation::ExceedsMaxIndividualRisk {
                 current: risk_percentage,
-                limit: self.max_individual_trade_risk,
+                limit: max_risk,
             });
         }
         
@@ -121,6 +456,39 @@ impl ProtocolLimits {
         Ok(())
     }
     
+    /// Severity of the violation for a trade without a take profit, if any
+    ///
+    /// Users on trailing exits have no fixed target by design, so only
+    /// fixed-exit users are held to the missing take profit policy.
+    pub const fn missing_take_profit_severity(&self) -> Option<ViolationSeverity> {
+        match self.exit_strategy {
+            ExitStrategy::Trailing => None,
+            ExitStrategy::Fixed => self.missing_take_profit_policy.severity(),
+        }
+    }
+    
+    /// Validate that a trade's potential loss, in quote currency, is within the absolute cap
+    pub fn validate_trade_loss_amount(&self, potential_loss: Decimal) -> Result<(), ProtocolLimitViolation> {
+        match self.enforced_max_trade_loss_amount() {
+            Some(limit) if potential_loss > limit => Err(ProtocolLimitViolation::ExceedsMaxTradeLossAmount {
+                current: potential_loss,
+                limit,
+            }),
+            _ => Ok(()),
+        }
+    }
+    
+    /// Validate that a trade's position notional, in quote currency, meets the floor
+    pub fn validate_position_notional(&self, notional: Decimal) -> Result<(), ProtocolLimitViolation> {
+        match self.min_position_notional {
+            Some(limit) if notional < limit => Err(ProtocolLimitViolation::BelowMinPositionNotional {
+                current: notional,
+                limit,
+            }),
+            _ => Ok(()),
+        }
+    }
+    
     /// Validate that portfolio risk complies with total portfolio limits
     pub fn validate_portfolio_risk(&self, total_risk: Decimal) -> Result<(), ProtocolLimitViolation> {
         if total_risk > self.max_total_portfolio_risk {
@@ -133,6 +501,29 @@ impl ProtocolLimits {
         Ok(())
     }
     
+    /// Portfolio risk limit as a fraction of `account_equity`
+    ///
+    /// The stricter of the percentage limit and the absolute quote-currency
+    /// cap, when one is set.
+    pub fn effective_max_portfolio_risk(&self, account_equity: Decimal) -> Decimal {
+        Self::stricter_limit(self.max_total_portfolio_risk, self.max_total_portfolio_risk_amount, account_equity)
+    }
+    
+    /// Daily loss limit as a fraction of `account_equity`
+    ///
+    /// The stricter of the percentage limit and the absolute quote-currency
+    /// cap, when one is set.
+    pub fn effective_max_daily_loss(&self, account_equity: Decimal) -> Decimal {
+        Self::stricter_limit(self.max_daily_loss, self.max_daily_loss_amount, account_equity)
+    }
+    
+    fn stricter_limit(percentage: Decimal, amount: Option<Decimal>, account_equity: Decimal) -> Decimal {
+        match amount {
+            Some(amount) if account_equity > Decimal::ZERO => percentage.min(amount / account_equity),
+            _ => percentage,
+        }
+    }
+    
     /// Validate that consecutive losses don't exceed circuit breaker threshold
     pub fn validate_consecutive_losses(&self, consecutive_losses: u32) -> Result<(), ProtocolLimitViolation> {
         if consecutive_losses >= self.max_consecutive_losses {
@@ -173,6 +564,12 @@ pub enum ProtocolLimitViolation {
     #[error("Individual trade risk {current} below minimum limit {limit}")]
     BelowMinIndividualRisk { current: Decimal, limit: Decimal },
     
+    #[error("Potential trade loss {current} exceeds maximum amount {limit}")]
+    ExceedsMaxTradeLossAmount { current: Decimal, limit: Decimal },
+    
+    #[error("Position notional {current} below minimum amount {limit}")]
+    BelowMinPositionNotional { current: Decimal, limit: Decimal },
+    
     #[error("Total portfolio risk {current} exceeds maximum limit {limit}")]
     ExceedsMaxPortfolioRisk { current: Decimal, limit: Decimal },
     
@@ -192,6 +589,15 @@ pub enum ProtocolLimitViolation {
     ExceedsMaxDrawdown { current: Decimal, limit: Decimal },
 }
 
+/// A protocol limit that is out of range or inconsistent with another
+#[derive(Debug, thiserror::Error, Clone, PartialEq, Serialize, Deserialize)]
+#[error("{field}: {reason}")]
+pub struct LimitValidationError {
+    /// Name of the offending `ProtocolLimits` field
+    pub field: String,
+    pub reason: String,
+}
+
 /// Trait for types that can be validated against protocol limits
 pub trait ProtocolCompliant {
     /// Validate this item against the given protocol limits
@@ -261,6 +667,42 @@ mod tests {
         }
     }
     
+    #[test]
+    fn test_hard_limit_buffer_absorbs_lot_rounding() {
+        let limits = ProtocolLimits {
+            hard_limit_buffer: Some(dec!(0.95)),
+            ..ProtocolLimits::conservative_limits()
+        };
+        assert!(limits.validate().is_ok());
+        assert_eq!(limits.enforced_max_individual_trade_risk(), dec!(0.019));
+        assert!(matches!(
+            limits.validate_individual_trade_risk(dec!(0.02)),
+            Err(ProtocolLimitViolation::ExceedsMaxIndividualRisk { limit, .. }) if limit == dec!(0.019)
+        ));
+        
+        // Sized right at the enforced limit: $190 over a $1500 stop is 0.12666... BTC
+        let (equity, stop_distance, lot_step) = (dec!(10000), dec!(1500), dec!(0.001));
+        let risk_percentage = limits.enforced_max_individual_trade_risk();
+        assert!(limits.validate_individual_trade_risk(risk_percentage).is_ok());
+        let size = equity * risk_percentage / stop_distance;
+        
+        // Rounding up to the lot step adds risk, but stays under the true 2% cap
+        let rounded = (size / lot_step).ceil() * lot_step;
+        assert_eq!(rounded, dec!(0.127));
+        let actual_risk = rounded * stop_distance / equity;
+        assert!(actual_risk > risk_percentage);
+        assert!(actual_risk <= limits.max_individual_trade_risk);
+        
+        // Without the buffer the same rounding breaches the cap
+        let unbuffered = ProtocolLimits::conservative_limits();
+        let size = equity * unbuffered.enforced_max_individual_trade_risk() / stop_distance;
+        let rounded = (size / lot_step).ceil() * lot_step;
+        assert!(rounded * stop_distance / equity > unbuffered.max_individual_trade_risk);
+        
+        let invalid = ProtocolLimits { hard_limit_buffer: Some(dec!(1.05)), ..limits };
+        assert!(invalid.validate().unwrap_err().iter().any(|error| error.field == "hard_limit_buffer"));
+    }
+    
     #[test]
     fn test_portfolio_risk_validation() {
         let limits = ProtocolLimits::default();
@@ -299,6 +741,25 @@ mod tests {
         }
     }
     
+    #[test]
+    fn test_limit_validation_reports_inconsistent_fields() {
+        for limits in [
+            ProtocolLimits::default_limits(),
+            ProtocolLimits::conservative_limits(),
+            ProtocolLimits::aggressive_limits(),
+        ] {
+            assert_eq!(limits.validate(), Ok(()));
+        }
+        
+        let limits = ProtocolLimits {
+            max_individual_trade_risk: dec!(0.12),
+            max_open_positions: 0,
+            ..ProtocolLimits::default()
+        };
+        let fields: Vec<String> = limits.validate().unwrap_err().into_iter().map(|e| e.field).collect();
+        assert_eq!(fields, ["max_individual_trade_risk", "max_open_positions"]);
+    }
+    
     #[test]
     fn test_reward_risk_ratio_validation() {
         let limits = ProtocolLimits::default();
@@ -3,6 +3,7 @@
 //! This module defines the structures that represent the outcome of
 //! risk validation performed on trade proposals.
 
+use chrono::{DateTime, Utc};
 use disciplina::PositionSize;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
@@ -66,6 +67,26 @@ pub struct ProtocolViolation {
     
     /// Suggested corrective action
     pub suggested_action: String,
+    
+    /// Machine-readable form of the suggested action, when the rule can compute one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hint: Option<SuggestedAction>,
+}
+
+/// Structured remediation for a violation, for one-click fixes in the UI
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SuggestedAction {
+    /// Resize the trade to this position size
+    ReducePositionSize { to: Decimal },
+    /// Close this many open positions to free risk budget
+    ClosePositions { count: u32 },
+    /// Move the take-profit to this price
+    SetTakeProfit { to: Decimal },
+    /// Wait until this time before trading again
+    WaitUntil { time: DateTime<Utc> },
+    /// Stop trading for the rest of the day
+    StopTradingToday,
 }
 
 /// Severity levels for protocol violations
@@ -197,9 +218,16 @@ impl ProtocolViolation {
             current_value,
             limit_value,
             suggested_action,
+            hint: None,
         }
     }
     
+    /// Attach a structured form of the suggested action
+    pub fn with_hint(mut self, hint: SuggestedAction) -> Self {
+        self.hint = Some(hint);
+        self
+    }
+    
     /// Calculate how much the current value exceeds the limit
     pub fn excess_amount(&self) -> Decimal {
         if self.current_value > self.limit_value {
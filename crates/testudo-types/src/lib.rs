@@ -30,6 +30,10 @@ pub struct TradeOrder {
     pub price: Option<Decimal>,  // None for market orders
     pub stop_price: Option<Decimal>,
     pub client_order_id: String,
+    /// Quote amount to spend instead of `quantity`, on venues advertising
+    /// `quote_orders`; `quantity` then holds the expected base fill
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quote_quantity: Option<Decimal>,
 }
 
 /// Order side (buy/sell)
@@ -63,6 +67,17 @@ pub struct OrderResult {
     pub timestamp: SystemTime,
 }
 
+/// Requested change to the legs of an existing OCO (bracket) order
+///
+/// `None` leaves the corresponding leg unchanged.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OcoModification {
+    pub order_id: String,
+    pub symbol: String,
+    pub stop_price: Option<Decimal>,
+    pub take_profit_price: Option<Decimal>,
+}
+
 /// Order status
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum OrderStatus {
@@ -83,6 +98,20 @@ pub struct AccountBalance {
     pub total: Decimal,
 }
 
+/// Optional order and market features an exchange adapter supports
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExchangeCapabilities {
+    /// One-cancels-other bracket orders
+    pub oco: bool,
+    /// Iceberg orders that show only part of their quantity
+    pub iceberg: bool,
+    /// Futures and perpetual contracts
+    pub futures: bool,
+    /// Market orders sized by the quote amount to spend ("buy $500 of BTC")
+    #[serde(default)]
+    pub quote_orders: bool,
+}
+
 /// Exchange adapter errors
 #[derive(Debug, Error, Clone)]
 pub enum ExchangeError {
@@ -120,6 +149,9 @@ pub trait ExchangeAdapterTrait: Send + Sync {
     /// Place a trade order on the exchange
     async fn place_order(&self, order: &TradeOrder) -> Result<OrderResult, ExchangeError>;
     
+    /// Update the stop and/or take-profit leg of an existing OCO order
+    async fn modify_oco_order(&self, modification: &OcoModification) -> Result<OrderResult, ExchangeError>;
+    
     /// Cancel an existing order
     async fn cancel_order(&self, order_id: &str) -> Result<(), ExchangeError>;
     
@@ -140,4 +172,44 @@ pub trait ExchangeAdapterTrait: Send + Sync {
     
     /// Check if a trading pair is supported
     async fn is_symbol_supported(&self, symbol: &str) -> Result<bool, ExchangeError>;
+    
+    /// Optional features this adapter supports (none unless overridden)
+    fn capabilities(&self) -> ExchangeCapabilities {
+        ExchangeCapabilities::default()
+    }
+    
+    /// Smallest distance a stop trigger may sit from the current price, as a
+    /// fraction of that price (0.005 = 0.5%); `None` when unrestricted
+    async fn min_stop_distance(&self, _symbol: &str) -> Result<Option<Decimal>, ExchangeError> {
+        Ok(None)
+    }
+    
+    /// Recent average true range of a symbol, in price units; `None` when
+    /// the venue provides no candle history to derive it from
+    async fn average_true_range(&self, _symbol: &str) -> Result<Option<Decimal>, ExchangeError> {
+        Ok(None)
+    }
+}
+
+/// Closest stop an exchange with a `min_distance` trigger rule accepts for
+/// a position on `side` priced at `reference_price`
+pub fn min_valid_stop(reference_price: Decimal, side: OrderSide, min_distance: Decimal) -> Decimal {
+    match side {
+        OrderSide::Buy => reference_price * (Decimal::ONE - min_distance),
+        OrderSide::Sell => reference_price * (Decimal::ONE + min_distance),
+    }
+}
+
+/// Whether `stop_loss` sits closer to `reference_price` than `min_distance` allows
+pub fn stop_too_close(
+    reference_price: Decimal,
+    stop_loss: Decimal,
+    side: OrderSide,
+    min_distance: Decimal,
+) -> bool {
+    let closest = min_valid_stop(reference_price, side, min_distance);
+    match side {
+        OrderSide::Buy => stop_loss > closest,
+        OrderSide::Sell => stop_loss < closest,
+    }
 }
\ No newline at end of file
@@ -0,0 +1,21 @@
+-- Testudo Trading Platform - Protocol State Persistence
+--
+-- Snapshot of each user's Testudo Protocol state, written in the same
+-- transaction as the trade that changed it so the two never diverge.
+
+CREATE TABLE protocol_state (
+    user_id UUID PRIMARY KEY REFERENCES user_accounts(id) ON DELETE CASCADE,
+
+    total_portfolio_risk DECIMAL(8,6) NOT NULL DEFAULT 0,
+    open_positions INTEGER NOT NULL DEFAULT 0,
+    consecutive_losses INTEGER NOT NULL DEFAULT 0,
+    daily_loss DECIMAL(18,8) NOT NULL DEFAULT 0,
+    circuit_breaker_active BOOLEAN NOT NULL DEFAULT FALSE,
+
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT valid_open_positions CHECK (open_positions >= 0),
+    CONSTRAINT valid_consecutive_losses CHECK (consecutive_losses >= 0)
+);
+
+COMMENT ON TABLE protocol_state IS 'Per-user Testudo Protocol state, updated atomically with trade recording';